@@ -52,6 +52,7 @@ use state_processing::{
     },
 };
 use std::borrow::Cow;
+use std::time::Duration;
 use strum::AsRefStr;
 use tree_hash::TreeHash;
 use types::{
@@ -453,11 +454,19 @@ impl<'a, T: BeaconChainTypes> IndexedAggregatedAttestation<'a, T> {
     ) -> Result<Hash256, Error> {
         let attestation = &signed_aggregate.message.aggregate;
 
+        // TODO(electra): reject Electra aggregates which fail
+        // `AttestationElectra::gossip_committee_index`.
+
         // Ensure attestation is within the last ATTESTATION_PROPAGATION_SLOT_RANGE slots (within a
         // MAXIMUM_GOSSIP_CLOCK_DISPARITY allowance).
         //
         // We do not queue future attestations for later processing.
-        verify_propagation_slot_range(&chain.slot_clock, attestation, &chain.spec)?;
+        verify_propagation_slot_range(
+            &chain.slot_clock,
+            attestation,
+            &chain.spec,
+            chain.config.maximum_gossip_clock_disparity(&chain.spec),
+        )?;
 
         // Check the attestation's epoch matches its target.
         if attestation.data.slot.epoch(T::EthSpec::slots_per_epoch())
@@ -717,6 +726,8 @@ impl<'a, T: BeaconChainTypes> IndexedUnaggregatedAttestation<'a, T> {
         attestation: &Attestation<T::EthSpec>,
         chain: &BeaconChain<T>,
     ) -> Result<(), Error> {
+        // TODO(electra): reject Electra attestations which fail
+        // `AttestationElectra::gossip_committee_index`.
         let attestation_epoch = attestation.data.slot.epoch(T::EthSpec::slots_per_epoch());
 
         // Check the attestation's epoch matches its target.
@@ -731,7 +742,12 @@ impl<'a, T: BeaconChainTypes> IndexedUnaggregatedAttestation<'a, T> {
         // MAXIMUM_GOSSIP_CLOCK_DISPARITY allowance).
         //
         // We do not queue future attestations for later processing.
-        verify_propagation_slot_range(&chain.slot_clock, attestation, &chain.spec)?;
+        verify_propagation_slot_range(
+            &chain.slot_clock,
+            attestation,
+            &chain.spec,
+            chain.config.maximum_gossip_clock_disparity(&chain.spec),
+        )?;
 
         // Check to ensure that the attestation is "unaggregated". I.e., it has exactly one
         // aggregation bit set.
@@ -1042,15 +1058,16 @@ fn verify_head_block_is_known<T: BeaconChainTypes>(
 /// Verify that the `attestation` is within the acceptable gossip propagation range, with reference
 /// to the current slot of the `chain`.
 ///
-/// Accounts for `MAXIMUM_GOSSIP_CLOCK_DISPARITY`.
+/// Accounts for `maximum_gossip_clock_disparity`.
 pub fn verify_propagation_slot_range<S: SlotClock, E: EthSpec>(
     slot_clock: &S,
     attestation: &Attestation<E>,
     spec: &ChainSpec,
+    maximum_gossip_clock_disparity: Duration,
 ) -> Result<(), Error> {
     let attestation_slot = attestation.data.slot;
     let latest_permissible_slot = slot_clock
-        .now_with_future_tolerance(spec.maximum_gossip_clock_disparity())
+        .now_with_future_tolerance(maximum_gossip_clock_disparity)
         .ok_or(BeaconChainError::UnableToReadSlot)?;
     if attestation_slot > latest_permissible_slot {
         return Err(Error::FutureSlot {
@@ -1061,7 +1078,7 @@ pub fn verify_propagation_slot_range<S: SlotClock, E: EthSpec>(
 
     // Taking advantage of saturating subtraction on `Slot`.
     let one_epoch_prior = slot_clock
-        .now_with_past_tolerance(spec.maximum_gossip_clock_disparity())
+        .now_with_past_tolerance(maximum_gossip_clock_disparity)
         .ok_or(BeaconChainError::UnableToReadSlot)?
         - E::slots_per_epoch();
 
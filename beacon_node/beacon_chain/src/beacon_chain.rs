@@ -7,6 +7,7 @@ use crate::attester_cache::{AttesterCache, AttesterCacheKey};
 use crate::beacon_block_streamer::{BeaconBlockStreamer, CheckCaches};
 use crate::beacon_proposer_cache::compute_proposer_duties_from_head;
 use crate::beacon_proposer_cache::BeaconProposerCache;
+use crate::blob_fee;
 use crate::blob_verification::{GossipBlobError, GossipVerifiedBlob};
 use crate::block_times_cache::BlockTimesCache;
 use crate::block_verification::POS_PANDA_BANNER;
@@ -21,7 +22,8 @@ use crate::block_verification_types::{
 pub use crate::canonical_head::CanonicalHead;
 use crate::chain_config::ChainConfig;
 use crate::data_availability_checker::{
-    Availability, AvailabilityCheckError, AvailableBlock, DataAvailabilityChecker,
+    Availability, AvailabilityCheckError, AvailabilityPendingBlock, AvailableBlock,
+    DataAvailabilityChecker,
 };
 use crate::early_attester_cache::EarlyAttesterCache;
 use crate::errors::{BeaconChainError as Error, BlockProductionError};
@@ -33,6 +35,7 @@ use crate::fork_choice_signal::{ForkChoiceSignalRx, ForkChoiceSignalTx, ForkChoi
 use crate::graffiti_calculator::GraffitiCalculator;
 use crate::head_tracker::{HeadTracker, HeadTrackerReader, SszHeadTracker};
 use crate::historical_blocks::HistoricalBlockError;
+use crate::kzg_commitments_proof_cache::KzgCommitmentsProofCache;
 use crate::light_client_finality_update_verification::{
     Error as LightClientFinalityUpdateError, VerifiedLightClientFinalityUpdate,
 };
@@ -40,6 +43,7 @@ use crate::light_client_optimistic_update_verification::{
     Error as LightClientOptimisticUpdateError, VerifiedLightClientOptimisticUpdate,
 };
 use crate::light_client_server_cache::LightClientServerCache;
+use crate::metered_lock::MeteredMutex;
 use crate::migrate::BackgroundMigrator;
 use crate::naive_aggregation_pool::{
     AggregatedAttestationMap, Error as NaiveAggregationError, NaiveAggregationPool,
@@ -59,6 +63,7 @@ use crate::persisted_beacon_chain::{PersistedBeaconChain, DUMMY_CANONICAL_HEAD_B
 use crate::persisted_fork_choice::PersistedForkChoice;
 use crate::pre_finalization_cache::PreFinalizationBlockCache;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
+use crate::slot_tasks::SlotTaskScheduler;
 use crate::sync_committee_verification::{
     Error as SyncCommitteeError, VerifiedSyncCommitteeMessage, VerifiedSyncContribution,
 };
@@ -119,6 +124,7 @@ use store::{
 };
 use task_executor::{ShutdownReason, TaskExecutor};
 use tokio_stream::Stream;
+use tracing::{info_span, Instrument};
 use tree_hash::TreeHash;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::payload::BlockProductionVersion;
@@ -184,9 +190,11 @@ pub enum WhenSlotSkipped {
     Prev,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum AvailabilityProcessingStatus {
-    MissingComponents(Slot, Hash256),
+    /// The block is waiting in the availability cache for its blobs. The handle resolves when it
+    /// is imported or dropped.
+    MissingComponents(AvailabilityPendingBlock),
     Imported(Hash256),
 }
 
@@ -466,7 +474,7 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     /// A cache of eth1 deposit data at epoch boundaries for deposit finalization
     pub eth1_finalization_cache: TimeoutRwLock<Eth1FinalizationCache>,
     /// Caches the beacon block proposer shuffling for a given epoch and shuffling key root.
-    pub beacon_proposer_cache: Arc<Mutex<BeaconProposerCache>>,
+    pub beacon_proposer_cache: Arc<MeteredMutex<BeaconProposerCache>>,
     /// Caches a map of `validator_index -> validator_pubkey`.
     pub(crate) validator_pubkey_cache: TimeoutRwLock<ValidatorPubkeyCache<T>>,
     /// A cache used when producing attestations.
@@ -501,6 +509,12 @@ pub struct BeaconChain<T: BeaconChainTypes> {
     pub data_availability_checker: Arc<DataAvailabilityChecker<T>>,
     /// The KZG trusted setup used by this chain.
     pub kzg: Option<Arc<Kzg>>,
+    /// Bounds the number of blocking threads verifying KZG proofs at once.
+    pub(crate) kzg_verification_permits: tokio::sync::Semaphore,
+    /// Proofs of inclusion of the KZG commitments in the bodies of blocks produced by this node.
+    pub(crate) kzg_commitments_proof_cache: KzgCommitmentsProofCache,
+    /// The work performed at fixed points within each slot.
+    pub slot_tasks: SlotTaskScheduler<T>,
 }
 
 pub enum BeaconBlockResponseWrapper<E: EthSpec> {
@@ -2637,6 +2651,34 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         handle.await.map_err(Error::TokioJoin)
     }
 
+    /// Spawns `task`, which verifies KZG proofs, on a blocking thread once fewer than
+    /// `ChainConfig::kzg_verification_threads` other such tasks are running.
+    ///
+    /// KZG verification is expensive enough that running it on async workers, or on an unbounded
+    /// number of blocking threads, can starve other work during blob-heavy slots.
+    pub async fn spawn_kzg_verification<F, R>(
+        &self,
+        task: F,
+        name: &'static str,
+    ) -> Result<R, Error>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        metrics::inc_gauge(&metrics::KZG_VERIFICATION_QUEUE_LENGTH);
+        let queue_timer = metrics::start_timer(&metrics::KZG_VERIFICATION_QUEUE_TIMES);
+        let permit = self.kzg_verification_permits.acquire().await;
+        drop(queue_timer);
+        metrics::dec_gauge(&metrics::KZG_VERIFICATION_QUEUE_LENGTH);
+        // The semaphore is never closed, but treat it as a shutdown if it is.
+        let _permit = permit.map_err(|_| Error::RuntimeShutdown)?;
+
+        metrics::inc_gauge(&metrics::KZG_VERIFICATION_ACTIVE_TASKS);
+        let result = self.spawn_blocking_handle(task, name).await;
+        metrics::dec_gauge(&metrics::KZG_VERIFICATION_ACTIVE_TASKS);
+        result
+    }
+
     /// Accepts a `chain_segment` and filters out any uninteresting blocks (e.g., pre-finalization
     /// or already-known).
     ///
@@ -2828,9 +2870,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                                 // The block was imported successfully.
                                 imported_blocks += 1;
                             }
-                            AvailabilityProcessingStatus::MissingComponents(slot, block_root) => {
+                            AvailabilityProcessingStatus::MissingComponents(pending) => {
                                 warn!(self.log, "Blobs missing in response to range request";
-                                    "block_root" => ?block_root, "slot" => slot);
+                                    "block_root" => ?pending.block_root(), "slot" => pending.slot());
                                 return ChainSegmentResult::Failed {
                                     imported_blocks,
                                     error: BlockError::AvailabilityCheck(
@@ -2874,10 +2916,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         block: Arc<SignedBeaconBlock<T::EthSpec>>,
     ) -> Result<GossipVerifiedBlock<T>, BlockError<T::EthSpec>> {
         let chain = self.clone();
+        let span = info_span!("block_gossip_verification", slot = %block.slot());
         self.task_executor
             .clone()
             .spawn_blocking_handle(
                 move || {
+                    let _guard = span.enter();
                     let slot = block.slot();
                     let graffiti_string = block.message().body().graffiti().as_utf8_lossy();
 
@@ -2987,7 +3031,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         r: Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>>,
     ) -> Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>> {
         let has_missing_components =
-            matches!(r, Ok(AvailabilityProcessingStatus::MissingComponents(_, _)));
+            matches!(r, Ok(AvailabilityProcessingStatus::MissingComponents(_)));
         if !has_missing_components {
             self.reqresp_pre_import_cache.write().remove(block_root);
         }
@@ -3063,13 +3107,18 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         // A small closure to group the verification and import errors.
         let chain = self.clone();
         let import_block = async move {
-            let execution_pending = unverified_block.into_execution_pending_block(
-                block_root,
-                &chain,
-                notify_execution_layer,
-            )?;
+            let execution_pending = info_span!("block_verification").in_scope(|| {
+                unverified_block.into_execution_pending_block(
+                    block_root,
+                    &chain,
+                    notify_execution_layer,
+                )
+            })?;
             publish_fn()?;
-            let executed_block = chain.into_executed_block(execution_pending).await?;
+            let executed_block = chain
+                .into_executed_block(execution_pending)
+                .instrument(info_span!("block_payload_verification"))
+                .await?;
             // Record the time it took to ask the execution layer.
             if let Some(seen_timestamp) = self.slot_clock.now_duration() {
                 self.block_times_cache.write().set_execution_time(
@@ -3090,7 +3139,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         };
 
         // Verify and import the block.
-        match import_block.await {
+        let span = info_span!(
+            "process_block",
+            block_root = ?block_root,
+            slot = %block_slot,
+            source = %block_source,
+        );
+        match import_block.instrument(span).await {
             // The block was successfully verified and imported. Yay.
             Ok(status @ AvailabilityProcessingStatus::Imported(block_root)) => {
                 debug!(
@@ -3106,15 +3161,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
                 Ok(status)
             }
-            Ok(status @ AvailabilityProcessingStatus::MissingComponents(slot, block_root)) => {
+            Ok(AvailabilityProcessingStatus::MissingComponents(pending)) => {
                 debug!(
                     self.log,
                     "Beacon block awaiting blobs";
-                    "block_root" => ?block_root,
-                    "block_slot" => slot,
+                    "block_root" => ?pending.block_root(),
+                    "block_slot" => pending.slot(),
                 );
 
-                Ok(status)
+                Ok(AvailabilityProcessingStatus::MissingComponents(pending))
             }
             Err(e @ BlockError::BeaconChainError(BeaconChainError::TokioJoin(_))) => {
                 debug!(
@@ -3210,9 +3265,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         block: AvailabilityPendingExecutedBlock<T::EthSpec>,
     ) -> Result<AvailabilityProcessingStatus, BlockError<T::EthSpec>> {
         let slot = block.block.slot();
-        let availability = self
-            .data_availability_checker
-            .put_pending_executed_block(block)?;
+        let availability = info_span!("block_availability").in_scope(|| {
+            self.data_availability_checker
+                .put_pending_executed_block(block)
+        })?;
         self.process_availability(slot, availability).await
     }
 
@@ -3245,7 +3301,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             let mut slashable_cache = self.observed_slashable.write();
             for header in blobs
                 .into_iter()
-                .filter_map(|b| b.as_ref().map(|b| b.signed_block_header.clone()))
+                .filter_map(|b| b.as_ref().map(|b| b.signed_block_header().clone()))
                 .unique()
             {
                 if verify_header_signature::<T, BlockError<T::EthSpec>>(self, &header).is_ok() {
@@ -3262,9 +3318,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 }
             }
         }
+        let data_availability_checker = self.data_availability_checker.clone();
         let availability = self
-            .data_availability_checker
-            .put_rpc_blobs(block_root, blobs)?;
+            .spawn_kzg_verification(
+                move || data_availability_checker.put_rpc_blobs(block_root, blobs),
+                "put_rpc_blobs",
+            )
+            .await??;
 
         self.process_availability(slot, availability).await
     }
@@ -3283,9 +3343,19 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 // Block is fully available, import into fork choice
                 self.import_available_block(block).await
             }
-            Availability::MissingComponents(block_root) => Ok(
-                AvailabilityProcessingStatus::MissingComponents(slot, block_root),
-            ),
+            Availability::MissingComponents(block_root) => {
+                Ok(AvailabilityProcessingStatus::MissingComponents(
+                    self.data_availability_checker.availability_pending_block(
+                        slot,
+                        block_root,
+                        || {
+                            self.canonical_head
+                                .fork_choice_read_lock()
+                                .contains_block(&block_root)
+                        },
+                    ),
+                ))
+            }
         }
     }
 
@@ -3319,9 +3389,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         // import
         let chain = self.clone();
+        let span = info_span!("block_import");
         let block_root = self
             .spawn_blocking_handle(
                 move || {
+                    let _guard = span.enter();
                     chain.import_block(
                         block,
                         block_root,
@@ -3347,6 +3419,8 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         //
         // If `import_block` errors (only errors with internal errors), the pending components will
         // be pruned on data_availability_checker maintenance as finality advances.
+        self.data_availability_checker
+            .notify_block_imported(block_root);
         self.data_availability_checker
             .remove_pending_components(block_root);
 
@@ -3422,6 +3496,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         {
             let _fork_choice_block_timer =
                 metrics::start_timer(&metrics::FORK_CHOICE_PROCESS_BLOCK_TIMES);
+            let _fork_choice_span = info_span!("fork_choice_on_block").entered();
             let block_delay = self
                 .slot_clock
                 .seconds_from_current_slot_start()
@@ -3923,12 +3998,30 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 block.body().attestations().len() as f64,
             );
 
+            if let Ok(commitments) = block.body().blob_kzg_commitments() {
+                metrics::observe(&metrics::BLOBS_PER_BLOCK, commitments.len() as f64);
+            }
+
             if let Ok(sync_aggregate) = block.body().sync_aggregate() {
                 metrics::set_gauge(
                     &metrics::BLOCK_SYNC_AGGREGATE_SET_BITS,
                     sync_aggregate.num_set_bits() as i64,
                 );
             }
+
+            if let Ok(payload) = block.body().execution_payload() {
+                let payload = payload.execution_payload_ref();
+                if let (Ok(blob_gas_used), Ok(excess_blob_gas)) =
+                    (payload.blob_gas_used(), payload.excess_blob_gas())
+                {
+                    metrics::set_gauge(&metrics::BLOCK_BLOB_GAS_USED, blob_gas_used as i64);
+                    metrics::set_gauge(&metrics::BLOCK_EXCESS_BLOB_GAS, excess_blob_gas as i64);
+                    metrics::set_float_gauge(
+                        &metrics::BLOCK_BLOB_BASE_FEE,
+                        blob_fee::wei_to_f64(blob_fee::blob_base_fee(excess_blob_gas)),
+                    );
+                }
+            }
         }
 
         let block_delay_total =
@@ -4971,6 +5064,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 &state,
                 prev_attestation_filter,
                 curr_attestation_filter,
+                Duration::from_millis(self.config.attestation_packing_time_budget_ms),
                 &self.spec,
             )
             .map_err(BlockProductionError::OpPoolError)?;
@@ -5349,6 +5443,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let (mut block, _) = block.deconstruct();
         *block.state_root_mut() = state_root;
 
+        // Compute the commitments' inclusion proof now so it can be reused when the block's blob
+        // sidecars are built on publication.
+        self.kzg_commitments_proof_cache
+            .insert_for_block(block.to_ref())?;
+
         let blobs_verification_timer =
             metrics::start_timer(&metrics::BLOCK_PRODUCTION_BLOBS_VERIFICATION_TIMES);
         let blob_items = match maybe_blobs_and_proofs {
@@ -6461,12 +6560,27 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
+        let current_slot = self.slot()?;
+
+        // Avoid the builder for a while after it revealed blobs which didn't match the block we
+        // signed, regardless of the health of the chain.
+        if let Some(fault_slot) = self
+            .execution_layer
+            .as_ref()
+            .and_then(|execution_layer| execution_layer.last_builder_fault())
+        {
+            let epochs_since_fault = current_slot
+                .epoch(T::EthSpec::slots_per_epoch())
+                .saturating_sub(fault_slot.epoch(T::EthSpec::slots_per_epoch()));
+            if epochs_since_fault < self.config.builder_fault_cooldown_epochs {
+                return Ok(ChainHealth::Unhealthy(FailedCondition::RecentBuilderFault));
+            }
+        }
+
         if self.config.builder_fallback_disable_checks {
             return Ok(ChainHealth::Healthy);
         }
 
-        let current_slot = self.slot()?;
-
         // Check slots at the head of the chain.
         let prev_slot = current_slot.saturating_sub(Slot::new(1));
         let head_skips = prev_slot.saturating_sub(cached_head.head_slot());
@@ -0,0 +1,99 @@
+//! The blob gas parameters and fee calculations of EIP-4844.
+use types::Uint256;
+
+pub const GAS_PER_BLOB: u64 = 1 << 17;
+pub const MIN_BLOB_BASE_FEE: u64 = 1;
+pub const BLOB_BASE_FEE_UPDATE_FRACTION: u64 = 3_338_477;
+
+/// `calc_excess_blob_gas` from EIP-4844, for the child of a block with the given blob gas values.
+///
+/// The target is `ChainSpec::target_blobs_per_block` blobs' worth of gas.
+pub fn next_excess_blob_gas(
+    excess_blob_gas: u64,
+    blob_gas_used: u64,
+    target_blob_gas_per_block: u64,
+) -> u64 {
+    excess_blob_gas
+        .saturating_add(blob_gas_used)
+        .saturating_sub(target_blob_gas_per_block)
+}
+
+/// `get_base_fee_per_blob_gas` from EIP-4844.
+pub fn blob_base_fee(excess_blob_gas: u64) -> Uint256 {
+    fake_exponential(
+        MIN_BLOB_BASE_FEE,
+        excess_blob_gas,
+        BLOB_BASE_FEE_UPDATE_FRACTION,
+    )
+}
+
+/// Convert an amount of wei to an `f64` for use in metrics, saturating at `f64::MAX` for amounts
+/// beyond `u128::MAX`.
+pub fn wei_to_f64(wei: Uint256) -> f64 {
+    if wei > Uint256::from(u128::MAX) {
+        f64::MAX
+    } else {
+        wei.as_u128() as f64
+    }
+}
+
+/// `fake_exponential` from EIP-4844, which approximates `factor * e ** (numerator / denominator)`
+/// using integer arithmetic.
+///
+/// Saturates at `Uint256::MAX` rather than overflowing.
+fn fake_exponential(factor: u64, numerator: u64, denominator: u64) -> Uint256 {
+    let numerator = Uint256::from(numerator);
+    let denominator = Uint256::from(denominator);
+
+    let mut output = Uint256::zero();
+    let mut numerator_accum = Uint256::from(factor) * denominator;
+    let mut i = Uint256::one();
+    while !numerator_accum.is_zero() {
+        let (Some(sum), Some(product)) = (
+            output.checked_add(numerator_accum),
+            numerator_accum.checked_mul(numerator),
+        ) else {
+            return Uint256::MAX;
+        };
+        output = sum;
+        numerator_accum = product / (denominator * i);
+        i += Uint256::one();
+    }
+    output / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_exponential_matches_reference_values() {
+        for (factor, numerator, denominator, expected) in [
+            (1, 0, 1, 1u64),
+            (38493, 0, 1000, 38493),
+            (0, 1234, 2345, 0),
+            (1, 2, 1, 6),
+            (1, 4, 2, 6),
+            (1, 3, 1, 16),
+            (10, 8, 2, 542),
+            (1, 50_000_000, 2_225_652, 5_709_098_764),
+        ] {
+            assert_eq!(
+                fake_exponential(factor, numerator, denominator),
+                Uint256::from(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn excess_blob_gas_tracks_target() {
+        let target = 3 * GAS_PER_BLOB;
+        assert_eq!(next_excess_blob_gas(0, target, target), 0);
+        assert_eq!(
+            next_excess_blob_gas(0, target + GAS_PER_BLOB, target),
+            GAS_PER_BLOB
+        );
+        assert_eq!(next_excess_blob_gas(GAS_PER_BLOB, 0, target), 0);
+        assert_eq!(blob_base_fee(0), Uint256::from(MIN_BLOB_BASE_FEE));
+    }
+}
@@ -7,19 +7,21 @@ use crate::block_verification::{
     cheap_state_advance_to_obtain_committees, get_validator_pubkey_cache, process_block_slash_info,
     BlockSlashInfo,
 };
-use crate::kzg_utils::{validate_blob, validate_blobs};
+use crate::data_availability_checker::AvailabilityCheckError;
+use crate::kzg_utils::{validate_blob, validate_blob_sidecars_batch, KzgBatchError};
 use crate::{metrics, BeaconChainError};
 use kzg::{Error as KzgError, Kzg, KzgCommitment};
-use slog::debug;
+use slog::{debug, warn};
 use ssz_derive::{Decode, Encode};
 use ssz_types::VariableList;
 use std::time::Duration;
+use strum::AsRefStr;
 use tree_hash::TreeHash;
 use types::blob_sidecar::BlobIdentifier;
 use types::{BeaconStateError, BlobSidecar, EthSpec, Hash256, SignedBeaconBlockHeader, Slot};
 
 /// An error occurred while validating a gossip blob.
-#[derive(Debug)]
+#[derive(Debug, AsRefStr)]
 pub enum GossipBlobError<E: EthSpec> {
     /// The blob sidecar is from a slot that is later than the current slot (with respect to the
     /// gossip clock disparity).
@@ -189,7 +191,7 @@ impl<T: BeaconChainTypes> GossipVerifiedBlob<T> {
         subnet_id: u64,
         chain: &BeaconChain<T>,
     ) -> Result<Self, GossipBlobError<T::EthSpec>> {
-        let header = blob.signed_block_header.clone();
+        let header = blob.signed_block_header().clone();
         // We only process slashing info if the gossip verification failed
         // since we do not process the blob any further in that case.
         validate_blob_sidecar_for_gossip(blob, subnet_id, chain).map_err(|e| {
@@ -199,6 +201,57 @@ impl<T: BeaconChainTypes> GossipVerifiedBlob<T> {
             )
         })
     }
+    /// Gossip verify all of the blob sidecars of a single block, checking their KZG proofs in a
+    /// single batch rather than one at a time.
+    ///
+    /// Each sidecar is expected on the subnet matching its index. Unlike `Self::new`, the KZG
+    /// proofs are checked before the other gossip conditions, so this should only be used for
+    /// sidecars which did not arrive from untrusted gossip peers (e.g. locally published blocks).
+    pub fn new_batch(
+        blobs: Vec<Arc<BlobSidecar<T::EthSpec>>>,
+        chain: &BeaconChain<T>,
+    ) -> Result<Vec<Self>, GossipBlobError<T::EthSpec>> {
+        let kzg = chain
+            .kzg
+            .as_ref()
+            .ok_or(GossipBlobError::KzgNotInitialized)?;
+        let sidecars = blobs.iter().map(|blob| blob.as_ref()).collect::<Vec<_>>();
+        if let Err(e) = validate_blob_sidecars_batch(kzg, &sidecars) {
+            return Err(match e {
+                KzgBatchError::InvalidBlob { index, error } => match blobs.get(index) {
+                    Some(blob) => process_block_slash_info::<_, GossipBlobError<T::EthSpec>>(
+                        chain,
+                        BlockSlashInfo::from_early_error_blob(
+                            blob.signed_block_header().clone(),
+                            GossipBlobError::KzgError(error),
+                        ),
+                    ),
+                    None => GossipBlobError::KzgError(error),
+                },
+                KzgBatchError::Batch(error) => GossipBlobError::KzgError(error),
+            });
+        }
+
+        blobs
+            .into_iter()
+            .map(|blob| {
+                let header = blob.signed_block_header().clone();
+                let subnet_id = blob.index;
+                validate_blob_sidecar_for_gossip_inner(
+                    blob,
+                    subnet_id,
+                    chain,
+                    KzgCheck::AlreadyVerified,
+                )
+                .map_err(|e| {
+                    process_block_slash_info::<_, GossipBlobError<T::EthSpec>>(
+                        chain,
+                        BlockSlashInfo::from_early_error_blob(header, e),
+                    )
+                })
+            })
+            .collect()
+    }
     /// Construct a `GossipVerifiedBlob` that is assumed to be valid.
     ///
     /// This should ONLY be used for testing.
@@ -230,7 +283,7 @@ impl<T: BeaconChainTypes> GossipVerifiedBlob<T> {
         self.blob.blob.kzg_commitment
     }
     pub fn signed_block_header(&self) -> SignedBeaconBlockHeader {
-        self.blob.blob.signed_block_header.clone()
+        self.blob.blob.signed_block_header().clone()
     }
     pub fn block_proposer_index(&self) -> u64 {
         self.blob.blob.block_proposer_index()
@@ -333,7 +386,7 @@ impl<E: EthSpec> KzgVerifiedBlobList<E> {
         blob_list: I,
         kzg: &Kzg,
         seen_timestamp: Duration,
-    ) -> Result<Self, KzgError> {
+    ) -> Result<Self, AvailabilityCheckError> {
         let blobs = blob_list.into_iter().collect::<Vec<_>>();
         verify_kzg_for_blob_list(blobs.iter(), kzg)?;
         Ok(Self {
@@ -358,27 +411,53 @@ impl<E: EthSpec> IntoIterator for KzgVerifiedBlobList<E> {
 }
 
 /// Complete kzg verification for a list of `BlobSidecar`s.
-/// Returns an error if any of the `BlobSidecar`s fails kzg verification.
+/// Returns an error if any of the `BlobSidecar`s fails kzg verification, identifying the first
+/// invalid sidecar where possible.
 ///
 /// Note: This function should be preferred over calling `verify_kzg_for_blob`
 /// in a loop since this function kzg verifies a list of blobs more efficiently.
 pub fn verify_kzg_for_blob_list<'a, E: EthSpec, I>(
     blob_iter: I,
     kzg: &'a Kzg,
-) -> Result<(), KzgError>
+) -> Result<(), AvailabilityCheckError>
 where
     I: Iterator<Item = &'a Arc<BlobSidecar<E>>>,
 {
-    let (blobs, (commitments, proofs)): (Vec<_>, (Vec<_>, Vec<_>)) = blob_iter
-        .map(|blob| (&blob.blob, (blob.kzg_commitment, blob.kzg_proof)))
-        .unzip();
-    validate_blobs::<E>(kzg, commitments.as_slice(), blobs, proofs.as_slice())
+    let sidecars = blob_iter.map(|blob| blob.as_ref()).collect::<Vec<_>>();
+    validate_blob_sidecars_batch::<E>(kzg, &sidecars).map_err(|e| match e {
+        KzgBatchError::InvalidBlob { index, error } => match sidecars.get(index) {
+            Some(sidecar) => AvailabilityCheckError::InvalidBlob {
+                block_root: sidecar.block_root(),
+                blob_index: sidecar.index,
+                error,
+            },
+            None => AvailabilityCheckError::Kzg(error),
+        },
+        KzgBatchError::Batch(error) => AvailabilityCheckError::Kzg(error),
+    })
+}
+
+/// Whether the KZG proof of a blob sidecar still needs to be checked during gossip verification.
+#[derive(Clone, Copy, PartialEq)]
+enum KzgCheck {
+    Required,
+    /// The proof has already been verified as part of a batch.
+    AlreadyVerified,
 }
 
 pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes>(
     blob_sidecar: Arc<BlobSidecar<T::EthSpec>>,
     subnet: u64,
     chain: &BeaconChain<T>,
+) -> Result<GossipVerifiedBlob<T>, GossipBlobError<T::EthSpec>> {
+    validate_blob_sidecar_for_gossip_inner(blob_sidecar, subnet, chain, KzgCheck::Required)
+}
+
+fn validate_blob_sidecar_for_gossip_inner<T: BeaconChainTypes>(
+    blob_sidecar: Arc<BlobSidecar<T::EthSpec>>,
+    subnet: u64,
+    chain: &BeaconChain<T>,
+    kzg_check: KzgCheck,
 ) -> Result<GossipVerifiedBlob<T>, GossipBlobError<T::EthSpec>> {
     let blob_slot = blob_sidecar.slot();
     let blob_index = blob_sidecar.index;
@@ -386,15 +465,15 @@ pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes>(
     let blob_proposer_index = blob_sidecar.block_proposer_index();
     let block_root = blob_sidecar.block_root();
     let blob_epoch = blob_slot.epoch(T::EthSpec::slots_per_epoch());
-    let signed_block_header = &blob_sidecar.signed_block_header;
+    let signed_block_header = blob_sidecar.signed_block_header();
 
     let seen_timestamp = chain.slot_clock.now_duration().unwrap_or_default();
 
     // This condition is not possible if we have received the blob from the network
-    // since we only subscribe to `MaxBlobsPerBlock` subnets over gossip network.
+    // since we only subscribe to `max_blobs_per_block` subnets over gossip network.
     // We include this check only for completeness.
     // Getting this error would imply something very wrong with our networking decoding logic.
-    if blob_index >= T::EthSpec::max_blobs_per_block() as u64 {
+    if blob_index >= chain.spec.max_blobs_per_block::<T::EthSpec>() {
         return Err(GossipBlobError::InvalidSubnet {
             expected: subnet,
             received: blob_index,
@@ -412,7 +491,7 @@ pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes>(
     // Verify that the sidecar is not from a future slot.
     let latest_permissible_slot = chain
         .slot_clock
-        .now_with_future_tolerance(chain.spec.maximum_gossip_clock_disparity())
+        .now_with_future_tolerance(chain.config.maximum_gossip_clock_disparity(&chain.spec))
         .ok_or(BeaconChainError::UnableToReadSlot)?;
     if blob_slot > latest_permissible_slot {
         return Err(GossipBlobError::FutureSlot {
@@ -436,18 +515,28 @@ pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes>(
 
     // Verify that this is the first blob sidecar received for the tuple:
     // (block_header.slot, block_header.proposer_index, blob_sidecar.index)
-    if chain
-        .observed_blob_sidecars
-        .read()
-        .proposer_is_known(&blob_sidecar)
-        .map_err(|e| GossipBlobError::BeaconChainError(e.into()))?
-    {
-        return Err(GossipBlobError::RepeatBlob {
-            proposer: blob_proposer_index,
-            slot: blob_slot,
-            index: blob_index,
-        });
-    }
+    //
+    // A repeat of a sidecar for the same block is ignored straight away. A sidecar for a
+    // different block is still ignored, but only after its proposer signature has been checked,
+    // so that a genuine equivocation can be told apart from a forgery.
+    let is_equivocation = {
+        let observed_blob_sidecars = chain.observed_blob_sidecars.read();
+        let is_equivocation = observed_blob_sidecars
+            .is_equivocation(&blob_sidecar)
+            .map_err(|e| GossipBlobError::BeaconChainError(e.into()))?;
+        if !is_equivocation
+            && observed_blob_sidecars
+                .proposer_is_known(&blob_sidecar)
+                .map_err(|e| GossipBlobError::BeaconChainError(e.into()))?
+        {
+            return Err(GossipBlobError::RepeatBlob {
+                proposer: blob_proposer_index,
+                slot: blob_slot,
+                index: blob_index,
+            });
+        }
+        is_equivocation
+    };
 
     // Verify the inclusion proof in the sidecar
     let _timer = metrics::start_timer(&metrics::BLOB_SIDECAR_INCLUSION_PROOF_VERIFICATION);
@@ -560,13 +649,35 @@ pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes>(
         });
     }
 
+    if is_equivocation {
+        chain
+            .observed_slashable
+            .write()
+            .observe_slashable(blob_slot, blob_proposer_index, block_root)
+            .map_err(|e| GossipBlobError::BeaconChainError(e.into()))?;
+        report_blob_proposer_equivocation(chain, proposer_index, blob_slot, blob_index, block_root);
+        return Err(GossipBlobError::RepeatBlob {
+            proposer: blob_proposer_index,
+            slot: blob_slot,
+            index: blob_index,
+        });
+    }
+
     // Kzg verification for gossip blob sidecar
-    let kzg = chain
-        .kzg
-        .as_ref()
-        .ok_or(GossipBlobError::KzgNotInitialized)?;
-    let kzg_verified_blob = KzgVerifiedBlob::new(blob_sidecar.clone(), kzg, seen_timestamp)
-        .map_err(GossipBlobError::KzgError)?;
+    let kzg_verified_blob = match kzg_check {
+        KzgCheck::Required => {
+            let kzg = chain
+                .kzg
+                .as_ref()
+                .ok_or(GossipBlobError::KzgNotInitialized)?;
+            KzgVerifiedBlob::new(blob_sidecar.clone(), kzg, seen_timestamp)
+                .map_err(GossipBlobError::KzgError)?
+        }
+        KzgCheck::AlreadyVerified => KzgVerifiedBlob {
+            blob: blob_sidecar.clone(),
+            seen_timestamp,
+        },
+    };
 
     chain
         .observed_slashable
@@ -589,12 +700,23 @@ pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes>(
     // retrieval of potentially valid blocks over rpc, but try to punish the proposer for signing
     // invalid messages. Issue for more background
     // https://github.com/ethereum/consensus-specs/issues/3261
-    if chain
-        .observed_blob_sidecars
-        .write()
+    let mut observed_blob_sidecars = chain.observed_blob_sidecars.write();
+    if observed_blob_sidecars
         .observe_sidecar(&blob_sidecar)
         .map_err(|e| GossipBlobError::BeaconChainError(e.into()))?
     {
+        if observed_blob_sidecars
+            .is_equivocation(&blob_sidecar)
+            .map_err(|e| GossipBlobError::BeaconChainError(e.into()))?
+        {
+            report_blob_proposer_equivocation(
+                chain,
+                proposer_index,
+                blob_slot,
+                blob_index,
+                block_root,
+            );
+        }
         return Err(GossipBlobError::RepeatBlob {
             proposer: proposer_index as u64,
             slot: blob_slot,
@@ -608,6 +730,26 @@ pub fn validate_blob_sidecar_for_gossip<T: BeaconChainTypes>(
     })
 }
 
+/// Records a signature-verified blob sidecar which conflicts with a previously seen sidecar from
+/// the same proposer, slot and index.
+fn report_blob_proposer_equivocation<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    proposer_index: usize,
+    slot: Slot,
+    index: u64,
+    block_root: Hash256,
+) {
+    metrics::inc_counter(&metrics::BLOB_SIDECAR_PROPOSER_EQUIVOCATIONS);
+    warn!(
+        chain.log,
+        "Proposer equivocation detected via blob sidecar";
+        "proposer" => proposer_index,
+        "slot" => slot,
+        "index" => index,
+        "block_root" => ?block_root,
+    );
+}
+
 /// Returns the canonical root of the given `blob`.
 ///
 /// Use this function to ensure that we report the blob hashing time Prometheus metric.
@@ -73,14 +73,16 @@ pub use fork_choice::{AttestationFromBlock, PayloadVerificationStatus};
 use parking_lot::RwLockReadGuard;
 use proto_array::Block as ProtoBlock;
 use safe_arith::ArithError;
-use slog::{debug, error, warn, Logger};
+use slog::{debug, error, warn, Drain, Logger};
 use slot_clock::SlotClock;
 use ssz::Encode;
 use ssz_derive::{Decode, Encode};
 use ssz_types::VariableList;
 use state_processing::per_block_processing::{errors::IntoWithIndex, is_merge_transition_block};
 use state_processing::{
-    block_signature_verifier::{BlockSignatureVerifier, Error as BlockSignatureVerifierError},
+    block_signature_verifier::{
+        BlockSignatureVerifier, Error as BlockSignatureVerifierError, SignatureOrigin,
+    },
     per_block_processing, per_slot_processing,
     state_advance::partial_state_advance,
     AllCaches, BlockProcessingError, BlockSignatureStrategy, ConsensusContext, SlotProcessingError,
@@ -620,7 +622,19 @@ pub fn signature_verify_chain_segment<T: BeaconChainTypes>(
     }
 
     if signature_verifier.verify().is_err() {
-        return Err(BlockError::InvalidSignature);
+        return Err(invalid_signature_error(
+            || {
+                let origin = signature_verifier.find_invalid();
+                let block_root = origin.and_then(|origin| {
+                    signature_verified_blocks
+                        .iter()
+                        .find(|svb| svb.block.slot() == origin.slot)
+                        .map(|svb| svb.block_root)
+                });
+                (origin, block_root)
+            },
+            chain,
+        ));
     }
 
     drop(pubkey_cache);
@@ -702,18 +716,36 @@ impl<T: BeaconChainTypes> IntoGossipVerifiedBlockContents<T> for PublishBlockReq
 
         let gossip_verified_blobs = blobs
             .map(|(kzg_proofs, blobs)| {
-                let mut gossip_verified_blobs = vec![];
+                // The header and the proof of the commitments' inclusion in the body are the same
+                // for every blob, so only compute them once. The proof of a block produced by this
+                // node was cached during production.
+                let signed_block_header = block.signed_block_header();
+                let kzg_commitments_inclusion_proof = match chain
+                    .kzg_commitments_proof_cache
+                    .get(&signed_block_header.message.body_root)
+                {
+                    Some(proof) => proof,
+                    None => BlobSidecar::kzg_commitments_inclusion_proof(&block)
+                        .map_err(BlockContentsError::SidecarError)?,
+                };
+                let mut blob_sidecars = vec![];
                 for (i, (kzg_proof, blob)) in kzg_proofs.iter().zip(blobs).enumerate() {
                     let _timer =
                         metrics::start_timer(&metrics::BLOB_SIDECAR_INCLUSION_PROOF_COMPUTATION);
-                    let blob = BlobSidecar::new(i, blob, &block, *kzg_proof)
-                        .map_err(BlockContentsError::SidecarError)?;
+                    let blob = BlobSidecar::new_with_existing_proof(
+                        i,
+                        blob,
+                        &block,
+                        signed_block_header.clone(),
+                        &kzg_commitments_inclusion_proof,
+                        *kzg_proof,
+                    )
+                    .map_err(BlockContentsError::SidecarError)?;
                     drop(_timer);
-                    let gossip_verified_blob =
-                        GossipVerifiedBlob::new(Arc::new(blob), i as u64, chain)?;
-                    gossip_verified_blobs.push(gossip_verified_blob);
+                    blob_sidecars.push(Arc::new(blob));
                 }
-                let gossip_verified_blobs = VariableList::from(gossip_verified_blobs);
+                let gossip_verified_blobs =
+                    VariableList::from(GossipVerifiedBlob::new_batch(blob_sidecars, chain)?);
                 Ok::<_, BlockContentsError<T::EthSpec>>(gossip_verified_blobs)
             })
             .transpose()?;
@@ -801,7 +833,7 @@ impl<T: BeaconChainTypes> GossipVerifiedBlock<T> {
         // Do not gossip or process blocks from future slots.
         let present_slot_with_tolerance = chain
             .slot_clock
-            .now_with_future_tolerance(chain.spec.maximum_gossip_clock_disparity())
+            .now_with_future_tolerance(chain.config.maximum_gossip_clock_disparity(&chain.spec))
             .ok_or(BeaconChainError::UnableToReadSlot)?;
         if block.slot() > present_slot_with_tolerance {
             return Err(BlockError::FutureSlot {
@@ -1064,7 +1096,10 @@ impl<T: BeaconChainTypes> SignatureVerifiedBlock<T> {
                 parent: Some(parent),
             })
         } else {
-            Err(BlockError::InvalidSignature)
+            Err(invalid_signature_error(
+                || (signature_verifier.find_invalid(), Some(block_root)),
+                chain,
+            ))
         }
     }
 
@@ -1119,7 +1154,10 @@ impl<T: BeaconChainTypes> SignatureVerifiedBlock<T> {
                 consensus_context,
             })
         } else {
-            Err(BlockError::InvalidSignature)
+            Err(invalid_signature_error(
+                || (signature_verifier.find_invalid(), Some(from.block_root)),
+                chain,
+            ))
         }
     }
 
@@ -1488,6 +1526,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
                         "error" => ?e
                     );
                 }
+                chain.persist_participation_summary(state_root, &summary);
                 summaries.push(summary);
             }
         }
@@ -2080,6 +2119,39 @@ fn get_signature_verifier<'a, T: BeaconChainTypes>(
     BlockSignatureVerifier::new(state, get_pubkey, decompressor, spec)
 }
 
+/// Returns `BlockError::InvalidSignature` after logging which signature was found to be invalid by
+/// `find_invalid`, which should call `BlockSignatureVerifier::find_invalid` and return the origin
+/// of the invalid signature along with the root of the block containing it.
+///
+/// Finding the invalid signature takes several batch verifications and the result is only logged,
+/// so `find_invalid` is only called if debug logging is enabled.
+fn invalid_signature_error<T: BeaconChainTypes>(
+    find_invalid: impl FnOnce() -> (Option<SignatureOrigin>, Option<Hash256>),
+    chain: &BeaconChain<T>,
+) -> BlockError<T::EthSpec> {
+    if !chain.log.is_debug_enabled() {
+        return BlockError::InvalidSignature;
+    }
+
+    let (origin, block_root) = find_invalid();
+    match origin {
+        Some(origin) => debug!(
+            chain.log,
+            "Invalid signature in block";
+            "slot" => origin.slot,
+            "block_root" => ?block_root,
+            "signature" => ?origin.kind,
+        ),
+        // This should not happen, since the same signatures have already failed to verify.
+        None => debug!(
+            chain.log,
+            "Unable to find invalid signature in block";
+            "block_root" => ?block_root,
+        ),
+    }
+    BlockError::InvalidSignature
+}
+
 /// Verify that `header` was signed with a valid signature from its proposer.
 ///
 /// Return `Ok(())` if the signature is valid, and an `Err` otherwise.
@@ -6,13 +6,18 @@ use crate::data_availability_checker::DataAvailabilityChecker;
 use crate::eth1_chain::{CachingEth1Backend, SszEth1};
 use crate::eth1_finalization_cache::Eth1FinalizationCache;
 use crate::fork_choice_signal::ForkChoiceSignalTx;
-use crate::fork_revert::{reset_fork_choice_to_finalization, revert_to_fork_boundary};
+use crate::fork_revert::{
+    check_fork_choice_consistency, reset_fork_choice_to_finalization, revert_to_fork_boundary,
+};
 use crate::graffiti_calculator::{GraffitiCalculator, GraffitiOrigin};
 use crate::head_tracker::HeadTracker;
 use crate::light_client_server_cache::LightClientServerCache;
+use crate::metered_lock::MeteredMutex;
 use crate::migrate::{BackgroundMigrator, MigratorConfig};
+use crate::observed_blob_sidecars::ObservedBlobSidecars;
 use crate::persisted_beacon_chain::PersistedBeaconChain;
 use crate::shuffling_cache::{BlockShufflingIds, ShufflingCache};
+use crate::slot_tasks::SlotTaskScheduler;
 use crate::timeout_rw_lock::TimeoutRwLock;
 use crate::validator_monitor::{ValidatorMonitor, ValidatorMonitorConfig};
 use crate::validator_pubkey_cache::ValidatorPubkeyCache;
@@ -27,10 +32,10 @@ use fork_choice::{ForkChoice, ResetPayloadStatuses};
 use futures::channel::mpsc::Sender;
 use kzg::Kzg;
 use operation_pool::{OperationPool, PersistedOperationPool};
-use parking_lot::{Mutex, RwLock};
+use parking_lot::RwLock;
 use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use slasher::Slasher;
-use slog::{crit, debug, error, info, o, Logger};
+use slog::{crit, debug, error, info, o, warn, Logger};
 use slot_clock::{SlotClock, TestingSlotClock};
 use state_processing::{per_slot_processing, AllCaches};
 use std::marker::PhantomData;
@@ -279,18 +284,35 @@ where
                     .to_string()
             })?;
 
+        let reset_payload_statuses = match self.chain_config.reset_payload_statuses_from {
+            Some(ancestor_root) if !self.chain_config.always_reset_payload_statuses => {
+                ResetPayloadStatuses::FromAncestor(ancestor_root)
+            }
+            _ => ResetPayloadStatuses::always_reset_conditionally(
+                self.chain_config.always_reset_payload_statuses,
+            ),
+        };
         let fork_choice =
             BeaconChain::<Witness<TSlotClock, TEth1Backend, _, _, _>>::load_fork_choice(
                 store.clone(),
-                ResetPayloadStatuses::always_reset_conditionally(
-                    self.chain_config.always_reset_payload_statuses,
-                ),
+                reset_payload_statuses,
                 &self.spec,
                 log,
             )
             .map_err(|e| format!("Unable to load fork choice from disk: {:?}", e))?
             .ok_or("Fork choice not found in store")?;
 
+        // Hot states are stored as diffs against the split state, so keep it in memory.
+        let split = store.get_split_info();
+        if let Some(split_state) = store
+            .get_hot_state(&split.state_root)
+            .map_err(|e| descriptive_db_error("split state", &e))?
+        {
+            store
+                .update_finalized_state(split.state_root, split.block_root, split_state)
+                .map_err(|e| format!("Failed to set split state as finalized state: {:?}", e))?;
+        }
+
         let genesis_block = store
             .get_blinded_block(&chain.genesis_block_root)
             .map_err(|e| descriptive_db_error("genesis block", &e))?
@@ -708,7 +730,10 @@ where
             .ok_or("Cannot build without a genesis state root")?;
         let validator_monitor_config = self.validator_monitor_config.unwrap_or_default();
         let head_tracker = Arc::new(self.head_tracker.unwrap_or_default());
-        let beacon_proposer_cache: Arc<Mutex<BeaconProposerCache>> = <_>::default();
+        let beacon_proposer_cache = Arc::new(MeteredMutex::new(
+            BeaconProposerCache::default(),
+            "beacon_proposer_cache",
+        ));
 
         let mut validator_monitor = ValidatorMonitor::new(
             validator_monitor_config,
@@ -729,11 +754,45 @@ where
             .get_head(current_slot, &self.spec)
             .map_err(|e| format!("Unable to get fork choice head: {:?}", e))?;
 
+        // Check that fork choice is consistent with the database, which it might not be after an
+        // unclean shutdown. If it isn't, revert to the most recent consistent block on the head
+        // chain rather than failing later on with an opaque error.
+        let consistency_check = check_fork_choice_consistency(
+            &fork_choice,
+            initial_head_block_root,
+            current_slot,
+            &store,
+            &self.spec,
+            &log,
+        )?;
+        let consistent_head_block_root = consistency_check.consistent_head_block_root;
+        let inconsistent = consistent_head_block_root != initial_head_block_root
+            || consistency_check.missing_blocks > 0;
+        if inconsistent {
+            warn!(
+                log,
+                "Fork choice is inconsistent with the database";
+                "info" => "reverting fork choice to the most recent consistent head",
+                "head_block_root" => ?initial_head_block_root,
+                "consistent_head_block_root" => ?consistent_head_block_root,
+                "missing_blocks" => consistency_check.missing_blocks,
+            );
+        }
+
         // Try to decode the head block according to the current fork, if that fails, try
         // to backtrack to before the most recent fork.
         let (head_block_root, head_block, head_reverted) =
-            match store.get_full_block(&initial_head_block_root) {
-                Ok(Some(block)) => (initial_head_block_root, block, false),
+            match store.get_full_block(&consistent_head_block_root) {
+                Ok(Some(block)) => {
+                    if inconsistent {
+                        head_tracker.register_block(
+                            consistent_head_block_root,
+                            block.parent_root(),
+                            block.slot(),
+                        );
+                    }
+                    (consistent_head_block_root, block, inconsistent)
+                }
                 Ok(None) => return Err("Head block not found in store".into()),
                 Err(StoreError::SszDecodeError(_)) => {
                     error!(
@@ -746,7 +805,7 @@ where
                     );
                     let (block_root, block) = revert_to_fork_boundary(
                         current_slot,
-                        initial_head_block_root,
+                        consistent_head_block_root,
                         store.clone(),
                         &self.spec,
                         &log,
@@ -764,8 +823,8 @@ where
             .map_err(|e| descriptive_db_error("head state", &e))?
             .ok_or("Head state not found in store")?;
 
-        // If the head reverted then we need to reset fork choice using the new head's finalized
-        // checkpoint.
+        // If the head reverted, or fork choice references blocks which are missing, then we need to
+        // reset fork choice using the new head's finalized checkpoint.
         if head_reverted {
             fork_choice = reset_fork_choice_to_finalization(
                 head_block_root,
@@ -888,6 +947,10 @@ where
             }
         };
 
+        // Read before `chain_config` is moved into the chain.
+        let override_da_boundary_epoch = self.chain_config.override_da_boundary_epoch;
+        let kzg_verification_threads = self.chain_config.kzg_verification_threads;
+
         let beacon_chain = BeaconChain {
             spec: self.spec.clone(),
             config: self.chain_config,
@@ -918,7 +981,7 @@ where
             observed_sync_aggregators: <_>::default(),
             // TODO: allow for persisting and loading the pool from disk.
             observed_block_producers: <_>::default(),
-            observed_blob_sidecars: <_>::default(),
+            observed_blob_sidecars: RwLock::new(ObservedBlobSidecars::new(&self.spec)),
             observed_slashable: <_>::default(),
             observed_voluntary_exits: <_>::default(),
             observed_proposer_slashings: <_>::default(),
@@ -944,7 +1007,10 @@ where
             beacon_proposer_cache,
             block_times_cache: <_>::default(),
             pre_finalization_block_cache: <_>::default(),
-            validator_pubkey_cache: TimeoutRwLock::new(validator_pubkey_cache),
+            validator_pubkey_cache: TimeoutRwLock::new_metered(
+                validator_pubkey_cache,
+                "validator_pubkey_cache",
+            ),
             attester_cache: <_>::default(),
             early_attester_cache: <_>::default(),
             reqresp_pre_import_cache: <_>::default(),
@@ -964,10 +1030,20 @@ where
             validator_monitor: RwLock::new(validator_monitor),
             genesis_backfill_slot,
             data_availability_checker: Arc::new(
-                DataAvailabilityChecker::new(slot_clock, self.kzg.clone(), store, &log, self.spec)
-                    .map_err(|e| format!("Error initializing DataAvailabiltyChecker: {:?}", e))?,
+                DataAvailabilityChecker::new(
+                    slot_clock,
+                    self.kzg.clone(),
+                    override_da_boundary_epoch,
+                    store,
+                    &log,
+                    self.spec,
+                )
+                .map_err(|e| format!("Error initializing DataAvailabiltyChecker: {:?}", e))?,
             ),
             kzg: self.kzg.clone(),
+            kzg_verification_permits: tokio::sync::Semaphore::new(kzg_verification_threads),
+            kzg_commitments_proof_cache: <_>::default(),
+            slot_tasks: SlotTaskScheduler::default(),
         };
 
         let head = beacon_chain.head_snapshot();
@@ -1003,6 +1079,26 @@ where
             }
         }
 
+        // Reload blobs and blocks which were awaiting availability when the node last shut down,
+        // so that they needn't be downloaded again.
+        let finalized_epoch = head.beacon_state.finalized_checkpoint().epoch;
+        match beacon_chain
+            .data_availability_checker
+            .reload_persisted(finalized_epoch + 1)
+        {
+            Ok(0) => (),
+            Ok(reloaded) => debug!(
+                log,
+                "Reloaded pending availability components";
+                "count" => reloaded,
+            ),
+            Err(e) => warn!(
+                log,
+                "Failed to reload pending availability components";
+                "error" => ?e,
+            ),
+        }
+
         info!(
             log,
             "Beacon chain initialized";
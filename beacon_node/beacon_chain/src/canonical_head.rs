@@ -32,6 +32,7 @@
 //! stack.
 
 use crate::beacon_chain::ATTESTATION_CACHE_LOCK_TIMEOUT;
+use crate::metered_lock::LockWaitTimer;
 use crate::persisted_fork_choice::PersistedForkChoice;
 use crate::shuffling_cache::BlockShufflingIds;
 use crate::{
@@ -42,43 +43,46 @@ use crate::{
     validator_monitor::{get_slot_delay_ms, timestamp_now},
     BeaconChain, BeaconChainError as Error, BeaconChainTypes, BeaconSnapshot,
 };
-use eth2::types::{EventKind, SseChainReorg, SseFinalizedCheckpoint, SseHead, SseLateHead};
+use eth2::types::{
+    EventKind, SseChainReorg, SseDutiesReorg, SseFinalizedCheckpoint, SseHead, SseLateHead,
+};
 use fork_choice::{
     ExecutionStatus, ForkChoiceStore, ForkChoiceView, ForkchoiceUpdateParameters, ProtoBlock,
     ResetPayloadStatuses,
 };
 use itertools::process_results;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
-use slog::{crit, debug, error, warn, Logger};
+use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
 use state_processing::AllCaches;
 use std::sync::Arc;
 use std::time::Duration;
-use store::{iter::StateRootsIterator, KeyValueStoreOp, StoreItem};
+use store::{iter::StateRootsIterator, KeyValueStore, KeyValueStoreOp, StoreItem};
 use task_executor::{JoinHandle, ShutdownReason};
+use tracing::info_span;
 use types::*;
 
 /// Simple wrapper around `RwLock` that uses private visibility to prevent any other modules from
 /// accessing the contained lock without it being explicitly noted in this module.
-pub struct CanonicalHeadRwLock<T>(RwLock<T>);
+pub struct CanonicalHeadRwLock<T>(RwLock<T>, LockWaitTimer);
 
 impl<T> From<RwLock<T>> for CanonicalHeadRwLock<T> {
     fn from(rw_lock: RwLock<T>) -> Self {
-        Self(rw_lock)
+        Self(rw_lock, LockWaitTimer::default())
     }
 }
 
 impl<T> CanonicalHeadRwLock<T> {
-    fn new(item: T) -> Self {
-        Self::from(RwLock::new(item))
+    fn new(item: T, lock_name: &str) -> Self {
+        Self(RwLock::new(item), LockWaitTimer::new(lock_name))
     }
 
     fn read(&self) -> RwLockReadGuard<T> {
-        self.0.read()
+        self.1.time_read(|| self.0.read())
     }
 
     fn write(&self) -> RwLockWriteGuard<T> {
-        self.0.write()
+        self.1.time_write(|| self.0.write())
     }
 }
 
@@ -267,8 +271,8 @@ impl<T: BeaconChainTypes> CanonicalHead<T> {
         };
 
         Self {
-            fork_choice: CanonicalHeadRwLock::new(fork_choice),
-            cached_head: CanonicalHeadRwLock::new(cached_head),
+            fork_choice: CanonicalHeadRwLock::new(fork_choice, "fork_choice"),
+            cached_head: CanonicalHeadRwLock::new(cached_head, "cached_head"),
             recompute_head_lock: Mutex::new(()),
         }
     }
@@ -498,9 +502,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let _timer = metrics::start_timer(&metrics::FORK_CHOICE_TIMES);
 
         let chain = self.clone();
+        let span = info_span!("fork_choice_recompute_head", slot = %current_slot);
         match self
             .spawn_blocking_handle(
-                move || chain.recompute_head_at_slot_internal(current_slot),
+                move || {
+                    let _guard = span.enter();
+                    chain.recompute_head_at_slot_internal(current_slot)
+                },
                 "recompute_head_internal",
             )
             .await
@@ -908,6 +916,33 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     execution_optimistic: new_head_is_optimistic,
                 }));
             }
+
+            // Register server-sent-events for any duties which were changed by the reorg.
+            if let Some(event_handler) = self.event_handler.as_ref().filter(|handler| {
+                handler.has_attester_duties_reorg_subscribers()
+                    || handler.has_proposer_duties_reorg_subscribers()
+            }) {
+                match duties_reorg_events(
+                    &old_snapshot.beacon_state,
+                    old_snapshot.beacon_block_root,
+                    &new_snapshot.beacon_state,
+                    new_snapshot.beacon_block_root,
+                    new_head_is_optimistic,
+                ) {
+                    Ok(events) => {
+                        for event in events {
+                            event_handler.register(event);
+                        }
+                    }
+                    Err(e) => {
+                        warn!(
+                            self.log,
+                            "Unable to find dependent roots, cannot register duties reorg events";
+                            "error" => ?e
+                        );
+                    }
+                }
+            }
         }
 
         Ok(())
@@ -1026,11 +1061,68 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     pub fn persist_fork_choice_in_batch_standalone(
         fork_choice: &BeaconForkChoice<T>,
     ) -> KeyValueStoreOp {
-        let persisted_fork_choice = PersistedForkChoice {
+        Self::persisted_fork_choice_standalone(fork_choice).as_kv_store_op(FORK_CHOICE_DB_KEY)
+    }
+
+    /// Return fork choice in the form in which it is written to disk.
+    pub fn persisted_fork_choice(&self) -> PersistedForkChoice {
+        Self::persisted_fork_choice_standalone(&self.canonical_head.fork_choice_read_lock())
+    }
+
+    /// Return fork choice in the form in which it is written to disk, without reference to a
+    /// `BeaconChain`.
+    pub fn persisted_fork_choice_standalone(
+        fork_choice: &BeaconForkChoice<T>,
+    ) -> PersistedForkChoice {
+        PersistedForkChoice {
             fork_choice: fork_choice.to_persisted(),
             fork_choice_store: fork_choice.fc_store().to_persisted(),
-        };
-        persisted_fork_choice.as_kv_store_op(FORK_CHOICE_DB_KEY)
+        }
+    }
+
+    /// Forget the payload statuses of the blocks in fork choice selected by
+    /// `reset_payload_statuses`, then recompute the head.
+    ///
+    /// Blocks which are reset become optimistic, and recomputing the head sends the new head to
+    /// the execution layer to be verified again. This allows recovery from the execution layer
+    /// erroneously marking part of the chain as invalid, without a restart or re-sync.
+    pub async fn reset_payload_statuses(
+        self: &Arc<Self>,
+        reset_payload_statuses: ResetPayloadStatuses,
+    ) -> Result<(), Error> {
+        let fork_choice = self.canonical_head.fork_choice_write_lock();
+
+        if let ResetPayloadStatuses::FromAncestor(ancestor_root) = reset_payload_statuses {
+            if !fork_choice.contains_block(&ancestor_root) {
+                return Err(Error::MissingBeaconBlock(ancestor_root));
+            }
+        }
+
+        // Payload statuses are reset whilst loading fork choice from disk, so write the current
+        // fork choice to disk and then restore from it. The write-lock is held throughout so that
+        // no changes are lost.
+        self.store
+            .hot_db
+            .do_atomically(vec![Self::persist_fork_choice_in_batch_standalone(
+                &fork_choice,
+            )])?;
+        self.canonical_head.restore_from_store(
+            fork_choice,
+            reset_payload_statuses,
+            &self.store,
+            &self.spec,
+            &self.log,
+        )?;
+
+        info!(
+            self.log,
+            "Reset payload statuses";
+            "reset_payload_statuses" => ?reset_payload_statuses,
+        );
+
+        self.recompute_head_at_current_slot().await;
+
+        Ok(())
     }
 }
 
@@ -1308,6 +1400,52 @@ pub fn find_reorg_slot<E: EthSpec>(
         .start_slot(E::slots_per_epoch()))
 }
 
+/// Returns an event for each of the attester duties of the new head's current and next epochs, and
+/// the proposer duties of its current epoch, whose dependent root differs between the old and new
+/// heads.
+///
+/// The dependent root of the proposer duties for an epoch is the block at the last slot of the
+/// prior epoch, whilst that of the attester duties is one epoch earlier again.
+fn duties_reorg_events<E: EthSpec>(
+    old_state: &BeaconState<E>,
+    old_block_root: Hash256,
+    new_state: &BeaconState<E>,
+    new_block_root: Hash256,
+    execution_optimistic: bool,
+) -> Result<Vec<EventKind<E>>, BeaconStateError> {
+    let slot = new_state.slot();
+    let current_epoch = new_state.current_epoch();
+
+    // The roots of the blocks at the last slot prior to `epoch` on the old and new chains.
+    let dependent_roots = |epoch: Epoch| -> Result<(Hash256, Hash256), BeaconStateError> {
+        Ok((
+            old_state.proposer_shuffling_decision_root_at_epoch(epoch, old_block_root)?,
+            new_state.proposer_shuffling_decision_root_at_epoch(epoch, new_block_root)?,
+        ))
+    };
+    let duties_reorg =
+        |epoch: Epoch, (old_dependent_root, new_dependent_root): (Hash256, Hash256)| {
+            (old_dependent_root != new_dependent_root).then_some(SseDutiesReorg {
+                slot,
+                epoch,
+                old_dependent_root,
+                new_dependent_root,
+                execution_optimistic,
+            })
+        };
+
+    let mut events = vec![];
+    for epoch in [current_epoch, current_epoch + 1] {
+        if let Some(reorg) = duties_reorg(epoch, dependent_roots(epoch.saturating_sub(1_u64))?) {
+            events.push(EventKind::AttesterDutiesReorg(reorg));
+        }
+    }
+    if let Some(reorg) = duties_reorg(current_epoch, dependent_roots(current_epoch)?) {
+        events.push(EventKind::ProposerDutiesReorg(reorg));
+    }
+    Ok(events)
+}
+
 fn observe_head_block_delays<E: EthSpec, S: SlotClock>(
     block_times_cache: &mut BlockTimesCache,
     head_block: &ProtoBlock,
@@ -1404,6 +1542,15 @@ fn observe_head_block_delays<E: EthSpec, S: SlotClock>(
                 .as_millis() as i64,
         );
 
+        // The time between the block being observed and it becoming available, i.e. the time spent
+        // waiting on blobs and the execution layer once the block itself was known.
+        if let (Some(observed), Some(available)) = (block_delays.observed, block_delays.available) {
+            metrics::set_gauge(
+                &metrics::BEACON_BLOCK_DELAY_AVAILABLE_OBSERVED,
+                available.saturating_sub(observed).as_millis() as i64,
+            );
+        }
+
         // The time the block became attestable after the start of the slot.
         metrics::set_gauge(
             &metrics::BEACON_BLOCK_DELAY_ATTESTABLE_SLOT_START,
@@ -1,7 +1,7 @@
 pub use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
-use types::{Checkpoint, Epoch};
+use types::{ChainSpec, Checkpoint, Epoch, Hash256};
 
 pub const DEFAULT_RE_ORG_HEAD_THRESHOLD: ReOrgThreshold = ReOrgThreshold(20);
 pub const DEFAULT_RE_ORG_PARENT_THRESHOLD: ReOrgThreshold = ReOrgThreshold(160);
@@ -16,6 +16,17 @@ pub const DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR: u32 = 3;
 /// Fraction of a slot lookahead for fork choice in the state advance timer (500ms on mainnet).
 pub const FORK_CHOICE_LOOKAHEAD_FACTOR: u32 = 24;
 
+/// Default time a gossip block may wait for its blobs before they are requested (4 seconds).
+pub const DEFAULT_PENDING_AVAILABILITY_TIMEOUT_MS: u64 = 4000;
+
+/// Default number of KZG verifications which may run at once.
+pub const DEFAULT_KZG_VERIFICATION_THREADS: usize = 4;
+
+/// Default time spent improving on the greedy packing of attestations into a block.
+///
+/// Off by default, so that block production time is unaffected unless an operator opts in.
+pub const DEFAULT_ATTESTATION_PACKING_TIME_BUDGET_MS: u64 = 0;
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct ChainConfig {
     /// Maximum number of slots to skip when importing an attestation.
@@ -59,11 +70,19 @@ pub struct ChainConfig {
     pub builder_fallback_epochs_since_finalization: usize,
     /// Whether any chain health checks should be considered when deciding whether to use the builder API.
     pub builder_fallback_disable_checks: bool,
+    /// Number of epochs after the builder reveals blobs which don't match the signed commitments
+    /// during which the BN refuses to use connected builders. Zero disables this.
+    pub builder_fault_cooldown_epochs: u64,
     /// When set to `true`, forget any valid/invalid/optimistic statuses in fork choice during start
     /// up.
     pub always_reset_payload_statuses: bool,
+    /// When set, forget the valid/invalid/optimistic statuses of this block and its descendants in
+    /// fork choice during start up.
+    pub reset_payload_statuses_from: Option<Hash256>,
     /// Whether to apply paranoid checks to blocks proposed by this beacon node.
     pub paranoid_block_proposal: bool,
+    /// Force the data availability boundary to this epoch, for testing.
+    pub override_da_boundary_epoch: Option<Epoch>,
     /// Optionally set timeout for calls to checkpoint sync endpoint.
     pub checkpoint_sync_url_timeout: u64,
     /// The offset before the start of a proposal slot at which payload attributes should be sent.
@@ -86,6 +105,29 @@ pub struct ChainConfig {
     pub epochs_per_migration: u64,
     /// When set to true Light client server computes and caches state proofs for serving updates
     pub enable_light_client_server: bool,
+    /// Whether to store a summary of validator participation at every epoch transition.
+    pub store_participation_summaries: bool,
+    /// Whether to request the blobs of gossip blocks from peers as soon as the block has been
+    /// verified, rather than waiting for them to arrive over gossip.
+    pub preemptive_blob_requests: bool,
+    /// Number of milliseconds a gossip block may wait for its blobs before they are requested from
+    /// peers. If that request fails, the block is dropped.
+    ///
+    /// If set to 0 then blocks wait for their blobs until they are pruned from the cache.
+    pub pending_availability_timeout_ms: u64,
+    /// The maximum number of blocking threads used to verify the KZG proofs of blobs at once.
+    /// Further verifications queue until a thread is free.
+    pub kzg_verification_threads: usize,
+    /// Number of milliseconds to spend improving on the greedy packing of attestations during
+    /// block production.
+    ///
+    /// If set to 0 then attestations are packed greedily.
+    pub attestation_packing_time_budget_ms: u64,
+    /// Overrides the spec's `MAXIMUM_GOSSIP_CLOCK_DISPARITY` wherever the node tolerates clock
+    /// skew: gossip propagation checks and the duties endpoints.
+    ///
+    /// Useful for devnets with short slots. If `None`, the spec's value is used.
+    pub maximum_gossip_clock_disparity_millis: Option<u64>,
 }
 
 impl Default for ChainConfig {
@@ -107,8 +149,11 @@ impl Default for ChainConfig {
             builder_fallback_skips_per_epoch: 8,
             builder_fallback_epochs_since_finalization: 3,
             builder_fallback_disable_checks: false,
+            builder_fault_cooldown_epochs: 4,
             always_reset_payload_statuses: false,
+            reset_payload_statuses_from: None,
             paranoid_block_proposal: false,
+            override_da_boundary_epoch: None,
             checkpoint_sync_url_timeout: 60,
             prepare_payload_lookahead: Duration::from_secs(4),
             // This value isn't actually read except in tests.
@@ -118,6 +163,12 @@ impl Default for ChainConfig {
             always_prepare_payload: false,
             epochs_per_migration: crate::migrate::DEFAULT_EPOCHS_PER_MIGRATION,
             enable_light_client_server: false,
+            store_participation_summaries: false,
+            preemptive_blob_requests: false,
+            pending_availability_timeout_ms: DEFAULT_PENDING_AVAILABILITY_TIMEOUT_MS,
+            kzg_verification_threads: DEFAULT_KZG_VERIFICATION_THREADS,
+            attestation_packing_time_budget_ms: DEFAULT_ATTESTATION_PACKING_TIME_BUDGET_MS,
+            maximum_gossip_clock_disparity_millis: None,
         }
     }
 }
@@ -131,4 +182,12 @@ impl ChainConfig {
                 Duration::from_secs(seconds_per_slot) / DEFAULT_RE_ORG_CUTOFF_DENOMINATOR
             })
     }
+
+    /// The clock disparity permitted when comparing gossip messages and duties requests to the
+    /// local slot clock.
+    pub fn maximum_gossip_clock_disparity(&self, spec: &ChainSpec) -> Duration {
+        self.maximum_gossip_clock_disparity_millis
+            .map(Duration::from_millis)
+            .unwrap_or_else(|| spec.maximum_gossip_clock_disparity())
+    }
 }
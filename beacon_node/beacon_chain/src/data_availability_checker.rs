@@ -3,6 +3,8 @@ use crate::block_verification_types::{
     AvailabilityPendingExecutedBlock, AvailableExecutedBlock, RpcBlock,
 };
 use crate::data_availability_checker::overflow_lru_cache::OverflowLRUCache;
+use crate::data_availability_checker::pending_blocks::PendingBlocks;
+use crate::slot_tasks::{SlotTask, SlotTaskPriority};
 use crate::{BeaconChain, BeaconChainTypes, BeaconStore};
 use kzg::Kzg;
 use slog::{debug, error, Logger};
@@ -12,15 +14,16 @@ use std::fmt::Debug;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::Duration;
-use task_executor::TaskExecutor;
 use types::blob_sidecar::{BlobIdentifier, BlobSidecar, FixedBlobSidecarList};
-use types::{BlobSidecarList, ChainSpec, Epoch, EthSpec, Hash256, SignedBeaconBlock};
+use types::{BlobSidecarList, ChainSpec, Epoch, EthSpec, Hash256, SignedBeaconBlock, Slot};
 
 mod error;
 mod overflow_lru_cache;
+mod pending_blocks;
 mod state_lru_cache;
 
 pub use error::{Error as AvailabilityCheckError, ErrorCategory as AvailabilityCheckErrorCategory};
+pub use pending_blocks::{AvailabilityPendingBlock, PendingBlockOutcome};
 use types::non_zero_usize::new_non_zero_usize;
 
 /// The LRU Cache stores `PendingComponents` which can store up to
@@ -39,8 +42,12 @@ pub const STATE_LRU_CAPACITY: usize = STATE_LRU_CAPACITY_NON_ZERO.get();
 /// checking whether a "availability check" is required at all.
 pub struct DataAvailabilityChecker<T: BeaconChainTypes> {
     availability_cache: Arc<OverflowLRUCache<T>>,
+    /// Handles to the blocks in `availability_cache` which are waiting for their blobs.
+    pending_blocks: PendingBlocks,
     slot_clock: T::SlotClock,
     kzg: Option<Arc<Kzg>>,
+    /// Forces the data availability boundary to this epoch, for testing.
+    override_da_boundary_epoch: Option<Epoch>,
     log: Logger,
     spec: ChainSpec,
 }
@@ -70,6 +77,7 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
     pub fn new(
         slot_clock: T::SlotClock,
         kzg: Option<Arc<Kzg>>,
+        override_da_boundary_epoch: Option<Epoch>,
         store: BeaconStore<T>,
         log: &Logger,
         spec: ChainSpec,
@@ -77,9 +85,11 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
         let overflow_cache = OverflowLRUCache::new(OVERFLOW_LRU_CAPACITY, store, spec.clone())?;
         Ok(Self {
             availability_cache: Arc::new(overflow_cache),
+            pending_blocks: PendingBlocks::default(),
             slot_clock,
             log: log.clone(),
             kzg,
+            override_da_boundary_epoch,
             spec,
         })
     }
@@ -134,8 +144,7 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
             .ok_or(AvailabilityCheckError::SlotClockError)?;
 
         let verified_blobs =
-            KzgVerifiedBlobList::new(Vec::from(blobs).into_iter().flatten(), kzg, seen_timestamp)
-                .map_err(AvailabilityCheckError::Kzg)?;
+            KzgVerifiedBlobList::new(Vec::from(blobs).into_iter().flatten(), kzg, seen_timestamp)?;
 
         self.availability_cache
             .put_kzg_verified_blobs(block_root, verified_blobs)
@@ -164,9 +173,45 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
             .put_pending_executed_block(executed_block)
     }
 
+    /// Remove a block and its blobs from the availability cache.
+    ///
+    /// Any handles to the block resolve as `Dropped`, unless it has already been imported.
     pub fn remove_pending_components(&self, block_root: Hash256) {
         self.availability_cache
-            .remove_pending_components(block_root)
+            .remove_pending_components(block_root);
+        self.pending_blocks
+            .resolve(block_root, PendingBlockOutcome::Dropped);
+    }
+
+    /// Get a handle to a block which is waiting in the availability cache for its blobs.
+    ///
+    /// The handle is registered before checking that the block is still in the cache, so that it
+    /// can't miss an import which completes concurrently. If the block has already left the cache,
+    /// the handle is resolved immediately using `is_imported`.
+    pub fn availability_pending_block(
+        &self,
+        slot: Slot,
+        block_root: Hash256,
+        is_imported: impl FnOnce() -> bool,
+    ) -> AvailabilityPendingBlock {
+        let pending = self.pending_blocks.subscribe(slot, block_root);
+        // Components are only removed once the block has been imported into fork choice, or
+        // dropped.
+        if !self.availability_cache.has_pending_components(&block_root) {
+            let outcome = if is_imported() {
+                PendingBlockOutcome::Imported
+            } else {
+                PendingBlockOutcome::Dropped
+            };
+            self.pending_blocks.resolve(block_root, outcome);
+        }
+        pending
+    }
+
+    /// Resolve the handles to a block which has been imported.
+    pub fn notify_block_imported(&self, block_root: Hash256) {
+        self.pending_blocks
+            .resolve(block_root, PendingBlockOutcome::Imported);
     }
 
     /// Verifies kzg commitments for an RpcBlock, returns a `MaybeAvailableBlock` that may
@@ -198,8 +243,7 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
                         .kzg
                         .as_ref()
                         .ok_or(AvailabilityCheckError::KzgNotInitialized)?;
-                    verify_kzg_for_blob_list(blob_list.iter(), kzg)
-                        .map_err(AvailabilityCheckError::Kzg)?;
+                    verify_kzg_for_blob_list(blob_list.iter(), kzg)?;
                     Some(blob_list)
                 } else {
                     None
@@ -287,6 +331,9 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
     /// The epoch at which we require a data availability check in block processing.
     /// `None` if the `Deneb` fork is disabled.
     pub fn data_availability_boundary(&self) -> Option<Epoch> {
+        if let Some(override_epoch) = self.override_da_boundary_epoch {
+            return self.spec.deneb_fork_epoch.map(|_| override_epoch);
+        }
         self.spec.deneb_fork_epoch.and_then(|fork_epoch| {
             self.slot_clock
                 .now()
@@ -334,6 +381,12 @@ impl<T: BeaconChainTypes> DataAvailabilityChecker<T> {
         self.availability_cache.write_all_to_disk()
     }
 
+    /// Move pending components persisted by a previous shutdown back into memory, dropping any
+    /// from before `cutoff_epoch`. Returns the number of entries reloaded.
+    pub fn reload_persisted(&self, cutoff_epoch: Epoch) -> Result<usize, AvailabilityCheckError> {
+        self.availability_cache.reload_from_disk(cutoff_epoch)
+    }
+
     /// Collects metrics from the data availability checker.
     pub fn metrics(&self) -> DataAvailabilityCheckerMetrics {
         DataAvailabilityCheckerMetrics {
@@ -351,17 +404,18 @@ pub struct DataAvailabilityCheckerMetrics {
     pub block_cache_size: usize,
 }
 
-pub fn start_availability_cache_maintenance_service<T: BeaconChainTypes>(
-    executor: TaskExecutor,
-    chain: Arc<BeaconChain<T>>,
-) {
+/// Registers a slot task which prunes the availability cache once per epoch, at the start of the
+/// slot 3/4 of the way through the epoch.
+pub fn register_availability_cache_maintenance_task<T: BeaconChainTypes>(chain: &BeaconChain<T>) {
     // this cache only needs to be maintained if deneb is configured
     if chain.spec.deneb_fork_epoch.is_some() {
-        let overflow_cache = chain.data_availability_checker.availability_cache.clone();
-        executor.spawn(
-            async move { availability_cache_maintenance_service(chain, overflow_cache).await },
-            "availability_cache_service",
-        );
+        chain.slot_tasks.register(SlotTask::new(
+            "availability_cache_maintenance",
+            SlotTaskPriority::Low,
+            Duration::ZERO,
+            chain.slot_clock.slot_duration(),
+            availability_cache_maintenance,
+        ));
     } else {
         debug!(
             chain.log,
@@ -370,69 +424,56 @@ pub fn start_availability_cache_maintenance_service<T: BeaconChainTypes>(
     }
 }
 
-async fn availability_cache_maintenance_service<T: BeaconChainTypes>(
+async fn availability_cache_maintenance<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
-    overflow_cache: Arc<OverflowLRUCache<T>>,
+    current_slot: Slot,
 ) {
-    let epoch_duration = chain.slot_clock.slot_duration() * T::EthSpec::slots_per_epoch() as u32;
-    loop {
-        match chain
-            .slot_clock
-            .duration_to_next_epoch(T::EthSpec::slots_per_epoch())
-        {
-            Some(duration) => {
-                // this service should run 3/4 of the way through the epoch
-                let additional_delay = (epoch_duration * 3) / 4;
-                tokio::time::sleep(duration + additional_delay).await;
-
-                let Some(deneb_fork_epoch) = chain.spec.deneb_fork_epoch else {
-                    // shutdown service if deneb fork epoch not set
-                    break;
-                };
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+    // this task should run 3/4 of the way through the epoch
+    if current_slot % slots_per_epoch != slots_per_epoch * 3 / 4 {
+        return;
+    }
 
-                debug!(
-                    chain.log,
-                    "Availability cache maintenance service firing";
-                );
-                let Some(current_epoch) = chain
-                    .slot_clock
-                    .now()
-                    .map(|slot| slot.epoch(T::EthSpec::slots_per_epoch()))
-                else {
-                    continue;
-                };
+    let Some(deneb_fork_epoch) = chain.spec.deneb_fork_epoch else {
+        return;
+    };
 
-                if current_epoch < deneb_fork_epoch {
-                    // we are not in deneb yet
-                    continue;
-                }
+    debug!(
+        chain.log,
+        "Availability cache maintenance service firing";
+    );
+    let current_epoch = current_slot.epoch(slots_per_epoch);
 
-                let finalized_epoch = chain
-                    .canonical_head
-                    .fork_choice_read_lock()
-                    .finalized_checkpoint()
-                    .epoch;
-                // any data belonging to an epoch before this should be pruned
-                let cutoff_epoch = std::cmp::max(
-                    finalized_epoch + 1,
-                    std::cmp::max(
-                        current_epoch
-                            .saturating_sub(chain.spec.min_epochs_for_blob_sidecars_requests),
-                        deneb_fork_epoch,
-                    ),
-                );
+    if current_epoch < deneb_fork_epoch {
+        // we are not in deneb yet
+        return;
+    }
 
-                if let Err(e) = overflow_cache.do_maintenance(cutoff_epoch) {
-                    error!(chain.log, "Failed to maintain availability cache"; "error" => ?e);
-                }
-            }
-            None => {
-                error!(chain.log, "Failed to read slot clock");
-                // If we can't read the slot clock, just wait another slot.
-                tokio::time::sleep(chain.slot_clock.slot_duration()).await;
-            }
-        };
+    let finalized_epoch = chain
+        .canonical_head
+        .fork_choice_read_lock()
+        .finalized_checkpoint()
+        .epoch;
+    // any data belonging to an epoch before this should be pruned
+    let cutoff_epoch = std::cmp::max(
+        finalized_epoch + 1,
+        std::cmp::max(
+            current_epoch.saturating_sub(chain.spec.min_epochs_for_blob_sidecars_requests),
+            deneb_fork_epoch,
+        ),
+    );
+
+    if let Err(e) = chain
+        .data_availability_checker
+        .availability_cache
+        .do_maintenance(cutoff_epoch)
+    {
+        error!(chain.log, "Failed to maintain availability cache"; "error" => ?e);
     }
+    chain
+        .data_availability_checker
+        .pending_blocks
+        .prune(cutoff_epoch.start_slot(slots_per_epoch));
 }
 
 /// A fully available block that is ready to be imported into fork choice.
@@ -6,6 +6,12 @@ pub enum Error {
     Kzg(KzgError),
     KzgNotInitialized,
     KzgVerificationFailed,
+    /// A blob failed kzg verification as part of a batch.
+    InvalidBlob {
+        block_root: Hash256,
+        blob_index: u64,
+        error: KzgError,
+    },
     KzgCommitmentMismatch {
         blob_commitment: KzgCommitment,
         block_commitment: KzgCommitment,
@@ -44,6 +50,7 @@ impl Error {
             | Error::RebuildingStateCaches(_)
             | Error::SlotClockError => ErrorCategory::Internal,
             Error::Kzg(_)
+            | Error::InvalidBlob { .. }
             | Error::BlobIndexInvalid(_)
             | Error::KzgCommitmentMismatch { .. }
             | Error::KzgVerificationFailed => ErrorCategory::Malicious,
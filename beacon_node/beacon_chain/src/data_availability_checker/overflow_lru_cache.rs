@@ -7,7 +7,7 @@
 //!
 //! The main object in this module is the `OverflowLruCache`. It contains two locks:
 //!
-//! - `self.critical` is an `RwLock` that protects content stored in memory.
+//! - `self.critical` is a `MeteredRwLock` that protects content stored in memory.
 //! - `self.maintenance_lock` is held when moving data between memory and disk.
 //!
 //! You mostly need to ensure that you don't try to hold the critical lock more than once
@@ -34,10 +34,11 @@ use crate::block_verification_types::{
     AvailabilityPendingExecutedBlock, AvailableBlock, AvailableExecutedBlock,
 };
 use crate::data_availability_checker::{Availability, AvailabilityCheckError};
+use crate::metered_lock::MeteredRwLock;
 use crate::store::{DBColumn, KeyValueStore};
 use crate::BeaconChainTypes;
 use lru::LruCache;
-use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
+use parking_lot::{Mutex, RwLockUpgradableReadGuard};
 use ssz::{Decode, Encode};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{FixedVector, VariableList};
@@ -280,8 +281,9 @@ impl OverflowKey {
 
     pub fn from_blob_id<E: EthSpec>(
         blob_id: BlobIdentifier,
+        spec: &ChainSpec,
     ) -> Result<Self, AvailabilityCheckError> {
-        if blob_id.index > E::max_blobs_per_block() as u64 || blob_id.index > u8::MAX as u64 {
+        if blob_id.index > spec.max_blobs_per_block::<E>() || blob_id.index > u8::MAX as u64 {
             return Err(AvailabilityCheckError::BlobIndexInvalid(blob_id.index));
         }
         Ok(Self::Blob(blob_id.block_root, blob_id.index as u8))
@@ -320,10 +322,13 @@ impl<T: BeaconChainTypes> OverflowStore<T> {
             .into_iter()
             .flatten()
         {
-            let key = OverflowKey::from_blob_id::<T::EthSpec>(BlobIdentifier {
-                block_root,
-                index: blob.blob_index(),
-            })?;
+            let key = OverflowKey::from_blob_id::<T::EthSpec>(
+                BlobIdentifier {
+                    block_root,
+                    index: blob.blob_index(),
+                },
+                self.0.get_chain_spec(),
+            )?;
 
             self.0
                 .hot_db
@@ -384,7 +389,7 @@ impl<T: BeaconChainTypes> OverflowStore<T> {
         &self,
         blob_id: &BlobIdentifier,
     ) -> Result<Option<Arc<BlobSidecar<T::EthSpec>>>, AvailabilityCheckError> {
-        let key = OverflowKey::from_blob_id::<T::EthSpec>(*blob_id)?;
+        let key = OverflowKey::from_blob_id::<T::EthSpec>(*blob_id, self.0.get_chain_spec())?;
 
         self.0
             .hot_db
@@ -513,6 +518,11 @@ impl<T: BeaconChainTypes> Critical<T> {
         }
     }
 
+    /// Returns `true` if there are pending components for `block_root` in memory or on disk.
+    pub fn contains(&self, block_root: &Hash256) -> bool {
+        self.in_memory.contains(block_root) || self.store_keys.contains(block_root)
+    }
+
     /// Returns the number of pending component entries in memory.
     pub fn num_blocks(&self) -> usize {
         self.in_memory.len()
@@ -528,7 +538,7 @@ impl<T: BeaconChainTypes> Critical<T> {
 /// interact with the cache through this.
 pub struct OverflowLRUCache<T: BeaconChainTypes> {
     /// Contains all the data we keep in memory, protected by an RwLock
-    critical: RwLock<Critical<T>>,
+    critical: MeteredRwLock<Critical<T>>,
     /// This is how we read and write components to the disk
     overflow_store: OverflowStore<T>,
     /// This cache holds a limited number of states in memory and reconstructs them
@@ -550,7 +560,7 @@ impl<T: BeaconChainTypes> OverflowLRUCache<T> {
         let mut critical = Critical::new(capacity);
         critical.reload_store_keys(&overflow_store)?;
         Ok(Self {
-            critical: RwLock::new(critical),
+            critical: MeteredRwLock::new(critical, "availability_cache"),
             overflow_store,
             state_cache: StateLRUCache::new(beacon_store, spec),
             maintenance_lock: Mutex::new(()),
@@ -686,6 +696,11 @@ impl<T: BeaconChainTypes> OverflowLRUCache<T> {
         }
     }
 
+    /// Returns `true` if there are pending components for `block_root` in memory or on disk.
+    pub fn has_pending_components(&self, block_root: &Hash256) -> bool {
+        self.critical.read().contains(block_root)
+    }
+
     pub fn remove_pending_components(&self, block_root: Hash256) {
         self.critical.write().remove_pending_components(block_root);
     }
@@ -709,6 +724,43 @@ impl<T: BeaconChainTypes> OverflowLRUCache<T> {
         Ok(())
     }
 
+    /// Move pending components which were written to disk (e.g. on shutdown) back into memory,
+    /// so that block lookups and blob requests can see them without reading from disk. Entries
+    /// from before `cutoff_epoch` are dropped, and reloading stops once the cache is full.
+    ///
+    /// Returns the number of entries moved back into memory.
+    pub fn reload_from_disk(&self, cutoff_epoch: Epoch) -> Result<usize, AvailabilityCheckError> {
+        let maintenance_lock = self.maintenance_lock.lock();
+        let mut critical_lock = self.critical.write();
+
+        let mut reloaded = 0;
+        let store_keys = critical_lock.store_keys.iter().copied().collect::<Vec<_>>();
+        for root in store_keys {
+            if critical_lock.in_memory.len() >= self.capacity.get() {
+                break;
+            }
+            // Entries popped from `store_keys` are removed from disk by the next pruning pass.
+            let Some(pending_components) =
+                critical_lock.pop_pending_components(root, &self.overflow_store)?
+            else {
+                continue;
+            };
+            if pending_components
+                .epoch()
+                .map(|epoch| epoch < cutoff_epoch)
+                .unwrap_or(true)
+            {
+                continue;
+            }
+            critical_lock.in_memory.put(root, pending_components);
+            reloaded += 1;
+        }
+
+        drop(critical_lock);
+        drop(maintenance_lock);
+        Ok(reloaded)
+    }
+
     /// maintain the cache
     pub fn do_maintenance(&self, cutoff_epoch: Epoch) -> Result<(), AvailabilityCheckError> {
         // ensure memory usage is below threshold
@@ -964,7 +1016,7 @@ mod test {
     use state_processing::ConsensusContext;
     use std::collections::{BTreeMap, HashMap, VecDeque};
     use std::ops::AddAssign;
-    use store::{HotColdDB, ItemStore, LevelDB, StoreConfig};
+    use store::{BeaconNodeBackend, HotColdDB, ItemStore, LevelDB, StoreConfig};
     use tempfile::{tempdir, TempDir};
     use types::non_zero_usize::new_non_zero_usize;
     use types::{ExecPayload, MinimalEthSpec};
@@ -975,7 +1027,7 @@ mod test {
         db_path: &TempDir,
         spec: ChainSpec,
         log: Logger,
-    ) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+    ) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, LevelDB<E>>> {
         let hot_path = db_path.path().join("hot_db");
         let cold_path = db_path.path().join("cold_db");
         let blobs_path = db_path.path().join("blobs_db");
@@ -1050,26 +1102,39 @@ mod test {
     #[test]
     fn overflow_key_encode_decode_equality() {
         type E = types::MainnetEthSpec;
+        let spec = E::default_spec();
         let key_block = OverflowKey::Block(Hash256::random());
-        let key_blob_0 = OverflowKey::from_blob_id::<E>(BlobIdentifier {
-            block_root: Hash256::random(),
-            index: 0,
-        })
+        let key_blob_0 = OverflowKey::from_blob_id::<E>(
+            BlobIdentifier {
+                block_root: Hash256::random(),
+                index: 0,
+            },
+            &spec,
+        )
         .expect("should create overflow key 0");
-        let key_blob_1 = OverflowKey::from_blob_id::<E>(BlobIdentifier {
-            block_root: Hash256::random(),
-            index: 1,
-        })
+        let key_blob_1 = OverflowKey::from_blob_id::<E>(
+            BlobIdentifier {
+                block_root: Hash256::random(),
+                index: 1,
+            },
+            &spec,
+        )
         .expect("should create overflow key 1");
-        let key_blob_2 = OverflowKey::from_blob_id::<E>(BlobIdentifier {
-            block_root: Hash256::random(),
-            index: 2,
-        })
+        let key_blob_2 = OverflowKey::from_blob_id::<E>(
+            BlobIdentifier {
+                block_root: Hash256::random(),
+                index: 2,
+            },
+            &spec,
+        )
         .expect("should create overflow key 2");
-        let key_blob_3 = OverflowKey::from_blob_id::<E>(BlobIdentifier {
-            block_root: Hash256::random(),
-            index: 3,
-        })
+        let key_blob_3 = OverflowKey::from_blob_id::<E>(
+            BlobIdentifier {
+                block_root: Hash256::random(),
+                index: 3,
+            },
+            &spec,
+        )
         .expect("should create overflow key 3");
 
         let keys = vec![key_block, key_blob_0, key_blob_1, key_blob_2, key_blob_3];
@@ -1181,7 +1246,7 @@ mod test {
     )
     where
         E: EthSpec,
-        T: BeaconChainTypes<HotStore = LevelDB<E>, ColdStore = LevelDB<E>, EthSpec = E>,
+        T: BeaconChainTypes<HotStore = BeaconNodeBackend<E>, ColdStore = LevelDB<E>, EthSpec = E>,
     {
         let log = test_logger();
         let chain_db_path = tempdir().expect("should get temp dir");
@@ -1733,6 +1798,35 @@ mod test {
             "cache store should have the rest"
         );
 
+        // reload as much as fits back into memory
+        let reloaded = recovered_cache
+            .reload_from_disk(Epoch::new(0))
+            .expect("should reload from disk");
+        assert_eq!(reloaded, capacity, "should reload a full cache");
+        assert_eq!(
+            recovered_cache.critical.read().in_memory.len(),
+            capacity,
+            "cache memory should be full"
+        );
+        assert_eq!(
+            recovered_cache.critical.read().store_keys.len(),
+            (n_epochs - 1) * capacity,
+            "cache store should have the rest"
+        );
+        let reloaded_roots = recovered_cache
+            .critical
+            .read()
+            .in_memory
+            .iter()
+            .map(|(root, _)| *root)
+            .collect::<Vec<_>>();
+        for root in reloaded_roots {
+            assert!(
+                recovered_cache.get_execution_valid_block(&root).is_some(),
+                "reloaded block should be visible"
+            );
+        }
+
         // now lets insert the remaining blobs until the cache is empty
         for (root, blobs) in remaining_blobs {
             let additional_blobs = blobs.len();
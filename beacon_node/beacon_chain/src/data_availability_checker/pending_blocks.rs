@@ -0,0 +1,186 @@
+//! Handles to blocks which are waiting in the availability cache for their blobs.
+//!
+//! Processing a block or blob which leaves a block without all of its components returns an
+//! `AvailabilityPendingBlock`. The handle resolves once the block is imported, or once it is
+//! dropped from the availability cache without being imported.
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::fmt;
+use tokio::sync::watch;
+use types::{Hash256, Slot};
+
+/// How an `AvailabilityPendingBlock` was resolved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PendingBlockOutcome {
+    /// All of the block's components arrived and it was imported.
+    Imported,
+    /// The block was removed from the availability cache without being imported, e.g. because
+    /// its blobs were withheld, it was pruned by finality, or its import failed.
+    Dropped,
+}
+
+/// A block which has been executed but which cannot be imported until all of its blobs are
+/// available.
+///
+/// The block itself is held by the availability cache. Clones of the handle all resolve together.
+#[derive(Clone)]
+pub struct AvailabilityPendingBlock {
+    slot: Slot,
+    block_root: Hash256,
+    outcome: watch::Receiver<Option<PendingBlockOutcome>>,
+}
+
+impl AvailabilityPendingBlock {
+    /// A handle which is not tracked by any availability cache, and so is already `Dropped`.
+    ///
+    /// Useful for tests which simulate the result of block processing.
+    pub fn detached(slot: Slot, block_root: Hash256) -> Self {
+        let (_, outcome) = watch::channel(None);
+        Self {
+            slot,
+            block_root,
+            outcome,
+        }
+    }
+
+    pub fn slot(&self) -> Slot {
+        self.slot
+    }
+
+    pub fn block_root(&self) -> Hash256 {
+        self.block_root
+    }
+
+    /// Returns the outcome of the block if it has already been resolved.
+    pub fn outcome(&self) -> Option<PendingBlockOutcome> {
+        if let Some(outcome) = *self.outcome.borrow() {
+            return Some(outcome);
+        }
+        // A handle whose sender has gone without sending an outcome can never be imported.
+        self.outcome
+            .has_changed()
+            .is_err()
+            .then_some(PendingBlockOutcome::Dropped)
+    }
+
+    /// Wait until the block is imported or dropped.
+    pub async fn resolved(mut self) -> PendingBlockOutcome {
+        loop {
+            if let Some(outcome) = *self.outcome.borrow_and_update() {
+                return outcome;
+            }
+            if self.outcome.changed().await.is_err() {
+                return PendingBlockOutcome::Dropped;
+            }
+        }
+    }
+}
+
+impl fmt::Debug for AvailabilityPendingBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AvailabilityPendingBlock")
+            .field("slot", &self.slot)
+            .field("block_root", &self.block_root)
+            .finish()
+    }
+}
+
+/// Two handles are equal if they refer to the same block, regardless of whether it is resolved.
+impl PartialEq for AvailabilityPendingBlock {
+    fn eq(&self, other: &Self) -> bool {
+        self.slot == other.slot && self.block_root == other.block_root
+    }
+}
+
+/// The senders for every block with an outstanding `AvailabilityPendingBlock`.
+#[derive(Default)]
+pub struct PendingBlocks {
+    senders: Mutex<HashMap<Hash256, (Slot, watch::Sender<Option<PendingBlockOutcome>>)>>,
+}
+
+impl PendingBlocks {
+    /// Get a handle to the block with `block_root`, which must be in the availability cache.
+    pub fn subscribe(&self, slot: Slot, block_root: Hash256) -> AvailabilityPendingBlock {
+        let outcome = self
+            .senders
+            .lock()
+            .entry(block_root)
+            .or_insert_with(|| (slot, watch::channel(None).0))
+            .1
+            .subscribe();
+        AvailabilityPendingBlock {
+            slot,
+            block_root,
+            outcome,
+        }
+    }
+
+    /// Resolve all handles to the block with `block_root`. Does nothing if there are none.
+    pub fn resolve(&self, block_root: Hash256, outcome: PendingBlockOutcome) {
+        if let Some((_, sender)) = self.senders.lock().remove(&block_root) {
+            sender.send_replace(Some(outcome));
+        }
+    }
+
+    /// Resolve the handles of all blocks prior to `cutoff_slot` as `Dropped`.
+    pub fn prune(&self, cutoff_slot: Slot) {
+        self.senders.lock().retain(|_, (slot, sender)| {
+            let keep = *slot >= cutoff_slot;
+            if !keep {
+                sender.send_replace(Some(PendingBlockOutcome::Dropped));
+            }
+            keep
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.senders.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.senders.lock().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn root(i: u64) -> Hash256 {
+        Hash256::from_low_u64_be(i)
+    }
+
+    #[tokio::test]
+    async fn resolve_and_prune() {
+        let pending_blocks = PendingBlocks::default();
+        let imported = pending_blocks.subscribe(Slot::new(10), root(1));
+        let imported_clone = pending_blocks.subscribe(Slot::new(10), root(1));
+        let pruned = pending_blocks.subscribe(Slot::new(5), root(2));
+        let kept = pending_blocks.subscribe(Slot::new(20), root(3));
+        assert_eq!(pending_blocks.len(), 3);
+        assert_eq!(imported.outcome(), None);
+
+        pending_blocks.resolve(root(1), PendingBlockOutcome::Imported);
+        pending_blocks.prune(Slot::new(10));
+        assert_eq!(pending_blocks.len(), 1);
+
+        assert_eq!(imported.resolved().await, PendingBlockOutcome::Imported);
+        assert_eq!(
+            imported_clone.resolved().await,
+            PendingBlockOutcome::Imported
+        );
+        assert_eq!(pruned.resolved().await, PendingBlockOutcome::Dropped);
+        assert_eq!(kept.outcome(), None);
+
+        pending_blocks.resolve(root(3), PendingBlockOutcome::Dropped);
+        assert_eq!(kept.resolved().await, PendingBlockOutcome::Dropped);
+        assert!(pending_blocks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn detached() {
+        let pending = AvailabilityPendingBlock::detached(Slot::new(1), root(1));
+        assert_eq!(pending.outcome(), Some(PendingBlockOutcome::Dropped));
+        assert_eq!(pending.resolved().await, PendingBlockOutcome::Dropped);
+    }
+}
@@ -153,6 +153,9 @@ pub enum BeaconChainError {
     EngineGetCapabilititesFailed(Box<execution_layer::Error>),
     ExecutionLayerGetBlockByNumberFailed(Box<execution_layer::Error>),
     ExecutionLayerGetBlockByHashFailed(Box<execution_layer::Error>),
+    ExecutionLayerGetPayloadBodiesFailed(Box<execution_layer::Error>),
+    EngineGetPayloadBodiesByRangeUnsupported,
+    PayloadBackfillWithPruningEnabled,
     BlockHashMissingFromExecutionLayer(ExecutionBlockHash),
     InconsistentPayloadReconstructed {
         slot: Slot,
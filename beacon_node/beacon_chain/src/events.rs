@@ -1,3 +1,4 @@
+use eth2::types::EventTopic;
 pub use eth2::types::{EventKind, SseBlock, SseFinalizedCheckpoint, SseHead};
 use slog::{trace, Logger};
 use tokio::sync::broadcast;
@@ -14,6 +15,8 @@ pub struct ServerSentEventHandler<E: EthSpec> {
     head_tx: Sender<EventKind<E>>,
     exit_tx: Sender<EventKind<E>>,
     chain_reorg_tx: Sender<EventKind<E>>,
+    attester_duties_reorg_tx: Sender<EventKind<E>>,
+    proposer_duties_reorg_tx: Sender<EventKind<E>>,
     contribution_tx: Sender<EventKind<E>>,
     payload_attributes_tx: Sender<EventKind<E>>,
     late_head: Sender<EventKind<E>>,
@@ -42,6 +45,8 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         let (head_tx, _) = broadcast::channel(capacity);
         let (exit_tx, _) = broadcast::channel(capacity);
         let (chain_reorg_tx, _) = broadcast::channel(capacity);
+        let (attester_duties_reorg_tx, _) = broadcast::channel(capacity);
+        let (proposer_duties_reorg_tx, _) = broadcast::channel(capacity);
         let (contribution_tx, _) = broadcast::channel(capacity);
         let (payload_attributes_tx, _) = broadcast::channel(capacity);
         let (late_head, _) = broadcast::channel(capacity);
@@ -60,6 +65,8 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
             head_tx,
             exit_tx,
             chain_reorg_tx,
+            attester_duties_reorg_tx,
+            proposer_duties_reorg_tx,
             contribution_tx,
             payload_attributes_tx,
             late_head,
@@ -111,6 +118,14 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
                 .chain_reorg_tx
                 .send(kind)
                 .map(|count| log_count("chain reorg", count)),
+            EventKind::AttesterDutiesReorg(_) => self
+                .attester_duties_reorg_tx
+                .send(kind)
+                .map(|count| log_count("attester duties reorg", count)),
+            EventKind::ProposerDutiesReorg(_) => self
+                .proposer_duties_reorg_tx
+                .send(kind)
+                .map(|count| log_count("proposer duties reorg", count)),
             EventKind::ContributionAndProof(_) => self
                 .contribution_tx
                 .send(kind)
@@ -153,6 +168,34 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         }
     }
 
+    /// Subscribe to the events for `topic`.
+    ///
+    /// This is shared by all of the HTTP API's event streams, so that they offer the same topics.
+    pub fn subscribe(&self, topic: EventTopic) -> Receiver<EventKind<E>> {
+        match topic {
+            EventTopic::Head => self.subscribe_head(),
+            EventTopic::Block => self.subscribe_block(),
+            EventTopic::BlobSidecar => self.subscribe_blob_sidecar(),
+            EventTopic::Attestation => self.subscribe_attestation(),
+            EventTopic::VoluntaryExit => self.subscribe_exit(),
+            EventTopic::FinalizedCheckpoint => self.subscribe_finalized(),
+            EventTopic::ChainReorg => self.subscribe_reorgs(),
+            EventTopic::AttesterDutiesReorg => self.subscribe_attester_duties_reorgs(),
+            EventTopic::ProposerDutiesReorg => self.subscribe_proposer_duties_reorgs(),
+            EventTopic::ContributionAndProof => self.subscribe_contributions(),
+            EventTopic::PayloadAttributes => self.subscribe_payload_attributes(),
+            EventTopic::LateHead => self.subscribe_late_head(),
+            EventTopic::LightClientFinalityUpdate => self.subscribe_light_client_finality_update(),
+            EventTopic::LightClientOptimisticUpdate => {
+                self.subscribe_light_client_optimistic_update()
+            }
+            EventTopic::BlockReward => self.subscribe_block_reward(),
+            EventTopic::AttesterSlashing => self.subscribe_attester_slashing(),
+            EventTopic::ProposerSlashing => self.subscribe_proposer_slashing(),
+            EventTopic::BlsToExecutionChange => self.subscribe_bls_to_execution_change(),
+        }
+    }
+
     pub fn subscribe_attestation(&self) -> Receiver<EventKind<E>> {
         self.attestation_tx.subscribe()
     }
@@ -181,6 +224,14 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.chain_reorg_tx.subscribe()
     }
 
+    pub fn subscribe_attester_duties_reorgs(&self) -> Receiver<EventKind<E>> {
+        self.attester_duties_reorg_tx.subscribe()
+    }
+
+    pub fn subscribe_proposer_duties_reorgs(&self) -> Receiver<EventKind<E>> {
+        self.proposer_duties_reorg_tx.subscribe()
+    }
+
     pub fn subscribe_contributions(&self) -> Receiver<EventKind<E>> {
         self.contribution_tx.subscribe()
     }
@@ -245,6 +296,14 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.chain_reorg_tx.receiver_count() > 0
     }
 
+    pub fn has_attester_duties_reorg_subscribers(&self) -> bool {
+        self.attester_duties_reorg_tx.receiver_count() > 0
+    }
+
+    pub fn has_proposer_duties_reorg_subscribers(&self) -> bool {
+        self.proposer_duties_reorg_tx.receiver_count() > 0
+    }
+
     pub fn has_contribution_subscribers(&self) -> bool {
         self.contribution_tx.receiver_count() > 0
     }
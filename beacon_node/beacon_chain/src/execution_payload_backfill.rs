@@ -0,0 +1,202 @@
+//! Re-populates the execution payloads of finalized blocks using the execution layer.
+//!
+//! Blocks are stored blinded, with their execution payloads in a separate column. Payloads may be
+//! missing from the database if they were pruned, or if blocks were imported in blinded form. For
+//! an archive node it is much cheaper to request the missing bodies in bulk from the execution
+//! layer via `engine_getPayloadBodiesByRange` than to re-download full blocks from peers.
+//!
+//! Every reconstructed payload is checked against the `ExecutionPayloadHeader` committed to by the
+//! stored block before it is written to disk.
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::PayloadBackfillSummary;
+use slog::{debug, info, warn};
+use std::collections::HashMap;
+use std::sync::Arc;
+use store::{KeyValueStore, StoreItem};
+use types::{EthSpec, ExecPayload, ExecutionBlockHash, ExecutionPayloadHeader, Hash256, Slot};
+
+/// The maximum number of payload bodies to request from the execution layer at once.
+const PAYLOAD_BODIES_PER_REQUEST: u64 = 32;
+
+/// A canonical block whose execution payload is not present in the database.
+struct MissingPayload<E: EthSpec> {
+    block_root: Hash256,
+    header: ExecutionPayloadHeader<E>,
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Restore the execution payloads of all canonical blocks in `start_slot..=end_slot` which are
+    /// missing from the database, using `engine_getPayloadBodiesByRange`.
+    ///
+    /// Only finalized blocks are considered, since the execution layer only serves bodies by
+    /// range for its canonical chain.
+    pub async fn backfill_execution_payloads(
+        self: &Arc<Self>,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<PayloadBackfillSummary, BeaconChainError> {
+        let execution_layer = self
+            .execution_layer
+            .as_ref()
+            .ok_or(BeaconChainError::ExecutionLayerMissing)?;
+
+        // Payloads of finalized blocks would be deleted again at the next finalization.
+        if self.store.get_config().prune_payloads {
+            return Err(BeaconChainError::PayloadBackfillWithPruningEnabled);
+        }
+
+        let capabilities = execution_layer
+            .get_engine_capabilities(None)
+            .await
+            .map_err(|e| BeaconChainError::EngineGetCapabilititesFailed(Box::new(e)))?;
+        if !capabilities.get_payload_bodies_by_range_v1 {
+            return Err(BeaconChainError::EngineGetPayloadBodiesByRangeUnsupported);
+        }
+
+        let finalized_slot = self
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+        let end_slot = std::cmp::min(end_slot, finalized_slot);
+
+        let mut summary = PayloadBackfillSummary::default();
+        if start_slot > end_slot {
+            return Ok(summary);
+        }
+
+        let chain = self.clone();
+        let (missing, scan_summary) = self
+            .spawn_blocking_handle(
+                move || chain.find_missing_payloads(start_slot, end_slot),
+                "payload_backfill_scan",
+            )
+            .await??;
+        summary = scan_summary;
+
+        info!(
+            self.log,
+            "Backfilling execution payloads";
+            "start_slot" => start_slot,
+            "end_slot" => end_slot,
+            "missing_payloads" => missing.len(),
+        );
+
+        let mut missing = missing.into_iter().peekable();
+        while let Some(first) = missing.next() {
+            // Group payloads with nearby block numbers into a single request.
+            let start = first.header.block_number();
+            let mut batch = vec![first];
+            while let Some(next) = missing.next_if(|next| {
+                next.header.block_number() >= start
+                    && next.header.block_number() < start + PAYLOAD_BODIES_PER_REQUEST
+            }) {
+                batch.push(next);
+            }
+            let count = batch
+                .iter()
+                .map(|missing| missing.header.block_number())
+                .max()
+                .unwrap_or(start)
+                - start
+                + 1;
+
+            let bodies = execution_layer
+                .get_payload_bodies_by_range(start, count)
+                .await
+                .map_err(|e| BeaconChainError::ExecutionLayerGetPayloadBodiesFailed(Box::new(e)))?;
+            let mut bodies_by_number = (start..start + count)
+                .zip(bodies.into_iter().chain(std::iter::repeat(None)))
+                .collect::<HashMap<_, _>>();
+
+            let mut ops = Vec::with_capacity(batch.len());
+            for MissingPayload { block_root, header } in batch {
+                let Some(body) = bodies_by_number.remove(&header.block_number()).flatten() else {
+                    debug!(
+                        self.log,
+                        "Payload body unavailable from execution layer";
+                        "block_root" => ?block_root,
+                        "block_number" => header.block_number(),
+                    );
+                    summary.payloads_unavailable += 1;
+                    continue;
+                };
+
+                match body.to_payload(header.clone()) {
+                    Ok(payload) if ExecutionPayloadHeader::from(payload.to_ref()) == header => {
+                        ops.push(payload.as_kv_store_op(block_root));
+                        summary.payloads_restored += 1;
+                    }
+                    Ok(_) | Err(_) => {
+                        warn!(
+                            self.log,
+                            "Execution layer returned inconsistent payload body";
+                            "block_root" => ?block_root,
+                            "block_hash" => ?header.block_hash(),
+                        );
+                        summary.payloads_invalid += 1;
+                    }
+                }
+            }
+
+            self.store.hot_db.do_atomically(ops)?;
+        }
+
+        info!(
+            self.log,
+            "Finished backfilling execution payloads";
+            "restored" => summary.payloads_restored,
+            "unavailable" => summary.payloads_unavailable,
+            "invalid" => summary.payloads_invalid,
+        );
+
+        Ok(summary)
+    }
+
+    /// Iterate the canonical chain between `start_slot` and `end_slot` (inclusive) and return the
+    /// blocks which have a non-default execution payload that is missing from the database.
+    fn find_missing_payloads(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<(Vec<MissingPayload<T::EthSpec>>, PayloadBackfillSummary), BeaconChainError> {
+        let mut summary = PayloadBackfillSummary::default();
+        let mut missing = vec![];
+        let mut prev_block_root = None;
+
+        for result in self.forwards_iter_block_roots_until(start_slot, end_slot)? {
+            let (block_root, _) = result?;
+
+            // Skip slots repeat the previous block root.
+            if prev_block_root == Some(block_root) {
+                continue;
+            }
+            prev_block_root = Some(block_root);
+            summary.blocks_checked += 1;
+
+            if self.store.execution_payload_exists(&block_root)? {
+                summary.payloads_already_present += 1;
+                continue;
+            }
+
+            let block = self
+                .store
+                .get_blinded_block(&block_root)?
+                .ok_or(BeaconChainError::MissingBeaconBlock(block_root))?;
+
+            // Pre-merge blocks have no payload to restore.
+            let Ok(payload) = block.message().execution_payload() else {
+                continue;
+            };
+            let header = payload.to_execution_payload_header();
+            if header.block_hash() == ExecutionBlockHash::zero() {
+                continue;
+            }
+
+            missing.push(MissingPayload { block_root, header });
+        }
+
+        Ok((missing, summary))
+    }
+}
@@ -9,7 +9,7 @@ use state_processing::{
 };
 use std::sync::Arc;
 use std::time::Duration;
-use store::{iter::ParentRootBlockIterator, HotColdDB, ItemStore};
+use store::{iter::ParentRootBlockIterator, Error as StoreError, HotColdDB, ItemStore};
 use types::{BeaconState, ChainSpec, EthSpec, ForkName, Hash256, SignedBeaconBlock, Slot};
 
 const CORRUPT_DB_MESSAGE: &str = "The database could be corrupt. Check its file permissions or \
@@ -79,6 +79,105 @@ pub fn revert_to_fork_boundary<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>
     Ok((block_root, block))
 }
 
+/// The result of checking the persisted fork choice against the database on startup.
+#[derive(Debug, PartialEq)]
+pub struct ConsistencyCheck {
+    /// The most recent block on the chain of the fork choice head which is present in the database
+    /// along with its blobs, if they are required.
+    pub consistent_head_block_root: Hash256,
+    /// The number of blocks known to fork choice which are missing from the database.
+    pub missing_blocks: usize,
+}
+
+/// Check that the blocks known to `fork_choice` are present in `store`, and find the most recent
+/// block on the chain of `head_block_root` which has its blobs (if they are within the data
+/// availability window at `current_slot`).
+///
+/// Inconsistencies are most likely the result of an unclean shutdown, and can be healed by
+/// resetting fork choice to the consistent head with `reset_fork_choice_to_finalization`.
+///
+/// Blocks which can't be decoded are left for `revert_to_fork_boundary` to deal with.
+pub fn check_fork_choice_consistency<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
+    fork_choice: &ForkChoice<BeaconForkChoiceStore<E, Hot, Cold>, E>,
+    head_block_root: Hash256,
+    current_slot: Slot,
+    store: &HotColdDB<E, Hot, Cold>,
+    spec: &ChainSpec,
+    log: &Logger,
+) -> Result<ConsistencyCheck, String> {
+    let proto_array = fork_choice.proto_array().core_proto_array();
+
+    let mut missing_blocks = 0;
+    for node in &proto_array.nodes {
+        if !store
+            .block_exists(&node.root)
+            .map_err(|e| format!("Error checking for block {:?}: {:?}", node.root, e))?
+        {
+            warn!(
+                log,
+                "Block known to fork choice is missing";
+                "block_root" => ?node.root,
+                "slot" => node.slot,
+            );
+            missing_blocks += 1;
+        }
+    }
+
+    // Blobs are only required for blocks in the data availability window, and never for the
+    // finalized block, which is the oldest block fork choice knows about.
+    let finalized_root = fork_choice.finalized_checkpoint().root;
+    let oldest_blob_slot = store.get_blob_info().oldest_blob_slot;
+    let availability_boundary = current_slot
+        .epoch(E::slots_per_epoch())
+        .saturating_sub(spec.min_epochs_for_blob_sidecars_requests);
+
+    for (block_root, slot) in proto_array.iter_block_roots(&head_block_root) {
+        let block = match store.get_blinded_block(&block_root) {
+            Ok(Some(block)) => block,
+            Ok(None) => continue,
+            Err(StoreError::SszDecodeError(_)) => {
+                return Ok(ConsistencyCheck {
+                    consistent_head_block_root: block_root,
+                    missing_blocks,
+                })
+            }
+            Err(e) => return Err(format!("Error loading block {:?}: {:?}", block_root, e)),
+        };
+
+        let blobs_required = block_root != finalized_root
+            && slot.epoch(E::slots_per_epoch()) >= availability_boundary
+            && oldest_blob_slot.map_or(false, |oldest_blob_slot| slot >= oldest_blob_slot)
+            && block
+                .message()
+                .body()
+                .blob_kzg_commitments()
+                .map_or(false, |commitments| !commitments.is_empty());
+        if blobs_required
+            && !store
+                .blobs_exist(&block_root)
+                .map_err(|e| format!("Error checking for blobs of {:?}: {:?}", block_root, e))?
+        {
+            warn!(
+                log,
+                "Blobs missing for block on the head chain";
+                "block_root" => ?block_root,
+                "slot" => slot,
+            );
+            continue;
+        }
+
+        return Ok(ConsistencyCheck {
+            consistent_head_block_root: block_root,
+            missing_blocks,
+        });
+    }
+
+    Err(format!(
+        "No block on the chain of the head {:?} is present in the database. {}",
+        head_block_root, CORRUPT_DB_MESSAGE
+    ))
+}
+
 /// Reset fork choice to the finalized checkpoint of the supplied head state.
 ///
 /// The supplied `head_block_root` should correspond to the most recently applied block on
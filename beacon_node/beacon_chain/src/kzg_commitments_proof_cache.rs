@@ -0,0 +1,91 @@
+use lru::LruCache;
+use merkle_proof::merkle_root_from_branch;
+use parking_lot::Mutex;
+use std::num::NonZeroUsize;
+use tree_hash::TreeHash;
+use types::beacon_block_body::BLOB_KZG_COMMITMENTS_INDEX;
+use types::non_zero_usize::new_non_zero_usize;
+use types::{AbstractExecPayload, BeaconBlockRef, BeaconStateError, EthSpec, Hash256};
+
+const CACHE_LIMIT: NonZeroUsize = new_non_zero_usize(8);
+
+/// Caches the proof of inclusion for a block's `blob_kzg_commitments` in its body, keyed by the
+/// body root.
+///
+/// The proof is computed when a block is produced, so that building its blob sidecars when the
+/// block is published doesn't require hashing the whole body again. The body root is the same for
+/// the blinded and full variants of a block.
+pub struct KzgCommitmentsProofCache {
+    proofs: Mutex<LruCache<Hash256, Vec<Hash256>>>,
+}
+
+impl Default for KzgCommitmentsProofCache {
+    fn default() -> Self {
+        Self {
+            proofs: Mutex::new(LruCache::new(CACHE_LIMIT)),
+        }
+    }
+}
+
+impl KzgCommitmentsProofCache {
+    /// Compute and store the proof for `block`, if it has any blobs.
+    pub fn insert_for_block<E: EthSpec, Payload: AbstractExecPayload<E>>(
+        &self,
+        block: BeaconBlockRef<E, Payload>,
+    ) -> Result<(), BeaconStateError> {
+        let body = block.body();
+        if !body.has_blobs() {
+            return Ok(());
+        }
+        let proof = body.kzg_commitments_merkle_proof()?;
+        // The body root follows from the proof, so there is no need to hash the body again.
+        let body_root = merkle_root_from_branch(
+            body.blob_kzg_commitments()?.tree_hash_root(),
+            &proof,
+            E::block_body_tree_depth(),
+            BLOB_KZG_COMMITMENTS_INDEX,
+        );
+        self.proofs.lock().put(body_root, proof);
+        Ok(())
+    }
+
+    /// Return the proof for the block body with `body_root`, if it is known.
+    pub fn get(&self, body_root: &Hash256) -> Option<Vec<Hash256>> {
+        self.proofs.lock().get(body_root).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{
+        BeaconBlock, BeaconBlockDeneb, EmptyBlock, FullPayload, KzgCommitment, MainnetEthSpec,
+    };
+
+    type E = MainnetEthSpec;
+
+    #[test]
+    fn keyed_by_body_root() {
+        let spec = E::default_spec();
+        let cache = KzgCommitmentsProofCache::default();
+
+        let mut block = BeaconBlockDeneb::<E, FullPayload<E>>::empty(&spec);
+        let block_without_blobs = BeaconBlock::Deneb(block.clone());
+        cache
+            .insert_for_block(block_without_blobs.to_ref())
+            .unwrap();
+        assert_eq!(cache.get(&block_without_blobs.body_root()), None);
+
+        block
+            .body
+            .blob_kzg_commitments
+            .push(KzgCommitment::empty_for_testing())
+            .unwrap();
+        let block = BeaconBlock::Deneb(block);
+        cache.insert_for_block(block.to_ref()).unwrap();
+        assert_eq!(
+            cache.get(&block.body_root()),
+            Some(block.body().kzg_commitments_merkle_proof().unwrap())
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use kzg::{Blob as KzgBlob, Error as KzgError, Kzg};
-use types::{Blob, EthSpec, Hash256, KzgCommitment, KzgProof};
+use types::{Blob, BlobSidecar, EthSpec, Hash256, KzgCommitment, KzgProof};
 
 /// Converts a blob ssz List object to an array to be used with the kzg
 /// crypto library.
@@ -35,6 +35,124 @@ pub fn validate_blobs<E: EthSpec>(
     kzg.verify_blob_kzg_proof_batch(&blobs, expected_kzg_commitments, kzg_proofs)
 }
 
+/// An error from `validate_blobs_with_attribution`.
+#[derive(Debug)]
+pub enum KzgBatchError {
+    /// The blob at `index` in the batch is invalid, or could not be decoded.
+    ///
+    /// If several blobs are invalid, this is always the one with the lowest index.
+    InvalidBlob { index: usize, error: KzgError },
+    /// The batch is invalid, but the failure could not be attributed to a single blob (e.g. the
+    /// input lists have different lengths).
+    Batch(KzgError),
+}
+
+impl KzgBatchError {
+    pub fn into_kzg_error(self) -> KzgError {
+        match self {
+            KzgBatchError::InvalidBlob { error, .. } | KzgBatchError::Batch(error) => error,
+        }
+    }
+}
+
+/// Validate a batch of blob-commitment-proof triplets, identifying the offending blob on failure.
+///
+/// The happy path costs the same as `validate_blobs`. If the batch fails, it is bisected to find
+/// the lowest-indexed invalid blob, so that the peer which supplied it can be penalised.
+pub fn validate_blobs_with_attribution<E: EthSpec>(
+    kzg: &Kzg,
+    expected_kzg_commitments: &[KzgCommitment],
+    blobs: Vec<&Blob<E>>,
+    kzg_proofs: &[KzgProof],
+) -> Result<(), KzgBatchError> {
+    let _timer = crate::metrics::start_timer(&crate::metrics::KZG_VERIFICATION_BATCH_TIMES);
+    let blobs = blobs
+        .into_iter()
+        .enumerate()
+        .map(|(index, blob)| {
+            ssz_blob_to_crypto_blob::<E>(blob)
+                .map_err(|error| KzgBatchError::InvalidBlob { index, error })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let batch_error =
+        match kzg.verify_blob_kzg_proof_batch(&blobs, expected_kzg_commitments, kzg_proofs) {
+            Ok(()) => return Ok(()),
+            Err(e) => e,
+        };
+
+    if blobs.len() != expected_kzg_commitments.len() || blobs.len() != kzg_proofs.len() {
+        return Err(KzgBatchError::Batch(batch_error));
+    }
+
+    match find_invalid_blob(kzg, &blobs, expected_kzg_commitments, kzg_proofs) {
+        Some((index, error)) => Err(KzgBatchError::InvalidBlob { index, error }),
+        None => Err(KzgBatchError::Batch(batch_error)),
+    }
+}
+
+/// Validate the KZG proofs of a list of `BlobSidecar`s in a single batch.
+///
+/// The sidecars may belong to a single block or to an entire RPC response. On failure, the index
+/// of `KzgBatchError::InvalidBlob` is the position of the offending sidecar in `blob_sidecars`.
+///
+/// This is used by the data availability checker and when publishing a block. Sidecars received
+/// on gossip arrive one at a time on their own subnets, so they are still verified individually.
+pub fn validate_blob_sidecars_batch<E: EthSpec>(
+    kzg: &Kzg,
+    blob_sidecars: &[&BlobSidecar<E>],
+) -> Result<(), KzgBatchError> {
+    let (blobs, (commitments, proofs)): (Vec<_>, (Vec<_>, Vec<_>)) = blob_sidecars
+        .iter()
+        .map(|sidecar| (&sidecar.blob, (sidecar.kzg_commitment, sidecar.kzg_proof)))
+        .unzip();
+    validate_blobs_with_attribution::<E>(kzg, &commitments, blobs, &proofs)
+}
+
+/// Bisect a batch of equal-length blobs, commitments and proofs, returning the lowest index which
+/// fails verification.
+fn find_invalid_blob(
+    kzg: &Kzg,
+    blobs: &[KzgBlob],
+    kzg_commitments: &[KzgCommitment],
+    kzg_proofs: &[KzgProof],
+) -> Option<(usize, KzgError)> {
+    match blobs.len() {
+        0 => None,
+        1 => kzg
+            .verify_blob_kzg_proof(&blobs[0], kzg_commitments[0], kzg_proofs[0])
+            .err()
+            .map(|error| (0, error)),
+        len => {
+            let mid = len / 2;
+            let left_failed = kzg
+                .verify_blob_kzg_proof_batch(
+                    &blobs[..mid],
+                    &kzg_commitments[..mid],
+                    &kzg_proofs[..mid],
+                )
+                .is_err();
+            if left_failed {
+                if let Some(invalid) = find_invalid_blob(
+                    kzg,
+                    &blobs[..mid],
+                    &kzg_commitments[..mid],
+                    &kzg_proofs[..mid],
+                ) {
+                    return Some(invalid);
+                }
+            }
+            find_invalid_blob(
+                kzg,
+                &blobs[mid..],
+                &kzg_commitments[mid..],
+                &kzg_proofs[mid..],
+            )
+            .map(|(index, error)| (index + mid, error))
+        }
+    }
+}
+
 /// Compute the kzg proof given an ssz blob and its kzg commitment.
 pub fn compute_blob_kzg_proof<E: EthSpec>(
     kzg: &Kzg,
@@ -76,3 +194,58 @@ pub fn verify_kzg_proof<E: EthSpec>(
 ) -> Result<bool, KzgError> {
     kzg.verify_kzg_proof(kzg_commitment, &z.0.into(), &y.0.into(), kzg_proof)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::KZG;
+    use rand::{rngs::StdRng, SeedableRng};
+    use types::MainnetEthSpec;
+
+    const NUM_BLOBS: usize = 6;
+
+    /// Returns `NUM_BLOBS` valid blobs with their commitments and proofs.
+    fn valid_batch() -> (Vec<KzgBlob>, Vec<KzgCommitment>, Vec<KzgProof>) {
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF0BAD5EEDu64);
+        let mut blobs = vec![];
+        let mut commitments = vec![];
+        let mut proofs = vec![];
+        for _ in 0..NUM_BLOBS {
+            let sidecar = BlobSidecar::<MainnetEthSpec>::random_valid(&mut rng, &KZG).unwrap();
+            blobs.push(ssz_blob_to_crypto_blob::<MainnetEthSpec>(&sidecar.blob).unwrap());
+            commitments.push(sidecar.kzg_commitment);
+            proofs.push(sidecar.kzg_proof);
+        }
+        (blobs, commitments, proofs)
+    }
+
+    #[test]
+    fn find_invalid_blob_valid_batch() {
+        let (blobs, commitments, proofs) = valid_batch();
+        assert!(find_invalid_blob(&KZG, &blobs, &commitments, &proofs).is_none());
+    }
+
+    #[test]
+    fn find_invalid_blob_at_each_position() {
+        for invalid_index in [0, NUM_BLOBS / 2, NUM_BLOBS - 1] {
+            let (blobs, commitments, mut proofs) = valid_batch();
+            // Use the proof of a neighbouring blob, which is a valid point but the wrong proof.
+            proofs[invalid_index] = proofs[(invalid_index + 1) % NUM_BLOBS];
+
+            let (index, error) = find_invalid_blob(&KZG, &blobs, &commitments, &proofs)
+                .expect("should find the invalid blob");
+            assert_eq!(index, invalid_index);
+            assert!(matches!(error, KzgError::KzgVerificationFailed));
+        }
+    }
+
+    #[test]
+    fn find_invalid_blob_returns_lowest_index() {
+        let (blobs, commitments, mut proofs) = valid_batch();
+        proofs.swap(NUM_BLOBS / 2, NUM_BLOBS - 1);
+
+        let (index, _) = find_invalid_blob(&KZG, &blobs, &commitments, &proofs)
+            .expect("should find the invalid blob");
+        assert_eq!(index, NUM_BLOBS / 2);
+    }
+}
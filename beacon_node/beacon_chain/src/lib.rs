@@ -9,6 +9,7 @@ mod beacon_fork_choice_store;
 pub mod beacon_proposer_cache;
 mod beacon_snapshot;
 pub mod bellatrix_readiness;
+pub mod blob_fee;
 pub mod blob_verification;
 pub mod block_reward;
 mod block_times_cache;
@@ -27,15 +28,18 @@ pub mod eth1_chain;
 mod eth1_finalization_cache;
 pub mod events;
 pub mod execution_payload;
+mod execution_payload_backfill;
 pub mod fork_choice_signal;
 pub mod fork_revert;
 pub mod graffiti_calculator;
 mod head_tracker;
 pub mod historical_blocks;
+mod kzg_commitments_proof_cache;
 pub mod kzg_utils;
 pub mod light_client_finality_update_verification;
 pub mod light_client_optimistic_update_verification;
 mod light_client_server_cache;
+pub mod metered_lock;
 pub mod metrics;
 pub mod migrate;
 mod naive_aggregation_pool;
@@ -46,12 +50,15 @@ pub mod observed_block_producers;
 pub mod observed_operations;
 mod observed_slashable;
 pub mod otb_verification_service;
+pub mod participation_summary;
 mod persisted_beacon_chain;
 mod persisted_fork_choice;
 mod pre_finalization_cache;
 pub mod proposer_prep_service;
 pub mod schema_change;
 pub mod shuffling_cache;
+pub mod shuffling_precompute;
+pub mod slot_tasks;
 pub mod state_advance_timer;
 pub mod sync_committee_rewards;
 pub mod sync_committee_verification;
@@ -59,12 +66,13 @@ pub mod test_utils;
 mod timeout_rw_lock;
 pub mod validator_monitor;
 pub mod validator_pubkey_cache;
+pub mod weak_subjectivity_period;
 
 pub use self::beacon_chain::{
     AttestationProcessingOutcome, AvailabilityProcessingStatus, BeaconBlockResponse,
     BeaconBlockResponseWrapper, BeaconChain, BeaconChainTypes, BeaconStore, BlockProcessStatus,
     ChainSegmentResult, ForkChoiceError, LightClientProducerEvent, OverrideForkchoiceUpdate,
-    ProduceBlockVerification, StateSkipConfig, WhenSlotSkipped,
+    ProduceBlockVerification, StateSkipConfig, WhenSlotSkipped, FORK_CHOICE_DB_KEY,
     INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON,
     INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON,
 };
@@ -86,11 +94,12 @@ pub use eth1_chain::{Eth1Chain, Eth1ChainBackend};
 pub use events::ServerSentEventHandler;
 pub use execution_layer::EngineState;
 pub use execution_payload::NotifyExecutionLayer;
-pub use fork_choice::{ExecutionStatus, ForkchoiceUpdateParameters};
+pub use fork_choice::{ExecutionStatus, ForkchoiceUpdateParameters, ResetPayloadStatuses};
 pub use kzg::{Kzg, TrustedSetup};
 pub use metrics::scrape_for_metrics;
 pub use migrate::MigratorConfig;
 pub use parking_lot;
+pub use persisted_fork_choice::PersistedForkChoice;
 pub use slot_clock;
 pub use state_processing::per_block_processing::errors::{
     AttestationValidationError, AttesterSlashingValidationError, DepositValidationError,
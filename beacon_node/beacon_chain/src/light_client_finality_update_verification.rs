@@ -51,7 +51,7 @@ impl<T: BeaconChainTypes> VerifiedLightClientFinalityUpdate<T> {
             .start_of(*rcv_finality_update.signature_slot())
             .ok_or(Error::SigSlotStartIsNone)?;
         let one_third_slot_duration = Duration::new(chain.spec.seconds_per_slot / 3, 0);
-        if seen_timestamp + chain.spec.maximum_gossip_clock_disparity()
+        if seen_timestamp + chain.config.maximum_gossip_clock_disparity(&chain.spec)
             < start_time + one_third_slot_duration
         {
             return Err(Error::TooEarly);
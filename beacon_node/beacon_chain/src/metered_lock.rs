@@ -0,0 +1,132 @@
+//! Wrappers around `parking_lot` locks which record the time spent waiting to acquire them.
+//!
+//! The wait times are exported via the `beacon_lock_wait_seconds` histogram, labelled with the
+//! name of the lock and the kind of access. This makes lock convoys on hot paths (e.g., blob
+//! verification contending on the availability cache) visible in production.
+//!
+//! Only the time spent *waiting* for the lock is recorded, not the time the guard is held.
+use crate::metrics;
+use parking_lot::{
+    Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard,
+};
+
+const READ: &str = "read";
+const WRITE: &str = "write";
+
+/// Records the time taken to acquire some named lock.
+///
+/// The histograms are resolved once at construction to keep the overhead of each acquisition to a
+/// couple of clock reads.
+#[derive(Default)]
+pub struct LockWaitTimer {
+    read: Option<metrics::Histogram>,
+    write: Option<metrics::Histogram>,
+}
+
+impl LockWaitTimer {
+    pub fn new(lock_name: &str) -> Self {
+        Self {
+            read: metrics::get_histogram(&metrics::LOCK_WAIT_TIMES, &[lock_name, READ]),
+            write: metrics::get_histogram(&metrics::LOCK_WAIT_TIMES, &[lock_name, WRITE]),
+        }
+    }
+
+    /// Time a shared acquisition of the lock by `acquire`.
+    pub fn time_read<G>(&self, acquire: impl FnOnce() -> G) -> G {
+        Self::time(self.read.as_ref(), acquire)
+    }
+
+    /// Time an exclusive acquisition of the lock by `acquire`.
+    pub fn time_write<G>(&self, acquire: impl FnOnce() -> G) -> G {
+        Self::time(self.write.as_ref(), acquire)
+    }
+
+    fn time<G>(histogram: Option<&metrics::Histogram>, acquire: impl FnOnce() -> G) -> G {
+        match histogram {
+            Some(histogram) => {
+                let _timer = histogram.start_timer();
+                acquire()
+            }
+            None => acquire(),
+        }
+    }
+}
+
+/// A `parking_lot::Mutex` which records the time spent waiting for `lock`.
+pub struct MeteredMutex<T> {
+    inner: Mutex<T>,
+    timer: LockWaitTimer,
+}
+
+impl<T> MeteredMutex<T> {
+    pub fn new(value: T, lock_name: &str) -> Self {
+        Self {
+            inner: Mutex::new(value),
+            timer: LockWaitTimer::new(lock_name),
+        }
+    }
+
+    pub fn lock(&self) -> MutexGuard<T> {
+        self.timer.time_write(|| self.inner.lock())
+    }
+}
+
+/// A `parking_lot::RwLock` which records the time spent waiting for `read`, `upgradable_read`
+/// and `write`.
+///
+/// Upgrades of an upgradable read guard are not timed.
+pub struct MeteredRwLock<T> {
+    inner: RwLock<T>,
+    timer: LockWaitTimer,
+}
+
+impl<T> MeteredRwLock<T> {
+    pub fn new(value: T, lock_name: &str) -> Self {
+        Self {
+            inner: RwLock::new(value),
+            timer: LockWaitTimer::new(lock_name),
+        }
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<T> {
+        self.timer.time_read(|| self.inner.read())
+    }
+
+    pub fn upgradable_read(&self) -> RwLockUpgradableReadGuard<T> {
+        // An upgradable read excludes writers and other upgradable readers, so it is closer to a
+        // write in terms of contention.
+        self.timer.time_write(|| self.inner.upgradable_read())
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<T> {
+        self.timer.time_write(|| self.inner.write())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_acquisitions() {
+        let lock = MeteredRwLock::new(0, "test_metered_rw_lock");
+        *lock.write() += 1;
+        assert_eq!(*lock.read(), 1);
+
+        let write =
+            metrics::get_histogram(&metrics::LOCK_WAIT_TIMES, &["test_metered_rw_lock", WRITE])
+                .unwrap();
+        let read =
+            metrics::get_histogram(&metrics::LOCK_WAIT_TIMES, &["test_metered_rw_lock", READ])
+                .unwrap();
+        assert_eq!(write.get_sample_count(), 1);
+        assert_eq!(read.get_sample_count(), 1);
+
+        let mutex = MeteredMutex::new((), "test_metered_mutex");
+        drop(mutex.lock());
+        let mutex_write =
+            metrics::get_histogram(&metrics::LOCK_WAIT_TIMES, &["test_metered_mutex", WRITE])
+                .unwrap();
+        assert_eq!(mutex_write.get_sample_count(), 1);
+    }
+}
@@ -97,6 +97,18 @@ lazy_static! {
         "block_sync_aggregate_set_bits",
         "The number of true bits in the last sync aggregate in a block"
     );
+    pub static ref BLOCK_BLOB_GAS_USED: Result<IntGauge> = try_create_int_gauge(
+        "block_blob_gas_used",
+        "The blob gas used by the payload of the last imported block"
+    );
+    pub static ref BLOCK_EXCESS_BLOB_GAS: Result<IntGauge> = try_create_int_gauge(
+        "block_excess_blob_gas",
+        "The excess blob gas of the payload of the last imported block"
+    );
+    pub static ref BLOCK_BLOB_BASE_FEE: Result<Gauge> = try_create_float_gauge(
+        "block_blob_base_fee",
+        "The blob base fee in wei of the payload of the last imported block"
+    );
 
     /*
      * Block Production
@@ -155,6 +167,13 @@ lazy_static! {
         Ok(vec![0_f64, 1_f64, 3_f64, 15_f64, 31_f64, 63_f64, 127_f64, 255_f64])
     );
 
+    pub static ref BLOBS_PER_BLOCK: Result<Histogram> = try_create_histogram_with_buckets(
+        "beacon_blobs_per_block_total",
+        "Number of blobs committed to by a block",
+        // Deneb allows at most 6. The larger buckets leave room for forks which raise the limit.
+        Ok(vec![0_f64, 1_f64, 2_f64, 3_f64, 4_f64, 5_f64, 6_f64, 9_f64, 12_f64, 16_f64, 32_f64, 64_f64, 128_f64])
+    );
+
     pub static ref BLOCK_SIZE: Result<Histogram> = try_create_histogram_with_buckets(
         "beacon_block_total_size",
         "Size of a signed beacon block",
@@ -868,6 +887,10 @@ lazy_static! {
         "beacon_block_delay_available_slot_start",
         "Duration between the time that block became available and the start of the slot.",
     );
+    pub static ref BEACON_BLOCK_DELAY_AVAILABLE_OBSERVED: Result<IntGauge> = try_create_int_gauge(
+        "beacon_block_delay_available_observed",
+        "Duration between the time the block was observed and the time it became available.",
+    );
     pub static ref BEACON_BLOCK_DELAY_ATTESTABLE_SLOT_START: Result<IntGauge> = try_create_int_gauge(
         "beacon_block_delay_attestable_slot_start",
         "Duration between the time that block became attestable and the start of the slot.",
@@ -1045,6 +1068,10 @@ lazy_static! {
         "blob_sidecar_inclusion_proof_computation_seconds",
         "Time taken to compute blob sidecar inclusion proof"
     );
+    pub static ref BLOB_SIDECAR_PROPOSER_EQUIVOCATIONS: Result<IntCounter> = try_create_int_counter(
+        "beacon_blob_sidecar_proposer_equivocations_total",
+        "Count of gossip blob sidecars which conflict with a previously seen sidecar from the same proposer, slot and index"
+    );
 }
 
 // Fifth lazy-static block is used to account for macro recursion limit.
@@ -1129,6 +1156,18 @@ lazy_static! {
         try_create_histogram("kzg_verification_single_seconds", "Runtime of single kzg verification");
     pub static ref KZG_VERIFICATION_BATCH_TIMES: Result<Histogram> =
         try_create_histogram("kzg_verification_batch_seconds", "Runtime of batched kzg verification");
+    pub static ref KZG_VERIFICATION_QUEUE_LENGTH: Result<IntGauge> = try_create_int_gauge(
+        "kzg_verification_queue_length",
+        "Number of kzg verification tasks waiting for a thread"
+    );
+    pub static ref KZG_VERIFICATION_QUEUE_TIMES: Result<Histogram> = try_create_histogram(
+        "kzg_verification_queue_seconds",
+        "Time kzg verification tasks spend waiting for a thread"
+    );
+    pub static ref KZG_VERIFICATION_ACTIVE_TASKS: Result<IntGauge> = try_create_int_gauge(
+        "kzg_verification_active_tasks",
+        "Number of kzg verification tasks running on a thread"
+    );
 
     pub static ref BLOCK_PRODUCTION_BLOBS_VERIFICATION_TIMES: Result<Histogram> = try_create_histogram(
             "beacon_block_production_blobs_verification_seconds",
@@ -1142,6 +1181,25 @@ lazy_static! {
         "Duration between start of the slot and the time at which all components of the block are available.",
     );
 
+    /*
+    * Slot task scheduler
+    */
+    pub static ref SLOT_TASK_TIMES: Result<HistogramVec> = try_create_histogram_vec(
+        "beacon_slot_task_seconds",
+        "Runtime of each task started by the slot task scheduler",
+        &["task"]
+    );
+    pub static ref SLOT_TASK_MISSED_DEADLINES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_slot_task_missed_deadlines_total",
+        "Count of slot tasks which completed after their deadline",
+        &["task"]
+    );
+    pub static ref SLOT_TASK_OVERLOADED: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_slot_task_overloaded_total",
+        "Count of slot tasks which were not started because their previous run was still in progress",
+        &["task"]
+    );
+
     /*
     * Data Availability cache metrics
     */
@@ -1176,6 +1234,17 @@ lazy_static! {
         "beacon_light_client_server_cache_prev_block_cache_miss",
         "Count of prev block cache misses",
     );
+
+    /*
+    * Lock contention
+    */
+    pub static ref LOCK_WAIT_TIMES: Result<HistogramVec> = try_create_histogram_vec_with_buckets(
+        "beacon_lock_wait_seconds",
+        "Time spent waiting to acquire critical beacon chain locks",
+        // 10us to ~2.6s
+        exponential_buckets(1e-5, 4.0, 10),
+        &["lock", "access"]
+    );
 }
 
 /// Scrape the `beacon_chain` for metrics that are not constantly updated (e.g., the present slot,
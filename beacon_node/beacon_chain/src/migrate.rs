@@ -11,7 +11,7 @@ use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use store::hot_cold_store::{migrate_database, HotColdDBError};
 use store::iter::RootsIterator;
-use store::{Error, ItemStore, StoreItem, StoreOp};
+use store::{get_key_for_col, DBColumn, Error, ItemStore, KeyValueStoreOp, StoreItem, StoreOp};
 pub use store::{HotColdDB, MemoryStore};
 use types::{
     BeaconState, BeaconStateError, BeaconStateHash, Checkpoint, Epoch, EthSpec, Hash256,
@@ -678,11 +678,16 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> BackgroundMigrator<E, Ho
                     StoreOp::DeleteBlobs(block_root),
                 ]
             })
-            .chain(
-                abandoned_states
-                    .into_iter()
-                    .map(|(slot, state_hash)| StoreOp::DeleteState(state_hash.into(), Some(slot))),
-            )
+            .chain(abandoned_states.into_iter().flat_map(|(slot, state_hash)| {
+                let state_root: Hash256 = state_hash.into();
+                [
+                    StoreOp::DeleteState(state_root, Some(slot)),
+                    StoreOp::KeyValueOp(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                        DBColumn::BeaconParticipationSummary.into(),
+                        state_root.as_bytes(),
+                    ))),
+                ]
+            }))
             .collect();
 
         // Persist the head in case the process is killed or crashes here. This prevents
@@ -4,9 +4,10 @@
 //! to this cache to reduce DoS risks.
 
 use crate::observed_block_producers::ProposalKey;
-use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::marker::PhantomData;
-use types::{BlobSidecar, EthSpec, Slot};
+use types::{BlobSidecar, ChainSpec, EthSpec, Hash256, Slot};
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
@@ -29,23 +30,25 @@ pub enum Error {
 /// like checking the proposer signature.
 pub struct ObservedBlobSidecars<E: EthSpec> {
     finalized_slot: Slot,
-    /// Stores all received blob indices for a given `(ValidatorIndex, Slot)` tuple.
-    items: HashMap<ProposalKey, HashSet<u64>>,
+    /// Stores all received blob indices for a given `(ValidatorIndex, Slot)` tuple, along with
+    /// the root of the block header carried by the first sidecar seen at each index.
+    items: HashMap<ProposalKey, HashMap<u64, Hash256>>,
+    /// The maximum number of blobs per block allowed by the `ChainSpec`.
+    max_blobs_per_block: u64,
     _phantom: PhantomData<E>,
 }
 
-impl<E: EthSpec> Default for ObservedBlobSidecars<E> {
+impl<E: EthSpec> ObservedBlobSidecars<E> {
     /// Instantiates `Self` with `finalized_slot == 0`.
-    fn default() -> Self {
+    pub fn new(spec: &ChainSpec) -> Self {
         Self {
             finalized_slot: Slot::new(0),
             items: HashMap::new(),
+            max_blobs_per_block: spec.max_blobs_per_block::<E>(),
             _phantom: PhantomData,
         }
     }
-}
 
-impl<E: EthSpec> ObservedBlobSidecars<E> {
     /// Observe the `blob_sidecar` at (`blob_sidecar.block_proposer_index, blob_sidecar.slot`).
     /// This will update `self` so future calls to it indicate that this `blob_sidecar` is known.
     ///
@@ -59,10 +62,15 @@ impl<E: EthSpec> ObservedBlobSidecars<E> {
                 slot: blob_sidecar.slot(),
                 proposer: blob_sidecar.block_proposer_index(),
             })
-            .or_insert_with(|| HashSet::with_capacity(E::max_blobs_per_block()));
-        let did_not_exist = blob_indices.insert(blob_sidecar.index);
-
-        Ok(!did_not_exist)
+            .or_insert_with(|| HashMap::with_capacity(self.max_blobs_per_block as usize));
+
+        match blob_indices.entry(blob_sidecar.index) {
+            Entry::Occupied(_) => Ok(true),
+            Entry::Vacant(entry) => {
+                entry.insert(blob_sidecar.block_root());
+                Ok(false)
+            }
+        }
     }
 
     /// Returns `true` if the `blob_sidecar` has already been observed in the cache within the prune window.
@@ -75,13 +83,29 @@ impl<E: EthSpec> ObservedBlobSidecars<E> {
                 proposer: blob_sidecar.block_proposer_index(),
             })
             .map_or(false, |blob_indices| {
-                blob_indices.contains(&blob_sidecar.index)
+                blob_indices.contains_key(&blob_sidecar.index)
             });
         Ok(is_known)
     }
 
+    /// Returns `true` if a sidecar for the same `(slot, proposer, index)` as `blob_sidecar` has
+    /// been observed, but for a different block. Since observed sidecars have a valid proposer
+    /// signature, this means the proposer has signed two distinct blocks at the same slot.
+    pub fn is_equivocation(&self, blob_sidecar: &BlobSidecar<E>) -> Result<bool, Error> {
+        self.sanitize_blob_sidecar(blob_sidecar)?;
+        let is_equivocation = self
+            .items
+            .get(&ProposalKey {
+                slot: blob_sidecar.slot(),
+                proposer: blob_sidecar.block_proposer_index(),
+            })
+            .and_then(|blob_indices| blob_indices.get(&blob_sidecar.index))
+            .map_or(false, |block_root| *block_root != blob_sidecar.block_root());
+        Ok(is_equivocation)
+    }
+
     fn sanitize_blob_sidecar(&self, blob_sidecar: &BlobSidecar<E>) -> Result<(), Error> {
-        if blob_sidecar.index >= E::max_blobs_per_block() as u64 {
+        if blob_sidecar.index >= self.max_blobs_per_block {
             return Err(Error::InvalidBlobIndex(blob_sidecar.index));
         }
         let finalized_slot = self.finalized_slot;
@@ -117,15 +141,16 @@ mod tests {
 
     fn get_blob_sidecar(slot: u64, proposer_index: u64, index: u64) -> Arc<BlobSidecar<E>> {
         let mut blob_sidecar = BlobSidecar::empty();
-        blob_sidecar.signed_block_header.message.slot = slot.into();
-        blob_sidecar.signed_block_header.message.proposer_index = proposer_index;
+        let header = &mut blob_sidecar.signed_block_header_mut().message;
+        header.slot = slot.into();
+        header.proposer_index = proposer_index;
         blob_sidecar.index = index;
         Arc::new(blob_sidecar)
     }
 
     #[test]
     fn pruning() {
-        let mut cache = ObservedBlobSidecars::default();
+        let mut cache = ObservedBlobSidecars::new(&E::default_spec());
 
         assert_eq!(cache.finalized_slot, 0, "finalized slot is zero");
         assert_eq!(cache.items.len(), 0, "no slots should be present");
@@ -263,7 +288,7 @@ mod tests {
 
     #[test]
     fn simple_observations() {
-        let mut cache = ObservedBlobSidecars::default();
+        let mut cache = ObservedBlobSidecars::new(&E::default_spec());
 
         // Slot 0, index 0
         let proposer_index_a = 420;
@@ -389,15 +414,9 @@ mod tests {
         );
 
         // Create a sidecar sharing slot and proposer but with a different block root.
-        let mut sidecar_d: BlobSidecar<E> = BlobSidecar {
-            index: sidecar_c.index,
-            blob: sidecar_c.blob.clone(),
-            kzg_commitment: sidecar_c.kzg_commitment,
-            kzg_proof: sidecar_c.kzg_proof,
-            signed_block_header: sidecar_c.signed_block_header.clone(),
-            kzg_commitment_inclusion_proof: sidecar_c.kzg_commitment_inclusion_proof.clone(),
-        };
-        sidecar_d.signed_block_header.message.body_root = Hash256::repeat_byte(7);
+        let mut sidecar_d: BlobSidecar<E> = (*sidecar_c).clone();
+        sidecar_d.signed_block_header_mut().message.body_root = Hash256::repeat_byte(7);
+        assert_ne!(sidecar_d.block_root(), sidecar_c.block_root());
         assert_eq!(
             cache.proposer_is_known(&sidecar_d),
             Ok(true),
@@ -408,6 +427,16 @@ mod tests {
             Ok(true),
             "indicates sidecar proposer was observed"
         );
+        assert_eq!(
+            cache.is_equivocation(&sidecar_d),
+            Ok(true),
+            "sidecar for a different block is an equivocation"
+        );
+        assert_eq!(
+            cache.is_equivocation(&sidecar_c),
+            Ok(false),
+            "originally observed sidecar is not an equivocation"
+        );
         let cached_blob_indices = cache
             .items
             .get(&ProposalKey::new(proposer_index_a, Slot::new(0)))
@@ -427,4 +456,21 @@ mod tests {
             "cannot add an index > MaxBlobsPerBlock"
         );
     }
+
+    #[test]
+    fn blob_index_limited_by_spec() {
+        let mut spec = E::default_spec();
+        spec.max_blobs_per_block = 3;
+        let mut cache = ObservedBlobSidecars::new(&spec);
+
+        let sidecar = get_blob_sidecar(0, 420, 2);
+        assert_eq!(cache.observe_sidecar(&sidecar), Ok(false));
+
+        let sidecar = get_blob_sidecar(0, 420, 3);
+        assert_eq!(
+            cache.observe_sidecar(&sidecar),
+            Err(Error::InvalidBlobIndex(3)),
+            "cannot add an index >= the spec's max_blobs_per_block"
+        );
+    }
 }
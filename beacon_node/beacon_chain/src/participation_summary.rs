@@ -0,0 +1,179 @@
+//! Compact records of validator participation, persisted at each epoch transition.
+//!
+//! Without them, the attestation performance API has to replay blocks to recover participation,
+//! which limits queries to short ranges. When `ChainConfig::store_participation_summaries` is
+//! enabled, the head, target and source flags of every validator are stored as bitfields for each
+//! epoch, so that long ranges can be answered from the database alone.
+//!
+//! A summary is keyed by the root of the state which was input to the epoch transition that
+//! produced it, i.e. the state at the last slot of the epoch *after* the one it describes. This
+//! keeps summaries from different forks apart: they are only ever read via the canonical chain's
+//! state roots, and those of abandoned forks are deleted along with the fork's other states.
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use slog::error;
+use ssz::{Decode, Encode};
+use ssz_derive::{Decode, Encode};
+use state_processing::per_epoch_processing::EpochProcessingSummary;
+use store::{DBColumn, Error as StoreError, StoreItem};
+use types::{BeaconStateError, Epoch, EthSpec, Hash256};
+
+/// The previous-epoch participation of every validator, as of some epoch transition.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct ParticipationSummary {
+    /// The epoch that the participation relates to.
+    pub epoch: Epoch,
+    active_unslashed: Vec<u8>,
+    source: Vec<u8>,
+    target: Vec<u8>,
+    head: Vec<u8>,
+}
+
+impl ParticipationSummary {
+    /// Summarise the previous-epoch participation from `summary`.
+    ///
+    /// Returns `None` for phase 0, where participation is accompanied by inclusion delays which
+    /// are not stored, so queries for those epochs continue to use block replay.
+    pub fn from_epoch_processing_summary<E: EthSpec>(
+        summary: &EpochProcessingSummary<E>,
+    ) -> Result<Option<Self>, BeaconStateError> {
+        let EpochProcessingSummary::Altair { participation, .. } = summary else {
+            return Ok(None);
+        };
+
+        let num_validators = participation.num_validators();
+        let empty = vec![0; num_validators.div_ceil(8)];
+        let mut result = Self {
+            epoch: participation.previous_epoch(),
+            active_unslashed: empty.clone(),
+            source: empty.clone(),
+            target: empty.clone(),
+            head: empty,
+        };
+        for index in 0..num_validators {
+            if summary.is_active_unslashed_in_previous_epoch(index) {
+                set_bit(&mut result.active_unslashed, index);
+            }
+            if summary.is_previous_epoch_source_attester(index)? {
+                set_bit(&mut result.source, index);
+            }
+            if summary.is_previous_epoch_target_attester(index)? {
+                set_bit(&mut result.target, index);
+            }
+            if summary.is_previous_epoch_head_attester(index)? {
+                set_bit(&mut result.head, index);
+            }
+        }
+
+        Ok(Some(result))
+    }
+
+    /// Returns `false` for unknown validators, as per `EpochProcessingSummary`.
+    pub fn is_active_unslashed(&self, index: usize) -> bool {
+        get_bit(&self.active_unslashed, index)
+    }
+
+    pub fn is_source_attester(&self, index: usize) -> bool {
+        get_bit(&self.source, index)
+    }
+
+    pub fn is_target_attester(&self, index: usize) -> bool {
+        get_bit(&self.target, index)
+    }
+
+    pub fn is_head_attester(&self, index: usize) -> bool {
+        get_bit(&self.head, index)
+    }
+}
+
+fn set_bit(bitfield: &mut [u8], index: usize) {
+    if let Some(byte) = bitfield.get_mut(index / 8) {
+        *byte |= 1 << (index % 8);
+    }
+}
+
+fn get_bit(bitfield: &[u8], index: usize) -> bool {
+    bitfield
+        .get(index / 8)
+        .map_or(false, |byte| byte & (1 << (index % 8)) != 0)
+}
+
+impl StoreItem for ParticipationSummary {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconParticipationSummary
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, StoreError> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Store the participation from `summary`, which was produced by the epoch transition of the
+    /// state with `state_root`.
+    ///
+    /// Does nothing unless `ChainConfig::store_participation_summaries` is set. Errors are logged
+    /// rather than returned, since they should not interrupt block import.
+    pub(crate) fn persist_participation_summary(
+        &self,
+        state_root: Hash256,
+        summary: &EpochProcessingSummary<T::EthSpec>,
+    ) {
+        if !self.config.store_participation_summaries {
+            return;
+        }
+
+        let result = ParticipationSummary::from_epoch_processing_summary(summary)
+            .map_err(BeaconChainError::from)
+            .and_then(|participation| match participation {
+                Some(participation) => Ok(self.store.put_item(&state_root, &participation)?),
+                None => Ok(()),
+            });
+        if let Err(e) = result {
+            error!(
+                self.log,
+                "Failed to store participation summary";
+                "state_root" => ?state_root,
+                "error" => ?e,
+            );
+        }
+    }
+
+    /// Load the stored participation for `epoch` on the canonical chain, if any.
+    pub fn get_participation_summary(
+        &self,
+        epoch: Epoch,
+    ) -> Result<Option<ParticipationSummary>, BeaconChainError> {
+        let transition_slot = (epoch + 1).end_slot(T::EthSpec::slots_per_epoch());
+        let Some(state_root) = self.state_root_at_slot(transition_slot)? else {
+            return Ok(None);
+        };
+        Ok(self
+            .store
+            .get_item::<ParticipationSummary>(&state_root)?
+            .filter(|participation| participation.epoch == epoch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitfield_round_trip() {
+        let mut bitfield = vec![0; 2];
+        for index in [0, 7, 9] {
+            set_bit(&mut bitfield, index);
+        }
+        // Out of bounds indices are ignored.
+        set_bit(&mut bitfield, 16);
+
+        let set = (0..20)
+            .filter(|index| get_bit(&bitfield, *index))
+            .collect::<Vec<_>>();
+        assert_eq!(set, vec![0, 7, 9]);
+    }
+}
@@ -1,72 +1,42 @@
+use crate::slot_tasks::{SlotTask, SlotTaskPriority};
 use crate::{BeaconChain, BeaconChainTypes};
 use slog::{debug, error};
 use slot_clock::SlotClock;
 use std::sync::Arc;
-use task_executor::TaskExecutor;
-use tokio::time::sleep;
+use types::Slot;
 
-/// Spawns a routine which ensures the EL is provided advance notice of any block producers.
+/// Registers a slot task which ensures the EL is provided advance notice of any block producers.
 ///
-/// This routine will run once per slot, at `chain.prepare_payload_lookahead()`
+/// This task will run once per slot, at `chain.prepare_payload_lookahead()`
 /// before the start of each slot.
 ///
-/// The service will not be started if there is no `execution_layer` on the `chain`.
-pub fn start_proposer_prep_service<T: BeaconChainTypes>(
-    executor: TaskExecutor,
-    chain: Arc<BeaconChain<T>>,
-) {
-    // Avoid spawning the service if there's no EL, it'll just error anyway.
+/// The task will not be registered if there is no `execution_layer` on the `chain`.
+pub fn register_proposer_prep_task<T: BeaconChainTypes>(chain: &BeaconChain<T>) {
+    // Avoid registering the task if there's no EL, it'll just error anyway.
     if chain.execution_layer.is_some() {
-        executor.clone().spawn(
-            async move { proposer_prep_service(executor, chain).await },
-            "proposer_prep_service",
-        );
+        let slot_duration = chain.slot_clock.slot_duration();
+        chain.slot_tasks.register(SlotTask::new(
+            "proposer_prep",
+            SlotTaskPriority::Normal,
+            slot_duration.saturating_sub(chain.config.prepare_payload_lookahead),
+            slot_duration,
+            proposer_prep,
+        ));
     }
 }
 
-/// Loop indefinitely, calling `BeaconChain::prepare_beacon_proposer_async` at an interval.
-async fn proposer_prep_service<T: BeaconChainTypes>(
-    executor: TaskExecutor,
-    chain: Arc<BeaconChain<T>>,
-) {
-    let slot_duration = chain.slot_clock.slot_duration();
-
-    loop {
-        match chain.slot_clock.duration_to_next_slot() {
-            Some(duration) => {
-                let additional_delay =
-                    slot_duration.saturating_sub(chain.config.prepare_payload_lookahead);
-                sleep(duration + additional_delay).await;
+/// Calls `BeaconChain::prepare_beacon_proposer` for the proposer of the slot after `current_slot`.
+async fn proposer_prep<T: BeaconChainTypes>(chain: Arc<BeaconChain<T>>, current_slot: Slot) {
+    debug!(
+        chain.log,
+        "Proposer prepare routine firing";
+    );
 
-                debug!(
-                    chain.log,
-                    "Proposer prepare routine firing";
-                );
-
-                let inner_chain = chain.clone();
-                executor.spawn(
-                    async move {
-                        if let Ok(current_slot) = inner_chain.slot() {
-                            if let Err(e) = inner_chain.prepare_beacon_proposer(current_slot).await
-                            {
-                                error!(
-                                    inner_chain.log,
-                                    "Proposer prepare routine failed";
-                                    "error" => ?e
-                                );
-                            }
-                        } else {
-                            debug!(inner_chain.log, "No slot for proposer prepare routine");
-                        }
-                    },
-                    "proposer_prep_update",
-                );
-            }
-            None => {
-                error!(chain.log, "Failed to read slot clock");
-                // If we can't read the slot clock, just wait another slot.
-                sleep(slot_duration).await;
-            }
-        };
+    if let Err(e) = chain.prepare_beacon_proposer(current_slot).await {
+        error!(
+            chain.log,
+            "Proposer prepare routine failed";
+            "error" => ?e
+        );
     }
 }
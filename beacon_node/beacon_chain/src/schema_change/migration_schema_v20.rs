@@ -0,0 +1,64 @@
+use crate::beacon_chain::BeaconChainTypes;
+use slog::{debug, info, Logger};
+use ssz::Encode;
+use std::sync::Arc;
+use store::{
+    get_key_for_col, metadata::HOT_STATE_DIFF_BASES_KEY, BeaconStateStorageContainer, DBColumn,
+    Error, HotColdDB, KeyValueStore, KeyValueStoreOp,
+};
+use types::Hash256;
+
+pub fn upgrade_to_v20<T: BeaconChainTypes>(
+    _db: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    log: Logger,
+) -> Result<Vec<KeyValueStoreOp>, Error> {
+    // No-op: existing full states remain valid, and new epoch-boundary states will be stored as
+    // diffs.
+    info!(log, "Upgrading to v20 schema");
+    Ok(vec![])
+}
+
+pub fn downgrade_from_v20<T: BeaconChainTypes>(
+    db: Arc<HotColdDB<T::EthSpec, T::HotStore, T::ColdStore>>,
+    log: Logger,
+) -> Result<Vec<KeyValueStoreOp>, Error> {
+    let state_roots = db
+        .hot_db
+        .iter_column_keys::<Hash256>(DBColumn::BeaconStateDiff)
+        .collect::<Result<Vec<_>, _>>()?;
+    debug!(
+        log,
+        "Collected {} state diffs to convert",
+        state_roots.len()
+    );
+
+    // Store each state in full, one at a time to limit memory usage. Converting a state doesn't
+    // prevent the states diffed against it from being loaded, so the order doesn't matter.
+    for state_root in &state_roots {
+        let state = db.load_hot_boundary_state(state_root)?.ok_or_else(|| {
+            Error::SchemaMigrationError(format!("missing state diff {state_root:?}"))
+        })?;
+        let ops = vec![
+            KeyValueStoreOp::PutKeyValue(
+                get_key_for_col(DBColumn::BeaconState.into(), state_root.as_bytes()),
+                BeaconStateStorageContainer::new(&state).as_ssz_bytes(),
+            ),
+            KeyValueStoreOp::DeleteKey(get_key_for_col(
+                DBColumn::BeaconStateDiff.into(),
+                state_root.as_bytes(),
+            )),
+        ];
+        db.hot_db.do_atomically(ops)?;
+    }
+
+    info!(
+        log,
+        "Downgrading to v19 schema";
+        "converted_states" => state_roots.len(),
+    );
+    // The list of diff bases is unknown to v19.
+    Ok(vec![KeyValueStoreOp::DeleteKey(get_key_for_col(
+        DBColumn::BeaconMeta.into(),
+        HOT_STATE_DIFF_BASES_KEY.as_bytes(),
+    ))])
+}
@@ -0,0 +1,160 @@
+//! Provides a task which runs at the start of each epoch and ensures that the shufflings for the
+//! new epoch are cached before its first blocks and attestations arrive.
+//!
+//! The state advance timer already primes these caches when it advances the head state across an
+//! epoch boundary. However, it only advances the head state a single slot past the head block, so
+//! if the last slot(s) of an epoch are skipped then the first block and attestations of the next
+//! epoch would have to compute the shufflings on the hot path. This task fills that gap by
+//! computing:
+//!
+//! - The proposer shuffling for the new epoch.
+//! - The attester shufflings for the new epoch and the epoch after it.
+//!
+//! The proposer shuffling for the epoch after the new epoch depends on the effective balances at
+//! its start, so it can't be computed ahead of the transition into that epoch.
+use crate::{
+    beacon_chain::ATTESTATION_CACHE_LOCK_TIMEOUT,
+    slot_tasks::{SlotTask, SlotTaskPriority},
+    BeaconChain, BeaconChainError, BeaconChainTypes,
+};
+use slog::{debug, error};
+use slot_clock::SlotClock;
+use state_processing::state_advance::partial_state_advance;
+use std::sync::Arc;
+use types::{AttestationShufflingId, EthSpec, RelativeEpoch, Slot};
+
+/// Registers a slot task which precomputes the shufflings for each new epoch, at the start of its
+/// first slot. It should complete before attestations for that slot are due.
+pub fn register_shuffling_precompute_task<T: BeaconChainTypes>(chain: &BeaconChain<T>) {
+    let slot_duration = chain.slot_clock.slot_duration();
+    chain.slot_tasks.register(SlotTask::new(
+        "shuffling_precompute",
+        SlotTaskPriority::Normal,
+        slot_duration / 24,
+        slot_duration / 3,
+        shuffling_precompute,
+    ));
+}
+
+/// Calls `precompute_shufflings` on a blocking thread, on the first slot of each epoch.
+async fn shuffling_precompute<T: BeaconChainTypes>(chain: Arc<BeaconChain<T>>, current_slot: Slot) {
+    if current_slot % T::EthSpec::slots_per_epoch() != 0 {
+        return;
+    }
+
+    let inner_chain = chain.clone();
+    let result = chain
+        .spawn_blocking_handle(
+            move || precompute_shufflings(&inner_chain, current_slot),
+            "shuffling_precompute_blocking",
+        )
+        .await
+        .and_then(|result| result);
+
+    match result {
+        Ok(true) => debug!(
+            chain.log,
+            "Precomputed shufflings for new epoch";
+            "epoch" => current_slot.epoch(T::EthSpec::slots_per_epoch()),
+        ),
+        Ok(false) => (),
+        Err(e) => error!(
+            chain.log,
+            "Failed to precompute shufflings";
+            "error" => ?e,
+        ),
+    }
+}
+
+/// Ensure that the proposer shuffling for the epoch of `current_slot` and the attester shufflings
+/// for that epoch and the next are cached for the canonical head.
+///
+/// Only acts when the head block is in the epoch prior to `current_slot`, since block import
+/// caches the shufflings for the epoch of the block and the next. Returns `true` if any shuffling
+/// was computed.
+pub fn precompute_shufflings<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    current_slot: Slot,
+) -> Result<bool, BeaconChainError> {
+    let slots_per_epoch = T::EthSpec::slots_per_epoch();
+    let current_epoch = current_slot.epoch(slots_per_epoch);
+    let head_block_root = chain.canonical_head.cached_head().head_block_root();
+    let head_block = chain
+        .canonical_head
+        .fork_choice_read_lock()
+        .get_block(&head_block_root);
+    let Some(head_block) = head_block else {
+        return Ok(false);
+    };
+
+    // Don't compute shufflings for a stale head, e.g. during sync.
+    if head_block.slot.epoch(slots_per_epoch) + 1 != current_epoch {
+        return Ok(false);
+    }
+
+    // The head block is the last block prior to `current_epoch`, so it decides both the proposer
+    // shuffling for `current_epoch` and the attester shuffling for the epoch after.
+    let attester_shuffling_ids = [
+        head_block.next_epoch_shuffling_id.clone(),
+        AttestationShufflingId::from_components(current_epoch + 1, head_block_root),
+    ];
+    let proposers_cached = chain
+        .beacon_proposer_cache
+        .lock()
+        .get_epoch::<T::EthSpec>(head_block_root, current_epoch)
+        .is_some();
+    let attester_shufflings_cached = {
+        let shuffling_cache = chain
+            .shuffling_cache
+            .try_read_for(ATTESTATION_CACHE_LOCK_TIMEOUT)
+            .ok_or(BeaconChainError::AttestationCacheLockTimeout)?;
+        attester_shuffling_ids
+            .iter()
+            .all(|shuffling_id| shuffling_cache.contains(shuffling_id))
+    };
+    if proposers_cached && attester_shufflings_cached {
+        return Ok(false);
+    }
+
+    // Advance the head state into `current_epoch`, using the "partial" method since the state
+    // roots are not relevant for the shufflings.
+    let target_slot = current_epoch.start_slot(slots_per_epoch);
+    let (state_root, mut state) = chain
+        .store
+        .get_advanced_hot_state(head_block_root, target_slot, head_block.state_root)?
+        .ok_or(BeaconChainError::MissingBeaconState(head_block.state_root))?;
+    if state.slot() < target_slot {
+        partial_state_advance(&mut state, Some(state_root), target_slot, &chain.spec)?;
+    }
+
+    if !proposers_cached {
+        let proposers = state.get_beacon_proposer_indices(&chain.spec)?;
+        chain.beacon_proposer_cache.lock().insert(
+            current_epoch,
+            head_block_root,
+            proposers,
+            state.fork(),
+        )?;
+    }
+
+    for relative_epoch in [RelativeEpoch::Current, RelativeEpoch::Next] {
+        let shuffling_id = AttestationShufflingId::new(head_block_root, &state, relative_epoch)?;
+        let shuffling_is_cached = chain
+            .shuffling_cache
+            .try_read_for(ATTESTATION_CACHE_LOCK_TIMEOUT)
+            .ok_or(BeaconChainError::AttestationCacheLockTimeout)?
+            .contains(&shuffling_id);
+
+        if !shuffling_is_cached {
+            state.build_committee_cache(relative_epoch, &chain.spec)?;
+            let committee_cache = state.committee_cache(relative_epoch)?;
+            chain
+                .shuffling_cache
+                .try_write_for(ATTESTATION_CACHE_LOCK_TIMEOUT)
+                .ok_or(BeaconChainError::AttestationCacheLockTimeout)?
+                .insert_committee_cache(shuffling_id, committee_cache);
+        }
+    }
+
+    Ok(true)
+}
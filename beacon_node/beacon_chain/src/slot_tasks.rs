@@ -0,0 +1,322 @@
+//! Provides a single scheduler for the work which the beacon chain performs at fixed points within
+//! each slot (e.g., advancing the head state, running fork choice ahead of the next slot or
+//! preparing payload attributes).
+//!
+//! Subsystems register a `SlotTask` with `BeaconChain::slot_tasks`, describing when within the slot
+//! it should start, when it should have completed by and its priority relative to other tasks
+//! starting at the same time. The scheduler then:
+//!
+//! - Starts each task at its offset into every slot, with higher priority tasks started first.
+//! - Starts any tasks which are overdue (e.g., because the scheduler woke up late) straight away,
+//!   rather than skipping them until the next slot.
+//! - Refuses to start a task whilst its previous run is still in progress.
+//! - Records the run time of every task and whether or not it met its deadline, via the
+//!   `beacon_slot_task_*` metrics.
+use crate::{metrics, BeaconChain, BeaconChainTypes};
+use parking_lot::Mutex;
+use slog::{debug, error, warn};
+use slot_clock::SlotClock;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+use task_executor::TaskExecutor;
+use tokio::time::{sleep, sleep_until, Instant};
+use types::Slot;
+
+type SlotTaskFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+type SlotTaskFn<T> = Box<dyn Fn(Arc<BeaconChain<T>>, Slot) -> SlotTaskFuture + Send + Sync>;
+
+/// The order in which tasks which are due at the same time are started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SlotTaskPriority {
+    High,
+    Normal,
+    Low,
+}
+
+/// Some work to be performed at a fixed offset into every slot.
+pub struct SlotTask<T: BeaconChainTypes> {
+    name: &'static str,
+    priority: SlotTaskPriority,
+    start: Duration,
+    deadline: Duration,
+    run: SlotTaskFn<T>,
+    is_running: Lock,
+}
+
+impl<T: BeaconChainTypes> SlotTask<T> {
+    /// Create a task called `name` which calls `run` with the current slot `start` into every
+    /// slot, and which should complete within `deadline` of the start of that slot.
+    ///
+    /// The `name` is used to label logs and metrics, so it should be unique.
+    pub fn new<F, R>(
+        name: &'static str,
+        priority: SlotTaskPriority,
+        start: Duration,
+        deadline: Duration,
+        run: F,
+    ) -> Self
+    where
+        F: Fn(Arc<BeaconChain<T>>, Slot) -> R + Send + Sync + 'static,
+        R: Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            name,
+            priority,
+            start,
+            deadline,
+            run: Box::new(move |chain, slot| Box::pin(run(chain, slot))),
+            is_running: Lock::new(),
+        }
+    }
+}
+
+/// The set of `SlotTask`s registered with a `BeaconChain`.
+pub struct SlotTaskScheduler<T: BeaconChainTypes> {
+    /// Sorted by start time, then priority.
+    tasks: Mutex<Vec<Arc<SlotTask<T>>>>,
+}
+
+impl<T: BeaconChainTypes> Default for SlotTaskScheduler<T> {
+    fn default() -> Self {
+        Self {
+            tasks: Mutex::new(vec![]),
+        }
+    }
+}
+
+impl<T: BeaconChainTypes> SlotTaskScheduler<T> {
+    /// Add `task` to the schedule.
+    ///
+    /// Tasks may be registered whilst the scheduler is running, they will be started from the
+    /// next slot onwards.
+    pub fn register(&self, task: SlotTask<T>) {
+        let mut tasks = self.tasks.lock();
+        tasks.push(Arc::new(task));
+        tasks.sort_by_key(|task| (task.start, task.priority));
+    }
+
+    /// Returns the names of the registered tasks, in the order in which they're started.
+    pub fn task_names(&self) -> Vec<&'static str> {
+        self.tasks.lock().iter().map(|task| task.name).collect()
+    }
+
+    fn tasks(&self) -> Vec<Arc<SlotTask<T>>> {
+        self.tasks.lock().clone()
+    }
+}
+
+/// Spawns the scheduler described in the module-level documentation.
+pub fn spawn_slot_task_scheduler<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    chain: Arc<BeaconChain<T>>,
+) {
+    executor.spawn(
+        slot_task_scheduler(executor.clone(), chain),
+        "slot_task_scheduler",
+    );
+}
+
+/// Provides the scheduler described in the module-level documentation.
+async fn slot_task_scheduler<T: BeaconChainTypes>(
+    executor: TaskExecutor,
+    chain: Arc<BeaconChain<T>>,
+) {
+    let slot_duration = chain.slot_clock.slot_duration();
+    let mut next_slot = None;
+
+    loop {
+        let (Some(current_slot), Some(prior_to_genesis)) = (
+            chain.slot_clock.now_or_genesis(),
+            chain.slot_clock.is_prior_to_genesis(),
+        ) else {
+            error!(chain.log, "Failed to read slot clock");
+            // If we can't read the slot clock, just wait another slot.
+            sleep(slot_duration).await;
+            continue;
+        };
+
+        let slot = slot_to_schedule(next_slot, current_slot, prior_to_genesis);
+        if next_slot.map_or(false, |next_slot| slot > next_slot) {
+            warn!(
+                chain.log,
+                "Slot tasks skipped";
+                "first_skipped_slot" => next_slot,
+                "resumed_slot" => slot,
+            );
+        }
+
+        let Some(slot_start) = slot_start_instant(&chain.slot_clock, slot) else {
+            error!(chain.log, "Failed to read slot clock");
+            sleep(slot_duration).await;
+            continue;
+        };
+        sleep_until(slot_start).await;
+        next_slot = Some(slot + 1);
+
+        for task in chain.slot_tasks.tasks() {
+            // Wait for the task to become due (which may already be past).
+            sleep_until(slot_start + task.start).await;
+
+            // Only start the task if its previous run has completed.
+            if task.is_running.lock() {
+                metrics::inc_counter_vec(&metrics::SLOT_TASK_OVERLOADED, &[task.name]);
+                warn!(
+                    chain.log,
+                    "Slot task overloaded";
+                    "task" => task.name,
+                    "slot" => slot,
+                    "msg" => "system resources may be overloaded"
+                );
+                continue;
+            }
+
+            let chain = chain.clone();
+            let deadline = slot_start + task.deadline;
+            let name = task.name;
+            executor.spawn(
+                async move {
+                    let started = Instant::now();
+                    (task.run)(chain.clone(), slot).await;
+                    let finished = Instant::now();
+
+                    let run_time = finished.duration_since(started);
+                    let missed_deadline = finished > deadline;
+                    metrics::observe_timer_vec(&metrics::SLOT_TASK_TIMES, &[task.name], run_time);
+                    if missed_deadline {
+                        metrics::inc_counter_vec(
+                            &metrics::SLOT_TASK_MISSED_DEADLINES,
+                            &[task.name],
+                        );
+                    }
+                    debug!(
+                        chain.log,
+                        "Completed slot task";
+                        "task" => task.name,
+                        "slot" => slot,
+                        "run_time_ms" => run_time.as_millis(),
+                        "missed_deadline" => missed_deadline,
+                    );
+
+                    // Permit this task to start again, next time it is due.
+                    task.is_running.unlock();
+                },
+                name,
+            );
+        }
+    }
+}
+
+/// Returns the slot whose tasks should be started next, given the slot after the last one that was
+/// scheduled (if any).
+///
+/// The tasks of every slot are scheduled in turn, even if the scheduler wakes up late and the slot
+/// has already started, in which case its overdue tasks are started straight away. Only if the
+/// scheduler falls more than a slot behind are slots skipped.
+fn slot_to_schedule(next_slot: Option<Slot>, current_slot: Slot, prior_to_genesis: bool) -> Slot {
+    match next_slot {
+        Some(next_slot) => std::cmp::max(next_slot, current_slot),
+        // Wait for the genesis slot to start.
+        None if prior_to_genesis => current_slot,
+        // Don't start tasks part of the way through the slot in which the scheduler starts.
+        None => current_slot + 1,
+    }
+}
+
+/// Returns the `Instant` at which `slot` starts, which may be in the past.
+fn slot_start_instant<S: SlotClock>(slot_clock: &S, slot: Slot) -> Option<Instant> {
+    let slot_start = slot_clock.start_of(slot)?;
+    let now_duration = slot_clock.now_duration()?;
+    let now = Instant::now();
+
+    match slot_start.checked_sub(now_duration) {
+        Some(until_slot_start) => Some(now + until_slot_start),
+        None => now.checked_sub(now_duration.saturating_sub(slot_start)),
+    }
+}
+
+/// Provides a simple thread-safe lock to be used for task co-ordination. Practically equivalent to
+/// `Mutex<()>`.
+#[derive(Clone)]
+struct Lock(Arc<AtomicBool>);
+
+impl Lock {
+    /// Instantiate an unlocked self.
+    pub fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    /// Lock self, returning `true` if the lock was already set.
+    pub fn lock(&self) -> bool {
+        self.0.fetch_or(true, Ordering::SeqCst)
+    }
+
+    /// Unlock self.
+    pub fn unlock(&self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::EphemeralHarnessType;
+    use types::MinimalEthSpec;
+
+    #[test]
+    fn lock() {
+        let lock = Lock::new();
+        assert!(!lock.lock());
+        assert!(lock.lock());
+        assert!(lock.lock());
+        lock.unlock();
+        assert!(!lock.lock());
+        assert!(lock.lock());
+    }
+
+    #[test]
+    fn tasks_ordered_by_start_then_priority() {
+        let slot_tasks = SlotTaskScheduler::<EphemeralHarnessType<MinimalEthSpec>>::default();
+        let task = |name, priority, start| {
+            SlotTask::new(
+                name,
+                priority,
+                Duration::from_secs(start),
+                Duration::from_secs(12),
+                |_, _| async {},
+            )
+        };
+
+        slot_tasks.register(task("late", SlotTaskPriority::High, 9));
+        slot_tasks.register(task("low", SlotTaskPriority::Low, 0));
+        slot_tasks.register(task("high", SlotTaskPriority::High, 0));
+        slot_tasks.register(task("normal", SlotTaskPriority::Normal, 0));
+
+        assert_eq!(
+            slot_tasks.task_names(),
+            vec!["high", "normal", "low", "late"]
+        );
+    }
+
+    #[test]
+    fn slot_to_schedule_after_late_wake() {
+        let slot = Slot::new(10);
+
+        // On startup, wait for the next slot, or for genesis.
+        assert_eq!(slot_to_schedule(None, slot, false), slot + 1);
+        assert_eq!(slot_to_schedule(None, Slot::new(0), true), Slot::new(0));
+
+        // After waking in time, the next slot's tasks are started at the start of that slot.
+        assert_eq!(slot_to_schedule(Some(slot + 1), slot, false), slot + 1);
+
+        // After waking late, the next slot's tasks are started straight away rather than skipped.
+        assert_eq!(slot_to_schedule(Some(slot + 1), slot + 1, false), slot + 1);
+
+        // After falling more than a slot behind, skip to the current slot.
+        assert_eq!(slot_to_schedule(Some(slot + 1), slot + 3, false), slot + 3);
+    }
+}
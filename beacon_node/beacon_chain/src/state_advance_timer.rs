@@ -1,4 +1,4 @@
-//! Provides a timer which runs in the tail-end of each slot and maybe advances the state of the
+//! Provides a task which runs in the tail-end of each slot and maybe advances the state of the
 //! head block forward a single slot.
 //!
 //! This provides an optimization with the following benefits:
@@ -15,19 +15,16 @@
 //! 2. There's a possibility that the head block is never built upon, causing wasted CPU cycles.
 use crate::validator_monitor::HISTORIC_EPOCHS as VALIDATOR_MONITOR_HISTORIC_EPOCHS;
 use crate::{
-    beacon_chain::ATTESTATION_CACHE_LOCK_TIMEOUT, chain_config::FORK_CHOICE_LOOKAHEAD_FACTOR,
+    beacon_chain::ATTESTATION_CACHE_LOCK_TIMEOUT,
+    chain_config::FORK_CHOICE_LOOKAHEAD_FACTOR,
+    slot_tasks::{SlotTask, SlotTaskPriority},
     BeaconChain, BeaconChainError, BeaconChainTypes,
 };
 use slog::{debug, error, warn, Logger};
 use slot_clock::SlotClock;
 use state_processing::per_slot_processing;
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc,
-};
+use std::sync::Arc;
 use store::KeyValueStore;
-use task_executor::TaskExecutor;
-use tokio::time::{sleep, sleep_until, Instant};
 use types::{AttestationShufflingId, BeaconStateError, EthSpec, Hash256, RelativeEpoch, Slot};
 
 /// If the head slot is more than `MAX_ADVANCE_DISTANCE` from the current slot, then don't perform
@@ -82,200 +79,123 @@ impl From<store::Error> for Error {
     }
 }
 
-/// Provides a simple thread-safe lock to be used for task co-ordination. Practically equivalent to
-/// `Mutex<()>`.
-#[derive(Clone)]
-struct Lock(Arc<AtomicBool>);
-
-impl Lock {
-    /// Instantiate an unlocked self.
-    pub fn new() -> Self {
-        Self(Arc::new(AtomicBool::new(false)))
-    }
-
-    /// Lock self, returning `true` if the lock was already set.
-    pub fn lock(&self) -> bool {
-        self.0.fetch_or(true, Ordering::SeqCst)
-    }
-
-    /// Unlock self.
-    pub fn unlock(&self) {
-        self.0.store(false, Ordering::SeqCst);
-    }
+/// Registers the state advance and the fork choice lookahead with the chain's slot task
+/// scheduler.
+///
+/// The state advance runs 3/4 of the way through each slot (9s on mainnet) and fork choice runs
+/// 23/24s of the way through each slot (11.5s on mainnet). Both should complete before the next
+/// slot begins.
+pub fn register_state_advance_tasks<T: BeaconChainTypes>(beacon_chain: &BeaconChain<T>) {
+    let slot_duration = beacon_chain.slot_clock.slot_duration();
+
+    beacon_chain.slot_tasks.register(SlotTask::new(
+        "state_advance",
+        SlotTaskPriority::High,
+        slot_duration - slot_duration / 4,
+        slot_duration,
+        |beacon_chain, _| state_advance(beacon_chain),
+    ));
+
+    beacon_chain.slot_tasks.register(SlotTask::new(
+        "fork_choice_lookahead",
+        SlotTaskPriority::High,
+        slot_duration - slot_duration / FORK_CHOICE_LOOKAHEAD_FACTOR,
+        slot_duration,
+        fork_choice_lookahead,
+    ));
 }
 
-/// Spawns the timer described in the module-level documentation.
-pub fn spawn_state_advance_timer<T: BeaconChainTypes>(
-    executor: TaskExecutor,
-    beacon_chain: Arc<BeaconChain<T>>,
-    log: Logger,
-) {
-    executor.spawn(
-        state_advance_timer(executor.clone(), beacon_chain, log),
-        "state_advance_timer",
-    );
+/// Advances the head state on a blocking thread, see the module-level documentation.
+async fn state_advance<T: BeaconChainTypes>(beacon_chain: Arc<BeaconChain<T>>) {
+    let log = beacon_chain.log.clone();
+    let inner_chain = beacon_chain.clone();
+    let result = beacon_chain
+        .spawn_blocking_handle(
+            move || advance_head(&inner_chain, &inner_chain.log),
+            "state_advance_blocking",
+        )
+        .await
+        .unwrap_or_else(|e| Err(Error::BeaconChain(e)));
+
+    match result {
+        Ok(()) => (),
+        Err(Error::BeaconChain(e)) => error!(
+            log,
+            "Failed to advance head state";
+            "error" => ?e
+        ),
+        Err(Error::StateAlreadyAdvanced { block_root }) => debug!(
+            log,
+            "State already advanced on slot";
+            "block_root" => ?block_root
+        ),
+        Err(Error::MaxDistanceExceeded {
+            current_slot,
+            head_slot,
+        }) => debug!(
+            log,
+            "Refused to advance head state";
+            "head_slot" => head_slot,
+            "current_slot" => current_slot,
+        ),
+        other => warn!(
+            log,
+            "Did not advance head state";
+            "reason" => ?other
+        ),
+    };
 }
 
-/// Provides the timer described in the module-level documentation.
-async fn state_advance_timer<T: BeaconChainTypes>(
-    executor: TaskExecutor,
+/// Runs fork choice pre-emptively for the next slot. This processes most of the attestations from
+/// `current_slot` off the hot path of block verification and production.
+async fn fork_choice_lookahead<T: BeaconChainTypes>(
     beacon_chain: Arc<BeaconChain<T>>,
-    log: Logger,
+    current_slot: Slot,
 ) {
-    let is_running = Lock::new();
-    let slot_clock = &beacon_chain.slot_clock;
-    let slot_duration = slot_clock.slot_duration();
-
-    loop {
-        let Some(duration_to_next_slot) = beacon_chain.slot_clock.duration_to_next_slot() else {
-            error!(log, "Failed to read slot clock");
-            // If we can't read the slot clock, just wait another slot.
-            sleep(slot_duration).await;
-            continue;
-        };
-
-        // Run the state advance 3/4 of the way through the slot (9s on mainnet).
-        let state_advance_offset = slot_duration / 4;
-        let state_advance_instant = if duration_to_next_slot > state_advance_offset {
-            Instant::now() + duration_to_next_slot - state_advance_offset
-        } else {
-            // Skip the state advance for the current slot and wait until the next one.
-            Instant::now() + duration_to_next_slot + slot_duration - state_advance_offset
-        };
-
-        // Run fork choice 23/24s of the way through the slot (11.5s on mainnet).
-        // We need to run after the state advance, so use the same condition as above.
-        let fork_choice_offset = slot_duration / FORK_CHOICE_LOOKAHEAD_FACTOR;
-        let fork_choice_instant = if duration_to_next_slot > state_advance_offset {
-            Instant::now() + duration_to_next_slot - fork_choice_offset
-        } else {
-            Instant::now() + duration_to_next_slot + slot_duration - fork_choice_offset
-        };
-
-        // Wait for the state advance.
-        sleep_until(state_advance_instant).await;
-
-        // Compute the current slot here at approx 3/4 through the slot. Even though this slot is
-        // only used by fork choice we need to calculate it here rather than after the state
-        // advance, in case the state advance flows over into the next slot.
-        let current_slot = match beacon_chain.slot() {
-            Ok(slot) => slot,
-            Err(e) => {
-                warn!(
-                    log,
-                    "Unable to determine slot in state advance timer";
-                    "error" => ?e
-                );
-                // If we can't read the slot clock, just wait another slot.
-                sleep(slot_duration).await;
-                continue;
-            }
-        };
-
-        // Only spawn the state advance task if the lock was previously free.
-        if !is_running.lock() {
-            let log = log.clone();
-            let beacon_chain = beacon_chain.clone();
-            let is_running = is_running.clone();
-
-            executor.spawn_blocking(
-                move || {
-                    match advance_head(&beacon_chain, &log) {
-                        Ok(()) => (),
-                        Err(Error::BeaconChain(e)) => error!(
-                            log,
-                            "Failed to advance head state";
-                            "error" => ?e
-                        ),
-                        Err(Error::StateAlreadyAdvanced { block_root }) => debug!(
-                            log,
-                            "State already advanced on slot";
-                            "block_root" => ?block_root
-                        ),
-                        Err(Error::MaxDistanceExceeded {
-                            current_slot,
-                            head_slot,
-                        }) => debug!(
-                            log,
-                            "Refused to advance head state";
-                            "head_slot" => head_slot,
-                            "current_slot" => current_slot,
-                        ),
-                        other => warn!(
-                            log,
-                            "Did not advance head state";
-                            "reason" => ?other
-                        ),
-                    };
-
-                    // Permit this blocking task to spawn again, next time the timer fires.
-                    is_running.unlock();
-                },
-                "state_advance_blocking",
-            );
-        } else {
+    let log = beacon_chain.log.clone();
+    let next_slot = current_slot + 1;
+
+    // Don't run fork choice during sync.
+    if beacon_chain.best_slot() + MAX_FORK_CHOICE_DISTANCE < current_slot {
+        return;
+    }
+
+    // Re-compute the head, dequeuing attestations for the current slot early.
+    beacon_chain.recompute_head_at_slot(next_slot).await;
+
+    // Prepare proposers so that the node can send payload attributes in the case where
+    // it decides to abandon a proposer boost re-org.
+    beacon_chain
+        .prepare_beacon_proposer(current_slot)
+        .await
+        .unwrap_or_else(|e| {
             warn!(
                 log,
-                "State advance routine overloaded";
-                "msg" => "system resources may be overloaded"
-            )
-        }
-
-        // Run fork choice pre-emptively for the next slot. This processes most of the attestations
-        // from this slot off the hot path of block verification and production.
-        // Wait for the fork choice instant (which may already be past).
-        sleep_until(fork_choice_instant).await;
-
-        let log = log.clone();
-        let beacon_chain = beacon_chain.clone();
-        let next_slot = current_slot + 1;
-        executor.spawn(
-            async move {
-                // Don't run fork choice during sync.
-                if beacon_chain.best_slot() + MAX_FORK_CHOICE_DISTANCE < current_slot {
-                    return;
+                "Unable to prepare proposer with lookahead";
+                "error" => ?e,
+                "slot" => next_slot,
+            );
+            None
+        });
+
+    // Use a blocking task to avoid blocking the core executor whilst waiting for locks
+    // in `ForkChoiceSignalTx`.
+    beacon_chain.task_executor.clone().spawn_blocking(
+        move || {
+            // Signal block proposal for the next slot (if it happens to be waiting).
+            if let Some(tx) = &beacon_chain.fork_choice_signal_tx {
+                if let Err(e) = tx.notify_fork_choice_complete(next_slot) {
+                    warn!(
+                        log,
+                        "Error signalling fork choice waiter";
+                        "error" => ?e,
+                        "slot" => next_slot,
+                    );
                 }
-
-                // Re-compute the head, dequeuing attestations for the current slot early.
-                beacon_chain.recompute_head_at_slot(next_slot).await;
-
-                // Prepare proposers so that the node can send payload attributes in the case where
-                // it decides to abandon a proposer boost re-org.
-                beacon_chain
-                    .prepare_beacon_proposer(current_slot)
-                    .await
-                    .unwrap_or_else(|e| {
-                        warn!(
-                            log,
-                            "Unable to prepare proposer with lookahead";
-                            "error" => ?e,
-                            "slot" => next_slot,
-                        );
-                        None
-                    });
-
-                // Use a blocking task to avoid blocking the core executor whilst waiting for locks
-                // in `ForkChoiceSignalTx`.
-                beacon_chain.task_executor.clone().spawn_blocking(
-                    move || {
-                        // Signal block proposal for the next slot (if it happens to be waiting).
-                        if let Some(tx) = &beacon_chain.fork_choice_signal_tx {
-                            if let Err(e) = tx.notify_fork_choice_complete(next_slot) {
-                                warn!(
-                                    log,
-                                    "Error signalling fork choice waiter";
-                                    "error" => ?e,
-                                    "slot" => next_slot,
-                                );
-                            }
-                        }
-                    },
-                    "fork_choice_advance_signal_tx",
-                );
-            },
-            "fork_choice_advance",
-        );
-    }
+            }
+        },
+        "fork_choice_advance_signal_tx",
+    );
 }
 
 /// Reads the `state_cache` from the `beacon_chain` and attempts to take a clone of the
@@ -351,6 +271,7 @@ fn advance_head<T: BeaconChainTypes>(
                 "error" => ?e
             );
         }
+        beacon_chain.persist_participation_summary(head_state_root, &summary);
 
         // Only notify the validator monitor for recent blocks.
         if state.current_epoch() + VALIDATOR_MONITOR_HISTORIC_EPOCHS as u64
@@ -484,19 +405,3 @@ fn advance_head<T: BeaconChainTypes>(
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn lock() {
-        let lock = Lock::new();
-        assert!(!lock.lock());
-        assert!(lock.lock());
-        assert!(lock.lock());
-        lock.unlock();
-        assert!(!lock.lock());
-        assert!(lock.lock());
-    }
-}
@@ -44,13 +44,13 @@ use state_processing::signature_sets::{
 };
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::time::Duration;
 use strum::AsRefStr;
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
 use types::consts::altair::SYNC_COMMITTEE_SUBNET_COUNT;
 use types::slot_data::SlotData;
 use types::sync_committee::Error as SyncCommitteeError;
-use types::ChainSpec;
 use types::{
     sync_committee_contribution::Error as ContributionError, AggregateSignature, BeaconStateError,
     EthSpec, Hash256, SignedContributionAndProof, Slot, SyncCommitteeContribution,
@@ -296,7 +296,11 @@ impl<T: BeaconChainTypes> VerifiedSyncContribution<T> {
         let subcommittee_index = contribution.subcommittee_index as usize;
 
         // Ensure sync committee contribution is within the MAXIMUM_GOSSIP_CLOCK_DISPARITY allowance.
-        verify_propagation_slot_range(&chain.slot_clock, contribution, &chain.spec)?;
+        verify_propagation_slot_range(
+            &chain.slot_clock,
+            contribution,
+            chain.config.maximum_gossip_clock_disparity(&chain.spec),
+        )?;
 
         // Validate subcommittee index.
         if contribution.subcommittee_index >= SYNC_COMMITTEE_SUBNET_COUNT {
@@ -459,7 +463,11 @@ impl VerifiedSyncCommitteeMessage {
         // MAXIMUM_GOSSIP_CLOCK_DISPARITY allowance).
         //
         // We do not queue future sync committee messages for later processing.
-        verify_propagation_slot_range(&chain.slot_clock, &sync_message, &chain.spec)?;
+        verify_propagation_slot_range(
+            &chain.slot_clock,
+            &sync_message,
+            chain.config.maximum_gossip_clock_disparity(&chain.spec),
+        )?;
 
         // Ensure the `subnet_id` is valid for the given validator.
         let pubkey = chain
@@ -571,15 +579,15 @@ impl VerifiedSyncCommitteeMessage {
 /// Verify that the `sync_contribution` is within the acceptable gossip propagation range, with reference
 /// to the current slot of the `chain`.
 ///
-/// Accounts for `MAXIMUM_GOSSIP_CLOCK_DISPARITY`.
+/// Accounts for `maximum_gossip_clock_disparity`.
 pub fn verify_propagation_slot_range<S: SlotClock, U: SlotData>(
     slot_clock: &S,
     sync_contribution: &U,
-    spec: &ChainSpec,
+    maximum_gossip_clock_disparity: Duration,
 ) -> Result<(), Error> {
     let message_slot = sync_contribution.get_slot();
     let latest_permissible_slot = slot_clock
-        .now_with_future_tolerance(spec.maximum_gossip_clock_disparity())
+        .now_with_future_tolerance(maximum_gossip_clock_disparity)
         .ok_or(BeaconChainError::UnableToReadSlot)?;
     if message_slot > latest_permissible_slot {
         return Err(Error::FutureSlot {
@@ -589,7 +597,7 @@ pub fn verify_propagation_slot_range<S: SlotClock, U: SlotData>(
     }
 
     let earliest_permissible_slot = slot_clock
-        .now_with_past_tolerance(spec.maximum_gossip_clock_disparity())
+        .now_with_past_tolerance(maximum_gossip_clock_disparity)
         .ok_or(BeaconChainError::UnableToReadSlot)?;
 
     if message_slot < earliest_permissible_slot {
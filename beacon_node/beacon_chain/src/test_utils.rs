@@ -54,7 +54,7 @@ use std::str::FromStr;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use store::{config::StoreConfig, HotColdDB, ItemStore, LevelDB, MemoryStore};
+use store::{config::StoreConfig, BeaconNodeBackend, HotColdDB, ItemStore, LevelDB, MemoryStore};
 use task_executor::TaskExecutor;
 use task_executor::{test_utils::TestRuntime, ShutdownReason};
 use tree_hash::TreeHash;
@@ -87,7 +87,7 @@ lazy_static! {
 pub type BaseHarnessType<E, THotStore, TColdStore> =
     Witness<TestingSlotClock, CachingEth1Backend<E>, E, THotStore, TColdStore>;
 
-pub type DiskHarnessType<E> = BaseHarnessType<E, LevelDB<E>, LevelDB<E>>;
+pub type DiskHarnessType<E> = BaseHarnessType<E, BeaconNodeBackend<E>, LevelDB<E>>;
 pub type EphemeralHarnessType<E> = BaseHarnessType<E, MemoryStore<E>, MemoryStore<E>>;
 
 pub type BoxedMutator<E, Hot, Cold> = Box<
@@ -264,7 +264,10 @@ impl<E: EthSpec> Builder<EphemeralHarnessType<E>> {
 
 impl<E: EthSpec> Builder<DiskHarnessType<E>> {
     /// Disk store, start from genesis.
-    pub fn fresh_disk_store(mut self, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) -> Self {
+    pub fn fresh_disk_store(
+        mut self,
+        store: Arc<HotColdDB<E, BeaconNodeBackend<E>, LevelDB<E>>>,
+    ) -> Self {
         let validator_keypairs = self
             .validator_keypairs
             .clone()
@@ -289,7 +292,10 @@ impl<E: EthSpec> Builder<DiskHarnessType<E>> {
     }
 
     /// Disk store, resume.
-    pub fn resumed_disk_store(mut self, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) -> Self {
+    pub fn resumed_disk_store(
+        mut self,
+        store: Arc<HotColdDB<E, BeaconNodeBackend<E>, LevelDB<E>>>,
+    ) -> Self {
         let mutator = move |builder: BeaconChainBuilder<_>| {
             builder
                 .resume_from_db()
@@ -2298,6 +2304,39 @@ where
         .await
     }
 
+    /// Uses `Self::extend_slots` to build `num_blocks` blocks with exactly `blobs_per_block`
+    /// blobs each, which are imported along with their blocks.
+    ///
+    /// The blobs and their KZG commitments and proofs come from the payloads of the mock
+    /// execution layer, so the harness must have one and the blocks must be post-Deneb.
+    pub async fn extend_chain_with_blobs(
+        &self,
+        num_blocks: usize,
+        blobs_per_block: usize,
+    ) -> Hash256 {
+        assert!(
+            blobs_per_block <= E::max_blobs_per_block(),
+            "can't produce more than {} blobs per block",
+            E::max_blobs_per_block()
+        );
+        let mock_execution_layer = self
+            .mock_execution_layer
+            .as_ref()
+            .expect("producing blobs requires a mock execution layer");
+
+        mock_execution_layer
+            .server
+            .execution_block_generator()
+            .blobs_per_payload = Some(blobs_per_block);
+        let head_block_root = self.extend_slots(num_blocks).await;
+        mock_execution_layer
+            .server
+            .execution_block_generator()
+            .blobs_per_payload = None;
+
+        head_block_root
+    }
+
     /// Uses `Self::extend_chain` to `num_slots` blocks.
     ///
     /// Utilizes:
@@ -2553,30 +2592,10 @@ pub fn generate_rand_block_and_blobs<E: EthSpec>(
         _ => return (block, blob_sidecars),
     };
 
-    let eth2::types::BlobsBundle {
-        commitments,
-        proofs,
-        blobs,
-    } = bundle;
-
-    for (index, ((blob, kzg_commitment), kzg_proof)) in blobs
-        .into_iter()
-        .zip(commitments.into_iter())
-        .zip(proofs.into_iter())
-        .enumerate()
-    {
-        blob_sidecars.push(BlobSidecar {
-            index: index as u64,
-            blob: blob.clone(),
-            kzg_commitment,
-            kzg_proof,
-            signed_block_header: block.signed_block_header(),
-            kzg_commitment_inclusion_proof: block
-                .message()
-                .body()
-                .kzg_commitment_merkle_proof(index)
-                .unwrap(),
-        });
+    let eth2::types::BlobsBundle { proofs, blobs, .. } = bundle;
+
+    for (index, (blob, kzg_proof)) in blobs.into_iter().zip(proofs.into_iter()).enumerate() {
+        blob_sidecars.push(BlobSidecar::new(index, blob, &block, kzg_proof).unwrap());
     }
     (block, blob_sidecars)
 }
@@ -1,3 +1,4 @@
+use crate::metered_lock::LockWaitTimer;
 use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
@@ -7,7 +8,10 @@ use std::time::Duration;
 ///
 /// Timeouts can be optionally disabled at runtime for all instances of this type by calling
 /// `TimeoutRwLock::disable_timeouts()`.
-pub struct TimeoutRwLock<T>(RwLock<T>);
+///
+/// Locks created with `TimeoutRwLock::new_metered` additionally record their acquisition wait
+/// times.
+pub struct TimeoutRwLock<T>(RwLock<T>, LockWaitTimer);
 
 const TIMEOUT_LOCKS_ENABLED_DEFAULT: bool = true;
 static TIMEOUT_LOCKS_ENABLED: AtomicBool = AtomicBool::new(TIMEOUT_LOCKS_ENABLED_DEFAULT);
@@ -21,7 +25,11 @@ impl TimeoutRwLock<()> {
 
 impl<T> TimeoutRwLock<T> {
     pub fn new(inner: T) -> Self {
-        Self(RwLock::new(inner))
+        Self(RwLock::new(inner), LockWaitTimer::default())
+    }
+
+    pub fn new_metered(inner: T, lock_name: &str) -> Self {
+        Self(RwLock::new(inner), LockWaitTimer::new(lock_name))
     }
 
     fn timeouts_enabled() -> bool {
@@ -31,18 +39,22 @@ impl<T> TimeoutRwLock<T> {
     }
 
     pub fn try_read_for(&self, timeout: Duration) -> Option<RwLockReadGuard<T>> {
-        if Self::timeouts_enabled() {
-            self.0.try_read_for(timeout)
-        } else {
-            Some(self.0.read())
-        }
+        self.1.time_read(|| {
+            if Self::timeouts_enabled() {
+                self.0.try_read_for(timeout)
+            } else {
+                Some(self.0.read())
+            }
+        })
     }
 
     pub fn try_write_for(&self, timeout: Duration) -> Option<RwLockWriteGuard<T>> {
-        if Self::timeouts_enabled() {
-            self.0.try_write_for(timeout)
-        } else {
-            Some(self.0.write())
-        }
+        self.1.time_write(|| {
+            if Self::timeouts_enabled() {
+                self.0.try_write_for(timeout)
+            } else {
+                Some(self.0.write())
+            }
+        })
     }
 }
@@ -3,9 +3,10 @@
 //! This component should not affect consensus.
 
 use crate::beacon_proposer_cache::{BeaconProposerCache, TYPICAL_SLOTS_PER_EPOCH};
+use crate::metered_lock::MeteredMutex;
 use crate::metrics;
 use itertools::Itertools;
-use parking_lot::{Mutex, RwLock};
+use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
@@ -397,7 +398,7 @@ pub struct ValidatorMonitor<E: EthSpec> {
     /// A Map representing the (non-finalized) missed blocks by epoch, validator_index(state.validators) and slot
     missed_blocks: HashSet<MissedBlock>,
     // A beacon proposer cache
-    beacon_proposer_cache: Arc<Mutex<BeaconProposerCache>>,
+    beacon_proposer_cache: Arc<MeteredMutex<BeaconProposerCache>>,
     // Unaggregated attestations generated by the committee index at each slot.
     unaggregated_attestations: HashMap<Slot, Attestation<E>>,
     log: Logger,
@@ -407,7 +408,7 @@ pub struct ValidatorMonitor<E: EthSpec> {
 impl<E: EthSpec> ValidatorMonitor<E> {
     pub fn new(
         config: ValidatorMonitorConfig,
-        beacon_proposer_cache: Arc<Mutex<BeaconProposerCache>>,
+        beacon_proposer_cache: Arc<MeteredMutex<BeaconProposerCache>>,
         log: Logger,
     ) -> Self {
         let ValidatorMonitorConfig {
@@ -1107,7 +1108,7 @@ impl<E: EthSpec> ValidatorMonitor<E> {
             .count() as u64
     }
 
-    pub fn get_beacon_proposer_cache(&self) -> Arc<Mutex<BeaconProposerCache>> {
+    pub fn get_beacon_proposer_cache(&self) -> Arc<MeteredMutex<BeaconProposerCache>> {
         self.beacon_proposer_cache.clone()
     }
 
@@ -0,0 +1,96 @@
+//! Provides a task which periodically checks that the node's finalized checkpoint remains within
+//! the weak subjectivity period.
+//!
+//! The configured weak subjectivity checkpoint is verified against the head on startup and when
+//! the chain finalizes past it. However, a node which stops finalizing (e.g. because it is stuck
+//! syncing) can fall so far behind the wall clock that a long-range attack on its view of the
+//! chain becomes possible. This task alerts the operator when that happens.
+use crate::{
+    slot_tasks::{SlotTask, SlotTaskPriority},
+    BeaconChain, BeaconChainError, BeaconChainTypes,
+};
+use slog::{crit, debug, error};
+use slot_clock::SlotClock;
+use state_processing::common::compute_weak_subjectivity_period;
+use std::sync::Arc;
+use types::{Epoch, EthSpec, Slot};
+
+/// Registers a slot task which checks the weak subjectivity period at the start of each epoch.
+///
+/// Does nothing unless a weak subjectivity checkpoint is configured.
+pub fn register_weak_subjectivity_period_task<T: BeaconChainTypes>(chain: &BeaconChain<T>) {
+    if chain.config.weak_subjectivity_checkpoint.is_none() {
+        return;
+    }
+
+    let slot_duration = chain.slot_clock.slot_duration();
+    chain.slot_tasks.register(SlotTask::new(
+        "weak_subjectivity_period",
+        SlotTaskPriority::Low,
+        slot_duration / 2,
+        slot_duration,
+        weak_subjectivity_period_check,
+    ));
+}
+
+/// Calls `is_within_weak_subjectivity_period` on a blocking thread, on the first slot of each
+/// epoch.
+async fn weak_subjectivity_period_check<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    current_slot: Slot,
+) {
+    if current_slot % T::EthSpec::slots_per_epoch() != 0 {
+        return;
+    }
+
+    let current_epoch = current_slot.epoch(T::EthSpec::slots_per_epoch());
+    let inner_chain = chain.clone();
+    let result = chain
+        .spawn_blocking_handle(
+            move || is_within_weak_subjectivity_period(&inner_chain, current_epoch),
+            "weak_subjectivity_period_blocking",
+        )
+        .await
+        .and_then(|result| result);
+
+    match result {
+        Ok(true) => debug!(
+            chain.log,
+            "Finalized checkpoint is within weak subjectivity period";
+            "epoch" => current_epoch,
+        ),
+        Ok(false) => {
+            let finalized_checkpoint = chain.canonical_head.cached_head().finalized_checkpoint();
+            crit!(
+                chain.log,
+                "Finalized checkpoint is outside the weak subjectivity period";
+                "msg" => "the node may be following a chain which was not finalized by the \
+                          honest majority. Verify the head against a trusted source.",
+                "finalized_epoch" => finalized_checkpoint.epoch,
+                "finalized_root" => ?finalized_checkpoint.root,
+                "current_epoch" => current_epoch,
+            )
+        }
+        Err(e) => error!(
+            chain.log,
+            "Failed to check weak subjectivity period";
+            "error" => ?e,
+        ),
+    }
+}
+
+/// Returns `true` if `current_epoch` falls within the weak subjectivity period of the finalized
+/// checkpoint of the head state.
+///
+/// The period is computed from the head state rather than the finalized state, which saves a
+/// state load and differs only by the validator set changes since finalization.
+pub fn is_within_weak_subjectivity_period<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    current_epoch: Epoch,
+) -> Result<bool, BeaconChainError> {
+    let head = chain.head_snapshot();
+    let finalized_epoch = head.beacon_state.finalized_checkpoint().epoch;
+    let ws_period = compute_weak_subjectivity_period(&head.beacon_state, &chain.spec)?;
+
+    Ok(current_epoch <= finalized_epoch + ws_period)
+}
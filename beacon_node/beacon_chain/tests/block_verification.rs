@@ -1,6 +1,8 @@
 #![cfg(not(debug_assertions))]
 
+use beacon_chain::blob_verification::GossipBlobError;
 use beacon_chain::block_verification_types::{AsBlock, ExecutedBlock, RpcBlock};
+use beacon_chain::data_availability_checker::PendingBlockOutcome;
 use beacon_chain::{
     test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType},
     AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes, ExecutionPendingBlock,
@@ -12,6 +14,7 @@ use beacon_chain::{
 use lazy_static::lazy_static;
 use logging::test_logger;
 use slasher::{Config as SlasherConfig, Slasher};
+use slot_clock::SlotClock;
 use state_processing::{
     common::get_indexed_attestation,
     per_block_processing::{per_block_processing, BlockSignatureStrategy},
@@ -19,8 +22,9 @@ use state_processing::{
 };
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::Duration;
 use tempfile::tempdir;
-use types::{test_utils::generate_deterministic_keypair, *};
+use types::{blob_sidecar::FixedBlobSidecarList, test_utils::generate_deterministic_keypair, *};
 
 type E = MainnetEthSpec;
 
@@ -207,18 +211,15 @@ fn update_blob_signed_header<E: EthSpec>(
     blobs: &mut BlobSidecarList<E>,
 ) {
     for old_blob_sidecar in blobs.iter_mut() {
-        let new_blob = Arc::new(BlobSidecar::<E> {
-            index: old_blob_sidecar.index,
-            blob: old_blob_sidecar.blob.clone(),
-            kzg_commitment: old_blob_sidecar.kzg_commitment,
-            kzg_proof: old_blob_sidecar.kzg_proof,
-            signed_block_header: signed_block.signed_block_header(),
-            kzg_commitment_inclusion_proof: signed_block
-                .message()
-                .body()
-                .kzg_commitment_merkle_proof(old_blob_sidecar.index as usize)
-                .unwrap(),
-        });
+        let new_blob = Arc::new(
+            BlobSidecar::new(
+                old_blob_sidecar.index as usize,
+                old_blob_sidecar.blob.clone(),
+                signed_block,
+                old_blob_sidecar.kzg_proof,
+            )
+            .unwrap(),
+        );
         *old_blob_sidecar = new_blob;
     }
 }
@@ -1185,6 +1186,188 @@ async fn verify_block_for_gossip_slashing_detection() {
     slasher_dir.close().unwrap();
 }
 
+#[tokio::test]
+async fn verify_blob_sidecar_for_gossip_equivocation_detection() {
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    let state = harness.get_current_state();
+    let ((block1, blobs1), _) = harness.make_block(state.clone(), Slot::new(1)).await;
+    let ((block2, blobs2), _) = harness.make_block(state, Slot::new(1)).await;
+    let (Some((kzg_proofs1, blobs1)), Some((kzg_proofs2, blobs2))) = (blobs1, blobs2) else {
+        // Blobs are only produced after Deneb.
+        return;
+    };
+    let sidecars1 = BlobSidecar::build_sidecars(blobs1, &block1, kzg_proofs1).unwrap();
+    let sidecars2 = BlobSidecar::build_sidecars(blobs2, &block2, kzg_proofs2).unwrap();
+    let (Some(sidecar1), Some(sidecar2)) = (sidecars1.first(), sidecars2.first()) else {
+        return;
+    };
+    assert_ne!(sidecar1.block_root(), sidecar2.block_root());
+
+    harness
+        .chain
+        .verify_blob_sidecar_for_gossip(sidecar1.clone(), 0)
+        .expect("the first sidecar should be verified");
+
+    // A repeat of the same sidecar is ignored without being treated as an equivocation.
+    assert!(matches!(
+        harness
+            .chain
+            .verify_blob_sidecar_for_gossip(sidecar1.clone(), 0)
+            .unwrap_err(),
+        GossipBlobError::RepeatBlob { .. }
+    ));
+    assert!(!harness
+        .chain
+        .observed_slashable
+        .read()
+        .is_slashable(
+            Slot::new(1),
+            sidecar1.block_proposer_index(),
+            sidecar1.block_root()
+        )
+        .unwrap());
+
+    // A validly signed sidecar for a different block at the same slot and index is an
+    // equivocation: it is still ignored, but the conflicting block root is recorded.
+    assert!(matches!(
+        harness
+            .chain
+            .verify_blob_sidecar_for_gossip(sidecar2.clone(), 0)
+            .unwrap_err(),
+        GossipBlobError::RepeatBlob { .. }
+    ));
+    assert!(harness
+        .chain
+        .observed_slashable
+        .read()
+        .is_slashable(
+            Slot::new(1),
+            sidecar1.block_proposer_index(),
+            sidecar1.block_root()
+        )
+        .unwrap());
+
+    // A sidecar for a different block with an invalid signature is rejected outright.
+    let mut forged = (**sidecar2).clone();
+    forged.signed_block_header_mut().signature = sidecar1.signed_block_header().signature.clone();
+    assert!(matches!(
+        harness
+            .chain
+            .verify_blob_sidecar_for_gossip(Arc::new(forged), 0)
+            .unwrap_err(),
+        GossipBlobError::ProposalSignatureInvalid
+    ));
+}
+
+#[tokio::test]
+async fn verify_blob_sidecar_for_gossip_uses_configured_clock_disparity() {
+    let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        .default_spec()
+        .chain_config(ChainConfig {
+            maximum_gossip_clock_disparity_millis: Some(0),
+            ..ChainConfig::default()
+        })
+        .keypairs(KEYPAIRS[0..VALIDATOR_COUNT].to_vec())
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.advance_slot();
+
+    let blob_slot = Slot::new(2);
+    let state = harness.get_current_state();
+    let ((block, blobs), _) = harness.make_block(state, blob_slot).await;
+    let Some((kzg_proofs, blobs)) = blobs else {
+        // Blobs are only produced after Deneb.
+        return;
+    };
+    let sidecars = BlobSidecar::build_sidecars(blobs, &block, kzg_proofs).unwrap();
+    let Some(sidecar) = sidecars.first() else {
+        return;
+    };
+
+    // Shortly before the start of the blob's slot: within the spec's disparity, but beyond the
+    // configured one.
+    let early = Duration::from_millis(100);
+    assert!(early < harness.spec.maximum_gossip_clock_disparity());
+    let slot_start = harness.chain.slot_clock.start_of(blob_slot).unwrap();
+    harness
+        .chain
+        .slot_clock
+        .set_current_time(slot_start - early);
+
+    assert!(matches!(
+        harness
+            .chain
+            .verify_blob_sidecar_for_gossip(sidecar.clone(), 0)
+            .unwrap_err(),
+        GossipBlobError::FutureSlot { .. }
+    ));
+
+    harness.chain.slot_clock.set_current_time(slot_start);
+    harness
+        .chain
+        .verify_blob_sidecar_for_gossip(sidecar.clone(), 0)
+        .expect("the sidecar should be accepted once its slot has started");
+}
+
+#[tokio::test]
+async fn availability_pending_block_resolved_on_import() {
+    let harness = get_harness(VALIDATOR_COUNT);
+    harness.advance_slot();
+
+    let state = harness.get_current_state();
+    let ((block, blobs), _) = harness.make_block(state, Slot::new(1)).await;
+    let Some((kzg_proofs, blobs)) = blobs else {
+        // Blobs are only produced after Deneb.
+        return;
+    };
+    let sidecars = BlobSidecar::build_sidecars(blobs, &block, kzg_proofs).unwrap();
+    if sidecars.is_empty() {
+        return;
+    }
+    let block_root = block.canonical_root();
+
+    let status = harness
+        .chain
+        .process_block(
+            block_root,
+            RpcBlock::new_without_blobs(Some(block_root), block),
+            NotifyExecutionLayer::Yes,
+            BlockImportSource::Gossip,
+            || Ok(()),
+        )
+        .await
+        .unwrap();
+    let AvailabilityProcessingStatus::MissingComponents(pending) = status else {
+        panic!("block should be waiting for its blobs, got: {status:?}");
+    };
+    assert_eq!(pending.outcome(), None);
+
+    let blobs = FixedBlobSidecarList::from(sidecars.into_iter().map(Some).collect::<Vec<_>>());
+    let status = harness
+        .chain
+        .process_rpc_blobs(Slot::new(1), block_root, blobs)
+        .await
+        .unwrap();
+    assert_eq!(status, AvailabilityProcessingStatus::Imported(block_root));
+    assert_eq!(pending.resolved().await, PendingBlockOutcome::Imported);
+
+    // A handle registered after a concurrent import has completed is resolved immediately,
+    // rather than waiting for an import which has already happened.
+    let late = harness
+        .chain
+        .data_availability_checker
+        .availability_pending_block(Slot::new(1), block_root, || {
+            harness
+                .chain
+                .canonical_head
+                .fork_choice_read_lock()
+                .contains_block(&block_root)
+        });
+    assert_eq!(late.outcome(), Some(PendingBlockOutcome::Imported));
+}
+
 #[tokio::test]
 async fn verify_block_for_gossip_doppelganger_detection() {
     let harness = get_harness(VALIDATOR_COUNT);
@@ -1,11 +1,11 @@
 use beacon_chain::blob_verification::GossipVerifiedBlob;
 use beacon_chain::test_utils::BeaconChainHarness;
-use eth2::types::{EventKind, SseBlobSidecar};
+use eth2::types::{EventKind, SseBlobSidecar, SseDutiesReorg};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
 use std::sync::Arc;
 use types::blob_sidecar::FixedBlobSidecarList;
-use types::{BlobSidecar, EthSpec, ForkName, MinimalEthSpec};
+use types::{BlobSidecar, Epoch, EthSpec, ForkName, MinimalEthSpec, Slot};
 
 type E = MinimalEthSpec;
 
@@ -65,10 +65,9 @@ async fn blob_sidecar_event_on_process_rpc_blobs() {
     let blob_1 = BlobSidecar::random_valid(&mut rng, kzg)
         .map(Arc::new)
         .unwrap();
-    let blob_2 = Arc::new(BlobSidecar {
-        index: 1,
-        ..BlobSidecar::random_valid(&mut rng, kzg).unwrap()
-    });
+    let mut blob_2 = BlobSidecar::random_valid(&mut rng, kzg).unwrap();
+    blob_2.index = 1;
+    let blob_2 = Arc::new(blob_2);
     let blobs = FixedBlobSidecarList::from(vec![Some(blob_1.clone()), Some(blob_2.clone())]);
     let expected_sse_blobs = vec![
         SseBlobSidecar::from_blob_sidecar(blob_1.as_ref()),
@@ -91,3 +90,66 @@ async fn blob_sidecar_event_on_process_rpc_blobs() {
     }
     assert_eq!(sse_blobs, expected_sse_blobs);
 }
+
+/// Verifies that duties reorg events are emitted when a reorg changes a duty dependent root.
+#[tokio::test]
+async fn duties_reorg_events_on_dependent_root_change() {
+    let harness = BeaconChainHarness::builder(E::default())
+        .default_spec()
+        .deterministic_keypairs(8)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+
+    let last_slot_of_epoch_0 = Slot::new(E::slots_per_epoch() - 1);
+    let common_ancestor = harness.extend_to_slot(last_slot_of_epoch_0 - 1).await;
+    let head_state = harness.chain.head_snapshot().beacon_state.clone();
+
+    // Compete a block at the last slot of epoch 0 with a block in the next slot which skips it.
+    let (old_head, _) = harness
+        .make_block(head_state.clone(), last_slot_of_epoch_0)
+        .await;
+    let old_head_root = old_head.0.canonical_root();
+    let (new_head, _) = harness
+        .make_block(head_state, last_slot_of_epoch_0 + 1)
+        .await;
+    let new_head_root = new_head.0.canonical_root();
+
+    let event_handler = harness.chain.event_handler.as_ref().unwrap();
+    let mut attester_receiver = event_handler.subscribe_attester_duties_reorgs();
+    let mut proposer_receiver = event_handler.subscribe_proposer_duties_reorgs();
+
+    harness
+        .process_block(last_slot_of_epoch_0, old_head_root, old_head)
+        .await
+        .unwrap();
+    assert!(attester_receiver.try_recv().is_err());
+    assert!(proposer_receiver.try_recv().is_err());
+
+    // The new head wins through proposer boost.
+    harness
+        .process_block(last_slot_of_epoch_0 + 1, new_head_root, new_head)
+        .await
+        .unwrap();
+    assert_eq!(harness.head_block_root(), new_head_root);
+
+    let duties_reorg = |epoch, old_dependent_root, new_dependent_root| SseDutiesReorg {
+        slot: last_slot_of_epoch_0 + 1,
+        epoch: Epoch::new(epoch),
+        old_dependent_root,
+        new_dependent_root,
+        execution_optimistic: false,
+    };
+
+    // The attester duties of epoch 1 were decided at genesis, so only those of epoch 2 changed.
+    assert_eq!(
+        attester_receiver.try_recv().unwrap(),
+        EventKind::AttesterDutiesReorg(duties_reorg(2, old_head_root, new_head_root))
+    );
+    assert!(attester_receiver.try_recv().is_err());
+    assert_eq!(
+        proposer_receiver.try_recv().unwrap(),
+        EventKind::ProposerDutiesReorg(duties_reorg(1, old_head_root, common_ancestor))
+    );
+    assert!(proposer_receiver.try_recv().is_err());
+}
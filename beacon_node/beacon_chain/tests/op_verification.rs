@@ -15,7 +15,7 @@ use state_processing::per_block_processing::errors::{
     AttesterSlashingInvalid, BlockOperationError, ExitInvalid, ProposerSlashingInvalid,
 };
 use std::sync::Arc;
-use store::{LevelDB, StoreConfig};
+use store::{BeaconNodeBackend, LevelDB, StoreConfig};
 use tempfile::{tempdir, TempDir};
 use types::*;
 
@@ -29,7 +29,7 @@ lazy_static! {
 
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
-type HotColdDB = store::HotColdDB<E, LevelDB<E>, LevelDB<E>>;
+type HotColdDB = store::HotColdDB<E, BeaconNodeBackend<E>, LevelDB<E>>;
 
 fn get_store(db_path: &TempDir) -> Arc<HotColdDB> {
     let spec = test_spec::<E>();
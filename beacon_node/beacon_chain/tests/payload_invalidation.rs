@@ -8,7 +8,7 @@ use beacon_chain::{
     canonical_head::{CachedHead, CanonicalHead},
     test_utils::{BeaconChainHarness, EphemeralHarnessType},
     BeaconChainError, BlockError, ChainConfig, ExecutionPayloadError, NotifyExecutionLayer,
-    OverrideForkchoiceUpdate, StateSkipConfig, WhenSlotSkipped,
+    OverrideForkchoiceUpdate, ResetPayloadStatuses, StateSkipConfig, WhenSlotSkipped,
     INVALID_FINALIZED_MERGE_TRANSITION_BLOCK_SHUTDOWN_REASON,
     INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON,
 };
@@ -2061,3 +2061,49 @@ async fn weights_after_resetting_optimistic_status() {
         rig.import_block(Payload::Valid).await;
     }
 }
+
+/// Check that the payload statuses of an erroneously invalidated block and its descendants can be
+/// reset whilst the node is running, without affecting its ancestors.
+#[tokio::test]
+async fn reset_payload_statuses_from_ancestor() {
+    let mut rig = InvalidPayloadRig::new();
+    rig.move_to_terminal_block();
+    let transition_root = rig.import_block(Payload::Valid).await;
+
+    let mut roots = vec![];
+    for _ in 0..4 {
+        roots.push(rig.import_block(Payload::Syncing).await);
+    }
+    rig.recompute_head().await;
+
+    rig.invalidate_manually(roots[1]).await;
+    assert_eq!(rig.harness.head_block_root(), roots[0]);
+    for root in &roots[1..] {
+        assert!(rig.execution_status(*root).is_invalid());
+    }
+
+    rig.harness
+        .chain
+        .reset_payload_statuses(ResetPayloadStatuses::FromAncestor(roots[1]))
+        .await
+        .unwrap();
+
+    // The reset blocks are optimistic again, so the head returns to the tip of the chain. The EL
+    // is still syncing, so it neither validates nor invalidates them.
+    assert_eq!(rig.harness.head_block_root(), *roots.last().unwrap());
+    for root in &roots[1..] {
+        assert!(rig.execution_status(*root).is_strictly_optimistic());
+    }
+    assert!(rig
+        .execution_status(transition_root)
+        .is_valid_and_post_bellatrix());
+
+    // An unknown ancestor is rejected.
+    assert!(matches!(
+        rig.harness
+            .chain
+            .reset_payload_statuses(ResetPayloadStatuses::FromAncestor(Hash256::repeat_byte(1)))
+            .await,
+        Err(BeaconChainError::MissingBeaconBlock(_))
+    ));
+}
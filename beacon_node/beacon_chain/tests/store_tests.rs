@@ -26,12 +26,16 @@ use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Duration;
 use store::chunked_vector::Chunk;
-use store::metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION, STATE_UPPER_LIMIT_NO_RETAIN};
+use store::metadata::{
+    HotStateDiffBases, SchemaVersion, CURRENT_SCHEMA_VERSION, HOT_STATE_DIFF_BASES_KEY,
+    STATE_UPPER_LIMIT_NO_RETAIN,
+};
 use store::{
     chunked_vector::{chunk_key, Field},
     get_key_for_col,
     iter::{BlockRootsIterator, StateRootsIterator},
-    BlobInfo, DBColumn, HotColdDB, KeyValueStore, KeyValueStoreOp, LevelDB, StoreConfig,
+    BeaconNodeBackend, BlobInfo, DBColumn, HotColdDB, HotStateDiff, ItemStore, KeyValueStore,
+    KeyValueStoreOp, LevelDB, StoreConfig, StoreOp,
 };
 use tempfile::{tempdir, TempDir};
 use tokio::time::sleep;
@@ -51,7 +55,7 @@ lazy_static! {
 type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<DiskHarnessType<E>>;
 
-fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, LevelDB<E>>> {
     get_store_generic(db_path, StoreConfig::default(), test_spec::<E>())
 }
 
@@ -59,7 +63,7 @@ fn get_store_generic(
     db_path: &TempDir,
     config: StoreConfig,
     spec: ChainSpec,
-) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+) -> Arc<HotColdDB<E, BeaconNodeBackend<E>, LevelDB<E>>> {
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
     let blobs_path = db_path.path().join("blobs_db");
@@ -78,7 +82,7 @@ fn get_store_generic(
 }
 
 fn get_harness(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, LevelDB<E>>>,
     validator_count: usize,
 ) -> TestHarness {
     // Most tests expect to retain historic states, so we use this as the default.
@@ -90,7 +94,7 @@ fn get_harness(
 }
 
 fn get_harness_generic(
-    store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, LevelDB<E>>>,
     validator_count: usize,
     chain_config: ChainConfig,
 ) -> TestHarness {
@@ -2835,6 +2839,64 @@ async fn finalizes_after_resuming_from_db() {
     );
 }
 
+#[tokio::test]
+async fn revert_missing_head_block_on_resume() {
+    let validator_count = 16;
+    let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 4;
+
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    let harness = BeaconChainHarness::builder(MinimalEthSpec)
+        .default_spec()
+        .keypairs(KEYPAIRS[0..validator_count].to_vec())
+        .fresh_disk_store(store.clone())
+        .mock_execution_layer()
+        .build();
+
+    harness.advance_slot();
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    harness
+        .chain
+        .persist_head_and_fork_choice()
+        .expect("should persist the head and fork choice");
+
+    // Simulate an unclean shutdown which persisted fork choice but lost the head block.
+    let head = harness.chain.head_snapshot();
+    let head_block_root = head.beacon_block_root;
+    let parent_root = head.beacon_block.parent_root();
+    store.delete_block(&head_block_root).unwrap();
+
+    let original_chain = harness.chain;
+
+    let resumed_harness = BeaconChainHarness::<DiskHarnessType<E>>::builder(MinimalEthSpec)
+        .default_spec()
+        .keypairs(KEYPAIRS[0..validator_count].to_vec())
+        .resumed_disk_store(store)
+        .testing_slot_clock(original_chain.slot_clock.clone())
+        .execution_layer(original_chain.execution_layer.clone())
+        .build();
+
+    assert_eq!(
+        resumed_harness.chain.head_snapshot().beacon_block_root,
+        parent_root,
+        "the head should revert to the parent of the missing block"
+    );
+    assert!(!resumed_harness
+        .chain
+        .canonical_head
+        .fork_choice_read_lock()
+        .contains_block(&head_block_root));
+}
+
 #[tokio::test]
 async fn revert_minority_fork_on_resume() {
     let validator_count = 16;
@@ -3152,6 +3214,50 @@ async fn deneb_prune_blobs_happy_case() {
     check_blob_existence(&harness, oldest_blob_slot, harness.head_slot(), true);
 }
 
+/// Check that blocks produced with a fixed number of blobs are imported along with their blobs.
+#[tokio::test]
+async fn deneb_extend_chain_with_blobs() {
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+
+    let Some(deneb_fork_epoch) = store.get_chain_spec().deneb_fork_epoch else {
+        // No-op prior to Deneb.
+        return;
+    };
+    let deneb_fork_slot = deneb_fork_epoch.start_slot(E::slots_per_epoch());
+
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+    let blobs_per_block = 2;
+
+    harness
+        .extend_chain_with_blobs(E::slots_per_epoch() as usize * 2, blobs_per_block)
+        .await;
+
+    for (block_root, slot) in harness
+        .chain
+        .forwards_iter_block_roots_until(deneb_fork_slot.max(Slot::new(1)), harness.head_slot())
+        .unwrap()
+        .map(Result::unwrap)
+    {
+        let block = harness
+            .chain
+            .get_blinded_block(&block_root)
+            .unwrap()
+            .unwrap();
+        let blobs = harness.chain.get_blobs(&block_root).unwrap();
+        assert_eq!(
+            block.message().body().blob_kzg_commitments().unwrap().len(),
+            blobs_per_block,
+            "block at slot {slot} has the wrong number of commitments"
+        );
+        assert_eq!(
+            blobs.len(),
+            blobs_per_block,
+            "block at slot {slot} has the wrong number of blobs"
+        );
+    }
+}
+
 /// Check that blob pruning does not prune without finalization.
 #[tokio::test]
 async fn deneb_prune_blobs_no_finalization() {
@@ -3482,6 +3588,243 @@ async fn prune_historic_states() {
     check_split_slot(&harness, store);
 }
 
+#[tokio::test]
+async fn hot_boundary_states_stored_as_diffs() {
+    let num_blocks_produced = E::slots_per_epoch() * 6;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            num_blocks_produced as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let split = store.get_split_info();
+    assert!(split.slot > 0);
+
+    // The finalized state is stored in full, and every later epoch-boundary state is stored as a
+    // diff against it.
+    assert!(store
+        .hot_db
+        .get::<HotStateDiff>(&split.state_root)
+        .unwrap()
+        .is_none());
+    let head_state = harness.get_current_state();
+    let mut boundary_slot = split.slot + E::slots_per_epoch();
+    while boundary_slot < head_state.slot() {
+        let state_root = *head_state.get_state_root(boundary_slot).unwrap();
+        let diff = store
+            .hot_db
+            .get::<HotStateDiff>(&state_root)
+            .unwrap()
+            .expect("boundary state should be stored as a diff");
+        assert_eq!(diff.base_state_root, split.state_root);
+
+        let mut state = store.load_hot_boundary_state(&state_root).unwrap().unwrap();
+        assert_eq!(state.slot(), boundary_slot);
+        assert_eq!(state.update_tree_hash_cache().unwrap(), state_root);
+
+        boundary_slot += E::slots_per_epoch();
+    }
+
+    // Previous bases are deleted once no diff refers to them, and every base which remains is
+    // stored in full.
+    let referenced = store
+        .hot_db
+        .iter_column::<Hash256>(DBColumn::BeaconStateDiff)
+        .map(|res| HotStateDiff::base_state_root_from_bytes(&res.unwrap().1).unwrap())
+        .collect::<HashSet<_>>();
+    let bases = store
+        .hot_db
+        .get::<HotStateDiffBases>(&HOT_STATE_DIFF_BASES_KEY)
+        .unwrap()
+        .unwrap();
+    assert!(bases.state_roots.contains(&split.state_root));
+    assert!(bases.state_roots.len() <= 2);
+    for state_root in &bases.state_roots {
+        assert!(store
+            .hot_db
+            .key_exists(DBColumn::BeaconState.into(), state_root.as_bytes())
+            .unwrap());
+    }
+    assert!(referenced.is_subset(&bases.state_roots.iter().copied().collect()));
+}
+
+#[tokio::test]
+async fn participation_summaries() {
+    let num_epochs = 6;
+    let absent_validator = LOW_VALIDATOR_COUNT - 1;
+
+    let db_path = tempdir().unwrap();
+    let spec = ForkName::Altair.make_genesis_spec(E::default_spec());
+    let store = get_store_generic(&db_path, StoreConfig::default(), spec);
+    let chain_config = ChainConfig {
+        store_participation_summaries: true,
+        ..ChainConfig::default()
+    };
+    let harness = get_harness_generic(store, LOW_VALIDATOR_COUNT, chain_config);
+
+    harness
+        .extend_chain(
+            (num_epochs * E::slots_per_epoch()) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators((0..absent_validator).collect()),
+        )
+        .await;
+
+    // Summaries of finalized epochs are still found after migration to the freezer.
+    assert!(
+        harness
+            .chain
+            .head_snapshot()
+            .beacon_state
+            .finalized_checkpoint()
+            .epoch
+            > 1
+    );
+
+    for epoch in 1..num_epochs - 1 {
+        let summary = harness
+            .chain
+            .get_participation_summary(Epoch::new(epoch))
+            .unwrap()
+            .unwrap_or_else(|| panic!("summary for epoch {epoch} should exist"));
+        assert_eq!(summary.epoch, epoch);
+
+        for index in 0..absent_validator {
+            assert!(summary.is_active_unslashed(index));
+            assert!(summary.is_source_attester(index));
+            assert!(summary.is_target_attester(index));
+            assert!(summary.is_head_attester(index));
+        }
+        assert!(summary.is_active_unslashed(absent_validator));
+        assert!(!summary.is_source_attester(absent_validator));
+        assert!(!summary.is_target_attester(absent_validator));
+        assert!(!summary.is_head_attester(absent_validator));
+        assert!(!summary.is_active_unslashed(LOW_VALIDATOR_COUNT));
+    }
+
+    // The transition which would summarise the latest epochs hasn't happened yet.
+    assert!(harness
+        .chain
+        .get_participation_summary(Epoch::new(num_epochs - 1))
+        .unwrap()
+        .is_none());
+}
+
+/// Check that payloads missing from finalized blocks are restored from the execution layer.
+#[tokio::test]
+async fn backfill_execution_payloads() {
+    let db_path = tempdir().unwrap();
+    let spec = ForkName::Capella.make_genesis_spec(E::default_spec());
+    let store_config = StoreConfig {
+        prune_payloads: false,
+        ..StoreConfig::default()
+    };
+    let store = get_store_generic(&db_path, store_config, spec);
+    let harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            (E::slots_per_epoch() * 5) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let finalized_slot = harness
+        .finalized_checkpoint()
+        .epoch
+        .start_slot(E::slots_per_epoch());
+    assert!(finalized_slot > 0, "chain should have finalized");
+
+    let mut block_roots = harness
+        .chain
+        .forwards_iter_block_roots_until(Slot::new(1), finalized_slot)
+        .unwrap()
+        .map(|result| result.unwrap().0)
+        .collect::<Vec<_>>();
+    block_roots.dedup();
+
+    // Delete the payloads of every other block, as if they had been imported blinded.
+    let deleted_payloads = block_roots
+        .iter()
+        .step_by(2)
+        .map(|block_root| {
+            let payload = store
+                .get_execution_payload_dangerous_fork_agnostic(block_root)
+                .unwrap()
+                .expect("payload should be stored");
+            (*block_root, payload)
+        })
+        .collect::<Vec<_>>();
+    store
+        .do_atomically_with_block_and_blobs_cache(
+            deleted_payloads
+                .iter()
+                .map(|(block_root, _)| StoreOp::DeleteExecutionPayload(*block_root))
+                .collect(),
+        )
+        .unwrap();
+
+    let summary = harness
+        .chain
+        .backfill_execution_payloads(Slot::new(1), finalized_slot)
+        .await
+        .unwrap();
+    assert_eq!(summary.blocks_checked, block_roots.len() as u64);
+    assert_eq!(
+        summary.payloads_already_present,
+        (block_roots.len() - deleted_payloads.len()) as u64
+    );
+    assert_eq!(summary.payloads_restored, deleted_payloads.len() as u64);
+    assert_eq!(summary.payloads_unavailable, 0);
+    assert_eq!(summary.payloads_invalid, 0);
+
+    for (block_root, payload) in deleted_payloads {
+        assert_eq!(
+            store
+                .get_execution_payload_dangerous_fork_agnostic(&block_root)
+                .unwrap(),
+            Some(payload)
+        );
+    }
+
+    // Every payload is now present, so there is nothing left to backfill.
+    let summary = harness
+        .chain
+        .backfill_execution_payloads(Slot::new(1), finalized_slot)
+        .await
+        .unwrap();
+    assert_eq!(summary.payloads_already_present, block_roots.len() as u64);
+    assert_eq!(summary.payloads_restored, 0);
+}
+
+/// Check that payloads aren't backfilled if they would be pruned again at the next finalization.
+#[tokio::test]
+async fn backfill_execution_payloads_with_pruning_enabled() {
+    let db_path = tempdir().unwrap();
+    let spec = ForkName::Capella.make_genesis_spec(E::default_spec());
+    let store = get_store_generic(&db_path, StoreConfig::default(), spec);
+    let harness = get_harness(store, LOW_VALIDATOR_COUNT);
+
+    let result = harness
+        .chain
+        .backfill_execution_payloads(Slot::new(0), harness.chain.slot().unwrap())
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(BeaconChainError::PayloadBackfillWithPruningEnabled)
+        ),
+        "{result:?}"
+    );
+}
+
 /// Checks that two chains are the same, for the purpose of these tests.
 ///
 /// Several fields that are hard/impossible to check are ignored (e.g., the store).
@@ -3555,7 +3898,10 @@ fn check_finalization(harness: &TestHarness, expected_slot: u64) {
 }
 
 /// Check that the HotColdDB's split_slot is equal to the start slot of the last finalized epoch.
-fn check_split_slot(harness: &TestHarness, store: Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>) {
+fn check_split_slot(
+    harness: &TestHarness,
+    store: Arc<HotColdDB<E, BeaconNodeBackend<E>, LevelDB<E>>>,
+) {
     let split_slot = store.get_split_slot();
     assert_eq!(
         harness
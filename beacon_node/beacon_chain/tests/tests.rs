@@ -2,18 +2,24 @@
 
 use beacon_chain::{
     attestation_verification::Error as AttnError,
+    shuffling_precompute::precompute_shufflings,
     test_utils::{
         AttestationStrategy, BeaconChainHarness, BlockStrategy, EphemeralHarnessType,
         OP_POOL_DB_KEY,
     },
+    weak_subjectivity_period::is_within_weak_subjectivity_period,
     BeaconChain, ChainConfig, NotifyExecutionLayer, StateSkipConfig, WhenSlotSkipped,
 };
 use lazy_static::lazy_static;
 use operation_pool::PersistedOperationPool;
-use state_processing::{per_slot_processing, per_slot_processing::Error as SlotProcessingError};
+use state_processing::{
+    common::compute_weak_subjectivity_period, per_slot_processing,
+    per_slot_processing::Error as SlotProcessingError,
+};
+use std::time::Duration;
 use types::{
-    BeaconState, BeaconStateError, BlockImportSource, EthSpec, Hash256, Keypair, MinimalEthSpec,
-    RelativeEpoch, Slot,
+    AttestationShufflingId, BeaconState, BeaconStateError, BlockImportSource, Epoch, EthSpec,
+    Hash256, Keypair, MinimalEthSpec, RelativeEpoch, Slot,
 };
 
 // Should ideally be divisible by 3.
@@ -871,3 +877,92 @@ async fn block_roots_skip_slot_behaviour() {
         "WhenSlotSkipped::Prev should return None on a future slot"
     );
 }
+
+#[tokio::test]
+async fn precomputes_shufflings_after_skipped_epoch_boundary() {
+    let slots_per_epoch = MinimalEthSpec::slots_per_epoch();
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    // Produce blocks up to a few slots before the end of epoch 1, then skip to the start of
+    // epoch 2 so that the state advance never crosses the epoch boundary.
+    harness
+        .extend_chain(
+            (slots_per_epoch * 2 - 3) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+    let current_epoch = Epoch::new(2);
+    let current_slot = current_epoch.start_slot(slots_per_epoch);
+    harness.set_current_slot(current_slot);
+
+    let head_block_root = harness.head_block_root();
+    let next_epoch_shuffling_id =
+        AttestationShufflingId::from_components(current_epoch + 1, head_block_root);
+    assert!(harness
+        .chain
+        .beacon_proposer_cache
+        .lock()
+        .get_epoch::<MinimalEthSpec>(head_block_root, current_epoch)
+        .is_none());
+    assert!(!harness
+        .chain
+        .shuffling_cache
+        .try_read_for(Duration::from_secs(1))
+        .unwrap()
+        .contains(&next_epoch_shuffling_id));
+
+    assert!(precompute_shufflings(&harness.chain, current_slot).unwrap());
+
+    let mut state = harness.chain.head_beacon_state_cloned();
+    while state.slot() < current_slot {
+        per_slot_processing(&mut state, None, &harness.chain.spec).unwrap();
+    }
+    let expected_proposers = state
+        .get_beacon_proposer_indices(&harness.chain.spec)
+        .unwrap();
+    assert_eq!(
+        harness
+            .chain
+            .beacon_proposer_cache
+            .lock()
+            .get_epoch::<MinimalEthSpec>(head_block_root, current_epoch)
+            .unwrap()
+            .to_vec(),
+        expected_proposers
+    );
+    assert!(harness
+        .chain
+        .shuffling_cache
+        .try_read_for(Duration::from_secs(1))
+        .unwrap()
+        .contains(&next_epoch_shuffling_id));
+
+    // Nothing is left to compute on a second run.
+    assert!(!precompute_shufflings(&harness.chain, current_slot).unwrap());
+}
+
+#[tokio::test]
+async fn weak_subjectivity_period_is_measured_from_finalization() {
+    let slots_per_epoch = MinimalEthSpec::slots_per_epoch();
+    let harness = get_harness(VALIDATOR_COUNT);
+
+    harness
+        .extend_chain(
+            (slots_per_epoch * 5) as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        )
+        .await;
+
+    let head = harness.chain.head_snapshot();
+    let finalized_epoch = head.beacon_state.finalized_checkpoint().epoch;
+    assert!(finalized_epoch > 0, "chain should have finalized");
+    let ws_period =
+        compute_weak_subjectivity_period(&head.beacon_state, &harness.chain.spec).unwrap();
+    assert!(ws_period >= harness.chain.spec.min_validator_withdrawability_delay);
+
+    let last_safe_epoch = finalized_epoch + ws_period;
+    assert!(is_within_weak_subjectivity_period(&harness.chain, last_safe_epoch).unwrap());
+    assert!(!is_within_weak_subjectivity_period(&harness.chain, last_safe_epoch + 1).unwrap());
+}
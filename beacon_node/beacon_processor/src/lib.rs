@@ -331,6 +331,11 @@ impl<T> FifoQueue<T> {
     pub fn len(&self) -> usize {
         self.queue.len()
     }
+
+    /// Returns how full the queue is, as a percentage.
+    pub fn fill_percent(&self) -> u8 {
+        fill_percent(self.queue.len(), self.max_length)
+    }
 }
 
 /// A simple last-in-first-out queue with a maximum length.
@@ -372,6 +377,18 @@ impl<T> LifoQueue<T> {
     pub fn len(&self) -> usize {
         self.queue.len()
     }
+
+    /// Returns how full the queue is, as a percentage.
+    pub fn fill_percent(&self) -> u8 {
+        fill_percent(self.queue.len(), self.max_length)
+    }
+}
+
+/// Returns `len` as a percentage of `max_length`, capped at 100.
+fn fill_percent(len: usize, max_length: usize) -> u8 {
+    len.saturating_mul(100)
+        .checked_div(max_length)
+        .map_or(0, |percent| percent.min(100) as u8)
 }
 
 /// A handle that sends a message on the provided channel to a receiver when it gets dropped.
@@ -1353,6 +1370,24 @@ impl<E: EthSpec> BeaconProcessor<E> {
                     api_request_p1_queue.len() as i64,
                 );
 
+                // Let the network know how loaded the gossip queues are, so that it may refuse
+                // additional inbound peers whilst we are struggling to keep up.
+                let work_queue_pressure = [
+                    attestation_queue.fill_percent(),
+                    aggregate_queue.fill_percent(),
+                    gossip_block_queue.fill_percent(),
+                    gossip_blob_queue.fill_percent(),
+                ]
+                .into_iter()
+                .max()
+                .unwrap_or(0);
+                metrics::set_gauge(
+                    &metrics::BEACON_PROCESSOR_WORK_QUEUE_PRESSURE_PERCENT,
+                    work_queue_pressure as i64,
+                );
+                self.network_globals
+                    .set_work_queue_pressure(work_queue_pressure);
+
                 if aggregate_queue.is_full() && aggregate_debounce.elapsed() {
                     error!(
                         self.log,
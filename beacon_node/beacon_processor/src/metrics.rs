@@ -119,6 +119,10 @@ lazy_static::lazy_static! {
         "beacon_processor_api_request_p1_queue_total",
         "Count of P1 HTTP requesets waiting to be processed."
     );
+    pub static ref BEACON_PROCESSOR_WORK_QUEUE_PRESSURE_PERCENT: Result<IntGauge> = try_create_int_gauge(
+        "beacon_processor_work_queue_pressure_percent",
+        "How full the fullest of the attestation, aggregate, block and blob gossip queues is, as a percentage."
+    );
 
     /*
      * Attestation reprocessing queue metrics.
@@ -5,18 +5,20 @@ use crate::config::{ClientGenesis, Config as ClientConfig};
 use crate::notifier::spawn_notifier;
 use crate::Client;
 use beacon_chain::attestation_simulator::start_attestation_simulator_service;
-use beacon_chain::data_availability_checker::start_availability_cache_maintenance_service;
+use beacon_chain::data_availability_checker::register_availability_cache_maintenance_task;
 use beacon_chain::graffiti_calculator::start_engine_version_cache_refresh_service;
 use beacon_chain::otb_verification_service::start_otb_verification_service;
-use beacon_chain::proposer_prep_service::start_proposer_prep_service;
+use beacon_chain::proposer_prep_service::register_proposer_prep_task;
 use beacon_chain::schema_change::migrate_schema;
+use beacon_chain::shuffling_precompute::register_shuffling_precompute_task;
+use beacon_chain::weak_subjectivity_period::register_weak_subjectivity_period_task;
 use beacon_chain::LightClientProducerEvent;
 use beacon_chain::{
     builder::{BeaconChainBuilder, Witness},
     eth1_chain::{CachingEth1Backend, Eth1Chain},
     slot_clock::{SlotClock, SystemTimeSlotClock},
-    state_advance_timer::spawn_state_advance_timer,
-    store::{HotColdDB, ItemStore, LevelDB, StoreConfig},
+    state_advance_timer::register_state_advance_tasks,
+    store::{BeaconNodeBackend, HotColdDB, ItemStore, LevelDB, StoreConfig},
     BeaconChain, BeaconChainTypes, Eth1ChainBackend, MigratorConfig, ServerSentEventHandler,
 };
 use beacon_processor::{BeaconProcessor, BeaconProcessorChannels};
@@ -396,10 +398,23 @@ where
                     // ensure that the snapshot is not newer than the beacon state that satisfies the
                     // deposit finalization conditions
                     debug!(context.log(), "Downloading deposit snapshot");
-                    let deposit_snapshot_result = remote
-                        .get_deposit_snapshot()
-                        .await
-                        .map_err(|e| match e {
+                    // Prefer SSZ, but fall back to JSON for remotes which reject SSZ requests.
+                    // Remotes which answer with a JSON body are handled by the SSZ request itself,
+                    // so an SSZ decoding error means the remote served SSZ for the wrong network
+                    // and retrying won't help.
+                    let deposit_snapshot_result = match remote.get_deposit_snapshot_ssz().await {
+                        Err(e @ ApiError::InvalidSsz(_)) => Err(e),
+                        Err(e) => {
+                            debug!(
+                                context.log(),
+                                "Unable to download deposit snapshot as SSZ";
+                                "error" => ?e
+                            );
+                            remote.get_deposit_snapshot().await
+                        }
+                        Ok(deposit_snapshot) => Ok(deposit_snapshot),
+                    }
+                    .map_err(|e| match e {
                             ApiError::InvalidSsz(e) => format!(
                                 "Unable to parse SSZ: {:?}. Ensure the checkpoint-sync-url refers to a \
                                 node for the correct network",
@@ -883,7 +898,9 @@ where
                     beacon_processor_channels.work_reprocessing_rx,
                     None,
                     beacon_chain.slot_clock.clone(),
-                    beacon_chain.spec.maximum_gossip_clock_disparity(),
+                    beacon_chain
+                        .config
+                        .maximum_gossip_clock_disparity(&beacon_chain.spec),
                     BeaconProcessorQueueLengths::from_state(
                         &beacon_chain
                             .canonical_head
@@ -895,13 +912,9 @@ where
                 )?;
             }
 
-            let state_advance_context = runtime_context.service_context("state_advance".into());
-            let state_advance_log = state_advance_context.log().clone();
-            spawn_state_advance_timer(
-                state_advance_context.executor,
-                beacon_chain.clone(),
-                state_advance_log,
-            );
+            register_state_advance_tasks(&beacon_chain);
+            register_shuffling_precompute_task(&beacon_chain);
+            register_weak_subjectivity_period_task(&beacon_chain);
 
             if let Some(execution_layer) = beacon_chain.execution_layer.as_ref() {
                 // Only send a head update *after* genesis.
@@ -970,12 +983,9 @@ where
                 );
             }
 
-            start_proposer_prep_service(runtime_context.executor.clone(), beacon_chain.clone());
+            register_proposer_prep_task(&beacon_chain);
             start_otb_verification_service(runtime_context.executor.clone(), beacon_chain.clone());
-            start_availability_cache_maintenance_service(
-                runtime_context.executor.clone(),
-                beacon_chain.clone(),
-            );
+            register_availability_cache_maintenance_task(&beacon_chain);
             start_engine_version_cache_refresh_service(
                 beacon_chain.as_ref(),
                 runtime_context.executor.clone(),
@@ -1033,7 +1043,7 @@ where
 }
 
 impl<TSlotClock, TEth1Backend, E>
-    ClientBuilder<Witness<TSlotClock, TEth1Backend, E, LevelDB<E>, LevelDB<E>>>
+    ClientBuilder<Witness<TSlotClock, TEth1Backend, E, BeaconNodeBackend<E>, LevelDB<E>>>
 where
     TSlotClock: SlotClock + 'static,
     TEth1Backend: Eth1ChainBackend<E> + 'static,
@@ -827,6 +827,44 @@ impl Service {
         self.inner.deposit_cache.read().cache.get_deposit_snapshot()
     }
 
+    /// Replaces the deposit cache with one initialized from `deposit_snapshot`.
+    ///
+    /// The snapshot is only imported if it is valid and covers more deposits than the cache
+    /// currently knows about, so a stale snapshot can never roll the deposit tree back.
+    pub fn import_deposit_snapshot(
+        &self,
+        deposit_snapshot: &DepositTreeSnapshot,
+    ) -> Result<(), Error> {
+        if !deposit_snapshot.is_valid() {
+            return Err(Error::FailedToInitializeFromSnapshot(
+                "Deposit root does not match the snapshot".to_string(),
+            ));
+        }
+
+        let deposit_contract_deploy_block = self.config().deposit_contract_deploy_block;
+        let mut deposit_cache = self.inner.deposit_cache.write();
+        let known_deposits = deposit_cache.cache.len() as u64;
+        if deposit_snapshot.deposit_count <= known_deposits {
+            return Err(Error::FailedToInitializeFromSnapshot(format!(
+                "Snapshot has {} deposits but {} are already known",
+                deposit_snapshot.deposit_count, known_deposits
+            )));
+        }
+
+        *deposit_cache =
+            DepositUpdater::from_snapshot(deposit_contract_deploy_block, deposit_snapshot)
+                .map_err(Error::FailedToInitializeFromSnapshot)?;
+
+        info!(
+            self.log,
+            "Imported deposit snapshot";
+            "deposit_count" => deposit_snapshot.deposit_count,
+            "execution_block_height" => deposit_snapshot.execution_block_height,
+        );
+
+        Ok(())
+    }
+
     /// Contacts the remote eth1 node and attempts to import deposit logs up to the configured
     /// follow-distance block.
     ///
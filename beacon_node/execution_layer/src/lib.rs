@@ -342,6 +342,7 @@ pub enum FailedCondition {
     Skips,
     SkipsPerEpoch,
     EpochsSinceFinalization,
+    RecentBuilderFault,
 }
 
 type PayloadContentsRefTuple<'a, E> = (ExecutionPayloadRef<'a, E>, Option<&'a BlobsBundle<E>>);
@@ -362,6 +363,9 @@ struct Inner<E: EthSpec> {
     /// This is used *only* in the informational sync status endpoint, so that a VC using this
     /// node can prefer another node with a healthier EL.
     last_new_payload_errored: RwLock<bool>,
+    /// The slot of the most recent block for which the builder revealed blobs that didn't match
+    /// the commitments we signed.
+    last_builder_fault: parking_lot::Mutex<Option<Slot>>,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -466,6 +470,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
             payload_cache: PayloadCache::default(),
             log,
             last_new_payload_errored: RwLock::new(false),
+            last_builder_fault: parking_lot::Mutex::new(None),
         };
 
         let el = Self {
@@ -534,6 +539,20 @@ impl<E: EthSpec> ExecutionLayer<E> {
             .put(FullPayloadContents::new(payload, maybe_blobs_bundle))
     }
 
+    /// Record that the builder revealed an invalid payload or blobs for the block at `slot`.
+    ///
+    /// The beacon chain avoids the builder for a while after a fault, see
+    /// `BeaconChain::is_healthy`.
+    pub fn record_builder_fault(&self, slot: Slot) {
+        metrics::inc_counter(&metrics::EXECUTION_LAYER_BUILDER_FAULTS);
+        *self.inner.last_builder_fault.lock() = Some(slot);
+    }
+
+    /// Returns the slot of the most recent block for which the builder was at fault, if any.
+    pub fn last_builder_fault(&self) -> Option<Slot> {
+        *self.inner.last_builder_fault.lock()
+    }
+
     /// Attempt to retrieve a full payload from the payload cache by the payload root
     pub fn get_payload_by_root(&self, root: &Hash256) -> Option<FullPayloadContents<E>> {
         self.inner.payload_cache.get(root)
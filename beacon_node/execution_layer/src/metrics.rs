@@ -79,6 +79,10 @@ lazy_static::lazy_static! {
         "The reasons why a payload from a builder was rejected",
         &["reason"]
     );
+    pub static ref EXECUTION_LAYER_BUILDER_FAULTS: Result<IntCounter> = try_create_int_counter(
+        "execution_layer_builder_faults_total",
+        "Count of blocks for which the builder revealed blobs that didn't match the signed commitments"
+    );
     pub static ref EXECUTION_LAYER_PAYLOAD_BIDS: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "execution_layer_payload_bids",
         "The gwei bid value of payloads received by local EEs or builders. Only shows values up to i64::MAX.",
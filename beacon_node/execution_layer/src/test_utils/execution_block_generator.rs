@@ -144,6 +144,8 @@ pub struct ExecutionBlockGenerator<E: EthSpec> {
      * deneb stuff
      */
     pub blobs_bundles: HashMap<PayloadId, BlobsBundle<E>>,
+    /// The number of blobs to include in each payload, or a random number if `None`.
+    pub blobs_per_payload: Option<usize>,
     pub kzg: Option<Arc<Kzg>>,
     rng: Arc<Mutex<StdRng>>,
 }
@@ -179,6 +181,7 @@ impl<E: EthSpec> ExecutionBlockGenerator<E> {
             cancun_time,
             prague_time,
             blobs_bundles: <_>::default(),
+            blobs_per_payload: None,
             kzg,
             rng: make_rng(),
         };
@@ -669,9 +672,10 @@ impl<E: EthSpec> ExecutionBlockGenerator<E> {
         match execution_payload.fork_name() {
             ForkName::Base | ForkName::Altair | ForkName::Bellatrix | ForkName::Capella => {}
             ForkName::Deneb | ForkName::Electra => {
-                // get random number between 0 and Max Blobs
-                let mut rng = self.rng.lock();
-                let num_blobs = rng.gen::<usize>() % (E::max_blobs_per_block() + 1);
+                // get random number between 0 and Max Blobs, unless a number has been set
+                let num_blobs = self.blobs_per_payload.unwrap_or_else(|| {
+                    self.rng.lock().gen::<usize>() % (E::max_blobs_per_block() + 1)
+                });
                 let (bundle, transactions) = generate_blobs(num_blobs)?;
                 for tx in Vec::from(transactions) {
                     execution_payload
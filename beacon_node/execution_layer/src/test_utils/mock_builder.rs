@@ -1,6 +1,6 @@
 use crate::test_utils::{DEFAULT_BUILDER_PAYLOAD_VALUE_WEI, DEFAULT_JWT_SECRET};
 use crate::{Config, ExecutionLayer, PayloadAttributes};
-use eth2::types::{BlobsBundle, BlockId, StateId, ValidatorId};
+use eth2::types::{BlobsBundle, BlockId, FullPayloadContents, StateId, ValidatorId};
 use eth2::{BeaconNodeHttpClient, Timeouts, CONSENSUS_VERSION_HEADER};
 use fork_choice::ForkchoiceUpdateParameters;
 use parking_lot::RwLock;
@@ -20,9 +20,9 @@ use types::builder_bid::{
 };
 use types::{
     Address, BeaconState, ChainSpec, EthSpec, ExecPayload, ExecutionPayload,
-    ExecutionPayloadHeaderRefMut, ForkName, ForkVersionedResponse, Hash256, PublicKeyBytes,
-    Signature, SignedBlindedBeaconBlock, SignedRoot, SignedValidatorRegistrationData, Slot,
-    Uint256,
+    ExecutionPayloadHeaderRefMut, ForkName, ForkVersionedResponse, Hash256, KzgProof,
+    PublicKeyBytes, Signature, SignedBlindedBeaconBlock, SignedRoot,
+    SignedValidatorRegistrationData, Slot, Uint256,
 };
 use types::{ExecutionBlockHash, SecretKey};
 use warp::{Filter, Rejection};
@@ -214,6 +214,7 @@ pub struct MockBuilder<E: EthSpec> {
     builder_sk: SecretKey,
     operations: Arc<RwLock<Vec<Operation>>>,
     invalidate_signatures: Arc<RwLock<bool>>,
+    invalidate_revealed_blobs: Arc<RwLock<bool>>,
 }
 
 impl<E: EthSpec> MockBuilder<E> {
@@ -264,6 +265,7 @@ impl<E: EthSpec> MockBuilder<E> {
             builder_sk: sk,
             operations: Arc::new(RwLock::new(vec![])),
             invalidate_signatures: Arc::new(RwLock::new(false)),
+            invalidate_revealed_blobs: Arc::new(RwLock::new(false)),
         }
     }
 
@@ -281,6 +283,11 @@ impl<E: EthSpec> MockBuilder<E> {
         *self.invalidate_signatures.write() = false;
     }
 
+    /// Reveal blobs with invalid KZG proofs when blinded blocks are submitted.
+    pub fn invalid_revealed_blobs(&self) {
+        *self.invalidate_revealed_blobs.write() = true;
+    }
+
     fn apply_operations<B: BidStuff<E>>(&self, bid: &mut B) {
         let mut guard = self.operations.write();
         while let Some(op) = guard.pop() {
@@ -349,10 +356,19 @@ pub fn serve<E: EthSpec>(
                             block.message.body.execution_payload.tree_hash_root()
                         }
                     };
-                    let payload = builder
+                    let mut payload = builder
                         .el
                         .get_payload_by_root(&root)
                         .ok_or_else(|| reject("missing payload for tx root"))?;
+                    if *builder.invalidate_revealed_blobs.read() {
+                        if let FullPayloadContents::PayloadAndBlobs(payload_and_blobs) =
+                            &mut payload
+                        {
+                            for proof in payload_and_blobs.blobs_bundle.proofs.iter_mut() {
+                                *proof = KzgProof::empty();
+                            }
+                        }
+                    }
                     let resp: ForkVersionedResponse<_> = ForkVersionedResponse {
                         version: Some(fork_name),
                         metadata: Default::default(),
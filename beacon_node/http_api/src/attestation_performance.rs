@@ -6,10 +6,12 @@ use state_processing::{
     per_epoch_processing::EpochProcessingSummary, BlockReplayError, BlockReplayer,
 };
 use std::sync::Arc;
-use types::{BeaconState, BeaconStateError, EthSpec, Hash256};
+use types::{BeaconState, BeaconStateError, Epoch, EthSpec, Hash256};
 use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_server_error};
 
 const MAX_REQUEST_RANGE_EPOCHS: usize = 100;
+/// The maximum range for a single validator when served from stored participation summaries.
+const MAX_SUMMARY_REQUEST_RANGE_EPOCHS: usize = 8192;
 const BLOCK_ROOT_CHUNK_SIZE: usize = 100;
 
 #[derive(Debug)]
@@ -66,15 +68,6 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
         )));
     }
 
-    // The response size can grow exceptionally large therefore we should check that the
-    // query is within permitted bounds to prevent potential OOM errors.
-    if (end_epoch - start_epoch).as_usize() > MAX_REQUEST_RANGE_EPOCHS {
-        return Err(custom_bad_request(format!(
-            "end_epoch must not exceed start_epoch by more than {} epochs. start: {}, end: {}",
-            MAX_REQUEST_RANGE_EPOCHS, query.start_epoch, query.end_epoch
-        )));
-    }
-
     // Either use the global validator set, or the specified index.
     //
     // Does no further validation of the indices, so in the event an index has not yet been
@@ -93,6 +86,36 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
         })?]
     };
 
+    // The response size can grow exceptionally large therefore we should check that the
+    // query is within permitted bounds to prevent potential OOM errors. Longer ranges are
+    // permitted for a single validator if they can be served without replaying blocks.
+    let range = (end_epoch - start_epoch).as_usize();
+    if range > MAX_REQUEST_RANGE_EPOCHS
+        && (index_range.len() > 1 || range > MAX_SUMMARY_REQUEST_RANGE_EPOCHS)
+    {
+        return Err(custom_bad_request(format!(
+            "end_epoch must not exceed start_epoch by more than {} epochs. start: {}, end: {}",
+            MAX_REQUEST_RANGE_EPOCHS, query.start_epoch, query.end_epoch
+        )));
+    }
+
+    if let Some(perfs) = attestation_performance_from_summaries(
+        &chain,
+        query.start_epoch,
+        query.end_epoch,
+        &index_range,
+    )? {
+        return Ok(perfs);
+    }
+
+    if range > MAX_REQUEST_RANGE_EPOCHS {
+        return Err(custom_bad_request(format!(
+            "participation summaries are not stored for this range, so end_epoch must not exceed \
+             start_epoch by more than {} epochs. start: {}, end: {}",
+            MAX_REQUEST_RANGE_EPOCHS, query.start_epoch, query.end_epoch
+        )));
+    }
+
     // Load block roots.
     let mut block_roots: Vec<Hash256> = chain
         .forwards_iter_block_roots_until(start_slot, end_slot)
@@ -211,3 +234,41 @@ pub fn get_attestation_performance<T: BeaconChainTypes>(
 
     Ok(perfs)
 }
+
+/// Compute the attestation performance for `start_epoch..=end_epoch` using the participation
+/// summaries stored at each epoch transition.
+///
+/// Returns `None` if a summary is missing for any epoch in the range, e.g. because the node was
+/// not storing them at the time.
+fn attestation_performance_from_summaries<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    start_epoch: Epoch,
+    end_epoch: Epoch,
+    index_range: &[u64],
+) -> Result<Option<Vec<AttestationPerformance>>, warp::Rejection> {
+    let mut perfs = AttestationPerformance::initialize(index_range.to_vec());
+
+    for epoch in (start_epoch.as_u64()..=end_epoch.as_u64()).map(Epoch::new) {
+        let Some(summary) = chain
+            .get_participation_summary(epoch)
+            .map_err(beacon_chain_error)?
+        else {
+            return Ok(None);
+        };
+
+        for perf in perfs.iter_mut() {
+            let index = perf.index as usize;
+            let stats = AttestationPerformanceStatistics {
+                active: summary.is_active_unslashed(index),
+                head: summary.is_head_attester(index),
+                target: summary.is_target_attester(index),
+                source: summary.is_source_attester(index),
+                // Inclusion delays are only available prior to Altair, which is never summarised.
+                delay: None,
+            };
+            perf.epochs.insert(epoch.as_u64(), stats);
+        }
+    }
+
+    Ok(Some(perfs))
+}
@@ -28,7 +28,7 @@ pub fn attester_duties<T: BeaconChainTypes>(
     // will equal `current_epoch + 1`
     let tolerant_current_epoch = chain
         .slot_clock
-        .now_with_future_tolerance(chain.spec.maximum_gossip_clock_disparity())
+        .now_with_future_tolerance(chain.config.maximum_gossip_clock_disparity(&chain.spec))
         .ok_or_else(|| warp_utils::reject::custom_server_error("unable to read slot clock".into()))?
         .epoch(T::EthSpec::slots_per_epoch());
 
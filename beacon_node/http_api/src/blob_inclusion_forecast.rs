@@ -0,0 +1,101 @@
+//! Recent blob usage and the resulting blob base fee, for tooling which times the submission of
+//! blob transactions.
+use beacon_chain::blob_fee::{blob_base_fee, next_excess_blob_gas, GAS_PER_BLOB};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    BlobBaseFeeProjection, BlobInclusionForecast, BlobInclusionForecastQuery, BlockBlobUsage,
+};
+use std::sync::Arc;
+use types::{EthSpec, ExecPayload};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request};
+
+/// The number of blocks considered when the query doesn't specify.
+const DEFAULT_BLOCKS: u64 = 32;
+/// The maximum number of blocks which may be requested.
+const MAX_BLOCKS: u64 = 1024;
+
+/// Summarise the blob usage of recent canonical blocks and project the blob base fee of the next
+/// epoch's worth of blocks.
+pub fn get_blob_inclusion_forecast<T: BeaconChainTypes>(
+    query: BlobInclusionForecastQuery,
+    chain: Arc<BeaconChain<T>>,
+) -> Result<BlobInclusionForecast, warp::Rejection> {
+    let num_blocks = query.blocks.unwrap_or(DEFAULT_BLOCKS);
+    if num_blocks == 0 || num_blocks > MAX_BLOCKS {
+        return Err(custom_bad_request(format!(
+            "blocks must be between 1 and {}",
+            MAX_BLOCKS
+        )));
+    }
+
+    // Walk back from the head, stopping early at the first block without blob gas fields, i.e.
+    // the last block prior to Deneb.
+    let mut blocks = vec![];
+    let mut block_root = chain.head_beacon_block_root();
+    while (blocks.len() as u64) < num_blocks {
+        let Some(block) = chain
+            .get_blinded_block(&block_root)
+            .map_err(beacon_chain_error)?
+        else {
+            break;
+        };
+        let body = block.message().body();
+        let (Ok(commitments), Ok(payload)) =
+            (body.blob_kzg_commitments(), body.execution_payload())
+        else {
+            break;
+        };
+        let header = payload.to_execution_payload_header();
+        let (Ok(blob_gas_used), Ok(excess_blob_gas)) =
+            (header.blob_gas_used(), header.excess_blob_gas())
+        else {
+            break;
+        };
+
+        blocks.push(BlockBlobUsage {
+            slot: block.slot(),
+            block_root,
+            blob_count: commitments.len() as u64,
+            blob_gas_used,
+            excess_blob_gas,
+            blob_base_fee: blob_base_fee(excess_blob_gas),
+        });
+        block_root = block.parent_root();
+    }
+    blocks.reverse();
+
+    let Some(head) = blocks.last() else {
+        return Err(custom_bad_request(
+            "the head block is prior to Deneb".to_string(),
+        ));
+    };
+
+    let num_blocks = blocks.len() as u64;
+    let total_blobs = blocks.iter().map(|block| block.blob_count).sum::<u64>();
+    let mean_blob_gas_used =
+        blocks.iter().map(|block| block.blob_gas_used).sum::<u64>() / num_blocks;
+
+    let target_blobs_per_block = chain.spec.target_blobs_per_block::<T::EthSpec>();
+    let target_blob_gas_per_block = target_blobs_per_block.saturating_mul(GAS_PER_BLOB);
+    let mut excess_blob_gas = head.excess_blob_gas;
+    let mut blob_gas_used = head.blob_gas_used;
+    let projections = (0..T::EthSpec::slots_per_epoch())
+        .map(|_| {
+            excess_blob_gas =
+                next_excess_blob_gas(excess_blob_gas, blob_gas_used, target_blob_gas_per_block);
+            blob_gas_used = mean_blob_gas_used;
+            BlobBaseFeeProjection {
+                excess_blob_gas,
+                blob_base_fee: blob_base_fee(excess_blob_gas),
+            }
+        })
+        .collect();
+
+    Ok(BlobInclusionForecast {
+        target_blobs_per_block,
+        max_blobs_per_block: chain.spec.max_blobs_per_block::<T::EthSpec>(),
+        mean_blobs_per_block: total_blobs as f64 / num_blocks as f64,
+        blocks,
+        projections,
+    })
+}
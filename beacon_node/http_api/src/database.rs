@@ -1,7 +1,11 @@
-use beacon_chain::store::metadata::CURRENT_SCHEMA_VERSION;
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use beacon_chain::blob_verification::verify_kzg_for_blob_list;
+use beacon_chain::store::metadata::{BlobInfo, CURRENT_SCHEMA_VERSION};
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use eth2::lighthouse::DatabaseInfo;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
+use types::{BlobSidecar, BlobSidecarList, Hash256, Slot};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_server_error};
 
 pub fn info<T: BeaconChainTypes>(
     chain: Arc<BeaconChain<T>>,
@@ -20,3 +24,151 @@ pub fn info<T: BeaconChainTypes>(
         blob_info,
     })
 }
+
+/// Import blob sidecars obtained out-of-band, e.g. exported from another node.
+///
+/// Every sidecar must belong to a block which is already in the database, match the kzg
+/// commitment at its index in that block and pass kzg verification. All sidecars are verified
+/// before any are written. Blobs already stored for a block are kept, with the imported sidecars
+/// merged in by index.
+///
+/// If any of the imported blobs are older than the oldest blob slot, and every block from the oldest
+/// imported block up to the oldest blob slot now has its blobs, the oldest blob slot is moved back
+/// so that they are served. Otherwise it is left unchanged, so that the node never advertises blobs
+/// it doesn't have. Blobs outside the data availability window are pruned again unless blob pruning
+/// is disabled.
+pub fn import_blobs<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    blob_sidecars: Vec<BlobSidecar<T::EthSpec>>,
+) -> Result<(), warp::Rejection> {
+    let kzg = chain
+        .kzg
+        .as_ref()
+        .ok_or_else(|| custom_server_error("kzg is not initialized".to_string()))?;
+
+    let mut sidecars_by_block: HashMap<Hash256, Vec<Arc<BlobSidecar<T::EthSpec>>>> = HashMap::new();
+    for sidecar in blob_sidecars {
+        sidecars_by_block
+            .entry(sidecar.block_root())
+            .or_default()
+            .push(Arc::new(sidecar));
+    }
+
+    let mut verified_blobs = Vec::with_capacity(sidecars_by_block.len());
+    let mut oldest_imported_slot: Option<Slot> = None;
+    for (block_root, sidecars) in sidecars_by_block {
+        let block = chain
+            .get_blinded_block(&block_root)
+            .map_err(beacon_chain_error)?
+            .ok_or_else(|| custom_bad_request(format!("unknown block: {block_root:?}")))?;
+        let signed_block_header = block.signed_block_header();
+        let commitments = block
+            .message()
+            .body()
+            .blob_kzg_commitments()
+            .map_err(|_| custom_bad_request(format!("pre-deneb block: {block_root:?}")))?;
+
+        for sidecar in &sidecars {
+            if *sidecar.signed_block_header() != signed_block_header {
+                return Err(custom_bad_request(format!(
+                    "blob {} has an invalid block header for block {block_root:?}",
+                    sidecar.index
+                )));
+            }
+            if commitments.get(sidecar.index as usize) != Some(&sidecar.kzg_commitment) {
+                return Err(custom_bad_request(format!(
+                    "blob {} does not match the commitments of block {block_root:?}",
+                    sidecar.index
+                )));
+            }
+            if !sidecar.verify_blob_sidecar_inclusion_proof() {
+                return Err(custom_bad_request(format!(
+                    "blob {} has an invalid inclusion proof for block {block_root:?}",
+                    sidecar.index
+                )));
+            }
+        }
+
+        verify_kzg_for_blob_list(sidecars.iter(), kzg).map_err(|e| {
+            custom_bad_request(format!("invalid blobs for block {block_root:?}: {e:?}"))
+        })?;
+        oldest_imported_slot = Some(
+            oldest_imported_slot.map_or(block.slot(), |slot| std::cmp::min(slot, block.slot())),
+        );
+
+        let mut blobs_by_index = chain
+            .get_blobs(&block_root)
+            .map_err(beacon_chain_error)?
+            .into_iter()
+            .map(|blob| (blob.index, blob))
+            .collect::<BTreeMap<_, _>>();
+        for sidecar in sidecars {
+            blobs_by_index.insert(sidecar.index, sidecar);
+        }
+        let blobs = BlobSidecarList::<T::EthSpec>::new(blobs_by_index.into_values().collect())
+            .map_err(|e| custom_server_error(format!("too many blobs: {e:?}")))?;
+
+        verified_blobs.push((block_root, blobs));
+    }
+
+    for (block_root, blobs) in verified_blobs {
+        chain
+            .store
+            .put_blobs(&block_root, blobs)
+            .map_err(|e| custom_server_error(format!("failed to store blobs: {e:?}")))?;
+    }
+
+    let blob_info = chain.store.get_blob_info();
+    if let (Some(oldest_imported_slot), Some(oldest_blob_slot)) =
+        (oldest_imported_slot, blob_info.oldest_blob_slot)
+    {
+        if oldest_imported_slot < oldest_blob_slot
+            && blobs_complete(&chain, oldest_imported_slot, oldest_blob_slot)
+                .map_err(beacon_chain_error)?
+        {
+            let new_blob_info = BlobInfo {
+                oldest_blob_slot: Some(oldest_imported_slot),
+                ..blob_info.clone()
+            };
+            chain
+                .store
+                .compare_and_set_blob_info_with_write(blob_info, new_blob_info)
+                .map_err(|e| custom_server_error(format!("failed to update blob info: {e:?}")))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if every canonical block from `start_slot` up to (but excluding) `end_slot` has
+/// all of its blobs in the database.
+fn blobs_complete<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    start_slot: Slot,
+    end_slot: Slot,
+) -> Result<bool, BeaconChainError> {
+    let mut prev_block_root = None;
+    for result in
+        chain.forwards_iter_block_roots_until(start_slot, end_slot.saturating_sub(1u64))?
+    {
+        let (block_root, _) = result?;
+        // Skipped slots repeat the root of the previous block.
+        if prev_block_root == Some(block_root) {
+            continue;
+        }
+        prev_block_root = Some(block_root);
+
+        let Some(block) = chain.get_blinded_block(&block_root)? else {
+            return Ok(false);
+        };
+        let num_commitments = block
+            .message()
+            .body()
+            .blob_kzg_commitments()
+            .map_or(0, |commitments| commitments.len());
+        if chain.get_blobs(&block_root)?.len() != num_commitments {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
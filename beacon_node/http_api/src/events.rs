@@ -0,0 +1,131 @@
+//! Serves the `/eth/v1/events` topics over a WebSocket.
+//!
+//! Each client gets its own bounded send queue. A forwarding task moves events from the event
+//! handler's broadcast channels onto the queue without ever waiting on the client, and a separate
+//! task writes the queue to the socket. When a client is too slow to keep up and its queue fills,
+//! or the forwarding task falls behind the broadcast channel, the client's `WebSocketDropPolicy`
+//! decides whether events are skipped or the connection is closed.
+use crate::metrics;
+use beacon_chain::events::EventKind;
+use eth2::types::WebSocketDropPolicy;
+use futures::{SinkExt, StreamExt};
+use slog::{debug, Logger};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use types::EthSpec;
+use warp::ws::{Message, WebSocket};
+
+/// The length of each client's send queue, before applying the `sse_capacity_multiplier`.
+pub const WEBSOCKET_QUEUE_LEN: usize = 256;
+
+/// Stream the events from `receivers` to `socket` until either side disconnects.
+pub async fn serve_websocket<E: EthSpec>(
+    socket: WebSocket,
+    receivers: Vec<BroadcastStream<EventKind<E>>>,
+    queue_len: usize,
+    policy: WebSocketDropPolicy,
+    log: Logger,
+) {
+    let (mut sink, mut incoming) = socket.split();
+    let (queue_tx, mut queue_rx) = mpsc::channel(queue_len);
+
+    let forward = async move {
+        let mut events = futures::stream::select_all(receivers);
+        let mut dropped = 0;
+        while let Some(event) = events.next().await {
+            let message = match event {
+                Ok(event) => event_message(&event),
+                // The broadcast channel overflowed before these events reached the queue.
+                Err(BroadcastStreamRecvError::Lagged(n)) => {
+                    if apply_drop_policy(policy, n, &mut dropped) {
+                        continue;
+                    }
+                    break;
+                }
+            };
+            if !enqueue(&queue_tx, message, policy, &mut dropped) {
+                break;
+            }
+        }
+    };
+
+    let send = async move {
+        while let Some(message) = queue_rx.recv().await {
+            if sink.send(message).await.is_err() {
+                break;
+            }
+        }
+    };
+
+    // Drive the incoming half of the socket so that pings are answered and a close from the client
+    // is noticed even when no events are being sent.
+    let receive = async move {
+        while let Some(Ok(message)) = incoming.next().await {
+            if message.is_close() {
+                break;
+            }
+        }
+    };
+
+    tokio::select! {
+        () = forward => debug!(log, "Closing slow WebSocket events client"),
+        () = send => debug!(log, "WebSocket events client stopped receiving"),
+        () = receive => debug!(log, "WebSocket events client disconnected"),
+    }
+}
+
+/// Add `message` to the client's queue, returning `false` if the connection should be closed.
+fn enqueue(
+    queue: &mpsc::Sender<Message>,
+    message: Message,
+    policy: WebSocketDropPolicy,
+    dropped: &mut u64,
+) -> bool {
+    // Tell the client about any events it missed before sending the next one.
+    if *dropped > 0 {
+        match queue.try_send(dropped_message(*dropped)) {
+            Ok(()) => *dropped = 0,
+            Err(TrySendError::Full(_)) => (),
+            Err(TrySendError::Closed(_)) => return false,
+        }
+    }
+
+    match queue.try_send(message) {
+        Ok(()) => true,
+        Err(TrySendError::Full(_)) => apply_drop_policy(policy, 1, dropped),
+        Err(TrySendError::Closed(_)) => false,
+    }
+}
+
+/// Record that `n` events could not be sent to the client, returning `false` if the connection
+/// should be closed.
+fn apply_drop_policy(policy: WebSocketDropPolicy, n: u64, dropped: &mut u64) -> bool {
+    match policy {
+        WebSocketDropPolicy::Drop => {
+            metrics::inc_counter_by(&metrics::HTTP_API_WEBSOCKET_EVENTS_DROPPED_TOTAL, n);
+            *dropped += n;
+            true
+        }
+        WebSocketDropPolicy::Disconnect => {
+            metrics::inc_counter(&metrics::HTTP_API_WEBSOCKET_SLOW_CLIENTS_DISCONNECTED_TOTAL);
+            false
+        }
+    }
+}
+
+/// An event, in the same form as the `event` and `data` fields of the SSE stream.
+fn event_message<E: EthSpec>(event: &EventKind<E>) -> Message {
+    let json = serde_json::json!({
+        "event": event.topic_name(),
+        "data": event,
+    });
+    Message::text(json.to_string())
+}
+
+/// A notification that `n` events were not sent to the client.
+fn dropped_message(n: u64) -> Message {
+    let json = serde_json::json!({
+        "error": format!("dropped {n} messages"),
+    });
+    Message::text(json.to_string())
+}
@@ -0,0 +1,230 @@
+//! Optional access control for the routes which are expensive to serve.
+//!
+//! Requests for historical states, the debug endpoints and rewards can be made to require a
+//! bearer token, and can be rate limited per group of routes. All other routes, including those
+//! used by validator clients, are unaffected.
+use crate::metrics;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+use warp::filters::BoxedFilter;
+use warp::path::FullPath;
+use warp::Filter;
+
+/// States which are cheap to serve, either because they are cached or because validator clients
+/// rely on them.
+const CHEAP_STATE_IDS: &[&str] = &["head", "genesis", "finalized", "justified"];
+
+/// A group of expensive routes, which share a rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteGroup {
+    /// `/eth/*/beacon/states/{state_id}/*`, for states other than those in `CHEAP_STATE_IDS`.
+    States,
+    /// `/eth/*/debug/*`.
+    Debug,
+    /// `/eth/*/beacon/rewards/*` and `/lighthouse/analysis/*`.
+    Rewards,
+}
+
+impl RouteGroup {
+    /// Returns the group of the route at `path`, or `None` if it isn't expensive.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+        match segments.as_slice() {
+            ["eth", _, "debug", ..] => Some(Self::Debug),
+            ["eth", _, "beacon", "rewards", ..] | ["lighthouse", "analysis", ..] => {
+                Some(Self::Rewards)
+            }
+            ["eth", _, "beacon", "states", state_id, ..] if !CHEAP_STATE_IDS.contains(state_id) => {
+                Some(Self::States)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for RouteGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "states" => Ok(Self::States),
+            "debug" => Ok(Self::Debug),
+            "rewards" => Ok(Self::Rewards),
+            other => Err(format!(
+                "unknown route group \"{}\", expected one of states, debug or rewards",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RouteGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::States => write!(f, "states"),
+            Self::Debug => write!(f, "debug"),
+            Self::Rewards => write!(f, "rewards"),
+        }
+    }
+}
+
+/// A limit of `max_requests` requests to the routes of `group` in every `period`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteQuota {
+    pub group: RouteGroup,
+    pub max_requests: u64,
+    pub period: Duration,
+}
+
+impl FromStr for RouteQuota {
+    type Err = String;
+
+    /// Parses a quota of the form `group:n/t`, with `t` in seconds.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (group_str, quota_str) = s
+            .split_once(':')
+            .ok_or_else(|| format!("missing ':' from quota \"{}\"", s))?;
+        let (requests_str, seconds_str) = quota_str.split_once('/').ok_or_else(|| {
+            format!(
+                "quota \"{}\" should be defined as \"group:n/t\" (t in seconds)",
+                s
+            )
+        })?;
+        let quota = RouteQuota {
+            group: group_str.parse()?,
+            max_requests: requests_str
+                .parse()
+                .map_err(|_| format!("invalid number of requests in quota \"{}\"", s))?,
+            period: Duration::from_secs(
+                seconds_str
+                    .parse()
+                    .map_err(|_| format!("invalid number of seconds in quota \"{}\"", s))?,
+            ),
+        };
+        if quota.period.is_zero() {
+            return Err(format!("the period of quota \"{}\" must be non-zero", s));
+        }
+        Ok(quota)
+    }
+}
+
+/// Parses a `;` separated list of quotas, with at most one quota per group.
+pub fn parse_route_quotas(s: &str) -> Result<Vec<RouteQuota>, String> {
+    let quotas = s
+        .split(';')
+        .map(str::trim)
+        .filter(|quota| !quota.is_empty())
+        .map(RouteQuota::from_str)
+        .collect::<Result<Vec<_>, _>>()?;
+    for (i, quota) in quotas.iter().enumerate() {
+        if quotas[..i].iter().any(|other| other.group == quota.group) {
+            return Err(format!("more than one quota for {}", quota.group));
+        }
+    }
+    Ok(quotas)
+}
+
+/// Counts the requests to each group of routes within fixed windows of the group's period.
+struct RateLimiter {
+    quotas: Vec<RouteQuota>,
+    /// The start of the current window of each group, and the number of requests within it.
+    windows: Mutex<HashMap<RouteGroup, (Instant, u64)>>,
+}
+
+impl RateLimiter {
+    /// Count a request to `group`, returning `false` if it exceeds the group's quota.
+    fn allow(&self, group: RouteGroup, now: Instant) -> bool {
+        let Some(quota) = self.quotas.iter().find(|quota| quota.group == group) else {
+            return true;
+        };
+        let mut windows = self.windows.lock();
+        let (window_start, requests) = windows.entry(group).or_insert((now, 0));
+        if now.saturating_duration_since(*window_start) >= quota.period {
+            *window_start = now;
+            *requests = 0;
+        }
+        if *requests < quota.max_requests {
+            *requests += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Compares a presented `Authorization` header with the expected one in constant time, so that
+/// the token can't be guessed from how long a rejection takes.
+fn header_matches(header: &str, expected_header: &str) -> bool {
+    header.as_bytes().ct_eq(expected_header.as_bytes()).into()
+}
+
+/// Returns a filter which rejects requests to expensive routes which don't present `token` as a
+/// bearer token (if a token is set), or which exceed the quota of their group.
+pub fn filter(token: Option<String>, quotas: Vec<RouteQuota>) -> BoxedFilter<()> {
+    let expected_header = token.map(|token| format!("Bearer {}", token));
+    let rate_limiter = Arc::new(RateLimiter {
+        quotas,
+        windows: <_>::default(),
+    });
+
+    warp::path::full()
+        .and(warp::header::optional::<String>("Authorization"))
+        .and_then(move |path: FullPath, header: Option<String>| {
+            let expected_header = expected_header.clone();
+            let rate_limiter = rate_limiter.clone();
+            async move {
+                let Some(group) = RouteGroup::from_path(path.as_str()) else {
+                    return Ok(());
+                };
+                let group_label = group.to_string();
+
+                if let Some(expected_header) = expected_header {
+                    match header {
+                        Some(header) if header_matches(&header, &expected_header) => (),
+                        Some(_) => {
+                            metrics::inc_counter_vec(
+                                &metrics::HTTP_API_EXPENSIVE_REQUESTS_REJECTED_TOTAL,
+                                &[&group_label, "invalid_token"],
+                            );
+                            return Err(warp_utils::reject::invalid_auth(format!(
+                                "invalid bearer token for {} routes",
+                                group
+                            )));
+                        }
+                        None => {
+                            metrics::inc_counter_vec(
+                                &metrics::HTTP_API_EXPENSIVE_REQUESTS_REJECTED_TOTAL,
+                                &[&group_label, "missing_token"],
+                            );
+                            return Err(warp_utils::reject::missing_auth(format!(
+                                "a bearer token is required for {} routes",
+                                group
+                            )));
+                        }
+                    }
+                }
+
+                if !rate_limiter.allow(group, Instant::now()) {
+                    metrics::inc_counter_vec(
+                        &metrics::HTTP_API_EXPENSIVE_REQUESTS_REJECTED_TOTAL,
+                        &[&group_label, "rate_limited"],
+                    );
+                    return Err(warp_utils::reject::rate_limited(format!(
+                        "rate limit exceeded for {} routes",
+                        group
+                    )));
+                }
+
+                Ok(())
+            }
+        })
+        .untuple_one()
+        .boxed()
+}
@@ -7,12 +7,15 @@
 
 mod attestation_performance;
 mod attester_duties;
+mod blob_inclusion_forecast;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
 mod build_block_contents;
 mod builder_states;
 mod database;
+mod events;
+mod expensive_routes;
 mod metrics;
 mod produce_block;
 mod proposer_duties;
@@ -20,6 +23,7 @@ mod publish_attestations;
 mod publish_blocks;
 mod standard_block_rewards;
 mod state_id;
+mod subnet_assignments;
 mod sync_committee_rewards;
 mod sync_committees;
 mod task_spawner;
@@ -34,7 +38,7 @@ use crate::produce_block::{produce_blinded_block_v2, produce_block_v2, produce_b
 use beacon_chain::{
     attestation_verification::VerifiedAttestation, observed_operations::ObservationOutcome,
     validator_monitor::timestamp_now, AttestationError as AttnError, BeaconChain, BeaconChainError,
-    BeaconChainTypes, WhenSlotSkipped,
+    BeaconChainTypes, ExecutionStatus, ResetPayloadStatuses, WhenSlotSkipped,
 };
 use beacon_processor::{work_reprocessing_queue::ReprocessQueueMessage, BeaconProcessorSend};
 pub use block_id::BlockId;
@@ -47,6 +51,7 @@ use eth2::types::{
     ValidatorsRequestBody,
 };
 use eth2::{CONSENSUS_VERSION_HEADER, CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER};
+pub use expensive_routes::{parse_route_quotas, RouteGroup, RouteQuota};
 use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, PubsubMessage};
 use lighthouse_version::version_with_platform;
 use logging::SSELoggingComponents;
@@ -59,7 +64,7 @@ pub use publish_blocks::{
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
-use ssz::Encode;
+use ssz::{Decode, Encode};
 pub use state_id::StateId;
 use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -79,11 +84,12 @@ use tokio_stream::{
 };
 use types::{
     fork_versioned_response::EmptyMetadata, Attestation, AttestationData, AttestationShufflingId,
-    AttesterSlashing, BeaconStateError, CommitteeCache, ConfigAndPreset, Epoch, EthSpec, ForkName,
-    ForkVersionedResponse, Hash256, ProposerPreparationData, ProposerSlashing, RelativeEpoch,
-    SignedAggregateAndProof, SignedBlindedBeaconBlock, SignedBlsToExecutionChange,
-    SignedContributionAndProof, SignedValidatorRegistrationData, SignedVoluntaryExit, Slot,
-    SyncCommitteeMessage, SyncContributionData,
+    AttesterSlashing, BeaconStateError, BlobSidecar, CommitteeCache, ConfigAndPreset,
+    DepositTreeSnapshot, Epoch, EthSpec, ForkName, ForkVersionedResponse, Hash256,
+    ProposerPreparationData, ProposerSlashing, RelativeEpoch, SignedAggregateAndProof,
+    SignedBlindedBeaconBlock, SignedBlsToExecutionChange, SignedContributionAndProof,
+    SignedValidatorRegistrationData, SignedVoluntaryExit, Slot, SyncCommitteeMessage,
+    SyncContributionData,
 };
 use validator::pubkey_to_validator_index;
 use version::{
@@ -150,6 +156,10 @@ pub struct Config {
     #[serde(with = "eth2::types::serde_status_code")]
     pub duplicate_block_status_code: StatusCode,
     pub enable_light_client_server: bool,
+    /// Bearer token required by the expensive routes, if any.
+    #[serde(skip)]
+    pub expensive_routes_token: Option<String>,
+    pub expensive_routes_quotas: Vec<RouteQuota>,
 }
 
 impl Default for Config {
@@ -166,6 +176,8 @@ impl Default for Config {
             enable_beacon_processor: true,
             duplicate_block_status_code: StatusCode::ACCEPTED,
             enable_light_client_server: false,
+            expensive_routes_token: None,
+            expensive_routes_quotas: vec![],
         }
     }
 }
@@ -324,7 +336,7 @@ pub fn serve<T: BeaconChainTypes>(
     let cors_builder = {
         let builder = warp::cors()
             .allow_methods(vec!["GET", "POST"])
-            .allow_headers(vec!["Content-Type"]);
+            .allow_headers(vec!["Content-Type", "Authorization"]);
 
         warp_utils::cors::set_builder_origins(
             builder,
@@ -341,6 +353,12 @@ pub fn serve<T: BeaconChainTypes>(
         ));
     }
 
+    // Create a filter that guards the expensive routes with the token and rate limits, if any.
+    let expensive_routes = expensive_routes::filter(
+        config.expensive_routes_token.clone(),
+        config.expensive_routes_quotas.clone(),
+    );
+
     // Create a filter that extracts the endpoint version.
     let any_version = warp::path(API_PREFIX).and(warp::path::param::<EndpointVersion>().or_else(
         |_| async move {
@@ -1746,6 +1764,8 @@ pub fn serve<T: BeaconChainTypes>(
         .and(chain_filter.clone());
 
     // POST beacon/pool/attestations
+    // TODO(electra): add the v2 endpoint, which accepts `AttestationElectra` keyed on the
+    // `Eth-Consensus-Version` header.
     let post_beacon_pool_attestations = beacon_pool_path
         .clone()
         .and(warp::path("attestations"))
@@ -3170,6 +3190,7 @@ pub fn serve<T: BeaconChainTypes>(
         );
 
     // GET validator/aggregate_attestation?attestation_data_root,slot
+    // TODO(electra): add the v2 endpoint, which takes a `committee_index`.
     let get_validator_aggregate_attestation = eth_v1
         .and(warp::path("validator"))
         .and(warp::path("aggregate_attestation"))
@@ -3954,6 +3975,24 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/subnet_assignments
+    let post_lighthouse_subnet_assignments = warp::path("lighthouse")
+        .and(warp::path("subnet_assignments"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |request_data: eth2::lighthouse::SubnetAssignmentsRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    subnet_assignments::get_subnet_assignments(request_data, &chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/syncing
     let get_lighthouse_syncing = warp::path("lighthouse")
         .and(warp::path("syncing"))
@@ -4011,7 +4050,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("connected"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(network_globals)
+        .and(network_globals.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
@@ -4029,6 +4068,105 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/peers/blob_withholding
+    let get_lighthouse_peers_blob_withholding = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("blob_withholding"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let mut peers = network_globals
+                        .peers
+                        .read()
+                        .peers()
+                        .filter(|(_, peer_info)| peer_info.blob_withholding_count() > 0)
+                        .map(
+                            |(peer_id, peer_info)| eth2::lighthouse::BlobWithholdingPeer {
+                                peer_id: peer_id.to_string(),
+                                blob_withholding_count: peer_info.blob_withholding_count(),
+                            },
+                        )
+                        .collect::<Vec<_>>();
+                    // Most suspicious peers first.
+                    peers.sort_by_key(|peer| std::cmp::Reverse(peer.blob_withholding_count));
+                    Ok(peers)
+                })
+            },
+        );
+
+    // GET lighthouse/gossip/mesh
+    let get_lighthouse_gossip_mesh = warp::path("lighthouse")
+        .and(warp::path("gossip"))
+        .and(warp::path("mesh"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(network_globals
+                        .gossip_mesh
+                        .read()
+                        .summary(&network_globals.peers.read()))
+                })
+            },
+        );
+
+    // GET lighthouse/peers/history
+    let get_lighthouse_peers_history = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("history"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals)
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(network_globals
+                        .peers
+                        .read()
+                        .session_history()
+                        .cloned()
+                        .collect::<Vec<_>>())
+                })
+            },
+        );
+
+    // POST lighthouse/gossip_scoring/reload
+    let post_lighthouse_gossip_scoring_reload = warp::path("lighthouse")
+        .and(warp::path("gossip_scoring"))
+        .and(warp::path("reload"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_tx_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_tx: UnboundedSender<NetworkMessage<T::EthSpec>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let (result_tx, result_rx) = oneshot::channel();
+                    publish_network_message(
+                        &network_tx,
+                        NetworkMessage::ReloadGossipScoringOverrides { result_tx },
+                    )?;
+                    result_rx
+                        .await
+                        .map_err(|_| {
+                            warp_utils::reject::custom_server_error(
+                                "network service did not respond".to_string(),
+                            )
+                        })?
+                        .map_err(warp_utils::reject::custom_bad_request)?;
+                    Ok(warp::reply::json(&()).into_response())
+                })
+            },
+        );
+
     // GET lighthouse/proto_array
     let get_lighthouse_proto_array = warp::path("lighthouse")
         .and(warp::path("proto_array"))
@@ -4051,6 +4189,73 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/debug/fork_choice_snapshot
+    let get_lighthouse_debug_fork_choice_snapshot = warp::path("lighthouse")
+        .and(warp::path("debug"))
+        .and(warp::path("fork_choice_snapshot"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    Response::builder()
+                        .status(200)
+                        .body(chain.persisted_fork_choice().as_ssz_bytes().into())
+                        .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "failed to create response: {}",
+                                e
+                            ))
+                        })
+                })
+            },
+        );
+
+    // GET lighthouse/optimistic_sync
+    let get_lighthouse_optimistic_sync = warp::path("lighthouse")
+        .and(warp::path("optimistic_sync"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let head_is_optimistic = chain
+                        .is_optimistic_or_invalid_head()
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    let optimistic_blocks = chain
+                        .canonical_head
+                        .fork_choice_read_lock()
+                        .proto_array()
+                        .core_proto_array()
+                        .nodes
+                        .iter()
+                        .filter_map(|node| match node.execution_status {
+                            ExecutionStatus::Optimistic(execution_block_hash) => {
+                                Some(eth2::lighthouse::OptimisticBlock {
+                                    slot: node.slot,
+                                    block_root: node.root,
+                                    execution_block_hash,
+                                })
+                            }
+                            _ => None,
+                        })
+                        .collect();
+                    Ok(api_types::GenericResponse::from(
+                        eth2::lighthouse::OptimisticSyncInfo {
+                            safe_slots_to_import_optimistically: chain
+                                .spec
+                                .safe_slots_to_import_optimistically,
+                            head_is_optimistic,
+                            optimistic_blocks,
+                        },
+                    ))
+                })
+            },
+        );
+
     // GET lighthouse/validator_inclusion/{epoch}/{validator_id}
     let get_lighthouse_validator_inclusion_global = warp::path("lighthouse")
         .and(warp::path("validator_inclusion"))
@@ -4149,7 +4354,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("deposit_cache"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(eth1_service_filter)
+        .and(eth1_service_filter.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>, eth1_service: eth1::Service| {
                 task_spawner.blocking_json_task(Priority::P1, move || {
@@ -4166,6 +4371,58 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    let lighthouse_eth1_deposit_snapshot = warp::path("lighthouse")
+        .and(warp::path("eth1"))
+        .and(warp::path("deposit_snapshot"))
+        .and(warp::path::end());
+
+    // POST lighthouse/eth1/deposit_snapshot
+    let post_lighthouse_eth1_deposit_snapshot = lighthouse_eth1_deposit_snapshot
+        .clone()
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(eth1_service_filter.clone())
+        .then(
+            |deposit_snapshot: DepositTreeSnapshot,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             eth1_service: eth1::Service| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    eth1_service
+                        .import_deposit_snapshot(&deposit_snapshot)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!(
+                                "unable to import deposit snapshot: {:?}",
+                                e
+                            ))
+                        })
+                })
+            },
+        );
+
+    // POST lighthouse/eth1/deposit_snapshot (SSZ)
+    let post_lighthouse_eth1_deposit_snapshot_ssz = lighthouse_eth1_deposit_snapshot
+        .and(warp::body::bytes())
+        .and(task_spawner_filter.clone())
+        .and(eth1_service_filter)
+        .then(
+            |body: Bytes, task_spawner: TaskSpawner<T::EthSpec>, eth1_service: eth1::Service| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let deposit_snapshot =
+                        DepositTreeSnapshot::from_ssz_bytes(&body).map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!("invalid SSZ: {e:?}"))
+                        })?;
+                    eth1_service
+                        .import_deposit_snapshot(&deposit_snapshot)
+                        .map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!(
+                                "unable to import deposit snapshot: {:?}",
+                                e
+                            ))
+                        })
+                })
+            },
+        );
+
     // GET lighthouse/staking
     let get_lighthouse_staking = warp::path("lighthouse")
         .and(warp::path("staking"))
@@ -4221,6 +4478,89 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/database/import_blobs
+    let post_lighthouse_database_import_blobs = database_path
+        .and(warp::path("import_blobs"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |blob_sidecars: Vec<BlobSidecar<T::EthSpec>>,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    database::import_blobs(chain, blob_sidecars)
+                })
+            },
+        );
+
+    // POST lighthouse/database/backfill_payloads
+    let post_lighthouse_database_backfill_payloads = database_path
+        .and(warp::path("backfill_payloads"))
+        .and(warp::query::<eth2::lighthouse::PayloadBackfillQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query: eth2::lighthouse::PayloadBackfillQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let summary = chain
+                        .backfill_execution_payloads(query.start_slot, query.end_slot)
+                        .await
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    Ok(warp::reply::json(&summary).into_response())
+                })
+            },
+        );
+
+    // POST lighthouse/fork_choice/reset_payload_statuses
+    let post_lighthouse_fork_choice_reset_payload_statuses = warp::path("lighthouse")
+        .and(warp::path("fork_choice"))
+        .and(warp::path("reset_payload_statuses"))
+        .and(warp::path::end())
+        .and(warp_utils::json::json())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |request: eth2::lighthouse::ResetPayloadStatusesRequest,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P0, async move {
+                    let reset_payload_statuses = match request.ancestor_root {
+                        Some(ancestor_root) => ResetPayloadStatuses::FromAncestor(ancestor_root),
+                        None => ResetPayloadStatuses::Always,
+                    };
+                    chain
+                        .reset_payload_statuses(reset_payload_statuses)
+                        .await
+                        .map_err(|e| match e {
+                            BeaconChainError::MissingBeaconBlock(root)
+                                if request.ancestor_root == Some(root) =>
+                            {
+                                warp_utils::reject::custom_bad_request(format!(
+                                    "ancestor {:?} is not known to fork choice",
+                                    root
+                                ))
+                            }
+                            e => warp_utils::reject::beacon_chain_error(e),
+                        })?;
+
+                    let cached_head = chain.canonical_head.cached_head();
+                    let response = eth2::lighthouse::ResetPayloadStatusesResponse {
+                        head_block_root: cached_head.head_block_root(),
+                        head_slot: cached_head.head_slot(),
+                        execution_optimistic: chain
+                            .is_optimistic_or_invalid_head()
+                            .map_err(warp_utils::reject::beacon_chain_error)?,
+                    };
+                    Ok(warp::reply::json(&response).into_response())
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_rewards
     let get_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4286,6 +4626,22 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/blob_inclusion_forecast
+    let get_lighthouse_blob_inclusion_forecast = warp::path("lighthouse")
+        .and(warp::path("blob_inclusion_forecast"))
+        .and(warp::query::<eth2::lighthouse::BlobInclusionForecastQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    blob_inclusion_forecast::get_blob_inclusion_forecast(query, chain)
+                        .map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
     // GET lighthouse/merge_readiness
     let get_lighthouse_merge_readiness = warp::path("lighthouse")
         .and(warp::path("merge_readiness"))
@@ -4310,7 +4666,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path::end())
         .and(multi_key_query::<api_types::EventQuery>())
         .and(task_spawner_filter.clone())
-        .and(chain_filter)
+        .and(chain_filter.clone())
         .then(
             |topics_res: Result<api_types::EventQuery, warp::Rejection>,
              task_spawner: TaskSpawner<T::EthSpec>,
@@ -4322,52 +4678,7 @@ pub fn serve<T: BeaconChainTypes>(
 
                     if let Some(event_handler) = chain.event_handler.as_ref() {
                         for topic in topics.topics {
-                            let receiver = match topic {
-                                api_types::EventTopic::Head => event_handler.subscribe_head(),
-                                api_types::EventTopic::Block => event_handler.subscribe_block(),
-                                api_types::EventTopic::BlobSidecar => {
-                                    event_handler.subscribe_blob_sidecar()
-                                }
-                                api_types::EventTopic::Attestation => {
-                                    event_handler.subscribe_attestation()
-                                }
-                                api_types::EventTopic::VoluntaryExit => {
-                                    event_handler.subscribe_exit()
-                                }
-                                api_types::EventTopic::FinalizedCheckpoint => {
-                                    event_handler.subscribe_finalized()
-                                }
-                                api_types::EventTopic::ChainReorg => {
-                                    event_handler.subscribe_reorgs()
-                                }
-                                api_types::EventTopic::ContributionAndProof => {
-                                    event_handler.subscribe_contributions()
-                                }
-                                api_types::EventTopic::PayloadAttributes => {
-                                    event_handler.subscribe_payload_attributes()
-                                }
-                                api_types::EventTopic::LateHead => {
-                                    event_handler.subscribe_late_head()
-                                }
-                                api_types::EventTopic::LightClientFinalityUpdate => {
-                                    event_handler.subscribe_light_client_finality_update()
-                                }
-                                api_types::EventTopic::LightClientOptimisticUpdate => {
-                                    event_handler.subscribe_light_client_optimistic_update()
-                                }
-                                api_types::EventTopic::BlockReward => {
-                                    event_handler.subscribe_block_reward()
-                                }
-                                api_types::EventTopic::AttesterSlashing => {
-                                    event_handler.subscribe_attester_slashing()
-                                }
-                                api_types::EventTopic::ProposerSlashing => {
-                                    event_handler.subscribe_proposer_slashing()
-                                }
-                                api_types::EventTopic::BlsToExecutionChange => {
-                                    event_handler.subscribe_bls_to_execution_change()
-                                }
-                            };
+                            let receiver = event_handler.subscribe(topic);
 
                             receivers.push(
                                 BroadcastStream::new(receiver)
@@ -4406,6 +4717,54 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/events/ws
+    let sse_capacity_multiplier = ctx.config.sse_capacity_multiplier;
+    let get_lighthouse_events_ws = warp::path("lighthouse")
+        .and(warp::path("events"))
+        .and(warp::path("ws"))
+        .and(warp::path::end())
+        .and(multi_key_query::<api_types::WebSocketEventQuery>())
+        .and(warp::ws())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter)
+        .and(log_filter.clone())
+        .then(
+            move |query_res: Result<api_types::WebSocketEventQuery, warp::Rejection>,
+                  ws: warp::ws::Ws,
+                  task_spawner: TaskSpawner<T::EthSpec>,
+                  chain: Arc<BeaconChain<T>>,
+                  log: Logger| {
+                task_spawner.blocking_response_task(Priority::P0, move || {
+                    let query = query_res?;
+                    let policy = match query.on_full.as_deref() {
+                        None | Some([]) => api_types::WebSocketDropPolicy::default(),
+                        Some([policy]) => *policy,
+                        Some(_) => {
+                            return Err(warp_utils::reject::custom_bad_request(
+                                "on_full may only be given once".to_string(),
+                            ))
+                        }
+                    };
+
+                    let event_handler = chain.event_handler.as_ref().ok_or_else(|| {
+                        warp_utils::reject::custom_server_error(
+                            "event handler was not initialized".to_string(),
+                        )
+                    })?;
+                    let receivers = query
+                        .topics
+                        .into_iter()
+                        .map(|topic| BroadcastStream::new(event_handler.subscribe(topic)))
+                        .collect::<Vec<_>>();
+
+                    let queue_len = events::WEBSOCKET_QUEUE_LEN * sse_capacity_multiplier;
+                    Ok(ws.on_upgrade(move |socket| {
+                        events::serve_websocket(socket, receivers, queue_len, policy, log)
+                    }))
+                })
+            },
+        );
+
     // Subscribe to logs via Server Side Events
     // /lighthouse/logs
     let lighthouse_log_events = warp::path("lighthouse")
@@ -4455,6 +4814,7 @@ pub fn serve<T: BeaconChainTypes>(
     // Define the ultimate set of routes that will be provided to the server.
     // Use `uor` rather than `or` in order to simplify types (see `UnifyingOrFilter`).
     let routes = warp::get()
+        .and(expensive_routes.clone())
         .and(
             get_beacon_genesis
                 .uor(get_beacon_state_root)
@@ -4506,7 +4866,12 @@ pub fn serve<T: BeaconChainTypes>(
                 .uor(get_lighthouse_nat)
                 .uor(get_lighthouse_peers)
                 .uor(get_lighthouse_peers_connected)
+                .uor(get_lighthouse_peers_blob_withholding)
+                .uor(get_lighthouse_peers_history)
+                .uor(get_lighthouse_gossip_mesh)
                 .uor(get_lighthouse_proto_array)
+                .uor(get_lighthouse_debug_fork_choice_snapshot)
+                .uor(get_lighthouse_optimistic_sync)
                 .uor(get_lighthouse_validator_inclusion_global)
                 .uor(get_lighthouse_validator_inclusion)
                 .uor(get_lighthouse_eth1_syncing)
@@ -4529,22 +4894,25 @@ pub fn serve<T: BeaconChainTypes>(
                         .and(get_beacon_light_client_bootstrap),
                 )
                 .uor(get_lighthouse_block_packing_efficiency)
+                .uor(get_lighthouse_blob_inclusion_forecast)
                 .uor(get_lighthouse_merge_readiness)
                 .uor(get_events)
+                .uor(get_lighthouse_events_ws)
                 .uor(get_expected_withdrawals)
                 .uor(lighthouse_log_events.boxed())
                 .recover(warp_utils::reject::handle_rejection),
         )
         .boxed()
         .uor(
-            warp::post().and(
+            warp::post().and(expensive_routes).and(
                 warp::header::exact(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
                     // Routes which expect `application/octet-stream` go within this `and`.
                     .and(
                         post_beacon_blocks_ssz
                             .uor(post_beacon_blocks_v2_ssz)
                             .uor(post_beacon_blinded_blocks_ssz)
-                            .uor(post_beacon_blinded_blocks_v2_ssz),
+                            .uor(post_beacon_blinded_blocks_v2_ssz)
+                            .uor(post_lighthouse_eth1_deposit_snapshot_ssz),
                     )
                     .uor(post_beacon_blocks)
                     .uor(post_beacon_blinded_blocks)
@@ -4571,9 +4939,15 @@ pub fn serve<T: BeaconChainTypes>(
                     .uor(post_validator_liveness_epoch)
                     .uor(post_lighthouse_liveness)
                     .uor(post_lighthouse_database_reconstruct)
+                    .uor(post_lighthouse_database_import_blobs)
+                    .uor(post_lighthouse_database_backfill_payloads)
+                    .uor(post_lighthouse_fork_choice_reset_payload_statuses)
+                    .uor(post_lighthouse_gossip_scoring_reload)
                     .uor(post_lighthouse_block_rewards)
                     .uor(post_lighthouse_ui_validator_metrics)
                     .uor(post_lighthouse_ui_validator_info)
+                    .uor(post_lighthouse_subnet_assignments)
+                    .uor(post_lighthouse_eth1_deposit_snapshot)
                     .recover(warp_utils::reject::handle_rejection),
             ),
         )
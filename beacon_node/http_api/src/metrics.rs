@@ -48,4 +48,17 @@ lazy_static::lazy_static! {
         "http_api_block_published_very_late_total",
         "The count of times a block was published beyond the attestation deadline"
     );
+    pub static ref HTTP_API_EXPENSIVE_REQUESTS_REJECTED_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "http_api_expensive_requests_rejected_total",
+        "Count of requests to expensive routes rejected for a missing or invalid token, or rate limiting",
+        &["group", "reason"]
+    );
+    pub static ref HTTP_API_WEBSOCKET_EVENTS_DROPPED_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "http_api_websocket_events_dropped_total",
+        "Count of events not sent to WebSocket clients because their send queue was full"
+    );
+    pub static ref HTTP_API_WEBSOCKET_SLOW_CLIENTS_DISCONNECTED_TOTAL: Result<IntCounter> = try_create_int_counter(
+        "http_api_websocket_slow_clients_disconnected_total",
+        "Count of WebSocket clients disconnected because their send queue was full"
+    );
 }
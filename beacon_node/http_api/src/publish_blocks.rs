@@ -1,6 +1,7 @@
 use crate::metrics;
 
 use beacon_chain::block_verification_types::{AsBlock, BlockContentsError};
+use beacon_chain::kzg_utils::validate_blobs;
 use beacon_chain::validator_monitor::{get_block_delay_ms, timestamp_now};
 use beacon_chain::{
     AvailabilityProcessingStatus, BeaconChain, BeaconChainError, BeaconChainTypes, BlockError,
@@ -265,8 +266,11 @@ pub async fn publish_block<T: BeaconChainTypes, B: IntoGossipVerifiedBlockConten
             }
             Ok(warp::reply().into_response())
         }
-        Ok(AvailabilityProcessingStatus::MissingComponents(_, block_root)) => {
-            let msg = format!("Missing parts of block with root {:?}", block_root);
+        Ok(AvailabilityProcessingStatus::MissingComponents(pending)) => {
+            let msg = format!(
+                "Missing parts of block with root {:?}",
+                pending.block_root()
+            );
             if let BroadcastValidation::Gossip = validation_level {
                 Err(warp_utils::reject::broadcast_without_import(msg))
             } else {
@@ -329,6 +333,54 @@ pub async fn publish_blinded_block<T: BeaconChainTypes>(
     .await
 }
 
+/// Check that the blobs revealed by the builder for `block` match the commitments in `block`.
+fn verify_builder_blobs<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    block: &SignedBlindedBeaconBlock<T::EthSpec>,
+    full_payload: &FullPayloadContents<T::EthSpec>,
+) -> Result<(), String> {
+    let FullPayloadContents::PayloadAndBlobs(payload_and_blobs) = full_payload else {
+        return Ok(());
+    };
+    let blobs_bundle = &payload_and_blobs.blobs_bundle;
+    let commitments = block
+        .message()
+        .body()
+        .blob_kzg_commitments()
+        .map_err(|_| "blobs were revealed for a block without commitments".to_string())?;
+
+    if blobs_bundle.commitments != *commitments {
+        return Err(format!(
+            "revealed {} commitments which don't match the {} in the block",
+            blobs_bundle.commitments.len(),
+            commitments.len()
+        ));
+    }
+    if blobs_bundle.blobs.len() != commitments.len()
+        || blobs_bundle.proofs.len() != commitments.len()
+    {
+        return Err(format!(
+            "revealed {} blobs and {} proofs for {} commitments",
+            blobs_bundle.blobs.len(),
+            blobs_bundle.proofs.len(),
+            commitments.len()
+        ));
+    }
+
+    // Without a trusted setup the blobs will be rejected when they are verified for gossip, but
+    // that isn't the builder's fault.
+    let Some(kzg) = chain.kzg.as_ref() else {
+        return Ok(());
+    };
+    validate_blobs::<T::EthSpec>(
+        kzg,
+        commitments,
+        blobs_bundle.blobs.iter().collect(),
+        &blobs_bundle.proofs,
+    )
+    .map_err(|e| format!("invalid KZG proofs: {:?}", e))
+}
+
 /// Deconstruct the given blinded block, and construct a full block. This attempts to use the
 /// execution layer's payload cache, and if that misses, attempts a blind block proposal to retrieve
 /// the full payload.
@@ -388,6 +440,25 @@ pub async fn reconstruct_block<T: BeaconChainTypes>(
                     ))
                 })?;
             info!(log, "Successfully published a block to the builder network"; "block_hash" => ?full_payload.block_hash());
+
+            // Don't gossip blobs which don't match the block we signed, and stop using a builder
+            // which reveals them.
+            if let Err(e) = verify_builder_blobs(&chain, &block, &full_payload) {
+                error!(
+                    log,
+                    "Builder revealed invalid blobs";
+                    "info" => "the builder will not be used for a while",
+                    "error" => &e,
+                    "block_root" => ?block_root,
+                    "slot" => block.slot(),
+                );
+                el.record_builder_fault(block.slot());
+                return Err(warp_utils::reject::custom_server_error(format!(
+                    "Builder revealed invalid blobs: {}",
+                    e
+                )));
+            }
+
             ProvenancedPayload::Builder(full_payload)
         };
 
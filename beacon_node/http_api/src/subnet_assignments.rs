@@ -0,0 +1,126 @@
+//! Attestation and sync committee subnet assignments for a set of validators over a range of
+//! epochs, for operators which want to set up subnet peering ahead of time.
+use crate::{attester_duties, sync_committees};
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use eth2::lighthouse::{
+    AttestationSubnetAssignment, EpochSubnetAssignments, SubnetAssignmentsRequest,
+    SyncSubnetAssignment,
+};
+use std::collections::HashMap;
+use types::{Epoch, EthSpec, SubnetId};
+use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_server_error};
+
+/// The maximum number of epochs which may be requested at once.
+const MAX_EPOCHS: u64 = 256;
+
+/// Compute the subnet assignments of `request.indices` for each epoch from `request.start_epoch`
+/// to `request.end_epoch` (inclusive), using the shuffling cache where possible.
+pub fn get_subnet_assignments<T: BeaconChainTypes>(
+    request: SubnetAssignmentsRequest,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<EpochSubnetAssignments>, warp::Rejection> {
+    let current_epoch = chain.epoch().map_err(beacon_chain_error)?;
+    if request.start_epoch < current_epoch {
+        return Err(custom_bad_request(format!(
+            "start_epoch {} is prior to the current epoch {}",
+            request.start_epoch, current_epoch
+        )));
+    }
+    if request.end_epoch < request.start_epoch {
+        return Err(custom_bad_request(
+            "end_epoch must not be less than start_epoch".to_string(),
+        ));
+    }
+    if (request.end_epoch - request.start_epoch).as_u64() >= MAX_EPOCHS {
+        return Err(custom_bad_request(format!(
+            "at most {} epochs may be requested",
+            MAX_EPOCHS
+        )));
+    }
+
+    // Sync committee membership is fixed for a whole period, so only compute it once per period.
+    let mut sync_assignments_by_period = HashMap::new();
+
+    (request.start_epoch.as_u64()..=request.end_epoch.as_u64())
+        .map(Epoch::new)
+        .map(|epoch| {
+            // The shuffling is only known up to one epoch in advance.
+            let attestation_subnets = if epoch <= current_epoch + 1 {
+                Some(attestation_subnet_assignments(
+                    epoch,
+                    &request.indices,
+                    chain,
+                )?)
+            } else {
+                None
+            };
+
+            let period = epoch
+                .sync_committee_period(&chain.spec)
+                .map_err(|e| custom_server_error(format!("invalid epoch: {:?}", e)))?;
+            let sync_committee_subnets = match sync_assignments_by_period.get(&period) {
+                Some(assignments) => assignments.clone(),
+                None => {
+                    let assignments = sync_subnet_assignments(epoch, &request.indices, chain)?;
+                    sync_assignments_by_period.insert(period, assignments.clone());
+                    assignments
+                }
+            };
+
+            Ok(EpochSubnetAssignments {
+                epoch,
+                attestation_subnets,
+                sync_committee_subnets,
+            })
+        })
+        .collect()
+}
+
+fn attestation_subnet_assignments<T: BeaconChainTypes>(
+    epoch: Epoch,
+    indices: &[u64],
+    chain: &BeaconChain<T>,
+) -> Result<Vec<AttestationSubnetAssignment>, warp::Rejection> {
+    attester_duties::attester_duties(epoch, indices, chain)?
+        .data
+        .into_iter()
+        .map(|duty| {
+            let subnet_id = SubnetId::compute_subnet::<T::EthSpec>(
+                duty.slot,
+                duty.committee_index,
+                duty.committees_at_slot,
+                &chain.spec,
+            )
+            .map_err(|e| custom_server_error(format!("unable to compute subnet: {:?}", e)))?;
+            Ok(AttestationSubnetAssignment {
+                validator_index: duty.validator_index,
+                slot: duty.slot,
+                committee_index: duty.committee_index,
+                subnet_id,
+            })
+        })
+        .collect()
+}
+
+fn sync_subnet_assignments<T: BeaconChainTypes>(
+    epoch: Epoch,
+    indices: &[u64],
+    chain: &BeaconChain<T>,
+) -> Result<Vec<SyncSubnetAssignment>, warp::Rejection> {
+    sync_committees::sync_committee_duties(epoch, indices, chain)?
+        .data
+        .into_iter()
+        .map(|duty| {
+            let mut subnet_ids = duty
+                .subnet_ids::<T::EthSpec>()
+                .map_err(|e| custom_server_error(format!("unable to compute subnets: {:?}", e)))?
+                .into_iter()
+                .collect::<Vec<_>>();
+            subnet_ids.sort_unstable_by_key(|subnet_id| **subnet_id);
+            Ok(SyncSubnetAssignment {
+                validator_index: duty.validator_index,
+                subnet_ids,
+            })
+        })
+        .collect()
+}
@@ -91,7 +91,7 @@ fn duties_from_state_load<T: BeaconChainTypes>(
     let current_epoch = chain.epoch()?;
     let tolerant_current_epoch = chain
         .slot_clock
-        .now_with_future_tolerance(chain.spec.maximum_gossip_clock_disparity())
+        .now_with_future_tolerance(chain.config.maximum_gossip_clock_disparity(&chain.spec))
         .ok_or(BeaconChainError::UnableToReadSlot)?
         .epoch(T::EthSpec::slots_per_epoch());
 
@@ -132,9 +132,28 @@ pub async fn create_api_server<T: BeaconChainTypes>(
     test_runtime: &TestRuntime,
     log: Logger,
 ) -> ApiServer<T, impl Future<Output = ()>> {
-    // Use port 0 to allocate a new unused port.
-    let port = 0;
+    create_api_server_with_config(
+        chain,
+        test_runtime,
+        log,
+        Config {
+            enabled: true,
+            // Use port 0 to allocate a new unused port.
+            listen_port: 0,
+            data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
+            enable_light_client_server: true,
+            ..Config::default()
+        },
+    )
+    .await
+}
 
+pub async fn create_api_server_with_config<T: BeaconChainTypes>(
+    chain: Arc<BeaconChain<T>>,
+    test_runtime: &TestRuntime,
+    log: Logger,
+    config: Config,
+) -> ApiServer<T, impl Future<Output = ()>> {
     let (network_senders, network_receivers) = NetworkSenders::new();
 
     // Default metadata
@@ -207,7 +226,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
         work_reprocessing_rx,
         None,
         chain.slot_clock.clone(),
-        chain.spec.maximum_gossip_clock_disparity(),
+        chain.config.maximum_gossip_clock_disparity(&chain.spec),
         BeaconProcessorQueueLengths::from_state(
             &chain.canonical_head.cached_head().snapshot.beacon_state,
             &chain.spec,
@@ -217,13 +236,7 @@ pub async fn create_api_server<T: BeaconChainTypes>(
     .unwrap();
 
     let ctx = Arc::new(Context {
-        config: Config {
-            enabled: true,
-            listen_port: port,
-            data_dir: std::path::PathBuf::from(DEFAULT_ROOT_DIR),
-            enable_light_client_server: true,
-            ..Config::default()
-        },
+        config,
         chain: Some(chain),
         network_senders: Some(network_senders),
         network_globals: Some(network_globals),
@@ -113,5 +113,9 @@ pub fn inconsistent_fork_rejection(error: InconsistentFork) -> warp::reject::Rej
 }
 
 pub fn unsupported_version_rejection(version: EndpointVersion) -> warp::reject::Rejection {
-    warp_utils::reject::custom_bad_request(format!("Unsupported endpoint version: {}", version))
+    warp_utils::reject::custom_bad_request(format!(
+        "{}: {}",
+        eth2::UNSUPPORTED_VERSION_MESSAGE,
+        version
+    ))
 }
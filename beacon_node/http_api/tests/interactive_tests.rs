@@ -1,15 +1,18 @@
 //! Generic tests that make use of the (newer) `InteractiveApiTester`
 use beacon_chain::{
     chain_config::{DisallowedReOrgOffsets, ReOrgThreshold},
-    test_utils::{AttestationStrategy, BlockStrategy, SyncCommitteeStrategy},
+    test_utils::{AttestationStrategy, BeaconChainHarness, BlockStrategy, SyncCommitteeStrategy},
     ChainConfig,
 };
 use beacon_processor::work_reprocessing_queue::ReprocessQueueMessage;
 use eth2::types::ProduceBlockV3Response;
 use eth2::types::{DepositContractData, StateId};
+use eth2::{BeaconNodeHttpClient, StatusCode, Timeouts};
 use execution_layer::{ForkchoiceState, PayloadAttributes};
-use http_api::test_utils::InteractiveTester;
+use http_api::test_utils::{create_api_server_with_config, ApiServer, InteractiveTester};
+use http_api::{parse_route_quotas, Config};
 use parking_lot::Mutex;
+use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
 use state_processing::{
     per_block_processing::get_expected_withdrawals, state_advance::complete_state_advance,
@@ -916,3 +919,86 @@ async fn queue_attestations_from_http() {
 
     attestation_future.await.unwrap();
 }
+
+// Test that the expensive routes require the bearer token and are rate limited, while the other
+// routes are unaffected.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn expensive_routes_token_and_rate_limits() {
+    let validator_count = 24;
+    let harness = BeaconChainHarness::builder(E::default())
+        .default_spec()
+        .deterministic_keypairs(validator_count)
+        .fresh_ephemeral_store()
+        .mock_execution_layer()
+        .build();
+    harness.extend_slots(2).await;
+
+    let ApiServer {
+        server,
+        listening_socket,
+        ..
+    } = create_api_server_with_config(
+        harness.chain.clone(),
+        &harness.runtime,
+        harness.logger().clone(),
+        Config {
+            enabled: true,
+            listen_port: 0,
+            expensive_routes_token: Some("secret".to_string()),
+            expensive_routes_quotas: parse_route_quotas("debug:1/60").unwrap(),
+            ..Config::default()
+        },
+    )
+    .await;
+    tokio::spawn(server);
+
+    let client = BeaconNodeHttpClient::new(
+        SensitiveUrl::parse(&format!("http://{listening_socket}")).unwrap(),
+        Timeouts::set_all(Duration::from_secs(1)),
+    );
+
+    // The head state doesn't require the token, but historical states do.
+    client
+        .get_beacon_states_root(StateId::Head)
+        .await
+        .unwrap()
+        .unwrap();
+    let error = client
+        .get_beacon_states_root(StateId::Slot(Slot::new(1)))
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::UNAUTHORIZED));
+
+    let state_root_url = format!("http://{listening_socket}/eth/v1/beacon/states/1/root");
+    let error = client
+        .get_response(state_root_url.as_str(), |builder| {
+            builder.bearer_auth("not-the-secret")
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::FORBIDDEN));
+    // The rejection must not echo the token which was presented.
+    assert!(!format!("{error:?}").contains("not-the-secret"));
+    client
+        .get_response(state_root_url.as_str(), |builder| {
+            builder.bearer_auth("secret")
+        })
+        .await
+        .unwrap();
+
+    // Only one debug request is allowed each minute.
+    let fork_choice_url = format!("http://{listening_socket}/eth/v1/debug/fork_choice");
+    client
+        .get_response(fork_choice_url.as_str(), |builder| {
+            builder.bearer_auth("secret")
+        })
+        .await
+        .unwrap();
+    let error = client
+        .get_response(fork_choice_url.as_str(), |builder| {
+            builder.bearer_auth("secret")
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.status(), Some(StatusCode::TOO_MANY_REQUESTS));
+}
@@ -5,6 +5,7 @@ use beacon_chain::{
 };
 use environment::null_logger;
 use eth2::{
+    lighthouse::BlobWithholdingPeer,
     mixin::{RequestAccept, ResponseForkName, ResponseOptional},
     reqwest::RequestBuilder,
     types::{
@@ -15,17 +16,19 @@ use eth2::{
 use execution_layer::test_utils::{
     MockBuilder, Operation, DEFAULT_BUILDER_PAYLOAD_VALUE_WEI, DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI,
 };
+use execution_layer::{ChainHealth, FailedCondition};
 use futures::stream::{Stream, StreamExt};
 use futures::FutureExt;
 use http_api::{
     test_utils::{create_api_server, ApiServer},
     BlockId, StateId,
 };
-use lighthouse_network::{Enr, EnrExt, PeerId};
+use lighthouse_network::{Enr, EnrExt, NetworkGlobals, PeerId};
 use network::NetworkReceivers;
 use proto_array::ExecutionStatus;
 use sensitive_url::SensitiveUrl;
 use slot_clock::SlotClock;
+use ssz::Encode;
 use state_processing::per_block_processing::get_expected_withdrawals;
 use state_processing::per_slot_processing;
 use state_processing::state_advance::partial_state_advance;
@@ -35,7 +38,7 @@ use tokio::time::Duration;
 use tree_hash::TreeHash;
 use types::application_domain::ApplicationDomain;
 use types::{
-    AggregateSignature, BitList, Domain, EthSpec, ExecutionBlockHash, Hash256, Keypair,
+    AggregateSignature, BitList, Domain, EthSpec, ExecutionBlockHash, Hash256, Keypair, KzgProof,
     MainnetEthSpec, RelativeEpoch, SelectionProof, SignedRoot, Slot,
 };
 
@@ -74,6 +77,7 @@ struct ApiTester {
     network_rx: NetworkReceivers<E>,
     local_enr: Enr,
     external_peer_id: PeerId,
+    network_globals: Arc<NetworkGlobals<E>>,
     mock_builder: Option<Arc<MockBuilder<E>>>,
 }
 
@@ -251,7 +255,7 @@ impl ApiTester {
         let log = null_logger().unwrap();
 
         let ApiServer {
-            ctx: _,
+            ctx,
             server,
             listening_socket,
             network_rx,
@@ -296,6 +300,7 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
+            network_globals: ctx.network_globals.clone().unwrap(),
             mock_builder,
         }
     }
@@ -348,7 +353,7 @@ impl ApiTester {
         let log = null_logger().unwrap();
 
         let ApiServer {
-            ctx: _,
+            ctx,
             server,
             listening_socket,
             network_rx,
@@ -383,6 +388,7 @@ impl ApiTester {
             network_rx,
             local_enr,
             external_peer_id,
+            network_globals: ctx.network_globals.clone().unwrap(),
             mock_builder: None,
         }
     }
@@ -1629,6 +1635,150 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_database_import_blobs(self) -> Self {
+        let block_id = BlockId(CoreBlockId::Finalized);
+        let (block_root, _, _) = block_id.root(&self.chain).unwrap();
+        let blobs = self.chain.get_blobs(&block_root).unwrap();
+        assert!(!blobs.is_empty());
+
+        self.chain
+            .store
+            .do_atomically_with_block_and_blobs_cache(vec![store::StoreOp::DeleteBlobs(block_root)])
+            .unwrap();
+        assert!(self.chain.get_blobs(&block_root).unwrap().is_empty());
+
+        // Simulate the blobs having been pruned, so that the oldest blob slot is after the block.
+        let block_slot = blobs[0].slot();
+        let blob_info = self.chain.store.get_blob_info();
+        self.chain
+            .store
+            .compare_and_set_blob_info_with_write(
+                blob_info.clone(),
+                store::metadata::BlobInfo {
+                    oldest_blob_slot: Some(block_slot + 1),
+                    ..blob_info
+                },
+            )
+            .unwrap();
+
+        let sidecars = blobs
+            .iter()
+            .map(|blob| blob.as_ref().clone())
+            .collect::<Vec<_>>();
+
+        // Sidecars which fail kzg verification are rejected without being stored.
+        let mut invalid_sidecars = sidecars.clone();
+        invalid_sidecars[0].kzg_proof = KzgProof::empty();
+        self.client
+            .post_lighthouse_database_import_blobs(&invalid_sidecars)
+            .await
+            .unwrap_err();
+        assert!(self.chain.get_blobs(&block_root).unwrap().is_empty());
+
+        self.client
+            .post_lighthouse_database_import_blobs(&sidecars)
+            .await
+            .unwrap();
+        assert_eq!(self.chain.get_blobs(&block_root).unwrap(), blobs);
+        assert_eq!(
+            self.chain.store.get_blob_info().oldest_blob_slot,
+            Some(block_slot)
+        );
+
+        self
+    }
+
+    pub async fn test_post_lighthouse_database_import_blobs_with_gap(self) -> Self {
+        // Find the two most recent finalized blocks with blobs.
+        let finalized_slot = self
+            .chain
+            .canonical_head
+            .cached_head()
+            .finalized_checkpoint()
+            .epoch
+            .start_slot(E::slots_per_epoch());
+        let mut block_roots = self
+            .chain
+            .forwards_iter_block_roots_until(Slot::new(0), finalized_slot)
+            .unwrap()
+            .map(|result| result.unwrap().0)
+            .collect::<Vec<_>>();
+        block_roots.dedup();
+        let blocks_with_blobs = block_roots
+            .into_iter()
+            .map(|block_root| (block_root, self.chain.get_blobs(&block_root).unwrap()))
+            .filter(|(_, blobs)| !blobs.is_empty())
+            .collect::<Vec<_>>();
+        let [.., (older_root, older_blobs), (newer_root, newer_blobs)] =
+            blocks_with_blobs.as_slice()
+        else {
+            panic!("expected at least two finalized blocks with blobs");
+        };
+        let older_slot = older_blobs[0].slot();
+        let newer_slot = newer_blobs[0].slot();
+
+        self.chain
+            .store
+            .do_atomically_with_block_and_blobs_cache(vec![
+                store::StoreOp::DeleteBlobs(*older_root),
+                store::StoreOp::DeleteBlobs(*newer_root),
+            ])
+            .unwrap();
+        let blob_info = self.chain.store.get_blob_info();
+        self.chain
+            .store
+            .compare_and_set_blob_info_with_write(
+                blob_info.clone(),
+                store::metadata::BlobInfo {
+                    oldest_blob_slot: Some(newer_slot + 1),
+                    ..blob_info
+                },
+            )
+            .unwrap();
+
+        let to_sidecars = |blobs: &BlobSidecarList<E>| {
+            blobs
+                .iter()
+                .map(|blob| blob.as_ref().clone())
+                .collect::<Vec<_>>()
+        };
+
+        // The blobs of the newer block are still missing, so the oldest blob slot can't move back
+        // past them.
+        self.client
+            .post_lighthouse_database_import_blobs(&to_sidecars(older_blobs))
+            .await
+            .unwrap();
+        assert_eq!(self.chain.get_blobs(older_root).unwrap(), *older_blobs);
+        assert_eq!(
+            self.chain.store.get_blob_info().oldest_blob_slot,
+            Some(newer_slot + 1)
+        );
+
+        // Filling the gap moves it back to the newer block.
+        self.client
+            .post_lighthouse_database_import_blobs(&to_sidecars(newer_blobs))
+            .await
+            .unwrap();
+        assert_eq!(
+            self.chain.store.get_blob_info().oldest_blob_slot,
+            Some(newer_slot)
+        );
+
+        // Now the older blobs are contiguous with the rest, so importing them again moves it back
+        // to the older block.
+        self.client
+            .post_lighthouse_database_import_blobs(&to_sidecars(older_blobs))
+            .await
+            .unwrap();
+        assert_eq!(
+            self.chain.store.get_blob_info().oldest_blob_slot,
+            Some(older_slot)
+        );
+
+        self
+    }
+
     pub async fn test_beacon_blocks_attestations(self) -> Self {
         for block_id in self.interesting_block_ids() {
             let result = self
@@ -4973,6 +5123,68 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_builder_fault_on_invalid_revealed_blobs(self) -> Self {
+        // Ensure the builder payload is chosen, and that it has blobs to reveal.
+        let mock_builder = self.mock_builder.as_ref().unwrap();
+        mock_builder.add_operation(Operation::Value(Uint256::from(
+            DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI + 1,
+        )));
+        mock_builder.invalid_revealed_blobs();
+        self.harness
+            .mock_execution_layer
+            .as_ref()
+            .unwrap()
+            .server
+            .execution_block_generator()
+            .blobs_per_payload = Some(1);
+
+        let fork = self.chain.canonical_head.cached_head().head_fork();
+        let genesis_validators_root = self.chain.genesis_validators_root;
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+        let (proposer_index, randao_reveal) = self.get_test_randao(slot, epoch).await;
+        let sk = &self.validator_keypairs()[proposer_index as usize].sk;
+
+        let block = self
+            .client
+            .get_validator_blinded_blocks::<E>(slot, &randao_reveal, None)
+            .await
+            .unwrap()
+            .data;
+        assert!(!block.body().blob_kzg_commitments().unwrap().is_empty());
+        let signed_block = block.sign(sk, &fork, genesis_validators_root, &self.chain.spec);
+
+        // The revealed blobs fail verification, so the block isn't published.
+        self.client
+            .post_beacon_blinded_blocks(&signed_block)
+            .await
+            .unwrap_err();
+        assert_ne!(
+            self.chain.head_beacon_block_root(),
+            signed_block.canonical_root()
+        );
+
+        // The fault is recorded, and the builder is avoided until it has cooled down.
+        let execution_layer = self.chain.execution_layer.as_ref().unwrap();
+        assert_eq!(execution_layer.last_builder_fault(), Some(slot));
+        assert!(matches!(
+            self.chain
+                .is_healthy(&self.chain.head_beacon_block_root())
+                .unwrap(),
+            ChainHealth::Unhealthy(FailedCondition::RecentBuilderFault)
+        ));
+
+        self.harness
+            .mock_execution_layer
+            .as_ref()
+            .unwrap()
+            .server
+            .execution_block_generator()
+            .blobs_per_payload = None;
+
+        self
+    }
+
     pub async fn test_lighthouse_rejects_invalid_withdrawals_root(self) -> Self {
         // Ensure builder payload *would be* chosen
         self.mock_builder
@@ -5066,12 +5278,80 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_peers_blob_withholding(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_peers_blob_withholding()
+            .await
+            .unwrap();
+        assert!(result.is_empty());
+
+        for _ in 0..2 {
+            self.network_globals
+                .peers
+                .write()
+                .record_blob_withholding(&self.external_peer_id)
+                .unwrap();
+        }
+
+        let result = self
+            .client
+            .get_lighthouse_peers_blob_withholding()
+            .await
+            .unwrap();
+        assert_eq!(
+            result,
+            vec![BlobWithholdingPeer {
+                peer_id: self.external_peer_id.to_string(),
+                blob_withholding_count: 2,
+            }]
+        );
+
+        self
+    }
+
     pub async fn test_get_lighthouse_proto_array(self) -> Self {
         self.client.get_lighthouse_proto_array().await.unwrap();
 
         self
     }
 
+    pub async fn test_get_lighthouse_debug_fork_choice_snapshot(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_debug_fork_choice_snapshot_ssz()
+            .await
+            .unwrap()
+            .unwrap();
+
+        let expected = self.chain.persisted_fork_choice().as_ssz_bytes();
+        assert_eq!(result, expected);
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_optimistic_sync(self) -> Self {
+        let result = self
+            .client
+            .get_lighthouse_optimistic_sync()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(
+            result.safe_slots_to_import_optimistically,
+            self.chain.spec.safe_slots_to_import_optimistically
+        );
+        assert_eq!(
+            result.head_is_optimistic,
+            self.chain.is_optimistic_or_invalid_head().unwrap()
+        );
+        // The test harness verifies all payloads, so nothing is optimistic.
+        assert!(result.optimistic_blocks.is_empty());
+
+        self
+    }
+
     pub async fn test_get_lighthouse_validator_inclusion_global(self) -> Self {
         let epoch = self.chain.epoch().unwrap() - 1;
         self.client
@@ -5118,6 +5398,58 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_eth1_deposit_snapshot(self) -> Self {
+        let snapshot = |finalized_byte: u8, deposit_count: u64| {
+            let mut snapshot = DepositTreeSnapshot {
+                finalized: vec![Hash256::repeat_byte(finalized_byte)],
+                deposit_root: Hash256::zero(),
+                deposit_count,
+                execution_block_hash: Hash256::repeat_byte(42),
+                execution_block_height: 100 + deposit_count,
+            };
+            snapshot.deposit_root = snapshot.calculate_root().unwrap();
+            snapshot
+        };
+
+        // Snapshots with an incorrect deposit root are rejected.
+        let mut invalid = snapshot(1, 1);
+        invalid.deposit_root = Hash256::repeat_byte(3);
+        assert!(self
+            .client
+            .post_lighthouse_eth1_deposit_snapshot(&invalid)
+            .await
+            .is_err());
+
+        let first = snapshot(1, 1);
+        self.client
+            .post_lighthouse_eth1_deposit_snapshot(&first)
+            .await
+            .unwrap();
+        assert_eq!(
+            self.client.get_deposit_snapshot_ssz().await.unwrap(),
+            Some(first.clone())
+        );
+
+        // A snapshot which doesn't advance the deposit tree is rejected.
+        assert!(self
+            .client
+            .post_lighthouse_eth1_deposit_snapshot_ssz(&first)
+            .await
+            .is_err());
+
+        let second = snapshot(2, 2);
+        self.client
+            .post_lighthouse_eth1_deposit_snapshot_ssz(&second)
+            .await
+            .unwrap();
+        assert_eq!(
+            self.client.get_deposit_snapshot().await.unwrap(),
+            Some(second)
+        );
+
+        self
+    }
+
     pub async fn test_get_lighthouse_staking(self) -> Self {
         let result = self.client.get_lighthouse_staking().await.unwrap();
 
@@ -5126,6 +5458,54 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_blob_inclusion_forecast(self) -> Self {
+        let forecast = self
+            .client
+            .get_lighthouse_blob_inclusion_forecast(Some(4))
+            .await
+            .unwrap()
+            .data;
+
+        let head = self.chain.head_snapshot();
+        let head_block = forecast.blocks.last().unwrap();
+        assert_eq!(forecast.blocks.len(), 4);
+        assert_eq!(head_block.block_root, head.beacon_block_root);
+        assert_eq!(
+            head_block.blob_count,
+            head.beacon_block
+                .message()
+                .body()
+                .blob_kzg_commitments()
+                .unwrap()
+                .len() as u64
+        );
+        assert!(forecast
+            .blocks
+            .windows(2)
+            .all(|pair| pair[0].slot < pair[1].slot));
+        assert_eq!(
+            forecast.target_blobs_per_block,
+            self.chain.spec.target_blobs_per_block::<E>()
+        );
+        assert_eq!(
+            forecast.max_blobs_per_block,
+            self.chain.spec.max_blobs_per_block::<E>()
+        );
+        assert_eq!(forecast.projections.len() as u64, E::slots_per_epoch());
+
+        // Requests for too many blocks are rejected.
+        assert_eq!(
+            self.client
+                .get_lighthouse_blob_inclusion_forecast(Some(1025))
+                .await
+                .unwrap_err()
+                .status(),
+            Some(StatusCode::BAD_REQUEST)
+        );
+
+        self
+    }
+
     pub async fn test_get_lighthouse_database_info(self) -> Self {
         let info = self.client.get_lighthouse_database_info().await.unwrap();
 
@@ -5149,6 +5529,98 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_post_lighthouse_subnet_assignments(self) -> Self {
+        let current_epoch = self.chain.epoch().unwrap();
+        let indices = (0..self.validator_keypairs().len() as u64).collect::<Vec<_>>();
+
+        let assignments = self
+            .client
+            .post_lighthouse_subnet_assignments(&indices, current_epoch, current_epoch + 2)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(assignments.len(), 3);
+
+        for epoch_assignments in &assignments[..2] {
+            let epoch = epoch_assignments.epoch;
+            let expected_attestation_subnets = self
+                .client
+                .post_validator_duties_attester(epoch, &indices)
+                .await
+                .unwrap()
+                .data
+                .into_iter()
+                .map(|duty| eth2::lighthouse::AttestationSubnetAssignment {
+                    validator_index: duty.validator_index,
+                    slot: duty.slot,
+                    committee_index: duty.committee_index,
+                    subnet_id: SubnetId::compute_subnet::<E>(
+                        duty.slot,
+                        duty.committee_index,
+                        duty.committees_at_slot,
+                        &self.chain.spec,
+                    )
+                    .unwrap(),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                epoch_assignments.attestation_subnets,
+                Some(expected_attestation_subnets)
+            );
+
+            let sync_duties = self
+                .client
+                .post_validator_duties_sync(epoch, &indices)
+                .await
+                .unwrap()
+                .data;
+            assert_eq!(
+                epoch_assignments.sync_committee_subnets.len(),
+                sync_duties.len()
+            );
+            for (assignment, duty) in epoch_assignments
+                .sync_committee_subnets
+                .iter()
+                .zip(&sync_duties)
+            {
+                assert_eq!(assignment.validator_index, duty.validator_index);
+                assert_eq!(
+                    assignment.subnet_ids.len(),
+                    duty.subnet_ids::<E>().unwrap().len()
+                );
+            }
+        }
+
+        // The shuffling two epochs ahead isn't known yet.
+        assert_eq!(assignments[2].attestation_subnets, None);
+
+        // Past epochs and overly long ranges are rejected.
+        if let Some(previous_epoch) = current_epoch.as_u64().checked_sub(1) {
+            assert_eq!(
+                self.client
+                    .post_lighthouse_subnet_assignments(
+                        &indices,
+                        Epoch::new(previous_epoch),
+                        current_epoch
+                    )
+                    .await
+                    .unwrap_err()
+                    .status(),
+                Some(StatusCode::BAD_REQUEST)
+            );
+        }
+        assert_eq!(
+            self.client
+                .post_lighthouse_subnet_assignments(&indices, current_epoch, current_epoch + 256)
+                .await
+                .unwrap_err()
+                .status(),
+            Some(StatusCode::BAD_REQUEST)
+        );
+
+        self
+    }
+
     pub async fn test_post_lighthouse_liveness(self) -> Self {
         let epoch = self.chain.epoch().unwrap();
         let head_state = self.chain.head_beacon_state_cloned();
@@ -5553,6 +6025,80 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_supports_validator_blocks_v3(self) -> Self {
+        assert!(self.client.supports_validator_blocks_v3().await.unwrap());
+
+        self
+    }
+
+    pub async fn test_supports_ssz_blocks(self) -> Self {
+        let head_slot = self.chain.head_snapshot().beacon_block.slot();
+
+        assert!(self.client.supports_ssz_blocks().await.unwrap());
+        assert_eq!(self.chain.head_snapshot().beacon_block.slot(), head_slot);
+
+        self
+    }
+
+    pub async fn test_supports_events(self) -> Self {
+        assert!(self
+            .client
+            .supports_events(&[EventTopic::Head, EventTopic::ChainReorg])
+            .await
+            .unwrap());
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_events_ws(self) -> Self {
+        let ws_url = |query: &str| {
+            format!(
+                "{}lighthouse/events/ws?{query}",
+                self.client.as_ref().replacen("http", "ws", 1)
+            )
+        };
+
+        // The drop policy may only be given once.
+        match tokio_tungstenite::connect_async(ws_url(
+            "topics=attestation&on_full=drop&on_full=disconnect",
+        ))
+        .await
+        {
+            Err(tokio_tungstenite::tungstenite::Error::Http(response)) => {
+                assert_eq!(response.status(), 400)
+            }
+            other => panic!("expected a bad request, got {other:?}"),
+        }
+
+        let (mut socket, _) =
+            tokio_tungstenite::connect_async(ws_url("topics=attestation&on_full=disconnect"))
+                .await
+                .unwrap();
+
+        self.client
+            .post_beacon_pool_attestations(self.attestations.as_slice())
+            .await
+            .unwrap();
+
+        // Events are sent in the same form and order as on the SSE stream.
+        for attestation in &self.attestations {
+            let message = tokio::time::timeout(Duration::from_secs(10), socket.next())
+                .await
+                .expect("should receive an event before the timeout")
+                .unwrap()
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+            assert_eq!(json["event"], "attestation");
+            assert_eq!(
+                json["data"],
+                serde_json::to_value(EventKind::<E>::Attestation(Box::new(attestation.clone())))
+                    .unwrap()
+            );
+        }
+
+        self
+    }
+
     pub async fn test_check_optimistic_responses(&mut self) {
         // Check responses are not optimistic.
         let result = self
@@ -5635,6 +6181,126 @@ async fn get_events_from_genesis() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_lighthouse_events_ws() {
+    ApiTester::new().await.test_get_lighthouse_events_ws().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn supports_validator_blocks_v3() {
+    ApiTester::new()
+        .await
+        .test_supports_validator_blocks_v3()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn supports_ssz_blocks() {
+    ApiTester::new().await.test_supports_ssz_blocks().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn supports_events() {
+    ApiTester::new().await.test_supports_events().await;
+}
+
+/// Returns a client for a server which answers every request with `rejection`, handled in the same
+/// way as by the Lighthouse HTTP API.
+fn client_for_rejecting_server(
+    rejection: impl Fn() -> warp::Rejection + Clone + Send + Sync + 'static,
+) -> BeaconNodeHttpClient {
+    let routes = warp::any()
+        .and_then(move || {
+            let rejection = rejection.clone();
+            async move { Err::<String, _>(rejection()) }
+        })
+        .recover(warp_utils::reject::handle_rejection);
+    let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+
+    BeaconNodeHttpClient::new(
+        SensitiveUrl::parse(&format!("http://{addr}")).unwrap(),
+        Timeouts::set_all(Duration::from_secs(SECONDS_PER_SLOT)),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn supports_validator_blocks_v3_unsupported_version() {
+    let client = client_for_rejecting_server(|| {
+        warp_utils::reject::custom_bad_request(format!("{}: v3", eth2::UNSUPPORTED_VERSION_MESSAGE))
+    });
+    assert!(!client.supports_validator_blocks_v3().await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn supports_validator_blocks_v3_not_found() {
+    let client = client_for_rejecting_server(warp::reject::not_found);
+    assert!(!client.supports_validator_blocks_v3().await.unwrap());
+    assert!(!client.supports_events(&[EventTopic::Head]).await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn supports_validator_blocks_v3_invalid_query() {
+    // A server which serves v3 rejects the probe, which has no `randao_reveal`.
+    let client = client_for_rejecting_server(|| {
+        warp_utils::reject::custom_bad_request("invalid query: missing field".to_string())
+    });
+    assert!(client.supports_validator_blocks_v3().await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn supports_ssz_blocks_json_only() {
+    // A server which only accepts JSON bodies.
+    let routes = warp::post()
+        .and(warp::body::json())
+        .map(|_: serde_json::Value| "published");
+    let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    let client = BeaconNodeHttpClient::new(
+        SensitiveUrl::parse(&format!("http://{addr}")).unwrap(),
+        Timeouts::set_all(Duration::from_secs(SECONDS_PER_SLOT)),
+    );
+
+    assert!(!client.supports_ssz_blocks().await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_deposit_snapshot_ssz_json_response() {
+    // A remote which ignores the `Accept` header and always responds with JSON.
+    let snapshot = DepositTreeSnapshot::default();
+    let response = GenericResponse::from(snapshot.clone());
+    let routes = warp::any().map(move || warp::reply::json(&response));
+    let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    let client = BeaconNodeHttpClient::new(
+        SensitiveUrl::parse(&format!("http://{addr}")).unwrap(),
+        Timeouts::set_all(Duration::from_secs(SECONDS_PER_SLOT)),
+    );
+
+    assert_eq!(
+        client.get_deposit_snapshot_ssz().await.unwrap(),
+        Some(snapshot)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn supports_endpoint_probes_time_out() {
+    let routes = warp::any().then(|| async {
+        tokio::time::sleep(Duration::from_secs(SECONDS_PER_SLOT)).await;
+        "too late"
+    });
+    let (addr, server) = warp::serve(routes).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    let client = BeaconNodeHttpClient::new(
+        SensitiveUrl::parse(&format!("http://{addr}")).unwrap(),
+        Timeouts::set_all(Duration::from_millis(100)),
+    );
+
+    assert!(client.supports_validator_blocks_v3().await.is_err());
+    assert!(client.supports_events(&[EventTopic::Head]).await.is_err());
+    assert!(client.supports_ssz_blocks().await.is_err());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn beacon_get() {
     ApiTester::new()
@@ -6418,6 +7084,25 @@ async fn builder_works_post_deneb() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn builder_fault_on_invalid_revealed_blobs() {
+    let mut config = ApiTesterConfig {
+        retain_historic_states: false,
+        spec: E::default_spec(),
+    };
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    config.spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+    config.spec.capella_fork_epoch = Some(Epoch::new(0));
+    config.spec.deneb_fork_epoch = Some(Epoch::new(0));
+
+    ApiTester::new_from_config(config)
+        .await
+        .test_post_validator_register_validator()
+        .await
+        .test_builder_fault_on_invalid_revealed_blobs()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn get_blob_sidecars() {
     let mut config = ApiTesterConfig {
@@ -6439,6 +7124,44 @@ async fn get_blob_sidecars() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_lighthouse_blob_inclusion_forecast() {
+    let mut config = ApiTesterConfig {
+        retain_historic_states: false,
+        spec: E::default_spec(),
+    };
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    config.spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+    config.spec.capella_fork_epoch = Some(Epoch::new(0));
+    config.spec.deneb_fork_epoch = Some(Epoch::new(0));
+
+    ApiTester::new_from_config(config)
+        .await
+        .test_get_lighthouse_blob_inclusion_forecast()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_lighthouse_database_import_blobs() {
+    let mut config = ApiTesterConfig {
+        retain_historic_states: false,
+        spec: E::default_spec(),
+    };
+    config.spec.altair_fork_epoch = Some(Epoch::new(0));
+    config.spec.bellatrix_fork_epoch = Some(Epoch::new(0));
+    config.spec.capella_fork_epoch = Some(Epoch::new(0));
+    config.spec.deneb_fork_epoch = Some(Epoch::new(0));
+
+    ApiTester::new_from_config(config)
+        .await
+        .test_post_beacon_blocks_valid()
+        .await
+        .test_post_lighthouse_database_import_blobs()
+        .await
+        .test_post_lighthouse_database_import_blobs_with_gap()
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn post_validator_liveness_epoch() {
     ApiTester::new()
@@ -6455,8 +7178,14 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_syncing()
         .await
+        .test_get_lighthouse_peers_blob_withholding()
+        .await
         .test_get_lighthouse_proto_array()
         .await
+        .test_get_lighthouse_debug_fork_choice_snapshot()
+        .await
+        .test_get_lighthouse_optimistic_sync()
+        .await
         .test_get_lighthouse_validator_inclusion()
         .await
         .test_get_lighthouse_validator_inclusion_global()
@@ -6467,6 +7196,8 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_eth1_deposit_cache()
         .await
+        .test_post_lighthouse_eth1_deposit_snapshot()
+        .await
         .test_get_lighthouse_staking()
         .await
         .test_get_lighthouse_database_info()
@@ -6474,6 +7205,16 @@ async fn lighthouse_endpoints() {
         .test_post_lighthouse_database_reconstruct()
         .await
         .test_post_lighthouse_liveness()
+        .await
+        .test_post_lighthouse_subnet_assignments()
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn post_lighthouse_subnet_assignments_altair() {
+    ApiTester::new_with_hard_forks(true, false)
+        .await
+        .test_post_lighthouse_subnet_assignments()
         .await;
 }
 
@@ -0,0 +1,53 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use lighthouse_network::libp2p::bytes::BytesMut;
+use lighthouse_network::rpc::encode_snappy_frames;
+use snap::write::FrameEncoder;
+use std::io::Write;
+
+/// The previous encoding strategy: always compress into a temporary buffer, then copy it out.
+fn encode_buffered(bytes: &[u8], dst: &mut BytesMut) {
+    let mut writer = FrameEncoder::new(Vec::new());
+    writer.write_all(bytes).unwrap();
+    writer.flush().unwrap();
+    dst.extend_from_slice(writer.get_ref());
+}
+
+fn payload(len: usize) -> Vec<u8> {
+    // Mostly-zero data with some structure, loosely resembling SSZ-encoded responses.
+    (0..len)
+        .map(|i| if i % 7 == 0 { (i % 251) as u8 } else { 0 })
+        .collect()
+}
+
+fn snappy_framing(c: &mut Criterion) {
+    // Ping, MetaData, Status, a block and a blob sidecar respectively.
+    for (name, len) in [
+        ("ping", 8),
+        ("metadata", 25),
+        ("status", 84),
+        ("block", 50_000),
+        ("blob_sidecar", 131_928),
+    ] {
+        let bytes = payload(len);
+
+        let mut group = c.benchmark_group(name);
+        group.bench_with_input(BenchmarkId::new("buffered", len), &bytes, |b, bytes| {
+            b.iter(|| {
+                let mut dst = BytesMut::new();
+                encode_buffered(black_box(bytes), &mut dst);
+                dst
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("adaptive", len), &bytes, |b, bytes| {
+            b.iter(|| {
+                let mut dst = BytesMut::new();
+                encode_snappy_frames(black_box(bytes), &mut dst).unwrap();
+                dst
+            })
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, snappy_framing);
+criterion_main!(benches);
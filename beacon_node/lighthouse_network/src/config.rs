@@ -1,4 +1,5 @@
 use crate::listen_addr::{ListenAddr, ListenAddress};
+use crate::peer_manager::config::DEFAULT_INBOUND_PRESSURE_THRESHOLD;
 use crate::rpc::config::{InboundRateLimiterConfig, OutboundRateLimiterConfig};
 use crate::types::GossipKind;
 use crate::{Enr, PeerIdSerialized};
@@ -69,6 +70,10 @@ pub struct Config {
     /// Target number of connected peers.
     pub target_peers: usize,
 
+    /// The beacon processor queue pressure (as a percentage) at or above which new inbound peers
+    /// are refused once we have reached the target peer count. `None` disables this.
+    pub inbound_pressure_threshold: Option<u8>,
+
     /// Discv5 configuration parameters.
     #[serde(skip)]
     pub discv5_config: discv5::Config,
@@ -88,6 +93,9 @@ pub struct Config {
     /// Disables peer scoring altogether.
     pub disable_peer_scoring: bool,
 
+    /// Path to a TOML file of gossipsub topic score parameter overrides.
+    pub gossip_scoring_overrides: Option<PathBuf>,
+
     /// Client version
     pub client_version: String,
 
@@ -326,12 +334,14 @@ impl Default for Config {
             enr_quic6_port: None,
             enr_tcp6_port: None,
             target_peers: 100,
+            inbound_pressure_threshold: Some(DEFAULT_INBOUND_PRESSURE_THRESHOLD),
             discv5_config,
             boot_nodes_enr: vec![],
             boot_nodes_multiaddr: vec![],
             libp2p_nodes: vec![],
             trusted_peers: vec![],
             disable_peer_scoring: false,
+            gossip_scoring_overrides: None,
             client_version: lighthouse_version::version_with_platform(),
             disable_discovery: false,
             disable_quic_support: false,
@@ -34,7 +34,7 @@ use slog::{crit, debug, error, info, trace, warn};
 use ssz::Encode;
 use std::num::NonZeroUsize;
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     net::{IpAddr, SocketAddr},
     path::Path,
     pin::Pin,
@@ -62,6 +62,10 @@ const MAX_DISCOVERY_RETRY: usize = 3;
 const MAX_CONCURRENT_SUBNET_QUERIES: usize = 4;
 /// The max number of subnets to search for in a single subnet discovery query.
 const MAX_SUBNETS_IN_QUERY: usize = 3;
+/// Once a subnet has reached `TARGET_SUBNET_PEERS`, it is not searched for again until the number
+/// of peers on it drops below this threshold. This stops peer churn around the target from
+/// triggering a new query at every duty boundary.
+const SUBNET_REQUERY_PEER_THRESHOLD: usize = TARGET_SUBNET_PEERS - 1;
 /// The number of closest peers to search for when doing a regular peer search.
 ///
 /// We could reduce this constant to speed up queries however at the cost of security. It will
@@ -177,6 +181,14 @@ pub struct Discovery<E: EthSpec> {
     /// A queue of subnet queries to be processed.
     queued_queries: VecDeque<SubnetQuery>,
 
+    /// Subnets being searched for by an active query, along with the furthest `min_ttl` requested
+    /// for them. New requests for these subnets are merged into the active query.
+    active_subnet_queries: HashMap<Subnet, Option<Instant>>,
+
+    /// Subnets which have reached `TARGET_SUBNET_PEERS` and have not since dropped below
+    /// `SUBNET_REQUERY_PEER_THRESHOLD`.
+    satisfied_subnets: HashSet<Subnet>,
+
     /// Active discovery queries.
     active_queries: FuturesUnordered<std::pin::Pin<Box<dyn Future<Output = QueryResult> + Send>>>,
 
@@ -318,6 +330,8 @@ impl<E: EthSpec> Discovery<E> {
             network_globals,
             find_peer_active: false,
             queued_queries: VecDeque::with_capacity(10),
+            active_subnet_queries: HashMap::new(),
+            satisfied_subnets: HashSet::new(),
             active_queries: FuturesUnordered::new(),
             discv5,
             event_stream,
@@ -374,6 +388,12 @@ impl<E: EthSpec> Discovery<E> {
         }
     }
 
+    /// Records that `subnet` has reached `TARGET_SUBNET_PEERS`, so that it is not searched for
+    /// again until it drops below `SUBNET_REQUERY_PEER_THRESHOLD`.
+    pub fn subnet_target_reached(&mut self, subnet: Subnet) {
+        self.satisfied_subnets.insert(subnet);
+    }
+
     /// Add an ENR to the routing table of the discovery mechanism.
     pub fn add_enr(&mut self, enr: Enr) {
         // add the enr to seen caches
@@ -642,6 +662,16 @@ impl<E: EthSpec> Discovery<E> {
             return;
         }
 
+        // If a query for this subnet is already running, extend its `min_ttl` rather than
+        // starting another one.
+        if let Some(active_min_ttl) = self.active_subnet_queries.get_mut(&subnet) {
+            if *active_min_ttl < min_ttl {
+                *active_min_ttl = min_ttl;
+            }
+            metrics::inc_counter_vec(&metrics::DISCOVERY_SUBNET_QUERIES_SKIPPED, &["in_progress"]);
+            return;
+        }
+
         // Search through any queued requests and update the timeout if a query for this subnet
         // already exists
         let mut found = false;
@@ -735,9 +765,30 @@ impl<E: EthSpec> Discovery<E> {
                         "connected_peers_on_subnet" => peers_on_subnet,
                         "target_subnet_peers" => TARGET_SUBNET_PEERS,
                     );
+                    self.satisfied_subnets.insert(subnet_query.subnet);
+                    metrics::inc_counter_vec(
+                        &metrics::DISCOVERY_SUBNET_QUERIES_SKIPPED,
+                        &["sufficient_peers"],
+                    );
                     return false;
                 }
 
+                if self.satisfied_subnets.contains(&subnet_query.subnet) {
+                    if peers_on_subnet >= SUBNET_REQUERY_PEER_THRESHOLD {
+                        trace!(self.log, "Discovery ignored";
+                            "reason" => "Subnet peer count within hysteresis",
+                            "subnet" => ?subnet_query.subnet,
+                            "connected_peers_on_subnet" => peers_on_subnet,
+                        );
+                        metrics::inc_counter_vec(
+                            &metrics::DISCOVERY_SUBNET_QUERIES_SKIPPED,
+                            &["hysteresis"],
+                        );
+                        return false;
+                    }
+                    self.satisfied_subnets.remove(&subnet_query.subnet);
+                }
+
                 let target_peers = TARGET_SUBNET_PEERS.saturating_sub(peers_on_subnet);
                 trace!(self.log, "Discovery query started for subnet";
                     "subnet_query" => ?subnet_query,
@@ -760,6 +811,14 @@ impl<E: EthSpec> Discovery<E> {
                 "Starting grouped subnet query";
                 "subnets" => ?filtered_subnet_queries,
             );
+            metrics::observe(
+                &metrics::DISCOVERY_SUBNETS_PER_QUERY,
+                filtered_subnet_queries.len() as f64,
+            );
+            for subnet_query in &filtered_subnet_queries {
+                self.active_subnet_queries
+                    .insert(subnet_query.subnet, subnet_query.min_ttl);
+            }
             self.start_query(
                 QueryType::Subnet(filtered_subnet_queries),
                 TARGET_PEERS_FOR_GROUPED_QUERY,
@@ -842,7 +901,15 @@ impl<E: EthSpec> Discovery<E> {
                     }
                 }
             }
-            QueryType::Subnet(queries) => {
+            QueryType::Subnet(mut queries) => {
+                // Fold in any requests which were merged into this query whilst it was running.
+                for query in queries.iter_mut() {
+                    if let Some(min_ttl) = self.active_subnet_queries.remove(&query.subnet) {
+                        if query.min_ttl < min_ttl {
+                            query.min_ttl = min_ttl;
+                        }
+                    }
+                }
                 let subnets_searched_for: Vec<Subnet> =
                     queries.iter().map(|query| query.subnet).collect();
                 match query.result {
@@ -1256,6 +1323,57 @@ mod tests {
         assert_eq!(discovery.queued_queries.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_subnet_query_merged_into_active_query() {
+        let mut discovery = build_discovery().await;
+        let now = Instant::now();
+        let subnet = Subnet::Attestation(SubnetId::new(1));
+        let later = Some(now + Duration::from_secs(10));
+
+        // A request for a subnet with an active query must not queue a new query.
+        discovery.active_subnet_queries.insert(subnet, Some(now));
+        discovery.add_subnet_query(subnet, later, 0);
+        assert!(discovery.queued_queries.is_empty());
+        assert_eq!(discovery.active_subnet_queries.get(&subnet), Some(&later));
+
+        // When the active query completes, the retry uses the merged `min_ttl`.
+        discovery.process_completed_queries(QueryResult {
+            query_type: QueryType::Subnet(vec![SubnetQuery {
+                subnet,
+                min_ttl: Some(now),
+                retries: 0,
+            }]),
+            result: Ok(vec![]),
+        });
+        assert!(discovery.active_subnet_queries.is_empty());
+        assert_eq!(
+            discovery.queued_queries.pop_back(),
+            Some(SubnetQuery {
+                subnet,
+                min_ttl: later,
+                retries: 1,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_satisfied_subnet_queried_below_threshold() {
+        let mut discovery = build_discovery().await;
+        let subnet = Subnet::Attestation(SubnetId::new(1));
+
+        discovery.subnet_target_reached(subnet);
+        assert!(discovery.satisfied_subnets.contains(&subnet));
+
+        // There are no peers on the subnet, so it is below the re-query threshold.
+        discovery.start_subnet_query(vec![SubnetQuery {
+            subnet,
+            min_ttl: None,
+            retries: 0,
+        }]);
+        assert!(!discovery.satisfied_subnets.contains(&subnet));
+        assert!(discovery.active_subnet_queries.contains_key(&subnet));
+    }
+
     fn make_enr(subnet_ids: Vec<usize>) -> Enr {
         let mut builder = Enr::builder();
         let keypair = secp256k1::Keypair::generate();
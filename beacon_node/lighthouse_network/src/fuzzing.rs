@@ -0,0 +1,403 @@
+//! Entry points for fuzzing the wire codecs with data received from peers.
+//!
+//! The first two bytes of every input select the protocol (or gossip topic) and the fork. The rest
+//! of the input is passed to the decoder exactly as it would arrive on the wire. `corpus` produces
+//! valid inputs in the same format, which are used to seed the fuzz targets in `fuzz/`.
+use crate::gossip_max_size;
+use crate::rpc::codec::ssz_snappy::{SSZSnappyInboundCodec, SSZSnappyOutboundCodec};
+use crate::rpc::methods::{
+    BlobsByRangeRequest, BlobsByRootRequest, BlocksByRootRequest, ErrorType, MetaData, MetaDataV2,
+    MetadataRequest, OldBlocksByRangeRequest, Ping, RPCCodedResponse, RPCResponse,
+    RPCResponseErrorCode, StatusMessage,
+};
+use crate::rpc::{
+    max_rpc_size, Encoding, GoodbyeReason, OutboundRequest, ProtocolId, SupportedProtocol,
+};
+use crate::types::{
+    EnrAttestationBitfield, EnrSyncCommitteeBitfield, GossipEncoding, GossipKind, GossipTopic,
+    PubsubMessage, SnappyTransform,
+};
+use gossipsub::{DataTransform, IdentTopic as Topic, RawMessage};
+use libp2p::bytes::BytesMut;
+use std::sync::Arc;
+use tokio_util::codec::{Decoder, Encoder};
+use types::{
+    blob_sidecar::BlobIdentifier, BeaconBlock, BlobSidecar, ChainSpec, Epoch, EthSpec, ForkContext,
+    ForkName, Hash256, MainnetEthSpec, Signature, SignedBeaconBlock, SignedVoluntaryExit, Slot,
+    SubnetId, SyncSubnetId, VoluntaryExit,
+};
+
+type E = MainnetEthSpec;
+
+/// Corpus directory name of the fuzz target for `decode_rpc_request`.
+pub const RPC_REQUEST_TARGET: &str = "rpc_request";
+/// Corpus directory name of the fuzz target for `decode_rpc_response`.
+pub const RPC_RESPONSE_TARGET: &str = "rpc_response";
+/// Corpus directory name of the fuzz target for `decode_gossip`.
+pub const GOSSIP_TARGET: &str = "gossip";
+
+const PROTOCOLS: [SupportedProtocol; 14] = [
+    SupportedProtocol::StatusV1,
+    SupportedProtocol::GoodbyeV1,
+    SupportedProtocol::BlocksByRangeV1,
+    SupportedProtocol::BlocksByRangeV2,
+    SupportedProtocol::BlocksByRootV1,
+    SupportedProtocol::BlocksByRootV2,
+    SupportedProtocol::BlobsByRangeV1,
+    SupportedProtocol::BlobsByRootV1,
+    SupportedProtocol::PingV1,
+    SupportedProtocol::MetaDataV1,
+    SupportedProtocol::MetaDataV2,
+    SupportedProtocol::LightClientBootstrapV1,
+    SupportedProtocol::LightClientOptimisticUpdateV1,
+    SupportedProtocol::LightClientFinalityUpdateV1,
+];
+
+fn gossip_kinds() -> [GossipKind; 12] {
+    [
+        GossipKind::BeaconBlock,
+        GossipKind::BeaconAggregateAndProof,
+        GossipKind::BlobSidecar(0),
+        GossipKind::Attestation(SubnetId::new(0)),
+        GossipKind::VoluntaryExit,
+        GossipKind::ProposerSlashing,
+        GossipKind::AttesterSlashing,
+        GossipKind::SignedContributionAndProof,
+        GossipKind::SyncCommitteeMessage(SyncSubnetId::new(0)),
+        GossipKind::BlsToExecutionChange,
+        GossipKind::LightClientFinalityUpdate,
+        GossipKind::LightClientOptimisticUpdate,
+    ]
+}
+
+/// A valid input for one of the fuzz targets.
+pub struct CorpusEntry {
+    /// The fuzz target, one of `RPC_REQUEST_TARGET`, `RPC_RESPONSE_TARGET` or `GOSSIP_TARGET`.
+    pub target: &'static str,
+    /// A unique, human-readable name for the input.
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Decodes `data` as an RPC request received from a peer on an inbound substream.
+pub fn decode_rpc_request(data: &[u8]) {
+    let Some((protocol, fork_context, spec, payload)) = split_rpc_input(data) else {
+        return;
+    };
+    let mut codec = SSZSnappyInboundCodec::<E>::new(
+        ProtocolId::new(protocol, Encoding::SSZSnappy),
+        max_rpc_size(&fork_context, spec.max_chunk_size as usize),
+        fork_context,
+    );
+    let _ = codec.decode(&mut BytesMut::from(payload));
+}
+
+/// Decodes `data` as the RPC response chunks received from a peer on an outbound substream.
+pub fn decode_rpc_response(data: &[u8]) {
+    let Some((protocol, fork_context, spec, payload)) = split_rpc_input(data) else {
+        return;
+    };
+    let mut codec = SSZSnappyOutboundCodec::<E>::new(
+        ProtocolId::new(protocol, Encoding::SSZSnappy),
+        max_rpc_size(&fork_context, spec.max_chunk_size as usize),
+        fork_context,
+    );
+    let mut buf = BytesMut::from(payload);
+    while !buf.is_empty() {
+        match codec.decode(&mut buf) {
+            Ok(Some(_)) => {}
+            Ok(None) | Err(_) => break,
+        }
+    }
+}
+
+/// Decompresses and decodes `data` as a gossip message received from a peer.
+pub fn decode_gossip(data: &[u8]) {
+    let [kind, fork, payload @ ..] = data else {
+        return;
+    };
+    let kinds = gossip_kinds();
+    let kind = kinds[*kind as usize % kinds.len()].clone();
+    let fork_name = select_fork(*fork);
+    let (fork_context, spec) = fork_context(fork_name);
+    let Some(fork_digest) = fork_context.to_context_bytes(fork_name) else {
+        return;
+    };
+    let topic: Topic = GossipTopic::new(kind, GossipEncoding::SSZSnappy, fork_digest).into();
+    let transform = SnappyTransform::new(gossip_max_size(
+        fork_name >= ForkName::Bellatrix,
+        spec.gossip_max_size as usize,
+    ));
+    let raw_message = RawMessage {
+        source: None,
+        data: payload.to_vec(),
+        sequence_number: None,
+        topic: topic.hash(),
+        signature: None,
+        key: None,
+        validated: false,
+    };
+    if let Ok(message) = transform.inbound_transform(raw_message) {
+        let _ = PubsubMessage::<E>::decode(&message.topic, &message.data, &fork_context);
+    }
+}
+
+/// Returns valid inputs for every fuzz target, covering each protocol and topic at the forks in
+/// which it is used.
+pub fn corpus() -> Vec<CorpusEntry> {
+    let mut entries = vec![];
+
+    for fork_name in ForkName::list_all() {
+        let (fork_context, spec) = fork_context(fork_name);
+
+        for request in rpc_requests(fork_name, &spec) {
+            let protocol = request.versioned_protocol();
+            let mut codec = SSZSnappyOutboundCodec::<E>::new(
+                ProtocolId::new(protocol, Encoding::SSZSnappy),
+                max_rpc_size(&fork_context, spec.max_chunk_size as usize),
+                fork_context.clone(),
+            );
+            let mut buf = BytesMut::new();
+            if codec.encode(request, &mut buf).is_ok() {
+                let name = format!("{:?}_{}", protocol, fork_name);
+                entries.push(rpc_entry(
+                    RPC_REQUEST_TARGET,
+                    name,
+                    protocol,
+                    fork_name,
+                    &buf,
+                ));
+            }
+        }
+
+        for (protocol, response) in rpc_responses(fork_name, &spec) {
+            let name = match response {
+                RPCCodedResponse::Success(_) => format!("{:?}_{}", protocol, fork_name),
+                _ => format!("{:?}_error_{}", protocol, fork_name),
+            };
+            let mut codec = SSZSnappyInboundCodec::<E>::new(
+                ProtocolId::new(protocol, Encoding::SSZSnappy),
+                max_rpc_size(&fork_context, spec.max_chunk_size as usize),
+                fork_context.clone(),
+            );
+            let mut buf = BytesMut::new();
+            if codec.encode(response, &mut buf).is_ok() {
+                entries.push(rpc_entry(
+                    RPC_RESPONSE_TARGET,
+                    name,
+                    protocol,
+                    fork_name,
+                    &buf,
+                ));
+            }
+        }
+
+        let transform = SnappyTransform::new(spec.gossip_max_size as usize);
+        for message in gossip_messages(fork_name, &spec) {
+            let kind = message.kind();
+            let Some(kind_index) = gossip_kinds()
+                .iter()
+                .position(|k| std::mem::discriminant(k) == std::mem::discriminant(&kind))
+            else {
+                continue;
+            };
+            let topic: Topic =
+                GossipTopic::new(kind.clone(), GossipEncoding::SSZSnappy, [0; 4]).into();
+            let Ok(compressed) = transform
+                .outbound_transform(&topic.hash(), message.encode(GossipEncoding::SSZSnappy))
+            else {
+                continue;
+            };
+            let mut data = vec![kind_index as u8, fork_index(fork_name)];
+            data.extend_from_slice(&compressed);
+            entries.push(CorpusEntry {
+                target: GOSSIP_TARGET,
+                name: format!("{}_{}", kind.as_ref(), fork_name),
+                data,
+            });
+        }
+    }
+
+    entries
+}
+
+fn select_fork(selector: u8) -> ForkName {
+    let forks = ForkName::list_all();
+    forks[selector as usize % forks.len()]
+}
+
+fn fork_index(fork_name: ForkName) -> u8 {
+    ForkName::list_all()
+        .iter()
+        .position(|f| *f == fork_name)
+        .unwrap_or_default() as u8
+}
+
+/// Returns a fork context and spec in which `fork_name` is active from genesis.
+fn fork_context(fork_name: ForkName) -> (Arc<ForkContext>, ChainSpec) {
+    let spec = fork_name.make_genesis_spec(E::default_spec());
+    let fork_context = ForkContext::new::<E>(Slot::new(0), Hash256::zero(), &spec);
+    (Arc::new(fork_context), spec)
+}
+
+fn split_rpc_input(data: &[u8]) -> Option<(SupportedProtocol, Arc<ForkContext>, ChainSpec, &[u8])> {
+    let [protocol, fork, payload @ ..] = data else {
+        return None;
+    };
+    let protocol = PROTOCOLS[*protocol as usize % PROTOCOLS.len()];
+    let (fork_context, spec) = fork_context(select_fork(*fork));
+    Some((protocol, fork_context, spec, payload))
+}
+
+fn rpc_entry(
+    target: &'static str,
+    name: String,
+    protocol: SupportedProtocol,
+    fork_name: ForkName,
+    encoded: &[u8],
+) -> CorpusEntry {
+    let protocol_index = PROTOCOLS
+        .iter()
+        .position(|p| *p == protocol)
+        .unwrap_or_default() as u8;
+    let mut data = vec![protocol_index, fork_index(fork_name)];
+    data.extend_from_slice(encoded);
+    CorpusEntry { target, name, data }
+}
+
+fn status_message() -> StatusMessage {
+    StatusMessage {
+        fork_digest: [0; 4],
+        finalized_root: Hash256::zero(),
+        finalized_epoch: Epoch::new(1),
+        head_root: Hash256::zero(),
+        head_slot: Slot::new(32),
+    }
+}
+
+fn empty_block(spec: &ChainSpec) -> Arc<SignedBeaconBlock<E>> {
+    Arc::new(SignedBeaconBlock::from_block(
+        BeaconBlock::empty(spec),
+        Signature::empty(),
+    ))
+}
+
+fn rpc_requests(fork_name: ForkName, spec: &ChainSpec) -> Vec<OutboundRequest<E>> {
+    let mut requests = vec![
+        OutboundRequest::Status(status_message()),
+        OutboundRequest::Goodbye(GoodbyeReason::Fault),
+        OutboundRequest::Ping(Ping { data: 1 }),
+        OutboundRequest::MetaData(MetadataRequest::new_v1()),
+        OutboundRequest::MetaData(MetadataRequest::new_v2()),
+        OutboundRequest::BlocksByRange(OldBlocksByRangeRequest::new_v1(0, 10, 1)),
+        OutboundRequest::BlocksByRange(OldBlocksByRangeRequest::new(0, 10, 1)),
+        OutboundRequest::BlocksByRoot(BlocksByRootRequest::new_v1(vec![Hash256::zero()], spec)),
+        OutboundRequest::BlocksByRoot(BlocksByRootRequest::new(vec![Hash256::zero()], spec)),
+    ];
+    if fork_name >= ForkName::Deneb {
+        requests.push(OutboundRequest::BlobsByRange(BlobsByRangeRequest {
+            start_slot: 0,
+            count: 10,
+        }));
+        requests.push(OutboundRequest::BlobsByRoot(BlobsByRootRequest::new(
+            vec![BlobIdentifier {
+                block_root: Hash256::zero(),
+                index: 0,
+            }],
+            spec,
+        )));
+    }
+    requests
+}
+
+fn rpc_responses(
+    fork_name: ForkName,
+    spec: &ChainSpec,
+) -> Vec<(SupportedProtocol, RPCCodedResponse<E>)> {
+    let success = RPCCodedResponse::Success;
+    let mut responses = vec![
+        (
+            SupportedProtocol::StatusV1,
+            success(RPCResponse::Status(status_message())),
+        ),
+        (
+            SupportedProtocol::PingV1,
+            success(RPCResponse::Pong(Ping { data: 1 })),
+        ),
+        (
+            SupportedProtocol::MetaDataV2,
+            success(RPCResponse::MetaData(MetaData::V2(MetaDataV2 {
+                seq_number: 1,
+                attnets: EnrAttestationBitfield::<E>::default(),
+                syncnets: EnrSyncCommitteeBitfield::<E>::default(),
+            }))),
+        ),
+        (
+            SupportedProtocol::BlocksByRangeV2,
+            success(RPCResponse::BlocksByRange(empty_block(spec))),
+        ),
+        (
+            SupportedProtocol::BlocksByRootV2,
+            success(RPCResponse::BlocksByRoot(empty_block(spec))),
+        ),
+        (
+            SupportedProtocol::BlocksByRangeV2,
+            RPCCodedResponse::Error(
+                RPCResponseErrorCode::ResourceUnavailable,
+                ErrorType::from("unavailable".to_string()),
+            ),
+        ),
+    ];
+    if fork_name >= ForkName::Deneb {
+        let blob_sidecar = Arc::new(BlobSidecar::<E>::empty());
+        responses.push((
+            SupportedProtocol::BlobsByRangeV1,
+            success(RPCResponse::BlobsByRange(blob_sidecar.clone())),
+        ));
+        responses.push((
+            SupportedProtocol::BlobsByRootV1,
+            success(RPCResponse::BlobsByRoot(blob_sidecar)),
+        ));
+    }
+    responses
+}
+
+fn gossip_messages(fork_name: ForkName, spec: &ChainSpec) -> Vec<PubsubMessage<E>> {
+    let mut messages = vec![
+        PubsubMessage::BeaconBlock(empty_block(spec)),
+        PubsubMessage::VoluntaryExit(Box::new(SignedVoluntaryExit {
+            message: VoluntaryExit {
+                epoch: Epoch::new(1),
+                validator_index: 0,
+            },
+            signature: Signature::empty(),
+        })),
+    ];
+    if fork_name >= ForkName::Deneb {
+        messages.push(PubsubMessage::BlobSidecar(Box::new((
+            0,
+            Arc::new(BlobSidecar::empty()),
+        ))));
+    }
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corpus_entries_decode() {
+        let corpus = corpus();
+        assert!(!corpus.is_empty());
+
+        for entry in corpus {
+            match entry.target {
+                RPC_REQUEST_TARGET => decode_rpc_request(&entry.data),
+                RPC_RESPONSE_TARGET => decode_rpc_response(&entry.data),
+                GOSSIP_TARGET => decode_gossip(&entry.data),
+                target => panic!("unknown fuzz target {target}"),
+            }
+        }
+    }
+}
@@ -38,6 +38,10 @@ lazy_static! {
         "libp2p_peer_disconnect_event_total",
         "Count of libp2p peer disconnect events"
     );
+    pub static ref INBOUND_PEERS_REFUSED_UNDER_PRESSURE: Result<IntCounter> = try_create_int_counter(
+        "libp2p_inbound_peers_refused_under_pressure_total",
+        "Count of inbound peers disconnected because the beacon processor queues were under pressure"
+    );
     pub static ref DISCOVERY_BYTES: Result<IntGaugeVec> = try_create_int_gauge_vec(
         "discovery_bytes",
         "The number of bytes sent and received in discovery",
@@ -137,6 +141,18 @@ lazy_static! {
             "Total number of discovery subnet queries",
             &["type"]
         );
+    pub static ref DISCOVERY_SUBNET_QUERIES_SKIPPED: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "discovery_subnet_queries_skipped_total",
+            "Number of subnet discovery requests which did not start a new discovery query",
+            &["reason"]
+        );
+    pub static ref DISCOVERY_SUBNETS_PER_QUERY: Result<Histogram> =
+        try_create_histogram_with_buckets(
+            "discovery_subnets_per_query",
+            "Number of subnets searched for by a single grouped discovery query",
+            Ok(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0])
+        );
 
     /*
      * Peer Reporting
@@ -10,6 +10,10 @@ pub const DEFAULT_PING_INTERVAL_INBOUND: u64 = 20;
 /// Default number of peers to connect to.
 pub const DEFAULT_TARGET_PEERS: usize = 50;
 
+/// Default beacon processor queue pressure, as a percentage, above which excess inbound peers are
+/// refused.
+pub const DEFAULT_INBOUND_PRESSURE_THRESHOLD: u8 = 80;
+
 /// Configurations for the PeerManager.
 #[derive(Debug)]
 pub struct Config {
@@ -22,6 +26,9 @@ pub struct Config {
     pub quic_enabled: bool,
     /// Target number of peers to connect to.
     pub target_peer_count: usize,
+    /// The beacon processor queue pressure (as a percentage) at or above which we refuse new
+    /// inbound peers beyond the target peer count.
+    pub inbound_pressure_threshold: Option<u8>,
 
     /* RPC related configurations */
     /// Time in seconds between status requests sent to peers.
@@ -41,6 +48,7 @@ impl Default for Config {
             metrics_enabled: false,
             quic_enabled: true,
             target_peer_count: DEFAULT_TARGET_PEERS,
+            inbound_pressure_threshold: Some(DEFAULT_INBOUND_PRESSURE_THRESHOLD),
             status_interval: DEFAULT_STATUS_INTERVAL,
             ping_interval_inbound: DEFAULT_PING_INTERVAL_INBOUND,
             ping_interval_outbound: DEFAULT_PING_INTERVAL_OUTBOUND,
@@ -80,6 +80,8 @@ pub struct PeerManager<E: EthSpec> {
     status_peers: HashSetDelay<PeerId>,
     /// The target number of peers we would like to connect to.
     target_peers: usize,
+    /// The work queue pressure at or above which we refuse inbound peers beyond `target_peers`.
+    inbound_pressure_threshold: Option<u8>,
     /// Peers queued to be dialed.
     peers_to_dial: Vec<Enr>,
     /// The number of temporarily banned peers. This is used to prevent instantaneous
@@ -151,6 +153,7 @@ impl<E: EthSpec> PeerManager<E> {
             discovery_enabled,
             metrics_enabled,
             target_peer_count,
+            inbound_pressure_threshold,
             status_interval,
             ping_interval_inbound,
             ping_interval_outbound,
@@ -168,6 +171,7 @@ impl<E: EthSpec> PeerManager<E> {
             outbound_ping_peers: HashSetDelay::new(Duration::from_secs(ping_interval_outbound)),
             status_peers: HashSetDelay::new(Duration::from_secs(status_interval)),
             target_peers: target_peer_count,
+            inbound_pressure_threshold,
             temporary_banned_peers: LRUTimeCache::new(PEER_RECONNECTION_TIMEOUT),
             sync_committee_subnets: Default::default(),
             heartbeat,
@@ -450,6 +454,17 @@ impl<E: EthSpec> PeerManager<E> {
         }
     }
 
+    /// Returns `true` if an inbound peer should be refused because the node is struggling to keep
+    /// up with its existing peers.
+    ///
+    /// New peers are only refused once we have reached our target, so that the node can still
+    /// recover from a loss of peers whilst under load.
+    pub fn inbound_pressure_limit_reached(&self) -> bool {
+        self.inbound_pressure_threshold.map_or(false, |threshold| {
+            self.network_globals.work_queue_pressure() >= threshold
+        }) && self.network_globals.connected_or_dialing_peers() >= self.target_peers
+    }
+
     /// Updates `PeerInfo` with `identify` information.
     pub fn identify(&mut self, peer_id: &PeerId, info: &IdentifyInfo) {
         if let Some(peer_info) = self.network_globals.peers.write().peer_info_mut(peer_id) {
@@ -1508,6 +1523,36 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_peer_manager_inbound_pressure_limit() {
+        let mut peer_manager = build_peer_manager(2).await;
+        peer_manager
+            .network_globals
+            .set_work_queue_pressure(config::DEFAULT_INBOUND_PRESSURE_THRESHOLD);
+
+        // Below the target, peers are accepted regardless of the pressure.
+        peer_manager.inject_connect_ingoing(
+            &PeerId::random(),
+            "/ip4/0.0.0.0".parse().unwrap(),
+            None,
+        );
+        assert!(!peer_manager.inbound_pressure_limit_reached());
+
+        // At the target, peers are only refused whilst under pressure.
+        peer_manager.inject_connect_ingoing(
+            &PeerId::random(),
+            "/ip4/0.0.0.0".parse().unwrap(),
+            None,
+        );
+        assert!(peer_manager.inbound_pressure_limit_reached());
+        assert!(!peer_manager.peer_limit_reached(true));
+
+        peer_manager
+            .network_globals
+            .set_work_queue_pressure(config::DEFAULT_INBOUND_PRESSURE_THRESHOLD - 1);
+        assert!(!peer_manager.inbound_pressure_limit_reached());
+    }
+
     #[tokio::test]
     async fn test_peer_manager_remove_unhealthy_peers_brings_peers_below_target() {
         let mut peer_manager = build_peer_manager(3).await;
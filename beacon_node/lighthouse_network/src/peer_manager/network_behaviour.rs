@@ -266,6 +266,25 @@ impl<E: EthSpec> PeerManager<E> {
             return;
         }
 
+        // Refuse excess inbound peers whilst we are struggling to process the work of the peers
+        // we already have.
+        if endpoint.is_listener()
+            && self.inbound_pressure_limit_reached()
+            && self
+                .network_globals
+                .peers
+                .read()
+                .peer_info(&peer_id)
+                .map_or(true, |peer| !peer.has_future_duty())
+        {
+            debug!(self.log, "Refusing inbound peer due to work queue pressure"; "peer_id" => %peer_id,
+                "pressure" => self.network_globals.work_queue_pressure()
+            );
+            metrics::inc_counter(&metrics::INBOUND_PEERS_REFUSED_UNDER_PRESSURE);
+            self.disconnect_peer(peer_id, GoodbyeReason::TooManyPeers);
+            return;
+        }
+
         // NOTE: We don't register peers that we are disconnecting immediately. The network service
         // does not need to know about these peers.
         match endpoint {
@@ -3,12 +3,13 @@ use crate::{metrics, multiaddr::Multiaddr, types::Subnet, Enr, Gossipsub, PeerId
 use peer_info::{ConnectionDirection, PeerConnectionStatus, PeerInfo};
 use rand::seq::SliceRandom;
 use score::{PeerAction, ReportSource, Score, ScoreState};
+use session::{DisconnectReason, PeerSession};
 use slog::{crit, debug, error, trace, warn};
 use std::net::IpAddr;
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use std::{cmp::Ordering, fmt::Display};
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
     fmt::Formatter,
 };
 use sync_status::SyncStatus;
@@ -17,12 +18,15 @@ use types::EthSpec;
 pub mod client;
 pub mod peer_info;
 pub mod score;
+pub mod session;
 pub mod sync_status;
 
 /// Max number of disconnected nodes to remember.
 const MAX_DC_PEERS: usize = 500;
 /// The maximum number of banned nodes to remember.
 pub const MAX_BANNED_PEERS: usize = 1000;
+/// The maximum number of past peer sessions to remember.
+const MAX_PEER_SESSIONS: usize = 1000;
 /// We ban an IP if there are more than `BANNED_PEERS_PER_IP_THRESHOLD` banned peers with this IP.
 const BANNED_PEERS_PER_IP_THRESHOLD: usize = 5;
 /// Relative factor of peers that are allowed to have a negative gossipsub score without penalizing
@@ -42,6 +46,8 @@ pub struct PeerDB<E: EthSpec> {
     banned_peers_count: BannedPeersCount,
     /// Specifies if peer scoring is disabled.
     disable_peer_scoring: bool,
+    /// The most recent sessions with peers which have since disconnected, oldest first.
+    session_history: VecDeque<PeerSession>,
     /// PeerDB's logger
     log: slog::Logger,
 }
@@ -58,6 +64,7 @@ impl<E: EthSpec> PeerDB<E> {
             disconnected_peers: 0,
             banned_peers_count: BannedPeersCount::default(),
             disable_peer_scoring,
+            session_history: VecDeque::with_capacity(MAX_PEER_SESSIONS),
             peers,
         }
     }
@@ -72,6 +79,11 @@ impl<E: EthSpec> PeerDB<E> {
             .score()
     }
 
+    /// Returns the most recent sessions with peers which have since disconnected, oldest first.
+    pub fn session_history(&self) -> impl Iterator<Item = &PeerSession> {
+        self.session_history.iter()
+    }
+
     /// Returns an iterator over all peers in the db.
     pub fn peers(&self) -> impl Iterator<Item = (&PeerId, &PeerInfo<E>)> {
         self.peers.iter()
@@ -356,6 +368,13 @@ impl<E: EthSpec> PeerDB<E> {
         Some(info.update_sync_status(sync_status))
     }
 
+    /// Allows the sync module to record a block from the peer whose blobs never became available.
+    /// Returns None if the peer doesn't exist, otherwise the number of such blocks recorded.
+    pub fn record_blob_withholding(&mut self, peer_id: &PeerId) -> Option<u64> {
+        let info = self.peers.get_mut(peer_id)?;
+        Some(info.record_blob_withholding())
+    }
+
     /// Updates the scores of known peers according to their connection status and the time that
     /// has passed. This function returns a list of peers that have been unbanned.
     /// NOTE: Peer scores cannot be penalized during the update, they can only increase. Therefore
@@ -821,6 +840,23 @@ impl<E: EthSpec> PeerDB<E> {
                 // Remove all subnets for disconnected peers.
                 info.clear_subnets();
 
+                if let Some((start, disconnect_reason)) = info.end_session() {
+                    if self.session_history.len() >= MAX_PEER_SESSIONS {
+                        self.session_history.pop_front();
+                    }
+                    self.session_history.push_back(PeerSession {
+                        peer_id: peer_id.to_string(),
+                        client: info.client().clone(),
+                        direction: info.connection_direction().cloned(),
+                        duration_secs: start.elapsed().as_secs(),
+                        disconnected_at: SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map_or(0, |time| time.as_secs()),
+                        disconnect_reason,
+                        score: info.score().score(),
+                    });
+                }
+
                 match old_state {
                     PeerConnectionStatus::Banned { .. } => {}
                     PeerConnectionStatus::Disconnected { .. } => {}
@@ -991,6 +1027,14 @@ impl<E: EthSpec> PeerDB<E> {
         (maybe_ban_op, purged_peers)
     }
 
+    /// Records why the current session with a peer is ending. Only the first reason given for a
+    /// session is kept.
+    pub(crate) fn set_disconnect_reason(&mut self, peer_id: &PeerId, reason: DisconnectReason) {
+        if let Some(info) = self.peers.get_mut(peer_id) {
+            info.set_disconnect_reason(reason);
+        }
+    }
+
     /// The peer manager has notified us that the peer is undergoing a normal disconnect. Optionally tag
     /// the peer to be banned after the disconnect.
     // VISIBILITY: Only the peer manager can adjust the connection state.
@@ -1265,6 +1309,7 @@ impl BannedPeersCount {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rpc::GoodbyeReason;
     use libp2p::core::multiaddr::Protocol;
     use slog::{o, Drain};
     use std::net::{Ipv4Addr, Ipv6Addr};
@@ -1328,6 +1373,30 @@ mod tests {
         assert_eq!(peer_info.unwrap().connections(), (n_in, n_out));
     }
 
+    #[test]
+    fn test_record_blob_withholding() {
+        let mut pdb = get_db();
+        let known_peer = PeerId::random();
+        let unknown_peer = PeerId::random();
+
+        pdb.connect_ingoing(&known_peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+        assert_eq!(
+            pdb.peer_info(&known_peer).unwrap().blob_withholding_count(),
+            0
+        );
+
+        assert_eq!(pdb.record_blob_withholding(&known_peer), Some(1));
+        assert_eq!(pdb.record_blob_withholding(&known_peer), Some(2));
+        assert_eq!(
+            pdb.peer_info(&known_peer).unwrap().blob_withholding_count(),
+            2
+        );
+
+        // Unknown peers are not added to the db.
+        assert_eq!(pdb.record_blob_withholding(&unknown_peer), None);
+        assert!(pdb.peer_info(&unknown_peer).is_none());
+    }
+
     #[test]
     fn test_outbound_only_peers_counted_correctly() {
         let mut pdb = get_db();
@@ -1447,6 +1516,56 @@ mod tests {
         assert_eq!(pdb.disconnected_peers, MAX_DC_PEERS);
     }
 
+    #[test]
+    fn test_session_history() {
+        let mut pdb = get_db();
+        let sent = PeerId::random();
+        let closed = PeerId::random();
+        let dialed = PeerId::random();
+
+        pdb.connect_ingoing(&sent, "/ip4/0.0.0.0".parse().unwrap(), None);
+        pdb.connect_outgoing(&closed, "/ip4/0.0.0.0".parse().unwrap(), None);
+        pdb.dialing_peer(&dialed, None);
+
+        // Only the first reason is recorded.
+        pdb.set_disconnect_reason(
+            &sent,
+            DisconnectReason::goodbye_sent(GoodbyeReason::TooManyPeers),
+        );
+        pdb.set_disconnect_reason(
+            &sent,
+            DisconnectReason::goodbye_received(GoodbyeReason::ClientShutdown),
+        );
+        for peer in [sent, closed, dialed] {
+            pdb.inject_disconnect(&peer);
+        }
+
+        // A failed dial is not a session.
+        let history = pdb.session_history().collect::<Vec<_>>();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].peer_id, sent.to_string());
+        assert_eq!(
+            history[0].disconnect_reason,
+            DisconnectReason::GoodbyeSent {
+                code: 129,
+                reason: GoodbyeReason::TooManyPeers.to_string(),
+            }
+        );
+        assert_eq!(history[1].peer_id, closed.to_string());
+        assert_eq!(
+            history[1].disconnect_reason,
+            DisconnectReason::ConnectionClosed
+        );
+
+        // The reason is forgotten once the session ends.
+        pdb.connect_ingoing(&sent, "/ip4/0.0.0.0".parse().unwrap(), None);
+        pdb.inject_disconnect(&sent);
+        assert_eq!(
+            pdb.session_history().last().unwrap().disconnect_reason,
+            DisconnectReason::ConnectionClosed
+        );
+    }
+
     #[test]
     fn test_banned_are_bounded() {
         let mut pdb = get_db();
@@ -1,5 +1,6 @@
 use super::client::Client;
 use super::score::{PeerAction, Score, ScoreState};
+use super::session::DisconnectReason;
 use super::sync_status::SyncStatus;
 use crate::discovery::Eth2Enr;
 use crate::{rpc::MetaData, types::Subnet};
@@ -35,6 +36,8 @@ pub struct PeerInfo<E: EthSpec> {
     /// The current syncing state of the peer. The state may be determined after it's initial
     /// connection.
     sync_status: SyncStatus,
+    /// The number of blocks this peer has sent us whose blobs never became available.
+    blob_withholding_count: u64,
     /// The ENR subnet bitfield of the peer. This may be determined after it's initial
     /// connection.
     meta_data: Option<MetaData<E>>,
@@ -51,6 +54,12 @@ pub struct PeerInfo<E: EthSpec> {
     connection_direction: Option<ConnectionDirection>,
     /// The enr of the peer, if known.
     enr: Option<Enr>,
+    /// When the current session with this peer began. None if the peer is not connected.
+    #[serde(skip)]
+    session_start: Option<Instant>,
+    /// Why the current session is ending, if known.
+    #[serde(skip)]
+    disconnect_reason: Option<DisconnectReason>,
 }
 
 impl<E: EthSpec> Default for PeerInfo<E> {
@@ -63,11 +72,14 @@ impl<E: EthSpec> Default for PeerInfo<E> {
             seen_multiaddrs: HashSet::new(),
             subnets: HashSet::new(),
             sync_status: SyncStatus::Unknown,
+            blob_withholding_count: 0,
             meta_data: None,
             min_ttl: None,
             is_trusted: false,
             connection_direction: None,
             enr: None,
+            session_start: None,
+            disconnect_reason: None,
         }
     }
 }
@@ -119,6 +131,11 @@ impl<E: EthSpec> PeerInfo<E> {
         &self.sync_status
     }
 
+    /// Returns the number of blocks this peer has sent us whose blobs never became available.
+    pub fn blob_withholding_count(&self) -> u64 {
+        self.blob_withholding_count
+    }
+
     /// Returns the metadata for the peer if currently known.
     pub fn meta_data(&self) -> Option<&MetaData<E>> {
         self.meta_data.as_ref()
@@ -326,6 +343,14 @@ impl<E: EthSpec> PeerInfo<E> {
         self.sync_status.update(sync_status)
     }
 
+    /// Records a block sent by this peer whose blobs never became available. Returns the number of
+    /// such blocks recorded so far.
+    // VISIBILITY: The network sync records blocks which time out waiting for their blobs
+    pub fn record_blob_withholding(&mut self) -> u64 {
+        self.blob_withholding_count = self.blob_withholding_count.saturating_add(1);
+        self.blob_withholding_count
+    }
+
     /// Sets the client of the peer.
     // VISIBILITY: The peer manager is able to set the client
     pub(in crate::peer_manager) fn set_client(&mut self, client: Client) {
@@ -353,6 +378,25 @@ impl<E: EthSpec> PeerInfo<E> {
         self.connection_status = connection_status
     }
 
+    /// Records why the current session is ending, unless a reason is already known.
+    pub(super) fn set_disconnect_reason(&mut self, reason: DisconnectReason) {
+        if self.session_start.is_some() && self.disconnect_reason.is_none() {
+            self.disconnect_reason = Some(reason);
+        }
+    }
+
+    /// Ends the current session, returning when it started and why it ended.
+    ///
+    /// Returns `None` if there was no session, e.g. because a dial failed.
+    pub(super) fn end_session(&mut self) -> Option<(Instant, DisconnectReason)> {
+        let start = self.session_start.take()?;
+        let reason = self
+            .disconnect_reason
+            .take()
+            .unwrap_or(DisconnectReason::ConnectionClosed);
+        Some((start, reason))
+    }
+
     /// Sets the ENR of the peer if one is known.
     pub(super) fn set_enr(&mut self, enr: Enr) {
         self.enr = Some(enr)
@@ -437,6 +481,7 @@ impl<E: EthSpec> PeerInfo<E> {
                     multiaddr,
                 };
                 self.connection_direction = Some(ConnectionDirection::Incoming);
+                self.session_start.get_or_insert_with(Instant::now);
             }
         }
     }
@@ -458,6 +503,7 @@ impl<E: EthSpec> PeerInfo<E> {
                     multiaddr,
                 };
                 self.connection_direction = Some(ConnectionDirection::Outgoing);
+                self.session_start.get_or_insert_with(Instant::now);
             }
         }
     }
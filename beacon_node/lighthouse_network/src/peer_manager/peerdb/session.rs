@@ -0,0 +1,54 @@
+//! Records of past peer sessions and how they ended.
+//!
+//! These are kept so that systematic problems with particular clients, such as a client which
+//! repeatedly sends us goodbyes, can be diagnosed after the peers involved have disconnected.
+
+use super::client::Client;
+use super::peer_info::ConnectionDirection;
+use crate::rpc::GoodbyeReason;
+use serde::Serialize;
+
+/// How a session with a peer ended.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DisconnectReason {
+    /// We sent the peer a goodbye.
+    GoodbyeSent { code: u64, reason: String },
+    /// The peer sent us a goodbye.
+    GoodbyeReceived { code: u64, reason: String },
+    /// The connection closed without a goodbye from either side.
+    ConnectionClosed,
+}
+
+impl DisconnectReason {
+    pub fn goodbye_sent(reason: GoodbyeReason) -> Self {
+        DisconnectReason::GoodbyeSent {
+            reason: reason.to_string(),
+            code: reason.into(),
+        }
+    }
+
+    pub fn goodbye_received(reason: GoodbyeReason) -> Self {
+        DisconnectReason::GoodbyeReceived {
+            reason: reason.to_string(),
+            code: reason.into(),
+        }
+    }
+}
+
+/// A session with a peer, from the first connection to the last disconnection.
+#[derive(Clone, Debug, Serialize)]
+pub struct PeerSession {
+    pub peer_id: String,
+    /// The client as identified during the session.
+    pub client: Client,
+    /// The direction of the session's first connection.
+    pub direction: Option<ConnectionDirection>,
+    /// The length of the session in seconds.
+    pub duration_secs: u64,
+    /// When the session ended, in seconds since the UNIX epoch.
+    pub disconnected_at: u64,
+    pub disconnect_reason: DisconnectReason,
+    /// The peer's score when the session ended.
+    pub score: f64,
+}
@@ -0,0 +1,145 @@
+//! Writes payloads in the snappy framing format used by the `ssz_snappy` RPC encoding.
+//!
+//! The framing format allows a stream to contain both compressed and uncompressed chunks, and
+//! every compliant decoder must accept either. We take advantage of this to skip compression for
+//! tiny payloads (e.g. `Ping`, `MetaData`, `Status`), where snappy cannot save any bytes and the
+//! encoder setup dominates the cost. Everything else is compressed by `snap`'s frame encoder
+//! directly into the output buffer, avoiding an intermediate copy of large payloads such as blob
+//! sidecars.
+//!
+//! See: https://github.com/google/snappy/blob/main/framing_format.txt
+
+use libp2p::bytes::{BufMut, BytesMut};
+use snap::write::FrameEncoder;
+use std::io::{self, Write};
+
+/// Payloads of at most this many bytes are written as a single uncompressed chunk.
+pub const UNCOMPRESSED_THRESHOLD: usize = 128;
+
+/// The stream identifier chunk which must begin every framed snappy stream.
+const STREAM_IDENTIFIER: [u8; 10] = [0xff, 0x06, 0x00, 0x00, b's', b'N', b'a', b'P', b'p', b'Y'];
+
+/// The chunk type of an uncompressed data chunk.
+const CHUNK_TYPE_UNCOMPRESSED: u8 = 0x01;
+
+/// The number of checksum bytes at the start of each data chunk.
+const CHECKSUM_LEN: usize = 4;
+
+/// Encode `bytes` as a framed snappy stream and append it to `dst`.
+pub fn encode_snappy_frames(bytes: &[u8], dst: &mut BytesMut) -> io::Result<()> {
+    if bytes.len() <= UNCOMPRESSED_THRESHOLD {
+        write_uncompressed(bytes, dst);
+        return Ok(());
+    }
+
+    let mut writer = FrameEncoder::new(dst.writer());
+    writer.write_all(bytes)?;
+    writer.flush()
+}
+
+/// Write `bytes` as a stream identifier followed by a single uncompressed chunk.
+///
+/// `bytes` must be shorter than the maximum chunk size of 64KiB.
+fn write_uncompressed(bytes: &[u8], dst: &mut BytesMut) {
+    let chunk_len = (bytes.len() + CHECKSUM_LEN) as u32;
+    dst.reserve(STREAM_IDENTIFIER.len() + 4 + CHECKSUM_LEN + bytes.len());
+    dst.extend_from_slice(&STREAM_IDENTIFIER);
+    dst.put_u8(CHUNK_TYPE_UNCOMPRESSED);
+    dst.extend_from_slice(&chunk_len.to_le_bytes()[..3]);
+    dst.put_u32_le(masked_crc32c(bytes));
+    dst.extend_from_slice(bytes);
+}
+
+/// The CRC-32C (Castagnoli) lookup table.
+const CRC32C_TABLE: [u32; 256] = crc32c_table();
+
+const fn crc32c_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0x82f6_3b78
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+fn crc32c(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, byte| {
+        CRC32C_TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// The checksum of a data chunk, masked as required by the framing format.
+fn masked_crc32c(bytes: &[u8]) -> u32 {
+    crc32c(bytes).rotate_right(15).wrapping_add(0xa282_ead8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snap::read::FrameDecoder;
+    use std::io::Read;
+
+    fn decode(encoded: &[u8]) -> Vec<u8> {
+        let mut decoded = vec![];
+        FrameDecoder::new(encoded)
+            .read_to_end(&mut decoded)
+            .unwrap();
+        decoded
+    }
+
+    #[test]
+    fn crc32c_check_value() {
+        assert_eq!(crc32c(b"123456789"), 0xe306_9283);
+    }
+
+    #[test]
+    fn round_trip() {
+        for len in [
+            0,
+            1,
+            8,
+            UNCOMPRESSED_THRESHOLD,
+            UNCOMPRESSED_THRESHOLD + 1,
+            65_536,
+            131_928,
+        ] {
+            let bytes = (0..len).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+            let mut dst = BytesMut::new();
+            encode_snappy_frames(&bytes, &mut dst).unwrap();
+            assert_eq!(decode(&dst), bytes, "len {len}");
+        }
+    }
+
+    #[test]
+    fn tiny_payloads_are_not_compressed() {
+        let bytes = [0u8; 64];
+        let mut dst = BytesMut::new();
+        encode_snappy_frames(&bytes, &mut dst).unwrap();
+
+        assert_eq!(dst[..STREAM_IDENTIFIER.len()], STREAM_IDENTIFIER);
+        assert_eq!(dst[STREAM_IDENTIFIER.len()], CHUNK_TYPE_UNCOMPRESSED);
+        assert_eq!(
+            dst.len(),
+            STREAM_IDENTIFIER.len() + 4 + CHECKSUM_LEN + bytes.len()
+        );
+    }
+
+    #[test]
+    fn appends_to_existing_buffer() {
+        let mut dst = BytesMut::from(&[42u8][..]);
+        encode_snappy_frames(&[1, 2, 3], &mut dst).unwrap();
+        assert_eq!(dst[0], 42);
+        assert_eq!(decode(&dst[1..]), vec![1, 2, 3]);
+    }
+}
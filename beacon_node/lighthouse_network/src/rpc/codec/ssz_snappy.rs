@@ -1,3 +1,4 @@
+use super::snappy_framing::encode_snappy_frames;
 use crate::rpc::methods::*;
 use crate::rpc::{
     codec::base::OutboundCodec,
@@ -6,12 +7,11 @@ use crate::rpc::{
 use crate::rpc::{InboundRequest, OutboundRequest};
 use libp2p::bytes::BytesMut;
 use snap::read::FrameDecoder;
-use snap::write::FrameEncoder;
 use ssz::{Decode, Encode};
 use ssz_types::VariableList;
 use std::io::Cursor;
 use std::io::ErrorKind;
-use std::io::{Read, Write};
+use std::io::Read;
 use std::marker::PhantomData;
 use std::sync::Arc;
 use tokio_util::codec::{Decoder, Encoder};
@@ -111,13 +111,8 @@ impl<E: EthSpec> Encoder<RPCCodedResponse<E>> for SSZSnappyInboundCodec<E> {
             .encode(bytes.len(), dst)
             .map_err(RPCError::from)?;
 
-        let mut writer = FrameEncoder::new(Vec::new());
-        writer.write_all(&bytes).map_err(RPCError::from)?;
-        writer.flush().map_err(RPCError::from)?;
-
         // Write compressed bytes to `dst`
-        dst.extend_from_slice(writer.get_ref());
-        Ok(())
+        encode_snappy_frames(&bytes, dst).map_err(RPCError::from)
     }
 }
 
@@ -240,13 +235,8 @@ impl<E: EthSpec> Encoder<OutboundRequest<E>> for SSZSnappyOutboundCodec<E> {
             .encode(bytes.len(), dst)
             .map_err(RPCError::from)?;
 
-        let mut writer = FrameEncoder::new(Vec::new());
-        writer.write_all(&bytes).map_err(RPCError::from)?;
-        writer.flush().map_err(RPCError::from)?;
-
         // Write compressed bytes to `dst`
-        dst.extend_from_slice(writer.get_ref());
-        Ok(())
+        encode_snappy_frames(&bytes, dst).map_err(RPCError::from)
     }
 }
 
@@ -745,6 +735,8 @@ mod tests {
     use super::*;
     use crate::rpc::protocol::*;
     use crate::types::{EnrAttestationBitfield, EnrSyncCommitteeBitfield};
+    use snap::write::FrameEncoder;
+    use std::io::Write;
     use types::{
         blob_sidecar::BlobIdentifier, BeaconBlock, BeaconBlockAltair, BeaconBlockBase,
         BeaconBlockBellatrix, EmptyBlock, Epoch, FullPayload, Signature, Slot,
@@ -1677,4 +1669,107 @@ mod tests {
             RPCError::InvalidData(_)
         ));
     }
+
+    /// Every truncation of `bytes`, and every copy of `bytes` with a single byte inverted.
+    fn malformed_encodings(bytes: &[u8]) -> Vec<BytesMut> {
+        let truncations = (0..bytes.len()).map(|len| BytesMut::from(&bytes[..len]));
+        let corruptions = (0..bytes.len()).map(|i| {
+            let mut corrupted = BytesMut::from(bytes);
+            corrupted[i] ^= 0xff;
+            corrupted
+        });
+        truncations.chain(corruptions).collect()
+    }
+
+    /// Malformed responses must be rejected or left pending, never cause a panic.
+    #[test]
+    fn test_decode_malformed_responses() {
+        let chain_spec = Spec::default_spec();
+
+        let responses = [
+            (
+                SupportedProtocol::StatusV1,
+                RPCResponse::Status(status_message()),
+                ForkName::Base,
+            ),
+            (
+                SupportedProtocol::MetaDataV2,
+                RPCResponse::MetaData(metadata_v2()),
+                ForkName::Altair,
+            ),
+            (
+                SupportedProtocol::BlocksByRangeV2,
+                RPCResponse::BlocksByRange(Arc::new(empty_base_block())),
+                ForkName::Altair,
+            ),
+            (
+                SupportedProtocol::BlocksByRootV2,
+                RPCResponse::BlocksByRoot(Arc::new(altair_block())),
+                ForkName::Altair,
+            ),
+            (
+                SupportedProtocol::BlobsByRangeV1,
+                RPCResponse::BlobsByRange(empty_blob_sidecar()),
+                ForkName::Deneb,
+            ),
+            (
+                SupportedProtocol::BlobsByRootV1,
+                RPCResponse::BlobsByRoot(empty_blob_sidecar()),
+                ForkName::Deneb,
+            ),
+        ];
+
+        for (protocol, response, fork_name) in responses {
+            let encoded = encode_response(
+                protocol,
+                RPCCodedResponse::Success(response),
+                fork_name,
+                &chain_spec,
+            )
+            .unwrap();
+            for mut malformed in malformed_encodings(&encoded) {
+                let _ = decode_response(protocol, &mut malformed, fork_name, &chain_spec);
+            }
+        }
+    }
+
+    /// Malformed requests must be rejected or left pending, never cause a panic.
+    #[test]
+    fn test_decode_malformed_requests() {
+        let chain_spec = Spec::default_spec();
+
+        let requests: &[OutboundRequest<Spec>] = &[
+            OutboundRequest::Ping(ping_message()),
+            OutboundRequest::Status(status_message()),
+            OutboundRequest::Goodbye(GoodbyeReason::Fault),
+            OutboundRequest::BlocksByRange(bbrange_request_v2()),
+            OutboundRequest::BlocksByRoot(bbroot_request_v2(&chain_spec)),
+            OutboundRequest::BlobsByRange(blbrange_request()),
+            OutboundRequest::BlobsByRoot(blbroot_request(&chain_spec)),
+        ];
+
+        for req in requests {
+            let fork_context = Arc::new(fork_context(ForkName::Deneb));
+            let max_packet_size = max_rpc_size(&fork_context, chain_spec.max_chunk_size as usize);
+            let protocol = ProtocolId::new(req.versioned_protocol(), Encoding::SSZSnappy);
+
+            let mut encoded = BytesMut::new();
+            SSZSnappyOutboundCodec::<Spec>::new(
+                protocol.clone(),
+                max_packet_size,
+                fork_context.clone(),
+            )
+            .encode(req.clone(), &mut encoded)
+            .unwrap();
+
+            for mut malformed in malformed_encodings(&encoded) {
+                let mut inbound_codec = SSZSnappyInboundCodec::<Spec>::new(
+                    protocol.clone(),
+                    max_packet_size,
+                    fork_context.clone(),
+                );
+                let _ = inbound_codec.decode(&mut malformed);
+            }
+        }
+    }
 }
@@ -288,8 +288,8 @@ pub struct BlobsByRangeRequest {
 }
 
 impl BlobsByRangeRequest {
-    pub fn max_blobs_requested<E: EthSpec>(&self) -> u64 {
-        self.count.saturating_mul(E::max_blobs_per_block() as u64)
+    pub fn max_blobs_requested<E: EthSpec>(&self, spec: &ChainSpec) -> u64 {
+        self.count.saturating_mul(spec.max_blobs_per_block::<E>())
     }
 }
 
@@ -0,0 +1,390 @@
+//! Operator-supplied overrides for the gossipsub topic score parameters.
+//!
+//! The overrides are read from a TOML file with a table for each topic, e.g.:
+//!
+//! ```toml
+//! [topics.beacon_block]
+//! topic_weight = 0.8
+//!
+//! [topics.beacon_attestation]
+//! first_message_deliveries_decay = 0.9
+//!
+//! [topics.beacon_attestation_5]
+//! topic_weight = 0.05
+//! ```
+//!
+//! Topics are named as in their gossipsub topic string. Subnet topics may also be named without
+//! their subnet id, in which case the overrides apply to every subnet. Overrides for a particular
+//! subnet are applied on top of those for all subnets.
+//!
+//! Only topics which Lighthouse already scores can be overridden, and a file which names any other
+//! topic is rejected.
+use crate::types::{GossipKind, GossipTopic, SSZ_SNAPPY_ENCODING_POSTFIX, TOPIC_PREFIX};
+use crate::TopicHash;
+use gossipsub::TopicScoreParams;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use types::ChainSpec;
+
+/// The names which refer to every subnet of a scored subnet topic.
+const SUBNET_FAMILIES: [&str; 2] = ["beacon_attestation", "blob_sidecar"];
+
+/// Overrides for the score parameters of a single topic.
+///
+/// See `TopicScoreParams` for the meaning of each field. Fields which are not set keep the value
+/// chosen by Lighthouse.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopicScoreOverrides {
+    pub topic_weight: Option<f64>,
+    pub time_in_mesh_weight: Option<f64>,
+    pub time_in_mesh_cap: Option<f64>,
+    pub first_message_deliveries_weight: Option<f64>,
+    pub first_message_deliveries_decay: Option<f64>,
+    pub first_message_deliveries_cap: Option<f64>,
+    pub mesh_message_deliveries_weight: Option<f64>,
+    pub mesh_message_deliveries_decay: Option<f64>,
+    pub mesh_message_deliveries_cap: Option<f64>,
+    pub mesh_message_deliveries_threshold: Option<f64>,
+    pub mesh_failure_penalty_weight: Option<f64>,
+    pub mesh_failure_penalty_decay: Option<f64>,
+    pub invalid_message_deliveries_weight: Option<f64>,
+    pub invalid_message_deliveries_decay: Option<f64>,
+}
+
+impl TopicScoreOverrides {
+    /// Returns `params` with each of the overridden fields replaced.
+    pub fn apply(&self, mut params: TopicScoreParams) -> TopicScoreParams {
+        let fields = [
+            (self.topic_weight, &mut params.topic_weight),
+            (self.time_in_mesh_weight, &mut params.time_in_mesh_weight),
+            (self.time_in_mesh_cap, &mut params.time_in_mesh_cap),
+            (
+                self.first_message_deliveries_weight,
+                &mut params.first_message_deliveries_weight,
+            ),
+            (
+                self.first_message_deliveries_decay,
+                &mut params.first_message_deliveries_decay,
+            ),
+            (
+                self.first_message_deliveries_cap,
+                &mut params.first_message_deliveries_cap,
+            ),
+            (
+                self.mesh_message_deliveries_weight,
+                &mut params.mesh_message_deliveries_weight,
+            ),
+            (
+                self.mesh_message_deliveries_decay,
+                &mut params.mesh_message_deliveries_decay,
+            ),
+            (
+                self.mesh_message_deliveries_cap,
+                &mut params.mesh_message_deliveries_cap,
+            ),
+            (
+                self.mesh_message_deliveries_threshold,
+                &mut params.mesh_message_deliveries_threshold,
+            ),
+            (
+                self.mesh_failure_penalty_weight,
+                &mut params.mesh_failure_penalty_weight,
+            ),
+            (
+                self.mesh_failure_penalty_decay,
+                &mut params.mesh_failure_penalty_decay,
+            ),
+            (
+                self.invalid_message_deliveries_weight,
+                &mut params.invalid_message_deliveries_weight,
+            ),
+            (
+                self.invalid_message_deliveries_decay,
+                &mut params.invalid_message_deliveries_decay,
+            ),
+        ];
+        for (value, field) in fields {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+        params
+    }
+}
+
+/// The contents of a gossipsub score overrides file.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GossipScoringOverrides {
+    #[serde(default)]
+    pub topics: HashMap<String, TopicScoreOverrides>,
+}
+
+impl GossipScoringOverrides {
+    /// Read the overrides from the TOML file at `path`.
+    ///
+    /// Returns an error if the file names a topic which is unknown or isn't scored.
+    pub fn load(path: &Path, spec: &ChainSpec) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            format!(
+                "Unable to read gossipsub score overrides from {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+        let overrides: Self = toml::from_str(&contents).map_err(|e| {
+            format!(
+                "Unable to parse gossipsub score overrides from {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+        overrides.validate(spec).map_err(|e| {
+            format!(
+                "Invalid gossipsub score overrides in {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+        Ok(overrides)
+    }
+
+    /// Check that every topic named by the overrides is scored by Lighthouse.
+    pub fn validate(&self, spec: &ChainSpec) -> Result<(), String> {
+        match self.topics.keys().find(|name| !is_scored_topic(name, spec)) {
+            Some(name) => Err(format!("topic {} is unknown or not scored", name)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns `params` with the overrides for `topic` applied.
+    ///
+    /// Returns an error if the resulting parameters are invalid.
+    pub fn apply(
+        &self,
+        topic: &TopicHash,
+        params: TopicScoreParams,
+    ) -> Result<TopicScoreParams, String> {
+        let Ok(topic) = GossipTopic::decode(topic.as_str()) else {
+            return Ok(params);
+        };
+        let kind = topic.kind();
+
+        let family = subnet_family(kind).and_then(|family| self.topics.get(family));
+        let exact = self.topics.get(&kind.to_string());
+        if family.is_none() && exact.is_none() {
+            return Ok(params);
+        }
+
+        let params = family
+            .into_iter()
+            .chain(exact)
+            .fold(params, |params, overrides| overrides.apply(params));
+        params
+            .validate()
+            .map_err(|e| format!("Invalid score parameters for topic {}: {}", kind, e))?;
+        Ok(params)
+    }
+}
+
+/// The name which refers to every subnet of the same kind as `kind`, if it is a scored subnet
+/// topic.
+fn subnet_family(kind: &GossipKind) -> Option<&'static str> {
+    match kind {
+        GossipKind::Attestation(_) => Some(SUBNET_FAMILIES[0]),
+        GossipKind::BlobSidecar(_) => Some(SUBNET_FAMILIES[1]),
+        _ => None,
+    }
+}
+
+/// Returns `true` if `name` is a topic or family of subnet topics which has score parameters, see
+/// `PeerScoreSettings::get_peer_score_params`.
+fn is_scored_topic(name: &str, spec: &ChainSpec) -> bool {
+    if SUBNET_FAMILIES.contains(&name) {
+        return true;
+    }
+    let topic = format!(
+        "/{}/00000000/{}/{}",
+        TOPIC_PREFIX, name, SSZ_SNAPPY_ENCODING_POSTFIX
+    );
+    let Ok(topic) = GossipTopic::decode(&topic) else {
+        return false;
+    };
+    // Overrides are looked up by the canonical name, e.g. `beacon_attestation_5` rather than
+    // `beacon_attestation_05`.
+    if topic.kind().to_string() != name {
+        return false;
+    }
+    match topic.kind() {
+        GossipKind::BeaconBlock
+        | GossipKind::BeaconAggregateAndProof
+        | GossipKind::VoluntaryExit
+        | GossipKind::ProposerSlashing
+        | GossipKind::AttesterSlashing => true,
+        GossipKind::Attestation(subnet_id) => **subnet_id < spec.attestation_subnet_count,
+        GossipKind::BlobSidecar(index) => *index < spec.blob_sidecar_subnet_count,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::gossipsub_scoring_parameters::{
+        lighthouse_gossip_thresholds, PeerScoreSettings,
+    };
+    use crate::types::GossipEncoding;
+    use gossipsub::IdentTopic as Topic;
+    use types::{EnrForkId, MainnetEthSpec, Slot, SubnetId};
+
+    fn topic_hash(kind: GossipKind) -> TopicHash {
+        Topic::from(GossipTopic::new(kind, GossipEncoding::default(), [0; 4])).hash()
+    }
+
+    #[test]
+    fn subnet_overrides_take_precedence() {
+        let overrides: GossipScoringOverrides = toml::from_str(
+            r#"
+            [topics.beacon_attestation]
+            topic_weight = 0.1
+            first_message_deliveries_decay = 0.9
+
+            [topics.beacon_attestation_5]
+            topic_weight = 0.2
+            "#,
+        )
+        .unwrap();
+        let base = TopicScoreParams::default();
+
+        let subnet_5 = overrides
+            .apply(
+                &topic_hash(GossipKind::Attestation(SubnetId::new(5))),
+                base.clone(),
+            )
+            .unwrap();
+        assert_eq!(subnet_5.topic_weight, 0.2);
+        assert_eq!(subnet_5.first_message_deliveries_decay, 0.9);
+
+        let subnet_6 = overrides
+            .apply(
+                &topic_hash(GossipKind::Attestation(SubnetId::new(6))),
+                base.clone(),
+            )
+            .unwrap();
+        assert_eq!(subnet_6.topic_weight, 0.1);
+
+        let block = overrides
+            .apply(&topic_hash(GossipKind::BeaconBlock), base.clone())
+            .unwrap();
+        assert_eq!(block.topic_weight, base.topic_weight);
+        assert_eq!(
+            block.first_message_deliveries_decay,
+            base.first_message_deliveries_decay
+        );
+    }
+
+    #[test]
+    fn invalid_overrides_are_rejected() {
+        let overrides: GossipScoringOverrides = toml::from_str(
+            r#"
+            [topics.beacon_block]
+            invalid_message_deliveries_weight = 1.0
+            "#,
+        )
+        .unwrap();
+        assert!(overrides
+            .apply(
+                &topic_hash(GossipKind::BeaconBlock),
+                TopicScoreParams::default()
+            )
+            .is_err());
+
+        assert!(toml::from_str::<GossipScoringOverrides>(
+            r#"
+            [topics.beacon_block]
+            topic_wieght = 1.0
+            "#,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn unscored_topics_are_rejected() {
+        let spec = ChainSpec::mainnet();
+        let validate = |contents: &str| {
+            toml::from_str::<GossipScoringOverrides>(contents)
+                .unwrap()
+                .validate(&spec)
+        };
+
+        assert!(validate(
+            r#"
+            [topics.beacon_block]
+            topic_weight = 0.8
+
+            [topics.blob_sidecar]
+            topic_weight = 0.1
+
+            [topics.blob_sidecar_2]
+            topic_weight = 0.2
+
+            [topics.beacon_attestation_63]
+            topic_weight = 0.05
+            "#
+        )
+        .is_ok());
+
+        for name in [
+            "sync_committee",
+            "sync_committee_1",
+            "bls_to_execution_change",
+            "beacon_attestation_64",
+            "beacon_attestation_05",
+            "blob_sidecar_6",
+            "beacon_blocks",
+        ] {
+            assert!(
+                validate(&format!("[topics.{name}]\ntopic_weight = 0.1")).is_err(),
+                "{name} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn blob_sidecar_overrides_apply() {
+        let overrides: GossipScoringOverrides = toml::from_str(
+            r#"
+            [topics.blob_sidecar]
+            topic_weight = 0.1
+            "#,
+        )
+        .unwrap();
+        let params = overrides
+            .apply(
+                &topic_hash(GossipKind::BlobSidecar(3)),
+                TopicScoreParams::default(),
+            )
+            .unwrap();
+        assert_eq!(params.topic_weight, 0.1);
+
+        // Blob sidecar topics are scored, so the overrides have parameters to apply to.
+        let spec = ChainSpec::mainnet();
+        let enr_fork_id = EnrForkId {
+            fork_digest: [0; 4],
+            ..EnrForkId::default()
+        };
+        let params = PeerScoreSettings::<MainnetEthSpec>::new(&spec, 8)
+            .get_peer_score_params(
+                spec.min_genesis_active_validator_count as usize,
+                &lighthouse_gossip_thresholds(),
+                &enr_fork_id,
+                Slot::new(0),
+            )
+            .unwrap();
+        assert!(params
+            .topics
+            .contains_key(&topic_hash(GossipKind::BlobSidecar(3))));
+    }
+}
@@ -14,6 +14,8 @@ const BEACON_AGGREGATE_PROOF_WEIGHT: f64 = 0.5;
 const VOLUNTARY_EXIT_WEIGHT: f64 = 0.05;
 const PROPOSER_SLASHING_WEIGHT: f64 = 0.05;
 const ATTESTER_SLASHING_WEIGHT: f64 = 0.05;
+/// The combined weight of all blob sidecar subnets.
+const BLOB_SIDECAR_WEIGHT: f64 = 0.5;
 
 /// The time window (seconds) that we expect messages to be forwarded to us in the mesh.
 const MESH_MESSAGE_DELIVERIES_WINDOW: u64 = 2;
@@ -37,6 +39,7 @@ pub struct PeerScoreSettings<E: EthSpec> {
     epoch: Duration,
 
     beacon_attestation_subnet_weight: f64,
+    blob_sidecar_subnet_weight: f64,
     max_positive_score: f64,
 
     decay_interval: Duration,
@@ -47,6 +50,7 @@ pub struct PeerScoreSettings<E: EthSpec> {
     target_committee_size: usize,
     target_aggregators_per_committee: usize,
     attestation_subnet_count: u64,
+    blob_sidecar_subnet_count: u64,
     phantom: PhantomData<E>,
 }
 
@@ -54,10 +58,13 @@ impl<E: EthSpec> PeerScoreSettings<E> {
     pub fn new(chain_spec: &ChainSpec, mesh_n: usize) -> PeerScoreSettings<E> {
         let slot = Duration::from_secs(chain_spec.seconds_per_slot);
         let beacon_attestation_subnet_weight = 1.0 / chain_spec.attestation_subnet_count as f64;
+        let blob_sidecar_subnet_weight =
+            BLOB_SIDECAR_WEIGHT / chain_spec.blob_sidecar_subnet_count as f64;
         let max_positive_score = (MAX_IN_MESH_SCORE + MAX_FIRST_MESSAGE_DELIVERIES_SCORE)
             * (BEACON_BLOCK_WEIGHT
                 + BEACON_AGGREGATE_PROOF_WEIGHT
                 + beacon_attestation_subnet_weight * chain_spec.attestation_subnet_count as f64
+                + BLOB_SIDECAR_WEIGHT
                 + VOLUNTARY_EXIT_WEIGHT
                 + PROPOSER_SLASHING_WEIGHT
                 + ATTESTER_SLASHING_WEIGHT);
@@ -66,6 +73,7 @@ impl<E: EthSpec> PeerScoreSettings<E> {
             slot,
             epoch: slot * E::slots_per_epoch() as u32,
             beacon_attestation_subnet_weight,
+            blob_sidecar_subnet_weight,
             max_positive_score,
             decay_interval: max(Duration::from_secs(1), slot),
             decay_to_zero: 0.01,
@@ -74,6 +82,7 @@ impl<E: EthSpec> PeerScoreSettings<E> {
             target_committee_size: chain_spec.target_committee_size,
             target_aggregators_per_committee: chain_spec.target_aggregators_per_committee as usize,
             attestation_subnet_count: chain_spec.attestation_subnet_count,
+            blob_sidecar_subnet_count: chain_spec.blob_sidecar_subnet_count,
             phantom: PhantomData,
         }
     }
@@ -168,6 +177,14 @@ impl<E: EthSpec> PeerScoreSettings<E> {
             );
         }
 
+        let blob_sidecar_subnet_params = self.get_blob_sidecar_subnet_params();
+        for i in 0..self.blob_sidecar_subnet_count {
+            params.topics.insert(
+                get_hash(GossipKind::BlobSidecar(i)),
+                blob_sidecar_subnet_params.clone(),
+            );
+        }
+
         Ok(params)
     }
 
@@ -232,10 +249,28 @@ impl<E: EthSpec> PeerScoreSettings<E> {
         ))
     }
 
+    /// The parameters for each blob sidecar subnet.
+    ///
+    /// A block may have fewer blobs than there are subnets, so peers aren't penalised for a lack
+    /// of mesh message deliveries.
+    pub fn get_blob_sidecar_subnet_params(&self) -> TopicScoreParams {
+        Self::get_topic_params(
+            self,
+            self.blob_sidecar_subnet_weight,
+            1.0,
+            self.epoch * 20,
+            None,
+        )
+    }
+
     pub fn attestation_subnet_count(&self) -> u64 {
         self.attestation_subnet_count
     }
 
+    pub fn blob_sidecar_subnet_count(&self) -> u64 {
+        self.blob_sidecar_subnet_count
+    }
+
     fn score_parameter_decay_with_base(
         decay_time: Duration,
         decay_interval: Duration,
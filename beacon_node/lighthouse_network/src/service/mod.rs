@@ -6,7 +6,7 @@ use crate::discovery::{
 };
 use crate::peer_manager::{
     config::Config as PeerManagerCfg, peerdb::score::PeerAction, peerdb::score::ReportSource,
-    ConnectionDirection, PeerManager, PeerManagerEvent,
+    peerdb::session::DisconnectReason, ConnectionDirection, PeerManager, PeerManagerEvent,
 };
 use crate::peer_manager::{MIN_OUTBOUND_ONLY_FACTOR, PEER_EXCESS_FACTOR, PRIORITY_PEER_EXCESS};
 use crate::rpc::methods::MetadataRequest;
@@ -27,6 +27,7 @@ use gossipsub::{
     IdentTopic as Topic, MessageAcceptance, MessageAuthenticity, MessageId, PublishError,
     TopicScoreParams,
 };
+use gossipsub_scoring_overrides::GossipScoringOverrides;
 use gossipsub_scoring_parameters::{lighthouse_gossip_thresholds, PeerScoreSettings};
 use libp2p::multiaddr::{self, Multiaddr, Protocol as MProtocol};
 use libp2p::swarm::behaviour::toggle::Toggle;
@@ -36,6 +37,7 @@ use slog::{crit, debug, info, o, trace, warn};
 use std::path::PathBuf;
 use std::pin::Pin;
 use std::{
+    collections::HashMap,
     sync::Arc,
     task::{Context, Poll},
 };
@@ -48,6 +50,7 @@ use utils::{build_transport, strip_peer_id, Context as ServiceContext, MAX_CONNE
 pub mod api_types;
 mod behaviour;
 mod gossip_cache;
+pub mod gossipsub_scoring_overrides;
 pub mod gossipsub_scoring_parameters;
 pub mod utils;
 /// The number of peers we target per subnet for discovery queries.
@@ -55,6 +58,9 @@ pub const TARGET_SUBNET_PEERS: usize = 3;
 
 const MAX_IDENTIFY_ADDRESSES: usize = 10;
 
+/// The interval at which the gossipsub mesh of each topic is sampled.
+const GOSSIP_MESH_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
 /// The types of events than can be obtained from polling the behaviour.
 #[derive(Debug)]
 pub enum NetworkEvent<AppReqId: ReqId, E: EthSpec> {
@@ -122,8 +128,18 @@ pub struct Network<AppReqId: ReqId, E: EthSpec> {
     fork_context: Arc<ForkContext>,
     /// Gossipsub score parameters.
     score_settings: PeerScoreSettings<E>,
+    /// Operator overrides of the gossipsub topic score parameters.
+    score_overrides: GossipScoringOverrides,
+    /// The file that `score_overrides` were loaded from, if any.
+    score_overrides_path: Option<PathBuf>,
+    /// The topic score parameters chosen by Lighthouse, before `score_overrides` are applied.
+    base_topic_params: HashMap<TopicHash, TopicScoreParams>,
     /// The interval for updating gossipsub scores
     update_gossipsub_scores: tokio::time::Interval,
+    /// The interval for sampling the gossipsub mesh of each topic.
+    sample_gossip_mesh: tokio::time::Interval,
+    /// The gossipsub `mesh_n_low` parameter, below which a topic is under-meshed.
+    mesh_n_low: usize,
     gossip_cache: GossipCache,
     /// This node's PeerId.
     pub local_peer_id: PeerId,
@@ -215,11 +231,16 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
 
         let local_peer_id = network_globals.local_peer_id();
 
-        let (gossipsub, update_gossipsub_scores) = {
+        let score_overrides = match &config.gossip_scoring_overrides {
+            Some(path) => GossipScoringOverrides::load(path, &ctx.chain_spec)?,
+            None => GossipScoringOverrides::default(),
+        };
+
+        let (gossipsub, update_gossipsub_scores, base_topic_params) = {
             let thresholds = lighthouse_gossip_thresholds();
 
             // Prepare scoring parameters
-            let params = {
+            let mut params = {
                 // Construct a set of gossipsub peer scoring parameters
                 // We don't know the number of active validators and the current slot yet
                 let active_validators = E::minimum_validator_count();
@@ -232,6 +253,12 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
                 )?
             };
 
+            // Keep the parameters chosen by Lighthouse so that overrides can be reverted later.
+            let base_topic_params = params.topics.clone();
+            for (topic, topic_params) in params.topics.iter_mut() {
+                *topic_params = score_overrides.apply(topic, topic_params.clone())?;
+            }
+
             trace!(log, "Using peer score params"; "params" => ?params);
 
             // Set up a scoring update interval
@@ -303,7 +330,7 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
                 gossipsub.register_topics_for_metrics(topics_to_keep_metrics_for);
             }
 
-            (gossipsub, update_gossipsub_scores)
+            (gossipsub, update_gossipsub_scores, base_topic_params)
         };
 
         let network_params = NetworkParams {
@@ -356,6 +383,7 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
                 quic_enabled: !config.disable_quic_support,
                 metrics_enabled: config.metrics_enabled,
                 target_peer_count: config.target_peers,
+                inbound_pressure_threshold: config.inbound_pressure_threshold,
                 ..Default::default()
             };
             PeerManager::new(peer_manager_cfg, network_globals.clone(), &log)?
@@ -455,7 +483,12 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
             network_dir: config.network_dir.clone(),
             fork_context: ctx.fork_context,
             score_settings,
+            score_overrides,
+            score_overrides_path: config.gossip_scoring_overrides.clone(),
+            base_topic_params,
             update_gossipsub_scores,
+            sample_gossip_mesh: tokio::time::interval(GOSSIP_MESH_SAMPLE_INTERVAL),
+            mesh_n_low: gs_config.mesh_n_low(),
             gossip_cache,
             local_peer_id,
             log,
@@ -896,21 +929,97 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
             "beacon_attestation_subnet_params" => ?beacon_attestation_subnet_params,
         );
 
-        self.gossipsub_mut()
-            .set_topic_params(get_topic(GossipKind::BeaconBlock), beacon_block_params)?;
+        self.set_topic_params(get_topic(GossipKind::BeaconBlock), beacon_block_params)?;
 
-        self.gossipsub_mut().set_topic_params(
+        self.set_topic_params(
             get_topic(GossipKind::BeaconAggregateAndProof),
             beacon_aggregate_proof_params,
         )?;
 
         for i in 0..self.score_settings.attestation_subnet_count() {
-            self.gossipsub_mut().set_topic_params(
+            self.set_topic_params(
                 get_topic(GossipKind::Attestation(SubnetId::new(i))),
                 beacon_attestation_subnet_params.clone(),
             )?;
         }
 
+        // Blob sidecar parameters don't change, but are set here so that the topics of a new fork
+        // are scored.
+        let blob_sidecar_subnet_params = self.score_settings.get_blob_sidecar_subnet_params();
+        for i in 0..self.score_settings.blob_sidecar_subnet_count() {
+            self.set_topic_params(
+                get_topic(GossipKind::BlobSidecar(i)),
+                blob_sidecar_subnet_params.clone(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the score parameters of `topic`, after applying any operator overrides.
+    ///
+    /// If the overrides would make the parameters invalid, they are ignored.
+    fn set_topic_params(&mut self, topic: Topic, params: TopicScoreParams) -> error::Result<()> {
+        let topic_hash = topic.hash();
+        let overridden = self
+            .score_overrides
+            .apply(&topic_hash, params.clone())
+            .unwrap_or_else(|e| {
+                warn!(self.log, "Ignoring gossipsub score overrides"; "error" => e);
+                params.clone()
+            });
+        self.base_topic_params.insert(topic_hash, params);
+        self.gossipsub_mut().set_topic_params(topic, overridden)?;
+        Ok(())
+    }
+
+    /// Re-reads the gossipsub score overrides file and applies it to the topics of the current
+    /// fork.
+    ///
+    /// Nothing is changed if the file cannot be read, any of the overrides are invalid, or the
+    /// parameters of any topic cannot be set.
+    pub fn reload_gossipsub_score_overrides(&mut self) -> Result<(), String> {
+        let path = self
+            .score_overrides_path
+            .clone()
+            .ok_or("No gossipsub score overrides file is configured")?;
+        let score_overrides = GossipScoringOverrides::load(&path, &self.fork_context.spec)?;
+
+        let fork_digest = self.enr_fork_id.fork_digest;
+        let topic_params = self
+            .base_topic_params
+            .iter()
+            .filter_map(|(topic_hash, params)| {
+                let topic = GossipTopic::decode(topic_hash.as_str()).ok()?;
+                (topic.fork_digest == fork_digest).then(|| {
+                    let params = score_overrides.apply(topic_hash, params.clone())?;
+                    Ok((Topic::from(topic), params))
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        // Apply every topic or none of them, restoring the topics already updated on failure.
+        let mut previous_params = vec![];
+        for (topic, params) in topic_params {
+            let previous = self.gossipsub().get_topic_params(&topic).cloned();
+            if let Err(e) = self.gossipsub_mut().set_topic_params(topic.clone(), params) {
+                for (topic, previous) in previous_params.into_iter().rev() {
+                    if let Some(previous) = previous {
+                        let _ = self.gossipsub_mut().set_topic_params(topic, previous);
+                    }
+                }
+                return Err(format!("Unable to set gossipsub score parameters: {}", e));
+            }
+            previous_params.push((topic, previous));
+        }
+        self.score_overrides = score_overrides;
+
+        info!(
+            self.log,
+            "Reloaded gossipsub score overrides";
+            "path" => %path.display(),
+            "topics" => self.score_overrides.topics.len(),
+        );
         Ok(())
     }
 
@@ -1048,6 +1157,7 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
                         "connected_peers_on_subnet" => peers_on_subnet,
                         "target_subnet_peers" => TARGET_SUBNET_PEERS,
                     );
+                    self.discovery_mut().subnet_target_reached(s.subnet);
                     false
                 // Queue an outgoing connection request to the cached peers that are on `s.subnet_id`.
                 // If we connect to the cached peers before the discovery query starts, then we potentially
@@ -1464,6 +1574,10 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
                             "reason" => %reason,
                             "client" => %self.network_globals.client(&peer_id),
                         );
+                        self.network_globals.peers.write().set_disconnect_reason(
+                            &peer_id,
+                            DisconnectReason::goodbye_received(reason),
+                        );
                         // NOTE: We currently do not inform the application that we are
                         // disconnecting here. The RPC handler will automatically
                         // disconnect for us.
@@ -1689,6 +1803,10 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
             PeerManagerEvent::DisconnectPeer(peer_id, reason) => {
                 debug!(self.log, "Peer Manager disconnecting peer";
                        "peer_id" => %peer_id, "reason" => %reason);
+                self.network_globals.peers.write().set_disconnect_reason(
+                    &peer_id,
+                    DisconnectReason::goodbye_sent(reason.clone()),
+                );
                 // send one goodbye
                 self.eth2_rpc_mut()
                     .shutdown(peer_id, RequestId::Internal, reason);
@@ -1872,6 +1990,14 @@ impl<AppReqId: ReqId, E: EthSpec> Network<AppReqId, E> {
             this.peer_manager.update_gossipsub_scores(&this.gossipsub);
         }
 
+        // sample the gossipsub mesh of each topic for the mesh health API
+        while self.sample_gossip_mesh.poll_tick(cx).is_ready() {
+            self.network_globals
+                .gossip_mesh
+                .write()
+                .update(self.gossipsub(), self.mesh_n_low);
+        }
+
         // poll the gossipsub cache to clear expired messages
         while let Poll::Ready(Some(result)) = self.gossip_cache.poll_next_unpin(cx) {
             match result {
@@ -1,12 +1,13 @@
 //! A collection of variables that are accessible outside of the network thread itself.
 use crate::peer_manager::peerdb::PeerDB;
 use crate::rpc::{MetaData, MetaDataV2};
-use crate::types::{BackFillState, SyncState};
+use crate::types::{BackFillState, GossipMeshTracker, SyncState};
 use crate::Client;
 use crate::EnrExt;
 use crate::{Enr, GossipTopic, Multiaddr, PeerId};
 use parking_lot::RwLock;
 use std::collections::HashSet;
+use std::sync::atomic::{AtomicU8, Ordering};
 use types::EthSpec;
 
 pub struct NetworkGlobals<E: EthSpec> {
@@ -22,10 +23,14 @@ pub struct NetworkGlobals<E: EthSpec> {
     pub local_metadata: RwLock<MetaData<E>>,
     /// The current gossipsub topic subscriptions.
     pub gossipsub_subscriptions: RwLock<HashSet<GossipTopic>>,
+    /// Periodic samples of the gossipsub mesh of each subscribed topic.
+    pub gossip_mesh: RwLock<GossipMeshTracker>,
     /// The current sync status of the node.
     pub sync_state: RwLock<SyncState>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
+    /// How full the beacon processor's gossip queues are, as a percentage.
+    work_queue_pressure: AtomicU8,
 }
 
 impl<E: EthSpec> NetworkGlobals<E> {
@@ -43,8 +48,10 @@ impl<E: EthSpec> NetworkGlobals<E> {
             local_metadata: RwLock::new(local_metadata),
             peers: RwLock::new(PeerDB::new(trusted_peers, disable_peer_scoring, log)),
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
+            gossip_mesh: RwLock::new(GossipMeshTracker::default()),
             sync_state: RwLock::new(SyncState::Stalled),
             backfill_state: RwLock::new(BackFillState::NotRequired),
+            work_queue_pressure: AtomicU8::new(0),
         }
     }
 
@@ -79,6 +86,16 @@ impl<E: EthSpec> NetworkGlobals<E> {
         self.peers.read().connected_or_dialing_peers().count()
     }
 
+    /// Returns how full the beacon processor's gossip queues are, as a percentage.
+    pub fn work_queue_pressure(&self) -> u8 {
+        self.work_queue_pressure.load(Ordering::Relaxed)
+    }
+
+    /// Updates how full the beacon processor's gossip queues are, as a percentage.
+    pub fn set_work_queue_pressure(&self, percent: u8) {
+        self.work_queue_pressure.store(percent, Ordering::Relaxed);
+    }
+
     /// Returns in the node is syncing.
     pub fn is_syncing(&self) -> bool {
         self.sync_state.read().is_syncing()
@@ -0,0 +1,231 @@
+//! Tracking of the gossipsub mesh of each subscribed topic.
+//!
+//! The mesh is sampled periodically, so that operators can see whether topics such as the blob
+//! subnets are under-meshed, and whether their meshes are churning, before data is missed.
+
+use crate::peer_manager::peerdb::PeerDB;
+use crate::peer_manager::ConnectionDirection;
+use crate::types::GossipTopic;
+use crate::{Gossipsub, PeerId};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use types::EthSpec;
+
+/// The period over which mesh churn rates are calculated.
+const CHURN_WINDOW: Duration = Duration::from_secs(300);
+
+/// The mesh health of a single gossipsub topic.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GossipMeshTopic {
+    /// The full gossipsub topic.
+    pub topic: String,
+    /// The kind of the topic, e.g. `blob_sidecar_0`.
+    pub kind: String,
+    /// The number of peers in the mesh.
+    pub mesh_peers: usize,
+    /// The number of mesh peers which we dialed.
+    pub outbound_peers: usize,
+    /// The number of mesh peers which dialed us.
+    pub inbound_peers: usize,
+    /// Whether the mesh has fewer peers than gossipsub tries to maintain (`mesh_n_low`).
+    pub under_meshed: bool,
+    /// The rate at which peers have joined the mesh over the last 5 minutes.
+    pub grafts_per_minute: f64,
+    /// The rate at which peers have left the mesh over the last 5 minutes.
+    pub prunes_per_minute: f64,
+    /// When a peer last joined the mesh, in seconds since the UNIX epoch.
+    pub last_grafted: Option<u64>,
+}
+
+#[derive(Default)]
+struct TopicMesh {
+    peers: HashSet<PeerId>,
+    /// The number of peers which joined the mesh at each sample in the `CHURN_WINDOW`.
+    grafts: VecDeque<(Instant, usize)>,
+    /// The number of peers which left the mesh at each sample in the `CHURN_WINDOW`.
+    prunes: VecDeque<(Instant, usize)>,
+    /// When a peer last joined the mesh, in seconds since the UNIX epoch.
+    last_grafted: Option<u64>,
+}
+
+/// Keeps samples of the gossipsub mesh of each subscribed topic.
+#[derive(Default)]
+pub struct GossipMeshTracker {
+    topics: HashMap<String, TopicMesh>,
+    mesh_n_low: usize,
+    first_update: Option<Instant>,
+}
+
+impl GossipMeshTracker {
+    /// Records the current mesh peers of each subscribed topic.
+    pub fn update(&mut self, gossipsub: &Gossipsub, mesh_n_low: usize) {
+        let meshes = gossipsub.topics().map(|topic_hash| {
+            let peers = gossipsub.mesh_peers(topic_hash).cloned().collect();
+            (topic_hash.as_str().to_string(), peers)
+        });
+        let unix_now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        self.update_at(meshes, mesh_n_low, Instant::now(), unix_now)
+    }
+
+    /// Topics which are not present in `meshes` are assumed to have been unsubscribed from.
+    fn update_at(
+        &mut self,
+        meshes: impl IntoIterator<Item = (String, HashSet<PeerId>)>,
+        mesh_n_low: usize,
+        now: Instant,
+        unix_now: u64,
+    ) {
+        self.mesh_n_low = mesh_n_low;
+        self.first_update.get_or_insert(now);
+
+        let mut subscribed = HashSet::new();
+        for (topic, peers) in meshes {
+            let mesh = self.topics.entry(topic.clone()).or_default();
+
+            let grafts = peers.difference(&mesh.peers).count();
+            let prunes = mesh.peers.difference(&peers).count();
+            if grafts > 0 {
+                mesh.grafts.push_back((now, grafts));
+                mesh.last_grafted = Some(unix_now);
+            }
+            if prunes > 0 {
+                mesh.prunes.push_back((now, prunes));
+            }
+            for events in [&mut mesh.grafts, &mut mesh.prunes] {
+                while events
+                    .front()
+                    .map_or(false, |(time, _)| now.duration_since(*time) > CHURN_WINDOW)
+                {
+                    events.pop_front();
+                }
+            }
+
+            mesh.peers = peers;
+            subscribed.insert(topic);
+        }
+        self.topics.retain(|topic, _| subscribed.contains(topic));
+    }
+
+    /// Returns the mesh health of each subscribed topic, sorted by topic.
+    pub fn summary<E: EthSpec>(&self, peers: &PeerDB<E>) -> Vec<GossipMeshTopic> {
+        self.summary_at(peers, Instant::now())
+    }
+
+    fn summary_at<E: EthSpec>(&self, peers: &PeerDB<E>, now: Instant) -> Vec<GossipMeshTopic> {
+        // Don't underestimate the churn rates before a full window has been observed.
+        let minutes = self
+            .first_update
+            .map_or(Duration::ZERO, |first| now.duration_since(first))
+            .min(CHURN_WINDOW)
+            .as_secs_f64()
+            / 60.0;
+        let rate = |events: &VecDeque<(Instant, usize)>| {
+            if minutes > 0.0 {
+                events.iter().map(|(_, count)| *count).sum::<usize>() as f64 / minutes
+            } else {
+                0.0
+            }
+        };
+
+        let mut summary = self
+            .topics
+            .iter()
+            .map(|(topic, mesh)| {
+                let (mut outbound_peers, mut inbound_peers) = (0, 0);
+                for peer_id in &mesh.peers {
+                    match peers
+                        .peer_info(peer_id)
+                        .and_then(|info| info.connection_direction())
+                    {
+                        Some(ConnectionDirection::Outgoing) => outbound_peers += 1,
+                        Some(ConnectionDirection::Incoming) => inbound_peers += 1,
+                        None => (),
+                    }
+                }
+                GossipMeshTopic {
+                    topic: topic.clone(),
+                    kind: GossipTopic::decode(topic)
+                        .map(|topic| topic.kind().to_string())
+                        .unwrap_or_else(|_| topic.clone()),
+                    mesh_peers: mesh.peers.len(),
+                    outbound_peers,
+                    inbound_peers,
+                    under_meshed: mesh.peers.len() < self.mesh_n_low,
+                    grafts_per_minute: rate(&mesh.grafts),
+                    prunes_per_minute: rate(&mesh.prunes),
+                    last_grafted: mesh.last_grafted,
+                }
+            })
+            .collect::<Vec<_>>();
+        summary.sort_by(|a, b| a.topic.cmp(&b.topic));
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    fn peer_db() -> PeerDB<MainnetEthSpec> {
+        let log = slog::Logger::root(slog::Discard, slog::o!());
+        PeerDB::new(vec![], false, &log)
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn churn_and_unsubscribed_topics() {
+        let topic = "/eth2/6a95a1a9/blob_sidecar_0/ssz_snappy".to_string();
+        let other_topic = "/eth2/6a95a1a9/beacon_block/ssz_snappy".to_string();
+        let (a, b, c) = (PeerId::random(), PeerId::random(), PeerId::random());
+        let start = Instant::now();
+        let mut tracker = GossipMeshTracker::default();
+
+        tracker.update_at(
+            [
+                (topic.clone(), HashSet::from([a, b])),
+                (other_topic, HashSet::from([a])),
+            ],
+            2,
+            start,
+            100,
+        );
+        // One minute later `b` has been replaced by `c`, and the other topic is unsubscribed.
+        let later = start + Duration::from_secs(60);
+        tracker.update_at([(topic.clone(), HashSet::from([a, c]))], 3, later, 160);
+
+        let summary = tracker.summary_at(&peer_db(), later);
+        assert_eq!(
+            summary,
+            vec![GossipMeshTopic {
+                topic,
+                kind: "blob_sidecar_0".to_string(),
+                mesh_peers: 2,
+                outbound_peers: 0,
+                inbound_peers: 0,
+                under_meshed: true,
+                grafts_per_minute: 3.0,
+                prunes_per_minute: 1.0,
+                last_grafted: Some(160),
+            }]
+        );
+
+        // Once the churn window has passed the old changes no longer count.
+        let much_later = later + CHURN_WINDOW + Duration::from_secs(1);
+        tracker.update_at(
+            [(summary[0].topic.clone(), HashSet::from([a, c]))],
+            2,
+            much_later,
+            500,
+        );
+        let summary = tracker.summary_at(&peer_db(), much_later);
+        assert_eq!(summary[0].grafts_per_minute, 0.0);
+        assert_eq!(summary[0].prunes_per_minute, 0.0);
+        assert_eq!(summary[0].last_grafted, Some(160));
+        assert!(!summary[0].under_meshed);
+    }
+}
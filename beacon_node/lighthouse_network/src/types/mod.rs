@@ -1,5 +1,7 @@
 pub mod error;
 mod globals;
+#[allow(clippy::mutable_key_type)] // PeerId in hashmaps are no longer permitted by clippy
+mod gossip_mesh;
 mod pubsub;
 mod subnet;
 mod sync_state;
@@ -13,6 +15,7 @@ pub type EnrSyncCommitteeBitfield<E> = BitVector<<E as EthSpec>::SyncCommitteeSu
 pub type Enr = discv5::enr::Enr<discv5::enr::CombinedKey>;
 
 pub use globals::NetworkGlobals;
+pub use gossip_mesh::{GossipMeshTopic, GossipMeshTracker};
 pub use pubsub::{PubsubMessage, SnappyTransform};
 pub use subnet::{Subnet, SubnetDiscovery};
 pub use sync_state::{BackFillState, SyncState};
@@ -20,4 +23,5 @@ pub use topics::{
     attestation_sync_committee_topics, core_topics_to_subscribe, fork_core_topics,
     subnet_from_topic_hash, GossipEncoding, GossipKind, GossipTopic, ALTAIR_CORE_TOPICS,
     BASE_CORE_TOPICS, CAPELLA_CORE_TOPICS, DENEB_CORE_TOPICS, LIGHT_CLIENT_GOSSIP_TOPICS,
+    SSZ_SNAPPY_ENCODING_POSTFIX, TOPIC_PREFIX,
 };
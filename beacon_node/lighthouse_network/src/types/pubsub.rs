@@ -169,6 +169,12 @@ impl<E: EthSpec> PubsubMessage<E> {
                         ))))
                     }
                     GossipKind::BeaconBlock => {
+                        let span = tracing::info_span!(
+                            "gossip_block_decode",
+                            bytes = data.len(),
+                            slot = tracing::field::Empty,
+                        );
+                        let _guard = span.enter();
                         let beacon_block =
                             match fork_context.from_context_bytes(gossip_topic.fork_digest) {
                                 Some(ForkName::Base) => SignedBeaconBlock::<E>::Base(
@@ -202,6 +208,7 @@ impl<E: EthSpec> PubsubMessage<E> {
                                     ))
                                 }
                             };
+                        span.record("slot", beacon_block.slot().as_u64());
                         Ok(PubsubMessage::BeaconBlock(Arc::new(beacon_block)))
                     }
                     GossipKind::BlobSidecar(blob_index) => {
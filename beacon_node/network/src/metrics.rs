@@ -1,5 +1,5 @@
 use beacon_chain::{
-    attestation_verification::Error as AttnError,
+    attestation_verification::Error as AttnError, blob_verification::GossipBlobError,
     light_client_finality_update_verification::Error as LightClientFinalityUpdateError,
     light_client_optimistic_update_verification::Error as LightClientOptimisticUpdateError,
     sync_committee_verification::Error as SyncCommitteeError,
@@ -68,10 +68,27 @@ lazy_static! {
         "beacon_processor_gossip_block_early_seconds",
         "Whenever a gossip block is received early this metrics is set to how early that block was."
     );
+    pub static ref BEACON_PROCESSOR_GOSSIP_BLOCK_PENDING_AVAILABILITY_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "beacon_processor_gossip_block_pending_availability_total",
+        "Total number of gossip blocks which waited for their blobs, by whether they were imported or dropped",
+        &["outcome"]
+    );
+    pub static ref BEACON_PROCESSOR_GOSSIP_BLOCK_PENDING_AVAILABILITY_SECONDS: Result<Histogram> = try_create_histogram(
+        "beacon_processor_gossip_block_pending_availability_seconds",
+        "Time gossip blocks spent waiting for their blobs before being imported or dropped."
+    );
     pub static ref BEACON_PROCESSOR_GOSSIP_BLOB_VERIFIED_TOTAL: Result<IntCounter> = try_create_int_counter(
         "beacon_processor_gossip_blob_verified_total",
         "Total number of gossip blob verified for propagation."
     );
+    pub static ref BEACON_PROCESSOR_GOSSIP_BLOB_VERIFICATION_SECONDS: Result<Histogram> = try_create_histogram(
+        "beacon_processor_gossip_blob_verification_seconds",
+        "Time taken to verify a gossip blob, including waiting for a KZG verification thread."
+    );
+    pub static ref BEACON_PROCESSOR_GOSSIP_BLOB_VERIFICATIONS_IN_FLIGHT: Result<IntGauge> = try_create_int_gauge(
+        "beacon_processor_gossip_blob_verifications_in_flight",
+        "Number of gossip blobs currently being verified concurrently."
+    );
     // Gossip Exits.
     pub static ref BEACON_PROCESSOR_EXIT_VERIFIED_TOTAL: Result<IntCounter> = try_create_int_counter(
         "beacon_processor_exit_verified_total",
@@ -193,6 +210,12 @@ lazy_static! {
             "Gossipsub sync_committee errors per error type",
             &["type"]
         );
+    pub static ref GOSSIP_BLOB_ERRORS_PER_TYPE: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "gossipsub_blob_errors_per_type",
+            "Gossipsub blob sidecar errors per error type",
+            &["type"]
+        );
     pub static ref GOSSIP_FINALITY_UPDATE_ERRORS_PER_TYPE: Result<IntCounterVec> =
         try_create_int_counter_vec(
             "gossipsub_light_client_finality_update_errors_per_type",
@@ -262,6 +285,25 @@ lazy_static! {
         "sync_lookups_stuck_total",
         "Total count of sync lookups that are stuck and dropped",
     );
+    pub static ref SYNC_PREEMPTIVE_BLOB_REQUESTS: Result<IntCounter> = try_create_int_counter(
+        "sync_preemptive_blob_requests_total",
+        "Total count of BlobsByRoot requests made pre-emptively for gossip blocks",
+    );
+    pub static ref SYNC_PENDING_AVAILABILITY_TIMEOUTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "sync_pending_availability_timeouts_total",
+        "Total count of gossip blocks which timed out waiting for their blobs, by the action taken",
+        &["action"]
+    );
+    pub static ref SYNC_BLOB_WITHHOLDING_PENALTIES: Result<IntCounterVec> = try_create_int_counter_vec(
+        "sync_blob_withholding_penalties_total",
+        "Total count of peers penalised for sending blocks whose blobs never became available, by reason",
+        &["reason"]
+    );
+    pub static ref SYNC_PREEMPTIVE_BLOBS_RECEIVED: Result<IntCounterVec> = try_create_int_counter_vec(
+        "sync_preemptive_blobs_received_total",
+        "Total count of blobs received from pre-emptive requests, by whether they were already known",
+        &["outcome"]
+    );
 
     /*
      * Block Delay Metrics
@@ -342,6 +384,10 @@ pub fn register_sync_committee_error(error: &SyncCommitteeError) {
     inc_counter_vec(&GOSSIP_SYNC_COMMITTEE_ERRORS_PER_TYPE, &[error.as_ref()]);
 }
 
+pub fn register_blob_error<E: EthSpec>(error: &GossipBlobError<E>) {
+    inc_counter_vec(&GOSSIP_BLOB_ERRORS_PER_TYPE, &[error.as_ref()]);
+}
+
 pub fn update_gossip_metrics<E: EthSpec>(
     gossipsub: &Gossipsub,
     network_globals: &Arc<NetworkGlobals<E>>,
@@ -9,7 +9,9 @@ use beacon_chain::block_verification_types::AsBlock;
 use beacon_chain::store::Error;
 use beacon_chain::{
     attestation_verification::{self, Error as AttnError, VerifiedAttestation},
-    data_availability_checker::AvailabilityCheckErrorCategory,
+    data_availability_checker::{
+        AvailabilityCheckErrorCategory, AvailabilityPendingBlock, PendingBlockOutcome,
+    },
     light_client_finality_update_verification::Error as LightClientFinalityUpdateError,
     light_client_optimistic_update_verification::Error as LightClientOptimisticUpdateError,
     observed_operations::ObservationOutcome,
@@ -30,6 +32,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use store::hot_cold_store::HotColdDBError;
 use tokio::sync::mpsc;
+use tracing::{info_span, Instrument};
 use types::{
     beacon_block::BlockImportSource, Attestation, AttesterSlashing, BlobSidecar, EthSpec, Hash256,
     IndexedAttestation, LightClientFinalityUpdate, LightClientOptimisticUpdate, ProposerSlashing,
@@ -616,10 +619,24 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         let delay = get_slot_delay_ms(seen_duration, slot, &self.chain.slot_clock);
         // Log metrics to track delay from other nodes on the network.
         metrics::set_gauge(&metrics::BEACON_BLOB_DELAY_GOSSIP, delay.as_millis() as i64);
-        match self
+        // Verification includes the KZG proof, so keep it off the async workers. Blobs for
+        // different blocks are independent, so many may be verified at once, bounded by the
+        // number of KZG verification threads.
+        let chain = self.chain.clone();
+        let verification_timer =
+            metrics::start_timer(&metrics::BEACON_PROCESSOR_GOSSIP_BLOB_VERIFICATION_SECONDS);
+        metrics::inc_gauge(&metrics::BEACON_PROCESSOR_GOSSIP_BLOB_VERIFICATIONS_IN_FLIGHT);
+        let verification_result = self
             .chain
-            .verify_blob_sidecar_for_gossip(blob_sidecar, blob_index)
-        {
+            .spawn_kzg_verification(
+                move || chain.verify_blob_sidecar_for_gossip(blob_sidecar, blob_index),
+                "gossip_blob_verification",
+            )
+            .await
+            .unwrap_or_else(|e| Err(GossipBlobError::BeaconChainError(e)));
+        metrics::dec_gauge(&metrics::BEACON_PROCESSOR_GOSSIP_BLOB_VERIFICATIONS_IN_FLIGHT);
+        drop(verification_timer);
+        match verification_result {
             Ok(gossip_verified_blob) => {
                 metrics::inc_counter(&metrics::BEACON_PROCESSOR_GOSSIP_BLOB_VERIFIED_TOTAL);
 
@@ -662,6 +679,7 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     .await
             }
             Err(err) => {
+                metrics::register_blob_error(&err);
                 match err {
                     GossipBlobError::BlobParentUnknown(blob) => {
                         debug!(
@@ -792,13 +810,13 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     processing_start_time.elapsed().as_millis() as i64,
                 );
             }
-            Ok(AvailabilityProcessingStatus::MissingComponents(slot, block_root)) => {
+            Ok(AvailabilityProcessingStatus::MissingComponents(pending)) => {
                 trace!(
                     self.log,
                     "Processed blob, waiting for other components";
-                    "slot" => %slot,
+                    "slot" => %pending.slot(),
                     "blob_index" => %blob_index,
-                    "block_root" => %block_root,
+                    "block_root" => %pending.block_root(),
                 );
             }
             Err(BlockError::BlockIsAlreadyKnown(_)) => {
@@ -856,38 +874,48 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         invalid_block_storage: InvalidBlockStorage,
         seen_duration: Duration,
     ) {
-        if let Some(gossip_verified_block) = self
-            .process_gossip_unverified_block(
-                message_id,
-                peer_id,
-                peer_client,
-                block,
-                reprocess_tx.clone(),
-                seen_duration,
-            )
-            .await
-        {
-            let block_root = gossip_verified_block.block_root;
-
-            if let Some(handle) = duplicate_cache.check_and_insert(block_root) {
-                self.process_gossip_verified_block(
+        // The root span of the block's journey through the import pipeline.
+        let span = info_span!(
+            "gossip_block",
+            slot = %block.slot(),
+            peer_id = %peer_id,
+        );
+        async move {
+            if let Some(gossip_verified_block) = self
+                .process_gossip_unverified_block(
+                    message_id,
                     peer_id,
-                    gossip_verified_block,
-                    reprocess_tx,
-                    invalid_block_storage,
+                    peer_client,
+                    block,
+                    reprocess_tx.clone(),
                     seen_duration,
                 )
-                .await;
-                // Drop the handle to remove the entry from the cache
-                drop(handle);
-            } else {
-                debug!(
-                    self.log,
-                    "RPC block is being imported";
-                    "block_root" => %block_root,
-                );
+                .await
+            {
+                let block_root = gossip_verified_block.block_root;
+
+                if let Some(handle) = duplicate_cache.check_and_insert(block_root) {
+                    self.process_gossip_verified_block(
+                        peer_id,
+                        gossip_verified_block,
+                        reprocess_tx,
+                        invalid_block_storage,
+                        seen_duration,
+                    )
+                    .await;
+                    // Drop the handle to remove the entry from the cache
+                    drop(handle);
+                } else {
+                    debug!(
+                        self.log,
+                        "RPC block is being imported";
+                        "block_root" => %block_root,
+                    );
+                }
             }
         }
+        .instrument(span)
+        .await
     }
 
     /// Process the beacon block received from the gossip network and
@@ -955,6 +983,15 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                 );
                 self.propagate_validation_result(message_id, peer_id, MessageAcceptance::Accept);
 
+                let expected_blobs = verified_block.block.num_expected_blobs();
+                if self.chain.config.preemptive_blob_requests && expected_blobs > 0 {
+                    self.send_sync_message(SyncMessage::PreemptiveBlobRequest {
+                        peer_id,
+                        block_root: verified_block.block_root,
+                        expected_blobs,
+                    });
+                }
+
                 // Log metrics to keep track of propagation delay times.
                 if let Some(duration) = SystemTime::now()
                     .duration_since(UNIX_EPOCH)
@@ -1159,6 +1196,78 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         }
     }
 
+    /// Hold the handle to a gossip block which is waiting for its blobs until it is imported or
+    /// dropped from the availability cache, then continue its import pipeline.
+    ///
+    /// Once imported, work awaiting the block is reprocessed and sync is told that the block is
+    /// imported, whichever path completed it. Once dropped, sync is told so that any lookup for
+    /// the block can fetch it again.
+    fn await_pending_availability(
+        &self,
+        pending: AvailabilityPendingBlock,
+        parent_root: Hash256,
+        reprocess_tx: mpsc::Sender<ReprocessQueueMessage>,
+    ) {
+        let sync_tx = self.sync_tx.clone();
+        let log = self.log.clone();
+        let start_time = Instant::now();
+        self.executor.spawn(
+            async move {
+                let slot = pending.slot();
+                let block_root = pending.block_root();
+                let outcome = pending.resolved().await;
+                let waited = start_time.elapsed();
+
+                let outcome_label = match outcome {
+                    PendingBlockOutcome::Imported => "imported",
+                    PendingBlockOutcome::Dropped => "dropped",
+                };
+                metrics::inc_counter_vec(
+                    &metrics::BEACON_PROCESSOR_GOSSIP_BLOCK_PENDING_AVAILABILITY_TOTAL,
+                    &[outcome_label],
+                );
+                metrics::observe_duration(
+                    &metrics::BEACON_PROCESSOR_GOSSIP_BLOCK_PENDING_AVAILABILITY_SECONDS,
+                    waited,
+                );
+                debug!(
+                    log,
+                    "Gossip block no longer pending availability";
+                    "outcome" => ?outcome,
+                    "slot" => slot,
+                    "block_root" => ?block_root,
+                    "waited_ms" => waited.as_millis(),
+                );
+
+                let imported = outcome == PendingBlockOutcome::Imported;
+                if imported
+                    && reprocess_tx
+                        .try_send(ReprocessQueueMessage::BlockImported {
+                            block_root,
+                            parent_root,
+                        })
+                        .is_err()
+                {
+                    error!(
+                        log,
+                        "Failed to inform block import";
+                        "source" => "gossip_pending_availability",
+                        "block_root" => ?block_root,
+                    )
+                }
+                sync_tx
+                    .send(SyncMessage::GossipBlockProcessResult {
+                        block_root,
+                        imported,
+                    })
+                    .unwrap_or_else(|e| {
+                        debug!(log, "Could not send message to the sync service"; "error" => %e)
+                    });
+            },
+            "gossip_block_pending_availability",
+        );
+    }
+
     /// Process the beacon block that has already passed gossip verification.
     ///
     /// Raises a log if there are errors.
@@ -1219,12 +1328,23 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     processing_start_time.elapsed().as_millis() as i64,
                 );
             }
-            Ok(AvailabilityProcessingStatus::MissingComponents(slot, block_root)) => {
+            Ok(AvailabilityProcessingStatus::MissingComponents(pending)) => {
                 trace!(
                     self.log,
                     "Processed block, waiting for other components";
-                    "slot" => slot,
-                    "block_root" => %block_root,
+                    "slot" => pending.slot(),
+                    "block_root" => %pending.block_root(),
+                );
+                if self.chain.config.pending_availability_timeout_ms > 0 {
+                    self.send_sync_message(SyncMessage::BlockPendingAvailability {
+                        peer_id,
+                        block_root: pending.block_root(),
+                    });
+                }
+                self.await_pending_availability(
+                    pending.clone(),
+                    block.message().parent_root(),
+                    reprocess_tx.clone(),
                 );
             }
             Err(BlockError::ParentUnknown(_)) => {
@@ -1914,6 +2034,9 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                         seen_clock,
                         failed_att.attestation(),
                         &self.chain.spec,
+                        self.chain
+                            .config
+                            .maximum_gossip_clock_disparity(&self.chain.spec),
                     );
 
                 // Only penalize the peer if it would have been invalid at the moment we received
@@ -2465,7 +2588,9 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                         sync_committee_verification::verify_propagation_slot_range(
                             seen_clock,
                             &sync_committee_message_slot,
-                            &self.chain.spec,
+                            self.chain
+                                .config
+                                .maximum_gossip_clock_disparity(&self.chain.spec),
                         );
                     hindsight_verification.is_err()
                 };
@@ -2779,6 +2904,9 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             &self.chain.slot_clock,
             attestation,
             &self.chain.spec,
+            self.chain
+                .config
+                .maximum_gossip_clock_disparity(&self.chain.spec),
         )
         .is_ok();
 
@@ -32,7 +32,7 @@ use std::{collections::HashSet, pin::Pin, sync::Arc, time::Duration};
 use store::HotColdDB;
 use strum::IntoStaticStr;
 use task_executor::ShutdownReason;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 use tokio::time::Sleep;
 use types::{
     ChainSpec, EthSpec, ForkContext, Slot, SubnetId, SyncCommitteeSubscription, SyncSubnetId,
@@ -108,6 +108,10 @@ pub enum NetworkMessage<E: EthSpec> {
         reason: GoodbyeReason,
         source: ReportSource,
     },
+    /// Re-read the gossipsub score overrides file and apply it.
+    ReloadGossipScoringOverrides {
+        result_tx: oneshot::Sender<Result<(), String>>,
+    },
 }
 
 /// Messages triggered by validators that may trigger a subscription to a subnet.
@@ -680,6 +684,14 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                 reason,
                 source,
             } => self.libp2p.goodbye_peer(&peer_id, reason, source),
+            NetworkMessage::ReloadGossipScoringOverrides { result_tx } => {
+                let result = self.libp2p.reload_gossipsub_score_overrides();
+                if let Err(e) = &result {
+                    warn!(self.log, "Failed to reload gossipsub score overrides"; "error" => e);
+                }
+                // Ignore the error if the requester has gone away.
+                let _ = result_tx.send(result);
+            }
             NetworkMessage::SubscribeCoreTopics => {
                 if self.subscribed_core_topics() {
                     return;
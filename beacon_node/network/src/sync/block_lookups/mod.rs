@@ -148,6 +148,13 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
             .collect()
     }
 
+    /// Returns true if there is an active lookup for `block_root`.
+    pub fn has_lookup(&self, block_root: &Hash256) -> bool {
+        self.single_block_lookups
+            .values()
+            .any(|lookup| lookup.block_root() == *block_root)
+    }
+
     /// Returns a vec of all parent lookup chains by tip, in descending slot order (tip first)
     pub(crate) fn active_parent_lookups(&self) -> Vec<NodeChain> {
         compute_parent_chains(
@@ -445,16 +452,17 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
         result: BlockProcessingResult<T::EthSpec>,
         cx: &mut SyncNetworkContext<T>,
     ) {
-        let lookup_result = match process_type {
-            BlockProcessType::SingleBlock { id } => {
-                self.on_processing_result_inner::<BlockRequestState<T::EthSpec>>(id, result, cx)
-            }
-            BlockProcessType::SingleBlob { id } => {
-                self.on_processing_result_inner::<BlobRequestState<T::EthSpec>>(id, result, cx)
-            }
-        };
-        let id = match process_type {
-            BlockProcessType::SingleBlock { id } | BlockProcessType::SingleBlob { id } => id,
+        let (id, lookup_result) = match process_type {
+            BlockProcessType::SingleBlock { id } => (
+                id,
+                self.on_processing_result_inner::<BlockRequestState<T::EthSpec>>(id, result, cx),
+            ),
+            BlockProcessType::SingleBlob { id } => (
+                id,
+                self.on_processing_result_inner::<BlobRequestState<T::EthSpec>>(id, result, cx),
+            ),
+            // Pre-emptive blob requests are made outside of any lookup.
+            BlockProcessType::PreemptiveBlobs { .. } => return,
         };
         self.on_lookup_result(id, lookup_result, "processing_result", cx);
     }
@@ -490,10 +498,7 @@ impl<T: BeaconChainTypes> BlockLookups<T> {
                 Action::Continue
             }
 
-            BlockProcessingResult::Ok(AvailabilityProcessingStatus::MissingComponents(
-                _,
-                _block_root,
-            )) => {
+            BlockProcessingResult::Ok(AvailabilityProcessingStatus::MissingComponents(_)) => {
                 // `on_processing_success` is called here to ensure the request state is updated prior to checking
                 // if both components have been processed.
                 request_state.on_processing_success()?;
@@ -14,13 +14,14 @@ use crate::sync::block_lookups::common::ResponseType;
 use beacon_chain::blob_verification::GossipVerifiedBlob;
 use beacon_chain::block_verification_types::{BlockImportData, RpcBlock};
 use beacon_chain::builder::Witness;
-use beacon_chain::data_availability_checker::Availability;
+use beacon_chain::data_availability_checker::{Availability, AvailabilityPendingBlock};
 use beacon_chain::eth1_chain::CachingEth1Backend;
 use beacon_chain::test_utils::{
     build_log, generate_rand_block_and_blobs, BeaconChainHarness, EphemeralHarnessType, NumBlobs,
 };
 use beacon_chain::{
-    AvailabilityPendingExecutedBlock, PayloadVerificationOutcome, PayloadVerificationStatus,
+    AvailabilityPendingExecutedBlock, BlockProcessStatus, PayloadVerificationOutcome,
+    PayloadVerificationStatus,
 };
 use beacon_processor::WorkEvent;
 use lighthouse_network::rpc::{RPCError, RPCResponseErrorCode};
@@ -477,7 +478,7 @@ impl TestRig {
                 BlockProcessingResult::Ok(AvailabilityProcessingStatus::Imported(block_root))
             } else {
                 BlockProcessingResult::Ok(AvailabilityProcessingStatus::MissingComponents(
-                    block_slot, block_root,
+                    AvailabilityPendingBlock::detached(block_slot, block_root),
                 ))
             },
         );
@@ -501,8 +502,7 @@ impl TestRig {
                 BlockProcessingResult::Ok(AvailabilityProcessingStatus::Imported(block_root))
             } else {
                 BlockProcessingResult::Ok(AvailabilityProcessingStatus::MissingComponents(
-                    Slot::new(0),
-                    block_root,
+                    AvailabilityPendingBlock::detached(Slot::new(0), block_root),
                 ))
             },
         )
@@ -541,6 +541,14 @@ impl TestRig {
         })
     }
 
+    fn single_blob_lookup_failed(&mut self, id: SingleLookupReqId, peer_id: PeerId) {
+        self.send_sync_message(SyncMessage::RpcError {
+            peer_id,
+            request_id: SyncRequestId::SingleBlob { id },
+            error: RPCError::UnsupportedProtocol,
+        })
+    }
+
     /// Return RPCErrors for all active requests of peer
     fn rpc_error_all_active_requests(&mut self, disconnected_peer_id: PeerId) {
         self.drain_network_rx();
@@ -667,6 +675,40 @@ impl TestRig {
             .unwrap_or_else(|e| panic!("Expected blob request for {for_block:?}: {e}"))
     }
 
+    #[track_caller]
+    fn expect_preemptive_blob_request(&mut self, for_block: Hash256) -> (Id, Vec<u64>) {
+        self.pop_received_network_event(|ev| match ev {
+            NetworkMessage::SendRequest {
+                peer_id: _,
+                request: Request::BlobsByRoot(request),
+                request_id: RequestId::Sync(SyncRequestId::PreemptiveBlobs { id }),
+            } if request
+                .blob_ids
+                .to_vec()
+                .iter()
+                .all(|r| r.block_root == for_block) =>
+            {
+                Some((*id, request.blob_ids.iter().map(|r| r.index).collect()))
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|e| panic!("Expected pre-emptive blob request for {for_block:?}: {e}"))
+    }
+
+    fn preemptive_blob_response(
+        &mut self,
+        id: Id,
+        peer_id: PeerId,
+        blob_sidecar: Option<Arc<BlobSidecar<E>>>,
+    ) {
+        self.send_sync_message(SyncMessage::RpcBlob {
+            request_id: SyncRequestId::PreemptiveBlobs { id },
+            peer_id,
+            blob_sidecar,
+            seen_timestamp: D,
+        });
+    }
+
     #[track_caller]
     fn expect_block_parent_request(&mut self, for_block: Hash256) -> SingleLookupReqId {
         self.pop_received_network_event(|ev| match ev {
@@ -807,7 +849,7 @@ impl TestRig {
         let (mut block, mut blobs) = self.rand_block_and_blobs(num_blobs);
         *block.message_mut().parent_root_mut() = parent_root;
         blobs.iter_mut().for_each(|blob| {
-            blob.signed_block_header = block.signed_block_header();
+            *blob.signed_block_header_mut() = block.signed_block_header();
         });
         (block, blobs)
     }
@@ -1491,6 +1533,69 @@ fn block_in_da_checker_skips_download() {
     r.expect_no_active_lookups();
 }
 
+#[test]
+fn block_pending_availability_times_out() {
+    let Some(mut r) = TestRig::test_setup_after_deneb() else {
+        return;
+    };
+    // The pending availability timeouts are kept in a delay queue, which needs a tokio runtime.
+    let handle = r.harness.runtime.task_executor.handle().unwrap();
+    let _guard = handle.enter();
+
+    let (block, _) = r.rand_block_and_blobs(NumBlobs::Number(1));
+    let block_root = block.canonical_root();
+    let peer_id = r.new_connected_peer();
+    let other_peer_id = r.new_connected_peer();
+    r.insert_block_to_da_checker(block.into());
+    for peer_id in [peer_id, other_peer_id, peer_id] {
+        r.send_sync_message(SyncMessage::BlockPendingAvailability {
+            peer_id,
+            block_root,
+        });
+    }
+
+    // The first timeout requests the missing blobs from every peer which sent the block.
+    r.sync_manager.expire_pending_availability(block_root);
+    let mut id = r.expect_blob_lookup_request(block_root);
+    r.assert_lookup_peers(block_root, vec![peer_id, other_peer_id]);
+    r.expect_no_penalty_for(peer_id);
+
+    // The peers aren't penalised while the lookup may still find the blobs.
+    r.sync_manager.expire_pending_availability(block_root);
+    r.expect_no_penalty_for(peer_id);
+    r.expect_no_penalty_for(other_peer_id);
+    r.assert_lookup_is_active(block_root);
+
+    for attempt in 1..=SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS {
+        r.single_blob_lookup_failed(id, peer_id);
+        if attempt < SINGLE_BLOCK_LOOKUP_MAX_ATTEMPTS {
+            id = r.expect_blob_lookup_request(block_root);
+        }
+    }
+    r.expect_no_active_lookups();
+
+    // Once the lookup has failed, the next timeout drops the block and penalises every peer
+    // which sent it.
+    r.sync_manager.expire_pending_availability(block_root);
+    r.expect_single_penalty(peer_id, "block_blobs_unavailable");
+    r.expect_single_penalty(other_peer_id, "block_blobs_unavailable");
+    assert!(matches!(
+        r.harness.chain.get_block_process_status(&block_root),
+        BlockProcessStatus::Unknown
+    ));
+    for peer_id in [peer_id, other_peer_id] {
+        assert_eq!(
+            r.network_globals
+                .peers
+                .read()
+                .peer_info(&peer_id)
+                .unwrap()
+                .blob_withholding_count(),
+            1
+        );
+    }
+}
+
 #[test]
 fn block_in_processing_cache_becomes_invalid() {
     let Some(mut r) = TestRig::test_setup_after_deneb() else {
@@ -1561,6 +1666,57 @@ fn blobs_in_da_checker_skip_download() {
     r.expect_no_active_lookups();
 }
 
+#[test]
+fn preemptive_blob_request_happy_path() {
+    let Some(mut r) = TestRig::test_setup_after_deneb() else {
+        return;
+    };
+    let (block, blobs) = r.rand_block_and_blobs(NumBlobs::Number(2));
+    let block_root = block.canonical_root();
+    let peer_id = r.new_connected_peer();
+    r.send_sync_message(SyncMessage::PreemptiveBlobRequest {
+        peer_id,
+        block_root,
+        expected_blobs: blobs.len(),
+    });
+
+    let (id, indices) = r.expect_preemptive_blob_request(block_root);
+    assert_eq!(indices, vec![0, 1]);
+    for blob in blobs {
+        r.preemptive_blob_response(id, peer_id, Some(blob.into()));
+    }
+    r.expect_block_process(ResponseType::Blob);
+    r.preemptive_blob_response(id, peer_id, None);
+    r.expect_no_penalty_for(peer_id);
+    r.expect_empty_beacon_processor();
+    r.expect_no_active_lookups();
+}
+
+#[test]
+fn preemptive_blob_request_skips_gossip_blobs_and_accepts_partial_response() {
+    let Some(mut r) = TestRig::test_setup_after_deneb() else {
+        return;
+    };
+    let (block, mut blobs) = r.rand_block_and_blobs(NumBlobs::Number(3));
+    let block_root = block.canonical_root();
+    let peer_id = r.new_connected_peer();
+    // The first blob has already arrived over gossip.
+    r.insert_blob_to_da_checker(blobs.remove(0));
+    r.send_sync_message(SyncMessage::PreemptiveBlobRequest {
+        peer_id,
+        block_root,
+        expected_blobs: 3,
+    });
+
+    let (id, indices) = r.expect_preemptive_blob_request(block_root);
+    assert_eq!(indices, vec![1, 2]);
+    // The peer only has one of the requested blobs, which is still processed.
+    r.preemptive_blob_response(id, peer_id, Some(blobs.remove(0).into()));
+    r.preemptive_blob_response(id, peer_id, None);
+    r.expect_block_process(ResponseType::Blob);
+    r.expect_no_penalty_for(peer_id);
+}
+
 mod deneb_only {
     use super::*;
     use beacon_chain::{
@@ -1846,8 +2002,7 @@ mod deneb_only {
             self.rig.single_block_component_processed(
                 self.block_req_id.expect("block request id").lookup_id,
                 BlockProcessingResult::Ok(AvailabilityProcessingStatus::MissingComponents(
-                    self.block.slot(),
-                    self.block_root,
+                    AvailabilityPendingBlock::detached(self.block.slot(), self.block_root),
                 )),
             );
             self.rig.expect_empty_network();
@@ -1900,8 +2055,7 @@ mod deneb_only {
             self.rig.parent_block_processed(
                 self.block_root,
                 BlockProcessingResult::Ok(AvailabilityProcessingStatus::MissingComponents(
-                    Slot::new(0),
-                    parent_root,
+                    AvailabilityPendingBlock::detached(Slot::new(0), parent_root),
                 )),
             );
             self.rig.expect_no_requests_for(parent_root);
@@ -1975,8 +2129,7 @@ mod deneb_only {
             self.rig.single_block_component_processed(
                 self.block_req_id.expect("block request id").lookup_id,
                 BlockProcessingResult::Ok(AvailabilityProcessingStatus::MissingComponents(
-                    self.slot,
-                    self.block_root,
+                    AvailabilityPendingBlock::detached(self.slot, self.block_root),
                 )),
             );
             // Add block to da_checker so blobs request can continue
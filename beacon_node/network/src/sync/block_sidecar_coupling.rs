@@ -2,7 +2,7 @@ use beacon_chain::block_verification_types::RpcBlock;
 use lighthouse_network::PeerId;
 use ssz_types::VariableList;
 use std::{collections::VecDeque, sync::Arc};
-use types::{BlobSidecar, EthSpec, SignedBeaconBlock};
+use types::{BlobSidecar, ChainSpec, EthSpec, SignedBeaconBlock};
 
 use super::range_sync::ByRangeRequestType;
 
@@ -52,7 +52,7 @@ impl<E: EthSpec> BlocksAndBlobsRequestInfo<E> {
         }
     }
 
-    pub fn into_responses(self) -> Result<Vec<RpcBlock<E>>, String> {
+    pub fn into_responses(self, spec: &ChainSpec) -> Result<Vec<RpcBlock<E>>, String> {
         let BlocksAndBlobsRequestInfo {
             accumulated_blocks,
             accumulated_sidecars,
@@ -61,10 +61,11 @@ impl<E: EthSpec> BlocksAndBlobsRequestInfo<E> {
 
         // There can't be more more blobs than blocks. i.e. sending any blob (empty
         // included) for a skipped slot is not permitted.
+        let max_blobs_per_block = spec.max_blobs_per_block::<E>() as usize;
         let mut responses = Vec::with_capacity(accumulated_blocks.len());
         let mut blob_iter = accumulated_sidecars.into_iter().peekable();
         for block in accumulated_blocks.into_iter() {
-            let mut blob_list = Vec::with_capacity(E::max_blobs_per_block());
+            let mut blob_list = Vec::with_capacity(max_blobs_per_block);
             while {
                 let pair_next_blob = blob_iter
                     .peek()
@@ -75,7 +76,7 @@ impl<E: EthSpec> BlocksAndBlobsRequestInfo<E> {
                 blob_list.push(blob_iter.next().ok_or("Missing next blob".to_string())?);
             }
 
-            let mut blobs_buffer = vec![None; E::max_blobs_per_block()];
+            let mut blobs_buffer = vec![None; max_blobs_per_block];
             for blob in blob_list {
                 let blob_index = blob.index as usize;
                 let Some(blob_opt) = blobs_buffer.get_mut(blob_index) else {
@@ -115,7 +116,7 @@ mod tests {
     use beacon_chain::test_utils::{generate_rand_block_and_blobs, NumBlobs};
     use lighthouse_network::PeerId;
     use rand::SeedableRng;
-    use types::{test_utils::XorShiftRng, ForkName, MinimalEthSpec as E};
+    use types::{test_utils::XorShiftRng, EthSpec, ForkName, MinimalEthSpec as E};
 
     #[test]
     fn no_blobs_into_responses() {
@@ -134,7 +135,7 @@ mod tests {
 
         // Assert response is finished and RpcBlocks can be constructed
         assert!(info.is_finished());
-        info.into_responses().unwrap();
+        info.into_responses(&E::default_spec()).unwrap();
     }
 
     #[test]
@@ -162,6 +163,32 @@ mod tests {
         // This makes sure we don't expect blobs here when they have expired. Checking this logic should
         // be hendled elsewhere.
         assert!(info.is_finished());
-        info.into_responses().unwrap();
+        info.into_responses(&E::default_spec()).unwrap();
+    }
+
+    #[test]
+    fn blob_index_beyond_spec_limit_into_responses() {
+        let peer_id = PeerId::random();
+        let mut info =
+            BlocksAndBlobsRequestInfo::<E>::new(ByRangeRequestType::BlocksAndBlobs, peer_id);
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let (block, blobs) =
+            generate_rand_block_and_blobs::<E>(ForkName::Deneb, NumBlobs::Number(3), &mut rng);
+
+        info.add_block_response(Some(block.into()));
+        info.add_block_response(None);
+        for blob in blobs {
+            info.add_sidecar_response(Some(blob.into()));
+        }
+        info.add_sidecar_response(None);
+
+        // The third blob's index is within the preset's bound but beyond the configured one.
+        let mut spec = E::default_spec();
+        spec.max_blobs_per_block = 2;
+        assert!(info.is_finished());
+        assert_eq!(
+            info.into_responses(&spec).unwrap_err(),
+            "Invalid blob index"
+        );
     }
 }
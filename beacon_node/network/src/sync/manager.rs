@@ -38,6 +38,7 @@ use super::block_lookups::BlockLookups;
 use super::network_context::{BlockOrBlob, RangeRequestId, RpcEvent, SyncNetworkContext};
 use super::peer_sync_info::{remote_sync_type, PeerSyncType};
 use super::range_sync::{RangeSync, RangeSyncType, EPOCHS_PER_BATCH};
+use crate::metrics;
 use crate::network_beacon_processor::{ChainSegmentProcessId, NetworkBeaconProcessor};
 use crate::service::NetworkMessage;
 use crate::status::ToStatusMessage;
@@ -49,18 +50,21 @@ use beacon_chain::block_verification_types::AsBlock;
 use beacon_chain::block_verification_types::RpcBlock;
 use beacon_chain::validator_monitor::timestamp_now;
 use beacon_chain::{
-    AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes, BlockError, EngineState,
+    AvailabilityProcessingStatus, BeaconChain, BeaconChainTypes, BlockError, BlockProcessStatus,
+    EngineState,
 };
+use delay_map::HashMapDelay;
 use futures::StreamExt;
 use lighthouse_network::rpc::RPCError;
 use lighthouse_network::types::{NetworkGlobals, SyncState};
 use lighthouse_network::SyncInfo;
 use lighthouse_network::{PeerAction, PeerId};
 use lru_cache::LRUTimeCache;
+use rand::seq::IteratorRandom;
 use slog::{crit, debug, error, info, o, trace, warn, Logger};
 use std::ops::Sub;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use types::{BlobSidecar, EthSpec, Hash256, SignedBeaconBlock, Slot};
 
@@ -78,6 +82,26 @@ pub const SLOT_IMPORT_TOLERANCE: usize = 32;
 /// arbitrary number that covers a full slot, but allows recovery if sync get stuck for a few slots.
 const NOTIFIED_UNKNOWN_ROOT_EXPIRY_SECONDS: u64 = 30;
 
+/// The number of peers, including the peer which sent us the block, that the blobs of a gossip
+/// block are pre-emptively requested from. Each peer is asked for every missing blob, so this
+/// bounds the bandwidth spent on blobs which also arrive over gossip.
+const PREEMPTIVE_BLOB_REQUEST_PEERS: usize = 2;
+
+/// The number of blocks with unavailable blobs a peer may send before it is penalised as a
+/// suspected blob withholder, rather than as a peer which was merely unlucky.
+const BLOB_WITHHOLDING_THRESHOLD: u64 = 3;
+
+/// A block received over gossip which is waiting for its blobs.
+#[derive(Debug, Clone)]
+struct PendingAvailability {
+    /// The peers which sent us the block.
+    peers: Vec<PeerId>,
+    /// Whether the blobs of the block have been requested from peers.
+    blobs_requested: bool,
+    /// When the entry expires from `SyncManager::pending_availability`.
+    deadline: Instant,
+}
+
 pub type Id = u32;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
@@ -95,6 +119,8 @@ pub enum RequestId {
     SingleBlob { id: SingleLookupReqId },
     /// Range request that is composed by both a block range request and a blob range request.
     RangeBlockAndBlobs { id: Id },
+    /// Request for the blobs of a block received over gossip, made before they are needed.
+    PreemptiveBlobs { id: Id },
 }
 
 #[derive(Debug)]
@@ -129,6 +155,20 @@ pub enum SyncMessage<E: EthSpec> {
     /// manager to attempt to find the block matching the unknown hash.
     UnknownBlockHashFromAttestation(PeerId, Hash256),
 
+    /// A block which commits to blobs has passed gossip verification. Its blobs are requested from
+    /// peers rather than only waiting for them to arrive over gossip.
+    PreemptiveBlobRequest {
+        peer_id: PeerId,
+        block_root: Hash256,
+        expected_blobs: usize,
+    },
+
+    /// A block received over gossip has been verified, but is waiting for its blobs.
+    BlockPendingAvailability {
+        peer_id: PeerId,
+        block_root: Hash256,
+    },
+
     /// A peer has disconnected.
     Disconnect(PeerId),
 
@@ -160,6 +200,7 @@ pub enum SyncMessage<E: EthSpec> {
 pub enum BlockProcessType {
     SingleBlock { id: Id },
     SingleBlob { id: Id },
+    PreemptiveBlobs { id: Id },
 }
 
 #[derive(Debug)]
@@ -210,6 +251,10 @@ pub struct SyncManager<T: BeaconChainTypes> {
     /// one event is useful, the rest generating log noise and wasted cycles
     notified_unknown_roots: LRUTimeCache<(PeerId, Hash256)>,
 
+    /// Gossip blocks which are waiting for their blobs, expiring after
+    /// `pending_availability_timeout_ms`.
+    pending_availability: HashMapDelay<Hash256, PendingAvailability>,
+
     /// The logger for the import manager.
     log: Logger,
 }
@@ -275,6 +320,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             notified_unknown_roots: LRUTimeCache::new(Duration::from_secs(
                 NOTIFIED_UNKNOWN_ROOT_EXPIRY_SECONDS,
             )),
+            pending_availability: HashMapDelay::new(Duration::from_millis(
+                beacon_chain.config.pending_availability_timeout_ms,
+            )),
             log: log.clone(),
         }
     }
@@ -303,6 +351,13 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         self.block_lookups.insert_failed_chain(block_root);
     }
 
+    #[cfg(test)]
+    pub(crate) fn expire_pending_availability(&mut self, block_root: Hash256) {
+        if let Some(pending) = self.pending_availability.remove(&block_root) {
+            self.on_pending_availability_timeout(block_root, pending);
+        }
+    }
+
     fn network_globals(&self) -> &NetworkGlobals<T::EthSpec> {
         self.network.network_globals()
     }
@@ -351,6 +406,10 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             RequestId::SingleBlob { id } => {
                 self.on_single_blob_response(id, peer_id, RpcEvent::RPCError(error))
             }
+            RequestId::PreemptiveBlobs { id } => {
+                self.network
+                    .on_preemptive_blobs_response(id, peer_id, RpcEvent::RPCError(error))
+            }
             RequestId::RangeBlockAndBlobs { id } => {
                 if let Some(sender_id) = self.network.range_request_failed(id) {
                     match sender_id {
@@ -600,6 +659,9 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                 _ = prune_lookups_interval.tick() => {
                     self.block_lookups.prune_lookups();
                 }
+                Some(Ok((block_root, pending))) = self.pending_availability.next() => {
+                    self.on_pending_availability_timeout(block_root, pending);
+                }
             }
         }
     }
@@ -665,6 +727,43 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     self.handle_unknown_block_root(peer_id, block_root);
                 }
             }
+            SyncMessage::PreemptiveBlobRequest {
+                peer_id,
+                block_root,
+                expected_blobs,
+            } => {
+                // The peer which sent us the block should also have its blobs. The other peers are
+                // chosen at random from those which are synced.
+                let other_peers = self
+                    .network_globals()
+                    .peers
+                    .read()
+                    .synced_peers()
+                    .filter(|synced_peer| **synced_peer != peer_id)
+                    .copied()
+                    .choose_multiple(&mut rand::thread_rng(), PREEMPTIVE_BLOB_REQUEST_PEERS - 1);
+                let peers = std::iter::once(peer_id)
+                    .chain(other_peers)
+                    .collect::<Vec<_>>();
+                self.network
+                    .preemptive_blobs_request(&peers, block_root, expected_blobs);
+            }
+            SyncMessage::BlockPendingAvailability {
+                peer_id,
+                block_root,
+            } => match self.pending_availability.get(&block_root) {
+                Some(pending) => {
+                    // Record every peer which sent us the block, without extending its deadline.
+                    if !pending.peers.contains(&peer_id) {
+                        let mut pending = pending.clone();
+                        pending.peers.push(peer_id);
+                        let remaining = pending.deadline.saturating_duration_since(Instant::now());
+                        self.pending_availability
+                            .insert_at(block_root, pending, remaining);
+                    }
+                }
+                None => self.track_pending_availability(block_root, vec![peer_id], false),
+            },
             SyncMessage::Disconnect(peer_id) => {
                 debug!(self.log, "Received disconnected message"; "peer_id" => %peer_id);
                 self.peer_disconnect(&peer_id);
@@ -740,6 +839,95 @@ impl<T: BeaconChainTypes> SyncManager<T> {
         }
     }
 
+    /// Wait `pending_availability_timeout_ms` for the blobs of the gossip block `block_root`.
+    fn track_pending_availability(
+        &mut self,
+        block_root: Hash256,
+        peers: Vec<PeerId>,
+        blobs_requested: bool,
+    ) {
+        let timeout = Duration::from_millis(self.chain.config.pending_availability_timeout_ms);
+        self.pending_availability.insert_at(
+            block_root,
+            PendingAvailability {
+                peers,
+                blobs_requested,
+                deadline: Instant::now() + timeout,
+            },
+            timeout,
+        );
+    }
+
+    /// A gossip block has been waiting for its blobs for `pending_availability_timeout_ms`.
+    ///
+    /// The first time, its blobs are requested from the peers which sent it. Once that lookup has
+    /// failed, the block is dropped from the availability cache and those peers are penalized.
+    fn on_pending_availability_timeout(
+        &mut self,
+        block_root: Hash256,
+        pending: PendingAvailability,
+    ) {
+        // The block has been imported, or dropped from the availability cache, in the meantime.
+        if !matches!(
+            self.chain.get_block_process_status(&block_root),
+            BlockProcessStatus::ExecutionValidated(_)
+        ) {
+            return;
+        }
+
+        if !pending.blobs_requested {
+            debug!(
+                self.log,
+                "Requesting blobs of block pending availability";
+                "block_root" => ?block_root,
+                "peers" => ?pending.peers,
+            );
+            metrics::inc_counter_vec(&metrics::SYNC_PENDING_AVAILABILITY_TIMEOUTS, &["request"]);
+            for peer_id in &pending.peers {
+                self.handle_unknown_block_root(*peer_id, block_root);
+            }
+            self.track_pending_availability(block_root, pending.peers, true);
+        } else if self.block_lookups.has_lookup(&block_root) {
+            // The lookup may still find the blobs, so only penalise the peers once it has failed.
+            debug!(
+                self.log,
+                "Waiting for blob lookup of block pending availability";
+                "block_root" => ?block_root,
+            );
+            self.track_pending_availability(block_root, pending.peers, true);
+        } else {
+            metrics::inc_counter_vec(&metrics::SYNC_PENDING_AVAILABILITY_TIMEOUTS, &["drop"]);
+            for peer_id in pending.peers {
+                let withheld_blocks = self
+                    .network_globals()
+                    .peers
+                    .write()
+                    .record_blob_withholding(&peer_id)
+                    .unwrap_or(0);
+                warn!(
+                    self.log,
+                    "Dropping block with unavailable blobs";
+                    "block_root" => ?block_root,
+                    "peer_id" => %peer_id,
+                    "withheld_blocks" => withheld_blocks,
+                );
+
+                // Blobs can go missing through no fault of the peer, so only penalise it heavily
+                // once it has repeatedly sent us blocks without their blobs.
+                let (action, msg) = if withheld_blocks >= BLOB_WITHHOLDING_THRESHOLD {
+                    (PeerAction::LowToleranceError, "suspected_blob_withholding")
+                } else {
+                    (PeerAction::MidToleranceError, "block_blobs_unavailable")
+                };
+                metrics::inc_counter_vec(&metrics::SYNC_BLOB_WITHHOLDING_PENALTIES, &[msg]);
+                self.network.report_peer(peer_id, action, msg);
+            }
+            self.chain
+                .data_availability_checker
+                .remove_pending_components(block_root);
+        }
+    }
+
     fn handle_unknown_block_root(&mut self, peer_id: PeerId, block_root: Hash256) {
         match self.should_search_for_block(None, &peer_id) {
             Ok(_) => {
@@ -849,7 +1037,7 @@ impl<T: BeaconChainTypes> SyncManager<T> {
                     None => RpcEvent::StreamTermination,
                 },
             ),
-            RequestId::SingleBlob { .. } => {
+            RequestId::SingleBlob { .. } | RequestId::PreemptiveBlobs { .. } => {
                 crit!(self.log, "Block received during blob request"; "peer_id" => %peer_id  );
             }
             RequestId::RangeBlockAndBlobs { id } => {
@@ -897,6 +1085,14 @@ impl<T: BeaconChainTypes> SyncManager<T> {
             RequestId::RangeBlockAndBlobs { id } => {
                 self.range_block_and_blobs_response(id, peer_id, blob.into())
             }
+            RequestId::PreemptiveBlobs { id } => self.network.on_preemptive_blobs_response(
+                id,
+                peer_id,
+                match blob {
+                    Some(blob) => RpcEvent::Response(blob, seen_timestamp),
+                    None => RpcEvent::StreamTermination,
+                },
+            ),
         }
     }
 
@@ -6,12 +6,14 @@ pub use self::requests::{BlobsByRootSingleBlockRequest, BlocksByRootSingleReques
 use super::block_sidecar_coupling::BlocksAndBlobsRequestInfo;
 use super::manager::{Id, RequestId as SyncRequestId};
 use super::range_sync::{BatchId, ByRangeRequestType, ChainId};
+use crate::metrics;
 use crate::network_beacon_processor::NetworkBeaconProcessor;
 use crate::service::{NetworkMessage, RequestId};
 use crate::status::ToStatusMessage;
 use crate::sync::block_lookups::SingleLookupId;
 use crate::sync::manager::{BlockProcessType, SingleLookupReqId};
 use beacon_chain::block_verification_types::RpcBlock;
+use beacon_chain::validator_monitor::timestamp_now;
 use beacon_chain::{BeaconChain, BeaconChainTypes, BlockProcessStatus, EngineState};
 use fnv::FnvHashMap;
 use lighthouse_network::rpc::methods::BlobsByRangeRequest;
@@ -122,6 +124,9 @@ pub struct SyncNetworkContext<T: BeaconChainTypes> {
     /// A mapping of active BlobsByRoot requests, including both current slot and parent lookups.
     blobs_by_root_requests: FnvHashMap<SingleLookupReqId, ActiveBlobsByRootRequest<T::EthSpec>>,
 
+    /// A mapping of active BlobsByRoot requests made pre-emptively for blocks received over gossip.
+    preemptive_blobs_requests: FnvHashMap<Id, ActiveBlobsByRootRequest<T::EthSpec>>,
+
     /// BlocksByRange requests paired with BlobsByRange
     range_blocks_and_blobs_requests:
         FnvHashMap<Id, (RangeRequestId, BlocksAndBlobsRequestInfo<T::EthSpec>)>,
@@ -170,6 +175,7 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
             request_id: 1,
             blocks_by_root_requests: <_>::default(),
             blobs_by_root_requests: <_>::default(),
+            preemptive_blobs_requests: <_>::default(),
             range_blocks_and_blobs_requests: FnvHashMap::default(),
             network_beacon_processor,
             chain,
@@ -211,9 +217,21 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
                 }
             });
 
+        let failed_preemptive_blob_ids =
+            self.preemptive_blobs_requests
+                .iter()
+                .filter_map(|(id, request)| {
+                    if request.peer_id == *peer_id {
+                        Some(SyncRequestId::PreemptiveBlobs { id: *id })
+                    } else {
+                        None
+                    }
+                });
+
         failed_range_ids
             .chain(failed_block_ids)
             .chain(failed_blob_ids)
+            .chain(failed_preemptive_blob_ids)
             .collect()
     }
 
@@ -362,7 +380,7 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
                     Some(BlocksAndBlobsByRangeResponse {
                         sender_id,
                         request_type,
-                        responses: info.into_responses(),
+                        responses: info.into_responses(&self.chain.spec),
                     })
                 } else {
                     None
@@ -520,6 +538,166 @@ impl<T: BeaconChainTypes> SyncNetworkContext<T> {
         Ok(LookupRequestResult::RequestSent(req_id))
     }
 
+    /// Request the blobs of a block received over gossip from each of `peers`, rather than only
+    /// waiting for them to arrive over gossip. Only the blobs not yet received are requested.
+    pub fn preemptive_blobs_request(
+        &mut self,
+        peers: &[PeerId],
+        block_root: Hash256,
+        expected_blobs: usize,
+    ) {
+        let imported_blob_indexes = self
+            .chain
+            .data_availability_checker
+            .imported_blob_indexes(&block_root)
+            .unwrap_or_default();
+        let indices = (0..expected_blobs as u64)
+            .filter(|index| !imported_blob_indexes.contains(index))
+            .collect::<Vec<_>>();
+        if indices.is_empty() {
+            return;
+        }
+
+        for peer_id in peers {
+            let id = self.next_id();
+            debug!(
+                self.log,
+                "Sending pre-emptive BlobsByRoot Request";
+                "method" => "BlobsByRoot",
+                "block_root" => ?block_root,
+                "blob_indices" => ?indices,
+                "peer" => %peer_id,
+                "id" => id
+            );
+
+            let request = BlobsByRootSingleBlockRequest {
+                block_root,
+                indices: indices.clone(),
+            };
+            if self
+                .send_network_msg(NetworkMessage::SendRequest {
+                    peer_id: *peer_id,
+                    request: Request::BlobsByRoot(request.clone().into_request(&self.chain.spec)),
+                    request_id: RequestId::Sync(SyncRequestId::PreemptiveBlobs { id }),
+                })
+                .is_err()
+            {
+                return;
+            }
+            metrics::inc_counter(&metrics::SYNC_PREEMPTIVE_BLOB_REQUESTS);
+
+            self.preemptive_blobs_requests
+                .insert(id, ActiveBlobsByRootRequest::new(request, *peer_id));
+        }
+    }
+
+    /// Handle a response to a pre-emptive blobs request, sending the blobs for processing once the
+    /// peer has returned all of those requested, or terminated the stream.
+    ///
+    /// The requests for a block are cancelled once it has been imported, after which any further
+    /// responses are ignored.
+    pub fn on_preemptive_blobs_response(
+        &mut self,
+        id: Id,
+        peer_id: PeerId,
+        blob: RpcEvent<Arc<BlobSidecar<T::EthSpec>>>,
+    ) {
+        let Entry::Occupied(mut request) = self.preemptive_blobs_requests.entry(id) else {
+            return;
+        };
+        let block_root = request.get().block_root();
+
+        let (blobs, seen_timestamp) = match blob {
+            RpcEvent::Response(blob, seen_timestamp) => {
+                match request.get_mut().add_response(blob) {
+                    Ok(Some(blobs)) => {
+                        request.remove();
+                        (blobs, seen_timestamp)
+                    }
+                    Ok(None) => return,
+                    Err(e) => {
+                        request.remove();
+                        self.report_peer(peer_id, PeerAction::LowToleranceError, e.into());
+                        return;
+                    }
+                }
+            }
+            // The peer is not penalised for returning fewer blobs than requested, since it may
+            // not have received them all yet.
+            RpcEvent::StreamTermination => {
+                (request.remove().into_received_blobs(), timestamp_now())
+            }
+            RpcEvent::RPCError(e) => {
+                request.remove();
+                debug!(
+                    self.log,
+                    "Pre-emptive blobs request failed";
+                    "block_root" => ?block_root,
+                    "peer" => %peer_id,
+                    "error" => ?e
+                );
+                return;
+            }
+        };
+
+        if self.chain.block_is_known_to_fork_choice(&block_root) {
+            self.preemptive_blobs_requests
+                .retain(|_, request| request.block_root() != block_root);
+            metrics::inc_counter_vec_by(
+                &metrics::SYNC_PREEMPTIVE_BLOBS_RECEIVED,
+                &["redundant"],
+                blobs.len() as u64,
+            );
+            return;
+        }
+
+        // Drop any blobs which have arrived over gossip, or from another peer, in the meantime.
+        let imported_blob_indexes = self
+            .chain
+            .data_availability_checker
+            .imported_blob_indexes(&block_root)
+            .unwrap_or_default();
+        let (redundant, blobs): (Vec<_>, Vec<_>) = blobs
+            .into_iter()
+            .partition(|blob| imported_blob_indexes.contains(&blob.index));
+        metrics::inc_counter_vec_by(
+            &metrics::SYNC_PREEMPTIVE_BLOBS_RECEIVED,
+            &["redundant"],
+            redundant.len() as u64,
+        );
+        metrics::inc_counter_vec_by(
+            &metrics::SYNC_PREEMPTIVE_BLOBS_RECEIVED,
+            &["new"],
+            blobs.len() as u64,
+        );
+        if blobs.is_empty() {
+            return;
+        }
+
+        let blobs = match to_fixed_blob_sidecar_list(blobs) {
+            Ok(blobs) => blobs,
+            Err(e) => {
+                self.report_peer(peer_id, PeerAction::LowToleranceError, e.into());
+                return;
+            }
+        };
+        if let Some(beacon_processor) = self.beacon_processor_if_enabled() {
+            debug!(self.log, "Sending pre-emptive blobs for processing"; "block" => ?block_root, "id" => id);
+            if let Err(e) = beacon_processor.send_rpc_blobs(
+                block_root,
+                blobs,
+                seen_timestamp,
+                BlockProcessType::PreemptiveBlobs { id },
+            ) {
+                error!(
+                    self.log,
+                    "Failed to send pre-emptive blobs to processor";
+                    "error" => ?e
+                );
+            }
+        }
+    }
+
     pub fn is_execution_engine_online(&self) -> bool {
         self.execution_engine_state == EngineState::Online
     }
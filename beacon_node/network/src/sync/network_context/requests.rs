@@ -158,6 +158,15 @@ impl<E: EthSpec> ActiveBlobsByRootRequest<E> {
         }
     }
 
+    pub fn block_root(&self) -> Hash256 {
+        self.request.block_root
+    }
+
+    /// The blobs received which have not yet been returned by `add_response`.
+    pub fn into_received_blobs(self) -> Vec<Arc<BlobSidecar<E>>> {
+        self.blobs
+    }
+
     /// Mark request as resolved (= has returned something downstream) while marking this status as
     /// true for future calls.
     pub fn resolve(&mut self) -> bool {
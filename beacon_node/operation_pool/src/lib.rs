@@ -25,7 +25,7 @@ use crate::attestation_storage::{AttestationMap, CheckpointKey};
 use crate::bls_to_execution_changes::BlsToExecutionChanges;
 use crate::sync_aggregate_id::SyncAggregateId;
 use attester_slashing::AttesterSlashingMaxCover;
-use max_cover::maximum_cover;
+use max_cover::{maximum_cover, maximum_cover_with_budget};
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rand::seq::SliceRandom;
 use rand::thread_rng;
@@ -37,6 +37,7 @@ use state_processing::{SigVerifiedOp, VerifyOperation};
 use std::collections::{hash_map::Entry, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
+use std::time::Duration;
 use types::{
     sync_aggregate::Error as SyncAggregateError, typenum::Unsigned, AbstractExecPayload,
     Attestation, AttestationData, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
@@ -199,6 +200,8 @@ impl<E: EthSpec> OperationPool<E> {
     /// ## Note
     ///
     /// This function assumes the given `attestation` is valid.
+    // TODO(electra): aggregate Electra attestations per committee, and pack them into blocks with
+    // `AttestationElectra::from_committee_aggregates`.
     pub fn insert_attestation(
         &self,
         attestation: Attestation<E>,
@@ -249,11 +252,14 @@ impl<E: EthSpec> OperationPool<E> {
     /// before an approximately optimal bundle is constructed. We use it to provide access
     /// to the fork choice data from the `BeaconChain` struct that doesn't logically belong
     /// in the operation pool.
+    ///
+    /// Up to `time_budget` is spent improving on the greedy packing of each epoch's attestations.
     pub fn get_attestations(
         &self,
         state: &BeaconState<E>,
         prev_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, E>) -> bool + Send,
         curr_epoch_validity_filter: impl for<'a> FnMut(&AttestationRef<'a, E>) -> bool + Send,
+        time_budget: Duration,
         spec: &ChainSpec,
     ) -> Result<Vec<Attestation<E>>, OpPoolError> {
         if !matches!(state, BeaconState::Base(_)) {
@@ -324,15 +330,21 @@ impl<E: EthSpec> OperationPool<E> {
                 if prev_epoch_key == curr_epoch_key {
                     vec![]
                 } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
+                    maximum_cover_with_budget(
+                        prev_epoch_att,
+                        prev_epoch_limit,
+                        "prev_epoch_attestations",
+                        time_budget,
+                    )
                 }
             },
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(
+                maximum_cover_with_budget(
                     curr_epoch_att,
                     E::MaxAttestations::to_usize(),
                     "curr_epoch_attestations",
+                    time_budget,
                 )
             },
         );
@@ -991,7 +1003,7 @@ mod release_tests {
         // Before the min attestation inclusion delay, get_attestations shouldn't return anything.
         assert_eq!(
             op_pool
-                .get_attestations(&state, |_| true, |_| true, spec)
+                .get_attestations(&state, |_| true, |_| true, Duration::ZERO, spec)
                 .expect("should have attestations")
                 .len(),
             0
@@ -1001,7 +1013,7 @@ mod release_tests {
         *state.slot_mut() += spec.min_attestation_inclusion_delay;
 
         let block_attestations = op_pool
-            .get_attestations(&state, |_| true, |_| true, spec)
+            .get_attestations(&state, |_| true, |_| true, Duration::ZERO, spec)
             .expect("Should have block attestations");
         assert_eq!(block_attestations.len(), committees.len());
 
@@ -1237,7 +1249,7 @@ mod release_tests {
 
         *state.slot_mut() += spec.min_attestation_inclusion_delay;
         let best_attestations = op_pool
-            .get_attestations(&state, |_| true, |_| true, spec)
+            .get_attestations(&state, |_| true, |_| true, Duration::ZERO, spec)
             .expect("should have best attestations");
         assert_eq!(best_attestations.len(), max_attestations);
 
@@ -1334,7 +1346,7 @@ mod release_tests {
 
         *state.slot_mut() += spec.min_attestation_inclusion_delay;
         let best_attestations = op_pool
-            .get_attestations(&state, |_| true, |_| true, spec)
+            .get_attestations(&state, |_| true, |_| true, Duration::ZERO, spec)
             .expect("should have valid best attestations");
         assert_eq!(best_attestations.len(), max_attestations);
 
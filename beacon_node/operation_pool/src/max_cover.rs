@@ -1,5 +1,6 @@
 use crate::metrics;
 use itertools::Itertools;
+use std::time::{Duration, Instant};
 
 /// Trait for types that we can compute a maximum cover for.
 ///
@@ -32,6 +33,7 @@ pub trait MaxCover: Clone {
 
 /// Helper struct to track which items of the input are still available for inclusion.
 /// Saves removing elements from the work vector.
+#[derive(Clone)]
 struct MaxCoverItem<T> {
     item: T,
     available: bool,
@@ -55,8 +57,83 @@ where
     I: IntoIterator<Item = T>,
     T: MaxCover,
 {
-    // Construct an initial vec of all items, marked available.
-    let mut all_items: Vec<_> = items_iter
+    greedy_cover(non_zero_items(items_iter, label), limit, None)
+}
+
+/// Compute an approximate maximum cover, spending up to `time_budget` searching for a better
+/// solution than the greedy algorithm finds.
+///
+/// The greedy solution is computed first. Then, starting with the highest scoring items, the
+/// greedy algorithm is re-run with each item forced to be the first in the solution, keeping the
+/// solution with the highest total score. This corrects the common failure of the greedy algorithm
+/// where one large item overlapping several others is chosen first.
+///
+/// The budget is checked between each attempt, so a single attempt may overrun it. A zero budget
+/// yields the greedy solution.
+pub fn maximum_cover_with_budget<I, T>(
+    items_iter: I,
+    limit: usize,
+    label: &str,
+    time_budget: Duration,
+) -> Vec<T>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover,
+{
+    let deadline = Instant::now() + time_budget;
+    let all_items = non_zero_items(items_iter, label);
+
+    let mut best = greedy_cover(all_items.clone(), limit, None);
+    let greedy_score = total_score(&best);
+    let mut best_score = greedy_score;
+
+    let mut candidates = (0..all_items.len()).collect::<Vec<_>>();
+    candidates.sort_by_key(|&i| std::cmp::Reverse(all_items[i].item.score()));
+
+    let mut attempts = 0;
+    for first in candidates {
+        if limit == 0 || Instant::now() >= deadline {
+            break;
+        }
+        attempts += 1;
+        let solution = greedy_cover(all_items.clone(), limit, Some(first));
+        let score = total_score(&solution);
+        if score > best_score {
+            best = solution;
+            best_score = score;
+        }
+    }
+
+    metrics::set_int_gauge(&metrics::MAX_COVER_ATTEMPTS, &[label], attempts);
+    metrics::set_int_gauge(
+        &metrics::MAX_COVER_GREEDY_SCORE,
+        &[label],
+        greedy_score as i64,
+    );
+    metrics::set_int_gauge(
+        &metrics::MAX_COVER_OPTIMISED_SCORE,
+        &[label],
+        best_score as i64,
+    );
+    if best_score > greedy_score {
+        metrics::inc_counter_vec(&metrics::MAX_COVER_IMPROVEMENTS, &[label]);
+    }
+
+    best
+}
+
+/// The total score of a solution, where each item's score is the score it had when it was added.
+fn total_score<T: MaxCover>(solution: &[T]) -> usize {
+    solution.iter().map(MaxCover::score).sum()
+}
+
+/// Construct an initial vec of all items with a non-zero score, marked available.
+fn non_zero_items<I, T>(items_iter: I, label: &str) -> Vec<MaxCoverItem<T>>
+where
+    I: IntoIterator<Item = T>,
+    T: MaxCover,
+{
+    let all_items: Vec<_> = items_iter
         .into_iter()
         .map(MaxCoverItem::new)
         .filter(|x| x.item.score() != 0)
@@ -68,21 +145,35 @@ where
         all_items.len() as i64,
     );
 
+    all_items
+}
+
+/// Run the greedy algorithm, optionally forcing the item at index `first` to be chosen first.
+fn greedy_cover<T: MaxCover>(
+    mut all_items: Vec<MaxCoverItem<T>>,
+    limit: usize,
+    mut first: Option<usize>,
+) -> Vec<T> {
     let mut result = vec![];
 
     for _ in 0..limit {
         // Select the item with the maximum score.
-        let best = match all_items
-            .iter_mut()
-            .filter(|x| x.available && x.item.score() != 0)
-            .max_by_key(|x| x.item.score())
-        {
-            Some(x) => {
-                x.available = false;
-                x.item.clone()
-            }
+        let best_index = match first.take().or_else(|| {
+            all_items
+                .iter()
+                .enumerate()
+                .filter(|(_, x)| x.available && x.item.score() != 0)
+                .max_by_key(|(_, x)| x.item.score())
+                .map(|(i, _)| i)
+        }) {
+            Some(i) => i,
             None => return result,
         };
+        let best = {
+            let x = &mut all_items[best_index];
+            x.available = false;
+            x.item.clone()
+        };
 
         // Update the covering sets of the other items, for the inclusion of the selected item.
         // Items covered by the selected item can't be re-covered.
@@ -207,6 +298,25 @@ mod test {
         assert_eq!(quality(&cover), 11);
     }
 
+    // With a time budget the first item is varied, which finds the optimal solution.
+    #[test]
+    fn suboptimal_with_budget() {
+        let sets = vec![
+            HashSet::from_iter(vec![0, 1, 8, 11, 14]),
+            HashSet::from_iter(vec![2, 3, 7, 9, 10]),
+            HashSet::from_iter(vec![4, 5, 6, 12, 13]),
+            HashSet::from_iter(vec![9, 10]),
+            HashSet::from_iter(vec![5, 6, 7, 8]),
+            HashSet::from_iter(vec![0, 1, 2, 3, 4]),
+        ];
+        let cover = maximum_cover_with_budget(sets.clone(), 3, "test", Duration::from_secs(60));
+        assert_eq!(quality(&cover), 15);
+
+        // A zero budget gives the greedy solution.
+        let cover = maximum_cover_with_budget(sets, 3, "test", Duration::ZERO);
+        assert_eq!(quality(&cover), 11);
+    }
+
     #[test]
     fn intersecting_ok() {
         let sets = vec![
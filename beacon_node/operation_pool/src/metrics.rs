@@ -28,4 +28,24 @@ lazy_static! {
         "Number of non-trivial items considered in a max coverage optimisation",
         &["label"]
     );
+    pub static ref MAX_COVER_ATTEMPTS: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "op_pool_max_cover_attempts",
+        "Number of alternative solutions tried within the time budget of a max coverage optimisation",
+        &["label"]
+    );
+    pub static ref MAX_COVER_GREEDY_SCORE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "op_pool_max_cover_greedy_score",
+        "Total score of the greedy solution to a max coverage optimisation (gwei for attestations)",
+        &["label"]
+    );
+    pub static ref MAX_COVER_OPTIMISED_SCORE: Result<IntGaugeVec> = try_create_int_gauge_vec(
+        "op_pool_max_cover_optimised_score",
+        "Total score of the solution chosen by a time-budgeted max coverage optimisation",
+        &["label"]
+    );
+    pub static ref MAX_COVER_IMPROVEMENTS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "op_pool_max_cover_improvements_total",
+        "Number of max coverage optimisations which found a better solution than the greedy one",
+        &["label"]
+    );
 }
@@ -51,6 +51,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("hot-db-backend")
+                .long("hot-db-backend")
+                .value_name("BACKEND")
+                .help("The key-value store backing the hot database. An existing database can be \
+                       converted to a different backend with `lighthouse db migrate-backend`.")
+                .value_parser(["leveldb", "redb"])
+                .default_value("leveldb")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /*
          * Network parameters.
          */
@@ -181,6 +192,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("inbound-peer-pressure-threshold")
+                .long("inbound-peer-pressure-threshold")
+                .value_name("PERCENT")
+                .help("Refuse new inbound peers once the target peer count is reached and the \
+                       beacon processor's gossip queues are at least this percentage full. \
+                       Existing peers are unaffected. Set to 0 to disable.")
+                .default_value("80")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("boot-nodes")
                 .long("boot-nodes")
@@ -344,6 +366,17 @@ pub fn cli_app() -> Command {
                 .hide(true)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("gossip-scoring-overrides")
+                .long("gossip-scoring-overrides")
+                .value_name("FILE")
+                .help("Path to a TOML file which overrides the gossipsub score parameters of \
+                       individual topics, e.g. their weights and decay rates. The file can be \
+                       reloaded without a restart via the `lighthouse/gossip_scoring/reload` \
+                       HTTP API endpoint.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("trusted-peers")
                 .long("trusted-peers")
@@ -572,6 +605,31 @@ pub fn cli_app() -> Command {
                 .display_order(0)
                 .default_value_if("enable_http", ArgPredicate::IsPresent, "true")
         )
+        .arg(
+            Arg::new("http-expensive-routes-token-file")
+                .long("http-expensive-routes-token-file")
+                .requires("enable_http")
+                .value_name("PATH")
+                .help("Path to a file containing a token which must be sent as a bearer token \
+                       (`Authorization: Bearer <token>`) in requests to the expensive HTTP API \
+                       routes. These are state queries other than for the head, genesis, \
+                       finalized and justified states, the debug routes and the rewards routes. \
+                       The routes used by validator clients are unaffected.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-expensive-routes-rate-limits")
+                .long("http-expensive-routes-rate-limits")
+                .requires("enable_http")
+                .value_name("QUOTAS")
+                .help("Rate limits for the expensive HTTP API routes, as semicolon separated \
+                       quotas of the form \"group:n/t\", allowing n requests every t seconds. \
+                       The groups are \"states\", \"debug\" and \"rewards\". E.g. \
+                       \"states:10/60;debug:1/12\".")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /* Prometheus metrics HTTP server related arguments */
         .arg(
             Arg::new("metrics")
@@ -650,6 +708,17 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("tracing-endpoint")
+                .long("tracing-endpoint")
+                .value_name("ADDRESS")
+                .help("Export tracing spans for the block import pipeline to the OpenTelemetry \
+                       collector at this address via OTLP/gRPC, e.g. http://localhost:4317. \
+                       Each stage of import (gossip decoding, verification, execution, \
+                       availability, import and fork choice) is recorded as a separate span.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
 
         /*
          * Standard staking flags
@@ -887,8 +956,10 @@ pub fn cli_app() -> Command {
         .arg(
             Arg::new("trusted-setup-file-override")
                 .long("trusted-setup-file-override")
+                .alias("trusted-setup-file")
                 .value_name("FILE")
                 .help("Path to a json file containing the trusted setup params. \
+                      The file must contain one G1 point per field element of a blob. \
                       NOTE: This will override the trusted setup that is generated \
                       from the mainnet kzg ceremony. Use with caution")
                 .action(ArgAction::Set)
@@ -1122,7 +1193,9 @@ pub fn cli_app() -> Command {
                     "Specify a weak subjectivity checkpoint in `block_root:epoch` format to verify \
                      the node's sync against. The block root should be 0x-prefixed. Note that this \
                      flag is for verification only, to perform a checkpoint sync from a recent \
-                     state use --checkpoint-sync-url."
+                     state use --checkpoint-sync-url. When set, the node also checks at the start \
+                     of each epoch that its finalized checkpoint is within the weak subjectivity \
+                     period."
                 )
                 .value_name("WSS_CHECKPOINT")
                 .action(ArgAction::Set)
@@ -1196,6 +1269,51 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("pending-availability-timeout")
+                .long("pending-availability-timeout")
+                .value_name("MILLISECONDS")
+                .help("The time a block received over gossip may wait for its blobs before they \
+                       are requested from peers. If that request fails, the block is dropped and \
+                       the peers which sent it are penalized. Set to 0 to wait for blobs \
+                       indefinitely.")
+                .default_value("4000")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("kzg-verification-threads")
+                .long("kzg-verification-threads")
+                .value_name("COUNT")
+                .help("The maximum number of threads which verify the KZG proofs of blobs at \
+                       once. Verifications beyond this limit are queued, so that blob-heavy \
+                       slots can't delay other work such as attestation processing.")
+                .default_value("4")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("preemptive-blob-requests")
+                .long("preemptive-blob-requests")
+                .help("Request the blobs of each block received over gossip from peers as soon as \
+                       the block has been verified, rather than only waiting for them to arrive \
+                       over gossip. This reduces the worst-case time for a block to become \
+                       available, at the cost of downloading some blobs twice.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("store-participation-summaries")
+                .long("store-participation-summaries")
+                .help("Store a summary of every validator's attestation participation at each \
+                       epoch transition. This allows the attestation performance API to serve \
+                       long ranges without replaying blocks, at the cost of roughly 4 bits per \
+                       validator per epoch of disk space.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         .arg(
             Arg::new("validator-monitor-auto")
                 .long("validator-monitor-auto")
@@ -1344,6 +1462,29 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("attestation-packing-time-budget")
+                .long("attestation-packing-time-budget")
+                .value_name("MILLISECONDS")
+                .help("The maximum number of milliseconds to spend improving on the greedy \
+                       packing of attestations during block production. Higher values may \
+                       increase block rewards at the cost of slower block production. Set to 0 \
+                       to pack attestations greedily.")
+                .default_value("0")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("maximum-gossip-clock-disparity")
+                .long("maximum-gossip-clock-disparity")
+                .value_name("MILLISECONDS")
+                .help("Override the MAXIMUM_GOSSIP_CLOCK_DISPARITY of the network config, which \
+                       is the clock skew tolerated when checking gossip messages and duties \
+                       requests against the local slot clock. Useful for networks with short \
+                       slots.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("paranoid-block-proposal")
                 .long("paranoid-block-proposal")
@@ -1356,6 +1497,18 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("override-da-boundary-epoch")
+                .long("override-da-boundary-epoch")
+                .value_name("EPOCH")
+                .help("Force the data availability boundary to this epoch, instead of the epoch \
+                       `MIN_EPOCHS_FOR_BLOB_SIDECARS_REQUESTS` before the current one. Blocks \
+                       prior to it won't require blobs, and blobs prior to it may be pruned. \
+                       For testing only.")
+                .hide(true)
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-fallback-skips")
                 .long("builder-fallback-skips")
@@ -1391,6 +1544,18 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("builder-fault-cooldown-epochs")
+                .long("builder-fault-cooldown-epochs")
+                .value_name("EPOCHS")
+                .help("If a connected builder reveals blobs which don't match the commitments \
+                        of the block we signed, this node will NOT query any connected builders \
+                        for this number of epochs, and will use the local execution engine for \
+                        payload construction. Set to 0 to keep using the builder.")
+                .default_value("4")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-fallback-disable-checks")
                 .long("builder-fallback-disable-checks")
@@ -1429,6 +1594,18 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("invalidate-reset")
+                .long("invalidate-reset")
+                .value_name("BLOCK_ROOT")
+                .help("Forget the payload statuses of the block with this root and all of its \
+                       descendants during start up, so that they are verified again by the \
+                       execution layer. This can assist in the recovery from the execution layer \
+                       erroneously marking part of the chain as invalid, without a re-sync. \
+                       Ignored if --reset-payload-statuses is present.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("disable-deposit-contract-sync")
                 .long("disable-deposit-contract-sync")
@@ -171,6 +171,23 @@ pub fn get_config<E: EthSpec>(
 
         client_config.http_api.enable_light_client_server =
             cli_args.get_flag("light-client-server");
+
+        if let Some(path) = cli_args.get_one::<String>("http-expensive-routes-token-file") {
+            let token = fs::read_to_string(path)
+                .map_err(|e| format!("Unable to read HTTP API token from {}: {}", path, e))?
+                .trim()
+                .to_string();
+            if token.is_empty() {
+                return Err(format!("The HTTP API token file {} is empty", path));
+            }
+            client_config.http_api.expensive_routes_token = Some(token);
+        }
+
+        if let Some(quotas) = cli_args.get_one::<String>("http-expensive-routes-rate-limits") {
+            client_config.http_api.expensive_routes_quotas =
+                http_api::parse_route_quotas(quotas)
+                    .map_err(|e| format!("Invalid --http-expensive-routes-rate-limits: {}", e))?;
+        }
     }
 
     if cli_args.get_flag("light-client-server") {
@@ -391,6 +408,14 @@ pub fn get_config<E: EthSpec>(
             .map_err(|e| format!("Failed to open trusted setup file: {}", e))?;
         let trusted_setup: TrustedSetup = serde_json::from_reader(file)
             .map_err(|e| format!("Unable to read trusted setup file: {}", e))?;
+        if trusted_setup.g1_len() != E::field_elements_per_blob() {
+            return Err(format!(
+                "Invalid trusted setup file: expected {} G1 points (FIELD_ELEMENTS_PER_BLOB), \
+                 found {}",
+                E::field_elements_per_blob(),
+                trusted_setup.g1_len()
+            ));
+        }
         client_config.trusted_setup = Some(trusted_setup);
     }
 
@@ -458,6 +483,8 @@ pub fn get_config<E: EthSpec>(
         client_config.store.blob_prune_margin_epochs = blob_prune_margin_epochs;
     }
 
+    client_config.store.hot_db_backend = clap_utils::parse_required(cli_args, "hot-db-backend")?;
+
     /*
      * Zero-ports
      *
@@ -586,6 +613,18 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.genesis_backfill = true;
     }
 
+    client_config.chain.store_participation_summaries =
+        cli_args.get_flag("store-participation-summaries");
+
+    client_config.chain.preemptive_blob_requests = cli_args.get_flag("preemptive-blob-requests");
+    client_config.chain.pending_availability_timeout_ms =
+        clap_utils::parse_required(cli_args, "pending-availability-timeout")?;
+    client_config.chain.kzg_verification_threads =
+        clap_utils::parse_required(cli_args, "kzg-verification-threads")?;
+    if client_config.chain.kzg_verification_threads == 0 {
+        return Err("--kzg-verification-threads must be a non-zero value".to_string());
+    }
+
     let beacon_graffiti = if let Some(graffiti) = cli_args.get_one::<String>("graffiti") {
         GraffitiOrigin::UserSpecified(GraffitiString::from_str(graffiti)?.into())
     } else if cli_args.get_flag("private") {
@@ -818,9 +857,19 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.fork_choice_before_proposal_timeout_ms = timeout;
     }
 
+    client_config.chain.attestation_packing_time_budget_ms =
+        clap_utils::parse_required(cli_args, "attestation-packing-time-budget")?;
+
+    client_config.chain.maximum_gossip_clock_disparity_millis =
+        clap_utils::parse_optional(cli_args, "maximum-gossip-clock-disparity")?;
+
     client_config.chain.always_reset_payload_statuses = cli_args.get_flag("reset-payload-statuses");
+    client_config.chain.reset_payload_statuses_from =
+        clap_utils::parse_optional(cli_args, "invalidate-reset")?;
 
     client_config.chain.paranoid_block_proposal = cli_args.get_flag("paranoid-block-proposal");
+    client_config.chain.override_da_boundary_epoch =
+        clap_utils::parse_optional(cli_args, "override-da-boundary-epoch")?;
 
     /*
      * Builder fallback configs.
@@ -835,6 +884,8 @@ pub fn get_config<E: EthSpec>(
         clap_utils::parse_required(cli_args, "builder-fallback-epochs-since-finalization")?;
     client_config.chain.builder_fallback_disable_checks =
         cli_args.get_flag("builder-fallback-disable-checks");
+    client_config.chain.builder_fault_cooldown_epochs =
+        clap_utils::parse_required(cli_args, "builder-fault-cooldown-epochs")?;
 
     // Graphical user interface config.
     if cli_args.get_flag("gui") {
@@ -1151,6 +1202,18 @@ pub fn set_network_config(
             .map_err(|_| format!("Invalid number of target peers: {}", target_peers_str))?;
     }
 
+    if let Some(threshold) =
+        clap_utils::parse_optional::<u8>(cli_args, "inbound-peer-pressure-threshold")?
+    {
+        if threshold > 100 {
+            return Err(format!(
+                "Invalid inbound peer pressure threshold: {}, expected a percentage",
+                threshold
+            ));
+        }
+        config.inbound_pressure_threshold = (threshold > 0).then_some(threshold);
+    }
+
     if let Some(value) = cli_args.get_one::<String>("network-load") {
         let network_load = value
             .parse::<u8>()
@@ -1198,6 +1261,9 @@ pub fn set_network_config(
         config.disable_peer_scoring = true;
     }
 
+    config.gossip_scoring_overrides =
+        clap_utils::parse_optional(cli_args, "gossip-scoring-overrides")?;
+
     if let Some(trusted_peers_str) = cli_args.get_one::<String>("trusted-peers") {
         config.trusted_peers = trusted_peers_str
             .split(',')
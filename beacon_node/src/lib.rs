@@ -2,7 +2,7 @@ mod cli;
 mod config;
 
 pub use beacon_chain;
-use beacon_chain::store::LevelDB;
+use beacon_chain::store::{BeaconNodeBackend, LevelDB};
 use beacon_chain::{
     builder::Witness, eth1_chain::CachingEth1Backend, slot_clock::SystemTimeSlotClock,
     TimeoutRwLock,
@@ -20,8 +20,9 @@ use std::sync::Arc;
 use types::EthSpec;
 
 /// A type-alias to the tighten the definition of a production-intended `Client`.
-pub type ProductionClient<E> =
-    Client<Witness<SystemTimeSlotClock, CachingEth1Backend<E>, E, LevelDB<E>, LevelDB<E>>>;
+pub type ProductionClient<E> = Client<
+    Witness<SystemTimeSlotClock, CachingEth1Backend<E>, E, BeaconNodeBackend<E>, LevelDB<E>>,
+>;
 
 /// The beacon node `Client` that will be used in production.
 ///
@@ -0,0 +1,112 @@
+//! Runtime selection of the key-value store backing the hot database.
+use super::*;
+use std::path::Path;
+
+/// The key-value store backing the hot database, chosen by `StoreConfig::hot_db_backend`.
+pub enum BeaconNodeBackend<E: EthSpec> {
+    LevelDb(LevelDB<E>),
+    Redb(Redb<E>),
+}
+
+macro_rules! with_db {
+    ($self:expr, $db:ident => $body:expr) => {
+        match $self {
+            BeaconNodeBackend::LevelDb($db) => $body,
+            BeaconNodeBackend::Redb($db) => $body,
+        }
+    };
+}
+
+impl<E: EthSpec> BeaconNodeBackend<E> {
+    /// Open the database at `path` with the given `backend`, creating a new database if one does
+    /// not already exist.
+    ///
+    /// Returns an error if the existing database was created by a different backend.
+    pub fn open(backend: DatabaseBackend, path: &Path) -> Result<Self, Error> {
+        if let Some(on_disk) = Self::backend_at(path) {
+            if on_disk != backend {
+                return Err(Error::DatabaseBackendMismatch {
+                    configured: backend,
+                    on_disk,
+                });
+            }
+        }
+
+        match backend {
+            DatabaseBackend::LevelDb => LevelDB::open(path).map(Self::LevelDb),
+            DatabaseBackend::Redb => Redb::open(path).map(Self::Redb),
+        }
+    }
+
+    /// Returns the backend of the database at `path`, or `None` if there is no database there.
+    pub fn backend_at(path: &Path) -> Option<DatabaseBackend> {
+        if Redb::<E>::exists_at(path) {
+            Some(DatabaseBackend::Redb)
+        } else if path.join("CURRENT").exists() {
+            // Every LevelDB database has a `CURRENT` file naming its manifest.
+            Some(DatabaseBackend::LevelDb)
+        } else {
+            None
+        }
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for BeaconNodeBackend<E> {
+    fn get_bytes(&self, column: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        with_db!(self, db => db.get_bytes(column, key))
+    }
+
+    fn put_bytes(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        with_db!(self, db => db.put_bytes(column, key, value))
+    }
+
+    fn put_bytes_sync(&self, column: &str, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        with_db!(self, db => db.put_bytes_sync(column, key, value))
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        with_db!(self, db => db.sync())
+    }
+
+    fn key_exists(&self, column: &str, key: &[u8]) -> Result<bool, Error> {
+        with_db!(self, db => db.key_exists(column, key))
+    }
+
+    fn key_delete(&self, column: &str, key: &[u8]) -> Result<(), Error> {
+        with_db!(self, db => db.key_delete(column, key))
+    }
+
+    fn do_atomically(&self, batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        with_db!(self, db => db.do_atomically(batch))
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        with_db!(self, db => db.begin_rw_transaction())
+    }
+
+    fn compact_column(&self, column: DBColumn) -> Result<(), Error> {
+        with_db!(self, db => db.compact_column(column))
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        with_db!(self, db => db.compact())
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        with_db!(self, db => db.iter_column_from(column, from))
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        with_db!(self, db => db.iter_raw_entries(column, prefix))
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        with_db!(self, db => db.iter_raw_keys(column, prefix))
+    }
+
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        with_db!(self, db => db.iter_column_keys(column))
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for BeaconNodeBackend<E> {}
@@ -1,9 +1,9 @@
 use crate::chunked_vector::ChunkError;
-use crate::config::StoreConfigError;
+use crate::config::{DatabaseBackend, StoreConfigError};
 use crate::hot_cold_store::HotColdDBError;
 use ssz::DecodeError;
 use state_processing::BlockReplayError;
-use types::{BeaconStateError, EpochCacheError, Hash256, InconsistentFork, Slot};
+use types::{BeaconStateError, EpochCacheError, ForkName, Hash256, InconsistentFork, Slot};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -57,6 +57,17 @@ pub enum Error {
         state_root: Hash256,
         slot: Slot,
     },
+    /// The database on disk was created by a different backend to the one configured. It must be
+    /// converted with `lighthouse db migrate-backend` first.
+    DatabaseBackendMismatch {
+        configured: DatabaseBackend,
+        on_disk: DatabaseBackend,
+    },
+    /// A hot state can only be stored as a diff against a state from the same fork.
+    HotStateDiffForkMismatch {
+        base: ForkName,
+        target: ForkName,
+    },
 }
 
 pub trait HandleUnavailable<T> {
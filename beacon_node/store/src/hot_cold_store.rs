@@ -6,24 +6,25 @@ use crate::config::{
     PREV_DEFAULT_SLOTS_PER_RESTORE_POINT,
 };
 use crate::forwards_iter::{HybridForwardsBlockRootsIterator, HybridForwardsStateRootsIterator};
+use crate::hot_state_diff::HotStateDiff;
 use crate::impls::beacon_state::{get_full_state, store_full_state};
 use crate::iter::{BlockRootsIterator, ParentRootBlockIterator, RootsIterator};
 use crate::leveldb_store::BytesKey;
 use crate::leveldb_store::LevelDB;
 use crate::memory_store::MemoryStore;
 use crate::metadata::{
-    AnchorInfo, BlobInfo, CompactionTimestamp, PruningCheckpoint, SchemaVersion, ANCHOR_INFO_KEY,
-    BLOB_INFO_KEY, COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION,
-    PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY, STATE_UPPER_LIMIT_NO_RETAIN,
+    AnchorInfo, BlobInfo, CompactionTimestamp, HotStateDiffBases, PruningCheckpoint, SchemaVersion,
+    ANCHOR_INFO_KEY, BLOB_INFO_KEY, COMPACTION_TIMESTAMP_KEY, CONFIG_KEY, CURRENT_SCHEMA_VERSION,
+    HOT_STATE_DIFF_BASES_KEY, PRUNING_CHECKPOINT_KEY, SCHEMA_VERSION_KEY, SPLIT_KEY,
+    STATE_UPPER_LIMIT_NO_RETAIN,
 };
 use crate::metrics;
 use crate::state_cache::{PutStateOutcome, StateCache};
 use crate::{
-    get_key_for_col, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore, KeyValueStoreOp,
-    PartialBeaconState, StoreItem, StoreOp,
+    get_key_for_col, BeaconNodeBackend, ChunkWriter, DBColumn, DatabaseBlock, Error, ItemStore,
+    KeyValueStore, KeyValueStoreOp, PartialBeaconState, StoreItem, StoreOp,
 };
 use itertools::process_results;
-use leveldb::iterator::LevelDBIterator;
 use lru::LruCache;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
@@ -35,6 +36,7 @@ use state_processing::{
     SlotProcessingError,
 };
 use std::cmp::min;
+use std::collections::HashSet;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
 use std::path::Path;
@@ -42,6 +44,12 @@ use std::sync::Arc;
 use std::time::Duration;
 use types::*;
 
+/// The maximum number of hot state diffs rebased onto the new finalized state by each migration.
+///
+/// Rebasing a diff reconstructs its state in full. The remaining diffs keep their base until they
+/// are rebased by a later migration, or deleted.
+const MAX_HOT_STATE_DIFF_REBASES: usize = 8;
+
 /// On-disk database that stores finalized states efficiently.
 ///
 /// Stores vector fields like the `block_roots` and `state_roots` separately, and only stores
@@ -138,6 +146,7 @@ pub enum HotColdDBError {
     MissingColdStateSummary(Hash256),
     MissingHotStateSummary(Hash256),
     MissingEpochBoundaryState(Hash256),
+    MissingStateDiffBase(Hash256),
     MissingSplitState(Hash256, Slot),
     MissingExecutionPayload(Hash256),
     MissingFullBlockExecutionPayloadPruned(Hash256, Slot),
@@ -196,7 +205,7 @@ impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
     }
 }
 
-impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
+impl<E: EthSpec> HotColdDB<E, BeaconNodeBackend<E>, LevelDB<E>> {
     /// Open a new or existing database, with the given paths to the hot and cold DBs.
     ///
     /// The `migrate_schema` function is passed in so that the parent `BeaconChain` can provide
@@ -218,7 +227,7 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
             blob_info: RwLock::new(BlobInfo::default()),
             cold_db: LevelDB::open(cold_path)?,
             blobs_db: LevelDB::open(blobs_db_path)?,
-            hot_db: LevelDB::open(hot_path)?,
+            hot_db: BeaconNodeBackend::open(config.hot_db_backend, hot_path)?,
             block_cache: Mutex::new(BlockCache::new(config.block_cache_size)),
             state_cache: Mutex::new(StateCache::new(config.state_cache_size)),
             historic_state_cache: Mutex::new(LruCache::new(config.historic_state_cache_size)),
@@ -337,23 +346,8 @@ impl<E: EthSpec> HotColdDB<E, LevelDB<E>, LevelDB<E>> {
 
     /// Return an iterator over the state roots of all temporary states.
     pub fn iter_temporary_state_roots(&self) -> impl Iterator<Item = Result<Hash256, Error>> + '_ {
-        let column = DBColumn::BeaconStateTemporary;
-        let start_key =
-            BytesKey::from_vec(get_key_for_col(column.into(), Hash256::zero().as_bytes()));
-
-        let keys_iter = self.hot_db.keys_iter();
-        keys_iter.seek(&start_key);
-
-        keys_iter
-            .take_while(move |key| key.matches_column(column))
-            .map(move |bytes_key| {
-                bytes_key.remove_column(column).ok_or_else(|| {
-                    HotColdDBError::IterationError {
-                        unexpected_key: bytes_key,
-                    }
-                    .into()
-                })
-            })
+        self.hot_db
+            .iter_column_keys::<Hash256>(DBColumn::BeaconStateTemporary)
     }
 }
 
@@ -942,6 +936,11 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
                         let state_key =
                             get_key_for_col(DBColumn::BeaconState.into(), state_root.as_bytes());
                         key_value_batch.push(KeyValueStoreOp::DeleteKey(state_key));
+                        let diff_key = get_key_for_col(
+                            DBColumn::BeaconStateDiff.into(),
+                            state_root.as_bytes(),
+                        );
+                        key_value_batch.push(KeyValueStoreOp::DeleteKey(diff_key));
                     }
                 }
 
@@ -1080,8 +1079,8 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
 
     /// Store a post-finalization state efficiently in the hot database.
     ///
-    /// On an epoch boundary, store a full state. On an intermediate slot, store
-    /// just a backpointer to the nearest epoch boundary.
+    /// On an epoch boundary, store a full state or a diff (see `store_hot_boundary_state`). On an
+    /// intermediate slot, store just a backpointer to the nearest epoch boundary.
     pub fn store_hot_state(
         &self,
         state_root: &Hash256,
@@ -1108,15 +1107,15 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             return Ok(());
         }
 
-        // On the epoch boundary, store the full state.
+        // On the epoch boundary, store the state itself.
         if state.slot() % E::slots_per_epoch() == 0 {
             trace!(
                 self.log,
-                "Storing full state on epoch boundary";
+                "Storing state on epoch boundary";
                 "slot" => state.slot().as_u64(),
                 "state_root" => format!("{:?}", state_root)
             );
-            store_full_state(state_root, state, ops)?;
+            self.store_hot_boundary_state(state_root, state, ops)?;
         }
 
         // Store a summary of the state.
@@ -1129,6 +1128,175 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         Ok(())
     }
 
+    /// Store an epoch-boundary state in the hot database.
+    ///
+    /// The state is stored as a diff against the finalized state if the finalized state is known
+    /// and from the same fork, and in full otherwise. The finalized state is always stored in
+    /// full, see `rebase_hot_state_diffs`.
+    fn store_hot_boundary_state(
+        &self,
+        state_root: &Hash256,
+        state: &BeaconState<E>,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) -> Result<(), Error> {
+        let finalized_state = self.state_cache.lock().get_finalized_state();
+        let base = finalized_state
+            .as_ref()
+            .map(|(base_state_root, base_state)| (*base_state_root, base_state));
+        store_hot_boundary_state_against(state_root, state, base, ops)
+    }
+
+    /// Push the ops which make `new_base_state` the base of hot state diffs, prior to it becoming
+    /// the split state. The ops are committed in the same batch as the rest of the migration.
+    ///
+    /// The new base is stored in full. It is recorded in `HotStateDiffBases` along with the
+    /// previous base, so that both can be deleted once no diff refers to them. At most
+    /// `MAX_HOT_STATE_DIFF_REBASES` diffs are rebased onto the new base, newest first, as the
+    /// oldest states are the first to be finalized. Diffs of states prior to the new base are
+    /// skipped, as they are about to be deleted.
+    fn rebase_hot_state_diffs(
+        &self,
+        new_base_state_root: Hash256,
+        new_base_state: &BeaconState<E>,
+        previous_base_state_root: Hash256,
+        ops: &mut Vec<KeyValueStoreOp>,
+    ) -> Result<(), Error> {
+        if !self
+            .hot_db
+            .key_exists(DBColumn::BeaconState.into(), new_base_state_root.as_bytes())?
+        {
+            store_hot_boundary_state_against(&new_base_state_root, new_base_state, None, ops)?;
+        }
+
+        let mut bases = self.load_hot_state_diff_bases()?.unwrap_or_default();
+        for state_root in [previous_base_state_root, new_base_state_root] {
+            if !bases.state_roots.contains(&state_root) {
+                bases.state_roots.push(state_root);
+            }
+        }
+        ops.push(bases.as_kv_store_op(HOT_STATE_DIFF_BASES_KEY));
+
+        let mut to_rebase = vec![];
+        for res in self
+            .hot_db
+            .iter_column::<Hash256>(DBColumn::BeaconStateDiff)
+        {
+            let (state_root, bytes) = res?;
+            if HotStateDiff::base_state_root_from_bytes(&bytes)? == new_base_state_root {
+                continue;
+            }
+            if let Some(summary) = self.load_hot_state_summary(&state_root)? {
+                if summary.slot > new_base_state.slot() {
+                    to_rebase.push((summary.slot, state_root));
+                }
+            }
+        }
+        to_rebase.sort_unstable_by_key(|(slot, _)| std::cmp::Reverse(*slot));
+        let num_deferred = to_rebase.len().saturating_sub(MAX_HOT_STATE_DIFF_REBASES);
+        to_rebase.truncate(MAX_HOT_STATE_DIFF_REBASES);
+
+        for (_, state_root) in &to_rebase {
+            let state = self
+                .load_hot_boundary_state(state_root)?
+                .ok_or(HotColdDBError::MissingEpochBoundaryState(*state_root))?;
+            store_hot_boundary_state_against(
+                state_root,
+                &state,
+                Some((new_base_state_root, new_base_state)),
+                ops,
+            )?;
+        }
+
+        debug!(
+            self.log,
+            "Rebasing hot state diffs";
+            "num_rebased" => to_rebase.len(),
+            "num_deferred" => num_deferred,
+            "base_slot" => new_base_state.slot(),
+        );
+        Ok(())
+    }
+
+    /// Delete the full states recorded in `HotStateDiffBases` which no diff refers to any more.
+    ///
+    /// States in `keep` are not deleted. This should include the previous base, in case a state
+    /// was diffed against it concurrently with the migration.
+    fn prune_hot_state_diff_bases(&self, keep: &[Hash256]) -> Result<(), Error> {
+        let Some(mut bases) = self.load_hot_state_diff_bases()? else {
+            return Ok(());
+        };
+
+        let referenced = self
+            .hot_db
+            .iter_column::<Hash256>(DBColumn::BeaconStateDiff)
+            .map(|res| res.and_then(|(_, bytes)| HotStateDiff::base_state_root_from_bytes(&bytes)))
+            .collect::<Result<HashSet<_>, _>>()?;
+
+        let mut ops = vec![];
+        let mut retained = vec![];
+        for state_root in bases.state_roots {
+            // A base may still be a live hot state if the finalized checkpoint hasn't advanced.
+            if keep.contains(&state_root)
+                || referenced.contains(&state_root)
+                || self.load_hot_state_summary(&state_root)?.is_some()
+            {
+                retained.push(state_root);
+                continue;
+            }
+            debug!(
+                self.log,
+                "Deleting old state diff base";
+                "state_root" => ?state_root,
+            );
+            ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                DBColumn::BeaconState.into(),
+                state_root.as_bytes(),
+            )));
+        }
+        if ops.is_empty() {
+            return Ok(());
+        }
+        bases.state_roots = retained;
+        ops.push(bases.as_kv_store_op(HOT_STATE_DIFF_BASES_KEY));
+        self.hot_db.do_atomically(ops)
+    }
+
+    fn load_hot_state_diff_bases(&self) -> Result<Option<HotStateDiffBases>, Error> {
+        self.hot_db.get(&HOT_STATE_DIFF_BASES_KEY)
+    }
+
+    /// Load an epoch-boundary state from the hot database, whether it is stored in full or as a
+    /// diff.
+    ///
+    /// The caches of a state loaded from a diff are not built.
+    pub fn load_hot_boundary_state(
+        &self,
+        state_root: &Hash256,
+    ) -> Result<Option<BeaconState<E>>, Error> {
+        if let Some(state) = get_full_state(&self.hot_db, state_root, &self.spec)? {
+            return Ok(Some(state));
+        }
+        match self.hot_db.get::<HotStateDiff>(state_root)? {
+            Some(diff) => self.apply_hot_state_diff(diff).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn apply_hot_state_diff(&self, diff: HotStateDiff) -> Result<BeaconState<E>, Error> {
+        // The base is usually the finalized state, which is held in memory.
+        let cached_base_state = self
+            .state_cache
+            .lock()
+            .get_by_state_root(diff.base_state_root);
+        let base_state = match cached_base_state {
+            Some(state) => state,
+            None => self
+                .load_hot_boundary_state(&diff.base_state_root)?
+                .ok_or(HotColdDBError::MissingStateDiffBase(diff.base_state_root))?,
+        };
+        diff.apply(&base_state, &self.spec)
+    }
+
     /// Get a post-finalization state from the database or store.
     pub fn get_hot_state(&self, state_root: &Hash256) -> Result<Option<BeaconState<E>>, Error> {
         if let Some(state) = self.state_cache.lock().get_by_state_root(*state_root) {
@@ -1188,10 +1356,11 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
             epoch_boundary_state_root,
         }) = self.load_hot_state_summary(state_root)?
         {
-            let mut boundary_state =
-                get_full_state(&self.hot_db, &epoch_boundary_state_root, &self.spec)?.ok_or(
-                    HotColdDBError::MissingEpochBoundaryState(epoch_boundary_state_root),
-                )?;
+            let mut boundary_state = self
+                .load_hot_boundary_state(&epoch_boundary_state_root)?
+                .ok_or(HotColdDBError::MissingEpochBoundaryState(
+                    epoch_boundary_state_root,
+                ))?;
 
             // Immediately rebase the state from disk on the finalized state so that we can reuse
             // parts of the tree for state root calculation in `replay_blocks`.
@@ -2554,7 +2723,8 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
     // 0. Check that the migration is sensible.
     // The new finalized state must increase the current split slot, and lie on an epoch
     // boundary (in order for the hot state summary scheme to work).
-    let current_split_slot = store.split.read_recursive().slot;
+    let current_split = store.get_split_info();
+    let current_split_slot = current_split.slot;
     let anchor_info = store.anchor_info.read_recursive().clone();
     let anchor_slot = anchor_info.as_ref().map(|a| a.anchor_slot);
 
@@ -2571,6 +2741,17 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
     }
 
     let mut hot_db_ops = vec![];
+
+    // Rebase the hot states that will remain after the migration onto the new finalized state, so
+    // that the states which they are currently diffed against can be deleted.
+    let mut rebase_ops = vec![];
+    store.rebase_hot_state_diffs(
+        finalized_state_root,
+        finalized_state,
+        current_split.state_root,
+        &mut rebase_ops,
+    )?;
+    hot_db_ops.extend(rebase_ops.into_iter().map(StoreOp::KeyValueOp));
     let mut cold_db_ops = vec![];
 
     // Chunk writer for the linear block roots in the freezer DB.
@@ -2599,8 +2780,16 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
             hot_db_ops.push(StoreOp::DeleteExecutionPayload(block_root));
         }
 
-        // Delete the old summary, and the full state if we lie on an epoch boundary.
-        hot_db_ops.push(StoreOp::DeleteState(state_root, Some(slot)));
+        // Delete the old summary, and the state itself if we lie on an epoch boundary. The old
+        // split state is kept for now as other states may still be diffed against it, see
+        // `prune_hot_state_diff_bases`.
+        if state_root == current_split.state_root {
+            let summary_key =
+                get_key_for_col(DBColumn::BeaconStateSummary.into(), state_root.as_bytes());
+            hot_db_ops.push(StoreOp::KeyValueOp(KeyValueStoreOp::DeleteKey(summary_key)));
+        } else {
+            hot_db_ops.push(StoreOp::DeleteState(state_root, Some(slot)));
+        }
 
         // Store the block root for this slot in the linear array of frozen block roots.
         block_root_writer.set(slot.as_usize(), block_root, &mut cold_db_ops)?;
@@ -2625,7 +2814,8 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
         cold_db_ops.push(op);
 
         if slot % store.config.slots_per_restore_point == 0 {
-            let state: BeaconState<E> = get_full_state(&store.hot_db, &state_root, &store.spec)?
+            let state: BeaconState<E> = store
+                .load_hot_boundary_state(&state_root)?
                 .ok_or(HotColdDBError::MissingStateToFreeze(state_root))?;
 
             store.store_cold_state(&state_root, &state, &mut cold_db_ops)?;
@@ -2694,13 +2884,16 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
     // Delete the blocks and states from the hot database if we got this far.
     store.do_atomically_with_block_and_blobs_cache(hot_db_ops)?;
 
-    // Update the cache's view of the finalized state.
+    // Diff states stored from now on against the new base, which is now stored in full.
     store.update_finalized_state(
         finalized_state_root,
         finalized_block_root,
         finalized_state.clone(),
     )?;
 
+    // Delete the previous diff bases which are no longer referenced by any diff.
+    store.prune_hot_state_diff_bases(&[finalized_state_root, current_split.state_root])?;
+
     debug!(
         store.log,
         "Freezer migration complete";
@@ -2710,6 +2903,31 @@ pub fn migrate_database<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>>(
     Ok(())
 }
 
+/// Store an epoch-boundary state as a diff against `base` if it is from the same fork, and in full
+/// otherwise.
+fn store_hot_boundary_state_against<E: EthSpec>(
+    state_root: &Hash256,
+    state: &BeaconState<E>,
+    base: Option<(Hash256, &BeaconState<E>)>,
+    ops: &mut Vec<KeyValueStoreOp>,
+) -> Result<(), Error> {
+    match base {
+        Some((base_state_root, base_state))
+            if base_state_root != *state_root
+                && base_state.fork_name_unchecked() == state.fork_name_unchecked() =>
+        {
+            HotStateDiff::compute(base_state_root, base_state, state)?.store(state_root, ops);
+            Ok(())
+        }
+        _ => {
+            // Replace the diff of a state which is being rebased.
+            let diff_key = get_key_for_col(DBColumn::BeaconStateDiff.into(), state_root.as_bytes());
+            ops.push(KeyValueStoreOp::DeleteKey(diff_key));
+            store_full_state(state_root, state, ops)
+        }
+    }
+}
+
 /// Struct for storing the split slot and state root in the database.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Encode, Decode, Deserialize, Serialize)]
 pub struct Split {
@@ -0,0 +1,284 @@
+//! Storage of hot epoch-boundary states as diffs.
+//!
+//! Rather than storing a full copy of every epoch-boundary state in the hot database, states are
+//! stored as a `HotStateDiff` against the finalized state, which is always stored in full and
+//! usually held in memory by the state cache.
+//!
+//! The bulk of a state is its validator registry, which changes very little from epoch to epoch,
+//! so the list fields indexed by validator are diffed entry by entry. The remainder of the state is
+//! stored in full. During long periods of non-finality this keeps the hot database small, and
+//! loading a state only requires decoding its diff and applying it to the in-memory base state.
+use crate::{get_key_for_col, metrics, DBColumn, Error, KeyValueStoreOp, StoreItem};
+use ssz::{Decode, DecodeError, Encode};
+use ssz_derive::{Decode, Encode};
+use types::{
+    milhouse, BeaconState, BeaconStateError, ChainSpec, EthSpec, Hash256, List, Unsigned, Validator,
+};
+
+/// The changes to a list field of a state, relative to the same field of the base state.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct ListDiff<T: Encode + Decode> {
+    /// The indices of the entries which differ from the base, or `None` if every entry is stored.
+    indices: Option<Vec<u64>>,
+    /// The values of the entries at `indices`, or of every entry.
+    values: Vec<T>,
+}
+
+impl<T: milhouse::Value + Encode + Decode> ListDiff<T> {
+    pub fn compute<N: Unsigned>(base: &List<T, N>, target: &List<T, N>) -> Self {
+        // Lists are only ever appended to, except when comparing states from different forks of
+        // the chain.
+        if target.len() < base.len() {
+            return Self::full(target);
+        }
+
+        let mut base_values = base.iter();
+        let (indices, values): (Vec<_>, Vec<_>) = target
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| base_values.next() != Some(*value))
+            .map(|(i, value)| (i as u64, value.clone()))
+            .unzip();
+
+        // Each changed entry costs an index as well as its value, so once more than half of the
+        // entries have changed it is cheaper to store all of them.
+        if indices.len() * 2 > target.len() {
+            Self::full(target)
+        } else {
+            Self {
+                indices: Some(indices),
+                values,
+            }
+        }
+    }
+
+    fn full<N: Unsigned>(list: &List<T, N>) -> Self {
+        Self {
+            indices: None,
+            values: list.iter().cloned().collect(),
+        }
+    }
+
+    pub fn apply<N: Unsigned>(self, base: &List<T, N>) -> Result<List<T, N>, Error> {
+        let Some(indices) = self.indices else {
+            return Ok(List::new(self.values).map_err(BeaconStateError::MilhouseError)?);
+        };
+        if indices.len() != self.values.len() {
+            return Err(invalid_diff("mismatched indices and values"));
+        }
+
+        let mut list = base.clone();
+        let mut len = list.len();
+        for (index, value) in indices.into_iter().zip(self.values) {
+            let index = index as usize;
+            if let Some(entry) = list.get_mut(index) {
+                *entry = value;
+            } else if index == len {
+                list.push(value).map_err(BeaconStateError::MilhouseError)?;
+                len += 1;
+            } else {
+                return Err(invalid_diff("index beyond the end of the list"));
+            }
+        }
+        list.apply_updates()
+            .map_err(BeaconStateError::MilhouseError)?;
+        Ok(list)
+    }
+}
+
+/// An epoch-boundary state stored as its differences from a base state.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct HotStateDiff {
+    /// The root of the state that this diff must be applied to.
+    pub base_state_root: Hash256,
+    /// The state with its `validators`, `balances` and `inactivity_scores` emptied, as SSZ.
+    state_bytes: Vec<u8>,
+    validators: ListDiff<Validator>,
+    balances: ListDiff<u64>,
+    /// Not present prior to Altair.
+    inactivity_scores: Option<ListDiff<u64>>,
+}
+
+impl HotStateDiff {
+    /// Compute the diff which transforms `base` into `target`.
+    ///
+    /// Both states must be from the same fork.
+    pub fn compute<E: EthSpec>(
+        base_state_root: Hash256,
+        base: &BeaconState<E>,
+        target: &BeaconState<E>,
+    ) -> Result<Self, Error> {
+        let _timer = metrics::start_timer(&metrics::BEACON_STATE_DIFF_COMPUTE_TIMES);
+
+        if base.fork_name_unchecked() != target.fork_name_unchecked() {
+            return Err(Error::HotStateDiffForkMismatch {
+                base: base.fork_name_unchecked(),
+                target: target.fork_name_unchecked(),
+            });
+        }
+
+        let validators = ListDiff::compute(base.validators(), target.validators());
+        let balances = ListDiff::compute(base.balances(), target.balances());
+        let inactivity_scores = match (base.inactivity_scores(), target.inactivity_scores()) {
+            (Ok(base_scores), Ok(target_scores)) => {
+                Some(ListDiff::compute(base_scores, target_scores))
+            }
+            _ => None,
+        };
+
+        let mut remainder = target.clone();
+        *remainder.validators_mut() = List::default();
+        *remainder.balances_mut() = List::default();
+        if let Ok(inactivity_scores) = remainder.inactivity_scores_mut() {
+            *inactivity_scores = List::default();
+        }
+
+        Ok(Self {
+            base_state_root,
+            state_bytes: remainder.as_ssz_bytes(),
+            validators,
+            balances,
+            inactivity_scores,
+        })
+    }
+
+    /// Reconstruct the target state by applying this diff to `base`.
+    ///
+    /// The returned state shares the unchanged parts of its validator registry with `base`. Its
+    /// caches are not built.
+    pub fn apply<E: EthSpec>(
+        self,
+        base: &BeaconState<E>,
+        spec: &ChainSpec,
+    ) -> Result<BeaconState<E>, Error> {
+        let _timer = metrics::start_timer(&metrics::BEACON_STATE_DIFF_APPLY_TIMES);
+
+        let mut state = BeaconState::from_ssz_bytes(&self.state_bytes, spec)?;
+        *state.validators_mut() = self.validators.apply(base.validators())?;
+        *state.balances_mut() = self.balances.apply(base.balances())?;
+        if let Some(inactivity_scores) = self.inactivity_scores {
+            *state.inactivity_scores_mut()? = inactivity_scores.apply(base.inactivity_scores()?)?;
+        }
+        Ok(state)
+    }
+
+    /// Read the root of the base state from an SSZ-encoded diff, without decoding the rest.
+    pub fn base_state_root_from_bytes(bytes: &[u8]) -> Result<Hash256, Error> {
+        // The base state root is the first field, and has a fixed length.
+        let root_bytes = bytes
+            .get(..Hash256::len_bytes())
+            .ok_or_else(|| invalid_diff("too short"))?;
+        Ok(Hash256::from_slice(root_bytes))
+    }
+
+    /// Store this diff as the state with root `state_root`.
+    pub fn store(&self, state_root: &Hash256, ops: &mut Vec<KeyValueStoreOp>) {
+        let bytes = self.as_ssz_bytes();
+        metrics::inc_counter_by(&metrics::BEACON_STATE_DIFF_WRITE_BYTES, bytes.len() as u64);
+        metrics::inc_counter(&metrics::BEACON_STATE_DIFF_WRITE_COUNT);
+        let key = get_key_for_col(DBColumn::BeaconStateDiff.into(), state_root.as_bytes());
+        ops.push(KeyValueStoreOp::PutKeyValue(key, bytes));
+    }
+}
+
+impl StoreItem for HotStateDiff {
+    fn db_column() -> DBColumn {
+        DBColumn::BeaconStateDiff
+    }
+
+    fn as_store_bytes(&self) -> Vec<u8> {
+        self.as_ssz_bytes()
+    }
+
+    fn from_store_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self::from_ssz_bytes(bytes)?)
+    }
+}
+
+fn invalid_diff(reason: &str) -> Error {
+    Error::SszDecodeError(DecodeError::BytesInvalid(format!(
+        "invalid state diff: {reason}"
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::test_utils::{SeedableRng, TestRandom, XorShiftRng};
+    use types::{Epoch, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    #[test]
+    fn list_diff_round_trip() {
+        type N = <E as EthSpec>::ValidatorRegistryLimit;
+        let base = List::<u64, N>::new((0..100).collect()).unwrap();
+
+        // A few changed and appended entries are stored sparsely.
+        let mut target = base.clone();
+        *target.get_mut(3).unwrap() = 1000;
+        *target.get_mut(50).unwrap() = 1001;
+        target.push(1002).unwrap();
+        target.apply_updates().unwrap();
+        let diff = ListDiff::compute(&base, &target);
+        assert_eq!(diff.indices, Some(vec![3, 50, 100]));
+        assert_eq!(
+            ListDiff::from_ssz_bytes(&diff.as_ssz_bytes()).unwrap(),
+            diff
+        );
+        assert_eq!(diff.apply(&base).unwrap(), target);
+
+        // A list with mostly changed entries is stored in full.
+        let target = List::<u64, N>::new((100..200).collect()).unwrap();
+        let diff = ListDiff::compute(&base, &target);
+        assert_eq!(diff.indices, None);
+        assert_eq!(diff.apply(&base).unwrap(), target);
+
+        // As is a list which is shorter than its base.
+        let target = List::<u64, N>::new((0..10).collect()).unwrap();
+        let diff = ListDiff::compute(&base, &target);
+        assert_eq!(diff.indices, None);
+        assert_eq!(diff.apply(&base).unwrap(), target);
+    }
+
+    #[test]
+    fn validator_diff_round_trip() {
+        type N = <E as EthSpec>::ValidatorRegistryLimit;
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+        let base = List::<Validator, N>::new(
+            (0..16)
+                .map(|_| Validator::random_for_test(&mut rng))
+                .collect(),
+        )
+        .unwrap();
+
+        let mut target = base.clone();
+        target.get_mut(7).unwrap().exit_epoch = Epoch::new(10);
+        target.apply_updates().unwrap();
+
+        let diff = ListDiff::compute(&base, &target);
+        assert_eq!(diff.indices, Some(vec![7]));
+        assert_eq!(diff.apply(&base).unwrap(), target);
+    }
+
+    #[test]
+    fn base_state_root_from_bytes() {
+        let empty_diff = || ListDiff {
+            indices: None,
+            values: vec![],
+        };
+        let diff = HotStateDiff {
+            base_state_root: Hash256::repeat_byte(7),
+            state_bytes: vec![1, 2, 3],
+            validators: empty_diff(),
+            balances: empty_diff(),
+            inactivity_scores: None,
+        };
+        let bytes = diff.as_ssz_bytes();
+        assert_eq!(
+            HotStateDiff::base_state_root_from_bytes(&bytes).unwrap(),
+            diff.base_state_root
+        );
+        assert!(HotStateDiff::base_state_root_from_bytes(&bytes[..31]).is_err());
+    }
+}
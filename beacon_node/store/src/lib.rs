@@ -2,11 +2,13 @@
 //!
 //! Provides the following stores:
 //!
-//! - `HotColdDB`: an on-disk store backed by leveldb. Used in production.
+//! - `HotColdDB`: an on-disk store backed by leveldb, or redb for the hot database. Used in
+//!   production.
 //! - `MemoryStore`: an in-memory store backed by a hash-map. Used for testing.
 //!
 //! Provides a simple API for storing/retrieving all types that sometimes needs type-hints. See
 //! tests for implementation examples.
+mod backend;
 mod chunk_writer;
 pub mod chunked_iter;
 pub mod chunked_vector;
@@ -16,6 +18,7 @@ pub mod errors;
 mod forwards_iter;
 mod garbage_collection;
 pub mod hot_cold_store;
+mod hot_state_diff;
 mod impls;
 mod leveldb_store;
 mod memory_store;
@@ -23,17 +26,21 @@ pub mod metadata;
 pub mod metrics;
 mod partial_beacon_state;
 pub mod reconstruct;
+mod redb_store;
 pub mod state_cache;
 
 pub mod iter;
 
+pub use self::backend::BeaconNodeBackend;
 pub use self::chunk_writer::ChunkWriter;
-pub use self::config::StoreConfig;
+pub use self::config::{DatabaseBackend, StoreConfig};
 pub use self::consensus_context::OnDiskConsensusContext;
 pub use self::hot_cold_store::{HotColdDB, HotStateSummary, Split};
+pub use self::hot_state_diff::HotStateDiff;
 pub use self::leveldb_store::LevelDB;
 pub use self::memory_store::MemoryStore;
 pub use self::partial_beacon_state::PartialBeaconState;
+pub use self::redb_store::Redb;
 pub use crate::metadata::BlobInfo;
 pub use errors::Error;
 pub use impls::beacon_state::StorageContainer as BeaconStateStorageContainer;
@@ -41,7 +48,7 @@ pub use metadata::AnchorInfo;
 pub use metrics::scrape_for_metrics;
 use parking_lot::MutexGuard;
 use std::sync::Arc;
-use strum::{EnumString, IntoStaticStr};
+use strum::{EnumIter, EnumString, IntoStaticStr};
 pub use types::*;
 
 pub type ColumnIter<'a, K> = Box<dyn Iterator<Item = Result<(K, Vec<u8>), Error>> + 'a>;
@@ -89,6 +96,7 @@ pub trait KeyValueStore<E: EthSpec>: Sync + Send + Sized + 'static {
         // i.e. entries being created and deleted.
         for column in [
             DBColumn::BeaconState,
+            DBColumn::BeaconStateDiff,
             DBColumn::BeaconStateSummary,
             DBColumn::BeaconBlock,
         ] {
@@ -214,7 +222,7 @@ pub enum StoreOp<'a, E: EthSpec> {
 }
 
 /// A unique column identifier.
-#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString)]
+#[derive(Debug, Clone, Copy, PartialEq, IntoStaticStr, EnumString, EnumIter)]
 pub enum DBColumn {
     /// For data related to the database itself.
     #[strum(serialize = "bma")]
@@ -226,6 +234,9 @@ pub enum DBColumn {
     /// For full `BeaconState`s in the hot database (finalized or fork-boundary states).
     #[strum(serialize = "ste")]
     BeaconState,
+    /// For epoch-boundary states in the hot database which are stored as diffs.
+    #[strum(serialize = "bsd")]
+    BeaconStateDiff,
     /// For the mapping from state roots to their slots or summaries.
     #[strum(serialize = "bss")]
     BeaconStateSummary,
@@ -267,6 +278,9 @@ pub enum DBColumn {
     BeaconHistoricalSummaries,
     #[strum(serialize = "olc")]
     OverflowLRUCache,
+    /// For per-epoch validator participation, keyed by the root of an epoch transition pre-state.
+    #[strum(serialize = "bps")]
+    BeaconParticipationSummary,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -293,6 +307,7 @@ impl DBColumn {
             Self::BeaconMeta
             | Self::BeaconBlock
             | Self::BeaconState
+            | Self::BeaconStateDiff
             | Self::BeaconBlob
             | Self::BeaconStateSummary
             | Self::BeaconStateTemporary
@@ -304,7 +319,8 @@ impl DBColumn {
             | Self::PubkeyCache
             | Self::BeaconRestorePoint
             | Self::DhtEnrs
-            | Self::OptimisticTransitionBlock => 32,
+            | Self::OptimisticTransitionBlock
+            | Self::BeaconParticipationSummary => 32,
             Self::BeaconBlockRoots
             | Self::BeaconStateRoots
             | Self::BeaconHistoricalRoots
@@ -389,6 +405,52 @@ mod tests {
         test_impl(store);
     }
 
+    #[test]
+    fn redb_store() {
+        let dir = tempdir().unwrap();
+        let store = Redb::open(dir.path()).unwrap();
+
+        test_impl(store);
+    }
+
+    #[test]
+    fn redb_iter_column() {
+        let dir = tempdir().unwrap();
+        let store = Redb::<MinimalEthSpec>::open(dir.path()).unwrap();
+        let mut keys = (0..4).map(|_| Hash256::random()).collect::<Vec<_>>();
+        keys.sort();
+        for key in &keys {
+            store.put(key, &StorableThing { a: 1, b: 42 }).unwrap();
+        }
+        // An entry in an adjacent column must not be included.
+        store
+            .put_bytes(DBColumn::BeaconBlob.into(), Hash256::zero().as_bytes(), &[])
+            .unwrap();
+
+        let iterated = store
+            .iter_column_keys::<Hash256>(DBColumn::BeaconBlock)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(iterated, keys);
+    }
+
+    #[test]
+    fn backend_mismatch() {
+        let dir = tempdir().unwrap();
+        drop(LevelDB::<MinimalEthSpec>::open(dir.path()).unwrap());
+
+        assert!(matches!(
+            BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::Redb, dir.path()),
+            Err(Error::DatabaseBackendMismatch {
+                configured: DatabaseBackend::Redb,
+                on_disk: DatabaseBackend::LevelDb,
+            })
+        ));
+        assert!(
+            BeaconNodeBackend::<MinimalEthSpec>::open(DatabaseBackend::LevelDb, dir.path()).is_ok()
+        );
+    }
+
     #[test]
     fn memorydb() {
         let store = MemoryStore::open();
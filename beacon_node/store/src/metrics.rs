@@ -91,6 +91,22 @@ lazy_static! {
         "store_beacon_state_write_bytes_total",
         "Total number of beacon state bytes written to the DB"
     );
+    pub static ref BEACON_STATE_DIFF_COMPUTE_TIMES: Result<Histogram> = try_create_histogram(
+        "store_beacon_state_diff_compute_seconds",
+        "Time taken to compute the diff of a hot state from the finalized state"
+    );
+    pub static ref BEACON_STATE_DIFF_APPLY_TIMES: Result<Histogram> = try_create_histogram(
+        "store_beacon_state_diff_apply_seconds",
+        "Time taken to reconstruct a hot state from its diff"
+    );
+    pub static ref BEACON_STATE_DIFF_WRITE_COUNT: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_state_diff_write_total",
+        "Total number of hot state diff writes to the DB"
+    );
+    pub static ref BEACON_STATE_DIFF_WRITE_BYTES: Result<IntCounter> = try_create_int_counter(
+        "store_beacon_state_diff_write_bytes_total",
+        "Total number of hot state diff bytes written to the DB"
+    );
     /*
      * Beacon Block
      */
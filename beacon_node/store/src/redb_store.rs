@@ -0,0 +1,465 @@
+use super::*;
+use crate::hot_cold_store::HotColdDBError;
+use crate::leveldb_store::BytesKey;
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
+use redb::{Database, Durability, ReadableTable, TableDefinition};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// The name of the database file within the database directory.
+pub const REDB_FILE_NAME: &str = "database.redb";
+
+/// All columns share a single table and are keyed exactly as in `LevelDB`, so that entries can be
+/// copied between the two backends verbatim.
+const TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("lighthouse");
+
+/// A wrapped redb database.
+pub struct Redb<E: EthSpec> {
+    /// Compaction requires exclusive access to the database, everything else shares it.
+    ///
+    /// Shared access is taken with `read_recursive`, so that a thread which holds an iterator can
+    /// still read from the database while compaction is waiting for the lock.
+    db: RwLock<Database>,
+    /// A mutex to synchronise sensitive read-write transactions.
+    transaction_mutex: Mutex<()>,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Redb<E> {
+    /// Open a database in the directory at `path`, creating a new database if one does not
+    /// already exist.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        std::fs::create_dir_all(path).map_err(|e| Error::DBError {
+            message: format!("Unable to create {}: {:?}", path.display(), e),
+        })?;
+        let db = Database::create(path.join(REDB_FILE_NAME))?;
+
+        // Create the table up front so that reads from an empty database don't fail.
+        let txn = db.begin_write()?;
+        txn.open_table(TABLE)?;
+        txn.commit()?;
+
+        Ok(Self {
+            db: RwLock::new(db),
+            transaction_mutex: Mutex::new(()),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns `true` if the directory at `path` contains a redb database.
+    pub fn exists_at(path: &Path) -> bool {
+        path.join(REDB_FILE_NAME).exists()
+    }
+
+    fn write<F>(&self, durability: Durability, f: F) -> Result<(), Error>
+    where
+        F: FnOnce(&mut redb::Table<&[u8], &[u8]>) -> Result<(), redb::StorageError>,
+    {
+        let db = self.db.read_recursive();
+        let mut txn = db.begin_write()?;
+        txn.set_durability(durability);
+        {
+            let mut table = txn.open_table(TABLE)?;
+            f(&mut table)?;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn put_bytes_with_durability(
+        &self,
+        col: &str,
+        key: &[u8],
+        val: &[u8],
+        durability: Durability,
+    ) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_WRITE_COUNT);
+        metrics::inc_counter_by(&metrics::DISK_DB_WRITE_BYTES, val.len() as u64);
+        let timer = metrics::start_timer(&metrics::DISK_DB_WRITE_TIMES);
+
+        self.write(durability, |table| {
+            table.insert(column_key.as_slice(), val)?;
+            Ok(())
+        })
+        .map(|()| {
+            metrics::stop_timer(timer);
+        })
+    }
+
+    /// Iterate through all entries with keys starting with `prefix`, in key order, starting from
+    /// `start_key`.
+    fn iter_prefix_from(&self, prefix: Vec<u8>, start_key: Vec<u8>) -> RawEntryIter {
+        // The iterator's read transaction must end before the database can be compacted, so the
+        // iterator holds the lock until it is dropped.
+        let db = self.db.read_recursive();
+        let range = (|| -> Result<_, Error> {
+            let txn = db.begin_read()?;
+            let table = txn.open_table(TABLE)?;
+            Ok(table.range(start_key.as_slice()..)?)
+        })();
+
+        match range {
+            Ok(range) => Box::new(GuardedIter {
+                iter: range
+                    .map(|res| {
+                        let (key, value) = res?;
+                        Ok((key.value().to_vec(), value.value().to_vec()))
+                    })
+                    .take_while(move |res: &Result<(Vec<u8>, Vec<u8>), Error>| {
+                        res.as_ref()
+                            .map(|(key, _)| key.starts_with(&prefix))
+                            .unwrap_or(true)
+                    }),
+                _db: db,
+            }),
+            Err(e) => Box::new(std::iter::once(Err(e))),
+        }
+    }
+
+    /// Iterate through the entries of `column` with keys of the column's key size, starting from
+    /// `from`.
+    fn iter_column_entries_from(&self, column: DBColumn, from: &[u8]) -> RawEntryIter {
+        let start_key = get_key_for_col(column.into(), from);
+        Box::new(
+            self.iter_prefix_from(column.as_bytes().to_vec(), start_key)
+                .map(move |res| {
+                    let (column_key, value) = res?;
+                    let key = &column_key[column.as_bytes().len()..];
+                    if key.len() != column.key_size() {
+                        return Err(HotColdDBError::IterationError {
+                            unexpected_key: BytesKey::from_vec(column_key),
+                        }
+                        .into());
+                    }
+                    Ok((key.to_vec(), value))
+                }),
+        )
+    }
+}
+
+impl<E: EthSpec> KeyValueStore<E> for Redb<E> {
+    /// Store some `value` in `column`, indexed with `key`.
+    fn put_bytes(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Eventual)
+    }
+
+    fn put_bytes_sync(&self, col: &str, key: &[u8], val: &[u8]) -> Result<(), Error> {
+        self.put_bytes_with_durability(col, key, val, Durability::Immediate)
+    }
+
+    fn sync(&self) -> Result<(), Error> {
+        // Committing an empty transaction with immediate durability persists all prior commits.
+        self.write(Durability::Immediate, |_| Ok(()))
+    }
+
+    /// Retrieve some bytes in `column` with `key`.
+    fn get_bytes(&self, col: &str, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_READ_COUNT);
+        let timer = metrics::start_timer(&metrics::DISK_DB_READ_TIMES);
+
+        let db = self.db.read_recursive();
+        let txn = db.begin_read()?;
+        let table = txn.open_table(TABLE)?;
+        let bytes = table
+            .get(column_key.as_slice())?
+            .map(|value| value.value().to_vec());
+
+        if let Some(bytes) = &bytes {
+            metrics::inc_counter_by(&metrics::DISK_DB_READ_BYTES, bytes.len() as u64);
+            metrics::stop_timer(timer);
+        }
+        Ok(bytes)
+    }
+
+    /// Return `true` if `key` exists in `column`.
+    fn key_exists(&self, col: &str, key: &[u8]) -> Result<bool, Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_EXISTS_COUNT);
+
+        let db = self.db.read_recursive();
+        let txn = db.begin_read()?;
+        let table = txn.open_table(TABLE)?;
+        Ok(table.get(column_key.as_slice())?.is_some())
+    }
+
+    /// Removes `key` from `column`.
+    fn key_delete(&self, col: &str, key: &[u8]) -> Result<(), Error> {
+        let column_key = get_key_for_col(col, key);
+
+        metrics::inc_counter(&metrics::DISK_DB_DELETE_COUNT);
+
+        self.write(Durability::Eventual, |table| {
+            table.remove(column_key.as_slice())?;
+            Ok(())
+        })
+    }
+
+    fn do_atomically(&self, ops_batch: Vec<KeyValueStoreOp>) -> Result<(), Error> {
+        self.write(Durability::Eventual, |table| {
+            for op in ops_batch {
+                match op {
+                    KeyValueStoreOp::PutKeyValue(key, value) => {
+                        table.insert(key.as_slice(), value.as_slice())?;
+                    }
+
+                    KeyValueStoreOp::DeleteKey(key) => {
+                        table.remove(key.as_slice())?;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn begin_rw_transaction(&self) -> MutexGuard<()> {
+        self.transaction_mutex.lock()
+    }
+
+    /// Redb can only compact the whole database, so this compacts every column.
+    fn compact_column(&self, _column: DBColumn) -> Result<(), Error> {
+        self.db.write().compact()?;
+        Ok(())
+    }
+
+    fn compact(&self) -> Result<(), Error> {
+        self.compact_column(DBColumn::BeaconState)
+    }
+
+    fn iter_column_from<K: Key>(&self, column: DBColumn, from: &[u8]) -> ColumnIter<K> {
+        Box::new(self.iter_column_entries_from(column, from).map(|res| {
+            let (key, value) = res?;
+            Ok((K::from_bytes(&key)?, value))
+        }))
+    }
+
+    fn iter_raw_entries(&self, column: DBColumn, prefix: &[u8]) -> RawEntryIter {
+        let start_key = get_key_for_col(column.into(), prefix);
+
+        Box::new(
+            self.iter_prefix_from(start_key.clone(), start_key)
+                .map(move |res| {
+                    let (column_key, value) = res?;
+                    let subkey = &column_key[column.as_bytes().len()..];
+                    Ok((Vec::from(subkey), value))
+                }),
+        )
+    }
+
+    fn iter_raw_keys(&self, column: DBColumn, prefix: &[u8]) -> RawKeyIter {
+        Box::new(
+            self.iter_raw_entries(column, prefix)
+                .map(|res| res.map(|(key, _)| key)),
+        )
+    }
+
+    /// Iterate through all keys in a particular column.
+    fn iter_column_keys<K: Key>(&self, column: DBColumn) -> ColumnKeyIter<K> {
+        Box::new(
+            self.iter_column_entries_from(column, &vec![0; column.key_size()])
+                .map(|res| {
+                    let (key, _) = res?;
+                    K::from_bytes(&key)
+                }),
+        )
+    }
+}
+
+impl<E: EthSpec> ItemStore<E> for Redb<E> {}
+
+/// An iterator which holds shared access to the database for as long as it is live.
+struct GuardedIter<'a, I> {
+    /// Declared before `_db` so that it is dropped first.
+    iter: I,
+    _db: RwLockReadGuard<'a, Database>,
+}
+
+impl<'a, I: Iterator> Iterator for GuardedIter<'a, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+macro_rules! impl_from_redb_error {
+    ($($error:ty),*) => {
+        $(
+            impl From<$error> for Error {
+                fn from(e: $error) -> Error {
+                    Error::DBError {
+                        message: format!("{:?}", e),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_redb_error!(
+    redb::DatabaseError,
+    redb::TransactionError,
+    redb::TableError,
+    redb::StorageError,
+    redb::CommitError,
+    redb::CompactionError
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tempfile::tempdir;
+    use types::MinimalEthSpec;
+
+    fn open() -> (tempfile::TempDir, Redb<MinimalEthSpec>) {
+        let dir = tempdir().unwrap();
+        let db = Redb::open(dir.path()).unwrap();
+        (dir, db)
+    }
+
+    #[test]
+    fn put_get_delete() {
+        let (_dir, db) = open();
+        let col: &str = DBColumn::BeaconBlock.into();
+
+        assert_eq!(db.get_bytes(col, b"key").unwrap(), None);
+        assert!(!db.key_exists(col, b"key").unwrap());
+
+        db.put_bytes(col, b"key", b"value").unwrap();
+        db.put_bytes_sync(col, b"synced", b"synced value").unwrap();
+        assert_eq!(db.get_bytes(col, b"key").unwrap(), Some(b"value".to_vec()));
+        assert_eq!(
+            db.get_bytes(col, b"synced").unwrap(),
+            Some(b"synced value".to_vec())
+        );
+        assert!(db.key_exists(col, b"key").unwrap());
+        // Columns don't share keys.
+        assert_eq!(
+            db.get_bytes(DBColumn::BeaconState.into(), b"key").unwrap(),
+            None
+        );
+
+        db.key_delete(col, b"key").unwrap();
+        assert_eq!(db.get_bytes(col, b"key").unwrap(), None);
+    }
+
+    #[test]
+    fn do_atomically() {
+        let (_dir, db) = open();
+        let col: &str = DBColumn::BeaconBlock.into();
+        db.put_bytes(col, b"deleted", b"value").unwrap();
+
+        db.do_atomically(vec![
+            KeyValueStoreOp::PutKeyValue(get_key_for_col(col, b"added"), b"value".to_vec()),
+            KeyValueStoreOp::DeleteKey(get_key_for_col(col, b"deleted")),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            db.get_bytes(col, b"added").unwrap(),
+            Some(b"value".to_vec())
+        );
+        assert_eq!(db.get_bytes(col, b"deleted").unwrap(), None);
+    }
+
+    #[test]
+    fn iter_raw_entries_by_prefix() {
+        let (_dir, db) = open();
+        let column = DBColumn::BeaconBlock;
+        for key in [&b"a1"[..], b"b1", b"b2", b"c1"] {
+            db.put_bytes(column.into(), key, key).unwrap();
+        }
+        db.put_bytes(DBColumn::BeaconState.into(), b"b3", b"b3")
+            .unwrap();
+
+        let entries = db
+            .iter_raw_entries(column, b"b")
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                (b"b1".to_vec(), b"b1".to_vec()),
+                (b"b2".to_vec(), b"b2".to_vec())
+            ]
+        );
+
+        let keys = db
+            .iter_raw_keys(column, b"")
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            keys,
+            vec![
+                b"a1".to_vec(),
+                b"b1".to_vec(),
+                b"b2".to_vec(),
+                b"c1".to_vec()
+            ]
+        );
+    }
+
+    #[test]
+    fn sync_persists_without_writing_entries() {
+        let (dir, db) = open();
+        let col: &str = DBColumn::BeaconBlock.into();
+        db.put_bytes(col, b"key", b"value").unwrap();
+        db.sync().unwrap();
+        assert_eq!(db.get_bytes("sync", b"sync").unwrap(), None);
+        drop(db);
+
+        let db = Redb::<MinimalEthSpec>::open(dir.path()).unwrap();
+        assert_eq!(db.get_bytes(col, b"key").unwrap(), Some(b"value".to_vec()));
+    }
+
+    #[test]
+    fn compact_waits_for_live_iterators() {
+        let (_dir, db) = open();
+        let db = Arc::new(db);
+        let column = DBColumn::BeaconBlock;
+        for i in 0..16u8 {
+            db.put_bytes(column.into(), &[i], &[i; 64]).unwrap();
+        }
+        for i in 0..8u8 {
+            db.key_delete(column.into(), &[i]).unwrap();
+        }
+
+        let mut iter = db.iter_raw_entries(column, &[]);
+        assert_eq!(iter.next().unwrap().unwrap().0, vec![8]);
+
+        let compacted = Arc::new(AtomicBool::new(false));
+        let compaction = {
+            let db = db.clone();
+            let compacted = compacted.clone();
+            std::thread::spawn(move || {
+                db.compact().unwrap();
+                compacted.store(true, Ordering::SeqCst);
+            })
+        };
+
+        // The iterator can still be read from, including alongside other reads.
+        std::thread::sleep(Duration::from_millis(100));
+        assert!(!compacted.load(Ordering::SeqCst));
+        assert_eq!(
+            db.get_bytes(column.into(), &[15]).unwrap(),
+            Some(vec![15; 64])
+        );
+        assert_eq!(iter.count(), 7);
+
+        compaction.join().unwrap();
+        assert!(compacted.load(Ordering::SeqCst));
+        assert_eq!(
+            db.iter_raw_keys(column, &[])
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap(),
+            (8..16u8).map(|i| vec![i]).collect::<Vec<_>>()
+        );
+    }
+}
@@ -100,6 +100,13 @@ impl<E: EthSpec> StateCache<E> {
         Ok(())
     }
 
+    /// Return the root and a copy of the finalized state, if it is initialized.
+    pub fn get_finalized_state(&self) -> Option<(Hash256, BeaconState<E>)> {
+        self.finalized_state
+            .as_ref()
+            .map(|finalized_state| (finalized_state.state_root, finalized_state.state.clone()))
+    }
+
     /// Rebase the given state on the finalized state in order to reduce its memory consumption.
     ///
     /// This function should only be called on states that are likely not to already share tree
@@ -2,33 +2,36 @@
 //!
 //! This service allows task execution on the beacon node for various functionality.
 
+use beacon_chain::slot_tasks::{spawn_slot_task_scheduler, SlotTask, SlotTaskPriority};
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use slog::{info, warn};
+use slog::info;
 use slot_clock::SlotClock;
 use std::sync::Arc;
-use tokio::time::sleep;
+use std::time::Duration;
 
-/// Spawns a timer service which periodically executes tasks for the beacon chain
+/// Spawns a timer service which periodically executes tasks for the beacon chain.
+///
+/// The timer runs `BeaconChain::per_slot_task` at the start of each slot, alongside any other
+/// tasks registered with `BeaconChain::slot_tasks`.
 pub fn spawn_timer<T: BeaconChainTypes>(
     executor: task_executor::TaskExecutor,
     beacon_chain: Arc<BeaconChain<T>>,
 ) -> Result<(), &'static str> {
     let log = executor.log().clone();
-    let timer_future = async move {
-        loop {
-            let Some(duration_to_next_slot) = beacon_chain.slot_clock.duration_to_next_slot()
-            else {
-                warn!(log, "Unable to determine duration to next slot");
-                return;
-            };
+    let slot_duration = beacon_chain.slot_clock.slot_duration();
 
-            sleep(duration_to_next_slot).await;
-            beacon_chain.per_slot_task().await;
-        }
-    };
+    // The head should be updated before attestations are produced, 1/3 of the way through the
+    // slot.
+    beacon_chain.slot_tasks.register(SlotTask::new(
+        "per_slot",
+        SlotTaskPriority::High,
+        Duration::ZERO,
+        slot_duration / 3,
+        |beacon_chain, _| async move { beacon_chain.per_slot_task().await },
+    ));
 
-    executor.spawn(timer_future, "timer");
-    info!(executor.log(), "Timer service started");
+    spawn_slot_task_scheduler(executor, beacon_chain);
+    info!(log, "Timer service started");
 
     Ok(())
 }
@@ -57,6 +57,17 @@ pub fn get_eth2_network_config(cli_args: &ArgMatches) -> Result<Eth2NetworkConfi
     }
 
     if let Some(slots) = parse_optional(cli_args, "safe-slots-to-import-optimistically")? {
+        // Lowering the parameter on mainnet weakens the protection against a malicious
+        // merge transition block, so only allow it on other networks.
+        let min_slots = ChainSpec::mainnet().safe_slots_to_import_optimistically;
+        if eth2_network_config.config.config_name.as_deref() == Some("mainnet") && slots < min_slots
+        {
+            return Err(format!(
+                "--safe-slots-to-import-optimistically must be at least {} on mainnet",
+                min_slots
+            ));
+        }
+
         eth2_network_config
             .config
             .safe_slots_to_import_optimistically = slots;
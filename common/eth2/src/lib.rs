@@ -28,7 +28,7 @@ use reqwest::{
 pub use reqwest::{StatusCode, Url};
 pub use sensitive_url::{SensitiveError, SensitiveUrl};
 use serde::{de::DeserializeOwned, Serialize};
-use ssz::Encode;
+use ssz::{Decode, Encode};
 use std::fmt;
 use std::future::Future;
 use std::path::PathBuf;
@@ -45,7 +45,12 @@ pub const EXECUTION_PAYLOAD_VALUE_HEADER: &str = "Eth-Execution-Payload-Value";
 pub const CONSENSUS_BLOCK_VALUE_HEADER: &str = "Eth-Consensus-Block-Value";
 
 pub const CONTENT_TYPE_HEADER: &str = "Content-Type";
+
+/// Included in the error message with which Lighthouse rejects an endpoint version it does not
+/// serve.
+pub const UNSUPPORTED_VERSION_MESSAGE: &str = "Unsupported endpoint version";
 pub const SSZ_CONTENT_TYPE_HEADER: &str = "application/octet-stream";
+pub const JSON_CONTENT_TYPE_HEADER: &str = "application/json";
 
 #[derive(Debug)]
 pub enum Error {
@@ -130,6 +135,7 @@ pub struct Timeouts {
     pub get_debug_beacon_states: Duration,
     pub get_deposit_snapshot: Duration,
     pub get_validator_block: Duration,
+    pub get_events: Duration,
 }
 
 impl Timeouts {
@@ -146,6 +152,7 @@ impl Timeouts {
             get_debug_beacon_states: timeout,
             get_deposit_snapshot: timeout,
             get_validator_block: timeout,
+            get_events: timeout,
         }
     }
 }
@@ -440,6 +447,25 @@ impl BeaconNodeHttpClient {
         ok_or_error(response).await
     }
 
+    /// Generic POST function supporting arbitrary responses and timeouts, with an SSZ body.
+    async fn post_generic_with_ssz_body<T: Into<Body>, U: IntoUrl>(
+        &self,
+        url: U,
+        body: T,
+        timeout: Option<Duration>,
+    ) -> Result<Response, Error> {
+        let mut builder = self.client.post(url);
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        let response = builder
+            .header(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
+            .body(body)
+            .send()
+            .await?;
+        ok_or_error(response).await
+    }
+
     /// `GET beacon/genesis`
     ///
     /// ## Errors
@@ -876,6 +902,29 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
+    /// Returns `false` if the beacon node does not accept SSZ-encoded blocks on `POST beacon/blocks`.
+    ///
+    /// The request has an empty body, so a beacon node which accepts SSZ rejects it without
+    /// publishing anything.
+    pub async fn supports_ssz_blocks(&self) -> Result<bool, Error> {
+        let mut path = self.eth_path(V1)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("beacon")
+            .push("blocks");
+
+        let response = self
+            .client
+            .post(path)
+            .timeout(self.timeouts.proposal)
+            .header(CONSENSUS_VERSION_HEADER, ForkName::latest().to_string())
+            .header("Content-Type", "application/octet-stream")
+            .send()
+            .await?;
+        Ok(response.status() != StatusCode::UNSUPPORTED_MEDIA_TYPE)
+    }
+
     /// `POST beacon/blinded_blocks`
     ///
     /// Returns `Ok(None)` on a 404 error.
@@ -1392,18 +1441,55 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
-    /// `GET beacon/deposit_snapshot`
-    pub async fn get_deposit_snapshot(&self) -> Result<Option<types::DepositTreeSnapshot>, Error> {
+    /// URL path for `v1/beacon/deposit_snapshot`.
+    fn get_deposit_snapshot_path(&self) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("deposit_snapshot");
+        Ok(path)
+    }
+
+    /// `GET beacon/deposit_snapshot`
+    pub async fn get_deposit_snapshot(&self) -> Result<Option<types::DepositTreeSnapshot>, Error> {
+        let path = self.get_deposit_snapshot_path()?;
         self.get_opt_with_timeout::<GenericResponse<_>, _>(path, self.timeouts.get_deposit_snapshot)
             .await
             .map(|opt| opt.map(|r| r.data))
     }
 
+    /// `GET beacon/deposit_snapshot`
+    /// `-H "accept: application/octet-stream"`
+    ///
+    /// Remotes which ignore the `Accept` header and respond with JSON are also supported.
+    pub async fn get_deposit_snapshot_ssz(
+        &self,
+    ) -> Result<Option<types::DepositTreeSnapshot>, Error> {
+        let path = self.get_deposit_snapshot_path()?;
+        self.get_response_with_response_headers(
+            path,
+            Accept::Ssz,
+            self.timeouts.get_deposit_snapshot,
+            |response, headers| async move {
+                let is_json = headers
+                    .get(CONTENT_TYPE_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .map_or(false, |value| value.starts_with(JSON_CONTENT_TYPE_HEADER));
+                if is_json {
+                    Ok(response
+                        .json::<GenericResponse<types::DepositTreeSnapshot>>()
+                        .await?
+                        .data)
+                } else {
+                    let bytes = response.bytes().await?;
+                    types::DepositTreeSnapshot::from_ssz_bytes(&bytes).map_err(Error::InvalidSsz)
+                }
+            },
+        )
+        .await
+    }
+
     /// `POST beacon/rewards/sync_committee`
     pub async fn post_beacon_rewards_sync_committee(
         &self,
@@ -1878,6 +1964,44 @@ impl BeaconNodeHttpClient {
         Ok(path)
     }
 
+    /// Returns `false` if the beacon node does not serve `GET v3/validator/blocks/{slot}`.
+    ///
+    /// The request omits the `randao_reveal`, so a beacon node which serves the endpoint rejects it
+    /// without producing a block.
+    pub async fn supports_validator_blocks_v3(&self) -> Result<bool, Error> {
+        let mut path = self.eth_path(V3)?;
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("validator")
+            .push("blocks")
+            .push("0");
+
+        let response = self
+            .client
+            .get(path)
+            .timeout(self.timeouts.get_validator_block)
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED => Ok(false),
+            // Lighthouse rejects an endpoint version it does not serve with a 400, so the message
+            // is needed to tell it apart from the rejection of the missing `randao_reveal`.
+            StatusCode::BAD_REQUEST => {
+                let is_unsupported_version = response
+                    .json::<ErrorMessage>()
+                    .await
+                    .map_or(false, |message| {
+                        message.message.contains(UNSUPPORTED_VERSION_MESSAGE)
+                    });
+                Ok(!is_unsupported_version)
+            }
+            _ => Ok(true),
+        }
+    }
+
     /// `GET v3/validator/blocks/{slot}`
     pub async fn get_validator_blocks_v3<E: EthSpec>(
         &self,
@@ -2350,11 +2474,8 @@ impl BeaconNodeHttpClient {
         Ok(())
     }
 
-    /// `GET events?topics`
-    pub async fn get_events<E: EthSpec>(
-        &self,
-        topic: &[EventTopic],
-    ) -> Result<impl Stream<Item = Result<EventKind<E>, Error>>, Error> {
+    /// returns `GET events?topics` URL path
+    fn events_path(&self, topic: &[EventTopic]) -> Result<Url, Error> {
         let mut path = self.eth_path(V1)?;
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
@@ -2367,6 +2488,16 @@ impl BeaconNodeHttpClient {
             .join(",");
         path.query_pairs_mut().append_pair("topics", &topic_string);
 
+        Ok(path)
+    }
+
+    /// `GET events?topics`
+    pub async fn get_events<E: EthSpec>(
+        &self,
+        topic: &[EventTopic],
+    ) -> Result<impl Stream<Item = Result<EventKind<E>, Error>>, Error> {
+        let path = self.events_path(topic)?;
+
         Ok(self
             .client
             .get(path)
@@ -2379,6 +2510,20 @@ impl BeaconNodeHttpClient {
             }))
     }
 
+    /// Returns `true` if the beacon node accepts a subscription to all of `topic`.
+    ///
+    /// The subscription is closed as soon as the response status has been received.
+    pub async fn supports_events(&self, topic: &[EventTopic]) -> Result<bool, Error> {
+        let path = self.events_path(topic)?;
+        let response = self
+            .client
+            .get(path)
+            .timeout(self.timeouts.get_events)
+            .send()
+            .await?;
+        Ok(response.status().is_success())
+    }
+
     /// `POST validator/duties/sync/{epoch}`
     pub async fn post_validator_duties_sync(
         &self,
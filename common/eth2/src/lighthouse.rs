@@ -2,20 +2,23 @@
 
 mod attestation_performance;
 pub mod attestation_rewards;
+mod blob_inclusion_forecast;
 mod block_packing_efficiency;
 mod block_rewards;
 mod standard_block_rewards;
+mod subnet_assignments;
 mod sync_committee_rewards;
 
 use crate::{
     types::{
-        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse, ValidatorId,
+        Accept, BlobSidecar, DepositTreeSnapshot, Epoch, EthSpec, ExecutionBlockHash,
+        FinalizedExecutionBlock, GenericResponse, ValidatorId,
     },
-    BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
+    BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot, Url,
 };
 use proto_array::core::ProtoArray;
 use serde::{Deserialize, Serialize};
-use ssz::four_byte_option_impl;
+use ssz::{four_byte_option_impl, Encode};
 use ssz_derive::{Decode, Encode};
 use store::{AnchorInfo, BlobInfo, Split, StoreConfig};
 
@@ -23,12 +26,19 @@ pub use attestation_performance::{
     AttestationPerformance, AttestationPerformanceQuery, AttestationPerformanceStatistics,
 };
 pub use attestation_rewards::StandardAttestationRewards;
+pub use blob_inclusion_forecast::{
+    BlobBaseFeeProjection, BlobInclusionForecast, BlobInclusionForecastQuery, BlockBlobUsage,
+};
 pub use block_packing_efficiency::{
     BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
 };
 pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
 pub use lighthouse_network::{types::SyncState, PeerInfo};
 pub use standard_block_rewards::StandardBlockReward;
+pub use subnet_assignments::{
+    AttestationSubnetAssignment, EpochSubnetAssignments, SubnetAssignmentsRequest,
+    SyncSubnetAssignment,
+};
 pub use sync_committee_rewards::SyncCommitteeReward;
 
 // Define "legacy" implementations of `Option<T>` which use four bytes for encoding the union
@@ -47,6 +57,15 @@ pub struct Peer<E: EthSpec> {
     pub peer_info: PeerInfo<E>,
 }
 
+/// Information returned by `peers/blob_withholding`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlobWithholdingPeer {
+    /// The Peer's ID
+    pub peer_id: String,
+    /// The number of blocks from the peer whose blobs never became available.
+    pub blob_withholding_count: u64,
+}
+
 /// The results of validators voting during an epoch.
 ///
 /// Provides information about the current and previous epochs.
@@ -365,6 +384,67 @@ pub struct DatabaseInfo {
     pub blob_info: BlobInfo,
 }
 
+/// Query parameters for the `lighthouse/database/backfill_payloads` endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayloadBackfillQuery {
+    /// Lower slot limit for the backfill (inclusive).
+    pub start_slot: Slot,
+    /// Upper slot limit for the backfill (inclusive, capped at the finalized slot).
+    pub end_slot: Slot,
+}
+
+/// Outcome of re-populating execution payloads from the execution layer.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayloadBackfillSummary {
+    /// Number of distinct canonical blocks in the requested range.
+    pub blocks_checked: u64,
+    /// Number of blocks whose payload was already stored.
+    pub payloads_already_present: u64,
+    /// Number of payloads reconstructed from the execution layer and stored.
+    pub payloads_restored: u64,
+    /// Number of payloads the execution layer did not return.
+    pub payloads_unavailable: u64,
+    /// Number of payloads returned by the execution layer which did not match the block's header.
+    pub payloads_invalid: u64,
+}
+
+/// Request body for the `lighthouse/fork_choice/reset_payload_statuses` endpoint.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResetPayloadStatusesRequest {
+    /// Only reset this block and its descendants. All blocks are reset if `None`.
+    #[serde(default)]
+    pub ancestor_root: Option<Hash256>,
+}
+
+/// The head of the chain after resetting payload statuses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResetPayloadStatusesResponse {
+    pub head_block_root: Hash256,
+    pub head_slot: Slot,
+    /// Whether the head is still awaiting verification by the execution layer.
+    pub execution_optimistic: bool,
+}
+
+/// The optimistic sync safety parameters and the blocks which have been imported optimistically.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptimisticSyncInfo {
+    /// Blocks at least this many slots older than the current slot may be imported
+    /// optimistically even if their parent is pre-merge.
+    pub safe_slots_to_import_optimistically: u64,
+    /// Whether the head block is optimistic (or invalid).
+    pub head_is_optimistic: bool,
+    /// All blocks in fork choice whose execution payload has not yet been verified.
+    pub optimistic_blocks: Vec<OptimisticBlock>,
+}
+
+/// A block in fork choice which was imported optimistically.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptimisticBlock {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub execution_block_hash: ExecutionBlockHash,
+}
+
 impl BeaconNodeHttpClient {
     /// `GET lighthouse/health`
     pub async fn get_lighthouse_health(&self) -> Result<GenericResponse<Health>, Error> {
@@ -398,6 +478,21 @@ impl BeaconNodeHttpClient {
      * fairly simply achieved, if desired.
      */
 
+    /// `GET lighthouse/peers/blob_withholding`
+    pub async fn get_lighthouse_peers_blob_withholding(
+        &self,
+    ) -> Result<Vec<BlobWithholdingPeer>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("blob_withholding");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/proto_array`
     pub async fn get_lighthouse_proto_array(&self) -> Result<GenericResponse<ProtoArray>, Error> {
         let mut path = self.server.full.clone();
@@ -410,6 +505,38 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/debug/fork_choice_snapshot`
+    ///
+    /// Returns the SSZ bytes of the node's `PersistedForkChoice`.
+    pub async fn get_lighthouse_debug_fork_choice_snapshot_ssz(
+        &self,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("debug")
+            .push("fork_choice_snapshot");
+
+        self.get_bytes_opt_accept_header(path, Accept::Ssz, self.timeouts.get_debug_beacon_states)
+            .await
+    }
+
+    /// `GET lighthouse/optimistic_sync`
+    pub async fn get_lighthouse_optimistic_sync(
+        &self,
+    ) -> Result<GenericResponse<OptimisticSyncInfo>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("optimistic_sync");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/validator_inclusion/{epoch}/global`
     pub async fn get_lighthouse_validator_inclusion_global(
         &self,
@@ -490,6 +617,40 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// URL path for `lighthouse/eth1/deposit_snapshot`.
+    fn lighthouse_eth1_deposit_snapshot_path(&self) -> Result<Url, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("eth1")
+            .push("deposit_snapshot");
+
+        Ok(path)
+    }
+
+    /// `POST lighthouse/eth1/deposit_snapshot`
+    pub async fn post_lighthouse_eth1_deposit_snapshot(
+        &self,
+        deposit_snapshot: &DepositTreeSnapshot,
+    ) -> Result<(), Error> {
+        let path = self.lighthouse_eth1_deposit_snapshot_path()?;
+        self.post(path, deposit_snapshot).await
+    }
+
+    /// `POST lighthouse/eth1/deposit_snapshot`
+    /// `-H "content-type: application/octet-stream"`
+    pub async fn post_lighthouse_eth1_deposit_snapshot_ssz(
+        &self,
+        deposit_snapshot: &DepositTreeSnapshot,
+    ) -> Result<(), Error> {
+        let path = self.lighthouse_eth1_deposit_snapshot_path()?;
+        self.post_generic_with_ssz_body(path, deposit_snapshot.as_ssz_bytes(), None)
+            .await?;
+        Ok(())
+    }
+
     /// `GET lighthouse/staking`
     pub async fn get_lighthouse_staking(&self) -> Result<bool, Error> {
         let mut path = self.server.full.clone();
@@ -528,6 +689,72 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `POST lighthouse/database/import_blobs`
+    pub async fn post_lighthouse_database_import_blobs<E: EthSpec>(
+        &self,
+        blob_sidecars: &[BlobSidecar<E>],
+    ) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("import_blobs");
+
+        self.post(path, &blob_sidecars).await
+    }
+
+    /// `POST lighthouse/database/backfill_payloads?start_slot,end_slot`
+    pub async fn post_lighthouse_database_backfill_payloads(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+    ) -> Result<PayloadBackfillSummary, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("database")
+            .push("backfill_payloads");
+
+        path.query_pairs_mut()
+            .append_pair("start_slot", &start_slot.to_string())
+            .append_pair("end_slot", &end_slot.to_string());
+
+        self.post_with_response(path, &()).await
+    }
+
+    /// `POST lighthouse/fork_choice/reset_payload_statuses`
+    pub async fn post_lighthouse_fork_choice_reset_payload_statuses(
+        &self,
+        request: &ResetPayloadStatusesRequest,
+    ) -> Result<ResetPayloadStatusesResponse, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice")
+            .push("reset_payload_statuses");
+
+        self.post_with_response(path, request).await
+    }
+
+    /// `POST lighthouse/gossip_scoring/reload`
+    pub async fn post_lighthouse_gossip_scoring_reload(&self) -> Result<(), Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("gossip_scoring")
+            .push("reload");
+
+        self.post(path, &()).await
+    }
+
     ///
     /// Analysis endpoints.
     ///
@@ -574,6 +801,51 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET` lighthouse/blob_inclusion_forecast?blocks
+    pub async fn get_lighthouse_blob_inclusion_forecast(
+        &self,
+        blocks: Option<u64>,
+    ) -> Result<GenericResponse<BlobInclusionForecast>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("blob_inclusion_forecast");
+
+        if let Some(blocks) = blocks {
+            path.query_pairs_mut()
+                .append_pair("blocks", &blocks.to_string());
+        }
+
+        self.get(path).await
+    }
+
+    /// `POST` lighthouse/subnet_assignments
+    pub async fn post_lighthouse_subnet_assignments(
+        &self,
+        indices: &[u64],
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+    ) -> Result<GenericResponse<Vec<EpochSubnetAssignments>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("subnet_assignments");
+
+        self.post_with_response(
+            path,
+            &SubnetAssignmentsRequest {
+                indices: indices.to_vec(),
+                start_epoch,
+                end_epoch,
+            },
+        )
+        .await
+    }
+
     /// `GET` lighthouse/analysis/attestation_performance/{index}?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_attestation_performance(
         &self,
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use types::{Hash256, Slot, Uint256};
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlobInclusionForecastQuery {
+    /// The number of recent blocks to consider.
+    pub blocks: Option<u64>,
+}
+
+/// The blob usage of a single block.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlockBlobUsage {
+    pub slot: Slot,
+    pub block_root: Hash256,
+    pub blob_count: u64,
+    pub blob_gas_used: u64,
+    pub excess_blob_gas: u64,
+    /// The blob base fee paid by the blobs in this block, in wei.
+    #[serde(with = "serde_utils::quoted_u256")]
+    pub blob_base_fee: Uint256,
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlobInclusionForecast {
+    pub target_blobs_per_block: u64,
+    pub max_blobs_per_block: u64,
+    /// Recent blocks on the canonical chain, oldest first.
+    pub blocks: Vec<BlockBlobUsage>,
+    pub mean_blobs_per_block: f64,
+    /// The next blocks, assuming that each uses the mean blob gas of `blocks`.
+    ///
+    /// The first projection is exact, since it is determined by the head block.
+    pub projections: Vec<BlobBaseFeeProjection>,
+}
+
+/// The projected blob gas pricing of a future block.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlobBaseFeeProjection {
+    pub excess_blob_gas: u64,
+    /// The blob base fee, in wei.
+    #[serde(with = "serde_utils::quoted_u256")]
+    pub blob_base_fee: Uint256,
+}
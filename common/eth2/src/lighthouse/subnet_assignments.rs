@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use types::{Epoch, Slot, SubnetId, SyncSubnetId};
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SubnetAssignmentsRequest {
+    #[serde(with = "serde_utils::quoted_u64_vec")]
+    pub indices: Vec<u64>,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+}
+
+/// The subnets which the requested validators are assigned to in a single epoch.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct EpochSubnetAssignments {
+    pub epoch: Epoch,
+    /// `None` if the epoch is too far in the future for its shuffling to be known.
+    pub attestation_subnets: Option<Vec<AttestationSubnetAssignment>>,
+    pub sync_committee_subnets: Vec<SyncSubnetAssignment>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct AttestationSubnetAssignment {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub slot: Slot,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committee_index: u64,
+    pub subnet_id: SubnetId,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SyncSubnetAssignment {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    pub subnet_ids: Vec<SyncSubnetId>,
+}
@@ -375,6 +375,52 @@ impl ValidatorClientHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/health/live`
+    pub async fn get_lighthouse_health_live(&self) -> Result<GenericResponse<LivenessData>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("health")
+            .push("live");
+
+        self.get_unsigned(path).await
+    }
+
+    /// `GET lighthouse/health/ready`
+    ///
+    /// The server responds with a 503 status when it is not ready, which is returned here as a
+    /// response with `ready == false` rather than as an error.
+    pub async fn get_lighthouse_health_ready(
+        &self,
+    ) -> Result<GenericResponse<ReadinessData>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("health")
+            .push("ready");
+
+        let response = self
+            .client
+            .get(path)
+            .headers(self.headers()?)
+            .send()
+            .await
+            .map_err(Error::from)?;
+        if response.status() == StatusCode::SERVICE_UNAVAILABLE {
+            response.json().await.map_err(Error::from)
+        } else {
+            ok_or_error(response)
+                .await?
+                .json()
+                .await
+                .map_err(Error::from)
+        }
+    }
+
     /// `GET lighthouse/spec`
     pub async fn get_lighthouse_spec<T: Serialize + DeserializeOwned>(
         &self,
@@ -531,6 +577,38 @@ impl ValidatorClientHttpClient {
         self.delete_with_unsigned_response(path, req).await
     }
 
+    /// `POST lighthouse/config_bundle/export`
+    pub async fn post_lighthouse_config_bundle_export(
+        &self,
+        req: &ExportConfigBundleRequest,
+    ) -> Result<GenericResponse<ConfigBundle>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("config_bundle")
+            .push("export");
+
+        self.post(path, req).await
+    }
+
+    /// `POST lighthouse/config_bundle/import`
+    pub async fn post_lighthouse_config_bundle_import(
+        &self,
+        req: &ImportConfigBundleRequest,
+    ) -> Result<ImportConfigBundleResponse, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("config_bundle")
+            .push("import");
+
+        self.post(path, req).await
+    }
+
     fn make_keystores_url(&self) -> Result<Url, Error> {
         let mut url = self.server.full.clone();
         url.path_segments_mut()
@@ -1,5 +1,5 @@
-use account_utils::ZeroizeString;
-use eth2_keystore::Keystore;
+use account_utils::{validator_definitions::Web3SignerDefinition, ZeroizeString};
+use eth2_keystore::{json_keystore::Crypto, Keystore};
 use graffiti::GraffitiString;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -197,3 +197,141 @@ pub struct SingleExportKeystoresResponse {
 pub struct SetGraffitiRequest {
     pub graffiti: GraffitiString,
 }
+
+/// The version of the `ConfigBundle` format written by this release.
+pub const CONFIG_BUNDLE_VERSION: u64 = 1;
+
+/// The configuration of a validator client, encrypted for moving it to another host.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub version: u64,
+    /// The network of the validator client which exported the bundle.
+    pub genesis_validators_root: Hash256,
+    /// The JSON of a `ConfigBundleContents`, encrypted as per EIP-2335.
+    pub crypto: Crypto,
+}
+
+/// The plaintext of a `ConfigBundle`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ConfigBundleContents {
+    pub validators: Vec<ConfigBundleValidator>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slashing_protection: Option<Interchange>,
+}
+
+/// The definition of a single validator within a `ConfigBundle`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ConfigBundleValidator {
+    pub enabled: bool,
+    pub voting_public_key: PublicKey,
+    pub signer: ConfigBundleSigner,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graffiti: Option<GraffitiString>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggested_fee_recipient: Option<Address>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<u64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_proposals: Option<bool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builder_boost_factor: Option<u64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefer_builder_proposals: Option<bool>,
+}
+
+/// How a validator within a `ConfigBundle` signs messages.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConfigBundleSigner {
+    LocalKeystore {
+        voting_keystore: Keystore,
+        voting_keystore_password: ZeroizeString,
+    },
+    Web3Signer(Web3SignerDefinition),
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportConfigBundleRequest {
+    /// The password used to encrypt the bundle.
+    pub password: ZeroizeString,
+    /// If `true`, the bundle includes the slashing protection data of its validators and the
+    /// validators are disabled on the exporting validator client.
+    #[serde(default)]
+    pub include_slashing_protection: bool,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportConfigBundleRequest {
+    pub bundle: ConfigBundle,
+    pub password: ZeroizeString,
+    /// If `true`, the bundle is validated against this validator client without making any
+    /// changes.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportConfigBundleResponse {
+    pub dry_run: bool,
+    pub data: Vec<ConfigBundleValidatorStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigBundleValidatorStatus {
+    pub voting_pubkey: PublicKeyBytes,
+    pub status: Status<ImportKeystoreStatus>,
+}
+
+/// The response of `GET lighthouse/health/live`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LivenessData {
+    pub live: bool,
+}
+
+/// The response of `GET lighthouse/health/ready`.
+///
+/// The validator client is ready when every check which is `required` is also `ready`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadinessData {
+    pub ready: bool,
+    pub keystores: KeystoreReadiness,
+    pub beacon_nodes: BeaconNodeReadiness,
+    pub slashing_protection: SlashingProtectionReadiness,
+}
+
+/// Ready when every enabled validator has had its keystore loaded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeystoreReadiness {
+    pub required: bool,
+    pub ready: bool,
+    pub enabled: usize,
+    pub loaded: usize,
+}
+
+/// Ready when at least `min_synced` beacon nodes are synced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeaconNodeReadiness {
+    pub required: bool,
+    pub ready: bool,
+    pub total: usize,
+    pub available: usize,
+    pub synced: usize,
+    pub min_synced: usize,
+}
+
+/// Ready when the slashing protection database can be read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SlashingProtectionReadiness {
+    pub required: bool,
+    pub ready: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
@@ -967,6 +967,17 @@ pub struct SseChainReorg {
     pub execution_optimistic: bool,
 }
 
+/// Sent when a re-org changes the dependent root of the attester or proposer duties for `epoch`,
+/// meaning that duties computed prior to the re-org may be wrong.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct SseDutiesReorg {
+    pub slot: Slot,
+    pub epoch: Epoch,
+    pub old_dependent_root: Hash256,
+    pub new_dependent_root: Hash256,
+    pub execution_optimistic: bool,
+}
+
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 pub struct SseLateHead {
     pub slot: Slot,
@@ -1073,6 +1084,8 @@ pub enum EventKind<E: EthSpec> {
     Head(SseHead),
     VoluntaryExit(SignedVoluntaryExit),
     ChainReorg(SseChainReorg),
+    AttesterDutiesReorg(SseDutiesReorg),
+    ProposerDutiesReorg(SseDutiesReorg),
     ContributionAndProof(Box<SignedContributionAndProof<E>>),
     LateHead(SseLateHead),
     LightClientFinalityUpdate(Box<LightClientFinalityUpdate<E>>),
@@ -1095,6 +1108,8 @@ impl<E: EthSpec> EventKind<E> {
             EventKind::VoluntaryExit(_) => "voluntary_exit",
             EventKind::FinalizedCheckpoint(_) => "finalized_checkpoint",
             EventKind::ChainReorg(_) => "chain_reorg",
+            EventKind::AttesterDutiesReorg(_) => "attester_duties_reorg",
+            EventKind::ProposerDutiesReorg(_) => "proposer_duties_reorg",
             EventKind::ContributionAndProof(_) => "contribution_and_proof",
             EventKind::PayloadAttributes(_) => "payload_attributes",
             EventKind::LateHead(_) => "late_head",
@@ -1139,6 +1154,16 @@ impl<E: EthSpec> EventKind<E> {
             "chain_reorg" => Ok(EventKind::ChainReorg(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Chain Reorg: {:?}", e)),
             )?)),
+            "attester_duties_reorg" => Ok(EventKind::AttesterDutiesReorg(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Attester Duties Reorg: {:?}", e))
+                })?,
+            )),
+            "proposer_duties_reorg" => Ok(EventKind::ProposerDutiesReorg(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Proposer Duties Reorg: {:?}", e))
+                })?,
+            )),
             "finalized_checkpoint" => Ok(EventKind::FinalizedCheckpoint(
                 serde_json::from_str(data).map_err(|e| {
                     ServerError::InvalidServerSentEvent(format!("Finalized Checkpoint: {:?}", e))
@@ -1214,6 +1239,38 @@ pub struct EventQuery {
     pub topics: Vec<EventTopic>,
 }
 
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketEventQuery {
+    #[serde(deserialize_with = "query_vec")]
+    pub topics: Vec<EventTopic>,
+    #[serde(default, deserialize_with = "option_query_vec")]
+    pub on_full: Option<Vec<WebSocketDropPolicy>>,
+}
+
+/// What to do when a WebSocket client falls so far behind that its send queue is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebSocketDropPolicy {
+    /// Skip new events until the queue has space, then tell the client how many were missed.
+    #[default]
+    Drop,
+    /// Close the connection.
+    Disconnect,
+}
+
+impl FromStr for WebSocketDropPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drop" => Ok(WebSocketDropPolicy::Drop),
+            "disconnect" => Ok(WebSocketDropPolicy::Disconnect),
+            _ => Err(format!("Invalid drop policy: {}", s)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum EventTopic {
@@ -1224,6 +1281,8 @@ pub enum EventTopic {
     VoluntaryExit,
     FinalizedCheckpoint,
     ChainReorg,
+    AttesterDutiesReorg,
+    ProposerDutiesReorg,
     ContributionAndProof,
     LateHead,
     PayloadAttributes,
@@ -1248,6 +1307,8 @@ impl FromStr for EventTopic {
             "voluntary_exit" => Ok(EventTopic::VoluntaryExit),
             "finalized_checkpoint" => Ok(EventTopic::FinalizedCheckpoint),
             "chain_reorg" => Ok(EventTopic::ChainReorg),
+            "attester_duties_reorg" => Ok(EventTopic::AttesterDutiesReorg),
+            "proposer_duties_reorg" => Ok(EventTopic::ProposerDutiesReorg),
             "contribution_and_proof" => Ok(EventTopic::ContributionAndProof),
             "payload_attributes" => Ok(EventTopic::PayloadAttributes),
             "late_head" => Ok(EventTopic::LateHead),
@@ -1273,6 +1334,8 @@ impl fmt::Display for EventTopic {
             EventTopic::VoluntaryExit => write!(f, "voluntary_exit"),
             EventTopic::FinalizedCheckpoint => write!(f, "finalized_checkpoint"),
             EventTopic::ChainReorg => write!(f, "chain_reorg"),
+            EventTopic::AttesterDutiesReorg => write!(f, "attester_duties_reorg"),
+            EventTopic::ProposerDutiesReorg => write!(f, "proposer_duties_reorg"),
             EventTopic::ContributionAndProof => write!(f, "contribution_and_proof"),
             EventTopic::PayloadAttributes => write!(f, "payload_attributes"),
             EventTopic::LateHead => write!(f, "late_head"),
@@ -10,7 +10,7 @@ use std::time::{Duration, Instant};
 use tracing_appender::non_blocking::NonBlocking;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_logging_layer::LoggingLayer;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
 pub const MAX_MESSAGE_WIDTH: usize = 40;
 
@@ -18,6 +18,7 @@ pub mod async_record;
 mod sse_logging_components;
 mod tracing_logging_layer;
 mod tracing_metrics_layer;
+mod tracing_otlp_layer;
 
 pub use sse_logging_components::SSELoggingComponents;
 pub use tracing_metrics_layer::MetricsLayer;
@@ -221,7 +222,11 @@ impl TimeLatch {
     }
 }
 
-pub fn create_tracing_layer(base_tracing_log_path: PathBuf) {
+/// Initialise the global `tracing` subscriber.
+///
+/// Events from dependencies which pass the `RUST_LOG` filter are written to rolling log files. If
+/// `tracing_endpoint` is provided, Lighthouse's own spans are also exported to it via OTLP.
+pub fn create_tracing_layer(base_tracing_log_path: PathBuf, tracing_endpoint: Option<&str>) {
     let filter_layer = match tracing_subscriber::EnvFilter::try_from_default_env()
         .or_else(|_| tracing_subscriber::EnvFilter::try_new("warn"))
     {
@@ -264,12 +269,24 @@ pub fn create_tracing_layer(base_tracing_log_path: PathBuf) {
         _discv5_guard,
     };
 
-    if let Err(e) = tracing_subscriber::fmt()
-        .with_env_filter(filter_layer)
-        .with_writer(std::io::sink)
-        .finish()
-        .with(MetricsLayer)
-        .with(custom_layer)
+    let otlp_layer = match tracing_endpoint.map(tracing_otlp_layer::create_otlp_layer) {
+        Some(Ok(layer)) => Some(layer),
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            None
+        }
+        None => None,
+    };
+
+    // The `RUST_LOG` filter only applies to dependency logging, so that it does not prevent
+    // Lighthouse's spans from being exported.
+    if let Err(e) = tracing_subscriber::registry()
+        .with(
+            MetricsLayer
+                .and_then(custom_layer)
+                .with_filter(filter_layer),
+        )
+        .with(otlp_layer)
         .try_init()
     {
         eprintln!("Failed to initialize dependency logging {e}");
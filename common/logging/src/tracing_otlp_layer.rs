@@ -0,0 +1,47 @@
+//! Exports the tracing spans of Lighthouse's own crates to an OpenTelemetry collector via OTLP.
+
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace, Resource};
+use tracing_core::{LevelFilter, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// The crates whose spans are exported. The spans of dependencies are left to `RUST_LOG`.
+const EXPORTED_TARGETS: &[&str] = &["beacon_chain", "lighthouse_network", "network"];
+
+/// Build a layer which exports spans to the OTLP/gRPC collector at `endpoint`.
+///
+/// Spans are exported in batches by a task on the current Tokio runtime, so this must be called
+/// from within one.
+pub fn create_otlp_layer<S>(endpoint: &str) -> Result<impl Layer<S>, String>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                "lighthouse",
+            )])),
+        )
+        .install_batch(runtime::Tokio)
+        .map_err(|e| format!("Failed to initialize OTLP tracing: {e}"))?;
+
+    let targets = Targets::new().with_targets(
+        EXPORTED_TARGETS
+            .iter()
+            .map(|target| (*target, LevelFilter::INFO)),
+    );
+
+    Ok(tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(targets))
+}
@@ -136,6 +136,24 @@ pub fn invalid_auth(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(InvalidAuthorization(msg))
 }
 
+#[derive(Debug)]
+pub struct MissingAuthorization(pub String);
+
+impl Reject for MissingAuthorization {}
+
+pub fn missing_auth(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(MissingAuthorization(msg))
+}
+
+#[derive(Debug)]
+pub struct RateLimited(pub String);
+
+impl Reject for RateLimited {}
+
+pub fn rate_limited(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(RateLimited(msg))
+}
+
 #[derive(Debug)]
 pub struct IndexedBadRequestErrors {
     pub message: String,
@@ -216,6 +234,12 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
     } else if let Some(e) = err.find::<crate::reject::InvalidAuthorization>() {
         code = StatusCode::FORBIDDEN;
         message = format!("FORBIDDEN: Invalid auth token: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::MissingAuthorization>() {
+        code = StatusCode::UNAUTHORIZED;
+        message = format!("UNAUTHORIZED: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::RateLimited>() {
+        code = StatusCode::TOO_MANY_REQUESTS;
+        message = format!("TOO_MANY_REQUESTS: {}", e.0);
     } else if let Some(e) = err.find::<warp::reject::MissingHeader>() {
         if e.name().eq("Authorization") {
             code = StatusCode::UNAUTHORIZED;
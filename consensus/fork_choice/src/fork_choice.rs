@@ -105,6 +105,9 @@ pub enum ResetPayloadStatuses {
     Always,
     /// Only reset all payload statuses back to "optimistic" when an "invalid" block is present.
     OnlyWithInvalidPayload,
+    /// Reset the payload statuses of the given block and all of its descendants back to
+    /// "optimistic", leaving all other blocks unchanged.
+    FromAncestor(Hash256),
 }
 
 impl ResetPayloadStatuses {
@@ -1419,9 +1422,28 @@ where
             return Ok(proto_array);
         }
 
-        // Reset all blocks back to being "optimistic". This helps recover from an EL consensus
-        // fault where an invalid payload becomes valid.
-        if let Err(e) = proto_array.set_all_blocks_to_optimistic::<E>(spec) {
+        // Reset blocks back to being "optimistic". This helps recover from an EL consensus fault
+        // where an invalid payload becomes valid.
+        let reset_result = match reset_payload_statuses {
+            ResetPayloadStatuses::Always | ResetPayloadStatuses::OnlyWithInvalidPayload => {
+                proto_array.set_all_blocks_to_optimistic::<E>(spec)
+            }
+            ResetPayloadStatuses::FromAncestor(ancestor_root) => {
+                // The ancestor may have been pruned, or the user may have supplied the wrong
+                // root. This isn't a fault in Lighthouse, so don't revert loudly below.
+                if !proto_array.contains_block(&ancestor_root) {
+                    warn!(
+                        log,
+                        "Not resetting payload statuses";
+                        "reason" => "ancestor block is unknown to fork choice",
+                        "ancestor_root" => ?ancestor_root,
+                    );
+                    return Ok(proto_array);
+                }
+                proto_array.set_descendants_to_optimistic::<E>(ancestor_root, spec)
+            }
+        };
+        if let Err(e) = reset_result {
             // If there is an error resetting the optimistic status then log loudly and revert
             // back to a proto-array which does not have the reset applied. This indicates a
             // significant error in Lighthouse and warrants detailed investigation.
@@ -1,5 +1,6 @@
 mod fork_choice;
 mod fork_choice_store;
+mod snapshot_diff;
 
 pub use crate::fork_choice::{
     AttestationFromBlock, Error, ForkChoice, ForkChoiceView, ForkchoiceUpdateParameters,
@@ -10,3 +11,4 @@ pub use fork_choice_store::ForkChoiceStore;
 pub use proto_array::{
     Block as ProtoBlock, ExecutionStatus, InvalidationOperation, ProposerHeadError,
 };
+pub use snapshot_diff::{Change, SnapshotDiff, WeightChange};
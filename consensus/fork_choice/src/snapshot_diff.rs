@@ -0,0 +1,284 @@
+//! Comparison of two snapshots of fork choice, e.g. taken from the
+//! `/lighthouse/debug/fork_choice_snapshot` endpoint of two nodes, or of one node at two times.
+//!
+//! This is intended for offline analysis of why nodes disagree on the head.
+use crate::PersistedForkChoice;
+use proto_array::{core::ProtoArray, ProtoArrayForkChoice};
+use std::collections::HashMap;
+use types::{Checkpoint, Hash256, Slot};
+
+/// A value in the old and the new snapshot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Change<T> {
+    pub old: T,
+    pub new: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    pub fn is_changed(&self) -> bool {
+        self.old != self.new
+    }
+}
+
+/// The change in weight of a block present in both snapshots.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightChange {
+    pub root: Hash256,
+    pub slot: Slot,
+    pub weight: Change<u64>,
+}
+
+impl WeightChange {
+    pub fn delta(&self) -> i128 {
+        i128::from(self.weight.new) - i128::from(self.weight.old)
+    }
+}
+
+/// The differences between an old and a new snapshot of fork choice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotDiff {
+    pub justified_checkpoint: Change<Checkpoint>,
+    pub finalized_checkpoint: Change<Checkpoint>,
+    /// The best descendant of the justified checkpoint, as of the last time fork choice was run
+    /// before each snapshot.
+    pub head: Change<Option<Hash256>>,
+    pub proposer_boost_root: Change<Hash256>,
+    /// Blocks which are only in the new snapshot.
+    pub added: Vec<Hash256>,
+    /// Blocks which are only in the old snapshot, usually because they were pruned.
+    pub removed: Vec<Hash256>,
+    /// Blocks in both snapshots whose weight changed, largest change first.
+    pub weight_changes: Vec<WeightChange>,
+    /// Blocks with an invalid payload in the new snapshot, which were either absent or not known
+    /// to be invalid in the old snapshot.
+    pub new_invalid: Vec<Hash256>,
+}
+
+impl SnapshotDiff {
+    /// Compares two persisted fork choices.
+    pub fn new(old: &PersistedForkChoice, new: &PersistedForkChoice) -> Result<Self, String> {
+        let old = ProtoArrayForkChoice::from_bytes(&old.proto_array_bytes)?;
+        let new = ProtoArrayForkChoice::from_bytes(&new.proto_array_bytes)?;
+        Ok(Self::from_proto_arrays(
+            old.core_proto_array(),
+            new.core_proto_array(),
+        ))
+    }
+
+    pub fn from_proto_arrays(old: &ProtoArray, new: &ProtoArray) -> Self {
+        let old_nodes = old
+            .nodes
+            .iter()
+            .map(|node| (node.root, node))
+            .collect::<HashMap<_, _>>();
+
+        let mut added = vec![];
+        let mut weight_changes = vec![];
+        let mut new_invalid = vec![];
+        for node in &new.nodes {
+            let old_node = old_nodes.get(&node.root);
+            match old_node {
+                Some(old_node) if old_node.weight != node.weight => {
+                    weight_changes.push(WeightChange {
+                        root: node.root,
+                        slot: node.slot,
+                        weight: Change {
+                            old: old_node.weight,
+                            new: node.weight,
+                        },
+                    })
+                }
+                Some(_) => (),
+                None => added.push(node.root),
+            }
+            if node.execution_status.is_invalid()
+                && !old_node.map_or(false, |old_node| old_node.execution_status.is_invalid())
+            {
+                new_invalid.push(node.root);
+            }
+        }
+        weight_changes.sort_by_key(|change| std::cmp::Reverse(change.delta().abs()));
+
+        let removed = old
+            .nodes
+            .iter()
+            .filter(|node| !new.indices.contains_key(&node.root))
+            .map(|node| node.root)
+            .collect();
+
+        Self {
+            justified_checkpoint: Change {
+                old: old.justified_checkpoint,
+                new: new.justified_checkpoint,
+            },
+            finalized_checkpoint: Change {
+                old: old.finalized_checkpoint,
+                new: new.finalized_checkpoint,
+            },
+            head: Change {
+                old: best_descendant_of_justified(old),
+                new: best_descendant_of_justified(new),
+            },
+            proposer_boost_root: Change {
+                old: old.previous_proposer_boost.root,
+                new: new.previous_proposer_boost.root,
+            },
+            added,
+            removed,
+            weight_changes,
+            new_invalid,
+        }
+    }
+}
+
+fn best_descendant_of_justified(proto_array: &ProtoArray) -> Option<Hash256> {
+    let justified_index = *proto_array
+        .indices
+        .get(&proto_array.justified_checkpoint.root)?;
+    let justified = proto_array.nodes.get(justified_index)?;
+    let head = justified
+        .best_descendant
+        .and_then(|index| proto_array.nodes.get(index))
+        .map_or(justified.root, |node| node.root);
+    Some(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proto_array::{Block, ExecutionStatus};
+    use types::{AttestationShufflingId, Epoch, ExecutionBlockHash, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    fn root(i: u64) -> Hash256 {
+        Hash256::from_low_u64_be(i)
+    }
+
+    fn new_fork_choice() -> ProtoArrayForkChoice {
+        let shuffling_id = AttestationShufflingId::from_components(Epoch::new(0), Hash256::zero());
+        let checkpoint = Checkpoint {
+            epoch: Epoch::new(0),
+            root: root(0),
+        };
+        ProtoArrayForkChoice::new::<E>(
+            Slot::new(0),
+            Slot::new(0),
+            Hash256::zero(),
+            checkpoint,
+            checkpoint,
+            shuffling_id.clone(),
+            shuffling_id,
+            ExecutionStatus::Optimistic(ExecutionBlockHash::zero()),
+        )
+        .unwrap()
+    }
+
+    fn add_block(fork_choice: &mut ProtoArrayForkChoice, slot: u64, parent: u64) {
+        let shuffling_id = AttestationShufflingId::from_components(Epoch::new(0), Hash256::zero());
+        let checkpoint = Checkpoint {
+            epoch: Epoch::new(0),
+            root: root(0),
+        };
+        fork_choice
+            .process_block::<E>(
+                Block {
+                    slot: Slot::new(slot),
+                    root: root(slot),
+                    parent_root: Some(root(parent)),
+                    state_root: Hash256::zero(),
+                    target_root: root(0),
+                    current_epoch_shuffling_id: shuffling_id.clone(),
+                    next_epoch_shuffling_id: shuffling_id,
+                    justified_checkpoint: checkpoint,
+                    finalized_checkpoint: checkpoint,
+                    execution_status: ExecutionStatus::Optimistic(ExecutionBlockHash::from_root(
+                        root(slot),
+                    )),
+                    unrealized_justified_checkpoint: Some(checkpoint),
+                    unrealized_finalized_checkpoint: Some(checkpoint),
+                },
+                Slot::new(slot),
+            )
+            .unwrap();
+    }
+
+    fn set_weight(fork_choice: &mut ProtoArrayForkChoice, slot: u64, weight: u64) {
+        let proto_array = fork_choice.core_proto_array_mut();
+        let index = proto_array.indices[&root(slot)];
+        proto_array.nodes[index].weight = weight;
+    }
+
+    #[test]
+    fn identical_snapshots() {
+        let mut fork_choice = new_fork_choice();
+        add_block(&mut fork_choice, 1, 0);
+        let proto_array = fork_choice.core_proto_array();
+
+        let diff = SnapshotDiff::from_proto_arrays(proto_array, proto_array);
+        assert!(!diff.justified_checkpoint.is_changed());
+        assert!(!diff.head.is_changed());
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert!(diff.weight_changes.is_empty());
+        assert!(diff.new_invalid.is_empty());
+    }
+
+    #[test]
+    fn weights_blocks_and_invalid_payloads() {
+        let mut old = new_fork_choice();
+        add_block(&mut old, 1, 0);
+        add_block(&mut old, 2, 1);
+        set_weight(&mut old, 1, 10);
+        set_weight(&mut old, 2, 5);
+
+        let mut new = ProtoArrayForkChoice::from_bytes(&old.as_bytes()).unwrap();
+        add_block(&mut new, 3, 1);
+        set_weight(&mut new, 1, 30);
+        set_weight(&mut new, 2, 4);
+        let proto_array = new.core_proto_array_mut();
+        let index = proto_array.indices[&root(2)];
+        proto_array.nodes[index].execution_status =
+            ExecutionStatus::Invalid(ExecutionBlockHash::from_root(root(2)));
+
+        let diff = SnapshotDiff::from_proto_arrays(old.core_proto_array(), new.core_proto_array());
+        assert_eq!(diff.added, vec![root(3)]);
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.new_invalid, vec![root(2)]);
+        assert_eq!(
+            diff.weight_changes,
+            vec![
+                WeightChange {
+                    root: root(1),
+                    slot: Slot::new(1),
+                    weight: Change { old: 10, new: 30 },
+                },
+                WeightChange {
+                    root: root(2),
+                    slot: Slot::new(2),
+                    weight: Change { old: 5, new: 4 },
+                },
+            ]
+        );
+
+        // The reverse diff sees the new block as removed and nothing newly invalid.
+        let diff = SnapshotDiff::from_proto_arrays(new.core_proto_array(), old.core_proto_array());
+        assert_eq!(diff.removed, vec![root(3)]);
+        assert!(diff.new_invalid.is_empty());
+    }
+
+    #[test]
+    fn from_persisted() {
+        let mut old = new_fork_choice();
+        add_block(&mut old, 1, 0);
+        let mut new = ProtoArrayForkChoice::from_bytes(&old.as_bytes()).unwrap();
+        add_block(&mut new, 2, 1);
+
+        let persisted = |fork_choice: &ProtoArrayForkChoice| PersistedForkChoice {
+            proto_array_bytes: fork_choice.as_bytes(),
+            queued_attestations: vec![],
+        };
+        let diff = SnapshotDiff::new(&persisted(&old), &persisted(&new)).unwrap();
+        assert_eq!(diff.added, vec![root(2)]);
+    }
+}
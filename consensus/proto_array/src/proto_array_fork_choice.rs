@@ -667,14 +667,61 @@ impl ProtoArrayForkChoice {
         &mut self,
         spec: &ChainSpec,
     ) -> Result<(), String> {
+        self.set_blocks_to_optimistic::<E>(None, spec)
+    }
+
+    /// Set the execution status of `ancestor_root` and all of its descendants to be optimistic.
+    ///
+    /// The statuses of all other blocks are unchanged, see `Self::set_all_blocks_to_optimistic`.
+    pub fn set_descendants_to_optimistic<E: EthSpec>(
+        &mut self,
+        ancestor_root: Hash256,
+        spec: &ChainSpec,
+    ) -> Result<(), String> {
+        self.set_blocks_to_optimistic::<E>(Some(ancestor_root), spec)
+    }
+
+    /// Set the execution status of `ancestor_root` and its descendants to be optimistic, or of all
+    /// nodes if `ancestor_root` is `None`.
+    fn set_blocks_to_optimistic<E: EthSpec>(
+        &mut self,
+        ancestor_root: Option<Hash256>,
+        spec: &ChainSpec,
+    ) -> Result<(), String> {
+        // Parents always precede their children in the `proto_array`, so a single forward pass
+        // finds all descendants of `ancestor_root`.
+        let num_nodes = self.proto_array.nodes.len();
+        let should_reset = if let Some(ancestor_root) = ancestor_root {
+            let ancestor_index = *self
+                .proto_array
+                .indices
+                .get(&ancestor_root)
+                .ok_or_else(|| format!("Unknown ancestor root: {:?}", ancestor_root))?;
+            let mut should_reset = vec![false; num_nodes];
+            for (node_index, node) in self.proto_array.nodes.iter().enumerate() {
+                should_reset[node_index] = node_index == ancestor_index
+                    || node
+                        .parent
+                        .and_then(|parent_index| should_reset.get(parent_index).copied())
+                        .unwrap_or(false);
+            }
+            should_reset
+        } else {
+            vec![true; num_nodes]
+        };
+
         // Iterate backwards through all nodes in the `proto_array`. Whilst it's not strictly
         // required to do this process in reverse, it seems natural when we consider how LMD votes
         // are counted.
         //
         // This function will touch all blocks, even those that do not descend from the finalized
-        // block. Since this function is expected to run at start-up during very rare
+        // block. Since this function is expected to run at start-up or by request during very rare
         // circumstances we prefer simplicity over efficiency.
-        for node_index in (0..self.proto_array.nodes.len()).rev() {
+        for node_index in (0..num_nodes).rev() {
+            if !should_reset.get(node_index).copied().unwrap_or(false) {
+                continue;
+            }
+
             let node = self
                 .proto_array
                 .nodes
@@ -4,6 +4,7 @@ mod get_attesting_indices;
 mod get_indexed_attestation;
 mod initiate_validator_exit;
 mod slash_validator;
+mod weak_subjectivity_period;
 
 pub mod altair;
 pub mod base;
@@ -15,6 +16,7 @@ pub use get_attesting_indices::{get_attesting_indices, get_attesting_indices_fro
 pub use get_indexed_attestation::get_indexed_attestation;
 pub use initiate_validator_exit::initiate_validator_exit;
 pub use slash_validator::slash_validator;
+pub use weak_subjectivity_period::{compute_weak_subjectivity_period, SAFETY_DECAY};
 
 use safe_arith::SafeArith;
 use types::{BeaconState, BeaconStateError, EthSpec};
@@ -0,0 +1,172 @@
+use safe_arith::{ArithError, SafeArith};
+use std::cmp::max;
+use types::{BeaconState, ChainSpec, Epoch, EthSpec, Unsigned};
+
+/// The weak subjectivity safety decay, as a percentage of the validator set.
+pub const SAFETY_DECAY: u64 = 10;
+
+const ETH_TO_GWEI: u64 = 1_000_000_000;
+
+/// Compute the weak subjectivity period of `state`, in epochs, as per the weak subjectivity guide.
+///
+/// The active validator count and total active balance are computed from the registry rather than
+/// the state's caches, so that this can be called on any state.
+pub fn compute_weak_subjectivity_period<E: EthSpec>(
+    state: &BeaconState<E>,
+    spec: &ChainSpec,
+) -> Result<Epoch, ArithError> {
+    let current_epoch = state.current_epoch();
+    let (active_validators, total_active_balance) = state
+        .validators()
+        .iter()
+        .filter(|validator| validator.is_active_at(current_epoch))
+        .try_fold((0u64, 0u64), |(count, balance), validator| {
+            Ok::<_, ArithError>((
+                count.safe_add(1)?,
+                balance.safe_add(validator.effective_balance)?,
+            ))
+        })?;
+    let total_active_balance = max(total_active_balance, spec.effective_balance_increment);
+
+    let epochs_for_validator_set_churn = match state {
+        BeaconState::Base(_)
+        | BeaconState::Altair(_)
+        | BeaconState::Bellatrix(_)
+        | BeaconState::Capella(_)
+        | BeaconState::Deneb(_) => {
+            base_epochs_for_validator_set_churn::<E>(active_validators, total_active_balance, spec)?
+        }
+        BeaconState::Electra(_) => {
+            electra_epochs_for_validator_set_churn(total_active_balance, spec)?
+        }
+    };
+
+    Ok(Epoch::new(
+        spec.min_validator_withdrawability_delay
+            .as_u64()
+            .safe_add(epochs_for_validator_set_churn)?,
+    ))
+}
+
+/// The number of epochs added to the withdrawability delay by the phase0 formula, for `n` active
+/// validators with a total balance of `total_active_balance` Gwei.
+fn base_epochs_for_validator_set_churn<E: EthSpec>(
+    n: u64,
+    total_active_balance: u64,
+    spec: &ChainSpec,
+) -> Result<u64, ArithError> {
+    let n = max(n, 1);
+    let t = total_active_balance.safe_div(n)?.safe_div(ETH_TO_GWEI)?;
+    let max_t = spec.max_effective_balance.safe_div(ETH_TO_GWEI)?;
+    let delta = max(
+        spec.min_per_epoch_churn_limit,
+        n.safe_div(spec.churn_limit_quotient)?,
+    );
+    let max_delta = E::MaxDeposits::to_u64().safe_mul(E::slots_per_epoch())?;
+    let d = SAFETY_DECAY;
+
+    let low_balance_threshold = max_t.safe_mul(d.safe_mul(3)?.safe_add(200)?)?;
+    let high_balance_threshold = t.safe_mul(d.safe_mul(12)?.safe_add(200)?)?;
+    if low_balance_threshold < high_balance_threshold {
+        let epochs_for_churn = n
+            .safe_mul(high_balance_threshold.safe_sub(low_balance_threshold)?)?
+            .safe_div(
+                delta
+                    .safe_mul(600)?
+                    .safe_mul(t.safe_mul(2)?.safe_add(max_t)?)?,
+            )?;
+        let epochs_for_top_ups = n
+            .safe_mul(d.safe_mul(3)?.safe_add(200)?)?
+            .safe_div(max_delta.safe_mul(600)?)?;
+        Ok(max(epochs_for_churn, epochs_for_top_ups))
+    } else {
+        n.safe_mul(3)?
+            .safe_mul(d)?
+            .safe_mul(t)?
+            .safe_div(max_delta.safe_mul(200)?.safe_mul(max_t.safe_sub(t)?)?)
+    }
+}
+
+/// The number of epochs added to the withdrawability delay by the Electra formula, for a total
+/// active balance of `total_active_balance` Gwei.
+fn electra_epochs_for_validator_set_churn(
+    total_active_balance: u64,
+    spec: &ChainSpec,
+) -> Result<u64, ArithError> {
+    let churn = max(
+        spec.min_per_epoch_churn_limit_electra,
+        total_active_balance.safe_div(spec.churn_limit_quotient)?,
+    );
+    let delta = churn.safe_sub(churn.safe_rem(spec.effective_balance_increment)?)?;
+    SAFETY_DECAY
+        .safe_mul(total_active_balance)?
+        .safe_div(delta.safe_mul(200)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    /// The weak subjectivity periods of the phase0 guide, as
+    /// `(average balance in ETH, validator count, period in epochs)`.
+    const BASE_WS_PERIODS: &[(u64, u64, u64)] = &[
+        (28, 32_768, 504),
+        (28, 65_536, 752),
+        (28, 131_072, 1_248),
+        (28, 262_144, 2_241),
+        (28, 524_288, 2_241),
+        (28, 1_048_576, 2_241),
+        (32, 32_768, 665),
+        (32, 65_536, 1_075),
+        (32, 131_072, 1_894),
+        (32, 262_144, 3_532),
+        (32, 524_288, 3_532),
+        (32, 1_048_576, 3_532),
+    ];
+
+    /// The weak subjectivity periods of the Electra guide, as
+    /// `(total active balance in ETH, period in epochs)`.
+    const ELECTRA_WS_PERIODS: &[(u64, u64)] = &[
+        (1_048_576, 665),
+        (2_097_152, 1_075),
+        (4_194_304, 1_894),
+        (8_388_608, 3_532),
+        (16_777_216, 3_532),
+        (33_554_432, 3_532),
+    ];
+
+    #[test]
+    fn base_ws_periods_match_guide() {
+        let spec = MainnetEthSpec::default_spec();
+        for &(average_balance, validator_count, expected) in BASE_WS_PERIODS {
+            let total_active_balance = average_balance * validator_count * ETH_TO_GWEI;
+            let churn = base_epochs_for_validator_set_churn::<MainnetEthSpec>(
+                validator_count,
+                total_active_balance,
+                &spec,
+            )
+            .unwrap();
+            assert_eq!(
+                spec.min_validator_withdrawability_delay.as_u64() + churn,
+                expected,
+                "average_balance: {average_balance}, validator_count: {validator_count}"
+            );
+        }
+    }
+
+    #[test]
+    fn electra_ws_periods_match_guide() {
+        let spec = MainnetEthSpec::default_spec();
+        for &(total_active_balance, expected) in ELECTRA_WS_PERIODS {
+            let churn =
+                electra_epochs_for_validator_set_churn(total_active_balance * ETH_TO_GWEI, &spec)
+                    .unwrap();
+            assert_eq!(
+                spec.min_validator_withdrawability_delay.as_u64() + churn,
+                expected,
+                "total_active_balance: {total_active_balance}"
+            );
+        }
+    }
+}
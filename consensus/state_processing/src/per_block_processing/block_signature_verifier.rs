@@ -8,7 +8,7 @@ use rayon::prelude::*;
 use std::borrow::Cow;
 use types::{
     AbstractExecPayload, BeaconState, BeaconStateError, ChainSpec, EthSpec, Hash256,
-    SignedBeaconBlock,
+    SignedBeaconBlock, Slot,
 };
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -67,6 +67,29 @@ impl From<BlockOperationError<AttestationInvalid>> for Error {
     }
 }
 
+/// The message signed by a signature included by a `BlockSignatureVerifier`.
+///
+/// Operations are identified by their index in the block body.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureKind {
+    BlockProposal,
+    RandaoReveal,
+    ProposerSlashing(usize),
+    AttesterSlashing(usize),
+    Attestation(usize),
+    VoluntaryExit(usize),
+    SyncAggregate,
+    BlsToExecutionChange(usize),
+}
+
+/// Identifies a signature included by a `BlockSignatureVerifier`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignatureOrigin {
+    /// The slot of the block containing the signature.
+    pub slot: Slot,
+    pub kind: SignatureKind,
+}
+
 /// Reads the BLS signatures and keys from a `SignedBeaconBlock`, storing them as a `Vec<SignatureSet>`.
 ///
 /// This allows for optimizations related to batch BLS operations (see the
@@ -82,6 +105,8 @@ where
     state: &'a BeaconState<E>,
     spec: &'a ChainSpec,
     sets: ParallelSignatureSets<'a>,
+    /// The origin of each set in `sets`.
+    origins: Vec<SignatureOrigin>,
 }
 
 #[derive(Default)]
@@ -115,6 +140,7 @@ where
             state,
             spec,
             sets: ParallelSignatureSets::default(),
+            origins: vec![],
         }
     }
 
@@ -190,7 +216,7 @@ where
             verified_proposer_index,
             self.spec,
         )?;
-        self.sets.push(set);
+        self.push(block.slot(), SignatureKind::BlockProposal, set);
         Ok(())
     }
 
@@ -207,7 +233,7 @@ where
             verified_proposer_index,
             self.spec,
         )?;
-        self.sets.push(set);
+        self.push(block.slot(), SignatureKind::RandaoReveal, set);
         Ok(())
     }
 
@@ -225,7 +251,8 @@ where
             .body()
             .proposer_slashings()
             .iter()
-            .try_for_each(|proposer_slashing| {
+            .enumerate()
+            .try_for_each(|(i, proposer_slashing)| {
                 let (set_1, set_2) = proposer_slashing_signature_set(
                     self.state,
                     self.get_pubkey.clone(),
@@ -233,8 +260,8 @@ where
                     self.spec,
                 )?;
 
-                self.sets.push(set_1);
-                self.sets.push(set_2);
+                self.push(block.slot(), SignatureKind::ProposerSlashing(i), set_1);
+                self.push(block.slot(), SignatureKind::ProposerSlashing(i), set_2);
 
                 Ok(())
             })
@@ -254,7 +281,8 @@ where
             .body()
             .attester_slashings()
             .iter()
-            .try_for_each(|attester_slashing| {
+            .enumerate()
+            .try_for_each(|(i, attester_slashing)| {
                 let (set_1, set_2) = attester_slashing_signature_sets(
                     self.state,
                     self.get_pubkey.clone(),
@@ -262,8 +290,8 @@ where
                     self.spec,
                 )?;
 
-                self.sets.push(set_1);
-                self.sets.push(set_2);
+                self.push(block.slot(), SignatureKind::AttesterSlashing(i), set_1);
+                self.push(block.slot(), SignatureKind::AttesterSlashing(i), set_2);
 
                 Ok(())
             })
@@ -284,16 +312,18 @@ where
             .body()
             .attestations()
             .iter()
-            .try_for_each(|attestation| {
+            .enumerate()
+            .try_for_each(|(i, attestation)| {
                 let indexed_attestation = ctxt.get_indexed_attestation(self.state, attestation)?;
 
-                self.sets.push(indexed_attestation_signature_set(
+                let set = indexed_attestation_signature_set(
                     self.state,
                     self.get_pubkey.clone(),
                     &attestation.signature,
                     indexed_attestation,
                     self.spec,
-                )?);
+                )?;
+                self.push(block.slot(), SignatureKind::Attestation(i), set);
                 Ok(())
             })
             .map_err(Error::into)
@@ -313,11 +343,12 @@ where
             .body()
             .voluntary_exits()
             .iter()
-            .try_for_each(|exit| {
+            .enumerate()
+            .try_for_each(|(i, exit)| {
                 let exit =
                     exit_signature_set(self.state, self.get_pubkey.clone(), exit, self.spec)?;
 
-                self.sets.push(exit);
+                self.push(block.slot(), SignatureKind::VoluntaryExit(i), exit);
 
                 Ok(())
             })
@@ -337,7 +368,7 @@ where
                 self.state,
                 self.spec,
             )? {
-                self.sets.push(signature_set);
+                self.push(block.slot(), SignatureKind::SyncAggregate, signature_set);
             }
         }
         Ok(())
@@ -350,12 +381,13 @@ where
     ) -> Result<()> {
         // To improve performance we might want to decompress the withdrawal pubkeys in parallel.
         if let Ok(bls_to_execution_changes) = block.message().body().bls_to_execution_changes() {
-            for bls_to_execution_change in bls_to_execution_changes {
-                self.sets.push(bls_execution_change_signature_set(
+            for (i, bls_to_execution_change) in bls_to_execution_changes.iter().enumerate() {
+                let set = bls_execution_change_signature_set(
                     self.state,
                     bls_to_execution_change,
                     self.spec,
-                )?);
+                )?;
+                self.push(block.slot(), SignatureKind::BlsToExecutionChange(i), set);
             }
         }
         Ok(())
@@ -365,13 +397,29 @@ where
     /// all the signatures are valid.
     ///
     /// See `ParallelSignatureSets::verify` for more info.
-    pub fn verify(self) -> Result<()> {
+    pub fn verify(&self) -> Result<()> {
         if self.sets.verify() {
             Ok(())
         } else {
             Err(Error::SignatureInvalid)
         }
     }
+
+    /// Returns the origin of an invalid signature included in `self`, or `None` if all signatures
+    /// are valid.
+    ///
+    /// This is much slower than `Self::verify` and is intended for diagnosing a batch which has
+    /// already failed `Self::verify`.
+    pub fn find_invalid(&self) -> Option<SignatureOrigin> {
+        self.sets
+            .find_invalid()
+            .and_then(|i| self.origins.get(i).copied())
+    }
+
+    fn push(&mut self, slot: Slot, kind: SignatureKind, set: SignatureSet<'a>) {
+        self.sets.push(set);
+        self.origins.push(SignatureOrigin { slot, kind });
+    }
 }
 
 impl<'a> ParallelSignatureSets<'a> {
@@ -393,13 +441,44 @@ impl<'a> ParallelSignatureSets<'a> {
     ///
     /// Uses `rayon` to do a map-reduce of Vitalik's method across multiple cores.
     #[must_use]
-    pub fn verify(self) -> bool {
-        let num_sets = self.sets.len();
-        let num_chunks = std::cmp::max(1, num_sets / rayon::current_num_threads());
-        self.sets
-            .into_par_iter()
-            .chunks(num_chunks)
-            .map(|chunk| verify_signature_sets(chunk.iter()))
-            .reduce(|| true, |current, this| current && this)
+    pub fn verify(&self) -> bool {
+        verify_parallel(&self.sets)
     }
+
+    /// Returns the index of an invalid signature set in `self`, or `None` if all the sets are
+    /// valid.
+    ///
+    /// The sets are bisected, verifying one half as a batch to determine which half contains an
+    /// invalid signature. Finding the invalid set therefore requires `O(log n)` batch
+    /// verifications, rather than the `n` required to verify each set individually.
+    ///
+    /// This assumes that `Self::verify` has already returned `false`, and does not verify the
+    /// whole batch again.
+    pub fn find_invalid(&self) -> Option<usize> {
+        // If `self` contains an invalid set, `self.sets[offset..offset + len]` always contains
+        // one.
+        let (mut offset, mut len) = (0, self.sets.len());
+        while len > 1 {
+            let mid = len / 2;
+            if verify_parallel(&self.sets[offset..offset + mid]) {
+                offset += mid;
+                len -= mid;
+            } else {
+                len = mid;
+            }
+        }
+
+        // Only the last remaining set is left unverified, so check it to rule out a batch with
+        // no invalid sets.
+        let remaining = self.sets.get(offset..offset + len)?;
+        (!remaining.is_empty() && !verify_parallel(remaining)).then_some(offset)
+    }
+}
+
+/// Verifies `sets` as a batch, using `rayon` to split the batch across multiple cores.
+fn verify_parallel(sets: &[SignatureSet]) -> bool {
+    let num_chunks = std::cmp::max(1, sets.len() / rayon::current_num_threads());
+    sets.par_chunks(num_chunks)
+        .map(|chunk| verify_signature_sets(chunk.iter()))
+        .reduce(|| true, |current, this| current && this)
 }
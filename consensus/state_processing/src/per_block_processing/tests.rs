@@ -5,6 +5,10 @@ use crate::per_block_processing::errors::{
     DepositInvalid, HeaderInvalid, IndexedAttestationInvalid, IntoWithIndex,
     ProposerSlashingInvalid,
 };
+use crate::per_block_processing::{
+    block_signature_verifier::{BlockSignatureVerifier, SignatureKind, SignatureOrigin},
+    signature_sets::get_pubkey_from_state,
+};
 use crate::{per_block_processing, BlockReplayError, BlockReplayer};
 use crate::{
     per_block_processing::{process_operations, verify_exit::verify_exit},
@@ -13,6 +17,7 @@ use crate::{
 use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
 use lazy_static::lazy_static;
 use ssz_types::Bitfield;
+use std::borrow::Cow;
 use test_utils::generate_deterministic_keypairs;
 use types::*;
 
@@ -204,6 +209,68 @@ async fn invalid_randao_reveal_signature() {
     assert_eq!(result, Err(BlockProcessingError::RandaoSignatureInvalid));
 }
 
+#[tokio::test]
+async fn find_invalid_attestation_signature() {
+    let spec = MainnetEthSpec::default_spec();
+    let harness = get_harness::<MainnetEthSpec>(EPOCH_OFFSET, VALIDATOR_COUNT).await;
+
+    let state = harness.get_current_state();
+    let slot = state.slot();
+
+    let ((signed_block, _), state) = harness
+        .make_block_with_modifier(state, slot + 1, |block| {
+            block.body_mut().attestations_mut()[0].signature = AggregateSignature::empty();
+        })
+        .await;
+
+    let mut verifier = BlockSignatureVerifier::new(
+        &state,
+        |i| get_pubkey_from_state(&state, i),
+        |pk_bytes| pk_bytes.decompress().ok().map(Cow::Owned),
+        &spec,
+    );
+    let mut ctxt = ConsensusContext::new(signed_block.slot());
+    verifier
+        .include_all_signatures(&signed_block, &mut ctxt)
+        .unwrap();
+
+    assert!(verifier.verify().is_err());
+    assert_eq!(
+        verifier.find_invalid(),
+        Some(SignatureOrigin {
+            slot: signed_block.slot(),
+            kind: SignatureKind::Attestation(0),
+        })
+    );
+}
+
+#[tokio::test]
+async fn find_invalid_signature_in_valid_block() {
+    let spec = MainnetEthSpec::default_spec();
+    let harness = get_harness::<MainnetEthSpec>(EPOCH_OFFSET, VALIDATOR_COUNT).await;
+
+    let state = harness.get_current_state();
+    let slot = state.slot();
+
+    let ((signed_block, _), state) = harness
+        .make_block_with_modifier(state, slot + 1, |_| ())
+        .await;
+
+    let mut verifier = BlockSignatureVerifier::new(
+        &state,
+        |i| get_pubkey_from_state(&state, i),
+        |pk_bytes| pk_bytes.decompress().ok().map(Cow::Owned),
+        &spec,
+    );
+    let mut ctxt = ConsensusContext::new(signed_block.slot());
+    verifier
+        .include_all_signatures(&signed_block, &mut ctxt)
+        .unwrap();
+
+    assert!(verifier.verify().is_ok());
+    assert_eq!(verifier.find_invalid(), None);
+}
+
 #[tokio::test]
 async fn valid_4_deposits() {
     let spec = MainnetEthSpec::default_spec();
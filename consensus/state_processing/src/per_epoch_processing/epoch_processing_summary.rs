@@ -51,6 +51,15 @@ impl<E: EthSpec> ParticipationEpochSummary<E> {
         }
     }
 
+    /// The number of validators in the registry prior to mutation.
+    pub fn num_validators(&self) -> usize {
+        self.validators.len()
+    }
+
+    pub fn previous_epoch(&self) -> Epoch {
+        self.previous_epoch
+    }
+
     pub fn is_active_and_unslashed(&self, val_index: usize, epoch: Epoch) -> bool {
         self.validators
             .get(val_index)
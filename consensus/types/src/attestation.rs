@@ -6,11 +6,11 @@ use test_random_derive::TestRandom;
 use tree_hash_derive::TreeHash;
 
 use crate::slot_data::SlotData;
-use crate::{test_utils::TestRandom, Hash256, Slot};
+use crate::{test_utils::TestRandom, Hash256, Slot, Unsigned};
 
 use super::{
-    AggregateSignature, AttestationData, BitList, ChainSpec, Domain, EthSpec, Fork, SecretKey,
-    Signature, SignedRoot,
+    AggregateSignature, AttestationData, BitList, BitVector, ChainSpec, Domain, EthSpec, Fork,
+    SecretKey, Signature, SignedRoot,
 };
 
 #[derive(Debug, PartialEq)]
@@ -18,6 +18,19 @@ pub enum Error {
     SszTypesError(ssz_types::Error),
     AlreadySigned(usize),
     SubnetCountIsZero(ArithError),
+    /// The committee index does not fit in the `committee_bits` of an Electra attestation.
+    InvalidCommitteeIndex(u64),
+    /// An Electra attestation can only be converted to a pre-Electra attestation if it covers
+    /// exactly one committee.
+    NotSingleCommittee(usize),
+    /// An Electra attestation has a non-zero `data.index`.
+    NonZeroDataIndex(u64),
+    /// No aggregates were supplied to build an on-chain aggregate from.
+    NoAggregates,
+    /// The aggregates of an on-chain aggregate attest to different data.
+    InconsistentData,
+    /// More than one aggregate of an on-chain aggregate covers this committee.
+    DuplicateCommittee(u64),
 }
 
 /// Details an attestation that can be slashable.
@@ -111,12 +124,268 @@ impl<E: EthSpec> Attestation<E> {
     }
 }
 
+impl<E: EthSpec> Attestation<E> {
+    /// Converts a pre-Electra attestation into its EIP-7549 representation.
+    ///
+    /// The committee index is moved out of `data.index` (which becomes `0`) and into the
+    /// `committee_bits` of the returned attestation.
+    pub fn to_electra(&self) -> Result<AttestationElectra<E>, Error> {
+        let committee_index = self.data.index;
+        let mut committee_bits = BitVector::default();
+        committee_bits
+            .set(committee_index as usize, true)
+            .map_err(|_| Error::InvalidCommitteeIndex(committee_index))?;
+
+        let aggregation_bits = copy_bits(&self.aggregation_bits)?;
+
+        let mut data = self.data.clone();
+        data.index = 0;
+
+        Ok(AttestationElectra {
+            aggregation_bits,
+            data,
+            signature: self.signature.clone(),
+            committee_bits,
+        })
+    }
+}
+
 impl<E: EthSpec> SlotData for Attestation<E> {
     fn get_slot(&self) -> Slot {
         self.data.slot
     }
 }
 
+/// An attestation in the format introduced by EIP-7549 (Electra).
+///
+/// The committee index is no longer part of the signed `AttestationData`. Instead, the
+/// `committee_bits` field records which committees of the slot are included, and the
+/// `aggregation_bits` span the concatenation of those committees (in ascending committee order).
+///
+/// This type is not yet used by gossip, the operation pool, block bodies or the HTTP API, all of
+/// which still carry the pre-Electra `Attestation` and are marked `TODO(electra)`. Use
+/// `Attestation::to_electra` and `AttestationElectra::to_base` to convert at the boundary.
+#[derive(
+    arbitrary::Arbitrary,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    TreeHash,
+    TestRandom,
+    Derivative,
+)]
+#[derivative(PartialEq, Hash(bound = "E: EthSpec"))]
+#[serde(bound = "E: EthSpec")]
+#[arbitrary(bound = "E: EthSpec")]
+pub struct AttestationElectra<E: EthSpec> {
+    pub aggregation_bits: BitList<E::MaxValidatorsPerSlot>,
+    pub data: AttestationData,
+    pub signature: AggregateSignature,
+    pub committee_bits: BitVector<E::MaxCommitteesPerSlot>,
+}
+
+impl<E: EthSpec> AttestationElectra<E> {
+    /// Returns the indices of all committees included in this attestation, in ascending order.
+    pub fn get_committee_indices(&self) -> Vec<u64> {
+        self.committee_bits
+            .iter()
+            .enumerate()
+            .filter_map(|(index, bit)| bit.then_some(index as u64))
+            .collect()
+    }
+
+    /// Returns the committee index if this attestation covers exactly one committee.
+    ///
+    /// This is always the case for unaggregated attestations and for aggregates produced by a
+    /// single committee's aggregator.
+    pub fn committee_index(&self) -> Option<u64> {
+        match self.get_committee_indices().as_slice() {
+            [index] => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Returns the committee index of an attestation received on gossip.
+    ///
+    /// Both the `beacon_attestation_{subnet_id}` and `beacon_aggregate_and_proof` topics require
+    /// the attestation to cover exactly one committee, with a `data.index` of `0`.
+    pub fn gossip_committee_index(&self) -> Result<u64, Error> {
+        if self.data.index != 0 {
+            return Err(Error::NonZeroDataIndex(self.data.index));
+        }
+        let committee_indices = self.get_committee_indices();
+        match committee_indices.as_slice() {
+            [index] => Ok(*index),
+            _ => Err(Error::NotSingleCommittee(committee_indices.len())),
+        }
+    }
+
+    /// Combines the aggregates of distinct committees into a single attestation for inclusion in
+    /// a block, as per `compute_on_chain_aggregate`.
+    ///
+    /// Each of `aggregates` must cover exactly one committee, and all must attest to the same
+    /// data. The aggregation bits of the result are those of each aggregate in committee order.
+    pub fn from_committee_aggregates(aggregates: &[Self]) -> Result<Self, Error> {
+        let mut aggregates = aggregates
+            .iter()
+            .map(|aggregate| Ok((aggregate.gossip_committee_index()?, aggregate)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        aggregates.sort_by_key(|(committee_index, _)| *committee_index);
+
+        let data = aggregates
+            .first()
+            .map(|(_, aggregate)| aggregate.data.clone())
+            .ok_or(Error::NoAggregates)?;
+        let mut committee_bits = BitVector::default();
+        let mut signature = AggregateSignature::infinity();
+        for (committee_index, aggregate) in &aggregates {
+            if aggregate.data != data {
+                return Err(Error::InconsistentData);
+            }
+            let committee_index = *committee_index as usize;
+            if committee_bits
+                .get(committee_index)
+                .map_err(Error::SszTypesError)?
+            {
+                return Err(Error::DuplicateCommittee(committee_index as u64));
+            }
+            committee_bits
+                .set(committee_index, true)
+                .map_err(Error::SszTypesError)?;
+            signature.add_assign_aggregate(&aggregate.signature);
+        }
+
+        let bits = aggregates
+            .iter()
+            .flat_map(|(_, aggregate)| aggregate.aggregation_bits.iter())
+            .collect::<Vec<_>>();
+        let mut aggregation_bits =
+            BitList::with_capacity(bits.len()).map_err(Error::SszTypesError)?;
+        for (i, bit) in bits.into_iter().enumerate() {
+            aggregation_bits.set(i, bit).map_err(Error::SszTypesError)?;
+        }
+
+        Ok(Self {
+            aggregation_bits,
+            data,
+            signature,
+            committee_bits,
+        })
+    }
+
+    /// Are the aggregation bitfields of these attestations disjoint?
+    ///
+    /// Bitfields are only comparable if both attestations cover the same set of committees.
+    pub fn signers_disjoint_from(&self, other: &Self) -> bool {
+        self.committee_bits == other.committee_bits
+            && self
+                .aggregation_bits
+                .intersection(&other.aggregation_bits)
+                .is_zero()
+    }
+
+    /// Aggregate another attestation into this one.
+    ///
+    /// The aggregation bitfields must be disjoint, and the data and committee bits must be the
+    /// same.
+    pub fn aggregate(&mut self, other: &Self) {
+        debug_assert_eq!(self.data, other.data);
+        debug_assert!(self.signers_disjoint_from(other));
+
+        self.aggregation_bits = self.aggregation_bits.union(&other.aggregation_bits);
+        self.signature.add_assign_aggregate(&other.signature);
+    }
+
+    /// Signs `self`, setting the `committee_position`'th bit of `aggregation_bits` to `true`.
+    ///
+    /// Returns an `AlreadySigned` error if the `committee_position`'th bit is already `true`.
+    pub fn sign(
+        &mut self,
+        secret_key: &SecretKey,
+        committee_position: usize,
+        fork: &Fork,
+        genesis_validators_root: Hash256,
+        spec: &ChainSpec,
+    ) -> Result<(), Error> {
+        let domain = spec.get_domain(
+            self.data.target.epoch,
+            Domain::BeaconAttester,
+            fork,
+            genesis_validators_root,
+        );
+        let message = self.data.signing_root(domain);
+
+        self.add_signature(&secret_key.sign(message), committee_position)
+    }
+
+    /// Adds `signature` to `self` and sets the `committee_position`'th bit of `aggregation_bits` to `true`.
+    ///
+    /// Returns an `AlreadySigned` error if the `committee_position`'th bit is already `true`.
+    pub fn add_signature(
+        &mut self,
+        signature: &Signature,
+        committee_position: usize,
+    ) -> Result<(), Error> {
+        if self
+            .aggregation_bits
+            .get(committee_position)
+            .map_err(Error::SszTypesError)?
+        {
+            Err(Error::AlreadySigned(committee_position))
+        } else {
+            self.aggregation_bits
+                .set(committee_position, true)
+                .map_err(Error::SszTypesError)?;
+
+            self.signature.add_assign(signature);
+
+            Ok(())
+        }
+    }
+
+    /// Converts this attestation into the pre-Electra representation, for use by code paths that
+    /// have not yet been updated to handle multi-committee attestations.
+    ///
+    /// Returns an error if the attestation does not cover exactly one committee.
+    pub fn to_base(&self) -> Result<Attestation<E>, Error> {
+        let committee_indices = self.get_committee_indices();
+        let [committee_index] = committee_indices.as_slice() else {
+            return Err(Error::NotSingleCommittee(committee_indices.len()));
+        };
+
+        let aggregation_bits = copy_bits(&self.aggregation_bits)?;
+
+        let mut data = self.data.clone();
+        data.index = *committee_index;
+
+        Ok(Attestation {
+            aggregation_bits,
+            data,
+            signature: self.signature.clone(),
+        })
+    }
+}
+
+impl<E: EthSpec> SlotData for AttestationElectra<E> {
+    fn get_slot(&self) -> Slot {
+        self.data.slot
+    }
+}
+
+/// Copy the bits of `source` into a new bitlist with a different maximum length.
+fn copy_bits<N: Unsigned + Clone, M: Unsigned + Clone>(
+    source: &BitList<N>,
+) -> Result<BitList<M>, Error> {
+    let mut bits = BitList::with_capacity(source.len()).map_err(Error::SszTypesError)?;
+    for (i, bit) in source.iter().enumerate() {
+        bits.set(i, bit).map_err(Error::SszTypesError)?;
+    }
+    Ok(bits)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,4 +418,110 @@ mod tests {
     }
 
     ssz_and_tree_hash_tests!(Attestation<MainnetEthSpec>);
+
+    mod electra {
+        use super::*;
+        use crate::test_utils::{SeedableRng, XorShiftRng};
+
+        ssz_and_tree_hash_tests!(AttestationElectra<MainnetEthSpec>);
+
+        fn base_attestation(committee_index: u64) -> Attestation<MainnetEthSpec> {
+            let mut rng = XorShiftRng::from_seed([42; 16]);
+            let mut attestation = Attestation::<MainnetEthSpec>::random_for_test(&mut rng);
+            attestation.data.index = committee_index;
+            attestation
+        }
+
+        #[test]
+        fn base_to_electra_round_trip() {
+            let base = base_attestation(3);
+            let electra = base.to_electra().unwrap();
+
+            assert_eq!(electra.data.index, 0);
+            assert_eq!(electra.committee_index(), Some(3));
+            assert_eq!(electra.get_committee_indices(), vec![3]);
+            assert_eq!(electra.aggregation_bits.len(), base.aggregation_bits.len());
+
+            assert_eq!(electra.to_base().unwrap(), base);
+        }
+
+        #[test]
+        fn invalid_committee_index() {
+            let base = base_attestation(MainnetEthSpec::max_committees_per_slot() as u64);
+            assert_eq!(
+                base.to_electra(),
+                Err(Error::InvalidCommitteeIndex(
+                    MainnetEthSpec::max_committees_per_slot() as u64
+                ))
+            );
+        }
+
+        #[test]
+        fn multi_committee_to_base() {
+            let mut electra = base_attestation(1).to_electra().unwrap();
+            electra.committee_bits.set(5, true).unwrap();
+
+            assert_eq!(electra.get_committee_indices(), vec![1, 5]);
+            assert_eq!(electra.committee_index(), None);
+            assert_eq!(electra.to_base(), Err(Error::NotSingleCommittee(2)));
+        }
+
+        #[test]
+        fn gossip_committee_index() {
+            let mut electra = base_attestation(4).to_electra().unwrap();
+            assert_eq!(electra.gossip_committee_index(), Ok(4));
+
+            electra.data.index = 4;
+            assert_eq!(
+                electra.gossip_committee_index(),
+                Err(Error::NonZeroDataIndex(4))
+            );
+
+            electra.data.index = 0;
+            electra.committee_bits.set(7, true).unwrap();
+            assert_eq!(
+                electra.gossip_committee_index(),
+                Err(Error::NotSingleCommittee(2))
+            );
+        }
+
+        #[test]
+        fn on_chain_aggregate() {
+            let mut first = base_attestation(5);
+            first.aggregation_bits = BitList::with_capacity(2).unwrap();
+            first.aggregation_bits.set(1, true).unwrap();
+            let mut second = base_attestation(2);
+            second.aggregation_bits = BitList::with_capacity(3).unwrap();
+            second.aggregation_bits.set(0, true).unwrap();
+            let first = first.to_electra().unwrap();
+            let second = second.to_electra().unwrap();
+
+            let aggregate =
+                AttestationElectra::from_committee_aggregates(&[first.clone(), second.clone()])
+                    .unwrap();
+            assert_eq!(aggregate.get_committee_indices(), vec![2, 5]);
+            // The bits of committee 2 come before those of committee 5.
+            assert_eq!(
+                aggregate.aggregation_bits.iter().collect::<Vec<_>>(),
+                vec![true, false, false, false, true]
+            );
+            assert_eq!(aggregate.data, first.data);
+
+            assert_eq!(
+                AttestationElectra::<MainnetEthSpec>::from_committee_aggregates(&[]),
+                Err(Error::NoAggregates)
+            );
+            assert_eq!(
+                AttestationElectra::from_committee_aggregates(&[first.clone(), first.clone()]),
+                Err(Error::DuplicateCommittee(5))
+            );
+
+            let mut other_data = second;
+            other_data.data.slot += 1;
+            assert_eq!(
+                AttestationElectra::from_committee_aggregates(&[first, other_data]),
+                Err(Error::InconsistentData)
+            );
+        }
+    }
 }
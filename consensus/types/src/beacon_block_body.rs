@@ -64,6 +64,8 @@ pub struct BeaconBlockBody<E: EthSpec, Payload: AbstractExecPayload<E> = FullPay
     pub graffiti: Graffiti,
     pub proposer_slashings: VariableList<ProposerSlashing, E::MaxProposerSlashings>,
     pub attester_slashings: VariableList<AttesterSlashing<E>, E::MaxAttesterSlashings>,
+    // TODO(electra): Electra bodies should carry `AttestationElectra`, which requires
+    // `Attestation` to become a superstruct.
     pub attestations: VariableList<Attestation<E>, E::MaxAttestations>,
     pub deposits: VariableList<Deposit, E::MaxDeposits>,
     pub voluntary_exits: VariableList<SignedVoluntaryExit, E::MaxVoluntaryExits>,
@@ -123,132 +125,105 @@ impl<'a, E: EthSpec, Payload: AbstractExecPayload<E>> BeaconBlockBodyRef<'a, E,
         &self,
         index: usize,
     ) -> Result<FixedVector<Hash256, E::KzgCommitmentInclusionProofDepth>, Error> {
-        match self {
+        let kzg_commitments_proof = self.kzg_commitments_merkle_proof()?;
+        self.complete_kzg_commitment_merkle_proof(index, &kzg_commitments_proof)
+    }
+
+    /// Produces the proof of inclusion for a `KzgCommitment` in `self.blob_kzg_commitments`
+    /// at `index`, using an existing proof of inclusion for `self.blob_kzg_commitments` in the
+    /// body (see `kzg_commitments_merkle_proof`).
+    ///
+    /// This avoids re-hashing the whole body when producing proofs for every blob in a block.
+    pub(crate) fn complete_kzg_commitment_merkle_proof(
+        &self,
+        index: usize,
+        kzg_commitments_proof: &[Hash256],
+    ) -> Result<FixedVector<Hash256, E::KzgCommitmentInclusionProofDepth>, Error> {
+        // We compute the branches by generating 2 merkle trees:
+        // 1. Merkle tree for the `blob_kzg_commitments` List object
+        // 2. Merkle tree for the `BeaconBlockBody` container
+        // We then merge the branches for both the trees all the way up to the root.
+        let blob_kzg_commitments = self.blob_kzg_commitments()?;
+
+        // Part1 (Branches for the subtree rooted at `blob_kzg_commitments`)
+        //
+        // Branches for `blob_kzg_commitments` without length mix-in
+        let depth = E::max_blob_commitments_per_block()
+            .next_power_of_two()
+            .ilog2();
+        let leaves: Vec<_> = blob_kzg_commitments
+            .iter()
+            .map(|commitment| commitment.tree_hash_root())
+            .collect();
+        let tree = MerkleTree::create(&leaves, depth as usize);
+        let (_, mut proof) = tree
+            .generate_proof(index, depth as usize)
+            .map_err(Error::MerkleTreeError)?;
+
+        // Add the branch corresponding to the length mix-in.
+        let length = blob_kzg_commitments.len();
+        let usize_len = std::mem::size_of::<usize>();
+        let mut length_bytes = [0; BYTES_PER_CHUNK];
+        length_bytes
+            .get_mut(0..usize_len)
+            .ok_or(Error::MerkleTreeError(MerkleTreeError::PleaseNotifyTheDevs))?
+            .copy_from_slice(&length.to_le_bytes());
+        let length_root = Hash256::from_slice(length_bytes.as_slice());
+        proof.push(length_root);
+
+        // Part 2
+        // Join the proofs for the subtree and the main tree
+        proof.extend_from_slice(kzg_commitments_proof);
+
+        debug_assert_eq!(proof.len(), E::kzg_proof_inclusion_proof_depth());
+        Ok(proof.into())
+    }
+
+    /// Produces the proof of inclusion for `self.blob_kzg_commitments` in the body.
+    ///
+    /// This requires hashing every field of the body, so callers producing proofs for several
+    /// blobs should compute it once and pass it to `complete_kzg_commitment_merkle_proof`.
+    pub fn kzg_commitments_merkle_proof(&self) -> Result<Vec<Hash256>, Error> {
+        // Branches for `BeaconBlockBody` container
+        let leaves = match self {
             Self::Base(_) | Self::Altair(_) | Self::Bellatrix(_) | Self::Capella(_) => {
-                Err(Error::IncorrectStateVariant)
-            }
-            Self::Deneb(body) => {
-                // We compute the branches by generating 2 merkle trees:
-                // 1. Merkle tree for the `blob_kzg_commitments` List object
-                // 2. Merkle tree for the `BeaconBlockBody` container
-                // We then merge the branches for both the trees all the way up to the root.
-
-                // Part1 (Branches for the subtree rooted at `blob_kzg_commitments`)
-                //
-                // Branches for `blob_kzg_commitments` without length mix-in
-                let depth = E::max_blob_commitments_per_block()
-                    .next_power_of_two()
-                    .ilog2();
-                let leaves: Vec<_> = body
-                    .blob_kzg_commitments
-                    .iter()
-                    .map(|commitment| commitment.tree_hash_root())
-                    .collect();
-                let tree = MerkleTree::create(&leaves, depth as usize);
-                let (_, mut proof) = tree
-                    .generate_proof(index, depth as usize)
-                    .map_err(Error::MerkleTreeError)?;
-
-                // Add the branch corresponding to the length mix-in.
-                let length = body.blob_kzg_commitments.len();
-                let usize_len = std::mem::size_of::<usize>();
-                let mut length_bytes = [0; BYTES_PER_CHUNK];
-                length_bytes
-                    .get_mut(0..usize_len)
-                    .ok_or(Error::MerkleTreeError(MerkleTreeError::PleaseNotifyTheDevs))?
-                    .copy_from_slice(&length.to_le_bytes());
-                let length_root = Hash256::from_slice(length_bytes.as_slice());
-                proof.push(length_root);
-
-                // Part 2
-                // Branches for `BeaconBlockBody` container
-                let leaves = [
-                    body.randao_reveal.tree_hash_root(),
-                    body.eth1_data.tree_hash_root(),
-                    body.graffiti.tree_hash_root(),
-                    body.proposer_slashings.tree_hash_root(),
-                    body.attester_slashings.tree_hash_root(),
-                    body.attestations.tree_hash_root(),
-                    body.deposits.tree_hash_root(),
-                    body.voluntary_exits.tree_hash_root(),
-                    body.sync_aggregate.tree_hash_root(),
-                    body.execution_payload.tree_hash_root(),
-                    body.bls_to_execution_changes.tree_hash_root(),
-                    body.blob_kzg_commitments.tree_hash_root(),
-                ];
-                let beacon_block_body_depth = leaves.len().next_power_of_two().ilog2() as usize;
-                let tree = MerkleTree::create(&leaves, beacon_block_body_depth);
-                let (_, mut proof_body) = tree
-                    .generate_proof(BLOB_KZG_COMMITMENTS_INDEX, beacon_block_body_depth)
-                    .map_err(Error::MerkleTreeError)?;
-                // Join the proofs for the subtree and the main tree
-                proof.append(&mut proof_body);
-
-                debug_assert_eq!(proof.len(), E::kzg_proof_inclusion_proof_depth());
-                Ok(proof.into())
-            }
-            // TODO(electra): De-duplicate proof computation.
-            Self::Electra(body) => {
-                // We compute the branches by generating 2 merkle trees:
-                // 1. Merkle tree for the `blob_kzg_commitments` List object
-                // 2. Merkle tree for the `BeaconBlockBody` container
-                // We then merge the branches for both the trees all the way up to the root.
-
-                // Part1 (Branches for the subtree rooted at `blob_kzg_commitments`)
-                //
-                // Branches for `blob_kzg_commitments` without length mix-in
-                let depth = E::max_blob_commitments_per_block()
-                    .next_power_of_two()
-                    .ilog2();
-                let leaves: Vec<_> = body
-                    .blob_kzg_commitments
-                    .iter()
-                    .map(|commitment| commitment.tree_hash_root())
-                    .collect();
-                let tree = MerkleTree::create(&leaves, depth as usize);
-                let (_, mut proof) = tree
-                    .generate_proof(index, depth as usize)
-                    .map_err(Error::MerkleTreeError)?;
-
-                // Add the branch corresponding to the length mix-in.
-                let length = body.blob_kzg_commitments.len();
-                let usize_len = std::mem::size_of::<usize>();
-                let mut length_bytes = [0; BYTES_PER_CHUNK];
-                length_bytes
-                    .get_mut(0..usize_len)
-                    .ok_or(Error::MerkleTreeError(MerkleTreeError::PleaseNotifyTheDevs))?
-                    .copy_from_slice(&length.to_le_bytes());
-                let length_root = Hash256::from_slice(length_bytes.as_slice());
-                proof.push(length_root);
-
-                // Part 2
-                // Branches for `BeaconBlockBody` container
-                let leaves = [
-                    body.randao_reveal.tree_hash_root(),
-                    body.eth1_data.tree_hash_root(),
-                    body.graffiti.tree_hash_root(),
-                    body.proposer_slashings.tree_hash_root(),
-                    body.attester_slashings.tree_hash_root(),
-                    body.attestations.tree_hash_root(),
-                    body.deposits.tree_hash_root(),
-                    body.voluntary_exits.tree_hash_root(),
-                    body.sync_aggregate.tree_hash_root(),
-                    body.execution_payload.tree_hash_root(),
-                    body.bls_to_execution_changes.tree_hash_root(),
-                    body.blob_kzg_commitments.tree_hash_root(),
-                ];
-                let beacon_block_body_depth = leaves.len().next_power_of_two().ilog2() as usize;
-                let tree = MerkleTree::create(&leaves, beacon_block_body_depth);
-                let (_, mut proof_body) = tree
-                    .generate_proof(BLOB_KZG_COMMITMENTS_INDEX, beacon_block_body_depth)
-                    .map_err(Error::MerkleTreeError)?;
-                // Join the proofs for the subtree and the main tree
-                proof.append(&mut proof_body);
-
-                debug_assert_eq!(proof.len(), E::kzg_proof_inclusion_proof_depth());
-                Ok(proof.into())
+                return Err(Error::IncorrectStateVariant)
             }
-        }
+            Self::Deneb(body) => [
+                body.randao_reveal.tree_hash_root(),
+                body.eth1_data.tree_hash_root(),
+                body.graffiti.tree_hash_root(),
+                body.proposer_slashings.tree_hash_root(),
+                body.attester_slashings.tree_hash_root(),
+                body.attestations.tree_hash_root(),
+                body.deposits.tree_hash_root(),
+                body.voluntary_exits.tree_hash_root(),
+                body.sync_aggregate.tree_hash_root(),
+                body.execution_payload.tree_hash_root(),
+                body.bls_to_execution_changes.tree_hash_root(),
+                body.blob_kzg_commitments.tree_hash_root(),
+            ],
+            Self::Electra(body) => [
+                body.randao_reveal.tree_hash_root(),
+                body.eth1_data.tree_hash_root(),
+                body.graffiti.tree_hash_root(),
+                body.proposer_slashings.tree_hash_root(),
+                body.attester_slashings.tree_hash_root(),
+                body.attestations.tree_hash_root(),
+                body.deposits.tree_hash_root(),
+                body.voluntary_exits.tree_hash_root(),
+                body.sync_aggregate.tree_hash_root(),
+                body.execution_payload.tree_hash_root(),
+                body.bls_to_execution_changes.tree_hash_root(),
+                body.blob_kzg_commitments.tree_hash_root(),
+            ],
+        };
+        let beacon_block_body_depth = leaves.len().next_power_of_two().ilog2() as usize;
+        let tree = MerkleTree::create(&leaves, beacon_block_body_depth);
+        let (_, proof) = tree
+            .generate_proof(BLOB_KZG_COMMITMENTS_INDEX, beacon_block_body_depth)
+            .map_err(Error::MerkleTreeError)?;
+        Ok(proof)
     }
 
     /// Return `true` if this block body has a non-zero number of blobs.
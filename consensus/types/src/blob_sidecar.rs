@@ -1,7 +1,7 @@
 use crate::test_utils::TestRandom;
 use crate::{
     beacon_block_body::BLOB_KZG_COMMITMENTS_INDEX, BeaconBlockHeader, BeaconStateError, Blob,
-    EthSpec, FixedVector, Hash256, SignedBeaconBlockHeader, Slot, VariableList,
+    ChainSpec, EthSpec, FixedVector, Hash256, SignedBeaconBlockHeader, Slot, VariableList,
 };
 use crate::{KzgProofs, SignedBeaconBlock};
 use bls::Signature;
@@ -15,7 +15,7 @@ use ssz::Encode;
 use ssz_derive::{Decode, Encode};
 use std::fmt::Debug;
 use std::hash::Hash;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use test_random_derive::TestRandom;
 use tree_hash::TreeHash;
 use tree_hash_derive::TreeHash;
@@ -30,15 +30,13 @@ pub struct BlobIdentifier {
 }
 
 impl BlobIdentifier {
-    pub fn get_all_blob_ids<E: EthSpec>(block_root: Hash256) -> Vec<BlobIdentifier> {
-        let mut blob_ids = Vec::with_capacity(E::max_blobs_per_block());
-        for i in 0..E::max_blobs_per_block() {
-            blob_ids.push(BlobIdentifier {
-                block_root,
-                index: i as u64,
-            });
-        }
-        blob_ids
+    pub fn get_all_blob_ids<E: EthSpec>(
+        block_root: Hash256,
+        spec: &ChainSpec,
+    ) -> Vec<BlobIdentifier> {
+        (0..spec.max_blobs_per_block::<E>())
+            .map(|index| BlobIdentifier { block_root, index })
+            .collect()
     }
 }
 
@@ -76,8 +74,20 @@ pub struct BlobSidecar<E: EthSpec> {
     pub blob: Blob<E>,
     pub kzg_commitment: KzgCommitment,
     pub kzg_proof: KzgProof,
-    pub signed_block_header: SignedBeaconBlockHeader,
+    /// Private so that `block_root_cache` is cleared whenever the header is modified, see
+    /// `signed_block_header_mut`.
+    signed_block_header: SignedBeaconBlockHeader,
     pub kzg_commitment_inclusion_proof: FixedVector<Hash256, E::KzgCommitmentInclusionProofDepth>,
+    /// The root of `signed_block_header.message`, computed on the first call to `block_root`.
+    ///
+    /// Not part of the spec.
+    #[serde(skip_serializing, skip_deserializing)]
+    #[ssz(skip_serializing, skip_deserializing)]
+    #[tree_hash(skip_hashing)]
+    #[test_random(default)]
+    #[arbitrary(default)]
+    #[derivative(PartialEq = "ignore", Hash = "ignore")]
+    block_root_cache: OnceLock<Hash256>,
 }
 
 impl<E: EthSpec> PartialOrd for BlobSidecar<E> {
@@ -125,6 +135,46 @@ impl<E: EthSpec> BlobSidecar<E> {
         blob: Blob<E>,
         signed_block: &SignedBeaconBlock<E>,
         kzg_proof: KzgProof,
+    ) -> Result<Self, BlobSidecarError> {
+        let kzg_commitments_inclusion_proof = Self::kzg_commitments_inclusion_proof(signed_block)?;
+        Self::new_with_existing_proof(
+            index,
+            blob,
+            signed_block,
+            signed_block.signed_block_header(),
+            &kzg_commitments_inclusion_proof,
+            kzg_proof,
+        )
+    }
+
+    /// Compute the proof of inclusion for `signed_block`'s `blob_kzg_commitments` in its body, for
+    /// use with `new_with_existing_proof`.
+    pub fn kzg_commitments_inclusion_proof(
+        signed_block: &SignedBeaconBlock<E>,
+    ) -> Result<Vec<Hash256>, BlobSidecarError> {
+        signed_block
+            .message()
+            .body()
+            .kzg_commitments_merkle_proof()
+            .map_err(|e| match e {
+                BeaconStateError::IncorrectStateVariant => BlobSidecarError::PreDeneb,
+                e => BlobSidecarError::BeaconState(e),
+            })
+    }
+
+    /// Create a sidecar using a pre-computed block header and proof of inclusion for the block's
+    /// `blob_kzg_commitments` in its body.
+    ///
+    /// Both are the same for every blob in a block, so they should be computed once when building
+    /// all of a block's sidecars. They must have been computed from `signed_block`, which is only
+    /// checked in debug builds.
+    pub fn new_with_existing_proof(
+        index: usize,
+        blob: Blob<E>,
+        signed_block: &SignedBeaconBlock<E>,
+        signed_block_header: SignedBeaconBlockHeader,
+        kzg_commitments_inclusion_proof: &[Hash256],
+        kzg_proof: KzgProof,
     ) -> Result<Self, BlobSidecarError> {
         let expected_kzg_commitments = signed_block
             .message()
@@ -134,19 +184,30 @@ impl<E: EthSpec> BlobSidecar<E> {
         let kzg_commitment = *expected_kzg_commitments
             .get(index)
             .ok_or(BlobSidecarError::MissingKzgCommitment)?;
+        debug_assert_eq!(
+            signed_block_header.message,
+            signed_block.message().block_header(),
+            "header does not belong to the block"
+        );
         let kzg_commitment_inclusion_proof = signed_block
             .message()
             .body()
-            .kzg_commitment_merkle_proof(index)?;
+            .complete_kzg_commitment_merkle_proof(index, kzg_commitments_inclusion_proof)?;
 
-        Ok(Self {
+        let blob_sidecar = Self {
             index: index as u64,
             blob,
             kzg_commitment,
             kzg_proof,
-            signed_block_header: signed_block.signed_block_header(),
+            signed_block_header,
             kzg_commitment_inclusion_proof,
-        })
+            block_root_cache: OnceLock::new(),
+        };
+        debug_assert!(
+            blob_sidecar.verify_blob_sidecar_inclusion_proof(),
+            "inclusion proof does not belong to the block"
+        );
+        Ok(blob_sidecar)
     }
 
     pub fn id(&self) -> BlobIdentifier {
@@ -160,8 +221,20 @@ impl<E: EthSpec> BlobSidecar<E> {
         self.signed_block_header.message.slot
     }
 
+    pub fn signed_block_header(&self) -> &SignedBeaconBlockHeader {
+        &self.signed_block_header
+    }
+
+    /// Get a mutable reference to the block header, clearing the cached block root.
+    pub fn signed_block_header_mut(&mut self) -> &mut SignedBeaconBlockHeader {
+        self.block_root_cache = OnceLock::new();
+        &mut self.signed_block_header
+    }
+
     pub fn block_root(&self) -> Hash256 {
-        self.signed_block_header.message.tree_hash_root()
+        *self
+            .block_root_cache
+            .get_or_init(|| self.signed_block_header.message.tree_hash_root())
     }
 
     pub fn block_parent_root(&self) -> Hash256 {
@@ -183,6 +256,7 @@ impl<E: EthSpec> BlobSidecar<E> {
                 signature: Signature::empty(),
             },
             kzg_commitment_inclusion_proof: Default::default(),
+            block_root_cache: OnceLock::new(),
         }
     }
 
@@ -253,9 +327,18 @@ impl<E: EthSpec> BlobSidecar<E> {
         block: &SignedBeaconBlock<E>,
         kzg_proofs: KzgProofs<E>,
     ) -> Result<BlobSidecarList<E>, BlobSidecarError> {
+        let signed_block_header = block.signed_block_header();
+        let kzg_commitments_inclusion_proof = Self::kzg_commitments_inclusion_proof(block)?;
         let mut blob_sidecars = vec![];
         for (i, (kzg_proof, blob)) in kzg_proofs.iter().zip(blobs).enumerate() {
-            let blob_sidecar = BlobSidecar::new(i, blob, block, *kzg_proof)?;
+            let blob_sidecar = BlobSidecar::new_with_existing_proof(
+                i,
+                blob,
+                block,
+                signed_block_header.clone(),
+                &kzg_commitments_inclusion_proof,
+                *kzg_proof,
+            )?;
             blob_sidecars.push(Arc::new(blob_sidecar));
         }
         Ok(VariableList::from(blob_sidecars))
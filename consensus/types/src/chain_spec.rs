@@ -173,6 +173,10 @@ pub struct ChainSpec {
      */
     pub deneb_fork_version: [u8; 4],
     pub deneb_fork_epoch: Option<Epoch>,
+    /// The maximum number of blobs per block. This is configurable so that networks can lower it
+    /// below the preset's `MaxBlobsPerBlock`, which bounds the lists that blobs are stored in.
+    /// Use `Self::max_blobs_per_block` to read it.
+    pub max_blobs_per_block: u64,
 
     /*
      * Electra hard fork params
@@ -561,6 +565,20 @@ impl ChainSpec {
         }
     }
 
+    /// Returns the maximum number of blobs which a block may commit to.
+    ///
+    /// This is never more than `E::max_blobs_per_block()`, which bounds the lists that blobs are
+    /// stored in.
+    pub fn max_blobs_per_block<E: EthSpec>(&self) -> u64 {
+        std::cmp::min(self.max_blobs_per_block, E::max_blobs_per_block() as u64)
+    }
+
+    /// Returns the number of blobs per block which the blob base fee adjusts towards, which is half
+    /// of `Self::max_blobs_per_block`.
+    pub fn target_blobs_per_block<E: EthSpec>(&self) -> u64 {
+        self.max_blobs_per_block::<E>() / 2
+    }
+
     /// Returns a `ChainSpec` compatible with the Ethereum Foundation specification.
     pub fn mainnet() -> Self {
         Self {
@@ -720,6 +738,7 @@ impl ChainSpec {
              */
             deneb_fork_version: [0x04, 0x00, 0x00, 0x00],
             deneb_fork_epoch: Some(Epoch::new(269568)),
+            max_blobs_per_block: default_max_blobs_per_block(),
 
             /*
              * Electra hard fork params
@@ -1022,6 +1041,7 @@ impl ChainSpec {
              */
             deneb_fork_version: [0x04, 0x00, 0x00, 0x64],
             deneb_fork_epoch: Some(Epoch::new(889856)),
+            max_blobs_per_block: default_max_blobs_per_block(),
 
             /*
              * Electra hard fork params
@@ -1175,6 +1195,9 @@ pub struct Config {
     #[serde(serialize_with = "serialize_fork_epoch")]
     #[serde(deserialize_with = "deserialize_fork_epoch")]
     pub deneb_fork_epoch: Option<MaybeQuoted<Epoch>>,
+    #[serde(default = "default_max_blobs_per_block")]
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub max_blobs_per_block: u64,
 
     #[serde(default = "default_electra_fork_version")]
     #[serde(with = "serde_utils::bytes_4_hex")]
@@ -1396,6 +1419,10 @@ const fn default_blob_sidecar_subnet_count() -> u64 {
     6
 }
 
+const fn default_max_blobs_per_block() -> u64 {
+    6
+}
+
 const fn default_min_per_epoch_churn_limit_electra() -> u64 {
     128_000_000_000
 }
@@ -1536,6 +1563,7 @@ impl Config {
             deneb_fork_epoch: spec
                 .deneb_fork_epoch
                 .map(|epoch| MaybeQuoted { value: epoch }),
+            max_blobs_per_block: spec.max_blobs_per_block::<E>(),
 
             electra_fork_version: spec.electra_fork_version,
             electra_fork_epoch: spec
@@ -1615,6 +1643,7 @@ impl Config {
             capella_fork_version,
             deneb_fork_epoch,
             deneb_fork_version,
+            max_blobs_per_block,
             electra_fork_epoch,
             electra_fork_version,
             seconds_per_slot,
@@ -1659,6 +1688,11 @@ impl Config {
             return None;
         }
 
+        // Blobs are held in lists bounded by the preset, so the limit can't be raised above it.
+        if max_blobs_per_block > E::max_blobs_per_block() as u64 {
+            return None;
+        }
+
         Some(ChainSpec {
             config_name: config_name.clone(),
             min_genesis_active_validator_count,
@@ -1673,6 +1707,7 @@ impl Config {
             capella_fork_version,
             deneb_fork_epoch: deneb_fork_epoch.map(|q| q.value),
             deneb_fork_version,
+            max_blobs_per_block,
             electra_fork_epoch: electra_fork_epoch.map(|q| q.value),
             electra_fork_version,
             seconds_per_slot,
@@ -1929,6 +1964,26 @@ mod yaml_tests {
         assert_eq!(new_spec, ChainSpec::minimal());
     }
 
+    #[test]
+    fn apply_max_blobs_per_block() {
+        let spec = ChainSpec::minimal();
+        let mut yamlconfig = Config::from_chain_spec::<MinimalEthSpec>(&spec);
+
+        yamlconfig.max_blobs_per_block = 3;
+        let new_spec = yamlconfig
+            .apply_to_chain_spec::<MinimalEthSpec>(&spec)
+            .expect("should have applied spec");
+        assert_eq!(new_spec.max_blobs_per_block::<MinimalEthSpec>(), 3);
+        assert_eq!(new_spec.target_blobs_per_block::<MinimalEthSpec>(), 1);
+
+        // The limit can't be raised above the preset.
+        yamlconfig.max_blobs_per_block = MinimalEthSpec::max_blobs_per_block() as u64 + 1;
+        assert_eq!(
+            yamlconfig.apply_to_chain_spec::<MinimalEthSpec>(&spec),
+            None
+        );
+    }
+
     #[test]
     fn test_defaults() {
         // Spec yaml string. Fields that serialize/deserialize with a default value are commented out.
@@ -1994,6 +2049,7 @@ mod yaml_tests {
         check_default!(attestation_subnet_extra_bits);
         check_default!(attestation_subnet_prefix_bits);
         check_default!(attestation_subnet_shuffling_prefix_bits);
+        check_default!(max_blobs_per_block);
 
         assert_eq!(chain_spec.bellatrix_fork_epoch, None);
     }
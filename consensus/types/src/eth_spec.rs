@@ -146,6 +146,11 @@ pub trait EthSpec:
     type MaxAttesterSlashingsElectra: Unsigned + Clone + Sync + Send + Debug + PartialEq;
     type MaxAttestationsElectra: Unsigned + Clone + Sync + Send + Debug + PartialEq;
     type MaxWithdrawalRequestsPerPayload: Unsigned + Clone + Sync + Send + Debug + PartialEq;
+    type MaxCommitteesPerSlot: Unsigned + Clone + Sync + Send + Debug + PartialEq + Eq;
+    /// The maximum number of validators that can attest in a single slot.
+    ///
+    /// Must be set to `MaxValidatorsPerCommittee * MaxCommitteesPerSlot`.
+    type MaxValidatorsPerSlot: Unsigned + Clone + Sync + Send + Debug + PartialEq + Eq;
 
     fn default_spec() -> ChainSpec;
 
@@ -347,6 +352,17 @@ pub trait EthSpec:
     fn max_withdrawal_requests_per_payload() -> usize {
         Self::MaxWithdrawalRequestsPerPayload::to_usize()
     }
+
+    /// Returns the `MAX_COMMITTEES_PER_SLOT` constant for this specification.
+    fn max_committees_per_slot() -> usize {
+        Self::MaxCommitteesPerSlot::to_usize()
+    }
+
+    /// Returns the `MAX_VALIDATORS_PER_COMMITTEE * MAX_COMMITTEES_PER_SLOT` value for this
+    /// specification.
+    fn max_validators_per_slot() -> usize {
+        Self::MaxValidatorsPerSlot::to_usize()
+    }
 }
 
 /// Macro to inherit some type values from another EthSpec.
@@ -405,6 +421,8 @@ impl EthSpec for MainnetEthSpec {
     type MaxAttesterSlashingsElectra = U1;
     type MaxAttestationsElectra = U8;
     type MaxWithdrawalRequestsPerPayload = U16;
+    type MaxCommitteesPerSlot = U64;
+    type MaxValidatorsPerSlot = U131072; // 2048 max validators per committee * 64 committees
 
     fn default_spec() -> ChainSpec {
         ChainSpec::mainnet()
@@ -438,6 +456,8 @@ impl EthSpec for MinimalEthSpec {
     type PendingConsolidationsLimit = U64;
     type MaxDepositReceiptsPerPayload = U4;
     type MaxWithdrawalRequestsPerPayload = U2;
+    type MaxCommitteesPerSlot = U4;
+    type MaxValidatorsPerSlot = U8192; // 2048 max validators per committee * 4 committees
 
     params_from_eth_spec!(MainnetEthSpec {
         JustificationBitsLength,
@@ -524,6 +544,8 @@ impl EthSpec for GnosisEthSpec {
     type MaxAttesterSlashingsElectra = U1;
     type MaxAttestationsElectra = U8;
     type MaxWithdrawalRequestsPerPayload = U16;
+    type MaxCommitteesPerSlot = U64;
+    type MaxValidatorsPerSlot = U131072; // 2048 max validators per committee * 64 committees
 
     fn default_spec() -> ChainSpec {
         ChainSpec::gnosis()
@@ -536,11 +558,19 @@ impl EthSpec for GnosisEthSpec {
 
 #[cfg(test)]
 mod test {
-    use crate::{EthSpec, GnosisEthSpec, MainnetEthSpec, MinimalEthSpec};
+    use crate::{EthSpec, GnosisEthSpec, MainnetEthSpec, MinimalEthSpec, Unsigned};
 
     fn assert_valid_spec<E: EthSpec>() {
         E::kzg_commitments_tree_depth();
         E::block_body_tree_depth();
+        assert_eq!(
+            E::max_validators_per_slot(),
+            E::MaxValidatorsPerCommittee::to_usize() * E::max_committees_per_slot()
+        );
+        assert_eq!(
+            E::max_committees_per_slot(),
+            E::default_spec().max_committees_per_slot
+        );
     }
 
     #[test]
@@ -27,26 +27,10 @@ pub fn generate_rand_block_and_blobs<E: EthSpec>(
         .message_mut()
         .body_mut()
         .blob_kzg_commitments_mut()
-        .expect("kzg commitment expected from Deneb") = commitments.clone();
+        .expect("kzg commitment expected from Deneb") = commitments;
 
-    for (index, ((blob, kzg_commitment), kzg_proof)) in blobs
-        .into_iter()
-        .zip(commitments.into_iter())
-        .zip(proofs.into_iter())
-        .enumerate()
-    {
-        blob_sidecars.push(BlobSidecar {
-            index: index as u64,
-            blob: blob.clone(),
-            kzg_commitment,
-            kzg_proof,
-            signed_block_header: block.signed_block_header(),
-            kzg_commitment_inclusion_proof: block
-                .message()
-                .body()
-                .kzg_commitment_merkle_proof(index)
-                .unwrap(),
-        });
+    for (index, (blob, kzg_proof)) in blobs.into_iter().zip(proofs.into_iter()).enumerate() {
+        blob_sidecars.push(BlobSidecar::new(index, blob, &block, kzg_proof).unwrap());
     }
     (block, blob_sidecars)
 }
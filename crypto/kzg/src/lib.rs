@@ -19,6 +19,8 @@ pub enum Error {
     Kzg(c_kzg::Error),
     /// The kzg verification failed
     KzgVerificationFailed,
+    /// The trusted setup doesn't have one G1 point per field element of a blob.
+    InvalidTrustedSetup { g1_points: usize, expected: usize },
 }
 
 impl From<c_kzg::Error> for Error {
@@ -36,6 +38,12 @@ pub struct Kzg {
 impl Kzg {
     /// Load the kzg trusted setup parameters from a vec of G1 and G2 points.
     pub fn new_from_trusted_setup(trusted_setup: TrustedSetup) -> Result<Self, Error> {
+        if trusted_setup.g1_len() != FIELD_ELEMENTS_PER_BLOB {
+            return Err(Error::InvalidTrustedSetup {
+                g1_points: trusted_setup.g1_len(),
+                expected: FIELD_ELEMENTS_PER_BLOB,
+            });
+        }
         Ok(Self {
             trusted_setup: KzgSettings::load_trusted_setup(
                 &trusted_setup.g1_points(),
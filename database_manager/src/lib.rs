@@ -1,12 +1,15 @@
 use beacon_chain::{
     builder::Witness, eth1_chain::CachingEth1Backend, schema_change::migrate_schema,
-    slot_clock::SystemTimeSlotClock,
+    slot_clock::SystemTimeSlotClock, ExecutionStatus, PersistedForkChoice, FORK_CHOICE_DB_KEY,
 };
 use beacon_node::{get_data_dir, get_slots_per_restore_point, ClientConfig};
 use clap::{Arg, ArgAction, ArgMatches, Command};
 use clap_utils::{get_color_style, FLAG_HEADER};
 use environment::{Environment, RuntimeContext};
+use proto_array::{core::ProtoNode, ProtoArrayForkChoice};
+use serde::Serialize;
 use slog::{info, warn, Logger};
+use std::collections::HashSet;
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
@@ -14,10 +17,11 @@ use store::metadata::STATE_UPPER_LIMIT_NO_RETAIN;
 use store::{
     errors::Error,
     metadata::{SchemaVersion, CURRENT_SCHEMA_VERSION},
-    DBColumn, HotColdDB, KeyValueStore, LevelDB,
+    BeaconNodeBackend, DBColumn, DatabaseBackend, HotColdDB, KeyValueStore, KeyValueStoreOp,
+    LevelDB, StoreItem,
 };
-use strum::{EnumString, EnumVariantNames, VariantNames};
-use types::{BeaconState, EthSpec, Slot};
+use strum::{EnumString, EnumVariantNames, IntoEnumIterator, VariantNames};
+use types::{BeaconState, Checkpoint, EthSpec, Hash256, Slot};
 
 pub const CMD: &str = "database_manager";
 
@@ -139,6 +143,25 @@ pub fn compact_cli_app() -> Command {
         )
 }
 
+pub fn migrate_backend_cli_app() -> Command {
+    Command::new("migrate-backend")
+        .styles(get_color_style())
+        .about(
+            "Copy the hot database to a different key-value store backend. The original \
+             database is kept as a backup alongside the new one.",
+        )
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .value_name("BACKEND")
+                .help("The backend to migrate to")
+                .value_parser(DatabaseBackend::VARIANTS.to_vec())
+                .action(ArgAction::Set)
+                .required(true)
+                .display_order(0),
+        )
+}
+
 pub fn prune_payloads_app() -> Command {
     Command::new("prune-payloads")
         .alias("prune_payloads")
@@ -171,6 +194,36 @@ pub fn prune_states_app() -> Command {
         .about("Prune all beacon states from the freezer database")
 }
 
+pub fn fork_choice_app() -> Command {
+    Command::new("fork-choice")
+        .styles(get_color_style())
+        .about(
+            "Display a summary of the fork choice persisted by the beacon node. The beacon node \
+             must be stopped to read from its database.",
+        )
+        .arg(
+            Arg::new("file")
+                .long("file")
+                .value_name("PATH")
+                .help(
+                    "Read the persisted fork choice from an SSZ file instead of the database, \
+                     e.g. one written by `inspect --column frk --output values`",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .help("Output format")
+                .default_value("human")
+                .value_parser(ForkChoiceFormat::VARIANTS.to_vec())
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
+
 pub fn cli_app() -> Command {
     Command::new(CMD)
         .display_order(0)
@@ -227,12 +280,14 @@ pub fn cli_app() -> Command {
                 .display_order(0),
         )
         .subcommand(migrate_cli_app())
+        .subcommand(migrate_backend_cli_app())
         .subcommand(version_cli_app())
         .subcommand(inspect_cli_app())
         .subcommand(compact_cli_app())
         .subcommand(prune_payloads_app())
         .subcommand(prune_blobs_app())
         .subcommand(prune_states_app())
+        .subcommand(fork_choice_app())
 }
 
 fn parse_client_config<E: EthSpec>(
@@ -261,6 +316,11 @@ fn parse_client_config<E: EthSpec>(
         client_config.store.blob_prune_margin_epochs = blob_prune_margin_epochs;
     }
 
+    // Open the hot database with whichever backend created it.
+    if let Some(backend) = BeaconNodeBackend::<E>::backend_at(&client_config.get_db_path()) {
+        client_config.store.hot_db_backend = backend;
+    }
+
     Ok(client_config)
 }
 
@@ -275,7 +335,7 @@ pub fn display_db_version<E: EthSpec>(
     let blobs_path = client_config.get_blobs_db_path();
 
     let mut version = CURRENT_SCHEMA_VERSION;
-    HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    HotColdDB::<E, BeaconNodeBackend<E>, LevelDB<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -357,11 +417,16 @@ pub fn inspect_db<E: EthSpec>(
     let mut num_keys = 0;
 
     let sub_db = if inspect_config.freezer {
-        LevelDB::<E>::open(&cold_path).map_err(|e| format!("Unable to open freezer DB: {e:?}"))?
+        LevelDB::<E>::open(&cold_path)
+            .map(BeaconNodeBackend::LevelDb)
+            .map_err(|e| format!("Unable to open freezer DB: {e:?}"))?
     } else if inspect_config.blobs_db {
-        LevelDB::<E>::open(&blobs_path).map_err(|e| format!("Unable to open blobs DB: {e:?}"))?
+        LevelDB::<E>::open(&blobs_path)
+            .map(BeaconNodeBackend::LevelDb)
+            .map_err(|e| format!("Unable to open blobs DB: {e:?}"))?
     } else {
-        LevelDB::<E>::open(&hot_path).map_err(|e| format!("Unable to open hot DB: {e:?}"))?
+        BeaconNodeBackend::open(client_config.store.hot_db_backend, &hot_path)
+            .map_err(|e| format!("Unable to open hot DB: {e:?}"))?
     };
 
     let skip = inspect_config.skip.unwrap_or(0);
@@ -472,11 +537,20 @@ pub fn compact_db<E: EthSpec>(
     let column = compact_config.column;
 
     let (sub_db, db_name) = if compact_config.freezer {
-        (LevelDB::<E>::open(&cold_path)?, "freezer_db")
+        (
+            BeaconNodeBackend::LevelDb(LevelDB::<E>::open(&cold_path)?),
+            "freezer_db",
+        )
     } else if compact_config.blobs_db {
-        (LevelDB::<E>::open(&blobs_path)?, "blobs_db")
+        (
+            BeaconNodeBackend::LevelDb(LevelDB::<E>::open(&blobs_path)?),
+            "blobs_db",
+        )
     } else {
-        (LevelDB::<E>::open(&hot_path)?, "hot_db")
+        (
+            BeaconNodeBackend::open(client_config.store.hot_db_backend, &hot_path)?,
+            "hot_db",
+        )
     };
     info!(
         log,
@@ -511,7 +585,7 @@ pub fn migrate_db<E: EthSpec>(
 
     let mut from = CURRENT_SCHEMA_VERSION;
     let to = migrate_config.to;
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, LevelDB<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -541,6 +615,136 @@ pub fn migrate_db<E: EthSpec>(
     )
 }
 
+pub struct MigrateBackendConfig {
+    to: DatabaseBackend,
+}
+
+fn parse_migrate_backend_config(cli_args: &ArgMatches) -> Result<MigrateBackendConfig, String> {
+    let to = clap_utils::parse_required(cli_args, "to")?;
+    Ok(MigrateBackendConfig { to })
+}
+
+/// The number of bytes of values copied in each write to the new database.
+const MIGRATE_BACKEND_BATCH_BYTES: usize = 64 * 1024 * 1024;
+
+/// Copy every column of the hot database into a new database with a different backend, then swap
+/// the new database into place, keeping the original as a backup.
+pub fn migrate_backend<E: EthSpec>(
+    migrate_backend_config: MigrateBackendConfig,
+    client_config: ClientConfig,
+    log: Logger,
+) -> Result<(), String> {
+    let hot_path = client_config.get_db_path();
+    let from = BeaconNodeBackend::<E>::backend_at(&hot_path)
+        .ok_or_else(|| format!("No hot database found at {}", hot_path.display()))?;
+    let to = migrate_backend_config.to;
+    if from == to {
+        return Err(format!("The hot database already uses {to}"));
+    }
+
+    let dir_name = hot_path
+        .file_name()
+        .ok_or("Invalid hot database path")?
+        .to_string_lossy()
+        .into_owned();
+    let new_path = hot_path.with_file_name(format!("{dir_name}.{to}.migrating"));
+    let backup_path = hot_path.with_file_name(format!("{dir_name}.{from}.backup"));
+    if backup_path.exists() {
+        return Err(format!(
+            "Backup path {} already exists, remove it before migrating",
+            backup_path.display()
+        ));
+    }
+    // Discard any partial copy from an earlier, interrupted migration.
+    if new_path.exists() {
+        fs::remove_dir_all(&new_path)
+            .map_err(|e| format!("Unable to remove {}: {e:?}", new_path.display()))?;
+    }
+
+    info!(
+        log,
+        "Migrating hot database backend";
+        "from" => %from,
+        "to" => %to,
+        "path" => ?hot_path,
+    );
+
+    {
+        let source = BeaconNodeBackend::<E>::open(from, &hot_path)
+            .map_err(|e| format!("Unable to open hot DB: {e:?}"))?;
+        let target = BeaconNodeBackend::<E>::open(to, &new_path)
+            .map_err(|e| format!("Unable to create new hot DB: {e:?}"))?;
+
+        for column in DBColumn::iter() {
+            let (mut keys, mut bytes) = (0, 0);
+            let mut batch = vec![];
+            let mut batch_bytes = 0;
+            for res in source.iter_raw_entries(column, &[]) {
+                let (key, value) = res.map_err(|e| format!("Unable to read {column:?}: {e:?}"))?;
+                keys += 1;
+                bytes += value.len();
+                batch_bytes += value.len();
+                batch.push(KeyValueStoreOp::PutKeyValue(
+                    store::get_key_for_col(column.as_str(), &key),
+                    value,
+                ));
+
+                if batch_bytes >= MIGRATE_BACKEND_BATCH_BYTES {
+                    target
+                        .do_atomically(std::mem::take(&mut batch))
+                        .map_err(|e| format!("Unable to write {column:?}: {e:?}"))?;
+                    batch_bytes = 0;
+                    info!(
+                        log,
+                        "Migrating column";
+                        "column" => ?column,
+                        "keys" => keys,
+                        "bytes" => bytes,
+                    );
+                }
+            }
+            target
+                .do_atomically(batch)
+                .map_err(|e| format!("Unable to write {column:?}: {e:?}"))?;
+
+            if keys > 0 {
+                info!(
+                    log,
+                    "Migrated column";
+                    "column" => ?column,
+                    "keys" => keys,
+                    "bytes" => bytes,
+                );
+            }
+        }
+        target
+            .sync()
+            .map_err(|e| format!("Unable to sync new hot DB: {e:?}"))?;
+    }
+
+    fs::rename(&hot_path, &backup_path).map_err(|e| {
+        format!(
+            "Unable to move old hot DB to {}: {e:?}",
+            backup_path.display()
+        )
+    })?;
+    fs::rename(&new_path, &hot_path)
+        .map_err(|e| format!("Unable to move new hot DB to {}: {e:?}", hot_path.display()))?;
+
+    info!(
+        log,
+        "Hot database migrated";
+        "backend" => %to,
+        "backup" => ?backup_path,
+    );
+    info!(
+        log,
+        "Start the beacon node with --hot-db-backend {} and delete the backup once it is running",
+        to
+    );
+    Ok(())
+}
+
 pub fn prune_payloads<E: EthSpec>(
     client_config: ClientConfig,
     runtime_context: &RuntimeContext<E>,
@@ -551,7 +755,7 @@ pub fn prune_payloads<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, LevelDB<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -577,7 +781,7 @@ pub fn prune_blobs<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, LevelDB<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -613,7 +817,7 @@ pub fn prune_states<E: EthSpec>(
     let cold_path = client_config.get_freezer_db_path();
     let blobs_path = client_config.get_blobs_db_path();
 
-    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+    let db = HotColdDB::<E, BeaconNodeBackend<E>, LevelDB<E>>::open(
         &hot_path,
         &cold_path,
         &blobs_path,
@@ -675,6 +879,191 @@ pub fn prune_states<E: EthSpec>(
     Ok(())
 }
 
+#[derive(Debug, PartialEq, Eq, EnumString, EnumVariantNames)]
+pub enum ForkChoiceFormat {
+    #[strum(serialize = "human")]
+    Human,
+    #[strum(serialize = "json")]
+    Json,
+}
+
+pub struct ForkChoiceConfig {
+    file: Option<PathBuf>,
+    format: ForkChoiceFormat,
+}
+
+fn parse_fork_choice_config(cli_args: &ArgMatches) -> Result<ForkChoiceConfig, String> {
+    let file = clap_utils::parse_optional(cli_args, "file")?;
+    let format = clap_utils::parse_required(cli_args, "format")?;
+    Ok(ForkChoiceConfig { file, format })
+}
+
+/// A summary of a `PersistedForkChoice`, for debugging.
+#[derive(Debug, Serialize)]
+pub struct ForkChoiceSummary {
+    pub time: Slot,
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    pub unrealized_justified_checkpoint: Checkpoint,
+    pub unrealized_finalized_checkpoint: Checkpoint,
+    pub proposer_boost_root: Hash256,
+    pub equivocating_indices: usize,
+    pub nodes: usize,
+    /// The best descendant of the justified checkpoint, as of the last fork choice run.
+    pub head: Option<Hash256>,
+    /// The leaves of the proto-array, by descending weight.
+    pub head_candidates: Vec<ForkChoiceNodeSummary>,
+    pub invalid_payloads: Vec<ForkChoiceNodeSummary>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ForkChoiceNodeSummary {
+    pub root: Hash256,
+    pub slot: Slot,
+    pub weight: u64,
+    pub execution_status: ExecutionStatus,
+}
+
+impl From<&ProtoNode> for ForkChoiceNodeSummary {
+    fn from(node: &ProtoNode) -> Self {
+        Self {
+            root: node.root,
+            slot: node.slot,
+            weight: node.weight,
+            execution_status: node.execution_status,
+        }
+    }
+}
+
+pub fn summarize_fork_choice(persisted: &PersistedForkChoice) -> Result<ForkChoiceSummary, String> {
+    let proto_array_fork_choice =
+        ProtoArrayForkChoice::from_bytes(&persisted.fork_choice.proto_array_bytes)?;
+    let proto_array = proto_array_fork_choice.core_proto_array();
+    let nodes = &proto_array.nodes;
+    let store = &persisted.fork_choice_store;
+
+    let head = proto_array
+        .indices
+        .get(&store.justified_checkpoint.root)
+        .and_then(|&index| nodes.get(index))
+        .map(|justified| {
+            justified
+                .best_descendant
+                .and_then(|index| nodes.get(index))
+                .map_or(justified.root, |node| node.root)
+        });
+
+    let parents = nodes
+        .iter()
+        .filter_map(|node| node.parent)
+        .collect::<HashSet<_>>();
+    let mut head_candidates = nodes
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !parents.contains(index))
+        .map(|(_, node)| ForkChoiceNodeSummary::from(node))
+        .collect::<Vec<_>>();
+    head_candidates.sort_by(|a, b| b.weight.cmp(&a.weight));
+
+    let invalid_payloads = nodes
+        .iter()
+        .filter(|node| node.execution_status.is_invalid())
+        .map(ForkChoiceNodeSummary::from)
+        .collect();
+
+    Ok(ForkChoiceSummary {
+        time: store.time,
+        justified_checkpoint: store.justified_checkpoint,
+        finalized_checkpoint: store.finalized_checkpoint,
+        unrealized_justified_checkpoint: store.unrealized_justified_checkpoint,
+        unrealized_finalized_checkpoint: store.unrealized_finalized_checkpoint,
+        proposer_boost_root: store.proposer_boost_root,
+        equivocating_indices: store.equivocating_indices.len(),
+        nodes: nodes.len(),
+        head,
+        head_candidates,
+        invalid_payloads,
+    })
+}
+
+pub fn display_fork_choice<E: EthSpec>(
+    fork_choice_config: ForkChoiceConfig,
+    client_config: ClientConfig,
+) -> Result<(), String> {
+    let bytes = if let Some(path) = &fork_choice_config.file {
+        fs::read(path).map_err(|e| format!("Unable to read {}: {e:?}", path.display()))?
+    } else {
+        let hot_path = client_config.get_db_path();
+        let hot_db = BeaconNodeBackend::<E>::open(client_config.store.hot_db_backend, &hot_path)
+            .map_err(|e| format!("Unable to open hot DB: {e:?}"))?;
+        hot_db
+            .get_bytes(DBColumn::ForkChoice.as_str(), FORK_CHOICE_DB_KEY.as_bytes())
+            .map_err(|e| format!("Unable to read fork choice: {e:?}"))?
+            .ok_or("No fork choice found in the hot DB")?
+    };
+    let persisted = PersistedForkChoice::from_store_bytes(&bytes)
+        .map_err(|e| format!("Unable to decode persisted fork choice: {e:?}"))?;
+    let summary = summarize_fork_choice(&persisted)?;
+
+    match fork_choice_config.format {
+        ForkChoiceFormat::Json => {
+            let json = serde_json::to_string_pretty(&summary)
+                .map_err(|e| format!("Unable to serialize summary: {e:?}"))?;
+            println!("{}", json);
+        }
+        ForkChoiceFormat::Human => {
+            let checkpoint = |checkpoint: Checkpoint| {
+                format!("epoch {}, root {:?}", checkpoint.epoch, checkpoint.root)
+            };
+            let node = |node: &ForkChoiceNodeSummary| {
+                format!(
+                    "  {:?} slot {} weight {} execution status {:?}",
+                    node.root, node.slot, node.weight, node.execution_status
+                )
+            };
+
+            println!("Time: slot {}", summary.time);
+            println!(
+                "Justified checkpoint: {}",
+                checkpoint(summary.justified_checkpoint)
+            );
+            println!(
+                "Finalized checkpoint: {}",
+                checkpoint(summary.finalized_checkpoint)
+            );
+            println!(
+                "Unrealized justified checkpoint: {}",
+                checkpoint(summary.unrealized_justified_checkpoint)
+            );
+            println!(
+                "Unrealized finalized checkpoint: {}",
+                checkpoint(summary.unrealized_finalized_checkpoint)
+            );
+            if summary.proposer_boost_root.is_zero() {
+                println!("Proposer boost root: none");
+            } else {
+                println!("Proposer boost root: {:?}", summary.proposer_boost_root);
+            }
+            println!("Equivocating indices: {}", summary.equivocating_indices);
+            println!("Proto-array nodes: {}", summary.nodes);
+            match summary.head {
+                Some(head) => println!("Head: {:?}", head),
+                None => println!("Head: justified checkpoint not found in proto-array"),
+            }
+            println!("Head candidates: {}", summary.head_candidates.len());
+            for candidate in &summary.head_candidates {
+                println!("{}", node(candidate));
+            }
+            println!("Invalid payloads: {}", summary.invalid_payloads.len());
+            for invalid in &summary.invalid_payloads {
+                println!("{}", node(invalid));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Run the database manager, returning an error string if the operation did not succeed.
 pub fn run<E: EthSpec>(cli_args: &ArgMatches, env: Environment<E>) -> Result<(), String> {
     let client_config = parse_client_config(cli_args, &env)?;
@@ -690,6 +1079,10 @@ pub fn run<E: EthSpec>(cli_args: &ArgMatches, env: Environment<E>) -> Result<(),
             let migrate_config = parse_migrate_config(cli_args)?;
             migrate_db(migrate_config, client_config, &context, log).map_err(format_err)
         }
+        Some(("migrate-backend", cli_args)) => {
+            let migrate_backend_config = parse_migrate_backend_config(cli_args)?;
+            migrate_backend::<E>(migrate_backend_config, client_config, log)
+        }
         Some(("inspect", cli_args)) => {
             let inspect_config = parse_inspect_config(cli_args)?;
             inspect_db::<E>(inspect_config, client_config)
@@ -726,6 +1119,10 @@ pub fn run<E: EthSpec>(cli_args: &ArgMatches, env: Environment<E>) -> Result<(),
 
             prune_states(client_config, prune_config, genesis_state, &context, log)
         }
+        Some(("fork-choice", cli_args)) => {
+            let fork_choice_config = parse_fork_choice_config(cli_args)?;
+            display_fork_choice::<E>(fork_choice_config, client_config)
+        }
         _ => Err("Unknown subcommand, for help `lighthouse database_manager --help`".into()),
     }
 }
@@ -0,0 +1,33 @@
+//! Writes the seed corpus for the fuzz targets.
+//!
+//! Usage: `cargo run --bin export_corpus -- [OUTPUT_DIR]`
+//!
+//! Each input is written to `OUTPUT_DIR/<target>/<name>`, where `OUTPUT_DIR` defaults to `corpus`,
+//! the directory read by `cargo fuzz run`.
+use lighthouse_network::fuzzing::corpus;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+fn main() {
+    let output_dir = PathBuf::from(std::env::args().nth(1).unwrap_or_else(|| "corpus".into()));
+
+    if let Err(e) = export(&output_dir) {
+        eprintln!("Failed to export corpus: {}", e);
+        process::exit(1);
+    }
+}
+
+fn export(output_dir: &Path) -> Result<(), String> {
+    let entries = corpus();
+    for entry in &entries {
+        let target_dir = output_dir.join(entry.target);
+        fs::create_dir_all(&target_dir)
+            .map_err(|e| format!("Unable to create {}: {}", target_dir.display(), e))?;
+        let path = target_dir.join(&entry.name);
+        fs::write(&path, &entry.data)
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+    }
+    println!("Wrote {} inputs to {}", entries.len(), output_dir.display());
+    Ok(())
+}
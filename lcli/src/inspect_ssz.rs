@@ -0,0 +1,124 @@
+//! Decode SSZ bytes from a file or hex string as any of the types known to `parse_ssz`, and print
+//! them as JSON, YAML or a tree of fields.
+use crate::parse_ssz::{decode_ssz, read_ssz_file};
+use clap::ArgMatches;
+use clap_utils::parse_required;
+use eth2_network_config::Eth2NetworkConfig;
+use log::info;
+use serde_yaml::Value;
+use std::fmt::Write;
+use std::str::FromStr;
+use types::EthSpec;
+
+/// Hex strings longer than a 32-byte root are abbreviated in the tree output.
+const MAX_TREE_HEX_LEN: usize = 66;
+
+enum OutputFormat {
+    Json,
+    Yaml,
+    Tree,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "yaml" => Ok(Self::Yaml),
+            "tree" => Ok(Self::Tree),
+            _ => Err(format!("Invalid output format \"{}\"", s)),
+        }
+    }
+}
+
+pub fn run<E: EthSpec>(
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let type_str: String = parse_required(matches, "type")?;
+    let input: String = parse_required(matches, "input")?;
+    let format = parse_required(matches, "format")?;
+
+    let bytes = if let Some(hex) = input.strip_prefix("0x") {
+        hex::decode(hex).map_err(|e| format!("Invalid hex input: {:?}", e))?
+    } else {
+        read_ssz_file(&input)?
+    };
+
+    let spec = &network_config.chain_spec::<E>()?;
+    info!(
+        "Using {} network config ({} preset)",
+        spec.config_name.as_deref().unwrap_or("unknown"),
+        E::spec_name()
+    );
+    info!("Type: {type_str}, length: {} bytes", bytes.len());
+
+    let item = decode_ssz::<E>(&type_str, &bytes, spec)?;
+
+    let output = match format {
+        OutputFormat::Json => serde_json::to_string_pretty(&item)
+            .map_err(|e| format!("Unable to write object to JSON: {e:?}"))?,
+        OutputFormat::Yaml => serde_yaml::to_string(&item)
+            .map_err(|e| format!("Unable to write object to YAML: {e:?}"))?,
+        OutputFormat::Tree => {
+            let mut output = String::new();
+            write_tree(&item, 0, &mut output);
+            output
+        }
+    };
+    print!("{output}");
+
+    Ok(())
+}
+
+/// Write the fields of `value` to `output`, one per line and indented by their depth.
+fn write_tree(value: &Value, depth: usize, output: &mut String) {
+    match value {
+        Value::Mapping(fields) => {
+            for (name, field) in fields {
+                write_node(&scalar(name), field, depth, output);
+            }
+        }
+        Value::Sequence(items) => {
+            for (i, item) in items.iter().enumerate() {
+                write_node(&format!("[{i}]"), item, depth, output);
+            }
+        }
+        other => {
+            let _ = writeln!(output, "{}", scalar(other));
+        }
+    }
+}
+
+fn write_node(label: &str, value: &Value, depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth);
+    // Writing to a `String` never fails.
+    let _ = match value {
+        Value::Mapping(_) => writeln!(output, "{indent}{label}"),
+        Value::Sequence(items) => writeln!(output, "{indent}{label} ({} items)", items.len()),
+        other => writeln!(output, "{indent}{label}: {}", scalar(other)),
+    };
+    if matches!(value, Value::Mapping(_) | Value::Sequence(_)) {
+        write_tree(value, depth + 1, output);
+    }
+}
+
+/// Format a value without any nested fields, abbreviating long hex strings such as blobs.
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) if s.starts_with("0x") && s.is_ascii() && s.len() > MAX_TREE_HEX_LEN => {
+            format!(
+                "{}..{} ({} bytes)",
+                &s[..10],
+                &s[s.len() - 8..],
+                (s.len() - 2) / 2
+            )
+        }
+        Value::String(s) => s.clone(),
+        Value::Null => "null".to_string(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
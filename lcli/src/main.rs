@@ -2,6 +2,7 @@ mod block_root;
 mod check_deposit_data;
 mod generate_bootnode_enr;
 mod indexed_attestations;
+mod inspect_ssz;
 mod mnemonic_validators;
 mod mock_el;
 mod parse_ssz;
@@ -262,6 +263,41 @@ fn main() {
                         .display_order(0)
                 )
         )
+        .subcommand(
+            Command::new("inspect-ssz")
+                .about("Decodes SSZ bytes from a file or hex string as any supported consensus \
+                    type, e.g. SignedBeaconBlockDeneb or BlobSidecar")
+                .arg(
+                    Arg::new("type")
+                        .long("type")
+                        .value_name("TYPE")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Type to decode. An unknown type will list the supported types.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("format")
+                        .short('f')
+                        .long("format")
+                        .value_name("FORMAT")
+                        .action(ArgAction::Set)
+                        .default_value("tree")
+                        .value_parser(["json", "yaml", "tree"])
+                        .help("Output format to use. The tree format abbreviates long hex \
+                            values such as blobs.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("input")
+                        .value_name("FILE|HEX")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to an SSZ file, or 0x-prefixed SSZ bytes. Files ending in \
+                            ssz_snappy are decompressed.")
+                        .display_order(0)
+                )
+        )
         .subcommand(
             Command::new("check-deposit-data")
                 .about("Checks the integrity of some deposit data.")
@@ -636,6 +672,11 @@ fn run<E: EthSpec>(env_builder: EnvironmentBuilder<E>, matches: &ArgMatches) ->
             run_parse_ssz::<E>(network_config, matches)
                 .map_err(|e| format!("Failed to pretty print hex: {}", e))
         }
+        Some(("inspect-ssz", matches)) => {
+            let network_config = get_network_config()?;
+            inspect_ssz::run::<E>(network_config, matches)
+                .map_err(|e| format!("Failed to run inspect-ssz command: {}", e))
+        }
         Some(("check-deposit-data", matches)) => check_deposit_data::run(matches)
             .map_err(|e| format!("Failed to run check-deposit-data command: {}", e)),
         Some(("generate-bootnode-enr", matches)) => generate_bootnode_enr::run::<E>(matches)
@@ -3,6 +3,7 @@ use clap_utils::parse_required;
 use eth2_network_config::Eth2NetworkConfig;
 use log::info;
 use serde::Serialize;
+use serde_yaml::Value;
 use snap::raw::Decoder;
 use ssz::Decode;
 use std::fs;
@@ -28,6 +29,136 @@ impl FromStr for OutputFormat {
     }
 }
 
+/// A consensus type which can be decoded by `pretty-ssz` and `inspect-ssz`.
+pub struct SszType {
+    /// The names by which the type may be selected, the first being its name in the specs.
+    pub names: &'static [&'static str],
+    /// Decode SSZ bytes into a serializable value.
+    ///
+    /// A `serde_yaml::Value` is used because, unlike `serde_json::Value`, it preserves the order
+    /// of fields.
+    pub decode: fn(&[u8], &ChainSpec) -> Result<Value, String>,
+}
+
+macro_rules! ssz_type {
+    // Types whose fork variant is determined from the slot, which requires the `ChainSpec`.
+    ([$($name:literal),+], $ty:ty, spec) => {
+        SszType {
+            names: &[$($name),+],
+            decode: |bytes, spec| to_value(<$ty>::from_ssz_bytes(bytes, spec)),
+        }
+    };
+    ([$($name:literal),+], $ty:ty) => {
+        SszType {
+            names: &[$($name),+],
+            decode: |bytes, _| to_value(<$ty as Decode>::from_ssz_bytes(bytes)),
+        }
+    };
+}
+
+/// All of the types which may be decoded.
+///
+/// More fork-specific decoders may need to be added in future, but shouldn't be 100% necessary,
+/// as the fork-generic decoders will always be available (requires correct --network flag).
+pub fn ssz_types<E: EthSpec>() -> Vec<SszType> {
+    vec![
+        ssz_type!(["SignedBeaconBlock"], SignedBeaconBlock<E>, spec),
+        ssz_type!(
+            ["SignedBeaconBlockBase", "SignedBeaconBlockPhase0"],
+            SignedBeaconBlockBase<E>
+        ),
+        ssz_type!(["SignedBeaconBlockAltair"], SignedBeaconBlockAltair<E>),
+        ssz_type!(
+            ["SignedBeaconBlockBellatrix"],
+            SignedBeaconBlockBellatrix<E>
+        ),
+        ssz_type!(["SignedBeaconBlockCapella"], SignedBeaconBlockCapella<E>),
+        ssz_type!(["SignedBeaconBlockDeneb"], SignedBeaconBlockDeneb<E>),
+        ssz_type!(["SignedBeaconBlockElectra"], SignedBeaconBlockElectra<E>),
+        ssz_type!(
+            ["SignedBlindedBeaconBlock"],
+            SignedBlindedBeaconBlock<E>,
+            spec
+        ),
+        ssz_type!(["BeaconBlock"], BeaconBlock<E>, spec),
+        ssz_type!(["SignedBeaconBlockHeader"], SignedBeaconBlockHeader),
+        ssz_type!(["BeaconState"], BeaconState<E>, spec),
+        ssz_type!(["BeaconStateBase", "BeaconStatePhase0"], BeaconStateBase<E>),
+        ssz_type!(["BeaconStateAltair"], BeaconStateAltair<E>),
+        ssz_type!(["BeaconStateBellatrix"], BeaconStateBellatrix<E>),
+        ssz_type!(["BeaconStateCapella"], BeaconStateCapella<E>),
+        ssz_type!(["BeaconStateDeneb"], BeaconStateDeneb<E>),
+        ssz_type!(["BeaconStateElectra"], BeaconStateElectra<E>),
+        ssz_type!(["ExecutionPayloadBellatrix"], ExecutionPayloadBellatrix<E>),
+        ssz_type!(["ExecutionPayloadCapella"], ExecutionPayloadCapella<E>),
+        ssz_type!(["ExecutionPayloadDeneb"], ExecutionPayloadDeneb<E>),
+        ssz_type!(["ExecutionPayloadElectra"], ExecutionPayloadElectra<E>),
+        ssz_type!(["BlobSidecar"], BlobSidecar<E>),
+        ssz_type!(["Attestation"], Attestation<E>),
+        ssz_type!(["AttestationElectra"], AttestationElectra<E>),
+        ssz_type!(["AttestationData"], AttestationData),
+        ssz_type!(["IndexedAttestation"], IndexedAttestation<E>),
+        ssz_type!(["SignedAggregateAndProof"], SignedAggregateAndProof<E>),
+        ssz_type!(["AttesterSlashing"], AttesterSlashing<E>),
+        ssz_type!(["ProposerSlashing"], ProposerSlashing),
+        ssz_type!(["SignedVoluntaryExit"], SignedVoluntaryExit),
+        ssz_type!(["SignedBlsToExecutionChange"], SignedBlsToExecutionChange),
+        ssz_type!(["SyncCommitteeMessage"], SyncCommitteeMessage),
+        ssz_type!(
+            ["SignedContributionAndProof"],
+            SignedContributionAndProof<E>
+        ),
+        ssz_type!(["DepositData"], DepositData),
+        ssz_type!(["Checkpoint"], Checkpoint),
+        ssz_type!(["Validator"], Validator),
+    ]
+}
+
+/// Decode `bytes` as the type named `type_str`.
+pub fn decode_ssz<E: EthSpec>(
+    type_str: &str,
+    bytes: &[u8],
+    spec: &ChainSpec,
+) -> Result<Value, String> {
+    let types = ssz_types::<E>();
+    let ssz_type = types
+        .iter()
+        .find(|ssz_type| ssz_type.names.contains(&type_str))
+        .ok_or_else(|| {
+            let known = types
+                .iter()
+                .map(|ssz_type| ssz_type.names[0])
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("Unknown type: {}. Known types are: {}", type_str, known)
+        })?;
+    (ssz_type.decode)(bytes, spec)
+}
+
+fn to_value<T: Serialize>(item: Result<T, ssz::DecodeError>) -> Result<Value, String> {
+    let item = item.map_err(|e| format!("SSZ decode failed: {e:?}"))?;
+    serde_yaml::to_value(&item).map_err(|e| format!("Unable to serialize object: {e:?}"))
+}
+
+/// Read SSZ bytes from `filename`, decompressing them if the file has an `ssz_snappy` extension.
+pub fn read_ssz_file(filename: &str) -> Result<Vec<u8>, String> {
+    if filename.ends_with("ssz_snappy") {
+        let bytes =
+            fs::read(filename).map_err(|e| format!("Unable to read {}: {}", filename, e))?;
+        let mut decoder = Decoder::new();
+        decoder
+            .decompress_vec(&bytes)
+            .map_err(|e| format!("Unable to decompress {}: {}", filename, e))
+    } else {
+        let mut bytes = vec![];
+        let mut file =
+            File::open(filename).map_err(|e| format!("Unable to open {}: {}", filename, e))?;
+        file.read_to_end(&mut bytes)
+            .map_err(|e| format!("Unable to read {}: {}", filename, e))?;
+        Ok(bytes)
+    }
+}
+
 pub fn run_parse_ssz<E: EthSpec>(
     network_config: Eth2NetworkConfig,
     matches: &ArgMatches,
@@ -40,18 +171,7 @@ pub fn run_parse_ssz<E: EthSpec>(
         .ok_or("No file supplied")?;
     let format = parse_required(matches, "format")?;
 
-    let bytes = if filename.ends_with("ssz_snappy") {
-        let bytes = fs::read(filename).unwrap();
-        let mut decoder = Decoder::new();
-        decoder.decompress_vec(&bytes).unwrap()
-    } else {
-        let mut bytes = vec![];
-        let mut file =
-            File::open(filename).map_err(|e| format!("Unable to open {}: {}", filename, e))?;
-        file.read_to_end(&mut bytes)
-            .map_err(|e| format!("Unable to read {}: {}", filename, e))?;
-        bytes
-    };
+    let bytes = read_ssz_file(filename)?;
 
     let spec = &network_config.chain_spec::<E>()?;
     info!(
@@ -61,76 +181,9 @@ pub fn run_parse_ssz<E: EthSpec>(
     );
     info!("Type: {type_str}");
 
-    // More fork-specific decoders may need to be added in future, but shouldn't be 100% necessary,
-    // as the fork-generic decoder will always be available (requires correct --network flag).
-    match type_str.as_str() {
-        "SignedBeaconBlock" => decode_and_print::<SignedBeaconBlock<E>>(
-            &bytes,
-            |bytes| SignedBeaconBlock::from_ssz_bytes(bytes, spec),
-            format,
-        )?,
-        "SignedBeaconBlockBase" | "SignedBeaconBlockPhase0" => {
-            decode_and_print(&bytes, SignedBeaconBlockBase::<E>::from_ssz_bytes, format)?
-        }
-        "SignedBeaconBlockAltair" => {
-            decode_and_print(&bytes, SignedBeaconBlockAltair::<E>::from_ssz_bytes, format)?
-        }
-        "SignedBeaconBlockBellatrix" => decode_and_print(
-            &bytes,
-            SignedBeaconBlockBellatrix::<E>::from_ssz_bytes,
-            format,
-        )?,
-        "SignedBeaconBlockCapella" => decode_and_print(
-            &bytes,
-            SignedBeaconBlockCapella::<E>::from_ssz_bytes,
-            format,
-        )?,
-        "SignedBeaconBlockDeneb" => {
-            decode_and_print(&bytes, SignedBeaconBlockDeneb::<E>::from_ssz_bytes, format)?
-        }
-        "SignedBeaconBlockElectra" => decode_and_print(
-            &bytes,
-            SignedBeaconBlockElectra::<E>::from_ssz_bytes,
-            format,
-        )?,
-        "BeaconState" => decode_and_print::<BeaconState<E>>(
-            &bytes,
-            |bytes| BeaconState::from_ssz_bytes(bytes, spec),
-            format,
-        )?,
-        "BeaconStateBase" | "BeaconStatePhase0" => {
-            decode_and_print(&bytes, BeaconStateBase::<E>::from_ssz_bytes, format)?
-        }
-        "BeaconStateAltair" => {
-            decode_and_print(&bytes, BeaconStateAltair::<E>::from_ssz_bytes, format)?
-        }
-        "BeaconStateBellatrix" => {
-            decode_and_print(&bytes, BeaconStateBellatrix::<E>::from_ssz_bytes, format)?
-        }
-        "BeaconStateCapella" => {
-            decode_and_print(&bytes, BeaconStateCapella::<E>::from_ssz_bytes, format)?
-        }
-        "BeaconStateDeneb" => {
-            decode_and_print(&bytes, BeaconStateDeneb::<E>::from_ssz_bytes, format)?
-        }
-        "BeaconStateElectra" => {
-            decode_and_print(&bytes, BeaconStateElectra::<E>::from_ssz_bytes, format)?
-        }
-        "BlobSidecar" => decode_and_print(&bytes, BlobSidecar::<E>::from_ssz_bytes, format)?,
-        other => return Err(format!("Unknown type: {}", other)),
-    };
-
-    Ok(())
-}
-
-fn decode_and_print<T: Serialize>(
-    bytes: &[u8],
-    decoder: impl FnOnce(&[u8]) -> Result<T, ssz::DecodeError>,
-    output_format: OutputFormat,
-) -> Result<(), String> {
-    let item = decoder(bytes).map_err(|e| format!("SSZ decode failed: {e:?}"))?;
+    let item = decode_ssz::<E>(type_str, &bytes, spec)?;
 
-    match output_format {
+    match format {
         OutputFormat::Json => {
             println!(
                 "{}",
@@ -610,7 +610,18 @@ fn run<E: EthSpec>(
 
     let path = tracing_log_path.clone().unwrap();
 
-    logging::create_tracing_layer(path);
+    let tracing_endpoint: Option<String> = match matches.subcommand() {
+        Some(("beacon_node", bn_matches)) => {
+            clap_utils::parse_optional(bn_matches, "tracing-endpoint")?
+        }
+        _ => None,
+    };
+
+    {
+        // The OTLP exporter spawns its tasks on the runtime which is current at initialisation.
+        let _runtime_guard = environment.runtime().enter();
+        logging::create_tracing_layer(path, tracing_endpoint.as_deref());
+    }
 
     // Allow Prometheus to export the time at which the process was started.
     metrics::expose_process_start_time(&log);
@@ -2,10 +2,12 @@ use beacon_node::ClientConfig as Config;
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR, DEFAULT_RE_ORG_HEAD_THRESHOLD,
+    DisallowedReOrgOffsets, DEFAULT_ATTESTATION_PACKING_TIME_BUDGET_MS,
+    DEFAULT_RE_ORG_CUTOFF_DENOMINATOR, DEFAULT_RE_ORG_HEAD_THRESHOLD,
     DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
 };
 use beacon_node::beacon_chain::graffiti_calculator::GraffitiOrigin;
+use beacon_node::beacon_chain::store::DatabaseBackend;
 use beacon_processor::BeaconProcessorConfig;
 use eth1::Eth1Endpoint;
 use lighthouse_network::PeerId;
@@ -214,6 +216,47 @@ fn fork_choice_before_proposal_timeout_zero() {
         .with_config(|config| assert_eq!(config.chain.fork_choice_before_proposal_timeout_ms, 0));
 }
 
+#[test]
+fn attestation_packing_time_budget_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing_time_budget_ms,
+                DEFAULT_ATTESTATION_PACKING_TIME_BUDGET_MS
+            );
+            assert_eq!(config.chain.attestation_packing_time_budget_ms, 0);
+        });
+}
+
+#[test]
+fn attestation_packing_time_budget_flag() {
+    CommandLineTest::new()
+        .flag("attestation-packing-time-budget", Some("50"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.attestation_packing_time_budget_ms, 50));
+}
+
+#[test]
+fn maximum_gossip_clock_disparity_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.maximum_gossip_clock_disparity_millis, None));
+}
+
+#[test]
+fn maximum_gossip_clock_disparity_flag() {
+    CommandLineTest::new()
+        .flag("maximum-gossip-clock-disparity", Some("100"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.maximum_gossip_clock_disparity_millis,
+                Some(100)
+            )
+        });
+}
+
 #[test]
 fn checkpoint_sync_url_timeout_flag() {
     CommandLineTest::new()
@@ -288,6 +331,26 @@ fn paranoid_block_proposal_on() {
         .with_config(|config| assert!(config.chain.paranoid_block_proposal));
 }
 
+#[test]
+fn override_da_boundary_epoch_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.override_da_boundary_epoch, None));
+}
+
+#[test]
+fn override_da_boundary_epoch_flag() {
+    CommandLineTest::new()
+        .flag("override-da-boundary-epoch", Some("12"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.override_da_boundary_epoch,
+                Some(Epoch::new(12))
+            )
+        });
+}
+
 #[test]
 fn reset_payload_statuses_default() {
     CommandLineTest::new()
@@ -303,6 +366,22 @@ fn reset_payload_statuses_present() {
         .with_config(|config| assert!(config.chain.always_reset_payload_statuses));
 }
 
+#[test]
+fn invalidate_reset_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.reset_payload_statuses_from, None));
+}
+
+#[test]
+fn invalidate_reset_flag() {
+    let root = Hash256::repeat_byte(0xaa);
+    CommandLineTest::new()
+        .flag("invalidate-reset", Some(&format!("{:?}", root)))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.reset_payload_statuses_from, Some(root)));
+}
+
 #[test]
 fn freezer_dir_flag() {
     let dir = TempDir::new().expect("Unable to create temporary directory");
@@ -654,6 +733,15 @@ fn builder_fallback_flags() {
             assert_eq!(config.chain.builder_fallback_disable_checks, true);
         },
     );
+    run_payload_builder_flag_test_with_config(
+        "builder",
+        "http://meow.cats",
+        Some("builder-fault-cooldown-epochs"),
+        Some("0"),
+        |config| {
+            assert_eq!(config.chain.builder_fault_cooldown_epochs, 0);
+        },
+    );
 }
 
 #[test]
@@ -803,6 +891,14 @@ fn safe_slots_to_import_optimistically_flag() {
             assert_eq!(spec.safe_slots_to_import_optimistically, 421337)
         });
 }
+#[test]
+#[should_panic]
+fn safe_slots_to_import_optimistically_flag_too_low_on_mainnet() {
+    CommandLineTest::new()
+        .flag("network", Some("mainnet"))
+        .flag("safe-slots-to-import-optimistically", Some("16"))
+        .run_with_zero_port();
+}
 
 // Tests for Network flags.
 #[test]
@@ -823,6 +919,33 @@ fn network_target_peers_flag() {
         });
 }
 #[test]
+fn network_inbound_peer_pressure_threshold_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.inbound_pressure_threshold, Some(80)));
+}
+#[test]
+fn network_inbound_peer_pressure_threshold_flag() {
+    CommandLineTest::new()
+        .flag("inbound-peer-pressure-threshold", Some("60"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.inbound_pressure_threshold, Some(60)));
+}
+#[test]
+fn network_inbound_peer_pressure_threshold_disabled() {
+    CommandLineTest::new()
+        .flag("inbound-peer-pressure-threshold", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.inbound_pressure_threshold, None));
+}
+#[test]
+#[should_panic]
+fn network_inbound_peer_pressure_threshold_invalid() {
+    CommandLineTest::new()
+        .flag("inbound-peer-pressure-threshold", Some("101"))
+        .run_with_zero_port();
+}
+#[test]
 fn network_subscribe_all_subnets_flag() {
     CommandLineTest::new()
         .flag("subscribe-all-subnets", None)
@@ -1199,6 +1322,24 @@ fn disable_peer_scoring_flag() {
         .with_config(|config| assert!(config.network.disable_peer_scoring));
 }
 #[test]
+fn gossip_scoring_overrides_flag() {
+    CommandLineTest::new()
+        .flag("gossip-scoring-overrides", Some("/tmp/overrides.toml"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.network.gossip_scoring_overrides,
+                Some(PathBuf::from("/tmp/overrides.toml"))
+            )
+        });
+}
+#[test]
+fn gossip_scoring_overrides_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.network.gossip_scoring_overrides, None));
+}
+#[test]
 fn disable_upnp_flag() {
     CommandLineTest::new()
         .flag("disable-upnp", None)
@@ -1990,6 +2131,19 @@ fn blob_prune_margin_epochs_on_startup_ten() {
         .with_config(|config| assert!(config.store.blob_prune_margin_epochs == 10));
 }
 #[test]
+fn hot_db_backend_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.hot_db_backend, DatabaseBackend::LevelDb));
+}
+#[test]
+fn hot_db_backend_redb() {
+    CommandLineTest::new()
+        .flag("hot-db-backend", Some("redb"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.store.hot_db_backend, DatabaseBackend::Redb));
+}
+#[test]
 fn reconstruct_historic_states_flag() {
     CommandLineTest::new()
         .flag("reconstruct-historic-states", None)
@@ -2003,6 +2157,65 @@ fn no_reconstruct_historic_states_flag() {
         .with_config(|config| assert!(!config.chain.reconstruct_historic_states));
 }
 #[test]
+fn store_participation_summaries_flag() {
+    CommandLineTest::new()
+        .flag("store-participation-summaries", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.chain.store_participation_summaries));
+}
+#[test]
+fn no_store_participation_summaries_flag() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.chain.store_participation_summaries));
+}
+#[test]
+fn preemptive_blob_requests_flag() {
+    CommandLineTest::new()
+        .flag("preemptive-blob-requests", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.chain.preemptive_blob_requests));
+}
+#[test]
+fn no_preemptive_blob_requests_flag() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.chain.preemptive_blob_requests));
+}
+#[test]
+fn pending_availability_timeout_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.pending_availability_timeout_ms, 4000));
+}
+#[test]
+fn pending_availability_timeout_flag() {
+    CommandLineTest::new()
+        .flag("pending-availability-timeout", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.pending_availability_timeout_ms, 0));
+}
+#[test]
+fn kzg_verification_threads_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.kzg_verification_threads, 4));
+}
+#[test]
+fn kzg_verification_threads_flag() {
+    CommandLineTest::new()
+        .flag("kzg-verification-threads", Some("2"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.kzg_verification_threads, 2));
+}
+#[test]
+#[should_panic]
+fn kzg_verification_threads_zero() {
+    CommandLineTest::new()
+        .flag("kzg-verification-threads", Some("0"))
+        .run_with_zero_port();
+}
+#[test]
 fn epochs_per_migration_default() {
     CommandLineTest::new()
         .run_with_zero_port()
@@ -2681,6 +2894,69 @@ fn http_duplicate_block_status_override() {
         });
 }
 
+#[test]
+fn http_expensive_routes_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.expensive_routes_token, None);
+            assert!(config.http_api.expensive_routes_quotas.is_empty());
+        });
+}
+
+#[test]
+fn http_expensive_routes_token_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let path = dir.path().join("api-token.txt");
+    let mut file = File::create(&path).expect("Unable to create file");
+    file.write_all(b"secret\n")
+        .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag(
+            "http-expensive-routes-token-file",
+            path.as_os_str().to_str(),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.expensive_routes_token.as_deref(),
+                Some("secret")
+            )
+        });
+}
+
+#[test]
+fn http_expensive_routes_rate_limits_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag(
+            "http-expensive-routes-rate-limits",
+            Some("states:10/60;debug:1/12"),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let quotas = &config.http_api.expensive_routes_quotas;
+            assert_eq!(quotas.len(), 2);
+            assert_eq!(quotas[0].group.to_string(), "states");
+            assert_eq!(quotas[0].max_requests, 10);
+            assert_eq!(quotas[0].period, Duration::from_secs(60));
+            assert_eq!(quotas[1].group.to_string(), "debug");
+            assert_eq!(quotas[1].max_requests, 1);
+            assert_eq!(quotas[1].period, Duration::from_secs(12));
+        });
+}
+
+#[test]
+#[should_panic]
+fn http_expensive_routes_rate_limits_invalid_group() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-expensive-routes-rate-limits", Some("blocks:10/60"))
+        .run_with_zero_port();
+}
+
 #[test]
 fn genesis_state_url_default() {
     CommandLineTest::new()
@@ -2705,3 +2981,19 @@ fn genesis_state_url_value() {
             assert_eq!(config.genesis_state_url_timeout, Duration::from_secs(42));
         });
 }
+
+#[test]
+#[should_panic]
+fn trusted_setup_file_wrong_point_count() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let mut file =
+        File::create(dir.path().join("trusted_setup.json")).expect("Unable to create file");
+    file.write_all(br#"{"g1_lagrange": [], "g2_monomial": []}"#)
+        .expect("Unable to write to file");
+    CommandLineTest::new()
+        .flag(
+            "trusted-setup-file",
+            dir.path().join("trusted_setup.json").as_os_str().to_str(),
+        )
+        .run_with_zero_port();
+}
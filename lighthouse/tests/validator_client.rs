@@ -1,7 +1,11 @@
-use validator_client::{config::DEFAULT_WEB3SIGNER_KEEP_ALIVE, ApiTopic, Config};
+use validator_client::{
+    config::DEFAULT_WEB3SIGNER_KEEP_ALIVE, http_api::ReadinessCheck, signing_lease::FailureMode,
+    ApiTopic, Config,
+};
 
 use crate::exec::CommandLineTestExec;
 use bls::{Keypair, PublicKeyBytes};
+use sensitive_url::SensitiveUrl;
 use std::fs::File;
 use std::io::Write;
 use std::net::IpAddr;
@@ -110,6 +114,17 @@ fn disable_auto_discover_flag() {
         .with_config(|config| assert!(config.disable_auto_discover));
 }
 
+#[test]
+fn disable_key_cache_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.disable_key_cache));
+    CommandLineTest::new()
+        .flag("disable-key-cache", None)
+        .run()
+        .with_config(|config| assert!(config.disable_key_cache));
+}
+
 #[test]
 fn init_slashing_protections_flag() {
     CommandLineTest::new()
@@ -340,6 +355,56 @@ fn http_store_keystore_passwords_in_secrets_dir_present() {
         .run()
         .with_config(|config| assert!(config.http_api.store_passwords_in_secrets_dir));
 }
+#[test]
+fn http_readiness_checks_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run()
+        .with_config(|config| {
+            assert_eq!(config.http_api.readiness_checks, ReadinessCheck::all());
+            assert_eq!(config.http_api.ready_min_synced_beacon_nodes, 1);
+        });
+}
+#[test]
+fn http_readiness_checks_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag(
+            "http-readiness-checks",
+            Some("keystores, slashing-protection"),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.readiness_checks,
+                vec![
+                    ReadinessCheck::Keystores,
+                    ReadinessCheck::SlashingProtection
+                ]
+            )
+        });
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-readiness-checks", Some("none"))
+        .run()
+        .with_config(|config| assert_eq!(config.http_api.readiness_checks, vec![]));
+}
+#[test]
+#[should_panic(expected = "Unknown readiness check")]
+fn wrong_http_readiness_checks_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-readiness-checks", Some("keystores,foo"))
+        .run();
+}
+#[test]
+fn http_ready_min_synced_beacon_nodes_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-ready-min-synced-beacon-nodes", Some("2"))
+        .run()
+        .with_config(|config| assert_eq!(config.http_api.ready_min_synced_beacon_nodes, 2));
+}
 
 // Tests for Metrics flags.
 #[test]
@@ -437,6 +502,70 @@ fn no_produce_block_v3_flag() {
         .with_config(|config| assert!(!config.produce_block_v3));
 }
 
+#[test]
+fn enable_duties_cache_flag() {
+    CommandLineTest::new()
+        .flag("enable-duties-cache", None)
+        .run()
+        .with_config(|config| assert!(config.enable_duties_cache));
+}
+
+#[test]
+fn no_enable_duties_cache_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.enable_duties_cache));
+}
+
+#[test]
+fn signing_lease_flags() {
+    CommandLineTest::new()
+        .flag("signing-lease-url", Some("http://localhost:9000"))
+        .flag("signing-lease-holder-id", Some("vc-1"))
+        .run()
+        .with_config(|config| {
+            let signing_lease = config.signing_lease.as_ref().unwrap();
+            assert_eq!(
+                signing_lease.url,
+                SensitiveUrl::parse("http://localhost:9000").unwrap()
+            );
+            assert_eq!(signing_lease.holder_id, "vc-1");
+            assert_eq!(signing_lease.lease_duration, Duration::from_secs(36));
+            assert_eq!(signing_lease.failure_mode, FailureMode::Closed);
+        });
+}
+
+#[test]
+fn signing_lease_duration_and_failure_mode_flags() {
+    CommandLineTest::new()
+        .flag("signing-lease-url", Some("http://localhost:9000"))
+        .flag("signing-lease-holder-id", Some("vc-1"))
+        .flag("signing-lease-duration", Some("60"))
+        .flag("signing-lease-failure-mode", Some("open"))
+        .run()
+        .with_config(|config| {
+            let signing_lease = config.signing_lease.as_ref().unwrap();
+            assert_eq!(signing_lease.lease_duration, Duration::from_secs(60));
+            assert_eq!(signing_lease.failure_mode, FailureMode::Open);
+        });
+}
+
+#[test]
+#[should_panic]
+fn signing_lease_url_without_holder_id_flag() {
+    CommandLineTest::new()
+        .flag("signing-lease-url", Some("http://localhost:9000"))
+        .run()
+        .with_config(|config| assert!(config.signing_lease.is_some()));
+}
+
+#[test]
+fn no_signing_lease_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(config.signing_lease.is_none()));
+}
+
 #[test]
 fn no_gas_limit_flag() {
     CommandLineTest::new()
@@ -592,6 +721,20 @@ fn wrong_broadcast_flag() {
         });
 }
 
+#[test]
+fn log_duty_ids_default() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(!config.log_duty_ids));
+}
+#[test]
+fn log_duty_ids_flag() {
+    CommandLineTest::new()
+        .flag("log-duty-ids", None)
+        .run()
+        .with_config(|config| assert!(config.log_duty_ids));
+}
+
 #[test]
 fn disable_latency_measurement_service() {
     CommandLineTest::new()
@@ -9,6 +9,8 @@ use tempfile::{tempdir, TempDir};
 use types::*;
 use validator_manager::{
     create_validators::CreateConfig,
+    export_bundle::ExportBundleConfig,
+    import_bundle::ImportBundleConfig,
     import_validators::ImportConfig,
     move_validators::{MoveConfig, PasswordSource, Validators},
 };
@@ -105,6 +107,18 @@ impl CommandLineTest<MoveConfig> {
     }
 }
 
+impl CommandLineTest<ExportBundleConfig> {
+    fn export_bundle() -> Self {
+        Self::default().flag("export-bundle", None)
+    }
+}
+
+impl CommandLineTest<ImportBundleConfig> {
+    fn import_bundle() -> Self {
+        Self::default().flag("import-bundle", None)
+    }
+}
+
 #[test]
 pub fn validator_create_without_output_path() {
     CommandLineTest::validators_create().assert_failed();
@@ -394,3 +408,95 @@ pub fn validator_move_count() {
             assert_eq!(expected, config);
         });
 }
+
+#[test]
+pub fn export_bundle_defaults() {
+    CommandLineTest::export_bundle()
+        .flag("--output-path", Some("./bundle.json"))
+        .flag("--vc-token", Some("./token.json"))
+        .assert_success(|config| {
+            let expected = ExportBundleConfig {
+                output_path: PathBuf::from("./bundle.json"),
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                password_file: None,
+                include_slashing_protection: false,
+                stdin_inputs: cfg!(windows) || false,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn export_bundle_misc_flags() {
+    CommandLineTest::export_bundle()
+        .flag("--output-path", Some("./bundle.json"))
+        .flag("--vc-url", Some("http://localhost:1"))
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--password-file", Some("./password.txt"))
+        .flag("--include-slashing-protection", None)
+        .flag("--stdin-inputs", None)
+        .assert_success(|config| {
+            let expected = ExportBundleConfig {
+                output_path: PathBuf::from("./bundle.json"),
+                vc_url: SensitiveUrl::parse("http://localhost:1").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                password_file: Some(PathBuf::from("./password.txt")),
+                include_slashing_protection: true,
+                stdin_inputs: true,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn export_bundle_missing_output_path() {
+    CommandLineTest::export_bundle()
+        .flag("--vc-token", Some("./token.json"))
+        .assert_failed();
+}
+
+#[test]
+pub fn import_bundle_defaults() {
+    CommandLineTest::import_bundle()
+        .flag("--bundle-file", Some("./bundle.json"))
+        .flag("--vc-token", Some("./token.json"))
+        .assert_success(|config| {
+            let expected = ImportBundleConfig {
+                bundle_path: PathBuf::from("./bundle.json"),
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                password_file: None,
+                dry_run: false,
+                stdin_inputs: cfg!(windows) || false,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn import_bundle_misc_flags() {
+    CommandLineTest::import_bundle()
+        .flag("--bundle-file", Some("./bundle.json"))
+        .flag("--vc-token", Some("./token.json"))
+        .flag("--password-file", Some("./password.txt"))
+        .flag("--dry-run", None)
+        .assert_success(|config| {
+            let expected = ImportBundleConfig {
+                bundle_path: PathBuf::from("./bundle.json"),
+                vc_url: SensitiveUrl::parse("http://localhost:5062").unwrap(),
+                vc_token_path: PathBuf::from("./token.json"),
+                password_file: Some(PathBuf::from("./password.txt")),
+                dry_run: true,
+                stdin_inputs: cfg!(windows) || false,
+            };
+            assert_eq!(expected, config);
+        });
+}
+
+#[test]
+pub fn import_bundle_missing_bundle_file() {
+    CommandLineTest::import_bundle()
+        .flag("--vc-token", Some("./token.json"))
+        .assert_failed();
+}
@@ -1,6 +1,6 @@
 use super::*;
 use crate::case_result::compare_result;
-use beacon_chain::kzg_utils::validate_blobs;
+use beacon_chain::kzg_utils::validate_blobs_with_attribution;
 use kzg::Error as KzgError;
 use serde::Deserialize;
 use std::marker::PhantomData;
@@ -55,22 +55,23 @@ impl<E: EthSpec> Case for KZGVerifyBlobKZGProofBatch<E> {
 
         let kzg = get_kzg()?;
 
-        let result =
-            parse_input(&self.input).and_then(
-                |(commitments, blobs, proofs)| match validate_blobs::<E>(
-                    &kzg,
-                    &commitments,
-                    blobs.iter().collect(),
-                    &proofs,
-                ) {
-                    Ok(_) => Ok(true),
-                    Err(KzgError::KzgVerificationFailed) => Ok(false),
-                    Err(e) => Err(Error::InternalError(format!(
-                        "Failed to validate blobs: {:?}",
-                        e
-                    ))),
-                },
-            );
+        let result = parse_input(&self.input).and_then(|(commitments, blobs, proofs)| {
+            match validate_blobs_with_attribution::<E>(
+                &kzg,
+                &commitments,
+                blobs.iter().collect(),
+                &proofs,
+            )
+            .map_err(|e| e.into_kzg_error())
+            {
+                Ok(_) => Ok(true),
+                Err(KzgError::KzgVerificationFailed) => Ok(false),
+                Err(e) => Err(Error::InternalError(format!(
+                    "Failed to validate blobs: {:?}",
+                    e
+                ))),
+            }
+        });
 
         compare_result::<bool, _>(&result, &self.output)
     }
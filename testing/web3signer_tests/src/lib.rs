@@ -364,6 +364,7 @@ mod tests {
                 Hash256::repeat_byte(42),
                 spec,
                 None,
+                None,
                 slot_clock,
                 &config,
                 executor,
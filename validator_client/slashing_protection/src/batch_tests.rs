@@ -0,0 +1,140 @@
+//! Tests for checking and inserting attestations in batches.
+#![cfg(test)]
+
+use crate::attestation_tests::attestation_data_builder;
+use crate::test_utils::*;
+use crate::*;
+use std::time::Instant;
+use tempfile::tempdir;
+
+fn create_db_with_validators(num_validators: usize) -> (tempfile::TempDir, SlashingDatabase) {
+    let dir = tempdir().unwrap();
+    let slashing_db_file = dir.path().join("slashing_protection.sqlite");
+    let slashing_db = SlashingDatabase::create(&slashing_db_file).unwrap();
+    let pubkeys = (0..num_validators).map(pubkey).collect::<Vec<_>>();
+    slashing_db.register_validators(pubkeys.iter()).unwrap();
+    (dir, slashing_db)
+}
+
+#[test]
+fn attestation_batch_many_validators() {
+    let num_validators = 16;
+    let (_dir, slashing_db) = create_db_with_validators(num_validators);
+
+    let pubkeys = (0..num_validators).map(pubkey).collect::<Vec<_>>();
+    let data = attestation_data_builder(0, 1);
+    let batch = pubkeys
+        .iter()
+        .map(|pk| (pk, &data, DEFAULT_DOMAIN))
+        .collect::<Vec<_>>();
+
+    let results = slashing_db.check_and_insert_attestations(&batch).unwrap();
+    assert_eq!(results, vec![Ok(Safe::Valid); num_validators]);
+
+    // Every attestation was recorded.
+    let results = slashing_db.check_and_insert_attestations(&batch).unwrap();
+    assert_eq!(results, vec![Ok(Safe::SameData); num_validators]);
+}
+
+#[test]
+fn attestation_batch_slashable_does_not_affect_others() {
+    let (_dir, slashing_db) = create_db_with_validators(2);
+    let (pk0, pk1) = (pubkey(0), pubkey(1));
+
+    let prev = attestation_data_builder(0, 2);
+    slashing_db
+        .check_and_insert_attestation(&pk0, &prev, DEFAULT_DOMAIN)
+        .unwrap();
+
+    // A double vote for `pk0` and a valid vote for `pk1`.
+    let double_vote = attestation_data_builder(1, 2);
+    let results = slashing_db
+        .check_and_insert_attestations(&[
+            (&pk0, &double_vote, DEFAULT_DOMAIN),
+            (&pk1, &double_vote, DEFAULT_DOMAIN),
+        ])
+        .unwrap();
+    assert_eq!(
+        results,
+        vec![
+            Err(NotSafe::InvalidAttestation(InvalidAttestation::DoubleVote(
+                SignedAttestation::from_attestation(&prev, DEFAULT_DOMAIN)
+            ))),
+            Ok(Safe::Valid),
+        ]
+    );
+
+    assert_eq!(
+        slashing_db.check_and_insert_attestation(&pk1, &double_vote, DEFAULT_DOMAIN),
+        Ok(Safe::SameData)
+    );
+}
+
+#[test]
+fn attestation_batch_unregistered_validator() {
+    let (_dir, slashing_db) = create_db_with_validators(1);
+    let (pk0, pk1) = (pubkey(0), pubkey(1));
+    let data = attestation_data_builder(0, 1);
+
+    let results = slashing_db
+        .check_and_insert_attestations(&[
+            (&pk0, &data, DEFAULT_DOMAIN),
+            (&pk1, &data, DEFAULT_DOMAIN),
+        ])
+        .unwrap();
+    assert_eq!(
+        results,
+        vec![Ok(Safe::Valid), Err(NotSafe::UnregisteredValidator(pk1))]
+    );
+}
+
+#[test]
+fn attestation_batch_empty() {
+    let (_dir, slashing_db) = create_db_with_validators(0);
+    assert!(slashing_db
+        .check_and_insert_attestations(&[])
+        .unwrap()
+        .is_empty());
+}
+
+/// Compare the time taken to insert one attestation per validator individually and as a batch.
+///
+/// Run with `cargo test --release -p slashing_protection -- --ignored --nocapture`.
+#[test]
+#[ignore]
+fn attestation_batch_benchmark() {
+    let num_validators = 5_000;
+    let num_epochs = 4;
+    let (_dir, slashing_db) = create_db_with_validators(num_validators);
+    let pubkeys = (0..num_validators).map(pubkey).collect::<Vec<_>>();
+
+    let mut individual_total = 0;
+    let mut batch_total = 0;
+    for epoch in 0..num_epochs {
+        // Alternate between individual and batch insertion, so both see a similar DB size.
+        let individual = attestation_data_builder(2 * epoch, 2 * epoch + 1);
+        let start = Instant::now();
+        for pk in &pubkeys {
+            slashing_db
+                .check_and_insert_attestation(pk, &individual, DEFAULT_DOMAIN)
+                .unwrap();
+        }
+        individual_total += start.elapsed().as_millis();
+
+        let batched = attestation_data_builder(2 * epoch + 1, 2 * epoch + 2);
+        let batch = pubkeys
+            .iter()
+            .map(|pk| (pk, &batched, DEFAULT_DOMAIN))
+            .collect::<Vec<_>>();
+        let start = Instant::now();
+        let results = slashing_db.check_and_insert_attestations(&batch).unwrap();
+        batch_total += start.elapsed().as_millis();
+        assert!(results.iter().all(|result| result == &Ok(Safe::Valid)));
+    }
+
+    println!(
+        "{num_validators} validators, {num_epochs} epochs: individual {}ms/epoch, batch {}ms/epoch",
+        individual_total / num_epochs as u128,
+        batch_total / num_epochs as u128,
+    );
+}
@@ -1,4 +1,5 @@
 mod attestation_tests;
+mod batch_tests;
 mod block_tests;
 mod extra_interchange_tests;
 pub mod interchange;
@@ -27,7 +28,7 @@ pub const SLASHING_PROTECTION_FILENAME: &str = "slashing_protection.sqlite";
 /// The attestation or block is not safe to sign.
 ///
 /// This could be because it's slashable, or because an error occurred.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum NotSafe {
     UnregisteredValidator(PublicKeyBytes),
     DisabledValidator(PublicKeyBytes),
@@ -41,7 +42,7 @@ pub enum NotSafe {
 }
 
 /// The attestation or block is safe to sign, and will not cause the signer to be slashed.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 pub enum Safe {
     /// Casting the exact same data (block or attestation) twice is never slashable.
     SameData,
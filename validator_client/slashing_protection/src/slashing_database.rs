@@ -620,6 +620,34 @@ impl SlashingDatabase {
         )
     }
 
+    /// Check and insert a batch of attestations for (possibly) many validators in a single
+    /// exclusive transaction.
+    ///
+    /// The outer `Result` is an error affecting the whole batch, in which case nothing is
+    /// committed. The inner results are the outcome for each attestation, in the same order as
+    /// `attestations`. A slashable attestation does not prevent the others from being inserted.
+    pub fn check_and_insert_attestations(
+        &self,
+        attestations: &[(&PublicKeyBytes, &AttestationData, Hash256)],
+    ) -> Result<Vec<Result<Safe, NotSafe>>, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+        let results = attestations
+            .iter()
+            .map(|(validator_pubkey, attestation, domain)| {
+                self.check_and_insert_attestation_signing_root_txn(
+                    validator_pubkey,
+                    attestation.source.epoch,
+                    attestation.target.epoch,
+                    attestation.signing_root(*domain).into(),
+                    &txn,
+                )
+            })
+            .collect();
+        txn.commit()?;
+        Ok(results)
+    }
+
     /// As for `check_and_insert_attestation` but without requiring the whole `AttestationData`.
     pub fn check_and_insert_attestation_signing_root(
         &self,
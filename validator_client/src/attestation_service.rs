@@ -1,4 +1,5 @@
 use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
+use crate::duty_log::{duty_logger, Duty};
 use crate::{
     duties_service::{DutiesService, DutyAndProof},
     http_metrics::metrics,
@@ -7,7 +8,7 @@ use crate::{
 };
 use environment::RuntimeContext;
 use futures::future::join_all;
-use slog::{crit, debug, error, info, trace, warn};
+use slog::{crit, debug, error, info, trace, warn, Logger};
 use slot_clock::SlotClock;
 use std::collections::HashMap;
 use std::ops::Deref;
@@ -26,6 +27,7 @@ pub struct AttestationServiceBuilder<T: SlotClock + 'static, E: EthSpec> {
     slot_clock: Option<T>,
     beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     context: Option<RuntimeContext<E>>,
+    log_duty_ids: bool,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
@@ -36,6 +38,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
             slot_clock: None,
             beacon_nodes: None,
             context: None,
+            log_duty_ids: false,
         }
     }
 
@@ -64,6 +67,11 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
         self
     }
 
+    pub fn log_duty_ids(mut self, log_duty_ids: bool) -> Self {
+        self.log_duty_ids = log_duty_ids;
+        self
+    }
+
     pub fn build(self) -> Result<AttestationService<T, E>, String> {
         Ok(AttestationService {
             inner: Arc::new(Inner {
@@ -82,6 +90,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationServiceBuilder<T, E> {
                 context: self
                     .context
                     .ok_or("Cannot build AttestationService without runtime_context")?,
+                log_duty_ids: self.log_duty_ids,
             }),
         })
     }
@@ -94,6 +103,7 @@ pub struct Inner<T, E: EthSpec> {
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
+    log_duty_ids: bool,
 }
 
 /// Attempts to produce attestations for all known validators 1/3rd of the way through each slot.
@@ -241,7 +251,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         validator_duties: Vec<DutyAndProof>,
         aggregate_production_instant: Instant,
     ) -> Result<(), ()> {
-        let log = self.context.log();
+        let log = &self.duty_log(slot, committee_index);
         let attestations_timer = metrics::start_timer_vec(
             &metrics::ATTESTATION_SERVICE_TIMES,
             &[metrics::ATTESTATIONS],
@@ -324,7 +334,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         committee_index: CommitteeIndex,
         validator_duties: &[DutyAndProof],
     ) -> Result<Option<AttestationData>, String> {
-        let log = self.context.log();
+        let log = &self.duty_log(slot, committee_index);
 
         if validator_duties.is_empty() {
             return Ok(None);
@@ -356,46 +366,61 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
             .await
             .map_err(|e| e.to_string())?;
 
-        // Create futures to produce signed `Attestation` objects.
-        let attestation_data_ref = &attestation_data;
-        let signing_futures = validator_duties.iter().map(|duty_and_proof| async move {
-            let duty = &duty_and_proof.duty;
-            let attestation_data = attestation_data_ref;
-
-            // Ensure that the attestation matches the duties.
-            #[allow(clippy::suspicious_operation_groupings)]
-            if duty.slot != attestation_data.slot || duty.committee_index != attestation_data.index
-            {
-                crit!(
-                    log,
-                    "Inconsistent validator duties during signing";
-                    "validator" => ?duty.pubkey,
-                    "duty_slot" => duty.slot,
-                    "attestation_slot" => attestation_data.slot,
-                    "duty_index" => duty.committee_index,
-                    "attestation_index" => attestation_data.index,
-                );
-                return None;
-            }
+        // Create the unsigned `Attestation` objects.
+        let unsigned_attestations = validator_duties
+            .iter()
+            .filter_map(|duty_and_proof| {
+                let duty = &duty_and_proof.duty;
+
+                // Ensure that the attestation matches the duties.
+                #[allow(clippy::suspicious_operation_groupings)]
+                if duty.slot != attestation_data.slot
+                    || duty.committee_index != attestation_data.index
+                {
+                    crit!(
+                        log,
+                        "Inconsistent validator duties during signing";
+                        "validator" => ?duty.pubkey,
+                        "duty_slot" => duty.slot,
+                        "attestation_slot" => attestation_data.slot,
+                        "duty_index" => duty.committee_index,
+                        "attestation_index" => attestation_data.index,
+                    );
+                    return None;
+                }
 
-            let mut attestation = Attestation {
-                aggregation_bits: BitList::with_capacity(duty.committee_length as usize).unwrap(),
-                data: attestation_data.clone(),
-                signature: AggregateSignature::infinity(),
-            };
+                let attestation = Attestation {
+                    aggregation_bits: BitList::with_capacity(duty.committee_length as usize)
+                        .unwrap(),
+                    data: attestation_data.clone(),
+                    signature: AggregateSignature::infinity(),
+                };
 
-            match self
-                .validator_store
-                .sign_attestation(
+                Some((
+                    duty.validator_index,
                     duty.pubkey,
                     duty.validator_committee_index as usize,
-                    &mut attestation,
-                    current_epoch,
-                )
-                .await
-            {
-                Ok(()) => Some((attestation, duty.validator_index)),
-                Err(ValidatorStoreError::UnknownPubkey(pubkey)) => {
+                    attestation,
+                ))
+            })
+            .collect::<Vec<_>>();
+        let pubkeys = unsigned_attestations
+            .iter()
+            .map(|(_, pubkey, _, _)| *pubkey)
+            .collect::<Vec<_>>();
+
+        // Sign all attestations, checking slashing protection for all of them at once.
+        let signed_attestations = self
+            .validator_store
+            .sign_attestations(unsigned_attestations, current_epoch)
+            .await;
+
+        let (ref attestations, ref validator_indices): (Vec<_>, Vec<_>) = signed_attestations
+            .into_iter()
+            .zip(pubkeys)
+            .filter_map(|((validator_index, result), pubkey)| match result {
+                Ok(attestation) => Some((attestation, validator_index)),
+                Err(ValidatorStoreError::UnknownPubkey(_)) => {
                     // A pubkey can be missing when a validator was recently
                     // removed via the API.
                     warn!(
@@ -403,7 +428,6 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                         "Missing pubkey for attestation";
                         "info" => "a validator may have recently been removed from this VC",
                         "pubkey" => ?pubkey,
-                        "validator" => ?duty.pubkey,
                         "committee_index" => committee_index,
                         "slot" => slot.as_u64(),
                     );
@@ -414,20 +438,13 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
                         log,
                         "Failed to sign attestation";
                         "error" => ?e,
-                        "validator" => ?duty.pubkey,
+                        "validator" => ?pubkey,
                         "committee_index" => committee_index,
                         "slot" => slot.as_u64(),
                     );
                     None
                 }
-            }
-        });
-
-        // Execute all the futures in parallel, collecting any successful results.
-        let (ref attestations, ref validator_indices): (Vec<_>, Vec<_>) = join_all(signing_futures)
-            .await
-            .into_iter()
-            .flatten()
+            })
             .unzip();
 
         if attestations.is_empty() {
@@ -495,7 +512,7 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         attestation_data: &AttestationData,
         validator_duties: &[DutyAndProof],
     ) -> Result<(), String> {
-        let log = self.context.log();
+        let log = &self.duty_log(attestation_data.slot, attestation_data.index);
 
         if !validator_duties
             .iter()
@@ -638,6 +655,17 @@ impl<T: SlotClock + 'static, E: EthSpec> AttestationService<T, E> {
         Ok(())
     }
 
+    /// Returns a logger for the attestation duties of `committee_index` at `slot`.
+    fn duty_log(&self, slot: Slot, committee_index: CommitteeIndex) -> Logger {
+        duty_logger(
+            self.context.log(),
+            self.log_duty_ids,
+            Duty::Attestation,
+            slot,
+            Some(committee_index),
+        )
+    }
+
     /// Spawn a blocking task to run the slashing protection pruning process.
     ///
     /// Start the task at `pruning_instant` to avoid interference with other tasks.
@@ -5,6 +5,7 @@
 use crate::check_synced::check_synced;
 use crate::http_metrics::metrics::{inc_counter_vec, ENDPOINT_ERRORS, ENDPOINT_REQUESTS};
 use environment::RuntimeContext;
+use eth2::types::EventTopic;
 use eth2::BeaconNodeHttpClient;
 use futures::future;
 use serde::{Deserialize, Serialize};
@@ -23,6 +24,15 @@ use types::{ChainSpec, Config, EthSpec};
 /// Message emitted when the VC detects the BN is using a different spec.
 const UPDATE_REQUIRED_LOG_HINT: &str = "this VC or the remote BN may need updating";
 
+/// The event topics which the VC subscribes to in order to follow the beacon node's head.
+pub const HEAD_EVENT_TOPICS: &[EventTopic] = &[EventTopic::Head, EventTopic::ChainReorg];
+
+/// The event topics which report re-orgs that change the VC's duties.
+pub const DUTIES_REORG_EVENT_TOPICS: &[EventTopic] = &[
+    EventTopic::AttesterDutiesReorg,
+    EventTopic::ProposerDutiesReorg,
+];
+
 /// The number of seconds *prior* to slot start that we will try and update the state of fallback
 /// nodes.
 ///
@@ -143,11 +153,39 @@ pub enum CandidateError {
     NotSynced,
 }
 
+/// The optional beacon node API features which the VC can make use of.
+///
+/// Features are assumed to be supported until the beacon node indicates otherwise, so that a
+/// probe which fails for an unrelated reason doesn't stop a supported feature from being used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// `GET v3/validator/blocks/{slot}`.
+    pub produce_block_v3: bool,
+    /// Subscriptions to the `HEAD_EVENT_TOPICS` on `GET events`.
+    pub head_events: bool,
+    /// Subscriptions to the `DUTIES_REORG_EVENT_TOPICS` on `GET events`.
+    pub duties_reorg_events: bool,
+    /// SSZ-encoded request bodies on `POST beacon/blocks` and `POST beacon/blinded_blocks`.
+    pub ssz_blocks: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self {
+            produce_block_v3: true,
+            head_events: true,
+            duties_reorg_events: true,
+            ssz_blocks: true,
+        }
+    }
+}
+
 /// Represents a `BeaconNodeHttpClient` inside a `BeaconNodeFallback` that may or may not be used
 /// for a query.
 pub struct CandidateBeaconNode<E> {
     beacon_node: BeaconNodeHttpClient,
     status: RwLock<Result<(), CandidateError>>,
+    capabilities: RwLock<Capabilities>,
     _phantom: PhantomData<E>,
 }
 
@@ -157,6 +195,7 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         Self {
             beacon_node,
             status: RwLock::new(Err(CandidateError::Uninitialized)),
+            capabilities: RwLock::new(Capabilities::default()),
             _phantom: PhantomData,
         }
     }
@@ -171,6 +210,11 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         }
     }
 
+    /// Returns the API features which `self` was found to support when it last connected.
+    pub async fn capabilities(&self) -> Capabilities {
+        *self.capabilities.read().await
+    }
+
     /// Indicate that `self` is offline.
     pub async fn set_offline(&self) {
         *self.status.write().await = Err(CandidateError::Offline)
@@ -203,9 +247,79 @@ impl<E: EthSpec> CandidateBeaconNode<E> {
         // holding a write-lock whilst we check the online status of the node.
         *self.status.write().await = new_status;
 
+        // Probe the node's features whenever it (re)connects, since it may have been upgraded or
+        // replaced in the meantime.
+        let is_available = |status: Result<(), CandidateError>| {
+            matches!(status, Ok(()) | Err(CandidateError::NotSynced))
+        };
+        if !is_available(previous_status) && is_available(new_status) {
+            self.refresh_capabilities(log).await;
+        }
+
         new_status
     }
 
+    /// Probe the node for the optional API features in `Capabilities`, updating
+    /// `self.capabilities`.
+    ///
+    /// A feature is only marked as unsupported if the node responds to say so.
+    async fn refresh_capabilities(&self, log: &Logger) {
+        let previous = self.capabilities().await;
+        let mut capabilities = previous;
+
+        if let Ok(supported) = self.beacon_node.supports_validator_blocks_v3().await {
+            capabilities.produce_block_v3 = supported;
+        }
+        if let Ok(supported) = self.beacon_node.supports_events(HEAD_EVENT_TOPICS).await {
+            capabilities.head_events = supported;
+        }
+        if let Ok(supported) = self
+            .beacon_node
+            .supports_events(DUTIES_REORG_EVENT_TOPICS)
+            .await
+        {
+            capabilities.duties_reorg_events = supported;
+        }
+        if let Ok(supported) = self.beacon_node.supports_ssz_blocks().await {
+            capabilities.ssz_blocks = supported;
+        }
+
+        if capabilities != previous {
+            if !capabilities.produce_block_v3 {
+                warn!(
+                    log,
+                    "Beacon node does not support v3 block production";
+                    "endpoint" => %self.beacon_node,
+                    "info" => "blocks will be requested using the v2 endpoints",
+                );
+            }
+            if !capabilities.head_events {
+                warn!(
+                    log,
+                    "Beacon node does not support head events";
+                    "endpoint" => %self.beacon_node,
+                    "info" => "attester and proposer duties will be polled every slot",
+                );
+            }
+            if !capabilities.ssz_blocks {
+                warn!(
+                    log,
+                    "Beacon node does not support SSZ blocks";
+                    "endpoint" => %self.beacon_node,
+                    "info" => "blocks will be published as JSON",
+                );
+            }
+            debug!(
+                log,
+                "Updated beacon node capabilities";
+                "endpoint" => %self.beacon_node,
+                "capabilities" => ?capabilities,
+            );
+        }
+
+        *self.capabilities.write().await = capabilities;
+    }
+
     /// Checks if the node is reachable.
     async fn is_online(&self, was_offline: bool, log: &Logger) -> Result<(), CandidateError> {
         let result = self
@@ -408,6 +522,16 @@ impl<T: SlotClock, E: EthSpec> BeaconNodeFallback<T, E> {
         n
     }
 
+    /// The capabilities of the candidate which uses `beacon_node`, or `None` if it is not one of
+    /// `self.candidates`.
+    pub async fn capabilities(&self, beacon_node: &BeaconNodeHttpClient) -> Option<Capabilities> {
+        let candidate = self
+            .candidates
+            .iter()
+            .find(|candidate| candidate.beacon_node.as_ref() == beacon_node.as_ref())?;
+        Some(candidate.capabilities().await)
+    }
+
     /// Loop through ALL candidates in `self.candidates` and update their sync status.
     ///
     /// It is possible for a node to return an unsynced status while continuing to serve
@@ -1,7 +1,8 @@
 use crate::beacon_node_fallback::{Error as FallbackError, Errors};
 use crate::{
-    beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced},
+    beacon_node_fallback::{ApiTopic, BeaconNodeFallback, Capabilities, RequireSynced},
     determine_graffiti,
+    duty_log::{duty_logger, Duty},
     graffiti_file::GraffitiFile,
     OfflineOnFailure,
 };
@@ -59,6 +60,7 @@ pub struct BlockServiceBuilder<T, E: EthSpec> {
     context: Option<RuntimeContext<E>>,
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
+    log_duty_ids: bool,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
@@ -71,6 +73,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
             context: None,
             graffiti: None,
             graffiti_file: None,
+            log_duty_ids: false,
         }
     }
 
@@ -109,6 +112,11 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
         self
     }
 
+    pub fn log_duty_ids(mut self, log_duty_ids: bool) -> Self {
+        self.log_duty_ids = log_duty_ids;
+        self
+    }
+
     pub fn build(self) -> Result<BlockService<T, E>, String> {
         Ok(BlockService {
             inner: Arc::new(Inner {
@@ -127,6 +135,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockServiceBuilder<T, E> {
                 proposer_nodes: self.proposer_nodes,
                 graffiti: self.graffiti,
                 graffiti_file: self.graffiti_file,
+                log_duty_ids: self.log_duty_ids,
             }),
         })
     }
@@ -174,6 +183,16 @@ impl<T: SlotClock, E: EthSpec> ProposerFallback<T, E> {
             .await
     }
 
+    /// The capabilities of `beacon_node`, which may be either a beacon node or a proposer node.
+    pub async fn capabilities(&self, beacon_node: &BeaconNodeHttpClient) -> Capabilities {
+        let capabilities = self.beacon_nodes.capabilities(beacon_node).await;
+        match (capabilities, &self.proposer_nodes) {
+            (None, Some(proposer_nodes)) => proposer_nodes.capabilities(beacon_node).await,
+            _ => capabilities,
+        }
+        .unwrap_or_default()
+    }
+
     // Try `func` on `self.beacon_nodes` first. If that doesn't work, try `self.proposer_nodes`.
     pub async fn request_proposers_last<'a, F, O, Err, R>(
         &'a self,
@@ -216,6 +235,7 @@ pub struct Inner<T, E: EthSpec> {
     context: RuntimeContext<E>,
     graffiti: Option<Graffiti>,
     graffiti_file: Option<GraffitiFile>,
+    log_duty_ids: bool,
 }
 
 /// Attempts to produce attestations for any block producer(s) at the start of the epoch.
@@ -434,7 +454,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         validator_pubkey: &PublicKeyBytes,
         unsigned_block: UnsignedBlock<E>,
     ) -> Result<(), BlockError> {
-        let log = self.context.log();
+        let log = &self.duty_log(slot);
         let signing_timer = metrics::start_timer(&metrics::BLOCK_SIGNING_TIMES);
 
         let res = match unsigned_block {
@@ -495,7 +515,8 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 |beacon_node| async {
-                    self.publish_signed_block_contents(&signed_block, beacon_node)
+                    let ssz = proposer_fallback.capabilities(beacon_node).await.ssz_blocks;
+                    self.publish_signed_block_contents(&signed_block, beacon_node, ssz)
                         .await
                 },
             )
@@ -519,7 +540,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         validator_pubkey: PublicKeyBytes,
         builder_boost_factor: Option<u64>,
     ) -> Result<(), BlockError> {
-        let log = self.context.log();
+        let log = &self.duty_log(slot);
         let _timer =
             metrics::start_timer_vec(&metrics::BLOCK_SERVICE_TIMES, &[metrics::BEACON_BLOCK]);
 
@@ -560,10 +581,14 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         let randao_reveal_ref = &randao_reveal;
         let self_ref = &self;
         let proposer_index = self.validator_store.validator_index(&validator_pubkey);
+        let builder_proposal = self
+            .validator_store
+            .get_builder_proposals(&validator_pubkey);
         let proposer_fallback = ProposerFallback {
             beacon_nodes: self.beacon_nodes.clone(),
             proposer_nodes: self.proposer_nodes.clone(),
         };
+        let proposer_fallback_ref = &proposer_fallback;
 
         info!(
             log,
@@ -580,20 +605,39 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
                 RequireSynced::No,
                 OfflineOnFailure::Yes,
                 |beacon_node| async move {
-                    let _get_timer = metrics::start_timer_vec(
-                        &metrics::BLOCK_SERVICE_TIMES,
-                        &[metrics::BEACON_BLOCK_HTTP_GET],
-                    );
-                    let block_response = Self::get_validator_block_v3(
-                        beacon_node,
-                        slot,
-                        randao_reveal_ref,
-                        graffiti,
-                        proposer_index,
-                        builder_boost_factor,
-                        log,
-                    )
-                    .await
+                    // Beacon nodes without the v3 endpoint are asked for a full or blinded block
+                    // according to the validator's builder preference instead.
+                    let block_response = if proposer_fallback_ref
+                        .capabilities(beacon_node)
+                        .await
+                        .produce_block_v3
+                    {
+                        let _get_timer = metrics::start_timer_vec(
+                            &metrics::BLOCK_SERVICE_TIMES,
+                            &[metrics::BEACON_BLOCK_HTTP_GET],
+                        );
+                        Self::get_validator_block_v3(
+                            beacon_node,
+                            slot,
+                            randao_reveal_ref,
+                            graffiti,
+                            proposer_index,
+                            builder_boost_factor,
+                            log,
+                        )
+                        .await
+                    } else {
+                        Self::get_validator_block(
+                            beacon_node,
+                            slot,
+                            randao_reveal_ref,
+                            graffiti,
+                            proposer_index,
+                            builder_proposal,
+                            log,
+                        )
+                        .await
+                    }
                     .map_err(|e| {
                         BlockError::Recoverable(format!(
                             "Error from beacon node when producing block: {:?}",
@@ -626,7 +670,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         validator_pubkey: PublicKeyBytes,
         builder_proposal: bool,
     ) -> Result<(), BlockError> {
-        let log = self.context.log();
+        let log = &self.duty_log(slot);
         let _timer =
             metrics::start_timer_vec(&metrics::BLOCK_SERVICE_TIMES, &[metrics::BEACON_BLOCK]);
 
@@ -717,34 +761,52 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         &self,
         signed_block: &SignedBlock<E>,
         beacon_node: &BeaconNodeHttpClient,
+        ssz: bool,
     ) -> Result<(), BlockError> {
-        let log = self.context.log();
         let slot = signed_block.slot();
+        let log = &self.duty_log(slot);
         match signed_block {
             SignedBlock::Full(signed_block) => {
                 let _post_timer = metrics::start_timer_vec(
                     &metrics::BLOCK_SERVICE_TIMES,
                     &[metrics::BEACON_BLOCK_HTTP_POST],
                 );
-                beacon_node
-                    .post_beacon_blocks(signed_block)
-                    .await
-                    .or_else(|e| handle_block_post_error(e, slot, log))?
+                if ssz {
+                    beacon_node.post_beacon_blocks_ssz(signed_block).await
+                } else {
+                    beacon_node.post_beacon_blocks(signed_block).await
+                }
+                .or_else(|e| handle_block_post_error(e, slot, log))?
             }
             SignedBlock::Blinded(signed_block) => {
                 let _post_timer = metrics::start_timer_vec(
                     &metrics::BLOCK_SERVICE_TIMES,
                     &[metrics::BLINDED_BEACON_BLOCK_HTTP_POST],
                 );
-                beacon_node
-                    .post_beacon_blinded_blocks(signed_block)
-                    .await
-                    .or_else(|e| handle_block_post_error(e, slot, log))?
+                if ssz {
+                    beacon_node
+                        .post_beacon_blinded_blocks_ssz(signed_block)
+                        .await
+                } else {
+                    beacon_node.post_beacon_blinded_blocks(signed_block).await
+                }
+                .or_else(|e| handle_block_post_error(e, slot, log))?
             }
         }
         Ok::<_, BlockError>(())
     }
 
+    /// Returns a logger for the block proposal at `slot`.
+    fn duty_log(&self, slot: Slot) -> Logger {
+        duty_logger(
+            self.context.log(),
+            self.log_duty_ids,
+            Duty::Block,
+            slot,
+            None,
+        )
+    }
+
     async fn get_validator_block_v3(
         beacon_node: &BeaconNodeHttpClient,
         slot: Slot,
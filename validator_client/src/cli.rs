@@ -118,6 +118,18 @@ pub fn cli_app() -> Command {
             )
             .display_order(0)
         )
+        .arg(
+            Arg::new("disable-key-cache")
+            .long("disable-key-cache")
+            .action(ArgAction::SetTrue)
+            .help_heading(FLAG_HEADER)
+            .help(
+                "If present, do not keep an encrypted cache of the decrypted validator keys on \
+                disk. Every keystore will be decrypted on startup, which can take several minutes \
+                with thousands of validators. Any existing key cache is deleted."
+            )
+            .display_order(0)
+        )
         .arg(
             Arg::new("use-long-timeouts")
                 .long("use-long-timeouts")
@@ -186,6 +198,72 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("enable-duties-cache")
+                .long("enable-duties-cache")
+                .help("Only re-download attester and proposer duties when the beacon node's head \
+                       or chain_reorg events indicate that they may have changed, rather than \
+                       polling for them every slot. This reduces the load on the beacon node when managing many \
+                       validators.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("log-duty-ids")
+                .long("log-duty-ids")
+                .help("Add a `duty_id` field to the logs of each block proposal, attestation and \
+                       sync committee duty. The ID is the same from fetching the data to sign \
+                       through to publishing it, so a duty can be followed through structured \
+                       logs, e.g. with `--log-format JSON`.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("signing-lease-url")
+                .long("signing-lease-url")
+                .value_name("URL")
+                .help("URL of a lease service from which a lease must be held for each validator \
+                       before signing blocks and attestations for it. This allows a standby \
+                       validator client to run with the same keys, which only signs once the \
+                       active client stops renewing its leases.")
+                .requires("signing-lease-holder-id")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("signing-lease-holder-id")
+                .long("signing-lease-holder-id")
+                .value_name("ID")
+                .help("Identifies this validator client to the signing lease service. It must \
+                       differ between validator clients which share keys, and stay the same \
+                       across restarts.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("signing-lease-duration")
+                .long("signing-lease-duration")
+                .value_name("SECONDS")
+                .help("Duration to request each signing lease for. Leases are renewed once half \
+                       of this has elapsed, so it should be at least two slots.")
+                .default_value("36")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("signing-lease-failure-mode")
+                .long("signing-lease-failure-mode")
+                .value_name("MODE")
+                .help("What to do when a signing lease has expired and the lease service can't \
+                       be reached. 'closed' refuses to sign. 'open' signs anyway, which is only \
+                       safe if no other validator client can be signing with the same keys.")
+                .value_parser(["closed", "open"])
+                .default_value("closed")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /* REST API related arguments */
         .arg(
             Arg::new("http")
@@ -272,6 +350,29 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("http-readiness-checks")
+                .long("http-readiness-checks")
+                .requires("http")
+                .value_name("CHECKS")
+                .help("Comma-separated list of checks which must pass for the \
+                    GET /lighthouse/health/ready HTTP endpoint to report the validator \
+                    client as ready. Possible values are: none, keystores, beacon-nodes, \
+                    slashing-protection. Default (when flag is omitted) is all checks.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-ready-min-synced-beacon-nodes")
+                .long("http-ready-min-synced-beacon-nodes")
+                .requires("http")
+                .value_name("COUNT")
+                .help("The number of synced beacon nodes required for the beacon-nodes \
+                    readiness check to pass.")
+                .default_value_if("http", ArgPredicate::IsPresent, "1")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         /* Prometheus metrics HTTP server related arguments */
         .arg(
             Arg::new("metrics")
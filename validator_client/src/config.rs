@@ -1,6 +1,7 @@
 use crate::beacon_node_fallback::ApiTopic;
 use crate::graffiti_file::GraffitiFile;
-use crate::{http_api, http_metrics};
+use crate::http_api::ReadinessCheck;
+use crate::{http_api, http_metrics, signing_lease};
 use clap::ArgMatches;
 use clap_utils::{flags::DISABLE_MALLOC_TUNING_FLAG, parse_optional, parse_required};
 use directory::{
@@ -38,6 +39,8 @@ pub struct Config {
     pub allow_unsynced_beacon_node: bool,
     /// If true, don't scan the validators dir for new keystores.
     pub disable_auto_discover: bool,
+    /// If true, don't cache decrypted validator keys on disk.
+    pub disable_key_cache: bool,
     /// If true, re-register existing validators in definitions.yml for slashing protection.
     pub init_slashing_protection: bool,
     /// If true, use longer timeouts for requests made to the beacon node.
@@ -87,6 +90,13 @@ pub struct Config {
     pub prefer_builder_proposals: bool,
     /// Whether we are running with distributed network support.
     pub distributed: bool,
+    /// Only re-download attester and proposer duties when the beacon node's head events indicate
+    /// that they may have changed.
+    pub enable_duties_cache: bool,
+    /// Label the logs of each duty with a `duty_id` which is constant across its lifecycle.
+    pub log_duty_ids: bool,
+    /// Configuration for the external service which grants leases to sign for each validator.
+    pub signing_lease: Option<signing_lease::Config>,
     pub web3_signer_keep_alive_timeout: Option<Duration>,
     pub web3_signer_max_idle_connections: Option<usize>,
 }
@@ -112,6 +122,7 @@ impl Default for Config {
             proposer_nodes: Vec::new(),
             allow_unsynced_beacon_node: false,
             disable_auto_discover: false,
+            disable_key_cache: false,
             init_slashing_protection: false,
             use_long_timeouts: false,
             graffiti: None,
@@ -134,6 +145,9 @@ impl Default for Config {
             builder_boost_factor: None,
             prefer_builder_proposals: false,
             distributed: false,
+            enable_duties_cache: false,
+            log_duty_ids: false,
+            signing_lease: None,
             web3_signer_keep_alive_timeout: DEFAULT_WEB3SIGNER_KEEP_ALIVE,
             web3_signer_max_idle_connections: None,
         }
@@ -196,6 +210,7 @@ impl Config {
         }
 
         config.disable_auto_discover = cli_args.get_flag("disable-auto-discover");
+        config.disable_key_cache = cli_args.get_flag("disable-key-cache");
         config.init_slashing_protection = cli_args.get_flag("init-slashing-protection");
         config.use_long_timeouts = cli_args.get_flag("use-long-timeouts");
 
@@ -241,6 +256,14 @@ impl Config {
             config.distributed = true;
         }
 
+        if cli_args.get_flag("enable-duties-cache") {
+            config.enable_duties_cache = true;
+        }
+
+        if cli_args.get_flag("log-duty-ids") {
+            config.log_duty_ids = true;
+        }
+
         if cli_args.get_flag("disable-run-on-all") {
             warn!(
                 log,
@@ -261,6 +284,22 @@ impl Config {
                 .collect::<Result<_, _>>()?;
         }
 
+        /*
+         * Signing leases
+         */
+        if let Some(url) = cli_args.get_one::<String>("signing-lease-url") {
+            config.signing_lease = Some(signing_lease::Config {
+                url: SensitiveUrl::parse(url)
+                    .map_err(|e| format!("Invalid signing lease URL: {:?}", e))?,
+                holder_id: parse_required(cli_args, "signing-lease-holder-id")?,
+                lease_duration: Duration::from_secs(parse_required(
+                    cli_args,
+                    "signing-lease-duration",
+                )?),
+                failure_mode: parse_required(cli_args, "signing-lease-failure-mode")?,
+            });
+        }
+
         /*
          * Web3 signer
          */
@@ -321,6 +360,24 @@ impl Config {
             config.http_api.store_passwords_in_secrets_dir = true;
         }
 
+        if let Some(readiness_checks) = cli_args.get_one::<String>("http-readiness-checks") {
+            config.http_api.readiness_checks = readiness_checks
+                .split(',')
+                .filter(|c| *c != "none")
+                .map(|c| {
+                    c.trim()
+                        .parse::<ReadinessCheck>()
+                        .map_err(|_| format!("Unknown readiness check: {c}"))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Some(min_synced) =
+            parse_optional::<usize>(cli_args, "http-ready-min-synced-beacon-nodes")?
+        {
+            config.http_api.ready_min_synced_beacon_nodes = min_synced;
+        }
+
         /*
          * Prometheus metrics HTTP server
          */
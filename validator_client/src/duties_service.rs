@@ -1,11 +1,13 @@
 //! The `DutiesService` contains the attester/proposer duties for all local validators.
 //!
 //! It learns of the local validator via the `crate::ValidatorStore` struct. It keeps the duties
-//! up-to-date by polling the beacon node on regular intervals.
+//! up-to-date by polling the beacon node on regular intervals. Optionally, attester duties are only
+//! re-downloaded when the beacon node's head events indicate that they may have changed.
 //!
 //! The `DutiesService` is also responsible for sending events to the `BlockService` which trigger
 //! block production.
 
+pub mod dependent_roots;
 pub mod sync;
 
 use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, OfflineOnFailure, RequireSynced};
@@ -15,6 +17,7 @@ use crate::{
     http_metrics::metrics,
     validator_store::{DoppelgangerStatus, Error as ValidatorStoreError, ValidatorStore},
 };
+use dependent_roots::{listen_for_dependent_root_changes, DependentRootCache};
 use environment::RuntimeContext;
 use eth2::types::{
     AttesterData, BeaconCommitteeSubscription, DutiesResponse, ProposerData, StateId, ValidatorId,
@@ -229,9 +232,22 @@ pub struct DutiesService<T, E: EthSpec> {
     pub enable_high_validator_count_metrics: bool,
     /// If this validator is running in distributed mode.
     pub distributed: bool,
+    /// Tracks head events from the beacon node so that attester and proposer duties are only
+    /// re-downloaded when their dependent root may have changed. Set to `None` to poll every slot.
+    pub dependent_root_cache: Option<Arc<DependentRootCache>>,
 }
 
 impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
+    /// Resolves once the beacon node reports a re-org which changed the attester duties.
+    ///
+    /// Never resolves unless the duties cache is enabled, since re-orgs are only followed then.
+    async fn attester_duties_changed(&self) {
+        match &self.dependent_root_cache {
+            Some(cache) => cache.attester_duties_changed().await,
+            None => std::future::pending().await,
+        }
+    }
+
     /// Returns the total number of validators known to the duties service.
     pub fn total_validator_count(&self) -> usize {
         self.validator_store.num_voting_validators()
@@ -372,6 +388,17 @@ pub fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
         "duties_service_indices",
     );
 
+    /*
+     * Spawn the task which listens for changes to the duty dependent roots.
+     */
+    if let Some(cache) = core_duties_service.dependent_root_cache.clone() {
+        let duties_service = core_duties_service.clone();
+        core_duties_service.context.executor.spawn(
+            listen_for_dependent_root_changes(duties_service, cache),
+            "duties_service_dependent_roots",
+        );
+    }
+
     /*
      * Spawn the task which keeps track of local block proposal duties.
      */
@@ -411,7 +438,11 @@ pub fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
         async move {
             loop {
                 if let Some(duration) = duties_service.slot_clock.duration_to_next_slot() {
-                    sleep(duration).await;
+                    // Poll early if the beacon node reports that the duties have changed.
+                    tokio::select! {
+                        _ = sleep(duration) => (),
+                        _ = duties_service.attester_duties_changed() => (),
+                    }
                 } else {
                     // Just sleep for one slot if we are unable to read the system clock, this gives
                     // us an opportunity for the clock to eventually come good.
@@ -638,15 +669,26 @@ async fn poll_beacon_attesters<T: SlotClock + 'static, E: EthSpec>(
         local_indices
     };
 
+    // If the beacon node's head events tell us that the dependent roots haven't changed since we
+    // last downloaded duties, only new validators need their duties downloaded.
+    let cache_generation = duties_service
+        .dependent_root_cache
+        .as_ref()
+        .map(|cache| (cache.generation(), cache.attesters_are_fresh(current_epoch)));
+    let duties_are_fresh = matches!(cache_generation, Some((_, true)));
+    let mut all_duties_updated = true;
+
     // Download the duties and update the duties for the current epoch.
     if let Err(e) = poll_beacon_attesters_for_epoch(
         duties_service,
         current_epoch,
         &local_indices,
         &local_pubkeys,
+        duties_are_fresh,
     )
     .await
     {
+        all_duties_updated = false;
         error!(
             log,
             "Failed to download attester duties";
@@ -665,10 +707,16 @@ async fn poll_beacon_attesters<T: SlotClock + 'static, E: EthSpec>(
     );
 
     // Download the duties and update the duties for the next epoch.
-    if let Err(e) =
-        poll_beacon_attesters_for_epoch(duties_service, next_epoch, &local_indices, &local_pubkeys)
-            .await
+    if let Err(e) = poll_beacon_attesters_for_epoch(
+        duties_service,
+        next_epoch,
+        &local_indices,
+        &local_pubkeys,
+        duties_are_fresh,
+    )
+    .await
     {
+        all_duties_updated = false;
         error!(
             log,
             "Failed to download attester duties";
@@ -680,6 +728,14 @@ async fn poll_beacon_attesters<T: SlotClock + 'static, E: EthSpec>(
 
     update_per_validator_duty_metrics::<T, E>(duties_service, next_epoch, current_slot);
 
+    if let (Some(cache), Some((generation, _))) =
+        (&duties_service.dependent_root_cache, cache_generation)
+    {
+        if all_duties_updated {
+            cache.confirm_attesters(generation);
+        }
+    }
+
     drop(next_epoch_timer);
     let subscriptions_timer =
         metrics::start_timer_vec(&metrics::DUTIES_SERVICE_TIMES, &[metrics::SUBSCRIPTIONS]);
@@ -789,11 +845,15 @@ async fn poll_beacon_attesters<T: SlotClock + 'static, E: EthSpec>(
 
 /// For the given `local_indices` and `local_pubkeys`, download the duties for the given `epoch` and
 /// store them in `duties_service.attesters`.
+///
+/// If `duties_are_fresh` is `true` then the existing duties are known to be up-to-date and only the
+/// duties of validators without any duties for `epoch` are downloaded.
 async fn poll_beacon_attesters_for_epoch<T: SlotClock + 'static, E: EthSpec>(
     duties_service: &Arc<DutiesService<T, E>>,
     epoch: Epoch,
     local_indices: &[u64],
     local_pubkeys: &HashSet<PublicKeyBytes>,
+    duties_are_fresh: bool,
 ) -> Result<(), Error> {
     let log = duties_service.context.log();
 
@@ -818,6 +878,12 @@ async fn poll_beacon_attesters_for_epoch<T: SlotClock + 'static, E: EthSpec>(
     // request for extra data unless necessary in order to save on network bandwidth.
     let uninitialized_validators =
         get_uninitialized_validators(duties_service, &epoch, local_pubkeys);
+
+    if duties_are_fresh && uninitialized_validators.is_empty() {
+        metrics::inc_counter_vec(&metrics::DUTIES_CACHE_HITS, &[metrics::ATTESTER_DUTIES]);
+        return Ok(());
+    }
+
     let initial_indices_to_request = if !uninitialized_validators.is_empty() {
         uninitialized_validators.as_slice()
     } else {
@@ -1254,62 +1320,24 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
     // Only download duties and push out additional block production events if we have some
     // validators.
     if !local_pubkeys.is_empty() {
-        let download_result = duties_service
-            .beacon_nodes
-            .first_success(
-                RequireSynced::No,
-                OfflineOnFailure::Yes,
-                |beacon_node| async move {
-                    let _timer = metrics::start_timer_vec(
-                        &metrics::DUTIES_SERVICE_TIMES,
-                        &[metrics::PROPOSER_DUTIES_HTTP_GET],
-                    );
-                    beacon_node
-                        .get_validator_duties_proposer(current_epoch)
-                        .await
-                },
+        // If the beacon node's head events tell us that the dependent roots haven't changed since
+        // we last downloaded the duties of all of our validators, there's no need to download them
+        // again.
+        let cache_generation = duties_service.dependent_root_cache.as_ref().map(|cache| {
+            (
+                cache.generation(),
+                cache.proposers_are_fresh(current_epoch, &local_pubkeys),
             )
-            .await;
-
-        match download_result {
-            Ok(response) => {
-                let dependent_root = response.dependent_root;
-
-                let relevant_duties = response
-                    .data
-                    .into_iter()
-                    .filter(|proposer_duty| local_pubkeys.contains(&proposer_duty.pubkey))
-                    .collect::<Vec<_>>();
-
-                debug!(
-                    log,
-                    "Downloaded proposer duties";
-                    "dependent_root" => %dependent_root,
-                    "num_relevant_duties" => relevant_duties.len(),
-                );
+        });
 
-                if let Some((prior_dependent_root, _)) = duties_service
-                    .proposers
-                    .write()
-                    .insert(current_epoch, (dependent_root, relevant_duties))
-                {
-                    if dependent_root != prior_dependent_root {
-                        warn!(
-                            log,
-                            "Proposer duties re-org";
-                            "prior_dependent_root" => %prior_dependent_root,
-                            "dependent_root" => %dependent_root,
-                            "msg" => "this may happen from time to time"
-                        )
-                    }
-                }
+        if let Some((_, true)) = cache_generation {
+            metrics::inc_counter_vec(&metrics::DUTIES_CACHE_HITS, &[metrics::PROPOSER_DUTIES]);
+        } else if download_proposer_duties(duties_service, current_epoch, &local_pubkeys).await {
+            if let (Some(cache), Some((generation, _))) =
+                (&duties_service.dependent_root_cache, cache_generation)
+            {
+                cache.confirm_proposers(generation, &local_pubkeys);
             }
-            // Don't return early here, we still want to try and produce blocks using the cached values.
-            Err(e) => error!(
-                log,
-                "Failed to download proposer duties";
-                "err" => %e,
-            ),
         }
 
         // Compute the block proposers for this slot again, now that we've received an update from
@@ -1354,6 +1382,78 @@ async fn poll_beacon_proposers<T: SlotClock + 'static, E: EthSpec>(
     Ok(())
 }
 
+/// Download the proposer duties of `local_pubkeys` for `epoch` and store them in
+/// `duties_service.proposers`, returning `true` on success.
+///
+/// Errors are logged rather than returned, so that blocks can still be produced using the cached
+/// duties.
+pub(crate) async fn download_proposer_duties<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: &DutiesService<T, E>,
+    epoch: Epoch,
+    local_pubkeys: &HashSet<PublicKeyBytes>,
+) -> bool {
+    let log = duties_service.context.log();
+
+    let download_result = duties_service
+        .beacon_nodes
+        .first_success(
+            RequireSynced::No,
+            OfflineOnFailure::Yes,
+            |beacon_node| async move {
+                let _timer = metrics::start_timer_vec(
+                    &metrics::DUTIES_SERVICE_TIMES,
+                    &[metrics::PROPOSER_DUTIES_HTTP_GET],
+                );
+                beacon_node.get_validator_duties_proposer(epoch).await
+            },
+        )
+        .await;
+
+    match download_result {
+        Ok(response) => {
+            let dependent_root = response.dependent_root;
+
+            let relevant_duties = response
+                .data
+                .into_iter()
+                .filter(|proposer_duty| local_pubkeys.contains(&proposer_duty.pubkey))
+                .collect::<Vec<_>>();
+
+            debug!(
+                log,
+                "Downloaded proposer duties";
+                "dependent_root" => %dependent_root,
+                "num_relevant_duties" => relevant_duties.len(),
+            );
+
+            if let Some((prior_dependent_root, _)) = duties_service
+                .proposers
+                .write()
+                .insert(epoch, (dependent_root, relevant_duties))
+            {
+                if dependent_root != prior_dependent_root {
+                    warn!(
+                        log,
+                        "Proposer duties re-org";
+                        "prior_dependent_root" => %prior_dependent_root,
+                        "dependent_root" => %dependent_root,
+                        "msg" => "this may happen from time to time"
+                    )
+                }
+            }
+            true
+        }
+        Err(e) => {
+            error!(
+                log,
+                "Failed to download proposer duties";
+                "err" => %e,
+            );
+            false
+        }
+    }
+}
+
 /// Notify the block service if it should produce a block.
 async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
     current_slot: Slot,
@@ -0,0 +1,434 @@
+//! Tracks the duty dependent roots reported by the beacon node's `head` and `chain_reorg` SSE
+//! events.
+//!
+//! Attester and proposer duties can only change when the block at their dependent root changes.
+//! While the VC is subscribed to head events, it is therefore unnecessary to query the beacon node
+//! for these duties every slot: we only need to re-query at epoch boundaries, after a re-org, when
+//! validators are added, or when the event stream is interrupted.
+//!
+//! Sync committee duties are not tracked here, since they are fixed for a whole sync committee
+//! period and are already only downloaded once per period.
+//!
+//! Beacon nodes which support them also send `attester_duties_reorg` and `proposer_duties_reorg`
+//! events when a re-org changes a dependent root. These are used in place of `chain_reorg`, so that
+//! re-orgs which leave the duties unchanged don't invalidate the cache, and so that changed duties
+//! are re-downloaded immediately rather than at the start of the next slot.
+
+use super::{download_proposer_duties, DutiesService};
+use crate::beacon_node_fallback::{OfflineOnFailure, RequireSynced, HEAD_EVENT_TOPICS};
+use crate::http_metrics::metrics;
+use crate::validator_store::DoppelgangerStatus;
+use eth2::types::{EventKind, EventTopic, SseHead};
+use futures::StreamExt;
+use parking_lot::RwLock;
+use slog::{debug, warn};
+use slot_clock::SlotClock;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+use types::{Epoch, EthSpec, Hash256, PublicKeyBytes};
+
+/// The `HEAD_EVENT_TOPICS` along with those which report changes to duties.
+const HEAD_AND_DUTIES_REORG_EVENT_TOPICS: &[EventTopic] = &[
+    EventTopic::Head,
+    EventTopic::ChainReorg,
+    EventTopic::AttesterDutiesReorg,
+    EventTopic::ProposerDutiesReorg,
+];
+
+/// The dependent roots most recently reported by the beacon node.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HeadDependentRoots {
+    epoch: Epoch,
+    current_duty_dependent_root: Hash256,
+    previous_duty_dependent_root: Hash256,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// The roots from the latest head event, or `None` if the event stream is not connected.
+    roots: Option<HeadDependentRoots>,
+    /// Incremented every time the cached duties may have become stale.
+    generation: u64,
+    /// The generation at which all attester duties were last confirmed with the beacon node.
+    attesters_confirmed_generation: Option<u64>,
+    /// The generation at which the proposer duties were last confirmed with the beacon node, along
+    /// with the validators they were downloaded for.
+    proposers_confirmed: Option<(u64, HashSet<PublicKeyBytes>)>,
+}
+
+impl Inner {
+    /// Returns `true` if the latest head event is from `current_epoch`, and `confirmed_generation`
+    /// is the current generation.
+    fn is_fresh(&self, current_epoch: Epoch, confirmed_generation: Option<u64>) -> bool {
+        self.roots
+            .map_or(false, |roots| roots.epoch == current_epoch)
+            && confirmed_generation == Some(self.generation)
+    }
+}
+
+/// Determines whether the locally cached attester and proposer duties are known to be up-to-date.
+#[derive(Debug, Default)]
+pub struct DependentRootCache {
+    inner: RwLock<Inner>,
+    /// Notified when the beacon node reports a re-org which changed the attester duties.
+    attester_duties_changed: Notify,
+}
+
+impl DependentRootCache {
+    /// Process a `head` event, invalidating the cached duties if the dependent roots or the
+    /// epoch have changed.
+    pub fn on_head(&self, head: &SseHead, slots_per_epoch: u64) -> bool {
+        let roots = HeadDependentRoots {
+            epoch: head.slot.epoch(slots_per_epoch),
+            current_duty_dependent_root: head.current_duty_dependent_root,
+            previous_duty_dependent_root: head.previous_duty_dependent_root,
+        };
+
+        let mut inner = self.inner.write();
+        if inner.roots == Some(roots) {
+            return false;
+        }
+        inner.roots = Some(roots);
+        inner.generation += 1;
+        true
+    }
+
+    /// Invalidate the cached duties, forcing them to be re-checked against the beacon node.
+    pub fn invalidate(&self) {
+        self.inner.write().generation += 1;
+    }
+
+    /// Invalidate the cached duties and wake the task which polls attester duties.
+    pub fn on_attester_duties_reorg(&self) {
+        self.invalidate();
+        self.attester_duties_changed.notify_one();
+    }
+
+    /// Resolves after the next call to `on_attester_duties_reorg`, or immediately if one has
+    /// happened since this was last awaited.
+    pub async fn attester_duties_changed(&self) {
+        self.attester_duties_changed.notified().await
+    }
+
+    /// Forget all head information, e.g. because the event stream disconnected. Duties will be
+    /// polled every slot until a new head event is received.
+    pub fn reset(&self) {
+        let mut inner = self.inner.write();
+        inner.roots = None;
+        inner.generation += 1;
+    }
+
+    /// Returns the current generation, to be passed to `confirm_attesters` or `confirm_proposers`
+    /// once the duties have been refreshed from the beacon node.
+    pub fn generation(&self) -> u64 {
+        self.inner.read().generation
+    }
+
+    /// Record that the attester duties were refreshed from the beacon node whilst the cache was at
+    /// `generation`.
+    ///
+    /// If an invalidation happened in the meantime then the confirmation is ignored.
+    pub fn confirm_attesters(&self, generation: u64) {
+        let mut inner = self.inner.write();
+        if inner.generation == generation {
+            inner.attesters_confirmed_generation = Some(generation);
+        }
+    }
+
+    /// Returns `true` if the cached attester duties for `current_epoch` and the next epoch cannot
+    /// have changed since they were last downloaded.
+    pub fn attesters_are_fresh(&self, current_epoch: Epoch) -> bool {
+        let inner = self.inner.read();
+        inner.is_fresh(current_epoch, inner.attesters_confirmed_generation)
+    }
+
+    /// Record that the proposer duties of `local_pubkeys` were refreshed from the beacon node
+    /// whilst the cache was at `generation`.
+    ///
+    /// If an invalidation happened in the meantime then the confirmation is ignored.
+    pub fn confirm_proposers(&self, generation: u64, local_pubkeys: &HashSet<PublicKeyBytes>) {
+        let mut inner = self.inner.write();
+        if inner.generation == generation {
+            inner.proposers_confirmed = Some((generation, local_pubkeys.clone()));
+        }
+    }
+
+    /// Returns `true` if the cached proposer duties of `local_pubkeys` for `current_epoch` cannot
+    /// have changed since they were last downloaded.
+    pub fn proposers_are_fresh(
+        &self,
+        current_epoch: Epoch,
+        local_pubkeys: &HashSet<PublicKeyBytes>,
+    ) -> bool {
+        let inner = self.inner.read();
+        inner
+            .proposers_confirmed
+            .as_ref()
+            .map_or(false, |(generation, confirmed_pubkeys)| {
+                inner.is_fresh(current_epoch, Some(*generation))
+                    && local_pubkeys.is_subset(confirmed_pubkeys)
+            })
+    }
+}
+
+/// Subscribe to `head` and `chain_reorg` events from the beacon node and feed them into the
+/// `DependentRootCache`, re-connecting whenever the stream ends.
+pub async fn listen_for_dependent_root_changes<T: SlotClock + 'static, E: EthSpec>(
+    duties_service: Arc<DutiesService<T, E>>,
+    cache: Arc<DependentRootCache>,
+) {
+    let log = duties_service.context.log().clone();
+    let beacon_nodes = &duties_service.beacon_nodes;
+
+    loop {
+        // Failures don't mark a beacon node as offline: one without head events can still serve
+        // every other request, and unreachable nodes are caught by the regular status updates.
+        let stream_result = beacon_nodes
+            .first_success(
+                RequireSynced::No,
+                OfflineOnFailure::No,
+                |beacon_node| async move {
+                    let capabilities = beacon_nodes
+                        .capabilities(beacon_node)
+                        .await
+                        .unwrap_or_default();
+                    if !capabilities.head_events {
+                        return Err("head events are not supported".to_string());
+                    }
+                    let topics = if capabilities.duties_reorg_events {
+                        HEAD_AND_DUTIES_REORG_EVENT_TOPICS
+                    } else {
+                        HEAD_EVENT_TOPICS
+                    };
+                    beacon_node
+                        .get_events::<E>(topics)
+                        .await
+                        .map(|stream| (stream, capabilities.duties_reorg_events))
+                        .map_err(|e| e.to_string())
+                },
+            )
+            .await;
+
+        match stream_result {
+            Ok((stream, duties_reorg_events)) => {
+                debug!(
+                    log,
+                    "Subscribed to beacon node head events";
+                    "duties_reorg_events" => duties_reorg_events,
+                );
+                let mut stream = std::pin::pin!(stream);
+                while let Some(event) = stream.next().await {
+                    match event {
+                        Ok(EventKind::Head(head)) => {
+                            if cache.on_head(&head, E::slots_per_epoch()) {
+                                metrics::inc_counter_vec(
+                                    &metrics::DUTIES_CACHE_INVALIDATIONS,
+                                    &[metrics::HEAD_EVENT],
+                                );
+                            }
+                        }
+                        // The duties reorg events say precisely when the duties have changed.
+                        Ok(EventKind::ChainReorg(_)) if duties_reorg_events => (),
+                        Ok(EventKind::ChainReorg(reorg)) => {
+                            debug!(
+                                log,
+                                "Invalidating duties cache after re-org";
+                                "slot" => reorg.slot,
+                                "depth" => reorg.depth,
+                            );
+                            cache.invalidate();
+                            metrics::inc_counter_vec(
+                                &metrics::DUTIES_CACHE_INVALIDATIONS,
+                                &[metrics::CHAIN_REORG_EVENT],
+                            );
+                        }
+                        Ok(EventKind::AttesterDutiesReorg(reorg)) => {
+                            debug!(
+                                log,
+                                "Attester duties changed by re-org";
+                                "epoch" => reorg.epoch,
+                                "old_dependent_root" => ?reorg.old_dependent_root,
+                                "new_dependent_root" => ?reorg.new_dependent_root,
+                            );
+                            cache.on_attester_duties_reorg();
+                            metrics::inc_counter_vec(
+                                &metrics::DUTIES_CACHE_INVALIDATIONS,
+                                &[metrics::ATTESTER_DUTIES_REORG_EVENT],
+                            );
+                        }
+                        Ok(EventKind::ProposerDutiesReorg(reorg)) => {
+                            debug!(
+                                log,
+                                "Proposer duties changed by re-org";
+                                "epoch" => reorg.epoch,
+                                "old_dependent_root" => ?reorg.old_dependent_root,
+                                "new_dependent_root" => ?reorg.new_dependent_root,
+                            );
+                            // Blocks are only produced at the start of the slot, so there's no
+                            // need to notify the block service until then.
+                            let local_pubkeys: HashSet<_> = duties_service
+                                .validator_store
+                                .voting_pubkeys(DoppelgangerStatus::ignored);
+                            if !local_pubkeys.is_empty() {
+                                download_proposer_duties(
+                                    &duties_service,
+                                    reorg.epoch,
+                                    &local_pubkeys,
+                                )
+                                .await;
+                            }
+                        }
+                        Ok(_) => (),
+                        Err(e) => {
+                            warn!(
+                                log,
+                                "Error on beacon node event stream";
+                                "error" => ?e,
+                            );
+                            break;
+                        }
+                    }
+                }
+            }
+            Err(e) => warn!(
+                log,
+                "Unable to subscribe to head events";
+                "error" => %e,
+                "msg" => "attester and proposer duties will be polled every slot",
+            ),
+        }
+
+        cache.reset();
+        metrics::inc_counter_vec(
+            &metrics::DUTIES_CACHE_INVALIDATIONS,
+            &[metrics::EVENT_STREAM_CLOSED],
+        );
+
+        // Wait a slot before re-connecting so that a broken beacon node isn't hammered.
+        sleep(duties_service.slot_clock.slot_duration()).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::Slot;
+
+    const SLOTS_PER_EPOCH: u64 = 32;
+
+    fn head(slot: u64, current: u64, previous: u64) -> SseHead {
+        SseHead {
+            slot: Slot::new(slot),
+            block: Hash256::zero(),
+            state: Hash256::zero(),
+            current_duty_dependent_root: Hash256::from_low_u64_be(current),
+            previous_duty_dependent_root: Hash256::from_low_u64_be(previous),
+            epoch_transition: false,
+            execution_optimistic: false,
+        }
+    }
+
+    #[test]
+    fn not_fresh_without_head_event() {
+        let cache = DependentRootCache::default();
+        cache.confirm_attesters(cache.generation());
+        assert!(!cache.attesters_are_fresh(Epoch::new(0)));
+    }
+
+    #[test]
+    fn fresh_until_dependent_root_changes() {
+        let cache = DependentRootCache::default();
+        let epoch = Epoch::new(1);
+
+        assert!(cache.on_head(&head(33, 1, 0), SLOTS_PER_EPOCH));
+        assert!(!cache.attesters_are_fresh(epoch));
+
+        cache.confirm_attesters(cache.generation());
+        assert!(cache.attesters_are_fresh(epoch));
+
+        // A new head with the same dependent roots does not invalidate the cache.
+        assert!(!cache.on_head(&head(33, 1, 0), SLOTS_PER_EPOCH));
+        assert!(cache.attesters_are_fresh(epoch));
+
+        // A changed dependent root does.
+        assert!(cache.on_head(&head(34, 2, 0), SLOTS_PER_EPOCH));
+        assert!(!cache.attesters_are_fresh(epoch));
+    }
+
+    #[test]
+    fn stale_after_epoch_transition() {
+        let cache = DependentRootCache::default();
+        cache.on_head(&head(63, 1, 0), SLOTS_PER_EPOCH);
+        cache.confirm_attesters(cache.generation());
+        assert!(cache.attesters_are_fresh(Epoch::new(1)));
+        assert!(!cache.attesters_are_fresh(Epoch::new(2)));
+    }
+
+    #[test]
+    fn confirmation_ignored_after_concurrent_invalidation() {
+        let cache = DependentRootCache::default();
+        cache.on_head(&head(33, 1, 0), SLOTS_PER_EPOCH);
+
+        let generation = cache.generation();
+        cache.invalidate();
+        cache.confirm_attesters(generation);
+        assert!(!cache.attesters_are_fresh(Epoch::new(1)));
+    }
+
+    #[tokio::test]
+    async fn attester_duties_reorg_invalidates_and_notifies() {
+        let cache = DependentRootCache::default();
+        cache.on_head(&head(33, 1, 0), SLOTS_PER_EPOCH);
+        cache.confirm_attesters(cache.generation());
+
+        cache.on_attester_duties_reorg();
+        assert!(!cache.attesters_are_fresh(Epoch::new(1)));
+        // The notification is stored until it is awaited.
+        cache.attester_duties_changed().await;
+    }
+
+    #[test]
+    fn reset_clears_freshness() {
+        let cache = DependentRootCache::default();
+        cache.on_head(&head(33, 1, 0), SLOTS_PER_EPOCH);
+        cache.confirm_attesters(cache.generation());
+        cache.reset();
+        cache.confirm_attesters(cache.generation());
+        assert!(!cache.attesters_are_fresh(Epoch::new(1)));
+    }
+
+    #[test]
+    fn proposers_confirmed_independently_of_attesters() {
+        let cache = DependentRootCache::default();
+        let epoch = Epoch::new(1);
+        let pubkeys = HashSet::from([PublicKeyBytes::empty()]);
+        cache.on_head(&head(33, 1, 0), SLOTS_PER_EPOCH);
+
+        cache.confirm_attesters(cache.generation());
+        assert!(!cache.proposers_are_fresh(epoch, &pubkeys));
+
+        cache.confirm_proposers(cache.generation(), &pubkeys);
+        assert!(cache.proposers_are_fresh(epoch, &pubkeys));
+        assert!(!cache.proposers_are_fresh(epoch + 1, &pubkeys));
+
+        // A changed dependent root invalidates the proposer duties too.
+        cache.on_head(&head(34, 2, 0), SLOTS_PER_EPOCH);
+        assert!(!cache.proposers_are_fresh(epoch, &pubkeys));
+    }
+
+    #[test]
+    fn proposers_stale_for_new_validators() {
+        let cache = DependentRootCache::default();
+        let epoch = Epoch::new(1);
+        let mut pubkeys = HashSet::from([PublicKeyBytes::empty()]);
+        cache.on_head(&head(33, 1, 0), SLOTS_PER_EPOCH);
+        cache.confirm_proposers(cache.generation(), &pubkeys);
+
+        // Removing a validator doesn't require the duties to be re-downloaded, adding one does.
+        assert!(cache.proposers_are_fresh(epoch, &HashSet::new()));
+        pubkeys.insert(PublicKeyBytes::deserialize(&[1; 48]).unwrap());
+        assert!(!cache.proposers_are_fresh(epoch, &pubkeys));
+    }
+}
@@ -0,0 +1,72 @@
+//! Correlation IDs for the logs of validator duties.
+//!
+//! When enabled, each log emitted while performing a duty carries a `duty_id` field which stays
+//! the same from fetching the data to sign through to publishing it. IDs are derived from the duty
+//! rather than generated, so separate tasks working on the same duty (e.g. attesting and then
+//! aggregating) share an ID without coordination, and the ID can be reconstructed by other tools.
+use slog::{o, Logger};
+use types::Slot;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duty {
+    Block,
+    Attestation,
+    SyncCommitteeMessage,
+    SyncContribution,
+}
+
+impl Duty {
+    fn as_str(self) -> &'static str {
+        match self {
+            Duty::Block => "block",
+            Duty::Attestation => "attestation",
+            Duty::SyncCommitteeMessage => "sync_committee_message",
+            Duty::SyncContribution => "sync_contribution",
+        }
+    }
+
+    /// Returns the correlation ID of this duty at `slot`.
+    ///
+    /// The `index` distinguishes duties of the same kind at the same slot, e.g. the committee
+    /// index of an attestation or the subnet of a sync contribution.
+    pub fn id(self, slot: Slot, index: Option<u64>) -> String {
+        match index {
+            Some(index) => format!("{}-{}-{}", self.as_str(), slot, index),
+            None => format!("{}-{}", self.as_str(), slot),
+        }
+    }
+}
+
+/// Returns a child of `log` which adds the `duty_id` of the given duty to each message, or `log`
+/// unchanged if `enabled` is false.
+pub fn duty_logger(
+    log: &Logger,
+    enabled: bool,
+    duty: Duty,
+    slot: Slot,
+    index: Option<u64>,
+) -> Logger {
+    if enabled {
+        log.new(o!("duty_id" => duty.id(slot, index)))
+    } else {
+        log.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duty_ids() {
+        assert_eq!(Duty::Block.id(Slot::new(100), None), "block-100");
+        assert_eq!(
+            Duty::Attestation.id(Slot::new(100), Some(3)),
+            "attestation-100-3"
+        );
+        assert_ne!(
+            Duty::SyncContribution.id(Slot::new(100), Some(0)),
+            Duty::SyncContribution.id(Slot::new(100), Some(1))
+        );
+    }
+}
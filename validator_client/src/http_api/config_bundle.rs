@@ -0,0 +1,528 @@
+//! Export and import of the configuration of all validators as a single encrypted bundle, to ease
+//! moving a validator client between hosts.
+use super::keystores::import_single_keystore;
+use crate::{InitializedValidators, ValidatorStore};
+use account_utils::{
+    read_password_string,
+    validator_definitions::{SigningDefinition, ValidatorDefinition},
+    ZeroizeString,
+};
+use eth2::lighthouse_vc::types::{
+    ConfigBundle, ConfigBundleContents, ConfigBundleSigner, ConfigBundleValidator,
+    ConfigBundleValidatorStatus, ExportConfigBundleRequest, GenericResponse,
+    ImportConfigBundleRequest, ImportConfigBundleResponse, ImportKeystoreStatus, Status,
+    CONFIG_BUNDLE_VERSION,
+};
+use eth2_keystore::json_keystore::{
+    Aes128Ctr, ChecksumModule, Cipher, CipherModule, Crypto, EmptyMap, EmptyString, KdfModule,
+    Sha256Checksum,
+};
+use eth2_keystore::{decrypt, default_kdf, encrypt, Keystore, PlainText, IV_SIZE, SALT_SIZE};
+use rand::Rng;
+use slog::{info, warn, Logger};
+use slot_clock::SlotClock;
+use std::path::PathBuf;
+use std::sync::Arc;
+use task_executor::TaskExecutor;
+use tokio::runtime::Handle;
+use types::{EthSpec, Hash256, PublicKey};
+use warp::Rejection;
+use warp_utils::reject::{custom_bad_request, custom_server_error};
+
+pub fn export<T: SlotClock + 'static, E: EthSpec>(
+    request: ExportConfigBundleRequest,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    task_executor: TaskExecutor,
+    log: Logger,
+) -> Result<GenericResponse<ConfigBundle>, Rejection> {
+    if request.password.as_str().is_empty() {
+        return Err(custom_bad_request("password must not be empty".into()));
+    }
+
+    let initialized_validators_rwlock = validator_store.initialized_validators();
+    let mut initialized_validators = initialized_validators_rwlock.write();
+
+    let validators = initialized_validators
+        .validator_definitions()
+        .iter()
+        .map(bundle_validator)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(custom_bad_request)?;
+
+    let handle = if request.include_slashing_protection {
+        Some(
+            task_executor
+                .handle()
+                .ok_or_else(|| custom_server_error("validator client shutdown".into()))?,
+        )
+    } else {
+        None
+    };
+
+    let mut disabled = vec![];
+    let result = build_bundle(
+        &request,
+        &validator_store,
+        &mut initialized_validators,
+        validators,
+        handle.as_ref(),
+        &mut disabled,
+        &log,
+    );
+
+    // Re-enable the validators if the bundle couldn't be built, so that a failed export doesn't
+    // leave them unable to validate.
+    if let (Err(_), Some(handle)) = (&result, &handle) {
+        for pubkey in &disabled {
+            if let Err(e) = handle.block_on(initialized_validators.set_validator_definition_fields(
+                pubkey,
+                Some(true),
+                None,
+                None,
+                None,
+                None,
+                None,
+            )) {
+                warn!(
+                    log,
+                    "Unable to re-enable validator after failed export";
+                    "pubkey" => ?pubkey,
+                    "error" => ?e,
+                );
+            }
+        }
+    }
+
+    result.map(GenericResponse::from)
+}
+
+/// Builds and encrypts the bundle of `validators`.
+///
+/// If `handle` is provided, the validators are disabled first so that the slashing protection data
+/// in the bundle is final. The bundle records whether each validator was enabled beforehand, and
+/// each validator which this disables is added to `disabled`.
+fn build_bundle<T: SlotClock + 'static, E: EthSpec>(
+    request: &ExportConfigBundleRequest,
+    validator_store: &ValidatorStore<T, E>,
+    initialized_validators: &mut InitializedValidators,
+    validators: Vec<ConfigBundleValidator>,
+    handle: Option<&Handle>,
+    disabled: &mut Vec<PublicKey>,
+    log: &Logger,
+) -> Result<ConfigBundle, Rejection> {
+    let slashing_protection = if let Some(handle) = handle {
+        for validator in validators.iter().filter(|validator| validator.enabled) {
+            handle
+                .block_on(initialized_validators.set_validator_definition_fields(
+                    &validator.voting_public_key,
+                    Some(false),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                ))
+                .map_err(|e| {
+                    custom_server_error(format!("unable to disable validator: {:?}", e))
+                })?;
+            disabled.push(validator.voting_public_key.clone());
+        }
+
+        let pubkeys = validators
+            .iter()
+            .map(|validator| validator.voting_public_key.compress())
+            .collect::<Vec<_>>();
+        let slashing_protection = validator_store
+            .export_slashing_protection_for_keys(&pubkeys)
+            .map_err(|e| {
+                custom_server_error(format!("error exporting slashing protection: {:?}", e))
+            })?;
+        Some(slashing_protection)
+    } else {
+        None
+    };
+
+    info!(
+        log,
+        "Exporting config bundle via HTTP API";
+        "count" => validators.len(),
+        "slashing_protection" => slashing_protection.is_some(),
+    );
+
+    let contents = ConfigBundleContents {
+        validators,
+        slashing_protection,
+    };
+    encrypt_bundle(
+        &contents,
+        &request.password,
+        validator_store.genesis_validators_root(),
+    )
+    .map_err(custom_server_error)
+}
+
+pub fn import<T: SlotClock + 'static, E: EthSpec>(
+    request: ImportConfigBundleRequest,
+    validator_dir: PathBuf,
+    secrets_dir: Option<PathBuf>,
+    validator_store: Arc<ValidatorStore<T, E>>,
+    task_executor: TaskExecutor,
+    log: Logger,
+) -> Result<ImportConfigBundleResponse, Rejection> {
+    let ImportConfigBundleRequest {
+        bundle,
+        password,
+        dry_run,
+    } = request;
+
+    // Check bundle validity. These are the only cases in which we should return a 4xx code.
+    if bundle.version != CONFIG_BUNDLE_VERSION {
+        return Err(custom_bad_request(format!(
+            "unsupported bundle version {}, expected {}",
+            bundle.version, CONFIG_BUNDLE_VERSION
+        )));
+    }
+    let genesis_validators_root = validator_store.genesis_validators_root();
+    if bundle.genesis_validators_root != genesis_validators_root {
+        return Err(custom_bad_request(format!(
+            "bundle is for a different network, genesis validators root {:?} != {:?}",
+            bundle.genesis_validators_root, genesis_validators_root
+        )));
+    }
+    let ConfigBundleContents {
+        validators,
+        slashing_protection,
+    } = decrypt_bundle(&bundle, &password).map_err(custom_bad_request)?;
+    if let Some(slashing_protection) = &slashing_protection {
+        if slashing_protection.metadata.genesis_validators_root != genesis_validators_root {
+            return Err(custom_bad_request(format!(
+                "slashing protection is for a different network, genesis validators root {:?} \
+                 != {:?}",
+                slashing_protection.metadata.genesis_validators_root, genesis_validators_root
+            )));
+        }
+    }
+
+    info!(
+        log,
+        "Importing config bundle via HTTP API";
+        "count" => validators.len(),
+        "slashing_protection" => slashing_protection.is_some(),
+        "dry_run" => dry_run,
+    );
+
+    if dry_run {
+        let data = validators
+            .iter()
+            .map(|validator| ConfigBundleValidatorStatus {
+                voting_pubkey: validator.voting_public_key.compress(),
+                status: match check_validator(validator, &validator_store) {
+                    Ok(status) => Status::ok(status),
+                    Err(e) => Status::error(ImportKeystoreStatus::Error, e),
+                },
+            })
+            .collect();
+        return Ok(ImportConfigBundleResponse { dry_run, data });
+    }
+
+    // Import slashing protection data before the validators, so that they don't start signing
+    // without it. Do not return early on failure, propagate the failure to each validator.
+    let slashing_protection_status = if let Some(slashing_protection) = slashing_protection {
+        validator_store.import_slashing_protection(slashing_protection)
+    } else {
+        warn!(
+            log,
+            "No slashing protection data provided with config bundle"
+        );
+        Ok(())
+    };
+
+    let data = validators
+        .into_iter()
+        .map(|validator| {
+            let voting_pubkey = validator.voting_public_key.compress();
+
+            let status = if let Err(e) = &slashing_protection_status {
+                Status::error(
+                    ImportKeystoreStatus::Error,
+                    format!("slashing protection import failed: {:?}", e),
+                )
+            } else if let Some(handle) = task_executor.handle() {
+                match import_single_validator(
+                    validator,
+                    validator_dir.clone(),
+                    secrets_dir.clone(),
+                    &validator_store,
+                    handle,
+                ) {
+                    Ok(status) => Status::ok(status),
+                    Err(e) => {
+                        warn!(
+                            log,
+                            "Error importing validator from config bundle, skipped";
+                            "pubkey" => ?voting_pubkey,
+                            "error" => ?e,
+                        );
+                        Status::error(ImportKeystoreStatus::Error, e)
+                    }
+                }
+            } else {
+                Status::error(
+                    ImportKeystoreStatus::Error,
+                    "validator client shutdown".into(),
+                )
+            };
+
+            ConfigBundleValidatorStatus {
+                voting_pubkey,
+                status,
+            }
+        })
+        .collect();
+
+    Ok(ImportConfigBundleResponse { dry_run, data })
+}
+
+/// Returns the bundle entry for the validator defined by `def`.
+fn bundle_validator(def: &ValidatorDefinition) -> Result<ConfigBundleValidator, String> {
+    let signer = match &def.signing_definition {
+        SigningDefinition::LocalKeystore {
+            voting_keystore_path,
+            voting_keystore_password,
+            voting_keystore_password_path,
+            ..
+        } => {
+            let voting_keystore_password =
+                match (voting_keystore_password, voting_keystore_password_path) {
+                    (Some(password), _) => password.clone(),
+                    (_, Some(path)) => read_password_string(path)?,
+                    (None, None) => {
+                        return Err(format!(
+                            "no keystore password is stored for {:?}",
+                            def.voting_public_key
+                        ))
+                    }
+                };
+            let voting_keystore = Keystore::from_json_file(voting_keystore_path)
+                .map_err(|e| format!("unable to read {:?}: {:?}", voting_keystore_path, e))?;
+            ConfigBundleSigner::LocalKeystore {
+                voting_keystore,
+                voting_keystore_password,
+            }
+        }
+        SigningDefinition::Web3Signer(web3signer) => {
+            ConfigBundleSigner::Web3Signer(web3signer.clone())
+        }
+    };
+
+    Ok(ConfigBundleValidator {
+        enabled: def.enabled,
+        voting_public_key: def.voting_public_key.clone(),
+        signer,
+        graffiti: def.graffiti.clone(),
+        suggested_fee_recipient: def.suggested_fee_recipient,
+        gas_limit: def.gas_limit,
+        builder_proposals: def.builder_proposals,
+        builder_boost_factor: def.builder_boost_factor,
+        prefer_builder_proposals: def.prefer_builder_proposals,
+    })
+}
+
+/// Checks whether `validator` could be imported, without making any changes.
+fn check_validator<T: SlotClock + 'static, E: EthSpec>(
+    validator: &ConfigBundleValidator,
+    validator_store: &ValidatorStore<T, E>,
+) -> Result<ImportKeystoreStatus, String> {
+    if let ConfigBundleSigner::LocalKeystore {
+        voting_keystore,
+        voting_keystore_password,
+    } = &validator.signer
+    {
+        check_keystore_pubkey(&validator.voting_public_key, voting_keystore)?;
+        voting_keystore
+            .decrypt_keypair(voting_keystore_password.as_ref())
+            .map_err(|e| format!("incorrect password: {:?}", e))?;
+    }
+
+    match validator_store
+        .initialized_validators()
+        .read()
+        .validator_definitions()
+        .iter()
+        .find(|def| def.voting_public_key == validator.voting_public_key)
+    {
+        Some(def) => match &validator.signer {
+            ConfigBundleSigner::LocalKeystore { .. }
+                if !def.signing_definition.is_local_keystore() =>
+            {
+                Err("cannot import duplicate of existing remote signer validator".into())
+            }
+            ConfigBundleSigner::Web3Signer(_) if def.signing_definition.is_local_keystore() => {
+                Err("Pubkey already present in local keystore.".into())
+            }
+            _ if def.enabled => Ok(ImportKeystoreStatus::Duplicate),
+            _ => Ok(ImportKeystoreStatus::Imported),
+        },
+        None => Ok(ImportKeystoreStatus::Imported),
+    }
+}
+
+fn check_keystore_pubkey(
+    voting_public_key: &PublicKey,
+    voting_keystore: &Keystore,
+) -> Result<(), String> {
+    if voting_keystore.public_key().as_ref() != Some(voting_public_key) {
+        return Err(format!(
+            "keystore pubkey {} does not match the validator",
+            voting_keystore.pubkey()
+        ));
+    }
+    Ok(())
+}
+
+fn import_single_validator<T: SlotClock + 'static, E: EthSpec>(
+    validator: ConfigBundleValidator,
+    validator_dir: PathBuf,
+    secrets_dir: Option<PathBuf>,
+    validator_store: &ValidatorStore<T, E>,
+    handle: Handle,
+) -> Result<ImportKeystoreStatus, String> {
+    let ConfigBundleValidator {
+        enabled,
+        voting_public_key,
+        signer,
+        graffiti,
+        suggested_fee_recipient,
+        gas_limit,
+        builder_proposals,
+        builder_boost_factor,
+        prefer_builder_proposals,
+    } = validator;
+
+    match signer {
+        ConfigBundleSigner::LocalKeystore {
+            voting_keystore,
+            voting_keystore_password,
+        } => {
+            check_keystore_pubkey(&voting_public_key, &voting_keystore)?;
+
+            let status = import_single_keystore(
+                voting_keystore,
+                voting_keystore_password,
+                validator_dir,
+                secrets_dir,
+                validator_store,
+                handle.clone(),
+            )?;
+            if status != ImportKeystoreStatus::Imported {
+                return Ok(status);
+            }
+
+            // Apply the per-validator settings, which the keystore import leaves unset.
+            let initialized_validators_rwlock = validator_store.initialized_validators();
+            let mut initialized_validators = initialized_validators_rwlock.write();
+            if let Some(fee_recipient) = suggested_fee_recipient {
+                initialized_validators
+                    .set_validator_fee_recipient(&voting_public_key, fee_recipient)
+                    .map_err(|e| format!("failed to set fee recipient: {:?}", e))?;
+            }
+            handle
+                .block_on(initialized_validators.set_validator_definition_fields(
+                    &voting_public_key,
+                    Some(enabled),
+                    gas_limit,
+                    builder_proposals,
+                    builder_boost_factor,
+                    prefer_builder_proposals,
+                    graffiti,
+                ))
+                .map_err(|e| format!("failed to apply validator settings: {:?}", e))?;
+
+            Ok(status)
+        }
+        ConfigBundleSigner::Web3Signer(web3signer) => {
+            if let Some(def) = validator_store
+                .initialized_validators()
+                .read()
+                .validator_definitions()
+                .iter()
+                .find(|def| def.voting_public_key == voting_public_key)
+            {
+                if def.signing_definition.is_local_keystore() {
+                    return Err("Pubkey already present in local keystore.".into());
+                } else if def.enabled {
+                    return Ok(ImportKeystoreStatus::Duplicate);
+                }
+            }
+
+            let web3signer_validator = ValidatorDefinition {
+                enabled,
+                voting_public_key,
+                graffiti,
+                suggested_fee_recipient,
+                gas_limit,
+                builder_proposals,
+                builder_boost_factor,
+                prefer_builder_proposals,
+                description: String::from("Added by config bundle import"),
+                signing_definition: SigningDefinition::Web3Signer(web3signer),
+            };
+            handle
+                .block_on(validator_store.add_validator(web3signer_validator))
+                .map_err(|e| format!("failed to initialize validator: {:?}", e))?;
+
+            Ok(ImportKeystoreStatus::Imported)
+        }
+    }
+}
+
+/// Encrypts `contents` with `password`, in the same manner as an EIP-2335 keystore.
+fn encrypt_bundle(
+    contents: &ConfigBundleContents,
+    password: &ZeroizeString,
+    genesis_validators_root: Hash256,
+) -> Result<ConfigBundle, String> {
+    let plain_text = PlainText::from(
+        serde_json::to_vec(contents).map_err(|e| format!("unable to serialize bundle: {:?}", e))?,
+    );
+
+    let salt = rand::thread_rng().gen::<[u8; SALT_SIZE]>();
+    let iv = rand::thread_rng().gen::<[u8; IV_SIZE]>().to_vec().into();
+    let kdf = default_kdf(salt.to_vec());
+    let cipher = Cipher::Aes128Ctr(Aes128Ctr { iv });
+
+    let (cipher_text, checksum) = encrypt(plain_text.as_bytes(), password.as_ref(), &kdf, &cipher)
+        .map_err(|e| format!("unable to encrypt bundle: {:?}", e))?;
+
+    Ok(ConfigBundle {
+        version: CONFIG_BUNDLE_VERSION,
+        genesis_validators_root,
+        crypto: Crypto {
+            kdf: KdfModule {
+                function: kdf.function(),
+                params: kdf,
+                message: EmptyString,
+            },
+            checksum: ChecksumModule {
+                function: Sha256Checksum::function(),
+                params: EmptyMap,
+                message: checksum.to_vec().into(),
+            },
+            cipher: CipherModule {
+                function: cipher.function(),
+                params: cipher,
+                message: cipher_text.into(),
+            },
+        },
+    })
+}
+
+fn decrypt_bundle(
+    bundle: &ConfigBundle,
+    password: &ZeroizeString,
+) -> Result<ConfigBundleContents, String> {
+    let plain_text = decrypt(password.as_ref(), &bundle.crypto)
+        .map_err(|e| format!("unable to decrypt bundle: {:?}", e))?;
+    serde_json::from_slice(plain_text.as_bytes())
+        .map_err(|e| format!("unable to parse bundle: {:?}", e))
+}
@@ -0,0 +1,120 @@
+//! Liveness and readiness probes, e.g. for container orchestration.
+//!
+//! These endpoints do not require the API token, since probes are not usually able to send one.
+//! They reveal only whether each check passes and the counts of keystores and beacon nodes.
+use crate::beacon_node_fallback::BeaconNodeFallback;
+use crate::ValidatorStore;
+use eth2::lighthouse_vc::types::{
+    BeaconNodeReadiness, KeystoreReadiness, ReadinessData, SlashingProtectionReadiness,
+};
+use serde::{Deserialize, Serialize};
+use slot_clock::SlotClock;
+use std::sync::Arc;
+use strum::{EnumString, EnumVariantNames};
+use types::EthSpec;
+
+/// A condition which must hold for `GET lighthouse/health/ready` to report the validator client as
+/// ready.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize, EnumString, EnumVariantNames)]
+#[strum(serialize_all = "kebab-case")]
+pub enum ReadinessCheck {
+    Keystores,
+    BeaconNodes,
+    SlashingProtection,
+}
+
+impl ReadinessCheck {
+    pub fn all() -> Vec<ReadinessCheck> {
+        use ReadinessCheck::*;
+        vec![Keystores, BeaconNodes, SlashingProtection]
+    }
+}
+
+/// Observe the readiness of the validator client against the `required` checks.
+///
+/// All checks are observed regardless of whether they are required, so that operators can see
+/// which checks would fail before requiring them.
+pub async fn observe_readiness<T: SlotClock + 'static, E: EthSpec>(
+    validator_store: Option<&ValidatorStore<T, E>>,
+    beacon_nodes: Option<&BeaconNodeFallback<T, E>>,
+    required: &[ReadinessCheck],
+    min_synced_beacon_nodes: usize,
+) -> ReadinessData {
+    let (enabled, loaded) = validator_store.map_or((0, 0), |validator_store| {
+        let validators = validator_store.initialized_validators();
+        let validators = validators.read();
+        let enabled = validators
+            .validator_definitions()
+            .iter()
+            .filter(|def| def.enabled)
+            .count();
+        (enabled, validators.num_enabled())
+    });
+    // Without a validator store there are no keystores to load, and no database to read.
+    let keystores = KeystoreReadiness {
+        required: required.contains(&ReadinessCheck::Keystores),
+        ready: validator_store.is_some() && loaded == enabled,
+        enabled,
+        loaded,
+    };
+
+    let (total, available, synced) = match beacon_nodes {
+        Some(beacon_nodes) => (
+            beacon_nodes.num_total(),
+            beacon_nodes.num_available().await,
+            beacon_nodes.num_synced().await,
+        ),
+        None => (0, 0, 0),
+    };
+    let beacon_nodes = BeaconNodeReadiness {
+        required: required.contains(&ReadinessCheck::BeaconNodes),
+        ready: synced >= min_synced_beacon_nodes,
+        total,
+        available,
+        synced,
+        min_synced: min_synced_beacon_nodes,
+    };
+
+    let slashing_protection_error = match validator_store {
+        Some(validator_store) => validator_store
+            .check_slashing_protection_db()
+            .err()
+            .map(|e| format!("{:?}", e)),
+        None => Some("validator store is not initialized".to_string()),
+    };
+    let slashing_protection = SlashingProtectionReadiness {
+        required: required.contains(&ReadinessCheck::SlashingProtection),
+        ready: slashing_protection_error.is_none(),
+        error: slashing_protection_error,
+    };
+
+    ReadinessData {
+        ready: [
+            (keystores.required, keystores.ready),
+            (beacon_nodes.required, beacon_nodes.ready),
+            (slashing_protection.required, slashing_protection.ready),
+        ]
+        .iter()
+        .all(|(required, ready)| !required || *ready),
+        keystores,
+        beacon_nodes,
+        slashing_protection,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+    use strum::VariantNames;
+
+    #[test]
+    fn readiness_check_all() {
+        let all = ReadinessCheck::all();
+        assert_eq!(all.len(), ReadinessCheck::VARIANTS.len());
+        assert!(ReadinessCheck::VARIANTS
+            .iter()
+            .map(|check| ReadinessCheck::from_str(check).unwrap())
+            .eq(all.into_iter()));
+    }
+}
@@ -1,7 +1,9 @@
 mod api_secret;
+mod config_bundle;
 mod create_signed_voluntary_exit;
 mod create_validator;
 mod graffiti;
+mod health;
 mod keystores;
 mod remotekeys;
 mod tests;
@@ -10,6 +12,7 @@ pub mod test_utils;
 
 use crate::http_api::graffiti::{delete_graffiti, get_graffiti, set_graffiti};
 
+use crate::beacon_node_fallback::BeaconNodeFallback;
 use crate::http_api::create_signed_voluntary_exit::create_signed_voluntary_exit;
 use crate::{determine_graffiti, GraffitiFile, ValidatorStore};
 use account_utils::{
@@ -27,6 +30,7 @@ use eth2::lighthouse_vc::{
         PublicKeyBytes, SetGraffitiRequest,
     },
 };
+pub use health::ReadinessCheck;
 use lighthouse_version::version_with_platform;
 use logging::SSELoggingComponents;
 use parking_lot::RwLock;
@@ -80,6 +84,7 @@ pub struct Context<T: SlotClock, E: EthSpec> {
     pub task_executor: TaskExecutor,
     pub api_secret: ApiSecret,
     pub validator_store: Option<Arc<ValidatorStore<T, E>>>,
+    pub beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
     pub validator_dir: Option<PathBuf>,
     pub secrets_dir: Option<PathBuf>,
     pub graffiti_file: Option<GraffitiFile>,
@@ -101,6 +106,10 @@ pub struct Config {
     pub allow_origin: Option<String>,
     pub allow_keystore_export: bool,
     pub store_passwords_in_secrets_dir: bool,
+    /// The checks which must pass for `GET lighthouse/health/ready` to report ready.
+    pub readiness_checks: Vec<ReadinessCheck>,
+    /// The number of synced beacon nodes required by `ReadinessCheck::BeaconNodes`.
+    pub ready_min_synced_beacon_nodes: usize,
 }
 
 impl Default for Config {
@@ -112,6 +121,8 @@ impl Default for Config {
             allow_origin: None,
             allow_keystore_export: false,
             store_passwords_in_secrets_dir: false,
+            readiness_checks: ReadinessCheck::all(),
+            ready_min_synced_beacon_nodes: 1,
         }
     }
 }
@@ -138,6 +149,8 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
     let config = &ctx.config;
     let allow_keystore_export = config.allow_keystore_export;
     let store_passwords_in_secrets_dir = config.store_passwords_in_secrets_dir;
+    let readiness_checks = config.readiness_checks.clone();
+    let ready_min_synced_beacon_nodes = config.ready_min_synced_beacon_nodes;
     let log = ctx.log.clone();
 
     // Configure CORS.
@@ -190,6 +203,13 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // The health probes report an uninitialized validator store rather than rejecting.
+    let inner_validator_store = ctx.validator_store.clone();
+    let optional_validator_store_filter = warp::any().map(move || inner_validator_store.clone());
+
+    let inner_beacon_nodes = ctx.beacon_nodes.clone();
+    let beacon_nodes_filter = warp::any().map(move || inner_beacon_nodes.clone());
+
     let inner_task_executor = ctx.task_executor.clone();
     let task_executor_filter = warp::any().map(move || inner_task_executor.clone());
 
@@ -292,6 +312,57 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             })
         });
 
+    // GET lighthouse/health/live
+    let get_lighthouse_health_live = warp::path("lighthouse")
+        .and(warp::path("health"))
+        .and(warp::path("live"))
+        .and(warp::path::end())
+        .and_then(|| {
+            warp_utils::task::blocking_json_task(|| {
+                Ok(api_types::GenericResponse::from(api_types::LivenessData {
+                    live: true,
+                }))
+            })
+        });
+
+    // GET lighthouse/health/ready
+    let get_lighthouse_health_ready = warp::path("lighthouse")
+        .and(warp::path("health"))
+        .and(warp::path("ready"))
+        .and(warp::path::end())
+        .and(optional_validator_store_filter)
+        .and(beacon_nodes_filter)
+        .and(task_executor_filter.clone())
+        .and_then(
+            move |validator_store: Option<Arc<ValidatorStore<T, E>>>,
+                  beacon_nodes: Option<Arc<BeaconNodeFallback<T, E>>>,
+                  task_executor: TaskExecutor| {
+                let readiness_checks = readiness_checks.clone();
+                warp_utils::task::blocking_task(move || {
+                    let Some(handle) = task_executor.handle() else {
+                        return Err(warp_utils::reject::custom_server_error(
+                            "Lighthouse shutting down".into(),
+                        ));
+                    };
+                    let readiness = handle.block_on(health::observe_readiness(
+                        validator_store.as_deref(),
+                        beacon_nodes.as_deref(),
+                        &readiness_checks,
+                        ready_min_synced_beacon_nodes,
+                    ));
+                    let status = if readiness.ready {
+                        StatusCode::OK
+                    } else {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    };
+                    Ok(warp::reply::with_status(
+                        warp::reply::json(&api_types::GenericResponse::from(readiness)),
+                        status,
+                    ))
+                })
+            },
+        );
+
     // GET lighthouse/spec
     let get_lighthouse_spec = warp::path("lighthouse")
         .and(warp::path("spec"))
@@ -817,6 +888,64 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
             },
         );
 
+    // POST /lighthouse/config_bundle/export
+    let post_config_bundle_export = warp::path("lighthouse")
+        .and(warp::path("config_bundle"))
+        .and(warp::path("export"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_store_filter.clone())
+        .and(task_executor_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            move |request, signer, validator_store, task_executor, log| {
+                blocking_signed_json_task(signer, move || {
+                    if allow_keystore_export {
+                        config_bundle::export(request, validator_store, task_executor, log)
+                    } else {
+                        Err(warp_utils::reject::custom_bad_request(
+                            "keystore export is disabled".to_string(),
+                        ))
+                    }
+                })
+            },
+        );
+
+    // POST /lighthouse/config_bundle/import
+    let post_config_bundle_import = warp::path("lighthouse")
+        .and(warp::path("config_bundle"))
+        .and(warp::path("import"))
+        .and(warp::path::end())
+        .and(warp::body::json())
+        .and(signer.clone())
+        .and(validator_dir_filter.clone())
+        .and(secrets_dir_filter.clone())
+        .and(validator_store_filter.clone())
+        .and(task_executor_filter.clone())
+        .and(log_filter.clone())
+        .and_then(
+            move |request,
+                  signer,
+                  validator_dir,
+                  secrets_dir,
+                  validator_store,
+                  task_executor,
+                  log| {
+                let secrets_dir = store_passwords_in_secrets_dir.then_some(secrets_dir);
+                blocking_signed_json_task(signer, move || {
+                    config_bundle::import(
+                        request,
+                        validator_dir,
+                        secrets_dir,
+                        validator_store,
+                        task_executor,
+                        log,
+                    )
+                })
+            },
+        );
+
     // Standard key-manager endpoints.
     let eth_v1 = warp::path("eth").and(warp::path("v1"));
     let std_keystores = eth_v1.and(warp::path("keystores")).and(warp::path::end());
@@ -1331,6 +1460,8 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .or(post_std_keystores)
                         .or(post_std_remotekeys)
                         .or(post_graffiti)
+                        .or(post_config_bundle_export)
+                        .or(post_config_bundle_import)
                         .recover(warp_utils::reject::handle_rejection),
                 ))
                 .or(warp::patch()
@@ -1345,8 +1476,14 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                         .recover(warp_utils::reject::handle_rejection),
                 )),
         )
-        // The auth route and logs  are the only routes that are allowed to be accessed without the API token.
-        .or(warp::get().and(get_auth.or(get_log_events.boxed())))
+        // The auth, logs and health probe routes are the only routes that are allowed to be
+        // accessed without the API token.
+        .or(warp::get().and(
+            get_auth
+                .or(get_log_events.boxed())
+                .or(get_lighthouse_health_live)
+                .or(get_lighthouse_health_ready),
+        ))
         // Maps errors into HTTP responses.
         .recover(warp_utils::reject::handle_rejection)
         // Add a `Server` header.
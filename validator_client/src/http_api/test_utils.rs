@@ -1,7 +1,7 @@
 use crate::doppelganger_service::DoppelgangerService;
 use crate::key_cache::{KeyCache, CACHE_FILENAME};
 use crate::{
-    http_api::{ApiSecret, Config as HttpConfig, Context},
+    http_api::{ApiSecret, Config as HttpConfig, Context, ReadinessCheck},
     initialized_validators::{InitializedValidators, OnDecryptFailure},
     Config, ValidatorDefinitions, ValidatorStore,
 };
@@ -112,6 +112,7 @@ impl ApiTester {
             Hash256::repeat_byte(42),
             spec,
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
+            None,
             slot_clock.clone(),
             &config,
             test_runtime.task_executor.clone(),
@@ -130,6 +131,7 @@ impl ApiTester {
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: None,
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -179,6 +181,8 @@ impl ApiTester {
             allow_origin: None,
             allow_keystore_export: true,
             store_passwords_in_secrets_dir: false,
+            readiness_checks: ReadinessCheck::all(),
+            ready_min_synced_beacon_nodes: 1,
         }
     }
 
@@ -5,7 +5,7 @@ mod keystores;
 
 use crate::doppelganger_service::DoppelgangerService;
 use crate::{
-    http_api::{ApiSecret, Config as HttpConfig, Context},
+    http_api::{ApiSecret, Config as HttpConfig, Context, ReadinessCheck},
     initialized_validators::InitializedValidators,
     Config, ValidatorDefinitions, ValidatorStore,
 };
@@ -100,6 +100,7 @@ impl ApiTester {
             Hash256::repeat_byte(42),
             spec.clone(),
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
+            None,
             slot_clock.clone(),
             &config,
             test_runtime.task_executor.clone(),
@@ -118,6 +119,7 @@ impl ApiTester {
             validator_dir: Some(validator_dir.path().into()),
             secrets_dir: Some(secrets_dir.path().into()),
             validator_store: Some(validator_store.clone()),
+            beacon_nodes: None,
             graffiti_file: None,
             graffiti_flag: Some(Graffiti::default()),
             spec: E::default_spec(),
@@ -128,6 +130,8 @@ impl ApiTester {
                 allow_origin: None,
                 allow_keystore_export: true,
                 store_passwords_in_secrets_dir: false,
+                readiness_checks: ReadinessCheck::all(),
+                ready_min_synced_beacon_nodes: 1,
             },
             sse_logging_components: None,
             log,
@@ -246,6 +250,63 @@ impl ApiTester {
 
         self
     }
+
+    /// The health probes must be usable without the API token.
+    fn probe_client(&self) -> ValidatorClientHttpClient {
+        let mut client = self.client.clone();
+        client.send_authorization_header(false);
+        client
+    }
+
+    pub async fn test_get_lighthouse_health_live(self) -> Self {
+        let result = self.probe_client().get_lighthouse_health_live().await;
+        assert_eq!(result.unwrap().data, LivenessData { live: true });
+
+        self
+    }
+
+    pub async fn test_get_lighthouse_health_ready(self) -> Self {
+        let result = self
+            .probe_client()
+            .get_lighthouse_health_ready()
+            .await
+            .unwrap()
+            .data;
+
+        // There are no beacon nodes, so the beacon node check fails and the validator client is
+        // not ready.
+        assert!(!result.ready);
+        assert_eq!(
+            result.keystores,
+            KeystoreReadiness {
+                required: true,
+                ready: true,
+                enabled: self.vals_enabled(),
+                loaded: self.vals_enabled(),
+            }
+        );
+        assert_eq!(
+            result.beacon_nodes,
+            BeaconNodeReadiness {
+                required: true,
+                ready: false,
+                total: 0,
+                available: 0,
+                synced: 0,
+                min_synced: 1,
+            }
+        );
+        assert_eq!(
+            result.slashing_protection,
+            SlashingProtectionReadiness {
+                required: true,
+                ready: true,
+                error: None,
+            }
+        );
+
+        self
+    }
     pub fn vals_total(&self) -> usize {
         self.initialized_validators.read().num_total()
     }
@@ -961,6 +1022,38 @@ async fn routes_with_invalid_auth() {
                 .set_graffiti(&PublicKeyBytes::empty(), GraffitiString::default())
                 .await
         })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            client
+                .post_lighthouse_config_bundle_export(&ExportConfigBundleRequest {
+                    password: random_password_string(),
+                    include_slashing_protection: <_>::default(),
+                })
+                .await
+        })
+        .await
+        .test_with_invalid_auth(|client| async move {
+            let password = random_password_string();
+            let keypair = Keypair::random();
+            let keystore = KeystoreBuilder::new(&keypair, password.as_ref(), String::new())
+                .unwrap()
+                .build()
+                .unwrap();
+            client
+                .post_lighthouse_config_bundle_import(&ImportConfigBundleRequest {
+                    bundle: ConfigBundle {
+                        version: CONFIG_BUNDLE_VERSION,
+                        genesis_validators_root: Hash256::zero(),
+                        crypto: serde_json::from_value(
+                            serde_json::to_value(&keystore).unwrap()["crypto"].clone(),
+                        )
+                        .unwrap(),
+                    },
+                    password,
+                    dry_run: <_>::default(),
+                })
+                .await
+        })
         .await;
 }
 
@@ -976,6 +1069,25 @@ async fn simple_getters() {
         .await;
 }
 
+#[tokio::test]
+async fn health_probes() {
+    ApiTester::new()
+        .await
+        .test_get_lighthouse_health_live()
+        .await
+        .test_get_lighthouse_health_ready()
+        .await
+        .create_hd_validators(HdValidatorScenario {
+            count: 2,
+            specify_mnemonic: false,
+            key_derivation_path_offset: 0,
+            disabled: vec![1],
+        })
+        .await
+        .test_get_lighthouse_health_ready()
+        .await;
+}
+
 #[tokio::test]
 async fn hd_validator_creation() {
     ApiTester::new()
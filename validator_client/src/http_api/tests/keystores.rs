@@ -6,7 +6,7 @@ use eth2::lighthouse_vc::types::UpdateFeeRecipientRequest;
 use eth2::lighthouse_vc::{
     http_client::ValidatorClientHttpClient as HttpClient,
     std_types::{KeystoreJsonStr as Keystore, *},
-    types::Web3SignerValidatorRequest,
+    types::{ExportConfigBundleRequest, ImportConfigBundleRequest, Web3SignerValidatorRequest},
 };
 use itertools::Itertools;
 use rand::{rngs::SmallRng, Rng, SeedableRng};
@@ -2184,3 +2184,224 @@ async fn import_remotekey_web3signer_enabled() {
     })
     .await
 }
+
+/// Export a config bundle of local and remote validators from one VC and import it on another.
+#[tokio::test]
+async fn migrate_config_bundle() {
+    run_dual_vc_test(move |tester1, tester2| async move {
+        let _ = (&tester1, &tester2);
+        let bundle_password = random_password_string();
+
+        // Create local and web3signer validators on VC1, with a custom fee recipient for one.
+        let (keystores, passwords): (Vec<_>, Vec<_>) = (0..2)
+            .map(|_| {
+                let password = random_password_string();
+                (new_keystore(password.clone()), password)
+            })
+            .unzip();
+        let import_res = tester1
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: keystores.clone(),
+                passwords: passwords.clone(),
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+        check_keystore_import_response(&import_res, all_imported(keystores.len()));
+
+        let (_, remote_val) = new_web3signer_validator();
+        tester1
+            .client
+            .post_lighthouse_validators_web3signer(&[remote_val.clone()])
+            .await
+            .unwrap();
+
+        let fee_recipient = Address::repeat_byte(0xfe);
+        tester1
+            .client
+            .post_fee_recipient(
+                &keystore_pubkey(&keystores[0]),
+                &UpdateFeeRecipientRequest {
+                    ethaddress: fee_recipient,
+                },
+            )
+            .await
+            .unwrap();
+
+        // Sign an attestation on VC1 so there is slashing protection data to carry over.
+        let mut attestation = make_attestation(1, 2);
+        tester1
+            .validator_store
+            .sign_attestation(
+                keystore_pubkey(&keystores[0]),
+                0,
+                &mut attestation,
+                Epoch::new(2),
+            )
+            .await
+            .unwrap();
+
+        // Export the bundle, which should disable all validators on VC1.
+        let bundle = tester1
+            .client
+            .post_lighthouse_config_bundle_export(&ExportConfigBundleRequest {
+                password: bundle_password.clone(),
+                include_slashing_protection: true,
+            })
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(tester1.vals_enabled(), 0);
+
+        // A dry run should report that everything can be imported, without importing anything.
+        let import_req = ImportConfigBundleRequest {
+            bundle,
+            password: bundle_password,
+            dry_run: true,
+        };
+        let dry_run_res = tester2
+            .client
+            .post_lighthouse_config_bundle_import(&import_req)
+            .await
+            .unwrap();
+        assert!(dry_run_res.dry_run);
+        assert_eq!(dry_run_res.data.len(), 3);
+        for status in &dry_run_res.data {
+            assert_eq!(status.status.status, ImportKeystoreStatus::Imported);
+        }
+        assert_eq!(tester2.vals_total(), 0);
+
+        // Import the bundle for real.
+        let import_res = tester2
+            .client
+            .post_lighthouse_config_bundle_import(&ImportConfigBundleRequest {
+                dry_run: false,
+                ..import_req
+            })
+            .await
+            .unwrap();
+        assert!(!import_res.dry_run);
+        for status in &import_res.data {
+            assert_eq!(
+                status.status.status,
+                ImportKeystoreStatus::Imported,
+                "message: {:?}",
+                status.status.message
+            );
+        }
+        assert_eq!(tester2.vals_enabled(), 3);
+
+        let get_res = tester2.client.get_keystores().await.unwrap();
+        for keystore in &keystores {
+            let expected = SingleKeystoreResponse {
+                validating_pubkey: keystore_pubkey(keystore),
+                derivation_path: keystore.path(),
+                readonly: Some(false),
+            };
+            assert!(get_res.data.contains(&expected), "{:?}", expected);
+        }
+        let remote_res = tester2.client.get_remotekeys().await.unwrap();
+        check_remotekey_get_response(
+            &remote_res,
+            vec![SingleListRemotekeysResponse {
+                pubkey: remote_val.voting_public_key.compress(),
+                url: web3_signer_url(),
+                readonly: false,
+            }],
+        );
+
+        let fee_recipient_res = tester2
+            .client
+            .get_fee_recipient(&keystore_pubkey(&keystores[0]))
+            .await
+            .unwrap();
+        assert_eq!(fee_recipient_res.ethaddress, fee_recipient);
+
+        // The slashing protection data should prevent a double vote on VC2.
+        let mut attestation = make_attestation(1, 2);
+        tester2
+            .validator_store
+            .sign_attestation(
+                keystore_pubkey(&keystores[0]),
+                0,
+                &mut attestation,
+                Epoch::new(2),
+            )
+            .await
+            .unwrap_err();
+    })
+    .await
+}
+
+#[tokio::test]
+async fn import_config_bundle_invalid() {
+    run_dual_vc_test(move |tester1, tester2| async move {
+        let _ = (&tester1, &tester2);
+        let password = random_password_string();
+        let import_res = tester1
+            .client
+            .post_keystores(&ImportKeystoresRequest {
+                keystores: vec![new_keystore(password.clone())],
+                passwords: vec![password.clone()],
+                slashing_protection: None,
+            })
+            .await
+            .unwrap();
+        check_keystore_import_response(&import_res, all_imported(1));
+
+        let bundle = tester1
+            .client
+            .post_lighthouse_config_bundle_export(&ExportConfigBundleRequest {
+                password: password.clone(),
+                include_slashing_protection: false,
+            })
+            .await
+            .unwrap()
+            .data;
+
+        // The wrong password should be rejected.
+        let err = tester2
+            .client
+            .post_lighthouse_config_bundle_import(&ImportConfigBundleRequest {
+                bundle: bundle.clone(),
+                password: random_password_string(),
+                dry_run: true,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 400);
+
+        // A bundle from another network should be rejected.
+        let mut other_network_bundle = bundle.clone();
+        other_network_bundle.genesis_validators_root = Hash256::repeat_byte(0xff);
+        let err = tester2
+            .client
+            .post_lighthouse_config_bundle_import(&ImportConfigBundleRequest {
+                bundle: other_network_bundle,
+                password: password.clone(),
+                dry_run: true,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.status().unwrap(), 400);
+
+        // Importing the bundle back into the VC it came from is a no-op.
+        let import_res = tester1
+            .client
+            .post_lighthouse_config_bundle_import(&ImportConfigBundleRequest {
+                bundle,
+                password,
+                dry_run: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(import_res.data.len(), 1);
+        assert_eq!(
+            import_res.data[0].status.status,
+            ImportKeystoreStatus::Duplicate
+        );
+        assert_eq!(tester2.vals_total(), 0);
+    })
+    .await
+}
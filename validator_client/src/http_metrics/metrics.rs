@@ -35,8 +35,20 @@ pub const SUBSCRIPTIONS_HTTP_POST: &str = "subscriptions_http_post";
 pub const UPDATE_PROPOSERS: &str = "update_proposers";
 pub const ATTESTATION_SELECTION_PROOFS: &str = "attestation_selection_proofs";
 pub const SUBSCRIPTIONS: &str = "subscriptions";
+pub const HEAD_EVENT: &str = "head_event";
+pub const CHAIN_REORG_EVENT: &str = "chain_reorg_event";
+pub const ATTESTER_DUTIES_REORG_EVENT: &str = "attester_duties_reorg_event";
+pub const EVENT_STREAM_CLOSED: &str = "event_stream_closed";
+pub const ATTESTER_DUTIES: &str = "attester";
+pub const PROPOSER_DUTIES: &str = "proposer";
 pub const LOCAL_KEYSTORE: &str = "local_keystore";
 pub const WEB3SIGNER: &str = "web3signer";
+pub const LEASE_GRANTED: &str = "granted";
+pub const LEASE_HELD_BY_OTHER: &str = "held_by_other";
+pub const LEASE_ERROR: &str = "error";
+pub const LEASE_ALLOWED: &str = "allowed";
+pub const LEASE_REFUSED: &str = "refused";
+pub const LEASE_FAIL_OPEN: &str = "fail_open";
 
 pub use lighthouse_metrics::*;
 
@@ -104,6 +116,30 @@ lazy_static::lazy_static! {
         "Duration to perform duties service tasks",
         &["task"]
     );
+    pub static ref DUTIES_CACHE_HITS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_duties_cache_hits_total",
+        "Count of duty polls skipped because the dependent root was unchanged, by duty",
+        &["duty"]
+    );
+    pub static ref DUTIES_CACHE_INVALIDATIONS: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_duties_cache_invalidations_total",
+        "Count of duty cache invalidations, by cause",
+        &["cause"]
+    );
+    pub static ref SIGNING_LEASE_REQUESTS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_signing_lease_requests_total",
+        "Count of requests to the signing lease service, by outcome",
+        &["outcome"]
+    );
+    pub static ref SIGNING_LEASE_CHECKS_TOTAL: Result<IntCounterVec> = try_create_int_counter_vec(
+        "vc_signing_lease_checks_total",
+        "Count of signing lease checks prior to signing, by result",
+        &["result"]
+    );
+    pub static ref SIGNING_LEASES_HELD: Result<IntGauge> = try_create_int_gauge(
+        "vc_signing_leases_held",
+        "Number of unexpired signing leases held by this validator client",
+    );
     pub static ref ATTESTATION_SERVICE_TIMES: Result<HistogramVec> = try_create_histogram_vec(
         "vc_attestation_service_task_times_seconds",
         "Duration to perform attestation service tasks",
@@ -113,6 +149,10 @@ lazy_static::lazy_static! {
         "vc_slashing_protection_prune_times_seconds",
         "Time required to prune the slashing protection DB",
     );
+    pub static ref SLASHING_PROTECTION_ATTESTATION_BATCH_TIMES: Result<Histogram> = try_create_histogram(
+        "vc_slashing_protection_attestation_batch_times_seconds",
+        "Time required to check and insert a batch of attestations into the slashing protection DB"
+    );
     pub static ref BLOCK_SERVICE_TIMES: Result<HistogramVec> = try_create_histogram_vec(
         "vc_beacon_block_service_task_times_seconds",
         "Duration to perform beacon block service tasks",
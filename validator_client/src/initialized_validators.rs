@@ -116,6 +116,7 @@ pub enum Error {
     UnableToReadKeystoreFile(eth2_keystore::Error),
     UnableToSaveKeyCache(key_cache::Error),
     UnableToDecryptKeyCache(key_cache::Error),
+    UnableToDeleteKeyCache(key_cache::Error),
     UnableToDeletePasswordFile(PathBuf, io::Error),
 }
 
@@ -642,7 +643,7 @@ impl InitializedValidators {
         //
         // Do this before modifying `self.validators` or deleting anything from
         // the filesystem.
-        if let Some(uuid) = uuid_opt {
+        if let Some(uuid) = uuid_opt.filter(|_| !self.config.disable_key_cache) {
             let key_cache = KeyCache::open_or_create(&self.validators_dir)
                 .map_err(Error::UnableToOpenKeyCache)?;
             let mut decrypted_key_cache = self
@@ -1204,8 +1205,33 @@ impl InitializedValidators {
             get_lockfile_path(&key_cache_path).ok_or(Error::BadKeyCachePath(key_cache_path))?;
         let _cache_lockfile = Lockfile::new(cache_lockfile_path)?;
 
-        let cache =
-            KeyCache::open_or_create(&self.validators_dir).map_err(Error::UnableToOpenKeyCache)?;
+        let cache = if self.config.disable_key_cache {
+            // Don't leave decrypted key material on disk from a previous run.
+            if KeyCache::delete(&self.validators_dir).map_err(Error::UnableToDeleteKeyCache)? {
+                info!(
+                    self.log,
+                    "Deleted key cache";
+                    "reason" => "key cache is disabled",
+                );
+            }
+            KeyCache::new()
+        } else {
+            match KeyCache::open_or_create(&self.validators_dir) {
+                Err(key_cache::Error::InsecurePermissions(path)) => {
+                    // Delete the file so that it is re-created with restricted permissions.
+                    warn!(
+                        self.log,
+                        "Ignoring key cache with insecure permissions";
+                        "path" => ?path,
+                        "msg" => "the cache will be rebuilt from the keystores",
+                    );
+                    KeyCache::delete(&self.validators_dir)
+                        .map_err(Error::UnableToDeleteKeyCache)?;
+                    KeyCache::new()
+                }
+                result => result.map_err(Error::UnableToOpenKeyCache)?,
+            }
+        };
 
         // Check if there is at least one local definition.
         let has_local_definitions = self.definitions.as_slice().iter().any(|def| {
@@ -1217,11 +1243,13 @@ impl InitializedValidators {
 
         // Only decrypt cache when there is at least one local definition.
         // Decrypting cache is a very expensive operation which is never used for web3signer.
-        let mut key_cache = if has_local_definitions {
+        let use_key_cache = has_local_definitions && !self.config.disable_key_cache;
+        let mut key_cache = if use_key_cache {
             self.decrypt_key_cache(cache, &mut key_stores, OnDecryptFailure::CreateNew)
                 .await?
         } else {
-            // Assign an empty KeyCache if all definitions are of the Web3Signer type.
+            // Assign an empty KeyCache if all definitions are of the Web3Signer type, or the
+            // cache is disabled.
             KeyCache::new()
         };
 
@@ -1352,7 +1380,7 @@ impl InitializedValidators {
             }
         }
 
-        if has_local_definitions {
+        if use_key_cache {
             for uuid in disabled_uuids {
                 key_cache.remove(&uuid);
             }
@@ -1360,7 +1388,7 @@ impl InitializedValidators {
 
         let validators_dir = self.validators_dir.clone();
         let log = self.log.clone();
-        if has_local_definitions && key_cache.is_modified() {
+        if use_key_cache && key_cache.is_modified() {
             tokio::task::spawn_blocking(move || {
                 match key_cache.save(validators_dir) {
                     Err(e) => warn!(
@@ -11,7 +11,7 @@ use eth2_keystore::{
 use rand::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io;
 use std::path::{Path, PathBuf};
 
@@ -110,11 +110,39 @@ impl KeyCache {
         let file = File::options()
             .read(true)
             .create_new(false)
-            .open(cache_path)
+            .open(&cache_path)
             .map_err(Error::UnableToOpenFile)?;
+
+        // The cache is only as strong as the weakest keystore password, so refuse to use a cache
+        // which other users are able to read.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = file
+                .metadata()
+                .map_err(Error::UnableToOpenFile)?
+                .permissions()
+                .mode();
+            if mode & 0o077 != 0 {
+                return Err(Error::InsecurePermissions(cache_path));
+            }
+        }
+
         serde_json::from_reader(file).map_err(Error::UnableToParseFile)
     }
 
+    /// Delete the cache file, if it exists.
+    ///
+    /// Returns `true` if a file was deleted.
+    pub fn delete<P: AsRef<Path>>(validators_dir: P) -> Result<bool, Error> {
+        let cache_path = Self::cache_file_path(validators_dir);
+        if !cache_path.exists() {
+            return Ok(false);
+        }
+        fs::remove_file(cache_path).map_err(Error::UnableToDeleteFile)?;
+        Ok(true)
+    }
+
     fn encrypt(&mut self) -> Result<(), Error> {
         self.crypto = Self::init_crypto();
         let secret_map: SerializedKeyMap = self
@@ -252,6 +280,10 @@ pub enum Error {
     /// The cache file or its temporary could not be written to the filesystem.
     UnableToWriteFile(io::Error),
     UnableToCreateFile(filesystem::Error),
+    /// The cache file could not be deleted.
+    UnableToDeleteFile(io::Error),
+    /// The cache file is accessible to users other than its owner.
+    InsecurePermissions(PathBuf),
     /// Couldn't decrypt the cache file
     UnableToDecrypt(KeystoreError),
     UnableToEncrypt(KeystoreError),
@@ -351,4 +383,33 @@ mod tests {
             );
         }
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_insecure_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut key_cache = KeyCache::new();
+        key_cache.add(
+            Keypair::random(),
+            &Uuid::from_u128(1),
+            PlainText::from(vec![1, 2, 3]),
+        );
+        assert!(key_cache.save(dir.path()).unwrap());
+
+        // A newly created cache is only readable by its owner.
+        KeyCache::open(dir.path()).unwrap();
+
+        let cache_path = KeyCache::cache_file_path(dir.path());
+        fs::set_permissions(&cache_path, fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(matches!(
+            KeyCache::open(dir.path()),
+            Err(Error::InsecurePermissions(path)) if path == cache_path
+        ));
+
+        assert!(KeyCache::delete(dir.path()).unwrap());
+        assert!(!cache_path.exists());
+        assert!(!KeyCache::delete(dir.path()).unwrap());
+    }
 }
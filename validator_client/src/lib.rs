@@ -4,6 +4,7 @@ mod block_service;
 mod check_synced;
 mod cli;
 mod duties_service;
+mod duty_log;
 mod graffiti_file;
 mod http_metrics;
 mod key_cache;
@@ -17,6 +18,7 @@ pub mod config;
 mod doppelganger_service;
 pub mod http_api;
 pub mod initialized_validators;
+pub mod signing_lease;
 pub mod validator_store;
 
 pub use beacon_node_fallback::ApiTopic;
@@ -35,11 +37,12 @@ use crate::beacon_node_fallback::{
 use crate::doppelganger_service::DoppelgangerService;
 use crate::graffiti_file::GraffitiFile;
 use crate::initialized_validators::Error::UnableToOpenVotingKeystore;
+use crate::signing_lease::SigningLeaseService;
 use account_utils::validator_definitions::ValidatorDefinitions;
 use attestation_service::{AttestationService, AttestationServiceBuilder};
 use block_service::{BlockService, BlockServiceBuilder};
 use clap::ArgMatches;
-use duties_service::{sync::SyncDutiesMap, DutiesService};
+use duties_service::{dependent_roots::DependentRootCache, sync::SyncDutiesMap, DutiesService};
 use environment::RuntimeContext;
 use eth2::{reqwest::ClientBuilder, types::Graffiti, BeaconNodeHttpClient, StatusCode, Timeouts};
 use http_api::ApiSecret;
@@ -84,6 +87,7 @@ const HTTP_GET_BEACON_BLOCK_SSZ_TIMEOUT_QUOTIENT: u32 = 4;
 const HTTP_GET_DEBUG_BEACON_STATE_QUOTIENT: u32 = 4;
 const HTTP_GET_DEPOSIT_SNAPSHOT_QUOTIENT: u32 = 4;
 const HTTP_GET_VALIDATOR_BLOCK_TIMEOUT_QUOTIENT: u32 = 4;
+const HTTP_GET_EVENTS_TIMEOUT_QUOTIENT: u32 = 4;
 
 const DOPPELGANGER_SERVICE_NAME: &str = "doppelganger";
 
@@ -95,6 +99,7 @@ pub struct ProductionValidatorClient<E: EthSpec> {
     attestation_service: AttestationService<SystemTimeSlotClock, E>,
     sync_committee_service: SyncCommitteeService<SystemTimeSlotClock, E>,
     doppelganger_service: Option<Arc<DoppelgangerService>>,
+    signing_lease_service: Option<Arc<SigningLeaseService>>,
     preparation_service: PreparationService<SystemTimeSlotClock, E>,
     validator_store: Arc<ValidatorStore<SystemTimeSlotClock, E>>,
     slot_clock: SystemTimeSlotClock,
@@ -334,6 +339,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                     get_debug_beacon_states: slot_duration / HTTP_GET_DEBUG_BEACON_STATE_QUOTIENT,
                     get_deposit_snapshot: slot_duration / HTTP_GET_DEPOSIT_SNAPSHOT_QUOTIENT,
                     get_validator_block: slot_duration / HTTP_GET_VALIDATOR_BLOCK_TIMEOUT_QUOTIENT,
+                    get_events: slot_duration / HTTP_GET_EVENTS_TIMEOUT_QUOTIENT,
                 }
             } else {
                 Timeouts::set_all(slot_duration)
@@ -440,12 +446,19 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             None
         };
 
+        let signing_lease_service = config
+            .signing_lease
+            .clone()
+            .map(|lease_config| SigningLeaseService::new(lease_config, log.clone()).map(Arc::new))
+            .transpose()?;
+
         let validator_store = Arc::new(ValidatorStore::new(
             validators,
             slashing_protection,
             genesis_validators_root,
             context.eth2_config.spec.clone(),
             doppelganger_service.clone(),
+            signing_lease_service.clone(),
             slot_clock.clone(),
             &config,
             context.executor.clone(),
@@ -481,6 +494,9 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             context: duties_context,
             enable_high_validator_count_metrics: config.enable_high_validator_count_metrics,
             distributed: config.distributed,
+            dependent_root_cache: config
+                .enable_duties_cache
+                .then(|| Arc::new(DependentRootCache::default())),
         });
 
         // Update the metrics server.
@@ -495,7 +511,8 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("block".into()))
             .graffiti(config.graffiti)
-            .graffiti_file(config.graffiti_file.clone());
+            .graffiti_file(config.graffiti_file.clone())
+            .log_duty_ids(config.log_duty_ids);
 
         // If we have proposer nodes, add them to the block service builder.
         if proposer_nodes_num > 0 {
@@ -510,6 +527,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .validator_store(validator_store.clone())
             .beacon_nodes(beacon_nodes.clone())
             .runtime_context(context.service_context("attestation".into()))
+            .log_duty_ids(config.log_duty_ids)
             .build()?;
 
         let preparation_service = PreparationServiceBuilder::new()
@@ -527,6 +545,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             slot_clock.clone(),
             beacon_nodes.clone(),
             context.service_context("sync_committee".into()),
+            config.log_duty_ids,
         );
 
         Ok(Self {
@@ -536,6 +555,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             attestation_service,
             sync_committee_service,
             doppelganger_service,
+            signing_lease_service,
             preparation_service,
             validator_store,
             config,
@@ -561,6 +581,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 task_executor: self.context.executor.clone(),
                 api_secret,
                 validator_store: Some(self.validator_store.clone()),
+                beacon_nodes: Some(self.beacon_nodes.clone()),
                 validator_dir: Some(self.config.validator_dir.clone()),
                 secrets_dir: Some(self.config.secrets_dir.clone()),
                 graffiti_file: self.config.graffiti_file.clone(),
@@ -628,6 +649,21 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             info!(log, "Doppelganger protection disabled.")
         }
 
+        if let Some(signing_lease_service) = self.signing_lease_service.clone() {
+            info!(
+                log,
+                "Signing leases enabled";
+                "url" => %signing_lease_service.config().url,
+                "holder_id" => &signing_lease_service.config().holder_id,
+                "failure_mode" => ?signing_lease_service.config().failure_mode,
+            );
+            signing_lease_service.start_update_service(
+                self.context.executor.clone(),
+                self.validator_store.clone(),
+                self.slot_clock.clone(),
+            );
+        }
+
         spawn_notifier(self).map_err(|e| format!("Failed to start notifier: {}", e))?;
 
         if self.config.enable_latency_measurement_service {
@@ -0,0 +1,380 @@
+//! Optional leases on validator keys, granted by an external lease service.
+//!
+//! These allow two or more validator clients to be run with the same keys as an active-passive
+//! group: a VC only signs blocks and attestations for a validator while it holds that validator's
+//! lease, and the service grants each lease to one holder at a time. Leases are requested with:
+//!
+//! ```text
+//! POST {url}/leases/{pubkey}
+//! {"holder": "<holder id>", "duration_secs": <seconds>}
+//! ```
+//!
+//! The service should respond with `200 OK` if the lease has been granted to (or renewed for) the
+//! holder for the requested duration, or `409 Conflict` if another holder has it.
+//!
+//! The leases of enabled validators are renewed each slot once half of their duration has elapsed,
+//! so that signing only waits on the service when a renewal has failed. If the service can't be
+//! reached and the lease has expired, signing is refused unless `FailureMode::Open` is configured.
+use crate::http_metrics::metrics;
+use crate::validator_store::{DoppelgangerStatus, ValidatorStore};
+use futures::future::join_all;
+use parking_lot::RwLock;
+use reqwest::{Client, StatusCode};
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use slog::{debug, error, warn, Logger};
+use slot_clock::SlotClock;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use task_executor::TaskExecutor;
+use tokio::time::sleep;
+use types::{EthSpec, PublicKeyBytes};
+
+pub const DEFAULT_LEASE_DURATION: Duration = Duration::from_secs(36);
+
+/// The timeout for each request to the lease service.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// What to do when a lease has expired and the lease service can't be reached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FailureMode {
+    /// Refuse to sign.
+    #[default]
+    Closed,
+    /// Sign as though the lease were held. Only safe if no other VC can be signing with the same
+    /// keys.
+    Open,
+}
+
+impl FromStr for FailureMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "closed" => Ok(FailureMode::Closed),
+            "open" => Ok(FailureMode::Open),
+            other => Err(format!(
+                "Unknown signing lease failure mode: {}, expected closed or open",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    pub url: SensitiveUrl,
+    /// Identifies this VC to the lease service. It must be unique within a group of VCs sharing
+    /// keys, and should stay the same across restarts so that leases can be renewed.
+    pub holder_id: String,
+    pub lease_duration: Duration,
+    pub failure_mode: FailureMode,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The lease is held by another VC.
+    HeldByOther,
+    /// The lease service couldn't be reached, or gave an unexpected response.
+    Unavailable(String),
+}
+
+#[derive(Serialize)]
+struct LeaseRequest<'a> {
+    holder: &'a str,
+    duration_secs: u64,
+}
+
+pub struct SigningLeaseService {
+    client: Client,
+    config: Config,
+    /// The time at which each of our leases expires.
+    leases: RwLock<HashMap<PublicKeyBytes, Instant>>,
+    log: Logger,
+}
+
+impl SigningLeaseService {
+    pub fn new(config: Config, log: Logger) -> Result<Self, String> {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Unable to build signing lease client: {:?}", e))?;
+        Ok(Self {
+            client,
+            config,
+            leases: RwLock::new(HashMap::new()),
+            log,
+        })
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns `true` if signing for `pubkey` is permitted, acquiring or renewing its lease first if
+    /// it isn't fresh.
+    pub async fn check(&self, pubkey: PublicKeyBytes) -> bool {
+        if self.is_fresh(&pubkey) {
+            metrics::inc_counter_vec(
+                &metrics::SIGNING_LEASE_CHECKS_TOTAL,
+                &[metrics::LEASE_ALLOWED],
+            );
+            return true;
+        }
+
+        let result = match self.acquire(pubkey).await {
+            Ok(()) => metrics::LEASE_ALLOWED,
+            Err(Error::HeldByOther) => {
+                warn!(
+                    self.log,
+                    "Not signing, lease held by another validator client";
+                    "pubkey" => ?pubkey,
+                );
+                metrics::LEASE_REFUSED
+            }
+            // A lease which hasn't expired can still be used while the service is unavailable.
+            Err(Error::Unavailable(_)) if self.remaining(&pubkey) > Duration::ZERO => {
+                metrics::LEASE_ALLOWED
+            }
+            Err(Error::Unavailable(e)) => match self.config.failure_mode {
+                FailureMode::Closed => {
+                    error!(
+                        self.log,
+                        "Not signing, unable to acquire lease";
+                        "error" => e,
+                        "pubkey" => ?pubkey,
+                    );
+                    metrics::LEASE_REFUSED
+                }
+                FailureMode::Open => {
+                    warn!(
+                        self.log,
+                        "Signing without lease";
+                        "msg" => "the signing lease failure mode is open",
+                        "error" => e,
+                        "pubkey" => ?pubkey,
+                    );
+                    metrics::LEASE_FAIL_OPEN
+                }
+            },
+        };
+        metrics::inc_counter_vec(&metrics::SIGNING_LEASE_CHECKS_TOTAL, &[result]);
+        result != metrics::LEASE_REFUSED
+    }
+
+    /// The time remaining on our lease for `pubkey`, which is zero if we don't hold it.
+    fn remaining(&self, pubkey: &PublicKeyBytes) -> Duration {
+        self.leases
+            .read()
+            .get(pubkey)
+            .map_or(Duration::ZERO, |expiry| {
+                expiry.saturating_duration_since(Instant::now())
+            })
+    }
+
+    /// Returns `true` if less than half of our lease for `pubkey` has elapsed.
+    fn is_fresh(&self, pubkey: &PublicKeyBytes) -> bool {
+        self.remaining(pubkey) > self.config.lease_duration / 2
+    }
+
+    /// Request the lease for `pubkey` from the lease service.
+    async fn acquire(&self, pubkey: PublicKeyBytes) -> Result<(), Error> {
+        let result = self.request_lease(pubkey).await;
+        let outcome = match &result {
+            Ok(expiry) => {
+                self.leases.write().insert(pubkey, *expiry);
+                metrics::LEASE_GRANTED
+            }
+            Err(Error::HeldByOther) => {
+                self.leases.write().remove(&pubkey);
+                metrics::LEASE_HELD_BY_OTHER
+            }
+            Err(Error::Unavailable(_)) => metrics::LEASE_ERROR,
+        };
+        metrics::inc_counter_vec(&metrics::SIGNING_LEASE_REQUESTS_TOTAL, &[outcome]);
+        result.map(|_| ())
+    }
+
+    /// Returns the expiry of the lease if it was granted.
+    async fn request_lease(&self, pubkey: PublicKeyBytes) -> Result<Instant, Error> {
+        let mut url = self.config.url.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::Unavailable("invalid lease service url".to_string()))?
+            .pop_if_empty()
+            .push("leases")
+            .push(&pubkey.to_string());
+
+        // The lease's duration is counted from before the request, in case the service grants it
+        // as soon as the request arrives.
+        let requested_at = Instant::now();
+        let response = self
+            .client
+            .post(url)
+            .json(&LeaseRequest {
+                holder: &self.config.holder_id,
+                duration_secs: self.config.lease_duration.as_secs(),
+            })
+            .send()
+            .await
+            .map_err(|e| Error::Unavailable(e.without_url().to_string()))?;
+
+        match response.status() {
+            StatusCode::OK => Ok(requested_at + self.config.lease_duration),
+            StatusCode::CONFLICT => Err(Error::HeldByOther),
+            status => Err(Error::Unavailable(format!(
+                "unexpected response status {}",
+                status
+            ))),
+        }
+    }
+
+    /// Renew our leases for `pubkeys` which are no longer fresh.
+    async fn renew_leases(&self, pubkeys: &[PublicKeyBytes]) {
+        let results = join_all(
+            pubkeys
+                .iter()
+                .filter(|pubkey| !self.is_fresh(pubkey))
+                .map(|pubkey| self.acquire(*pubkey)),
+        )
+        .await;
+
+        let held_by_other = results
+            .iter()
+            .filter(|result| **result == Err(Error::HeldByOther))
+            .count();
+        if held_by_other > 0 {
+            debug!(
+                self.log,
+                "Signing leases held by another validator client";
+                "count" => held_by_other,
+            );
+        }
+
+        let mut errors = results.into_iter().filter_map(|result| match result {
+            Err(Error::Unavailable(e)) => Some(e),
+            _ => None,
+        });
+        if let Some(e) = errors.next() {
+            warn!(
+                self.log,
+                "Unable to renew signing leases";
+                "error" => e,
+                "count" => errors.count() + 1,
+            );
+        }
+
+        let now = Instant::now();
+        let held = self
+            .leases
+            .read()
+            .values()
+            .filter(|expiry| **expiry > now)
+            .count();
+        metrics::set_gauge(&metrics::SIGNING_LEASES_HELD, held as i64);
+    }
+
+    /// Start a task which renews the leases of all validators permitted to sign, each slot.
+    pub fn start_update_service<T: SlotClock + 'static, E: EthSpec>(
+        self: Arc<Self>,
+        executor: TaskExecutor,
+        validator_store: Arc<ValidatorStore<T, E>>,
+        slot_clock: T,
+    ) {
+        let future = async move {
+            loop {
+                let pubkeys: Vec<_> = validator_store.voting_pubkeys(DoppelgangerStatus::only_safe);
+                self.renew_leases(&pubkeys).await;
+
+                sleep(
+                    slot_clock
+                        .duration_to_next_slot()
+                        .unwrap_or_else(|| slot_clock.slot_duration()),
+                )
+                .await;
+            }
+        };
+        executor.spawn(future, "signing_lease");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, SocketAddr};
+    use warp::Filter;
+
+    /// Serve a lease service which only grants leases to `holder`.
+    fn serve_leases(holder: &'static str) -> SensitiveUrl {
+        #[derive(Deserialize)]
+        struct Request {
+            holder: String,
+        }
+
+        let route = warp::post()
+            .and(warp::path!("leases" / String))
+            .and(warp::body::json())
+            .map(move |_pubkey: String, request: Request| {
+                let status = if request.holder == holder {
+                    warp::http::StatusCode::OK
+                } else {
+                    warp::http::StatusCode::CONFLICT
+                };
+                warp::reply::with_status(warp::reply(), status)
+            });
+        let (addr, server) =
+            warp::serve(route).bind_ephemeral(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)));
+        tokio::spawn(server);
+        SensitiveUrl::parse(&format!("http://{}", addr)).unwrap()
+    }
+
+    fn service(
+        url: SensitiveUrl,
+        holder_id: &str,
+        failure_mode: FailureMode,
+    ) -> SigningLeaseService {
+        let config = Config {
+            url,
+            holder_id: holder_id.to_string(),
+            lease_duration: DEFAULT_LEASE_DURATION,
+            failure_mode,
+        };
+        SigningLeaseService::new(config, logging::test_logger()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn only_the_holder_may_sign() {
+        let url = serve_leases("active");
+        let active = service(url.clone(), "active", FailureMode::Closed);
+        let passive = service(url, "passive", FailureMode::Open);
+        let pubkey = PublicKeyBytes::empty();
+
+        assert!(active.check(pubkey).await);
+        assert!(active.is_fresh(&pubkey));
+        // Being refused the lease is never treated as a failure of the service.
+        assert!(!passive.check(pubkey).await);
+        assert_eq!(passive.remaining(&pubkey), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn unavailable_service_uses_failure_mode() {
+        // Nothing listens on the discard port.
+        let url = SensitiveUrl::parse("http://127.0.0.1:9").unwrap();
+        let pubkey = PublicKeyBytes::empty();
+
+        let closed = service(url.clone(), "closed", FailureMode::Closed);
+        assert!(!closed.check(pubkey).await);
+
+        let open = service(url.clone(), "open", FailureMode::Open);
+        assert!(open.check(pubkey).await);
+
+        // An unexpired lease remains usable, whatever the failure mode.
+        let held = service(url, "held", FailureMode::Closed);
+        held.leases
+            .write()
+            .insert(pubkey, Instant::now() + DEFAULT_LEASE_DURATION / 4);
+        assert!(held.check(pubkey).await);
+    }
+}
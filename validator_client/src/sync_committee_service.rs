@@ -1,6 +1,7 @@
 use crate::beacon_node_fallback::{ApiTopic, BeaconNodeFallback, RequireSynced};
 use crate::{
     duties_service::DutiesService,
+    duty_log::{duty_logger, Duty},
     validator_store::{Error as ValidatorStoreError, ValidatorStore},
     OfflineOnFailure,
 };
@@ -48,6 +49,7 @@ pub struct Inner<T: SlotClock + 'static, E: EthSpec> {
     slot_clock: T,
     beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
     context: RuntimeContext<E>,
+    log_duty_ids: bool,
     /// Boolean to track whether the service has posted subscriptions to the BN at least once.
     ///
     /// This acts as a latch that fires once upon start-up, and then never again.
@@ -61,6 +63,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         slot_clock: T,
         beacon_nodes: Arc<BeaconNodeFallback<T, E>>,
         context: RuntimeContext<E>,
+        log_duty_ids: bool,
     ) -> Self {
         Self {
             inner: Arc::new(Inner {
@@ -69,6 +72,7 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 slot_clock,
                 beacon_nodes,
                 context,
+                log_duty_ids,
                 first_subscription_done: AtomicBool::new(false),
             }),
         }
@@ -251,7 +255,13 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
         beacon_block_root: Hash256,
         validator_duties: Vec<SyncDuty>,
     ) -> Result<(), ()> {
-        let log = self.context.log();
+        let log = &duty_logger(
+            self.context.log(),
+            self.log_duty_ids,
+            Duty::SyncCommitteeMessage,
+            slot,
+            None,
+        );
 
         // Create futures to produce sync committee signatures.
         let signature_futures = validator_duties.iter().map(|duty| async move {
@@ -367,7 +377,13 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
     ) -> Result<(), ()> {
         sleep_until(aggregate_instant).await;
 
-        let log = self.context.log();
+        let log = &duty_logger(
+            self.context.log(),
+            self.log_duty_ids,
+            Duty::SyncContribution,
+            slot,
+            Some(*subnet_id),
+        );
 
         let contribution = &self
             .beacon_nodes
@@ -2,10 +2,12 @@ use crate::{
     doppelganger_service::DoppelgangerService,
     http_metrics::metrics,
     initialized_validators::InitializedValidators,
+    signing_lease::SigningLeaseService,
     signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod},
     Config,
 };
 use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
+use futures::future::join_all;
 use parking_lot::{Mutex, RwLock};
 use slashing_protection::{
     interchange::Interchange, InterchangeError, NotSafe, Safe, SlashingDatabase,
@@ -29,10 +31,20 @@ use types::{
 pub use crate::doppelganger_service::DoppelgangerStatus;
 use crate::preparation_service::ProposalData;
 
+/// The means to sign an attestation, once it has passed its slashing protection check.
+struct AttestationSigner {
+    signing_method: Arc<SigningMethod>,
+    signing_context: SigningContext,
+    /// The domain to check the attestation against slashing protection with, or `None` if the
+    /// validator does not require local slashing protection.
+    slashing_protection_domain: Option<Hash256>,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Error {
     DoppelgangerProtected(PublicKeyBytes),
     UnknownToDoppelgangerService(PublicKeyBytes),
+    NoSigningLease(PublicKeyBytes),
     UnknownPubkey(PublicKeyBytes),
     Slashable(NotSafe),
     SameData,
@@ -66,6 +78,7 @@ pub struct ValidatorStore<T, E: EthSpec> {
     spec: Arc<ChainSpec>,
     log: Logger,
     doppelganger_service: Option<Arc<DoppelgangerService>>,
+    signing_lease_service: Option<Arc<SigningLeaseService>>,
     slot_clock: T,
     fee_recipient_process: Option<Address>,
     gas_limit: Option<u64>,
@@ -88,6 +101,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         genesis_validators_root: Hash256,
         spec: ChainSpec,
         doppelganger_service: Option<Arc<DoppelgangerService>>,
+        signing_lease_service: Option<Arc<SigningLeaseService>>,
         slot_clock: T,
         config: &Config,
         task_executor: TaskExecutor,
@@ -101,6 +115,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             spec: Arc::new(spec),
             log,
             doppelganger_service,
+            signing_lease_service,
             slot_clock,
             fee_recipient_process: config.fee_recipient,
             gas_limit: config.gas_limit,
@@ -137,6 +152,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.validators.clone()
     }
 
+    pub fn genesis_validators_root(&self) -> Hash256 {
+        self.genesis_validators_root
+    }
+
     /// Indicates if the `voting_public_key` exists in self and is enabled.
     pub fn has_validator(&self, voting_public_key: &PublicKeyBytes) -> bool {
         self.validators
@@ -317,6 +336,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.validators.read().num_enabled()
     }
 
+    /// Check that the slashing protection database can be read.
+    pub fn check_slashing_protection_db(&self) -> Result<(), NotSafe> {
+        self.slashing_protection.num_validator_rows().map(|_| ())
+    }
+
     fn fork(&self, epoch: Epoch) -> Fork {
         self.spec.fork_at_epoch(epoch)
     }
@@ -341,6 +365,17 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         }
     }
 
+    /// Returns an error if signing leases are required and the lease for `validator_pubkey` is not
+    /// held.
+    async fn check_signing_lease(&self, validator_pubkey: PublicKeyBytes) -> Result<(), Error> {
+        match &self.signing_lease_service {
+            Some(service) if !service.check(validator_pubkey).await => {
+                Err(Error::NoSigningLease(validator_pubkey))
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Returns a `SigningMethod` for `validator_pubkey` regardless of that validators doppelganger
     /// protection status.
     ///
@@ -580,6 +615,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         let domain_hash = signing_context.domain_hash(&self.spec);
 
         let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+        self.check_signing_lease(validator_pubkey).await?;
 
         // Check for slashing conditions.
         let slashing_status = if signing_method
@@ -646,6 +682,130 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         attestation: &mut Attestation<E>,
         current_epoch: Epoch,
     ) -> Result<(), Error> {
+        let signer = self
+            .attestation_signer(validator_pubkey, attestation, current_epoch)
+            .await?;
+
+        // Checking for slashing conditions.
+        let slashing_status = match signer.slashing_protection_domain {
+            Some(domain_hash) => self.slashing_protection.check_and_insert_attestation(
+                &validator_pubkey,
+                &attestation.data,
+                domain_hash,
+            ),
+            None => Ok(Safe::Valid),
+        };
+        self.process_attestation_slashing_status(validator_pubkey, attestation, slashing_status)?;
+
+        // We can safely sign this attestation.
+        self.sign_checked_attestation(signer, validator_committee_position, attestation)
+            .await
+    }
+
+    /// Sign a batch of attestations, checking them against the slashing protection database in a
+    /// single transaction.
+    ///
+    /// As with `sign_attestation`, every attestation is checked *before* it is signed, and only
+    /// those which pass the check are signed.
+    ///
+    /// Each tuple is `(validator_index, validator_pubkey, validator_committee_position,
+    /// attestation)`. Results are returned in the same order, paired with the validator index.
+    pub async fn sign_attestations(
+        &self,
+        attestations: Vec<(u64, PublicKeyBytes, usize, Attestation<E>)>,
+        current_epoch: Epoch,
+    ) -> Vec<(u64, Result<Attestation<E>, Error>)> {
+        let signer_futures = attestations.into_iter().map(
+            |(validator_index, validator_pubkey, committee_position, attestation)| async move {
+                let signer = self
+                    .attestation_signer(validator_pubkey, &attestation, current_epoch)
+                    .await;
+                (
+                    validator_index,
+                    validator_pubkey,
+                    committee_position,
+                    attestation,
+                    signer,
+                )
+            },
+        );
+        let prepared = join_all(signer_futures).await;
+
+        // Check every attestation which requires local slashing protection in one transaction.
+        let to_check = prepared
+            .iter()
+            .filter_map(|(_, validator_pubkey, _, attestation, signer)| {
+                let domain_hash = signer.as_ref().ok()?.slashing_protection_domain?;
+                Some((validator_pubkey, &attestation.data, domain_hash))
+            })
+            .collect::<Vec<_>>();
+        let mut slashing_statuses = {
+            let _timer =
+                metrics::start_timer(&metrics::SLASHING_PROTECTION_ATTESTATION_BATCH_TIMES);
+            if to_check.is_empty() {
+                vec![]
+            } else {
+                match self
+                    .slashing_protection
+                    .check_and_insert_attestations(&to_check)
+                {
+                    Ok(statuses) => statuses,
+                    Err(e) => vec![Err(e); to_check.len()],
+                }
+            }
+        }
+        .into_iter();
+
+        // Only sign the attestations which passed the check.
+        let signing_futures = prepared.into_iter().map(
+            |(
+                validator_index,
+                validator_pubkey,
+                validator_committee_position,
+                mut attestation,
+                signer,
+            )| {
+                let checked = signer.and_then(|signer| {
+                    let slashing_status = match signer.slashing_protection_domain {
+                        Some(_) => slashing_statuses
+                            .next()
+                            .unwrap_or(Err(NotSafe::ConsistencyError)),
+                        None => Ok(Safe::Valid),
+                    };
+                    self.process_attestation_slashing_status(
+                        validator_pubkey,
+                        &attestation,
+                        slashing_status,
+                    )
+                    .map(|()| signer)
+                });
+                async move {
+                    let result = match checked {
+                        Ok(signer) => self
+                            .sign_checked_attestation(
+                                signer,
+                                validator_committee_position,
+                                &mut attestation,
+                            )
+                            .await
+                            .map(|()| attestation),
+                        Err(e) => Err(e),
+                    };
+                    (validator_index, result)
+                }
+            },
+        );
+        join_all(signing_futures).await
+    }
+
+    /// Perform the checks which must pass before `attestation` is checked against slashing
+    /// protection and signed, returning the means to sign it.
+    async fn attestation_signer(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        attestation: &Attestation<E>,
+        current_epoch: Epoch,
+    ) -> Result<AttestationSigner, Error> {
         // Make sure the target epoch is not higher than the current epoch to avoid potential attacks.
         if attestation.data.target.epoch > current_epoch {
             return Err(Error::GreaterThanCurrentEpoch {
@@ -656,40 +816,54 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
 
         // Get the signing method and check doppelganger protection.
         let signing_method = self.doppelganger_checked_signing_method(validator_pubkey)?;
+        self.check_signing_lease(validator_pubkey).await?;
 
-        // Checking for slashing conditions.
         let signing_epoch = attestation.data.target.epoch;
         let signing_context = self.signing_context(Domain::BeaconAttester, signing_epoch);
-        let domain_hash = signing_context.domain_hash(&self.spec);
-        let slashing_status = if signing_method
+        let slashing_protection_domain = signing_method
             .requires_local_slashing_protection(self.enable_web3signer_slashing_protection)
-        {
-            self.slashing_protection.check_and_insert_attestation(
-                &validator_pubkey,
-                &attestation.data,
-                domain_hash,
+            .then(|| signing_context.domain_hash(&self.spec));
+
+        Ok(AttestationSigner {
+            signing_method,
+            signing_context,
+            slashing_protection_domain,
+        })
+    }
+
+    /// Sign `attestation`, which must already have passed its slashing protection check.
+    async fn sign_checked_attestation(
+        &self,
+        signer: AttestationSigner,
+        validator_committee_position: usize,
+        attestation: &mut Attestation<E>,
+    ) -> Result<(), Error> {
+        let signature = signer
+            .signing_method
+            .get_signature::<E, BlindedPayload<E>>(
+                SignableMessage::AttestationData(&attestation.data),
+                signer.signing_context,
+                &self.spec,
+                &self.task_executor,
             )
-        } else {
-            Ok(Safe::Valid)
-        };
+            .await?;
+        attestation
+            .add_signature(&signature, validator_committee_position)
+            .map_err(Error::UnableToSignAttestation)
+    }
 
+    /// Log and record metrics for the outcome of checking an attestation against slashing
+    /// protection, returning an error if it must not be signed.
+    fn process_attestation_slashing_status(
+        &self,
+        validator_pubkey: PublicKeyBytes,
+        attestation: &Attestation<E>,
+        slashing_status: Result<Safe, NotSafe>,
+    ) -> Result<(), Error> {
         match slashing_status {
             // We can safely sign this attestation.
             Ok(Safe::Valid) => {
-                let signature = signing_method
-                    .get_signature::<E, BlindedPayload<E>>(
-                        SignableMessage::AttestationData(&attestation.data),
-                        signing_context,
-                        &self.spec,
-                        &self.task_executor,
-                    )
-                    .await?;
-                attestation
-                    .add_signature(&signature, validator_committee_position)
-                    .map_err(Error::UnableToSignAttestation)?;
-
                 metrics::inc_counter_vec(&metrics::SIGNED_ATTESTATIONS_TOTAL, &[metrics::SUCCESS]);
-
                 Ok(())
             }
             Ok(Safe::SameData) => {
@@ -721,6 +895,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
                     self.log,
                     "Not signing slashable attestation";
                     "attestation" => format!("{:?}", attestation.data),
+                    "validator" => ?validator_pubkey,
                     "error" => format!("{:?}", e)
                 );
                 metrics::inc_counter_vec(
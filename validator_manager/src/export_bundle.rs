@@ -0,0 +1,180 @@
+use super::common::*;
+use crate::DumpConfig;
+use account_utils::{read_password_from_user, read_password_string, ZeroizeString};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{lighthouse_vc::types::ExportConfigBundleRequest, SensitiveUrl};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const CMD: &str = "export-bundle";
+pub const OUTPUT_PATH_FLAG: &str = "output-path";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const PASSWORD_FILE_FLAG: &str = "password-file";
+pub const INCLUDE_SLASHING_PROTECTION_FLAG: &str = "include-slashing-protection";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Exports the validators of a validator client to a single encrypted bundle file \
+                using the HTTP API. The bundle contains the validator definitions, their keystores \
+                and per-validator settings (graffiti, fee recipient, gas limit and builder \
+                preferences). It can be imported into another validator client using the \
+                \"import-bundle\" command. The validator client must be started with \
+                --http-allow-keystore-export.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(OUTPUT_PATH_FLAG)
+                .long(OUTPUT_PATH_FLAG)
+                .value_name("PATH_TO_JSON_FILE")
+                .help("The path at which to write the bundle. The file must not already exist.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(PASSWORD_FILE_FLAG)
+                .long(PASSWORD_FILE_FLAG)
+                .value_name("PATH")
+                .help(
+                    "The file containing the password used to encrypt the bundle. If this \
+                    flag is not provided, the password will be read from the user.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(INCLUDE_SLASHING_PROTECTION_FLAG)
+                .long(INCLUDE_SLASHING_PROTECTION_FLAG)
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help(
+                    "If present, include the slashing protection data of all validators in the \
+                    bundle. All validators will be DISABLED on the validator client so that \
+                    the slashing protection data in the bundle is final. This flag should be \
+                    used when moving the validators to another host.",
+                )
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(STDIN_INPUTS_FLAG)
+                .action(ArgAction::SetTrue)
+                .hide(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty.")
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ExportBundleConfig {
+    pub output_path: PathBuf,
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub password_file: Option<PathBuf>,
+    pub include_slashing_protection: bool,
+    pub stdin_inputs: bool,
+}
+
+impl ExportBundleConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            output_path: clap_utils::parse_required(matches, OUTPUT_PATH_FLAG)?,
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            password_file: clap_utils::parse_optional(matches, PASSWORD_FILE_FLAG)?,
+            include_slashing_protection: matches.get_flag(INCLUDE_SLASHING_PROTECTION_FLAG),
+            stdin_inputs: cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG),
+        })
+    }
+}
+
+pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<(), String> {
+    let config = ExportBundleConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+/// Reads the bundle password from `password_file`, or from the user if it is `None`.
+pub fn read_bundle_password(
+    password_file: Option<&PathBuf>,
+    stdin_inputs: bool,
+) -> Result<ZeroizeString, String> {
+    match password_file {
+        Some(path) => read_password_string(path)
+            .map_err(|e| format!("Unable to read password from {:?}: {}", path, e)),
+        None => {
+            eprintln!("Please enter the bundle password:");
+            read_password_from_user(stdin_inputs)
+        }
+    }
+}
+
+async fn run(config: ExportBundleConfig) -> Result<(), String> {
+    let ExportBundleConfig {
+        output_path,
+        vc_url,
+        vc_token_path,
+        password_file,
+        include_slashing_protection,
+        stdin_inputs,
+    } = config;
+
+    if output_path.exists() {
+        return Err(format!("{:?} already exists", output_path));
+    }
+
+    let password = read_bundle_password(password_file.as_ref(), stdin_inputs)?;
+
+    let (http_client, _keystores) = vc_http_client(vc_url.clone(), &vc_token_path).await?;
+
+    if include_slashing_protection {
+        eprintln!("Validators will be disabled on the VC whilst exporting slashing protection");
+    }
+
+    let bundle = http_client
+        .post_lighthouse_config_bundle_export(&ExportConfigBundleRequest {
+            password,
+            include_slashing_protection,
+        })
+        .await
+        .map_err(|e| format!("Failed to export config bundle: {:?}", e))?
+        .data;
+
+    write_to_json_file(&output_path, &bundle)?;
+    eprintln!("Config bundle written to {:?}", output_path);
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use super::common::*;
+use crate::export_bundle::read_bundle_password;
+use crate::DumpConfig;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use clap_utils::FLAG_HEADER;
+use eth2::{
+    lighthouse_vc::{
+        std_types::ImportKeystoreStatus,
+        types::{ConfigBundle, ImportConfigBundleRequest},
+    },
+    SensitiveUrl,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+pub const CMD: &str = "import-bundle";
+pub const BUNDLE_FILE_FLAG: &str = "bundle-file";
+pub const VC_URL_FLAG: &str = "vc-url";
+pub const VC_TOKEN_FLAG: &str = "vc-token";
+pub const PASSWORD_FILE_FLAG: &str = "password-file";
+pub const DRY_RUN_FLAG: &str = "dry-run";
+
+pub fn cli_app() -> Command {
+    Command::new(CMD)
+        .about(
+            "Imports a bundle created by the \"export-bundle\" command into a validator client \
+                using the HTTP API. The bundle must have been exported from a validator client \
+                on the same network.",
+        )
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new(BUNDLE_FILE_FLAG)
+                .long(BUNDLE_FILE_FLAG)
+                .value_name("PATH_TO_JSON_FILE")
+                .help("The path to the bundle file created by the \"export-bundle\" command.")
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_URL_FLAG)
+                .long(VC_URL_FLAG)
+                .value_name("HTTP_ADDRESS")
+                .help("A HTTP(S) address of a validator client using the keymanager-API.")
+                .default_value("http://localhost:5062")
+                .requires(VC_TOKEN_FLAG)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(VC_TOKEN_FLAG)
+                .long(VC_TOKEN_FLAG)
+                .value_name("PATH")
+                .help("The file containing a token required by the validator client.")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(PASSWORD_FILE_FLAG)
+                .long(PASSWORD_FILE_FLAG)
+                .value_name("PATH")
+                .help(
+                    "The file containing the password used to decrypt the bundle. If this \
+                    flag is not provided, the password will be read from the user.",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(DRY_RUN_FLAG)
+                .long(DRY_RUN_FLAG)
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help(
+                    "If present, check that the bundle can be imported into the validator \
+                    client without making any changes.",
+                )
+                .display_order(0),
+        )
+        .arg(
+            Arg::new(STDIN_INPUTS_FLAG)
+                .action(ArgAction::SetTrue)
+                .hide(cfg!(windows))
+                .long(STDIN_INPUTS_FLAG)
+                .help("If present, read all user inputs from stdin instead of tty.")
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ImportBundleConfig {
+    pub bundle_path: PathBuf,
+    pub vc_url: SensitiveUrl,
+    pub vc_token_path: PathBuf,
+    pub password_file: Option<PathBuf>,
+    pub dry_run: bool,
+    pub stdin_inputs: bool,
+}
+
+impl ImportBundleConfig {
+    fn from_cli(matches: &ArgMatches) -> Result<Self, String> {
+        Ok(Self {
+            bundle_path: clap_utils::parse_required(matches, BUNDLE_FILE_FLAG)?,
+            vc_url: clap_utils::parse_required(matches, VC_URL_FLAG)?,
+            vc_token_path: clap_utils::parse_required(matches, VC_TOKEN_FLAG)?,
+            password_file: clap_utils::parse_optional(matches, PASSWORD_FILE_FLAG)?,
+            dry_run: matches.get_flag(DRY_RUN_FLAG),
+            stdin_inputs: cfg!(windows) || matches.get_flag(STDIN_INPUTS_FLAG),
+        })
+    }
+}
+
+pub async fn cli_run(matches: &ArgMatches, dump_config: DumpConfig) -> Result<(), String> {
+    let config = ImportBundleConfig::from_cli(matches)?;
+    if dump_config.should_exit_early(&config)? {
+        Ok(())
+    } else {
+        run(config).await
+    }
+}
+
+async fn run(config: ImportBundleConfig) -> Result<(), String> {
+    let ImportBundleConfig {
+        bundle_path,
+        vc_url,
+        vc_token_path,
+        password_file,
+        dry_run,
+        stdin_inputs,
+    } = config;
+
+    let bundle_file = fs::OpenOptions::new()
+        .read(true)
+        .create(false)
+        .open(&bundle_path)
+        .map_err(|e| format!("Unable to open {:?}: {:?}", bundle_path, e))?;
+    let bundle: ConfigBundle = serde_json::from_reader(&bundle_file)
+        .map_err(|e| format!("Unable to parse JSON in {:?}: {:?}", bundle_path, e))?;
+
+    let password = read_bundle_password(password_file.as_ref(), stdin_inputs)?;
+
+    let (http_client, _keystores) = vc_http_client(vc_url.clone(), &vc_token_path).await?;
+
+    let response = http_client
+        .post_lighthouse_config_bundle_import(&ImportConfigBundleRequest {
+            bundle,
+            password,
+            dry_run,
+        })
+        .await
+        .map_err(|e| format!("Failed to import config bundle: {:?}", e))?;
+
+    let count = response.data.len();
+    let mut errors = 0;
+    for (i, validator) in response.data.iter().enumerate() {
+        let action = if dry_run { "would be" } else { "was" };
+        match validator.status.status {
+            ImportKeystoreStatus::Imported => eprintln!(
+                "Validator {} of {} ({:?}) {} imported",
+                i + 1,
+                count,
+                validator.voting_pubkey,
+                action
+            ),
+            ImportKeystoreStatus::Duplicate => eprintln!(
+                "Validator {} of {} ({:?}) already exists on the VC",
+                i + 1,
+                count,
+                validator.voting_pubkey
+            ),
+            ImportKeystoreStatus::Error => {
+                errors += 1;
+                eprintln!(
+                    "Validator {} of {} ({:?}) failed with message: {:?}",
+                    i + 1,
+                    count,
+                    validator.voting_pubkey,
+                    validator.status.message
+                )
+            }
+        }
+    }
+
+    if errors > 0 {
+        Err(format!(
+            "{} of {} validators failed to import",
+            errors, count
+        ))
+    } else {
+        Ok(())
+    }
+}
@@ -8,6 +8,8 @@ use types::EthSpec;
 
 pub mod common;
 pub mod create_validators;
+pub mod export_bundle;
+pub mod import_bundle;
 pub mod import_validators;
 pub mod move_validators;
 
@@ -56,6 +58,8 @@ pub fn cli_app() -> Command {
         .subcommand(create_validators::cli_app())
         .subcommand(import_validators::cli_app())
         .subcommand(move_validators::cli_app())
+        .subcommand(export_bundle::cli_app())
+        .subcommand(import_bundle::cli_app())
 }
 
 /// Run the account manager, returning an error if the operation did not succeed.
@@ -83,6 +87,12 @@ pub fn run<E: EthSpec>(matches: &ArgMatches, env: Environment<E>) -> Result<(),
                     Some((move_validators::CMD, matches)) => {
                         move_validators::cli_run(matches, dump_config).await
                     }
+                    Some((export_bundle::CMD, matches)) => {
+                        export_bundle::cli_run(matches, dump_config).await
+                    }
+                    Some((import_bundle::CMD, matches)) => {
+                        import_bundle::cli_run(matches, dump_config).await
+                    }
                     Some((unknown, _)) => Err(format!(
                         "{} is not a valid {} command. See --help.",
                         unknown, CMD